@@ -0,0 +1,182 @@
+use crate::app::AppState;
+use crate::command;
+use crate::core::{
+    crypto, AsyncTask, CommandError, DatabaseInfo, DatabaseStatus, InstallDatabaseParams,
+    OperationResult,
+};
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::json;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_log::log;
+
+/// 设置中开启了本机 HTTP API 时在后台启动服务，供脚本和 CI 在不打开界面的情况下管理数据库
+///
+/// 只监听 127.0.0.1，请求需带上 `Authorization: Bearer <api_token>`。各接口直接调用对应的命令，
+/// 返回的 JSON 与前端收到的相同；操作失败时状态码为 422，响应体仍为 `OperationResult`。
+pub fn start(app: AppHandle) {
+    let state = app.state::<AppState>().inner().clone();
+    let mut settings = state.get_settings();
+    if !settings.api_server_enabled {
+        return;
+    }
+    if settings.api_token.is_empty() {
+        settings.api_token = crypto::random_token();
+        state.update_settings(settings.clone());
+    }
+
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.api_server_port));
+    let router = router(app, settings.api_token);
+    tauri::async_runtime::spawn(async move {
+        let listener = match tokio::net::TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::error!("Failed to start the API server on {}: {}", addr, e);
+                return;
+            }
+        };
+        log::info!("API server listening on http://{}", addr);
+        if let Err(e) = axum::serve(listener, router).await {
+            log::error!("API server stopped: {}", e);
+        }
+    });
+}
+
+fn router(app: AppHandle, token: String) -> Router {
+    Router::new()
+        .route("/api/databases", get(list_databases).post(install_database))
+        .route("/api/databases/:id", get(get_database))
+        .route("/api/databases/:id/status", get(database_status))
+        .route("/api/databases/:id/start", post(start_database))
+        .route("/api/databases/:id/stop", post(stop_database))
+        .route("/api/databases/:id/restart", post(restart_database))
+        .route("/api/tasks/:id", get(task_status))
+        .layer(middleware::from_fn_with_state(Arc::from(token), authorize))
+        .with_state(app)
+}
+
+/// 校验 `Authorization` 请求头中的令牌
+async fn authorize(State(token): State<Arc<str>>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|value| constant_time_eq(value.as_bytes(), token.as_bytes()));
+    if !authorized {
+        return ApiError(
+            StatusCode::UNAUTHORIZED,
+            "Invalid or missing API token".to_string(),
+        )
+        .into_response();
+    }
+    next.run(request).await
+}
+
+/// 比较令牌时耗时与匹配的前缀长度无关
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// 接口错误，响应体为 `{"error": "..."}`
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn not_found(what: &str) -> Self {
+        Self(StatusCode::NOT_FOUND, format!("{} not found", what))
+    }
+}
+
+impl From<CommandError> for ApiError {
+    fn from(error: CommandError) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// 操作结果，失败时状态码为 422
+fn operation<T: serde::Serialize>(result: OperationResult<T>) -> Response {
+    let status = if result.success {
+        StatusCode::OK
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+    (status, Json(result)).into_response()
+}
+
+async fn list_databases(State(app): State<AppHandle>) -> Json<Vec<DatabaseInfo>> {
+    Json(command::get_databases(app.state()))
+}
+
+async fn get_database(
+    State(app): State<AppHandle>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<DatabaseInfo>> {
+    command::get_database(app.state(), id)
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Database"))
+}
+
+async fn database_status(
+    State(app): State<AppHandle>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<DatabaseStatus>> {
+    command::get_database_status(app.state(), id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Database"))
+}
+
+async fn start_database(
+    State(app): State<AppHandle>,
+    Path(id): Path<String>,
+) -> ApiResult<Response> {
+    Ok(operation(command::start_database(app.state(), id).await?))
+}
+
+async fn stop_database(
+    State(app): State<AppHandle>,
+    Path(id): Path<String>,
+) -> ApiResult<Response> {
+    Ok(operation(command::stop_database(app.state(), id).await?))
+}
+
+async fn restart_database(
+    State(app): State<AppHandle>,
+    Path(id): Path<String>,
+) -> ApiResult<Response> {
+    Ok(operation(command::restart_database(app.state(), id).await?))
+}
+
+/// 开始安装，返回任务 ID，通过 `/api/tasks/:id` 查询进度
+async fn install_database(
+    State(app): State<AppHandle>,
+    Json(params): Json<InstallDatabaseParams>,
+) -> ApiResult<Response> {
+    let task_id = command::install_database(app.state(), params, app.clone())
+        .await
+        .map_err(|e| ApiError(StatusCode::BAD_REQUEST, e.to_string()))?;
+    Ok((StatusCode::ACCEPTED, Json(json!({ "task_id": task_id }))).into_response())
+}
+
+async fn task_status(
+    State(app): State<AppHandle>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<AsyncTask>> {
+    command::get_task_status(app.state(), id)
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Task"))
+}
@@ -1,15 +1,73 @@
-use crate::core::{AsyncTask, DatabaseInfo, DatabaseManager, GlobalSettings};
+use crate::core::metrics::{self, MetricSample, MetricsHistory, Sampler};
+use crate::core::relocate::{self, StorageMigration};
+use crate::core::state_schema::{self, STATE_SCHEMA_VERSION};
+use crate::core::watchdog::{self, RestartBackoff};
+use crate::core::{
+    activation, backend, backup, dependency, download, keychain, launchd, preflight, schedule,
+    utils,
+};
+use crate::core::{
+    AppError, AppNotification, AppStateExport, AppStateImportResult, AsyncTask, BackendKind,
+    BackupMode, BackupPruneResult, BackupRecord, DatabaseCrashedEvent, DatabaseDiskUsage,
+    DatabaseInfo, DatabaseManager, DatabaseStatus, DatabaseType, GlobalSettings,
+    InstallDatabaseParams, OperationResult, ScheduleAction, TaskFilter, TaskStatus,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::io::Write;
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
 use tauri_plugin_log::log;
 
+/// 过期备份清理间隔
+const BACKUP_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 关闭指标采集时检查设置的间隔
+const METRICS_IDLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 每采集多少次将指标历史保存到磁盘
+const METRICS_SAVE_EVERY: u32 = 6;
+
+/// 检查存储目录所在磁盘剩余空间的间隔
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 存储目录所在磁盘剩余空间低于该值时发出警告
+const LOW_DISK_SPACE: u64 = 2 * 1024 * 1024 * 1024;
+
+/// 检查定时计划的间隔
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 电脑休眠期间错过的计划，唤醒后只补执行这么久以内的
+const SCHEDULE_CATCH_UP: chrono::Duration = chrono::Duration::minutes(5);
+
+/// 检查哪些数据库需要代为监听端口的间隔
+const ACTIVATION_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 同时执行的安装任务数，其余任务排队等待
+const MAX_CONCURRENT_INSTALLS: usize = 1;
+
+/// 导出应用状态的格式版本
+const STATE_EXPORT_VERSION: u32 = 1;
+
+/// 保留的 state.json 历史版本数（`state.json.1` 为最近一次）
+const STATE_BACKUP_COUNT: usize = 3;
+
+/// 串行化状态文件的写入，避免多个线程同时保存时互相覆盖临时文件
+static STATE_WRITE_LOCK: Mutex<()> = Mutex::new(());
+
 #[derive(Debug, Serialize, Deserialize)]
 struct AppStateData {
+    /// 结构版本，见 [`state_schema`]
+    #[serde(default)]
+    schema_version: u32,
     databases: Vec<DatabaseInfo>,
     settings: GlobalSettings,
+    #[serde(default)]
+    backups: Vec<BackupRecord>,
 }
 
 /// 应用状态
@@ -19,6 +77,40 @@ pub struct AppState {
     pub settings: Arc<Mutex<GlobalSettings>>,
     pub db_manager: Arc<DatabaseManager>,
     pub tasks: Arc<Mutex<HashMap<String, AsyncTask>>>,
+    pub backups: Arc<Mutex<HashMap<String, BackupRecord>>>,
+    /// 正在跟踪的日志，值为停止标记
+    pub log_watchers: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    /// 安装队列，按提交顺序保存执行中和等待中的任务 ID
+    pub install_queue: Arc<(Mutex<VecDeque<String>>, Condvar)>,
+    /// 未成功完成的安装任务的参数，用于重试
+    pub install_requests: Arc<Mutex<HashMap<String, InstallRequest>>>,
+    /// 后台采集的系统和数据库指标历史
+    pub metrics: Arc<Mutex<MetricsHistory>>,
+}
+
+/// 提交安装任务时的参数
+#[derive(Clone, Debug)]
+pub struct InstallRequest {
+    pub params: InstallDatabaseParams,
+    /// 离线安装使用的本地发行包
+    pub archive: Option<PathBuf>,
+}
+
+/// 占用的安装名额，释放时从安装队列移除并唤醒等待的任务
+pub struct InstallSlot {
+    state: AppState,
+    task_id: String,
+}
+
+impl Drop for InstallSlot {
+    fn drop(&mut self) {
+        let (queue, cond) = &*self.state.install_queue;
+        let mut queue = queue.lock().unwrap();
+        queue.retain(|id| id != &self.task_id);
+        self.state.update_queue_positions(&queue);
+        drop(queue);
+        cond.notify_all();
+    }
 }
 
 impl AppState {
@@ -37,24 +129,155 @@ impl AppState {
         Ok(())
     }
 
-    fn save_state(&self) -> Result<(), String> {
+    pub fn save_state(&self) -> Result<(), String> {
         Self::ensure_state_dir()?;
 
         let state_path = Self::get_state_path();
         let databases: Vec<DatabaseInfo> = {
             let db_map = self.databases.lock().unwrap();
-            db_map.values().cloned().collect()
+            db_map
+                .values()
+                .cloned()
+                .map(Self::externalize_password)
+                .collect()
+        };
+        let settings = Self::externalize_settings_secrets(self.settings.lock().unwrap().clone());
+        let backups: Vec<BackupRecord> = {
+            let backup_map = self.backups.lock().unwrap();
+            backup_map.values().cloned().collect()
         };
-        let settings = self.settings.lock().unwrap().clone();
 
         let state_data = AppStateData {
+            schema_version: STATE_SCHEMA_VERSION,
             databases,
             settings,
+            backups,
         };
-        let json = serde_json::to_string_pretty(&state_data)
+        Self::write_state_file(&state_path, &state_data)
+    }
+
+    /// 先写入临时文件再原子地替换 state.json，替换前将当前文件轮转为 `state.json.1` ~ `state.json.N`
+    ///
+    /// 写入中途崩溃时 state.json 仍是上一次的完整内容。
+    fn write_state_file(state_path: &Path, state_data: &AppStateData) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(state_data)
             .map_err(|e| format!("Failed to serialize state: {}", e))?;
 
-        fs::write(&state_path, json).map_err(|e| format!("Failed to write state file: {}", e))
+        let _guard = STATE_WRITE_LOCK.lock().unwrap();
+        let temp_path = state_path.with_extension("json.tmp");
+        let mut file = fs::File::create(&temp_path)
+            .map_err(|e| format!("Failed to create temporary state file: {}", e))?;
+        file.write_all(json.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| format!("Failed to write state file: {}", e))?;
+        drop(file);
+
+        if state_path.exists() {
+            for n in (1..STATE_BACKUP_COUNT).rev() {
+                let from = Self::state_backup_path(state_path, n);
+                if from.exists() {
+                    let _ = fs::rename(&from, Self::state_backup_path(state_path, n + 1));
+                }
+            }
+            if let Err(e) = fs::copy(state_path, Self::state_backup_path(state_path, 1)) {
+                log::warn!("Failed to back up state file: {}", e);
+            }
+        }
+        fs::rename(&temp_path, state_path)
+            .map_err(|e| format!("Failed to replace state file: {}", e))
+    }
+
+    /// 第 `n` 个历史版本的路径，如 `state.json.1`
+    fn state_backup_path(state_path: &Path, n: usize) -> PathBuf {
+        let file_name = state_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "state.json".to_string());
+        state_path.with_file_name(format!("{}.{}", file_name, n))
+    }
+
+    /// 将密码写入系统钥匙串，state.json 中只保存凭据引用
+    ///
+    /// 钥匙串不可用时保留明文密码，旧版本的状态文件在下次保存时自动迁移。
+    /// 没有密码时保留已有的凭据引用：从钥匙串读取失败时密码为空，但记录仍在钥匙串中。
+    fn externalize_password(mut db_info: DatabaseInfo) -> DatabaseInfo {
+        let Some(password) = db_info.password.take() else {
+            return db_info;
+        };
+
+        match keychain::store_password(&db_info.id, &password) {
+            Ok(()) => db_info.credential_ref = Some(keychain::credential_ref(&db_info.id)),
+            Err(e) => {
+                log::warn!("Failed to store password for {}: {:#}", db_info.id, e);
+                db_info.password = Some(password);
+            }
+        }
+        db_info
+    }
+
+    /// 根据凭据引用从系统钥匙串读取密码
+    fn resolve_password(mut db_info: DatabaseInfo) -> DatabaseInfo {
+        if db_info.password.is_some() || db_info.credential_ref.is_none() {
+            return db_info;
+        }
+
+        match keychain::load_password(&db_info.id) {
+            Ok(password) => db_info.password = password,
+            Err(e) => log::warn!("Failed to load password for {}: {:#}", db_info.id, e),
+        }
+        db_info
+    }
+
+    /// 将 API 令牌和远程备份密钥写入系统钥匙串，state.json 中只保存引用，与数据库密码的处理相同
+    fn externalize_settings_secrets(mut settings: GlobalSettings) -> GlobalSettings {
+        if !settings.api_token.is_empty() {
+            match keychain::store_password(keychain::API_TOKEN_ACCOUNT, &settings.api_token) {
+                Ok(()) => {
+                    settings.api_token.clear();
+                    settings.api_token_ref =
+                        Some(keychain::credential_ref(keychain::API_TOKEN_ACCOUNT));
+                }
+                Err(e) => log::warn!("Failed to store API token: {:#}", e),
+            }
+        }
+
+        if let Some(target) = settings
+            .remote_backup
+            .as_mut()
+            .filter(|target| !target.secret_key.is_empty())
+        {
+            let account = keychain::REMOTE_BACKUP_SECRET_ACCOUNT;
+            match keychain::store_password(account, &target.secret_key) {
+                Ok(()) => {
+                    target.secret_key.clear();
+                    target.secret_key_ref = Some(keychain::credential_ref(account));
+                }
+                Err(e) => log::warn!("Failed to store remote backup secret key: {:#}", e),
+            }
+        }
+        settings
+    }
+
+    /// 根据引用从系统钥匙串读取 API 令牌和远程备份密钥
+    fn resolve_settings_secrets(mut settings: GlobalSettings) -> GlobalSettings {
+        if settings.api_token.is_empty() && settings.api_token_ref.is_some() {
+            match keychain::load_password(keychain::API_TOKEN_ACCOUNT) {
+                Ok(token) => settings.api_token = token.unwrap_or_default(),
+                Err(e) => log::warn!("Failed to load API token: {:#}", e),
+            }
+        }
+
+        if let Some(target) = settings
+            .remote_backup
+            .as_mut()
+            .filter(|target| target.secret_key.is_empty() && target.secret_key_ref.is_some())
+        {
+            match keychain::load_password(keychain::REMOTE_BACKUP_SECRET_ACCOUNT) {
+                Ok(secret) => target.secret_key = secret.unwrap_or_default(),
+                Err(e) => log::warn!("Failed to load remote backup secret key: {:#}", e),
+            }
+        }
+        settings
     }
 
     fn load_state() -> Result<AppStateData, String> {
@@ -62,46 +285,124 @@ impl AppState {
 
         if !state_path.exists() {
             return Ok(AppStateData {
+                schema_version: STATE_SCHEMA_VERSION,
                 databases: Vec::new(),
                 settings: GlobalSettings::default(),
+                backups: Vec::new(),
             });
         }
 
-        let content = fs::read_to_string(&state_path)
-            .map_err(|e| format!("Failed to read state file: {}", e))?;
+        let (state_data, from) = match Self::read_state_file(&state_path) {
+            Ok(result) => result,
+            Err(e) => return Self::recover_state(&state_path, e),
+        };
+
+        // 旧版本的状态文件先备份再迁移，迁移后立即写回，避免每次启动重复迁移
+        if from < STATE_SCHEMA_VERSION {
+            let backup_path = state_path.with_file_name(format!("state.v{}.json.bak", from));
+            fs::copy(&state_path, &backup_path)
+                .map_err(|e| format!("Failed to back up state file before migration: {}", e))?;
+            Self::write_state_file(&state_path, &state_data)?;
+            log::info!(
+                "Migrated state from schema version {} to {}, original saved to {}",
+                from,
+                STATE_SCHEMA_VERSION,
+                backup_path.display()
+            );
+        }
+        Ok(state_data)
+    }
 
-        serde_json::from_str(&content).map_err(|e| format!("Failed to deserialize state: {}", e))
+    /// 读取状态文件并迁移到当前结构版本，返回状态和迁移前的版本
+    fn read_state_file(path: &Path) -> Result<(AppStateData, u32), String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read state file: {}", e))?;
+        let mut value: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to deserialize state: {}", e))?;
+        let from = state_schema::migrate(&mut value)
+            .map_err(|e| format!("Failed to migrate state: {:#}", e))?;
+        let state_data = serde_json::from_value(value)
+            .map_err(|e| format!("Failed to deserialize state: {}", e))?;
+        Ok((state_data, from))
+    }
+
+    /// state.json 无法读取时从最近的可用历史版本恢复
+    ///
+    /// 损坏的文件改名为 `state.json.corrupt` 保留，恢复的状态立即写回 state.json。
+    fn recover_state(state_path: &Path, error: String) -> Result<AppStateData, String> {
+        let recovered = (1..=STATE_BACKUP_COUNT).find_map(|n| {
+            let backup_path = Self::state_backup_path(state_path, n);
+            Self::read_state_file(&backup_path)
+                .ok()
+                .map(|(state_data, _)| (backup_path, state_data))
+        });
+        let Some((backup_path, state_data)) = recovered else {
+            return Err(error);
+        };
+
+        log::warn!("{}; recovered state from {}", error, backup_path.display());
+        let corrupt_path = state_path.with_extension("json.corrupt");
+        if let Err(e) = fs::rename(state_path, &corrupt_path) {
+            log::warn!("Failed to keep corrupt state file: {}", e);
+        }
+        Self::write_state_file(state_path, &state_data)?;
+        Ok(state_data)
     }
 
     pub fn new() -> Self {
         let state_data = Self::load_state().unwrap_or_else(|e| {
             eprintln!("Failed to load state: {}. Using defaults.", e);
+            // 保留无法恢复的文件，避免之后保存时被覆盖
+            let state_path = Self::get_state_path();
+            if state_path.exists() {
+                let _ = fs::rename(&state_path, state_path.with_extension("json.corrupt"));
+            }
             AppStateData {
+                schema_version: STATE_SCHEMA_VERSION,
                 databases: Vec::new(),
                 settings: GlobalSettings::default(),
+                backups: Vec::new(),
             }
         });
 
-        let settings = state_data.settings;
+        let settings = Self::resolve_settings_secrets(state_data.settings);
         let storage_path = PathBuf::from(&settings.default_storage_path);
         let db_manager = DatabaseManager::new(&storage_path);
+        db_manager.set_startup_timeout(Duration::from_secs(settings.startup_timeout_secs));
+        download::configure(&settings);
 
         if let Err(e) = db_manager.init_directories() {
             eprintln!("Failed to initialize directories: {}", e);
         }
+        let metrics = if settings.persist_metrics {
+            MetricsHistory::load(&utils::get_metrics_path(&storage_path))
+        } else {
+            MetricsHistory::default()
+        };
 
         // 从保存的状态恢复数据库列表
         let databases: HashMap<String, DatabaseInfo> = state_data
             .databases
             .into_iter()
+            .map(Self::resolve_password)
             .map(|db| (db.id.clone(), db))
             .collect();
+        let backups: HashMap<String, BackupRecord> = state_data
+            .backups
+            .into_iter()
+            .map(|record| (record.id.clone(), record))
+            .collect();
 
         let app_state = Self {
             databases: Arc::new(Mutex::new(databases)),
             settings: Arc::new(Mutex::new(settings)),
             db_manager: Arc::new(db_manager),
             tasks: Arc::new(Mutex::new(HashMap::new())),
+            backups: Arc::new(Mutex::new(backups)),
+            log_watchers: Arc::new(Mutex::new(HashMap::new())),
+            install_queue: Arc::new((Mutex::new(VecDeque::new()), Condvar::new())),
+            install_requests: Arc::new(Mutex::new(HashMap::new())),
+            metrics: Arc::new(Mutex::new(metrics)),
         };
         log::info!("App state initialized. {:?}", app_state);
         app_state
@@ -121,33 +422,132 @@ impl AppState {
 
     /// 更新数据库
     ///
-    /// 为了保证"每种数据库只允许一个实例"，如果发现存在其它同 `db_type` 的条目，会被移除。
-    pub fn update_database(&self, db_info: DatabaseInfo) {
+    /// 原有的密码被清空时一并删除钥匙串中的记录。
+    pub fn update_database(&self, mut db_info: DatabaseInfo) {
         let mut databases = self.databases.lock().unwrap();
+        let password_cleared = db_info.password.is_none()
+            && databases
+                .get(&db_info.id)
+                .is_some_and(|old| old.password.is_some());
+        if password_cleared {
+            db_info.credential_ref = None;
+        }
+        let id = db_info.id.clone();
+        databases.insert(id.clone(), db_info);
+        drop(databases);
 
-        let db_type = db_info.db_type.clone();
-        let duplicate_ids: Vec<String> = databases
-            .iter()
-            .filter(|(id, existing)| *id != &db_info.id && existing.db_type == db_type)
-            .map(|(id, _)| id.clone())
-            .collect();
-
-        for id in duplicate_ids {
-            databases.remove(&id);
+        if password_cleared {
+            if let Err(e) = keychain::delete_password(&id) {
+                log::warn!("Failed to delete password for {}: {:#}", id, e);
+            }
         }
+        let _ = self.save_state();
+    }
 
+    /// 注册新安装的数据库，ID 已存在时返回错误
+    pub fn add_database(&self, db_info: DatabaseInfo) -> Result<(), String> {
+        let mut databases = self.databases.lock().unwrap();
+        if databases.contains_key(&db_info.id) {
+            return Err(format!("Database id already exists: {}", db_info.id));
+        }
         databases.insert(db_info.id.clone(), db_info);
         drop(databases);
 
+        self.save_state()
+    }
+
+    /// 批量更新数据库，只保存一次状态
+    pub fn update_databases(&self, db_infos: Vec<DatabaseInfo>) {
+        if db_infos.is_empty() {
+            return;
+        }
+
+        let mut databases = self.databases.lock().unwrap();
+        for db_info in db_infos {
+            databases.insert(db_info.id.clone(), db_info);
+        }
+        drop(databases);
+
         let _ = self.save_state();
     }
 
+    /// 按依赖顺序依次启动数据库及其依赖，依赖启动失败时跳过依赖它的数据库
+    ///
+    /// 启动时会等待数据库可以连接，因此每个数据库都在其依赖就绪后才启动。
+    pub fn start_with_dependencies(
+        &self,
+        ids: &[String],
+    ) -> Result<HashMap<String, OperationResult<()>>, String> {
+        let order = {
+            let databases = self.databases.lock().unwrap();
+            dependency::start_order(&databases, ids).map_err(|e| format!("{:#}", e))?
+        };
+
+        let mut results: HashMap<String, OperationResult<()>> = HashMap::new();
+        for id in order {
+            let Some(mut db_info) = self.get_database(&id) else {
+                continue;
+            };
+            if db_info.status == DatabaseStatus::Running {
+                continue;
+            }
+
+            let failed_dependency = db_info
+                .depends_on
+                .iter()
+                .find(|dependency| results.get(*dependency).is_some_and(|r| !r.success));
+            if let Some(dependency) = failed_dependency {
+                let name = self
+                    .get_database(dependency)
+                    .map(|db| db.name)
+                    .unwrap_or_else(|| dependency.clone());
+                results.insert(
+                    id,
+                    OperationResult::error(format!("Dependency {} failed to start", name)),
+                );
+                continue;
+            }
+
+            let result = match self.db_manager.start_database(&mut db_info) {
+                Ok(result) => {
+                    if result.success {
+                        self.update_database(db_info);
+                    }
+                    result
+                }
+                Err(e) => OperationResult::error(format!("Failed to start database: {}", e)),
+            };
+            results.insert(id, result);
+        }
+        Ok(results)
+    }
+
+    /// 按依赖顺序启动所有设置了自动启动的数据库
+    pub fn start_autostart_databases(
+        &self,
+    ) -> Result<HashMap<String, OperationResult<()>>, String> {
+        let ids: Vec<String> = self
+            .get_all_databases()
+            .into_iter()
+            .filter(|db| db.auto_start)
+            .map(|db| db.id)
+            .collect();
+        self.start_with_dependencies(&ids)
+    }
+
     /// 删除数据库
     pub fn remove_database(&self, id: &str) -> Option<DatabaseInfo> {
         let mut databases = self.databases.lock().unwrap();
         let result = databases.remove(id);
+        // 其他数据库不再依赖已删除的数据库
+        for db_info in databases.values_mut() {
+            db_info.depends_on.retain(|dep| dep != id);
+        }
         drop(databases);
 
+        if let Err(e) = keychain::delete_password(id) {
+            log::warn!("Failed to delete password for {}: {:#}", id, e);
+        }
         let _ = self.save_state();
         result
     }
@@ -159,13 +559,95 @@ impl AppState {
         drop(tasks);
     }
 
+    /// 将安装任务加入队列末尾
+    pub fn enqueue_install(&self, task_id: &str) {
+        let (queue, _) = &*self.install_queue;
+        let mut queue = queue.lock().unwrap();
+        queue.push_back(task_id.to_string());
+        self.update_queue_positions(&queue);
+    }
+
+    /// 阻塞等待轮到该安装任务执行，返回的名额在释放前一直占用
+    pub fn wait_install_slot(&self, task_id: &str) -> InstallSlot {
+        let (queue, cond) = &*self.install_queue;
+        let queue = queue.lock().unwrap();
+        let _queue = cond
+            .wait_while(queue, |queue| {
+                queue
+                    .iter()
+                    .position(|id| id == task_id)
+                    .is_some_and(|index| index >= MAX_CONCURRENT_INSTALLS)
+            })
+            .unwrap();
+        InstallSlot {
+            state: self.clone(),
+            task_id: task_id.to_string(),
+        }
+    }
+
+    /// 按安装队列更新等待中任务的排队位置
+    fn update_queue_positions(&self, queue: &VecDeque<String>) {
+        let mut tasks = self.tasks.lock().unwrap();
+        for (index, id) in queue.iter().enumerate() {
+            if let Some(task) = tasks.get_mut(id) {
+                let position = index
+                    .checked_sub(MAX_CONCURRENT_INSTALLS)
+                    .map(|waiting| waiting + 1);
+                if task.queue_position != position {
+                    task.queue_position = position;
+                    task.updated_at = utils::get_timestamp();
+                }
+            }
+        }
+    }
+
+    /// 记录安装任务的参数
+    pub fn save_install_request(&self, task_id: &str, request: InstallRequest) {
+        let mut requests = self.install_requests.lock().unwrap();
+        requests.insert(task_id.to_string(), request);
+    }
+
+    /// 获取安装任务的参数，任务已成功完成时返回 None
+    pub fn get_install_request(&self, task_id: &str) -> Option<InstallRequest> {
+        let requests = self.install_requests.lock().unwrap();
+        requests.get(task_id).cloned()
+    }
+
+    /// 删除安装任务的参数
+    pub fn remove_install_request(&self, task_id: &str) -> Option<InstallRequest> {
+        let mut requests = self.install_requests.lock().unwrap();
+        requests.remove(task_id)
+    }
+
     /// 获取任务
     pub fn get_task(&self, id: &str) -> Option<AsyncTask> {
         let tasks = self.tasks.lock().unwrap();
         tasks.get(id).cloned()
     }
 
-    /// 根据数据库类型获取数据库
+    /// 获取符合条件的任务，按创建时间倒序排列
+    pub fn list_tasks(&self, filter: &TaskFilter) -> Vec<AsyncTask> {
+        let tasks = self.tasks.lock().unwrap();
+        let mut result: Vec<AsyncTask> = tasks
+            .values()
+            .filter(|task| filter.matches(task))
+            .cloned()
+            .collect();
+        drop(tasks);
+
+        result.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        result
+    }
+
+    /// 删除已完成的任务，返回删除的数量；失败的任务保留以便查看错误和重试
+    pub fn clear_completed_tasks(&self) -> usize {
+        let mut tasks = self.tasks.lock().unwrap();
+        let count = tasks.len();
+        tasks.retain(|_, task| task.status != TaskStatus::Completed);
+        count - tasks.len()
+    }
+
+    /// 根据数据库类型获取数据库，存在多个实例时返回其中之一
     pub fn get_database_by_type(&self, db_type: &str) -> Option<DatabaseInfo> {
         let databases = self.databases.lock().unwrap();
         databases
@@ -174,6 +656,12 @@ impl AppState {
             .cloned()
     }
 
+    /// 根据端口获取已注册的数据库
+    pub fn find_database_by_port(&self, port: u16) -> Option<DatabaseInfo> {
+        let databases = self.databases.lock().unwrap();
+        databases.values().find(|db| db.port == port).cloned()
+    }
+
     /// 获取设置
     pub fn get_settings(&self) -> GlobalSettings {
         let settings = self.settings.lock().unwrap();
@@ -182,10 +670,879 @@ impl AppState {
 
     /// 更新设置
     pub fn update_settings(&self, settings: GlobalSettings) {
+        self.db_manager
+            .set_startup_timeout(Duration::from_secs(settings.startup_timeout_secs));
+        download::configure(&settings);
         let mut current_settings = self.settings.lock().unwrap();
         *current_settings = settings;
         drop(current_settings);
 
         let _ = self.save_state();
     }
+
+    /// 将存储根目录迁移到 `new_path`
+    ///
+    /// 停止运行中的数据库，移动应用管理的目录，改写数据库信息、备份记录和配置文件中的路径后
+    /// 重新启动原来运行的数据库。任一步失败时恢复原目录、配置和状态。
+    pub fn migrate_storage_path(&self, new_path: &Path) -> OperationResult<()> {
+        let old_root = self.db_manager.storage_path();
+        if let Err(e) = StorageMigration::check(&old_root, new_path) {
+            return OperationResult::error_from(format!("{:#}", e), &e);
+        }
+        if !self.install_queue.0.lock().unwrap().is_empty() {
+            return OperationResult::error(
+                "Wait for running installs to finish before moving the storage path",
+            );
+        }
+        // 容器创建时已挂载原数据目录，无法随之迁移
+        if let Some(db) = self.get_all_databases().iter().find(|db| {
+            db.backend == BackendKind::Docker && Path::new(&db.data_path).starts_with(&old_root)
+        }) {
+            return OperationResult::error(format!(
+                "{} runs in a Docker container that mounts {}, remove it before moving the storage path",
+                db.name, db.data_path
+            ));
+        }
+
+        let mut stopped = Vec::new();
+        for mut db_info in self.get_all_databases() {
+            if db_info.status != DatabaseStatus::Running {
+                continue;
+            }
+            let result = self
+                .db_manager
+                .stop_database(&mut db_info)
+                .unwrap_or_else(|e| OperationResult::error(format!("{:#}", e)));
+            if !result.success {
+                let _ = self.start_with_dependencies(&stopped);
+                return OperationResult::error(format!(
+                    "Failed to stop {}: {}",
+                    db_info.name, result.message
+                ));
+            }
+            stopped.push(db_info.id.clone());
+            self.update_database(db_info);
+        }
+
+        let databases = self.get_all_databases();
+        let backups = self.list_backups(None);
+        let settings = self.get_settings();
+        let mut migration = match StorageMigration::move_dirs(&old_root, new_path) {
+            Ok(migration) => migration,
+            Err(e) => {
+                let _ = self.start_with_dependencies(&stopped);
+                return OperationResult::error_from(format!("Failed to move storage: {:#}", e), &e);
+            }
+        };
+        let rollback = |migration: StorageMigration, message: String| {
+            if let Err(e) = migration.rollback() {
+                log::error!("Failed to roll back storage migration: {:#}", e);
+            }
+            self.restore_storage_state(&old_root, databases.clone(), backups.clone(), &settings);
+            let _ = self.start_with_dependencies(&stopped);
+            OperationResult::error(message)
+        };
+
+        let mut relocated = databases.clone();
+        for db_info in &mut relocated {
+            if let Err(e) = migration.relocate_database(db_info) {
+                let message = format!("Failed to update {}: {:#}", db_info.name, e);
+                return rollback(migration, message);
+            }
+        }
+        let relocated_backups = backups
+            .iter()
+            .cloned()
+            .map(|mut record| {
+                if let Some(path) = migration.relocate(&record.file_path) {
+                    record.file_path = path;
+                }
+                record
+            })
+            .collect();
+        let mut new_settings = settings.clone();
+        new_settings.default_storage_path = new_path.to_string_lossy().to_string();
+        self.restore_storage_state(
+            new_path,
+            relocated.clone(),
+            relocated_backups,
+            &new_settings,
+        );
+
+        for db_info in relocated
+            .iter()
+            .filter(|db| db.backend == BackendKind::LaunchAgent)
+        {
+            if let Err(e) = launchd::refresh_plist(db_info) {
+                log::warn!("Failed to update LaunchAgent of {}: {:#}", db_info.name, e);
+            }
+        }
+
+        let failed = match self.start_with_dependencies(&stopped) {
+            Ok(results) => results
+                .into_iter()
+                .find(|(_, result)| !result.success)
+                .map(|(id, result)| format!("Failed to start {}: {}", id, result.message)),
+            Err(e) => Some(e),
+        };
+        if let Some(message) = failed {
+            self.stop_databases(&stopped);
+            return rollback(migration, message);
+        }
+
+        OperationResult::success(format!("Storage moved to {}", new_path.display()), None)
+    }
+
+    /// 整体替换数据库列表、备份记录和设置，并切换存储根目录
+    fn restore_storage_state(
+        &self,
+        storage_path: &Path,
+        databases: Vec<DatabaseInfo>,
+        backups: Vec<BackupRecord>,
+        settings: &GlobalSettings,
+    ) {
+        *self.databases.lock().unwrap() = databases
+            .into_iter()
+            .map(|db| (db.id.clone(), db))
+            .collect();
+        *self.backups.lock().unwrap() = backups
+            .into_iter()
+            .map(|record| (record.id.clone(), record))
+            .collect();
+        self.db_manager.set_storage_path(storage_path);
+        if let Err(e) = self.db_manager.init_directories() {
+            log::warn!("Failed to initialize directories: {:#}", e);
+        }
+        self.update_settings(settings.clone());
+    }
+
+    /// 停止指定的数据库，忽略失败
+    fn stop_databases(&self, ids: &[String]) {
+        for id in ids {
+            let Some(mut db_info) = self.get_database(id) else {
+                continue;
+            };
+            if let Ok(result) = self.db_manager.stop_database(&mut db_info) {
+                if result.success {
+                    self.update_database(db_info);
+                }
+            }
+        }
+    }
+
+    /// 导出数据库列表、设置和备份记录
+    ///
+    /// `include_secrets` 为 false 时去掉数据库密码、远程备份密钥和 API 令牌；钥匙串中的凭据引用只在本机有效，始终不导出。
+    pub fn export_state(&self, include_secrets: bool) -> AppStateExport {
+        let mut databases = self.get_all_databases();
+        databases.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        for db_info in &mut databases {
+            db_info.credential_ref = None;
+            db_info.pid = None;
+            if !include_secrets {
+                db_info.password = None;
+            }
+        }
+        let mut settings = self.get_settings();
+        settings.api_token_ref = None;
+        if let Some(target) = settings.remote_backup.as_mut() {
+            target.secret_key_ref = None;
+            if !include_secrets {
+                target.secret_key.clear();
+            }
+        }
+        if !include_secrets {
+            settings.api_token.clear();
+        }
+
+        AppStateExport {
+            format_version: STATE_EXPORT_VERSION,
+            exported_at: utils::get_timestamp(),
+            include_secrets,
+            databases,
+            settings,
+            backups: self.list_backups(None),
+        }
+    }
+
+    /// 将导出的应用状态合并到当前状态
+    ///
+    /// 只导入记录，不复制程序和数据文件。位于导出方存储目录下的路径改写到本机的存储目录，
+    /// 导入的数据库均为停止状态，ID 或端口与已注册的数据库冲突时跳过。设置整体替换，但保留本机的存储目录；
+    /// 导出时未包含密钥则保留本机的远程备份配置和 API 令牌。
+    pub fn import_state(&self, export: AppStateExport) -> Result<AppStateImportResult, String> {
+        if export.format_version > STATE_EXPORT_VERSION {
+            return Err(format!(
+                "Unsupported export format version {}",
+                export.format_version
+            ));
+        }
+        let old_root = PathBuf::from(&export.settings.default_storage_path);
+        let new_root = self.db_manager.storage_path();
+        let relocate = |path: &mut String| {
+            if let Some(relocated) = relocate::relocated_path(path, &old_root, &new_root) {
+                *path = relocated;
+            }
+        };
+
+        let mut result = AppStateImportResult::default();
+        let mut databases = self.get_all_databases();
+        let mut imported = Vec::new();
+        for mut db_info in export.databases {
+            // 文件型数据库没有监听端口
+            let has_port = !matches!(db_info.db_type, DatabaseType::SQLite | DatabaseType::DuckDB);
+            if databases
+                .iter()
+                .any(|db| db.id == db_info.id || (has_port && db.port == db_info.port))
+            {
+                result.skipped_databases.push(db_info.name);
+                continue;
+            }
+            relocate(&mut db_info.install_path);
+            relocate(&mut db_info.data_path);
+            relocate(&mut db_info.log_path);
+            if let Some(config) = db_info.config.as_mut() {
+                relocate(config);
+            }
+            db_info.status = DatabaseStatus::Stopped;
+            db_info.pid = None;
+            db_info.credential_ref = None;
+            databases.push(db_info.clone());
+            imported.push(db_info);
+        }
+        result.imported_databases = imported.len();
+
+        // 依赖和复制源指向目标环境中不存在的数据库时去掉，否则启动顺序无法计算
+        let known_ids: Vec<&str> = databases.iter().map(|db| db.id.as_str()).collect();
+        for db_info in &mut imported {
+            let name = db_info.name.clone();
+            db_info.depends_on.retain(|dep| {
+                let known = known_ids.contains(&dep.as_str());
+                if !known {
+                    result.dropped_references.push(format!("{}: {}", name, dep));
+                }
+                known
+            });
+            if let Some(source) = db_info
+                .replica_of
+                .take_if(|source| !known_ids.contains(&source.as_str()))
+            {
+                result
+                    .dropped_references
+                    .push(format!("{}: {}", name, source));
+            }
+        }
+
+        let known_backups: Vec<String> = self
+            .list_backups(None)
+            .into_iter()
+            .map(|record| record.id)
+            .collect();
+        let imported_ids: Vec<&str> = imported.iter().map(|db| db.id.as_str()).collect();
+        let backups: Vec<BackupRecord> = export
+            .backups
+            .into_iter()
+            .filter(|record| {
+                imported_ids.contains(&record.database_id.as_str())
+                    && !known_backups.contains(&record.id)
+            })
+            .map(|mut record| {
+                relocate(&mut record.file_path);
+                record
+            })
+            .collect();
+        result.imported_backups = backups.len();
+
+        let current = self.get_settings();
+        let mut settings = export.settings;
+        settings.default_storage_path = current.default_storage_path;
+        if !export.include_secrets {
+            if current.remote_backup.is_some() {
+                settings.remote_backup = current.remote_backup;
+            }
+            settings.api_token = current.api_token;
+            settings.api_token_ref = current.api_token_ref;
+        }
+        let fields = crate::core::settings::validate(&settings);
+        if !fields.is_empty() {
+            return Err(AppError::InvalidSettings { fields }.to_string());
+        }
+
+        self.backups.lock().unwrap().extend(
+            backups
+                .into_iter()
+                .map(|record| (record.id.clone(), record)),
+        );
+        self.update_databases(imported);
+        self.update_settings(settings);
+        Ok(result)
+    }
+
+    /// 添加备份记录
+    pub fn add_backup(&self, record: BackupRecord) {
+        let mut backups = self.backups.lock().unwrap();
+        backups.insert(record.id.clone(), record);
+        drop(backups);
+
+        let _ = self.save_state();
+    }
+
+    /// 获取备份记录
+    pub fn get_backup(&self, id: &str) -> Option<BackupRecord> {
+        let backups = self.backups.lock().unwrap();
+        backups.get(id).cloned()
+    }
+
+    /// 获取备份记录列表，按创建时间倒序排列
+    ///
+    /// 指定 `database_id` 时只返回该数据库的备份。
+    pub fn list_backups(&self, database_id: Option<&str>) -> Vec<BackupRecord> {
+        let backups = self.backups.lock().unwrap();
+        let mut records: Vec<BackupRecord> = backups
+            .values()
+            .filter(|record| database_id.is_none_or(|id| record.database_id == id))
+            .cloned()
+            .collect();
+        drop(backups);
+
+        records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        records
+    }
+
+    /// 统计数据库的数据目录、日志和备份占用的磁盘空间
+    pub fn disk_usage(&self, db_info: &DatabaseInfo) -> DatabaseDiskUsage {
+        let data_size = utils::dir_size(Path::new(&db_info.data_path));
+        let log_size = utils::dir_size(Path::new(&db_info.log_path));
+        let backup_size = self
+            .list_backups(Some(&db_info.id))
+            .iter()
+            .map(|record| utils::dir_size(Path::new(&record.file_path)))
+            .sum();
+        let total_size = data_size + log_size + backup_size;
+        DatabaseDiskUsage {
+            database_id: db_info.id.clone(),
+            name: db_info.name.clone(),
+            data_size,
+            log_size,
+            backup_size,
+            total_size,
+            total_size_text: utils::format_file_size(total_size),
+        }
+    }
+
+    /// 删除备份记录
+    pub fn remove_backup(&self, id: &str) -> Option<BackupRecord> {
+        let mut backups = self.backups.lock().unwrap();
+        let result = backups.remove(id);
+        drop(backups);
+
+        let _ = self.save_state();
+        result
+    }
+
+    /// 删除超出保留期限的备份记录及其文件
+    pub fn prune_backups(&self, retention_days: u32) -> BackupPruneResult {
+        let expired: Vec<BackupRecord> = {
+            let backups = self.backups.lock().unwrap();
+            backups
+                .values()
+                .filter(|record| backup::is_expired(record, retention_days))
+                .cloned()
+                .collect()
+        };
+
+        let mut result = BackupPruneResult::default();
+        for record in expired {
+            match backup::remove_backup_file(&record) {
+                Ok(size) => {
+                    self.backups.lock().unwrap().remove(&record.id);
+                    result.removed_count += 1;
+                    result.reclaimed_bytes += size;
+                }
+                Err(e) => log::warn!("Failed to prune backup {}: {}", record.id, e),
+            }
+        }
+        result.reclaimed_bytes += self.prune_wal_archives();
+
+        if result.removed_count > 0 {
+            let _ = self.save_state();
+        }
+        result
+    }
+
+    /// 清理各 PostgreSQL 实例的 WAL 归档，只保留恢复最早的物理备份所需的 WAL，返回释放的字节数
+    fn prune_wal_archives(&self) -> u64 {
+        let backups: Vec<BackupRecord> = self.backups.lock().unwrap().values().cloned().collect();
+        let mut reclaimed = 0;
+        for db_info in self
+            .get_all_databases()
+            .into_iter()
+            .filter(|db| db.db_type == DatabaseType::PostgreSQL)
+        {
+            let oldest_backup = backups
+                .iter()
+                .filter(|record| {
+                    record.database_id == db_info.id && record.mode == BackupMode::Physical
+                })
+                .filter_map(|record| chrono::DateTime::parse_from_rfc3339(&record.created_at).ok())
+                .min()
+                .map(std::time::SystemTime::from);
+            let wal_dir = self.db_manager.backup_dir(&db_info).join("wal");
+            match backup::prune_wal_archive(&wal_dir, oldest_backup) {
+                Ok(size) => reclaimed += size,
+                Err(e) => log::warn!("Failed to prune WAL archive of {}: {:#}", db_info.name, e),
+            }
+        }
+        reclaimed
+    }
+
+    /// 启动后台任务，按 `backup_retention_days` 定期清理过期备份
+    pub fn start_backup_pruner(&self) {
+        let app_state = self.clone();
+        std::thread::spawn(move || loop {
+            let retention_days = app_state.get_settings().backup_retention_days;
+            let result = app_state.prune_backups(retention_days);
+            if result.removed_count > 0 {
+                log::info!(
+                    "Pruned {} expired backups, reclaimed {} bytes",
+                    result.removed_count,
+                    result.reclaimed_bytes
+                );
+            }
+            std::thread::sleep(BACKUP_PRUNE_INTERVAL);
+        });
+    }
+
+    /// 后台定期采集系统和各数据库进程的指标，采集间隔和是否保存到磁盘随设置生效
+    pub fn start_metrics_sampler(&self) {
+        let app_state = self.clone();
+        std::thread::spawn(move || {
+            let mut sampler = Sampler::default();
+            let mut unsaved = 0;
+            loop {
+                let settings = app_state.get_settings();
+                if settings.metrics_interval_secs == 0 {
+                    std::thread::sleep(METRICS_IDLE_INTERVAL);
+                    continue;
+                }
+
+                let databases = app_state.get_all_databases();
+                let ids: Vec<String> = databases.iter().map(|db| db.id.clone()).collect();
+                let samples = sampler.sample(&databases);
+                let mut history = app_state.metrics.lock().unwrap();
+                for (key, sample) in samples {
+                    history.record(&key, sample);
+                }
+                history.retain(&ids);
+
+                unsaved += 1;
+                if settings.persist_metrics && unsaved >= METRICS_SAVE_EVERY {
+                    let path = utils::get_metrics_path(&app_state.db_manager.storage_path());
+                    if let Err(e) = history.save(&path) {
+                        log::warn!("Failed to save metrics history: {:#}", e);
+                    }
+                    unsaved = 0;
+                }
+                drop(history);
+
+                std::thread::sleep(Duration::from_secs(settings.metrics_interval_secs));
+            }
+        });
+    }
+
+    /// 获取最近 `range` 内的指标历史，`id` 为空时返回系统整体指标
+    pub fn metrics_history(&self, id: Option<&str>, range: Duration) -> Vec<MetricSample> {
+        let since = chrono::Utc::now().timestamp() - range.as_secs() as i64;
+        self.metrics
+            .lock()
+            .unwrap()
+            .since(id.unwrap_or(metrics::SYSTEM_KEY), since)
+    }
+
+    /// 启动后台任务，按依赖顺序启动设置了自动启动的数据库，启动失败时通过 `on_failure` 通知
+    pub fn spawn_autostart<F>(&self, on_failure: F)
+    where
+        F: Fn(AppNotification) + Send + 'static,
+    {
+        let app_state = self.clone();
+        std::thread::spawn(move || match app_state.start_autostart_databases() {
+            Ok(results) => {
+                for (id, result) in results.iter().filter(|(_, r)| !r.success) {
+                    log::warn!("Failed to autostart {}: {}", id, result.message);
+                    let name = app_state
+                        .get_database(id)
+                        .map(|db_info| db_info.name)
+                        .unwrap_or_else(|| id.clone());
+                    on_failure(AppNotification {
+                        title: format!("Failed to start {}", name),
+                        body: result.message.clone(),
+                        error: true,
+                    });
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to autostart databases: {}", e);
+                on_failure(AppNotification {
+                    title: "Failed to start databases".to_string(),
+                    body: e,
+                    error: true,
+                });
+            }
+        });
+    }
+
+    /// 启动后台任务，定期检查存储目录所在磁盘的剩余空间
+    ///
+    /// 低于 [`LOW_DISK_SPACE`] 时通过 `on_low` 发出警告，空间恢复后再次不足时重新警告。
+    pub fn start_disk_monitor<F>(&self, on_low: F)
+    where
+        F: Fn(AppNotification) + Send + 'static,
+    {
+        let app_state = self.clone();
+        std::thread::spawn(move || {
+            let mut warned = false;
+            loop {
+                let storage_path = PathBuf::from(app_state.get_settings().default_storage_path);
+                if let Some(available) = preflight::available_space(&storage_path) {
+                    let low = available < LOW_DISK_SPACE;
+                    if low && !warned {
+                        log::warn!(
+                            "Low disk space: {} MB available for {}",
+                            available / 1024 / 1024,
+                            storage_path.display()
+                        );
+                        on_low(AppNotification {
+                            title: "Low disk space".to_string(),
+                            body: format!(
+                                "Only {} MB left on the disk holding {}",
+                                available / 1024 / 1024,
+                                storage_path.display()
+                            ),
+                            error: false,
+                        });
+                    }
+                    warned = low;
+                }
+                std::thread::sleep(DISK_CHECK_INTERVAL);
+            }
+        });
+    }
+
+    /// 启动后台任务，按各数据库的定时计划启动或停止数据库，执行失败时通过 `on_failure` 通知
+    ///
+    /// 只在计划时间到达时执行一次，之后手动启动或停止不会被计划撤销。
+    pub fn start_scheduler<F>(&self, on_failure: F)
+    where
+        F: Fn(AppNotification) + Send + 'static,
+    {
+        let app_state = self.clone();
+        std::thread::spawn(move || {
+            let mut last_check = chrono::Local::now().naive_local();
+            loop {
+                std::thread::sleep(SCHEDULE_CHECK_INTERVAL);
+                let now = chrono::Local::now().naive_local();
+                let from = last_check.max(now - SCHEDULE_CATCH_UP);
+                last_check = now;
+
+                for db_info in app_state.get_all_databases() {
+                    // 同时到期多个计划时以时间最晚的为准
+                    let due = db_info
+                        .schedules
+                        .iter()
+                        .filter(|s| schedule::is_due(s, from, now))
+                        .max_by(|a, b| a.time.cmp(&b.time))
+                        .map(|s| s.action);
+                    let Some(action) = due else {
+                        continue;
+                    };
+                    if let Err(message) = app_state.run_scheduled(&db_info, action) {
+                        log::warn!(
+                            "Scheduled {:?} of {} failed: {}",
+                            action,
+                            db_info.name,
+                            message
+                        );
+                        let verb = match action {
+                            ScheduleAction::Start => "start",
+                            ScheduleAction::Stop => "stop",
+                        };
+                        on_failure(AppNotification {
+                            title: format!("Failed to {} {} on schedule", verb, db_info.name),
+                            body: message,
+                            error: true,
+                        });
+                    }
+                }
+            }
+        });
+    }
+
+    /// 执行定时计划，已处于目标状态时跳过；启动时一并启动依赖的数据库
+    fn run_scheduled(&self, db_info: &DatabaseInfo, action: ScheduleAction) -> Result<(), String> {
+        match action {
+            ScheduleAction::Start => {
+                if db_info.status == DatabaseStatus::Running {
+                    return Ok(());
+                }
+                log::info!("Starting {} on schedule", db_info.name);
+                let results = self.start_with_dependencies(std::slice::from_ref(&db_info.id))?;
+                match results.into_values().find(|result| !result.success) {
+                    Some(result) => Err(result.message),
+                    None => Ok(()),
+                }
+            }
+            ScheduleAction::Stop => {
+                if db_info.status != DatabaseStatus::Running {
+                    return Ok(());
+                }
+                log::info!("Stopping {} on schedule", db_info.name);
+                let mut db_info = db_info.clone();
+                let result = self
+                    .db_manager
+                    .stop_database(&mut db_info)
+                    .map_err(|e| e.to_string())?;
+                if !result.success {
+                    return Err(result.message);
+                }
+                self.update_database(db_info);
+                Ok(())
+            }
+        }
+    }
+
+    /// 启动后台任务，为开启按需启动且已停止的数据库代为监听端口
+    ///
+    /// 收到连接时启动数据库及其依赖，就绪后转发该连接；启动失败时通过 `on_failure` 通知。
+    /// 数据库启动、关闭按需启动或被删除后释放端口。
+    pub fn start_activation_monitor<F>(&self, on_failure: F)
+    where
+        F: Fn(AppNotification) + Send + Sync + 'static,
+    {
+        let app_state = self.clone();
+        let on_failure = Arc::new(on_failure);
+        std::thread::spawn(move || {
+            let activator = app_state.db_manager.activator().clone();
+            // 端口被其它程序占用时只记录一次
+            let mut busy = HashSet::new();
+            loop {
+                let databases = app_state.get_all_databases();
+                for id in activator.armed_databases() {
+                    if !databases.iter().any(|db_info| db_info.id == id) {
+                        activator.release(&id);
+                    }
+                }
+                for db_info in databases {
+                    let wanted = db_info.on_demand
+                        && activation::supports(&db_info)
+                        && db_info.status == DatabaseStatus::Stopped
+                        && app_state.db_manager.transient_status(&db_info.id).is_none();
+                    if !wanted {
+                        activator.release(&db_info.id);
+                        continue;
+                    }
+                    if activator.is_armed(&db_info) {
+                        continue;
+                    }
+                    // 端口或监听地址修改后重新监听
+                    activator.release(&db_info.id);
+
+                    let state = app_state.clone();
+                    let on_failure = on_failure.clone();
+                    let db_id = db_info.id.clone();
+                    let armed = activator.arm(&db_info, move |client| {
+                        if let Err(message) = state.activate(&db_id, client) {
+                            let name = state
+                                .get_database(&db_id)
+                                .map(|db_info| db_info.name)
+                                .unwrap_or_else(|| db_id.clone());
+                            log::warn!("Failed to start {} on demand: {}", name, message);
+                            on_failure(AppNotification {
+                                title: format!("Failed to start {} on demand", name),
+                                body: message,
+                                error: true,
+                            });
+                        }
+                    });
+                    match armed {
+                        Ok(()) => {
+                            busy.remove(&db_info.id);
+                        }
+                        Err(e) => {
+                            if busy.insert(db_info.id.clone()) {
+                                log::warn!("Cannot start {} on demand: {:#}", db_info.name, e);
+                            }
+                        }
+                    }
+                }
+                std::thread::sleep(ACTIVATION_CHECK_INTERVAL);
+            }
+        });
+    }
+
+    /// 按需启动：启动数据库及其依赖，就绪后把收到的连接转发给数据库
+    fn activate(&self, id: &str, client: TcpStream) -> Result<(), String> {
+        if let Some(db_info) = self.get_database(id) {
+            log::info!("Starting {} on demand", db_info.name);
+        }
+        let results = self.start_with_dependencies(&[id.to_string()])?;
+        if let Some(result) = results.into_values().find(|result| !result.success) {
+            return Err(result.message);
+        }
+        let db_info = self
+            .get_database(id)
+            .ok_or_else(|| AppError::DatabaseNotFound.to_string())?;
+        activation::proxy(client, &db_info).map_err(|e| format!("{:#}", e))
+    }
+
+    /// 停止本次运行中由应用启动的数据库，用于退出应用时清理
+    pub fn stop_started_databases(&self) {
+        let ids = self.db_manager.started_databases();
+        let databases: Vec<DatabaseInfo> = ids
+            .iter()
+            .filter_map(|id| self.get_database(id))
+            .filter(|db| db.status == DatabaseStatus::Running)
+            .collect();
+
+        let mut stopped = Vec::new();
+        for mut db_info in databases {
+            match self.db_manager.stop_database(&mut db_info) {
+                Ok(result) if result.success => stopped.push(db_info),
+                Ok(result) => log::warn!("Failed to stop {}: {}", db_info.name, result.message),
+                Err(e) => log::warn!("Failed to stop {}: {}", db_info.name, e),
+            }
+        }
+        self.update_databases(stopped);
+    }
+
+    /// 启动后台守护任务，检测由应用启动的数据库进程是否意外退出
+    ///
+    /// 崩溃的数据库会被标记为 `Crashed` 并通过 `on_crash` 通知，
+    /// 开启 `restart_on_crash` 时按退避策略自动重启。
+    pub fn start_watchdog<F>(&self, on_crash: F)
+    where
+        F: Fn(DatabaseCrashedEvent) + Send + 'static,
+    {
+        let app_state = self.clone();
+        std::thread::spawn(move || {
+            let backoffs: Arc<Mutex<HashMap<String, RestartBackoff>>> = Arc::default();
+            loop {
+                std::thread::sleep(watchdog::WATCHDOG_INTERVAL);
+                for id in app_state.db_manager.started_databases() {
+                    let Some(db_info) = app_state.get_database(&id) else {
+                        continue;
+                    };
+                    let backend = backend::backend_of(&db_info);
+                    if db_info.status != DatabaseStatus::Running
+                        || !backend.is_supervised()
+                        || backend.status(&db_info) != Some(DatabaseStatus::Stopped)
+                    {
+                        continue;
+                    }
+
+                    app_state.handle_crash(db_info, &backoffs, &on_crash);
+                }
+            }
+        });
+    }
+
+    /// 标记数据库崩溃并通知，需要重启时在单独的线程中按退避策略重启，不阻塞守护线程
+    fn handle_crash(
+        &self,
+        mut db_info: DatabaseInfo,
+        backoffs: &Arc<Mutex<HashMap<String, RestartBackoff>>>,
+        on_crash: &impl Fn(DatabaseCrashedEvent),
+    ) {
+        log::warn!("{} exited unexpectedly", db_info.name);
+        self.db_manager.mark_exited(&db_info.id);
+        db_info.status = DatabaseStatus::Crashed;
+        db_info.pid = None;
+        db_info.updated_at = utils::get_timestamp();
+        self.update_database(db_info.clone());
+
+        let (delay, attempt) = if self.get_settings().restart_on_crash {
+            let mut backoffs = backoffs.lock().unwrap();
+            let backoff = backoffs.entry(db_info.id.clone()).or_default();
+            (backoff.next_delay(), backoff.attempts())
+        } else {
+            (None, 0)
+        };
+        on_crash(DatabaseCrashedEvent {
+            database_id: db_info.id.clone(),
+            name: db_info.name.clone(),
+            restarting: delay.is_some(),
+            attempt: delay.map_or(0, |_| attempt),
+        });
+
+        if let Some(delay) = delay {
+            let app_state = self.clone();
+            let backoffs = backoffs.clone();
+            std::thread::spawn(move || {
+                app_state.restart_after_crash(&db_info.id, delay, &backoffs)
+            });
+        }
+    }
+
+    /// 等待退避时间后重启崩溃的数据库，重启失败时继续重试直到超过最大次数
+    fn restart_after_crash(
+        &self,
+        id: &str,
+        mut delay: Duration,
+        backoffs: &Mutex<HashMap<String, RestartBackoff>>,
+    ) {
+        loop {
+            std::thread::sleep(delay);
+
+            // 等待期间可能已被手动启动或删除
+            let Some(mut db_info) = self
+                .get_database(id)
+                .filter(|db| db.status == DatabaseStatus::Crashed)
+            else {
+                return;
+            };
+
+            match self.db_manager.start_database(&mut db_info) {
+                Ok(result) if result.success => {
+                    log::info!("Restarted {} after crash", db_info.name);
+                    if let Some(backoff) = backoffs.lock().unwrap().get_mut(id) {
+                        backoff.restarted();
+                    }
+                    self.update_database(db_info);
+                    return;
+                }
+                Ok(result) => log::warn!("Failed to restart {}: {}", db_info.name, result.message),
+                Err(e) => log::warn!("Failed to restart {}: {}", db_info.name, e),
+            }
+            let next = backoffs
+                .lock()
+                .unwrap()
+                .get_mut(id)
+                .and_then(RestartBackoff::next_delay);
+            match next {
+                Some(next) => delay = next,
+                None => return,
+            }
+        }
+    }
+
+    /// 注册日志跟踪，返回停止标记；同一数据库已有跟踪时先停止旧的
+    pub fn start_log_watcher(&self, database_id: &str) -> Arc<AtomicBool> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut watchers = self.log_watchers.lock().unwrap();
+        if let Some(previous) = watchers.insert(database_id.to_string(), stop.clone()) {
+            previous.store(true, Ordering::Relaxed);
+        }
+        stop
+    }
+
+    /// 停止日志跟踪，返回是否存在正在跟踪的日志
+    pub fn stop_log_watcher(&self, database_id: &str) -> bool {
+        let mut watchers = self.log_watchers.lock().unwrap();
+        match watchers.remove(database_id) {
+            Some(stop) => {
+                stop.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
 }
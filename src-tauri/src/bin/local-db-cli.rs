@@ -0,0 +1,395 @@
+use anyhow::{anyhow, bail, Context, Result};
+use local_db_lib::app::AppState;
+use local_db_lib::command::{install_with_plan, prepare_install};
+use local_db_lib::core::{
+    log_tail, BackendKind, BackupMode, DatabaseInfo, DatabaseStatus, InstallDatabaseParams,
+    OperationResult,
+};
+use std::collections::HashMap;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+const USAGE: &str = "\
+Usage: local-db-cli <command> [options]
+
+Commands:
+  list                            List databases and their status
+  start <database>                Start a database and the databases it depends on
+  stop <database>                 Stop a database
+  install <type> [options]        Install a database (mysql, postgresql, redis, ...)
+      --version <version>  --name <name>  --port <port>
+      --username <username>  --password <password>  --password-stdin
+      --backend <native|docker|package|launch_agent>
+  backup <database> [options]     Back up a database
+      --physical  --passphrase <passphrase>
+  logs <database> [options]       Print the end of the database log
+      --lines <n>  -f, --follow
+
+<database> is a database ID or name.
+
+--password is visible to other users in the process list. Prefer --password-stdin,
+which reads the password from standard input (prompting in a terminal), or set
+the LOCAL_DB_PASSWORD environment variable.
+
+The CLI reads and writes the same state as the app (~/.local-db/state.json).
+Quit the app before making changes here, otherwise the app overwrites them.";
+
+/// 不带值的开关，其余以 `--` 开头的参数都需要一个值
+const SWITCHES: &[&str] = &["--physical", "--follow", "-f", "--password-stdin"];
+
+/// 未指定 `--password` 和 `--password-stdin` 时读取安装密码的环境变量
+const PASSWORD_ENV: &str = "LOCAL_DB_PASSWORD";
+
+/// 日志默认输出的行数
+const DEFAULT_LOG_LINES: usize = 100;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode> {
+    let Some((command, rest)) = args.split_first() else {
+        println!("{}", USAGE);
+        return Ok(ExitCode::SUCCESS);
+    };
+    if matches!(command.as_str(), "help" | "-h" | "--help") {
+        println!("{}", USAGE);
+        return Ok(ExitCode::SUCCESS);
+    }
+    let args = Args::parse(rest)?;
+
+    let state = AppState::new();
+    sync_status(&state);
+    match command.as_str() {
+        "list" => {
+            args.allow(&[], 0)?;
+            list(&state)
+        }
+        "start" => {
+            args.allow(&[], 1)?;
+            start(&state, &args)
+        }
+        "stop" => {
+            args.allow(&[], 1)?;
+            stop(&state, &args)
+        }
+        "install" => {
+            args.allow(
+                &[
+                    "--version",
+                    "--name",
+                    "--port",
+                    "--username",
+                    "--password",
+                    "--password-stdin",
+                    "--backend",
+                ],
+                1,
+            )?;
+            install(&state, &args)
+        }
+        "backup" => {
+            args.allow(&["--physical", "--passphrase"], 1)?;
+            backup(&state, &args)
+        }
+        "logs" => {
+            args.allow(&["--lines", "--follow", "-f"], 1)?;
+            logs(&state, &args)
+        }
+        _ => bail!("Unknown command {}\n\n{}", command, USAGE),
+    }
+}
+
+/// 解析后的命令行参数
+struct Args {
+    positional: Vec<String>,
+    /// `--name value` 形式的选项
+    values: HashMap<String, String>,
+    switches: Vec<String>,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut parsed = Self {
+            positional: Vec::new(),
+            values: HashMap::new(),
+            switches: Vec::new(),
+        };
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if SWITCHES.contains(&arg.as_str()) {
+                parsed.switches.push(arg.clone());
+            } else if arg.starts_with("--") {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow!("Missing value for {}", arg))?;
+                parsed.values.insert(arg.clone(), value.clone());
+            } else {
+                parsed.positional.push(arg.clone());
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// 检查选项都是命令支持的，位置参数的个数与 `positional` 相同
+    fn allow(&self, options: &[&str], positional: usize) -> Result<()> {
+        let unknown = self
+            .values
+            .keys()
+            .chain(&self.switches)
+            .find(|option| !options.contains(&option.as_str()));
+        if let Some(option) = unknown {
+            bail!("Unknown option {}", option);
+        }
+        if self.positional.len() != positional {
+            bail!(
+                "Expected {} argument(s), got {}\n\n{}",
+                positional,
+                self.positional.len(),
+                USAGE
+            );
+        }
+        Ok(())
+    }
+
+    fn value(&self, option: &str) -> Option<String> {
+        self.values.get(option).cloned()
+    }
+
+    fn switch(&self, names: &[&str]) -> bool {
+        self.switches.iter().any(|s| names.contains(&s.as_str()))
+    }
+}
+
+/// 按进程和服务的实际状态更新保存的状态，应用未运行期间数据库可能已停止或被其它方式启动
+fn sync_status(state: &AppState) {
+    let changed: Vec<DatabaseInfo> = state
+        .get_all_databases()
+        .into_iter()
+        .filter_map(|mut db_info| {
+            let status = state.db_manager.get_status(&db_info);
+            if status == db_info.status {
+                return None;
+            }
+            db_info.status = status;
+            Some(db_info)
+        })
+        .collect();
+    state.update_databases(changed);
+}
+
+/// 按 ID 或名称查找数据库，名称重复时需要使用 ID
+fn find_database(state: &AppState, key: &str) -> Result<DatabaseInfo> {
+    if let Some(db_info) = state.get_database(key) {
+        return Ok(db_info);
+    }
+    let mut matches: Vec<DatabaseInfo> = state
+        .get_all_databases()
+        .into_iter()
+        .filter(|db_info| db_info.name == key)
+        .collect();
+    match matches.len() {
+        0 => bail!("Database not found: {}", key),
+        1 => Ok(matches.remove(0)),
+        n => bail!("{} databases are named {}, use the ID instead", n, key),
+    }
+}
+
+/// 输出操作结果，失败时输出到标准错误并返回非零退出码
+fn report<T>(name: &str, result: &OperationResult<T>) -> ExitCode {
+    if result.success {
+        println!("{}: {}", name, result.message);
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{}: {}", name, result.message);
+        ExitCode::FAILURE
+    }
+}
+
+fn list(state: &AppState) -> Result<ExitCode> {
+    let mut databases = state.get_all_databases();
+    if databases.is_empty() {
+        println!("No databases installed");
+        return Ok(ExitCode::SUCCESS);
+    }
+    databases.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let header = ["ID", "NAME", "TYPE", "VERSION", "PORT", "STATUS"].map(String::from);
+    let rows: Vec<[String; 6]> = databases
+        .iter()
+        .map(|db_info| {
+            [
+                db_info.id.clone(),
+                db_info.name.clone(),
+                db_info.db_type.display_name().to_string(),
+                db_info.version.clone(),
+                db_info.port.to_string(),
+                db_info.status.label().to_string(),
+            ]
+        })
+        .collect();
+    let mut widths = header.clone().map(|column| column.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in std::iter::once(&header).chain(&rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn start(state: &AppState, args: &Args) -> Result<ExitCode> {
+    let db_info = find_database(state, &args.positional[0])?;
+    if db_info.status == DatabaseStatus::Running {
+        println!("{} is already running", db_info.name);
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let results = state
+        .start_with_dependencies(std::slice::from_ref(&db_info.id))
+        .map_err(anyhow::Error::msg)?;
+    let mut code = ExitCode::SUCCESS;
+    for (id, result) in &results {
+        let name = state
+            .get_database(id)
+            .map(|db_info| db_info.name)
+            .unwrap_or_else(|| id.clone());
+        report(&name, result);
+        if !result.success {
+            code = ExitCode::FAILURE;
+        }
+    }
+    Ok(code)
+}
+
+fn stop(state: &AppState, args: &Args) -> Result<ExitCode> {
+    let mut db_info = find_database(state, &args.positional[0])?;
+    let result = state.db_manager.stop_database(&mut db_info)?;
+    let name = db_info.name.clone();
+    if result.success {
+        state.update_database(db_info);
+    }
+    Ok(report(&name, &result))
+}
+
+fn install(state: &AppState, args: &Args) -> Result<ExitCode> {
+    let backend = match args.value("--backend") {
+        Some(backend) => serde_json::from_value::<BackendKind>(serde_json::json!(backend))
+            .map_err(|_| anyhow!("Unknown backend {}", backend))?,
+        None => BackendKind::default(),
+    };
+    let port = args
+        .value("--port")
+        .map(|port| port.parse::<u16>())
+        .transpose()
+        .context("Invalid port")?;
+    let mut params = InstallDatabaseParams {
+        db_type: args.positional[0].to_lowercase(),
+        version: args.value("--version"),
+        name: args.value("--name"),
+        port,
+        username: args.value("--username"),
+        password: install_password(args)?,
+        backend,
+        preset: None,
+        env: HashMap::new(),
+        extra_args: Vec::new(),
+    };
+
+    let plan = prepare_install(state, &mut params, None).map_err(anyhow::Error::msg)?;
+    plan.preflight()?;
+    println!(
+        "Installing {}, this may take a few minutes...",
+        params.db_type
+    );
+    let db_info = install_with_plan(state, plan)?;
+    state
+        .add_database(db_info.clone())
+        .map_err(anyhow::Error::msg)?;
+    println!(
+        "Installed {} {} on port {} (ID {})",
+        db_info.name, db_info.version, db_info.port, db_info.id
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+/// 安装使用的密码，依次取 `--password-stdin` 读取的一行、`--password` 和环境变量 LOCAL_DB_PASSWORD
+///
+/// `--password` 会出现在进程列表中，其他两种方式不会。
+fn install_password(args: &Args) -> Result<Option<String>> {
+    if args.switch(&["--password-stdin"]) {
+        if args.value("--password").is_some() {
+            bail!("--password and --password-stdin cannot be used together");
+        }
+        let stdin = std::io::stdin();
+        if stdin.is_terminal() {
+            eprint!("Password: ");
+            std::io::stderr().flush()?;
+        }
+        let mut password = String::new();
+        stdin
+            .read_line(&mut password)
+            .context("Failed to read the password")?;
+        return Ok(Some(password.trim_end_matches(['\r', '\n']).to_string()));
+    }
+    Ok(args.value("--password").or_else(|| {
+        std::env::var(PASSWORD_ENV)
+            .ok()
+            .filter(|password| !password.is_empty())
+    }))
+}
+
+fn backup(state: &AppState, args: &Args) -> Result<ExitCode> {
+    let db_info = find_database(state, &args.positional[0])?;
+    let mode = if args.switch(&["--physical"]) {
+        BackupMode::Physical
+    } else {
+        BackupMode::Logical
+    };
+    let passphrase = args.value("--passphrase");
+    let record = state
+        .db_manager
+        .backup_database(&db_info, mode, passphrase.as_deref())
+        .context("Failed to backup database")?;
+    println!("Backup of {} created: {}", db_info.name, record.file_path);
+    state.add_backup(record);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn logs(state: &AppState, args: &Args) -> Result<ExitCode> {
+    let db_info = find_database(state, &args.positional[0])?;
+    let lines = args
+        .value("--lines")
+        .map(|lines| lines.parse::<usize>())
+        .transpose()
+        .context("Invalid number of lines")?
+        .unwrap_or(DEFAULT_LOG_LINES);
+    let log_path = Path::new(&db_info.log_path);
+    for line in log_tail::read_last_lines(log_path, lines).context("Failed to read log file")? {
+        println!("{}", line);
+    }
+    // 持续输出新增的日志，直到按下 Ctrl+C
+    if args.switch(&["--follow", "-f"]) {
+        log_tail::follow(log_path, Arc::new(AtomicBool::new(false)), |line| {
+            println!("{}", line)
+        });
+    }
+    Ok(ExitCode::SUCCESS)
+}
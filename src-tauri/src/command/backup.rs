@@ -0,0 +1,263 @@
+use crate::app::AppState;
+use crate::command::run_blocking;
+use crate::core::{backup, export, remote, snapshot};
+use crate::core::{
+    utils, AppError, AppNotification, BackupMode, BackupPruneResult, BackupRecord, CommandError,
+    DataSnapshot, ExportFormat, OperationResult,
+};
+use crate::notify;
+use tauri::{AppHandle, State};
+
+/// 备份数据库，`backup_mode` 默认为逻辑备份，提供口令时对备份文件加密
+#[tauri::command]
+pub async fn backup_database(
+    state: State<'_, AppState>,
+    id: String,
+    backup_mode: Option<BackupMode>,
+    passphrase: Option<String>,
+    app_handle: AppHandle,
+) -> Result<OperationResult<BackupRecord>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(db_info) => match state.db_manager.backup_database(
+            &db_info,
+            backup_mode.unwrap_or_default(),
+            passphrase.as_deref(),
+        ) {
+            Ok(record) => {
+                state.add_backup(record.clone());
+                OperationResult::success("Backup created successfully", Some(record))
+            }
+            Err(e) => {
+                // 大库备份耗时较长，用户可能已切换到其它窗口
+                notify::send(
+                    &app_handle,
+                    AppNotification {
+                        title: format!("Backup of {} failed", db_info.name),
+                        body: e.to_string(),
+                        error: true,
+                    },
+                );
+                OperationResult::error(format!("Failed to backup database: {}", e))
+            }
+        },
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 导出数据库中的数据，导出记录与备份一起出现在备份历史中
+///
+/// SQL 格式写入 `path` 文件，CSV / JSON 格式在 `path` 目录下为每张表生成一个文件。
+#[tauri::command]
+pub async fn export_data(
+    state: State<'_, AppState>,
+    id: String,
+    target_db: String,
+    format: ExportFormat,
+    path: String,
+) -> Result<OperationResult<BackupRecord>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(db_info) => {
+            match export::export_data(&db_info, &target_db, format, std::path::Path::new(&path)) {
+                Ok(record) => {
+                    state.add_backup(record.clone());
+                    OperationResult::success("Data exported successfully", Some(record))
+                }
+                Err(e) => OperationResult::error(format!("Failed to export data: {:#}", e)),
+            }
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 从备份恢复数据库，加密的备份需要提供创建时的口令
+#[tauri::command]
+pub async fn restore_database(
+    state: State<'_, AppState>,
+    backup_id: String,
+    passphrase: Option<String>,
+) -> Result<OperationResult<()>, CommandError> {
+    let record = match state.get_backup(&backup_id) {
+        Some(record) => record,
+        None => return Ok(OperationResult::error("Backup not found")),
+    };
+
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&record.database_id) {
+        Some(mut db_info) => {
+            match state
+                .db_manager
+                .restore_database(&mut db_info, &record, passphrase.as_deref())
+            {
+                Ok(result) => {
+                    if result.success {
+                        state.update_database(db_info);
+                    }
+                    result
+                }
+                Err(e) => OperationResult::error(format!("Failed to restore database: {}", e)),
+            }
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 获取备份历史，可按数据库过滤
+#[tauri::command]
+pub async fn list_backups(
+    state: State<'_, AppState>,
+    database_id: Option<String>,
+) -> Result<Vec<BackupRecord>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || state.list_backups(database_id.as_deref())).await
+}
+
+/// 删除备份记录及备份文件，导出的目录可能很大，在后台线程中删除
+#[tauri::command]
+pub async fn delete_backup(
+    state: State<'_, AppState>,
+    backup_id: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_backup(&backup_id) {
+        Some(record) => {
+            if let Err(e) = backup::remove_backup_file(&record) {
+                return OperationResult::error(format!("Failed to delete backup: {}", e));
+            }
+            state.remove_backup(&backup_id);
+            OperationResult::success("Backup deleted successfully", None)
+        }
+        None => OperationResult::error("Backup not found"),
+    })
+    .await
+}
+
+/// 上传备份到设置中配置的远程存储
+#[tauri::command]
+pub async fn upload_backup(
+    state: State<'_, AppState>,
+    backup_id: String,
+) -> Result<OperationResult<BackupRecord>, CommandError> {
+    let mut record = match state.get_backup(&backup_id) {
+        Some(record) => record,
+        None => return Ok(OperationResult::error("Backup not found")),
+    };
+
+    let target = match state.get_settings().remote_backup {
+        Some(target) => target,
+        None => {
+            return Ok(OperationResult::error(
+                "Remote backup target is not configured",
+            ))
+        }
+    };
+
+    let state = state.inner().clone();
+    run_blocking(move || match remote::upload_backup(&target, &record) {
+        Ok(remote_url) => {
+            record.remote_url = Some(remote_url);
+            state.add_backup(record.clone());
+            OperationResult::success("Backup uploaded successfully", Some(record))
+        }
+        Err(e) => OperationResult::error(format!("Failed to upload backup: {}", e)),
+    })
+    .await
+}
+
+/// 清理超出保留期限的备份，返回释放的磁盘空间
+#[tauri::command]
+pub async fn prune_backups(
+    state: State<'_, AppState>,
+) -> Result<OperationResult<BackupPruneResult>, CommandError> {
+    let retention_days = state.get_settings().backup_retention_days;
+    let state = state.inner().clone();
+    let result = run_blocking(move || state.prune_backups(retention_days)).await?;
+    Ok(OperationResult::success(
+        format!(
+            "Removed {} backups, reclaimed {}",
+            result.removed_count,
+            utils::format_file_size(result.reclaimed_bytes)
+        ),
+        Some(result),
+    ))
+}
+
+/// 为数据目录创建快照，运行中的数据库会先停止，完成后重新启动
+#[tauri::command]
+pub async fn snapshot_database(
+    state: State<'_, AppState>,
+    id: String,
+    label: String,
+) -> Result<OperationResult<DataSnapshot>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => match state.db_manager.snapshot_data(&mut db_info, &label) {
+            Ok(result) => {
+                state.update_database(db_info);
+                result
+            }
+            Err(e) => OperationResult::error(format!("Failed to create snapshot: {:#}", e)),
+        },
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 获取数据库的快照列表，最新的在前
+#[tauri::command]
+pub async fn list_snapshots(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<Vec<DataSnapshot>>, CommandError> {
+    let storage_path = state.db_manager.storage_path();
+    run_blocking(move || match snapshot::list(&storage_path, &id) {
+        Ok(snapshots) => OperationResult::success("Snapshots listed", Some(snapshots)),
+        Err(e) => OperationResult::error(format!("Failed to list snapshots: {:#}", e)),
+    })
+    .await
+}
+
+/// 用快照替换数据目录，运行中的数据库会先停止，完成后重新启动
+#[tauri::command]
+pub async fn restore_snapshot(
+    state: State<'_, AppState>,
+    id: String,
+    snapshot_id: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => match state
+            .db_manager
+            .restore_snapshot(&mut db_info, &snapshot_id)
+        {
+            Ok(result) => {
+                state.update_database(db_info);
+                result
+            }
+            Err(e) => OperationResult::error(format!("Failed to restore snapshot: {:#}", e)),
+        },
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 删除数据目录快照
+#[tauri::command]
+pub async fn delete_snapshot(
+    state: State<'_, AppState>,
+    id: String,
+    snapshot_id: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let storage_path = state.db_manager.storage_path();
+    run_blocking(
+        move || match snapshot::delete(&storage_path, &id, &snapshot_id) {
+            Ok(()) => OperationResult::success("Snapshot deleted", None),
+            Err(e) => OperationResult::error(format!("Failed to delete snapshot: {:#}", e)),
+        },
+    )
+    .await
+}
@@ -0,0 +1,86 @@
+use crate::app::AppState;
+use crate::command::run_blocking;
+use crate::core::benchmark::{self, BenchmarkProfile, BenchmarkResult};
+use crate::core::{utils, AppError, AsyncTask, CommandError, TaskStatus};
+use tauri::{AppHandle, Emitter, State};
+
+/// 对运行中的数据库压测，返回任务ID，进度通过 benchmark-progress 事件发送，结果写入压测历史
+///
+/// `profile` 默认为 standard，`label` 为结果的备注（如使用的配置预设）。
+#[tauri::command]
+pub async fn run_benchmark(
+    state: State<'_, AppState>,
+    id: String,
+    profile: Option<BenchmarkProfile>,
+    label: Option<String>,
+    app_handle: AppHandle,
+) -> Result<String, CommandError> {
+    let db_info = state.get_database(&id).ok_or(AppError::DatabaseNotFound)?;
+    let profile = profile.unwrap_or_default();
+
+    let task_id = format!(
+        "benchmark-{}-{}",
+        db_info.db_type.as_str(),
+        utils::generate_id()
+    );
+    state.add_task(AsyncTask {
+        id: task_id.clone(),
+        task_type: "benchmark".to_string(),
+        db_type: db_info.db_type.as_str().to_string(),
+        status: TaskStatus::Running,
+        progress: 0,
+        message: format!("Benchmarking {}...", db_info.name),
+        error: None,
+        error_code: None,
+        queue_position: None,
+        created_at: utils::get_timestamp(),
+        updated_at: utils::get_timestamp(),
+    });
+
+    let tasks = state.tasks.clone();
+    let storage_path = state.db_manager.storage_path();
+    let task_id_for_thread = task_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let update_task = |update: &dyn Fn(&mut AsyncTask)| {
+            let mut tasks = tasks.lock().unwrap();
+            if let Some(task) = tasks.get_mut(&task_id_for_thread) {
+                update(task);
+                task.updated_at = utils::get_timestamp();
+                let _ = app_handle.emit("benchmark-progress", task.clone());
+            }
+        };
+
+        let result = benchmark::run(&db_info, profile, label, |progress, message| {
+            update_task(&|task| {
+                task.progress = progress;
+                task.message = message.to_string();
+            })
+        })
+        .and_then(|result| benchmark::record(&storage_path, result));
+        match result {
+            Ok(()) => update_task(&|task| {
+                task.status = TaskStatus::Completed;
+                task.progress = 100;
+                task.message = "Benchmark completed successfully".to_string();
+            }),
+            Err(e) => update_task(&|task| {
+                task.status = TaskStatus::Failed;
+                task.error = Some(format!("{:#}", e));
+                task.error_code = AppError::of(&e);
+                task.message = "Benchmark failed".to_string();
+            }),
+        }
+    });
+
+    Ok(task_id)
+}
+
+/// 获取压测历史，按时间顺序排列，`id` 不为空时只返回该数据库的结果
+#[tauri::command]
+pub async fn get_benchmark_history(
+    state: State<'_, AppState>,
+    id: Option<String>,
+) -> Result<Vec<BenchmarkResult>, CommandError> {
+    let storage_path = state.db_manager.storage_path();
+    run_blocking(move || benchmark::history(&storage_path, id.as_deref())).await
+}
@@ -1,29 +1,59 @@
-use crate::app::AppState;
-use crate::core::{DatabaseInfo, DatabaseStatus, DatabaseType, OperationResult};
+use crate::app::{AppState, InstallRequest};
+use crate::command::run_blocking;
+use crate::core::{
+    activation, backend, client, clipboard, config_file, connection, custom, dependency, docker,
+    duckdb, external, health, log_tail, mongo, offline, password, pg_extension, preflight, preset,
+    provision, query, query_stats, redis, replication, sample_data, schedule, schema, slow_query,
+    snapshot, sqlite, surreal, versions,
+};
+use crate::core::{
+    AppError, BackendKind, CommandError, ConfigPreset, ConnectionInfoKind, ConnectionUriFormat,
+    CustomServerParams, DatabaseClient, DatabaseConfig, DatabaseDiskUsage, DatabaseInfo,
+    DatabaseManager, DatabaseObject, DatabaseSchedule, DatabaseStatus, DatabaseType, EnvFramework,
+    HealthCheckResult, InstallDatabaseParams, LanEndpoint, LogLineEvent, MongoCollectionInfo,
+    MongoDatabaseInfo, OperationResult, QueryResult, RedisKeyPage, RedisReply, SampleDataset,
+    SqliteFile, SurrealStatementResult,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tauri::State;
+use tauri_plugin_log::log;
 
 /// 获取所有数据库列表
 #[tauri::command]
 pub fn get_databases(state: State<AppState>) -> Vec<DatabaseInfo> {
-    state.get_all_databases()
+    state
+        .get_all_databases()
+        .into_iter()
+        .map(|db_info| state.db_manager.with_transient_status(db_info))
+        .collect()
 }
 
 /// 根据ID获取数据库信息
 #[tauri::command]
 pub fn get_database(state: State<AppState>, id: String) -> Option<DatabaseInfo> {
-    state.get_database(&id)
+    state
+        .get_database(&id)
+        .map(|db_info| state.db_manager.with_transient_status(db_info))
 }
 
 /// 根据数据库类型获取数据库
 #[tauri::command]
 pub fn get_database_by_type(state: State<AppState>, db_type: String) -> Option<DatabaseInfo> {
-    state.get_database_by_type(&db_type)
+    state
+        .get_database_by_type(&db_type)
+        .map(|db_info| state.db_manager.with_transient_status(db_info))
 }
 
 /// 启动数据库
 #[tauri::command]
-pub fn start_database(state: State<AppState>, id: String) -> OperationResult<()> {
-    match state.get_database(&id) {
+pub async fn start_database(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
         Some(mut db_info) => match state.db_manager.start_database(&mut db_info) {
             Ok(result) => {
                 if result.success {
@@ -33,14 +63,19 @@ pub fn start_database(state: State<AppState>, id: String) -> OperationResult<()>
             }
             Err(e) => OperationResult::error(format!("Failed to start database: {}", e)),
         },
-        None => OperationResult::error("Database not found"),
-    }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
 }
 
 /// 停止数据库
 #[tauri::command]
-pub fn stop_database(state: State<AppState>, id: String) -> OperationResult<()> {
-    match state.get_database(&id) {
+pub async fn stop_database(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
         Some(mut db_info) => match state.db_manager.stop_database(&mut db_info) {
             Ok(result) => {
                 if result.success {
@@ -50,57 +85,316 @@ pub fn stop_database(state: State<AppState>, id: String) -> OperationResult<()>
             }
             Err(e) => OperationResult::error(format!("Failed to stop database: {}", e)),
         },
-        None => OperationResult::error("Database not found"),
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 并发启动所有已停止的数据库，返回以数据库 ID 为键的操作结果
+#[tauri::command]
+pub async fn start_all_databases(
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, OperationResult<()>>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || {
+        let databases = state
+            .get_all_databases()
+            .into_iter()
+            .filter(|db| db.status == DatabaseStatus::Stopped)
+            .collect();
+
+        run_concurrently(&state, databases, |db_manager, db_info| {
+            db_manager.start_database(db_info).unwrap_or_else(|e| {
+                OperationResult::error(format!("Failed to start database: {}", e))
+            })
+        })
+    })
+    .await
+}
+
+/// 并发停止所有运行中的数据库，返回以数据库 ID 为键的操作结果
+#[tauri::command]
+pub async fn stop_all_databases(
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, OperationResult<()>>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || {
+        let databases = state
+            .get_all_databases()
+            .into_iter()
+            .filter(|db| db.status == DatabaseStatus::Running && db.db_type != DatabaseType::SQLite)
+            .collect();
+
+        run_concurrently(&state, databases, |db_manager, db_info| {
+            db_manager.stop_database(db_info).unwrap_or_else(|e| {
+                OperationResult::error(format!("Failed to stop database: {}", e))
+            })
+        })
+    })
+    .await
+}
+
+/// 按依赖顺序启动所有设置了自动启动的数据库，返回以数据库 ID 为键的操作结果
+#[tauri::command]
+pub async fn start_autostart_databases(
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, OperationResult<()>>, CommandError> {
+    let state = state.inner().clone();
+    Ok(run_blocking(move || state.start_autostart_databases()).await??)
+}
+
+/// 每个数据库在独立线程中执行 `operation`，全部完成后一次性保存成功的状态变更
+fn run_concurrently<F>(
+    state: &AppState,
+    databases: Vec<DatabaseInfo>,
+    operation: F,
+) -> HashMap<String, OperationResult<()>>
+where
+    F: Fn(&DatabaseManager, &mut DatabaseInfo) -> OperationResult<()> + Sync,
+{
+    let outcomes: Vec<(DatabaseInfo, OperationResult<()>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = databases
+            .into_iter()
+            .map(|mut db_info| {
+                let operation = &operation;
+                scope.spawn(move || {
+                    let result = operation(&state.db_manager, &mut db_info);
+                    (db_info, result)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok())
+            .collect()
+    });
+
+    let mut results = HashMap::with_capacity(outcomes.len());
+    let mut updated = Vec::new();
+    for (db_info, result) in outcomes {
+        if result.success {
+            updated.push(db_info.clone());
+        }
+        results.insert(db_info.id, result);
     }
+    state.update_databases(updated);
+    results
 }
 
 /// 重启数据库
 #[tauri::command]
-pub fn restart_database(state: State<AppState>, id: String) -> OperationResult<()> {
-    match state.get_database(&id) {
-        Some(mut db_info) => {
-            // 先停止
-            if let Err(e) = state.db_manager.stop_database(&mut db_info) {
-                return OperationResult::error(format!("Failed to stop database: {}", e));
+pub async fn restart_database(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    // 先停止
+    let db_manager = state.db_manager.clone();
+    let (stopped, mut db_info) = run_blocking(move || {
+        let mut db_info = db_info;
+        let stopped = db_manager.stop_database(&mut db_info);
+        (stopped, db_info)
+    })
+    .await?;
+    if let Err(e) = stopped {
+        return Ok(OperationResult::error(format!(
+            "Failed to stop database: {}",
+            e
+        )));
+    }
+
+    // 等待一会儿
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+    // 再启动
+    run_blocking(move || {
+        let started = state.db_manager.start_database(&mut db_info);
+        match started {
+            Ok(result) => {
+                if result.success {
+                    state.update_database(db_info);
+                }
+                result
+            }
+            Err(e) => OperationResult::error(format!("Failed to start database: {}", e)),
+        }
+    })
+    .await
+}
+
+/// 修改数据库端口，运行中的数据库会被重启
+#[tauri::command]
+pub async fn update_database_port(
+    state: State<'_, AppState>,
+    id: String,
+    new_port: u16,
+) -> Result<OperationResult<()>, CommandError> {
+    let in_use = state
+        .get_all_databases()
+        .iter()
+        .any(|db| db.id != id && db.port == new_port);
+    if in_use {
+        return Ok(OperationResult::failure(AppError::PortInUse {
+            port: new_port,
+        }));
+    }
+
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => match state.db_manager.update_port(&mut db_info, new_port) {
+            Ok(result) => {
+                if result.success {
+                    state.update_database(db_info);
+                }
+                result
+            }
+            Err(e) => OperationResult::error_from(format!("Failed to update port: {}", e), &e),
+        },
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 修改数据库密码
+#[tauri::command]
+pub async fn update_database_password(
+    state: State<'_, AppState>,
+    id: String,
+    new_password: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => match state
+            .db_manager
+            .update_password(&mut db_info, &new_password)
+        {
+            Ok(result) => {
+                if result.success {
+                    state.update_database(db_info);
+                }
+                result
             }
+            Err(e) => OperationResult::error(format!("Failed to update password: {:#}", e)),
+        },
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 为 Redis 设置密码，提供 `username` 时同时创建 ACL 用户，立即生效并保存凭据
+#[tauri::command]
+pub async fn secure_redis(
+    state: State<'_, AppState>,
+    id: String,
+    password: String,
+    username: Option<String>,
+) -> Result<OperationResult<()>, CommandError> {
+    let Some(mut db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+    if db_info.db_type != DatabaseType::Redis {
+        return Ok(OperationResult::error(format!(
+            "{} is not a Redis instance",
+            db_info.name
+        )));
+    }
 
-            // 等待一会儿
-            std::thread::sleep(std::time::Duration::from_secs(2));
+    let state = state.inner().clone();
+    run_blocking(move || {
+        let username = username.filter(|u| !u.is_empty());
+        match redis::secure(&db_info, username.as_deref(), &password) {
+            Ok(()) => {
+                db_info.username = username;
+                db_info.password = Some(password);
+                db_info.updated_at = crate::core::utils::get_timestamp();
+                let message = format!("{} is now password protected", db_info.name);
+                state.update_database(db_info);
+                OperationResult::success(message, None)
+            }
+            Err(e) => OperationResult::error(format!("Failed to secure Redis: {:#}", e)),
+        }
+    })
+    .await
+}
 
-            // 再启动
-            match state.db_manager.start_database(&mut db_info) {
+/// 为 MongoDB 开启访问控制，创建管理员并保存凭据，数据库会重启
+#[tauri::command]
+pub async fn enable_mongodb_auth(
+    state: State<'_, AppState>,
+    id: String,
+    username: String,
+    password: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => {
+            match state
+                .db_manager
+                .enable_mongodb_auth(&mut db_info, &username, &password)
+            {
                 Ok(result) => {
-                    if result.success {
-                        state.update_database(db_info);
-                    }
+                    state.update_database(db_info);
                     result
                 }
-                Err(e) => OperationResult::error(format!("Failed to start database: {}", e)),
+                Err(e) => {
+                    OperationResult::error(format!("Failed to enable authentication: {:#}", e))
+                }
             }
         }
-        None => OperationResult::error("Database not found"),
-    }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
 }
 
 /// 获取数据库状态
 #[tauri::command]
-pub fn get_database_status(state: State<AppState>, id: String) -> Option<DatabaseStatus> {
-    state
-        .get_database(&id)
-        .map(|db_info| state.db_manager.get_status(&db_info))
+pub async fn get_database_status(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Option<DatabaseStatus>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || {
+        state
+            .get_database(&id)
+            .map(|db_info| state.db_manager.get_status(&db_info))
+    })
+    .await
 }
 
 /// 删除数据库
 #[tauri::command]
-pub fn delete_database(state: State<AppState>, id: String, with_data: bool) -> OperationResult<()> {
-    match state.get_database(&id) {
+pub async fn delete_database(
+    state: State<'_, AppState>,
+    id: String,
+    with_data: bool,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
         Some(mut db_info) => {
+            // 容器、系统软件包和外部安装的数据目录不由应用管理，可能是系统的数据目录
+            if with_data
+                && matches!(
+                    db_info.backend,
+                    BackendKind::Docker | BackendKind::Package | BackendKind::External
+                )
+            {
+                return OperationResult::error(
+                    "The data directory of this database is not managed by the app",
+                );
+            }
+
             // 如果数据库正在运行，先停止
             if state.db_manager.get_status(&db_info) == DatabaseStatus::Running {
                 if let Err(e) = state.db_manager.stop_database(&mut db_info) {
                     return OperationResult::error(format!("Failed to stop database: {}", e));
                 }
             }
+            detach_replica(&state, &db_info);
 
             // 如果需要删除数据
             if with_data {
@@ -114,47 +408,407 @@ pub fn delete_database(state: State<AppState>, id: String, with_data: bool) -> O
 
             OperationResult::success("Database deleted successfully", None)
         }
-        None => OperationResult::error("Database not found"),
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 卸载数据库：删除实例的数据、日志和配置，没有其他实例共用时一并删除程序文件
+#[tauri::command]
+pub async fn uninstall_database(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || uninstall(&state, &id)).await
+}
+
+fn uninstall(state: &AppState, id: &str) -> OperationResult<()> {
+    let Some(mut db_info) = state.get_database(id) else {
+        return OperationResult::failure(AppError::DatabaseNotFound);
+    };
+
+    // 外部安装只从列表中移除，不停止服务，之后自动发现也不再注册
+    if db_info.backend == BackendKind::External {
+        let mut settings = state.get_settings();
+        settings.ignored_external.push(db_info.install_path.clone());
+        state.update_settings(settings);
+        state.remove_database(id);
+        return OperationResult::success(format!("{} removed", db_info.name), None);
+    }
+
+    if state.db_manager.get_status(&db_info) == DatabaseStatus::Running {
+        if let Err(e) = state.db_manager.stop_database(&mut db_info) {
+            return OperationResult::error(format!("Failed to stop database: {}", e));
+        }
+    }
+    detach_replica(state, &db_info);
+
+    let shared = state.get_all_databases().iter().any(|db| {
+        db.id != db_info.id
+            && db.backend == db_info.backend
+            && db.install_path == db_info.install_path
+    });
+    if let Err(e) = backend::backend_of(&db_info).uninstall(&db_info, !shared) {
+        return OperationResult::error(format!("Failed to uninstall database: {:#}", e));
+    }
+    if let Err(e) = snapshot::delete_all(&state.db_manager.storage_path(), &db_info.id) {
+        log::warn!("Failed to remove snapshots of {}: {:#}", db_info.name, e);
+    }
+
+    state.remove_database(id);
+    OperationResult::success(format!("{} uninstalled successfully", db_info.name), None)
+}
+
+/// 删除副本时清理源数据库上为其保留的复制槽和复制账号，失败时只记录警告；
+/// 删除源数据库时解除其副本的复制关系和启动依赖
+fn detach_replica(state: &AppState, db_info: &DatabaseInfo) {
+    for mut replica in state
+        .get_all_databases()
+        .into_iter()
+        .filter(|db| db.replica_of.as_deref() == Some(db_info.id.as_str()))
+    {
+        replica.replica_of = None;
+        replica.depends_on.retain(|dep| *dep != db_info.id);
+        replica.updated_at = crate::core::utils::get_timestamp();
+        state.update_database(replica);
+    }
+
+    let Some(source) = db_info
+        .replica_of
+        .as_deref()
+        .and_then(|id| state.get_database(id))
+    else {
+        return;
+    };
+    if let Err(e) = replication::detach(&source, db_info) {
+        log::warn!(
+            "Failed to detach {} from {}: {:#}",
+            db_info.name,
+            source.name,
+            e
+        );
+    }
+}
+
+/// 接管本机端口上已在运行的数据库，注册为外部管理的实例
+///
+/// 按协议探测确认端口上确实是该类型的数据库，提供凭据时同时验证能否登录。
+#[tauri::command]
+pub async fn adopt_database(
+    state: State<'_, AppState>,
+    port: u16,
+    db_type: DatabaseType,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<OperationResult<DatabaseInfo>, CommandError> {
+    if state.find_database_by_port(port).is_some() {
+        return Ok(OperationResult::failure(AppError::PortInUse { port }));
+    }
+
+    let state = state.inner().clone();
+    run_blocking(
+        move || match external::adopt(&db_type, port, username, password) {
+            Ok(db_info) => {
+                state.update_database(db_info.clone());
+                OperationResult::success(format!("{} adopted", db_info.name), Some(db_info))
+            }
+            Err(e) => OperationResult::error_from(format!("Failed to adopt database: {:#}", e), &e),
+        },
+    )
+    .await
+}
+
+/// 注册自定义服务：由用户提供二进制和启动参数，应用按 PID 文件管理进程
+#[tauri::command]
+pub async fn register_custom_server(
+    state: State<'_, AppState>,
+    params: CustomServerParams,
+) -> Result<OperationResult<DatabaseInfo>, CommandError> {
+    if let Some(other) = state.find_database_by_port(params.port) {
+        return Ok(OperationResult::error(format!(
+            "Port {} is already used by {}",
+            params.port, other.name
+        )));
     }
+
+    let state = state.inner().clone();
+    run_blocking(move || {
+        let storage_path = PathBuf::from(state.get_settings().default_storage_path);
+        match custom::register(&storage_path, &params) {
+            Ok(db_info) => {
+                state.update_database(db_info.clone());
+                OperationResult::success(format!("{} registered", db_info.name), Some(db_info))
+            }
+            Err(e) => OperationResult::error_from(
+                format!("Failed to register custom server: {:#}", e),
+                &e,
+            ),
+        }
+    })
+    .await
+}
+
+/// 检查本机是否可以用容器运行数据库
+#[tauri::command]
+pub async fn is_docker_available() -> Result<bool, CommandError> {
+    run_blocking(docker::is_available).await
 }
 
-/// 安装数据库参数
-#[derive(serde::Deserialize)]
-pub struct InstallDatabaseParams {
-    pub db_type: String,
-    pub version: Option<String>,
-    pub port: Option<u16>,
-    pub username: Option<String>,
-    pub password: Option<String>,
+/// 查询数据库可安装的版本，供安装时选择
+///
+/// 需要访问网络，使用异步请求，避免阻塞界面
+#[tauri::command]
+pub async fn get_available_versions(
+    db_type: DatabaseType,
+) -> Result<Vec<versions::AvailableVersion>, CommandError> {
+    Ok(versions::available_versions(&db_type).await?)
 }
 
 /// 安装数据库
 #[tauri::command]
-pub fn install_database(
-    state: State<AppState>,
+pub async fn install_database(
+    state: State<'_, AppState>,
     params: InstallDatabaseParams,
     _app_handle: tauri::AppHandle,
-) -> Result<String, String> {
-    // 检查是否已安装该类型的数据库
-    if state.get_database_by_type(&params.db_type).is_some() {
-        return Err("Database type already installed".to_string());
+) -> Result<String, CommandError> {
+    spawn_install(state, params, None, _app_handle)
+}
+
+/// 从本地发行包离线安装数据库，用于无法访问网络的机器
+///
+/// 发行包需与在线安装下载的相同，检查通过后按在线安装的流程解压、配置和初始化。
+#[tauri::command]
+pub async fn install_database_from_archive(
+    state: State<'_, AppState>,
+    params: InstallDatabaseParams,
+    archive_path: String,
+    app_handle: tauri::AppHandle,
+) -> Result<String, CommandError> {
+    let archive = PathBuf::from(archive_path);
+    spawn_install(state, params, Some(archive), app_handle)
+}
+
+/// 校验通过的安装参数，由 [`prepare_install`] 生成
+pub struct InstallPlan {
+    db_type: DatabaseType,
+    backend: BackendKind,
+    storage_path: PathBuf,
+    version: Option<String>,
+    port: Option<u16>,
+    instance_name: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    preset: Option<ConfigPreset>,
+    env: HashMap<String, String>,
+    extra_args: Vec<String>,
+    archive: Option<PathBuf>,
+}
+
+impl InstallPlan {
+    /// 下载前检查磁盘空间和端口，排队的安装应在开始执行时检查，前面的安装可能已占用空间或端口
+    pub fn preflight(&self) -> anyhow::Result<()> {
+        preflight::check_install(&preflight::InstallCheck {
+            db_type: &self.db_type,
+            backend: self.backend,
+            storage_path: &self.storage_path,
+            port: self.port,
+            archive: self.archive.as_deref(),
+        })
     }
+}
 
+/// 校验安装参数，确定运行方式、实例名、端口和默认凭据
+///
+/// 界面和命令行安装共用，离线安装时会将 `params.version` 设为从发行包识别的版本。
+pub fn prepare_install(
+    state: &AppState,
+    params: &mut InstallDatabaseParams,
+    archive: Option<PathBuf>,
+) -> Result<InstallPlan, String> {
     // 解析数据库类型
     let db_type: DatabaseType = match params.db_type.as_str() {
         "mysql" => DatabaseType::MySQL,
+        "mariadb" => DatabaseType::MariaDB,
         "postgresql" => DatabaseType::PostgreSQL,
         "mongodb" => DatabaseType::MongoDB,
         "redis" => DatabaseType::Redis,
         "qdrant" => DatabaseType::Qdrant,
         "surrealdb" => DatabaseType::SurrealDB,
+        "opensearch" => DatabaseType::OpenSearch,
+        "influxdb" => DatabaseType::InfluxDB,
+        "cassandra" => DatabaseType::Cassandra,
+        "sqlite" => DatabaseType::SQLite,
+        "duckdb" => DatabaseType::DuckDB,
         _ => return Err("Unsupported database type".to_string()),
     };
 
+    if params.backend == BackendKind::External {
+        return Err("Externally managed databases cannot be installed".to_string());
+    }
+
     // 获取存储路径
     let settings = state.get_settings();
     let storage_path = std::path::PathBuf::from(&settings.default_storage_path);
 
+    // 设置中选择使用系统包管理器时，本机安装改由包管理器完成
+    let backend_kind = if params.backend == BackendKind::Native
+        && archive.is_none()
+        && settings.use_package_manager
+        && backend::supports_package_install(&db_type)
+    {
+        BackendKind::Package
+    } else if params.backend == BackendKind::Native
+        && settings.use_launch_agents
+        && cfg!(target_os = "macos")
+        && !matches!(db_type, DatabaseType::SQLite | DatabaseType::DuckDB)
+    {
+        BackendKind::LaunchAgent
+    } else {
+        params.backend
+    };
+
+    // 离线安装只用于本机安装，版本未指定时从发行包文件名识别
+    if let Some(archive) = &archive {
+        if params.backend != BackendKind::Native {
+            return Err("Only native installs can use a local archive".to_string());
+        }
+        let version = offline::check_archive(&db_type, archive, params.version.as_deref())
+            .map_err(|e| e.to_string())?;
+        params.version = Some(version);
+    }
+
+    // 资源配置预设写入本机安装生成的配置文件
+    if let Some(preset) = params.preset {
+        if !matches!(backend_kind, BackendKind::Native | BackendKind::LaunchAgent) {
+            return Err("Configuration presets are only supported for native installs".to_string());
+        }
+        preset::preset_overrides(&db_type, preset).map_err(|e| e.to_string())?;
+    }
+
+    // 同类型已有实例时需要实例名，数据和日志目录按实例名区分
+    let instance_name = params
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(String::from);
+    let existing: Vec<DatabaseInfo> = state
+        .get_all_databases()
+        .into_iter()
+        .filter(|db| db.db_type == db_type)
+        .collect();
+    if !existing.is_empty() {
+        if backend_kind == BackendKind::Package
+            && existing.iter().any(|db| db.backend == BackendKind::Package)
+        {
+            return Err(format!(
+                "Only one {} instance can be installed through the package manager",
+                db_type.display_name()
+            ));
+        }
+
+        #[cfg(target_os = "macos")]
+        if params.backend == BackendKind::Native
+            && !crate::core::macos::supports_multiple_instances(&db_type)
+        {
+            // 不同版本的 formula（如 postgresql@16 和 postgresql@18）可以并存
+            let formula = versions::homebrew_formula(&db_type, params.version.as_deref());
+            let formula_in_use = match &formula {
+                Ok(formula) => existing.iter().any(|db| {
+                    versions::homebrew_formula(&db.db_type, Some(&db.version))
+                        .is_ok_and(|other| other == *formula)
+                }),
+                Err(_) => true,
+            };
+            if formula_in_use {
+                return Err(format!(
+                    "Only one {} instance of each version is supported on macOS",
+                    db_type.display_name()
+                ));
+            }
+        }
+
+        let name = instance_name.as_deref().ok_or_else(|| {
+            format!(
+                "An instance name is required to install another {} instance",
+                db_type.display_name()
+            )
+        })?;
+        let data_path = crate::core::utils::get_db_data_path(
+            &storage_path,
+            &crate::core::utils::get_instance_dir_name(db_type.as_str(), Some(name)),
+        );
+        if existing
+            .iter()
+            .any(|db| db.name == name || std::path::Path::new(&db.data_path) == data_path)
+        {
+            return Err(format!("Instance name already exists: {}", name));
+        }
+    }
+
+    // 未指定端口且已有同类型实例时，自动选择下一个可用端口
+    let port = match params.port {
+        Some(port) => {
+            if let Some(other) = state.find_database_by_port(port) {
+                return Err(format!("Port {} is already used by {}", port, other.name));
+            }
+            Some(port)
+        }
+        // SQLite 没有服务进程，不占用端口
+        None if db_type == DatabaseType::SQLite => None,
+        // 系统软件包使用默认端口
+        None if backend_kind == BackendKind::Package => None,
+        None if !existing.is_empty() => Some(
+            next_available_port(state, &db_type)
+                .ok_or_else(|| "No available port found".to_string())?,
+        ),
+        None => None,
+    };
+
+    // 根据数据库类型设置默认凭据
+    let (default_user, default_pass) = match db_type {
+        DatabaseType::Redis
+        | DatabaseType::Qdrant
+        | DatabaseType::OpenSearch
+        | DatabaseType::Cassandra
+        | DatabaseType::SQLite
+        | DatabaseType::DuckDB => (None, None),
+        _ => (
+            Some("admin".to_string()),
+            Some(password::DEFAULT_PASSWORD.to_string()),
+        ),
+    };
+
+    // 设置默认用户名和密码
+    let username_param = params.username.clone().or(default_user);
+    let password_param = params.password.clone().or(default_pass);
+
+    Ok(InstallPlan {
+        db_type,
+        backend: backend_kind,
+        storage_path,
+        version: params.version.clone(),
+        port,
+        instance_name,
+        username: username_param,
+        password: password_param,
+        preset: params.preset,
+        env: params.env.clone(),
+        extra_args: params.extra_args.clone(),
+        archive,
+    })
+}
+
+/// 创建安装任务并在后台线程中安装，返回任务 ID
+fn spawn_install(
+    state: State<AppState>,
+    mut params: InstallDatabaseParams,
+    archive: Option<PathBuf>,
+    _app_handle: tauri::AppHandle,
+) -> Result<String, CommandError> {
+    let plan = prepare_install(&state, &mut params, archive.clone())?;
+
     // 创建任务ID
     let task_id = format!(
         "install-{}-{}",
@@ -171,39 +825,51 @@ pub fn install_database(
         progress: 0,
         message: "Installation pending...".to_string(),
         error: None,
+        error_code: None,
+        queue_position: None,
         created_at: crate::core::utils::get_timestamp(),
         updated_at: crate::core::utils::get_timestamp(),
     };
     state.add_task(task.clone());
+    // 在提交时排队，保证按提交顺序安装
+    state.enqueue_install(&task_id);
+    // 保存参数，失败后可以重试
+    state.save_install_request(
+        &task_id,
+        InstallRequest {
+            params: params.clone(),
+            archive: archive.clone(),
+        },
+    );
 
-    // 克隆需要在线程中使用的数据
-    let db_type_clone = db_type.clone();
-    let storage_path_clone = storage_path.clone();
-    let version_param = params.version.clone();
-    let port_param = params.port;
-
-    // 根据数据库类型设置默认凭据
-    let (default_user, default_pass) = match db_type {
-        DatabaseType::Redis | DatabaseType::Qdrant => (None, None),
-        _ => (Some("admin".to_string()), Some("admin888".to_string())),
-    };
-
-    // 设置默认用户名和密码
-    let username_param = params.username.clone().or(default_user);
-    let password_param = params.password.clone().or(default_pass);
-
-    // 克隆 Arc 指针以在线程中使用
-    let databases_arc = state.databases.clone();
-    let settings_arc = state.settings.clone();
+    // 克隆应用状态以在线程中使用
+    let app_state = state.inner().clone();
     let tasks_arc = state.tasks.clone();
     let task_id_for_thread = task_id.clone();
     let app_handle_clone = _app_handle.clone();
 
-    // 启动线程执行安装
-    std::thread::spawn(move || {
+    // 在后台线程池中执行安装
+    tauri::async_runtime::spawn_blocking(move || {
         use tauri::Emitter;
         let task_id_clone = task_id_for_thread.clone();
 
+        // 等待前面的安装完成，避免同时写存储目录和占满带宽；结束时自动让出
+        let _install_slot = app_state.wait_install_slot(&task_id_clone);
+
+        // 下载前检查磁盘空间和端口，前面的安装可能已占用空间或端口，因此在开始执行时检查
+        if let Err(e) = plan.preflight() {
+            let mut tasks = tasks_arc.lock().unwrap();
+            if let Some(task) = tasks.get_mut(&task_id_clone) {
+                task.status = crate::core::TaskStatus::Failed;
+                task.error = Some(format!("{:#}", e));
+                task.error_code = AppError::of(&e);
+                task.message = "Pre-install check failed".to_string();
+                task.updated_at = crate::core::utils::get_timestamp();
+                let _ = app_handle_clone.emit("install-progress", task.clone());
+            }
+            return;
+        }
+
         // 模拟安装进度的函数
         let update_progress = |progress: u8, message: &str| {
             let mut tasks = tasks_arc.lock().unwrap();
@@ -227,12 +893,12 @@ pub fn install_database(
         let progress_stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
         let progress_stop_clone = progress_stop.clone();
 
-        std::thread::spawn(move || {
+        tauri::async_runtime::spawn(async move {
             let mut current_progress = 0;
             while !progress_stop_clone.load(std::sync::atomic::Ordering::Relaxed)
                 && current_progress < 98
             {
-                std::thread::sleep(std::time::Duration::from_secs(1));
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
                 if progress_stop_clone.load(std::sync::atomic::Ordering::Relaxed) {
                     break;
                 }
@@ -257,261 +923,1183 @@ pub fn install_database(
             }
         });
 
-        #[cfg(target_os = "macos")]
-        let install_result: Result<crate::core::DatabaseInfo, String> = {
-            let options = crate::core::macos::HomebrewInstallOptions {
-                version: version_param.as_deref(),
-                port: port_param,
-                username: username_param.as_deref(),
-                password: password_param.as_deref(),
-                auto_start: true,
-            };
+        let install_result = install_with_plan(&app_state, plan);
+
+        // 停止进度模拟
+        progress_stop.store(true, std::sync::atomic::Ordering::Relaxed);
+
+        // 处理安装结果
+        match install_result {
+            Ok(db_info) => match app_state.add_database(db_info) {
+                Ok(()) => {
+                    use tauri::Emitter;
+                    app_state.remove_install_request(&task_id_clone);
+                    let mut tasks = tasks_arc.lock().unwrap();
+                    if let Some(task) = tasks.get_mut(&task_id_clone) {
+                        task.status = crate::core::TaskStatus::Completed;
+                        task.progress = 100;
+                        task.message = "Installation completed successfully".to_string();
+                        task.updated_at = crate::core::utils::get_timestamp();
+                        let _ = app_handle_clone.emit("install-progress", task.clone());
+                    }
+                }
+                Err(e) => {
+                    use tauri::Emitter;
+                    let mut tasks = tasks_arc.lock().unwrap();
+                    if let Some(task) = tasks.get_mut(&task_id_clone) {
+                        task.status = crate::core::TaskStatus::Failed;
+                        task.error = Some(format!("Failed to add database to state: {}", e));
+                        task.message = "Installation completed but failed to register".to_string();
+                        task.updated_at = crate::core::utils::get_timestamp();
+                        let _ = app_handle_clone.emit("install-progress", task.clone());
+                    }
+                }
+            },
+            Err(e) => {
+                use tauri::Emitter;
+                let mut tasks = tasks_arc.lock().unwrap();
+                if let Some(task) = tasks.get_mut(&task_id_clone) {
+                    task.status = crate::core::TaskStatus::Failed;
+                    task.error = Some(format!("{:#}", e));
+                    task.error_code = AppError::of(&e);
+                    task.message = "Installation failed".to_string();
+                    task.updated_at = crate::core::utils::get_timestamp();
+                    let _ = app_handle_clone.emit("install-progress", task.clone());
+                }
+            }
+        }
+    });
+
+    Ok(task_id)
+}
+
+/// 按安装计划安装数据库，然后应用资源预设和启动参数并完成首次初始化，不注册到应用状态
+///
+/// 安装后的步骤失败时只记录警告，不影响安装结果。
+pub fn install_with_plan(state: &AppState, plan: InstallPlan) -> anyhow::Result<DatabaseInfo> {
+    let options = backend::InstallOptions {
+        version: plan.version.as_deref(),
+        port: plan.port,
+        instance_name: plan.instance_name.as_deref(),
+        username: plan.username.as_deref(),
+        password: plan.password.as_deref(),
+        auto_start: true,
+        archive: plan.archive.as_deref(),
+    };
+    let mut db_info = backend::backend_for(&plan.db_type, plan.backend)
+        .install(&plan.db_type, &plan.storage_path, &options)
+        .map_err(|e| e.context("Installation failed"))?;
+
+    // 预设应用失败时保留默认配置，不影响安装结果
+    if let Some(preset) = plan.preset {
+        let result = apply_preset_to(state, &mut db_info, preset, true);
+        if !result.success {
+            log::warn!(
+                "Failed to apply {:?} preset to {}: {}",
+                preset,
+                db_info.name,
+                result.message
+            );
+        }
+    }
+
+    // 自定义启动参数在安装后设置，需要时重启使其生效
+    if !plan.env.is_empty() || !plan.extra_args.is_empty() {
+        let result =
+            state
+                .db_manager
+                .update_launch_options(&mut db_info, plan.env, plan.extra_args, true);
+        match result {
+            Ok(result) if result.success => {}
+            Ok(result) => log::warn!(
+                "Failed to apply launch options to {}: {}",
+                db_info.name,
+                result.message
+            ),
+            Err(e) => log::warn!(
+                "Failed to apply launch options to {}: {:#}",
+                db_info.name,
+                e
+            ),
+        }
+    }
+
+    // 新的 SurrealDB 实例没有命名空间和数据库，创建默认值方便直接使用
+    if db_info.db_type == DatabaseType::SurrealDB && db_info.status == DatabaseStatus::Running {
+        if let Err(e) = surreal::initialize(&db_info) {
+            log::warn!("Failed to initialize {}: {:#}", db_info.name, e);
+        }
+    }
+
+    // 初始化后只有空密码的 root，设置为实例信息中保存的凭据
+    if matches!(db_info.db_type, DatabaseType::MySQL | DatabaseType::MariaDB)
+        && db_info.backend == BackendKind::Native
+        && db_info.status == DatabaseStatus::Running
+    {
+        if let Err(e) = provision::initialize_mysql_credentials(&db_info) {
+            log::warn!("Failed to set credentials for {}: {:#}", db_info.name, e);
+        }
+    }
+
+    Ok(db_info)
+}
+
+/// 从默认端口开始查找未被其它实例占用且可以监听的端口
+fn next_available_port(state: &AppState, db_type: &DatabaseType) -> Option<u16> {
+    // Qdrant 同时占用 HTTP 端口和其后的 gRPC 端口
+    let step = match db_type {
+        DatabaseType::Qdrant => 2,
+        _ => 1,
+    };
+    (db_type.default_port()..u16::MAX)
+        .step_by(step)
+        .find(|&port| {
+            state.find_database_by_port(port).is_none()
+                && crate::core::port::is_port_available(port)
+        })
+}
+
+/// 更新数据库自启动设置
+#[tauri::command]
+pub fn update_database_autostart(
+    state: State<AppState>,
+    id: String,
+    auto_start: bool,
+) -> OperationResult<()> {
+    match state.get_database(&id) {
+        Some(mut db_info) => {
+            db_info.auto_start = auto_start;
+            db_info.updated_at = crate::core::utils::get_timestamp();
+            state.update_database(db_info);
+            OperationResult::success("Autostart setting updated", None)
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    }
+}
+
+/// 开启或关闭按需启动，关闭时立即释放代为监听的端口
+#[tauri::command]
+pub fn update_database_on_demand(
+    state: State<AppState>,
+    id: String,
+    on_demand: bool,
+) -> OperationResult<()> {
+    match state.get_database(&id) {
+        Some(mut db_info) => {
+            if on_demand && !activation::supports(&db_info) {
+                return OperationResult::error(format!(
+                    "{} cannot be started on demand",
+                    db_info.name
+                ));
+            }
+            if !on_demand {
+                state.db_manager.activator().release(&id);
+            }
+            db_info.on_demand = on_demand;
+            db_info.updated_at = crate::core::utils::get_timestamp();
+            state.update_database(db_info);
+            OperationResult::success("On-demand start setting updated", None)
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    }
+}
+
+/// 更新数据库的定时启停计划
+#[tauri::command]
+pub fn update_database_schedules(
+    state: State<AppState>,
+    id: String,
+    schedules: Vec<DatabaseSchedule>,
+) -> OperationResult<()> {
+    if let Err(e) = schedule::validate(&schedules) {
+        return OperationResult::error(format!("{:#}", e));
+    }
+    match state.get_database(&id) {
+        Some(mut db_info) => {
+            db_info.schedules = schedules;
+            db_info.updated_at = crate::core::utils::get_timestamp();
+            state.update_database(db_info);
+            OperationResult::success("Schedules updated", None)
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    }
+}
+
+/// 更新数据库依赖，存在循环依赖或依赖不存在时拒绝修改
+#[tauri::command]
+pub fn update_database_dependencies(
+    state: State<AppState>,
+    id: String,
+    depends_on: Vec<String>,
+) -> OperationResult<()> {
+    let mut depends_on = depends_on;
+    depends_on.sort();
+    depends_on.dedup();
+
+    let validation = {
+        let databases = state.databases.lock().unwrap();
+        dependency::validate_dependencies(&databases, &id, &depends_on)
+    };
+    if let Err(e) = validation {
+        return OperationResult::error(format!("{:#}", e));
+    }
+
+    match state.get_database(&id) {
+        Some(mut db_info) => {
+            db_info.depends_on = depends_on;
+            db_info.updated_at = crate::core::utils::get_timestamp();
+            state.update_database(db_info);
+            OperationResult::success("Dependencies updated", None)
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    }
+}
+
+/// 在 MySQL / PostgreSQL 实例上执行一条 SQL，返回结果行或影响的行数
+#[tauri::command]
+pub async fn execute_query(
+    state: State<'_, AppState>,
+    id: String,
+    sql: String,
+    database: Option<String>,
+    row_limit: Option<usize>,
+    timeout_secs: Option<u64>,
+) -> Result<OperationResult<QueryResult>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    let timeout = timeout_secs
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(query::DEFAULT_QUERY_TIMEOUT);
+    run_blocking(move || {
+        match query::execute(
+            &db_info,
+            database.as_deref(),
+            &sql,
+            row_limit.unwrap_or(query::DEFAULT_ROW_LIMIT),
+            timeout,
+        ) {
+            Ok(result) => OperationResult::success("Query executed", Some(result)),
+            Err(e) => OperationResult::error(format!("{:#}", e)),
+        }
+    })
+    .await
+}
+
+/// 读取 MySQL / PostgreSQL 实例的慢查询日志，未启用时先启用，`since` 为 Unix 时间戳（秒）
+#[tauri::command]
+pub async fn get_slow_queries(
+    state: State<'_, AppState>,
+    id: String,
+    since: Option<i64>,
+) -> Result<OperationResult<slow_query::SlowQueryLog>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    run_blocking(move || match slow_query::read(&db_info, since) {
+        Ok(log) if log.restart_required => OperationResult::success(
+            "Slow query logging enabled, restart the database to write it to a log file",
+            Some(log),
+        ),
+        Ok(log) if log.newly_enabled => {
+            OperationResult::success("Slow query logging enabled", Some(log))
+        }
+        Ok(log) => OperationResult::success("Slow queries loaded", Some(log)),
+        Err(e) => OperationResult::error(format!("Failed to read slow queries: {:#}", e)),
+    })
+    .await
+}
+
+/// 获取 MySQL / PostgreSQL 实例中总耗时最高的查询，统计未启用时修改配置，重启数据库后生效
+#[tauri::command]
+pub async fn get_query_stats(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<query_stats::QueryStats>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    let storage_path = state.db_manager.storage_path();
+    run_blocking(
+        move || match query_stats::collect(&storage_path, &db_info) {
+            Ok(stats) if stats.restart_required => OperationResult::success(
+                "Query statistics enabled, restart the database to start collecting",
+                Some(stats),
+            ),
+            Ok(stats) => OperationResult::success("Query statistics loaded", Some(stats)),
+            Err(e) => OperationResult::error(format!("Failed to read query statistics: {:#}", e)),
+        },
+    )
+    .await
+}
+
+/// 列出 PostgreSQL 实例可以安装的扩展，`database` 为空时查看默认数据库
+#[tauri::command]
+pub async fn list_available_extensions(
+    state: State<'_, AppState>,
+    id: String,
+    database: Option<String>,
+) -> Result<OperationResult<Vec<pg_extension::PgExtension>>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    run_blocking(
+        move || match pg_extension::list(&db_info, database.as_deref()) {
+            Ok(extensions) => OperationResult::success("Extensions loaded", Some(extensions)),
+            Err(e) => OperationResult::error(format!("Failed to list extensions: {:#}", e)),
+        },
+    )
+    .await
+}
+
+/// 在 PostgreSQL 数据库中创建扩展，pgvector 和 PostGIS 不在安装目录中时先下载，返回扩展版本
+#[tauri::command]
+pub async fn install_extension(
+    state: State<'_, AppState>,
+    id: String,
+    name: String,
+    database: Option<String>,
+) -> Result<OperationResult<String>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    run_blocking(
+        move || match pg_extension::install(&db_info, database.as_deref(), &name) {
+            Ok(version) => OperationResult::success(
+                format!("Extension {} {} installed", name, version),
+                Some(version),
+            ),
+            Err(e) => {
+                OperationResult::error(format!("Failed to install extension {}: {:#}", name, e))
+            }
+        },
+    )
+    .await
+}
+
+/// 在 Redis 实例上执行一条原始命令，`args` 的第一项为命令名
+#[tauri::command]
+pub async fn execute_redis_command(
+    state: State<'_, AppState>,
+    id: String,
+    args: Vec<String>,
+    database: Option<u32>,
+) -> Result<OperationResult<RedisReply>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+    if db_info.db_type != DatabaseType::Redis {
+        return Ok(OperationResult::error("Not a Redis database"));
+    }
+
+    run_blocking(move || {
+        match redis::execute(&db_info, database, &args, redis::DEFAULT_COMMAND_TIMEOUT) {
+            Ok(reply) => OperationResult::success("Command executed", Some(reply)),
+            Err(e) => OperationResult::error(format!("{:#}", e)),
+        }
+    })
+    .await
+}
+
+/// 分页浏览 Redis 的键，`cursor` 为上一页返回的游标，首页不传
+#[tauri::command]
+pub async fn scan_redis_keys(
+    state: State<'_, AppState>,
+    id: String,
+    cursor: Option<u64>,
+    pattern: Option<String>,
+    count: Option<usize>,
+    database: Option<u32>,
+) -> Result<OperationResult<RedisKeyPage>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+    if db_info.db_type != DatabaseType::Redis {
+        return Ok(OperationResult::error("Not a Redis database"));
+    }
+
+    run_blocking(move || {
+        match redis::scan_keys(
+            &db_info,
+            database,
+            cursor.unwrap_or(0),
+            pattern.as_deref(),
+            count.unwrap_or(redis::DEFAULT_SCAN_COUNT),
+            redis::DEFAULT_COMMAND_TIMEOUT,
+        ) {
+            Ok(page) => OperationResult::success("Keys scanned", Some(page)),
+            Err(e) => OperationResult::error(format!("{:#}", e)),
+        }
+    })
+    .await
+}
+
+/// 列出 MongoDB 实例中的数据库
+#[tauri::command]
+pub async fn list_mongo_databases(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<Vec<MongoDatabaseInfo>>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+    if db_info.db_type != DatabaseType::MongoDB {
+        return Ok(OperationResult::error("Not a MongoDB database"));
+    }
+
+    run_blocking(move || match mongo::list_databases(&db_info) {
+        Ok(databases) => OperationResult::success("Databases listed", Some(databases)),
+        Err(e) => OperationResult::error(format!("{:#}", e)),
+    })
+    .await
+}
+
+/// 列出 MongoDB 数据库中的集合及文档数
+#[tauri::command]
+pub async fn list_mongo_collections(
+    state: State<'_, AppState>,
+    id: String,
+    database: String,
+) -> Result<OperationResult<Vec<MongoCollectionInfo>>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+    if db_info.db_type != DatabaseType::MongoDB {
+        return Ok(OperationResult::error("Not a MongoDB database"));
+    }
+
+    run_blocking(move || match mongo::list_collections(&db_info, &database) {
+        Ok(collections) => OperationResult::success("Collections listed", Some(collections)),
+        Err(e) => OperationResult::error(format!("{:#}", e)),
+    })
+    .await
+}
+
+/// 在 MongoDB 数据库上执行 JSON 格式的数据库命令，如 `{"dbStats": 1}`
+#[tauri::command]
+pub async fn run_mongo_command(
+    state: State<'_, AppState>,
+    id: String,
+    database: String,
+    command: String,
+) -> Result<OperationResult<serde_json::Value>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+    if db_info.db_type != DatabaseType::MongoDB {
+        return Ok(OperationResult::error("Not a MongoDB database"));
+    }
+
+    run_blocking(
+        move || match mongo::run_command(&db_info, &database, &command) {
+            Ok(result) => OperationResult::success("Command executed", Some(result)),
+            Err(e) => OperationResult::error(format!("{:#}", e)),
+        },
+    )
+    .await
+}
+
+/// 在 SurrealDB 实例上执行 SurrealQL，返回每条语句的结果
+///
+/// 未指定命名空间和数据库时使用安装时创建的默认值，传空字符串则不选择。
+#[tauri::command]
+pub async fn execute_surreal_query(
+    state: State<'_, AppState>,
+    id: String,
+    ns: Option<String>,
+    db: Option<String>,
+    query: String,
+) -> Result<OperationResult<Vec<SurrealStatementResult>>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    let ns = ns.unwrap_or_else(|| surreal::DEFAULT_NAMESPACE.to_string());
+    let db = db.unwrap_or_else(|| surreal::DEFAULT_DATABASE.to_string());
+    run_blocking(move || {
+        match surreal::execute(
+            &db_info,
+            Some(&ns),
+            Some(&db),
+            &query,
+            surreal::DEFAULT_QUERY_TIMEOUT,
+        ) {
+            Ok(results) => OperationResult::success("Query executed", Some(results)),
+            Err(e) => OperationResult::error(format!("{:#}", e)),
+        }
+    })
+    .await
+}
+
+/// 列出实例中的数据库、模式和表（集合）及估算行数，用于实例概览
+#[tauri::command]
+pub async fn get_database_objects(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<Vec<DatabaseObject>>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+    if db_info.status != DatabaseStatus::Running {
+        return Ok(OperationResult::error(format!(
+            "{} must be running to list objects",
+            db_info.name
+        )));
+    }
+
+    run_blocking(move || match schema::list_objects(&db_info) {
+        Ok(objects) => OperationResult::success("Objects listed", Some(objects)),
+        Err(e) => OperationResult::error(format!("{:#}", e)),
+    })
+    .await
+}
+
+/// 在实例上创建一个数据库（MySQL / PostgreSQL）
+#[tauri::command]
+pub async fn create_logical_database(
+    state: State<'_, AppState>,
+    id: String,
+    name: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    run_blocking(move || match provision::create_database(&db_info, &name) {
+        Ok(()) => OperationResult::success(format!("Database {} created", name), None),
+        Err(e) => OperationResult::error(format!("{:#}", e)),
+    })
+    .await
+}
+
+/// 在实例上创建用户，并授予 `grants` 中各数据库的全部权限（MySQL / PostgreSQL / MongoDB）
+#[tauri::command]
+pub async fn create_user(
+    state: State<'_, AppState>,
+    id: String,
+    username: String,
+    password: String,
+    grants: Option<Vec<String>>,
+) -> Result<OperationResult<()>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    let grants = grants.unwrap_or_default();
+    run_blocking(
+        move || match provision::create_user(&db_info, &username, &password, &grants) {
+            Ok(()) => OperationResult::success(format!("User {} created", username), None),
+            Err(e) => OperationResult::error(format!("{:#}", e)),
+        },
+    )
+    .await
+}
+
+/// 把 .sql / .sql.gz 转储导入到实例，返回任务ID，进度通过 import-progress 事件发送
+#[tauri::command]
+pub async fn import_dump(
+    state: State<'_, AppState>,
+    id: String,
+    path: String,
+    target_db: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, CommandError> {
+    let db_info = state.get_database(&id).ok_or(AppError::DatabaseNotFound)?;
+    let path = PathBuf::from(path);
+    if !path.is_file() {
+        return Err(format!("Dump file not found: {}", path.display()).into());
+    }
+
+    let task_id = format!(
+        "import-{}-{}",
+        db_info.db_type.as_str(),
+        crate::core::utils::generate_id()
+    );
+    state.add_task(crate::core::AsyncTask {
+        id: task_id.clone(),
+        task_type: "import".to_string(),
+        db_type: db_info.db_type.as_str().to_string(),
+        status: crate::core::TaskStatus::Running,
+        progress: 0,
+        message: format!("Importing {}...", path.display()),
+        error: None,
+        error_code: None,
+        queue_position: None,
+        created_at: crate::core::utils::get_timestamp(),
+        updated_at: crate::core::utils::get_timestamp(),
+    });
+
+    let tasks = state.tasks.clone();
+    let task_id_for_thread = task_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        use tauri::Emitter;
+        let update_task = |update: &dyn Fn(&mut crate::core::AsyncTask)| {
+            let mut tasks = tasks.lock().unwrap();
+            if let Some(task) = tasks.get_mut(&task_id_for_thread) {
+                update(task);
+                task.updated_at = crate::core::utils::get_timestamp();
+                let _ = app_handle.emit("import-progress", task.clone());
+            }
+        };
+
+        let result =
+            crate::core::import::import_dump(&db_info, &path, target_db.as_deref(), |progress| {
+                update_task(&|task| task.progress = progress)
+            });
+        match result {
+            Ok(()) => update_task(&|task| {
+                task.status = crate::core::TaskStatus::Completed;
+                task.progress = 100;
+                task.message = "Import completed successfully".to_string();
+            }),
+            Err(e) => update_task(&|task| {
+                task.status = crate::core::TaskStatus::Failed;
+                task.error = Some(format!("{:#}", e));
+                task.error_code = AppError::of(&e);
+                task.message = "Import failed".to_string();
+            }),
+        }
+    });
+
+    Ok(task_id)
+}
+
+/// 向实例写入内置的示例数据集，便于演示和验证新安装的实例
+#[tauri::command]
+pub async fn seed_sample_data(
+    state: State<'_, AppState>,
+    id: String,
+    dataset: SampleDataset,
+) -> Result<OperationResult<()>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    run_blocking(move || match sample_data::seed(&db_info, dataset) {
+        Ok(location) => {
+            OperationResult::success(format!("Sample data loaded into {}", location), None)
+        }
+        Err(e) => OperationResult::error(format!("{:#}", e)),
+    })
+    .await
+}
+
+/// 对数据库执行协议级健康检查
+#[tauri::command]
+pub async fn check_database_health(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<HealthCheckResult>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+
+    let name = db_info.name.clone();
+    let result =
+        run_blocking(move || health::check_health(&db_info, health::DEFAULT_HEALTH_TIMEOUT))
+            .await?;
+    if result.healthy {
+        Ok(OperationResult::success(
+            format!("{} is healthy", name),
+            Some(result),
+        ))
+    } else {
+        Ok(OperationResult {
+            success: false,
+            message: format!("{} is unhealthy", name),
+            data: Some(result),
+            error: None,
+        })
+    }
+}
+
+/// 使用给定凭据测试连接，未提供时使用已保存的凭据，成功时返回服务端版本
+#[tauri::command]
+pub async fn test_connection(
+    state: State<'_, AppState>,
+    id: String,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<OperationResult<String>, CommandError> {
+    let Some(mut db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+    if username.is_some() {
+        db_info.username = username;
+    }
+    if password.is_some() {
+        db_info.password = password;
+    }
+
+    run_blocking(move || match connection::test_connection(&db_info) {
+        Ok(version) => OperationResult::success(
+            format!("Connected to {} successfully", db_info.name),
+            version,
+        ),
+        Err(e) => OperationResult::error(format!("Connection failed: {:#}", e)),
+    })
+    .await
+}
+
+/// 生成连接字符串，`format` 默认为数据库原生 URI
+#[tauri::command]
+pub fn get_connection_uri(
+    state: State<AppState>,
+    id: String,
+    format: Option<ConnectionUriFormat>,
+) -> OperationResult<String> {
+    match state.get_database(&id) {
+        Some(db_info) => match connection::connection_uri(&db_info, format.unwrap_or_default()) {
+            Ok(uri) => OperationResult::success("Connection URI generated", Some(uri)),
+            Err(e) => OperationResult::error(e.to_string()),
+        },
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    }
+}
 
-            crate::core::macos::install_database_via_homebrew(
-                &db_type_clone,
-                &storage_path_clone,
-                &options,
-            )
-            .map_err(|e| format!("Installation failed: {}", e))
+/// 将连接字符串、密码或 `.env` 连接变量复制到剪贴板
+///
+/// `clear_after_secs` 秒后剪贴板内容未被替换时自动清空，避免凭据长时间留在剪贴板中。
+#[tauri::command]
+pub async fn copy_connection_info(
+    state: State<'_, AppState>,
+    id: String,
+    what: ConnectionInfoKind,
+    clear_after_secs: Option<u64>,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || {
+        let Some(db_info) = state.get_database(&id) else {
+            return OperationResult::failure(AppError::DatabaseNotFound);
         };
-
-        #[cfg(target_os = "windows")]
-        let install_result: Result<crate::core::DatabaseInfo, String> = {
-            let options = crate::core::windows::WindowsInstallOptions {
-                version: version_param.as_deref(),
-                port: port_param,
-                username: username_param.as_deref(),
-                password: password_param.as_deref(),
-                auto_start: true,
-            };
-
-            crate::core::windows::install_database(&db_type_clone, &storage_path_clone, &options)
-                .map_err(|e| format!("Installation failed: {}", e))
+        let text = match what {
+            ConnectionInfoKind::Uri => {
+                connection::connection_uri(&db_info, ConnectionUriFormat::Native)
+            }
+            ConnectionInfoKind::Password => match db_info.password.clone() {
+                Some(password) if !password.is_empty() => Ok(password),
+                _ => return OperationResult::error(format!("{} has no password", db_info.name)),
+            },
+            ConnectionInfoKind::Env => connection::env_block(&db_info),
         };
-
-        #[cfg(target_os = "linux")]
-        let install_result: Result<crate::core::DatabaseInfo, String> = {
-            let options = crate::core::linux::LinuxInstallOptions {
-                version: version_param.as_deref(),
-                port: port_param,
-                username: username_param.as_deref(),
-                password: password_param.as_deref(),
-                auto_start: true,
-            };
-
-            crate::core::linux::install_database(&db_type_clone, &storage_path_clone, &options)
-                .map_err(|e| format!("Installation failed: {}", e))
+        let text = match text {
+            Ok(text) => text,
+            Err(e) => return OperationResult::error(e.to_string()),
         };
-
-        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-        let install_result: Result<crate::core::DatabaseInfo, String> = {
-            // Create database info - simulate installation completion
-            Ok(crate::core::DatabaseInfo {
-                id: crate::core::utils::generate_id(),
-                name: db_type_clone.display_name().to_string(),
-                db_type: db_type_clone.clone(),
-                version: version_param.unwrap_or_else(|| "latest".to_string()),
-                install_path: storage_path_clone
-                    .join("bin")
-                    .join(db_type_clone.as_str())
-                    .to_string_lossy()
-                    .to_string(),
-                data_path: crate::core::utils::get_db_data_path(
-                    &storage_path_clone,
-                    db_type_clone.as_str(),
-                )
-                .to_string_lossy()
-                .to_string(),
-                log_path: crate::core::utils::get_db_log_path(
-                    &storage_path_clone,
-                    db_type_clone.as_str(),
+        if let Err(e) = clipboard::write(&text) {
+            return OperationResult::error(format!("Failed to copy: {:#}", e));
+        }
+        match clear_after_secs.filter(|secs| *secs > 0) {
+            Some(secs) => {
+                clipboard::clear_after(text, Duration::from_secs(secs));
+                OperationResult::success(
+                    format!("Copied, the clipboard will be cleared in {} seconds", secs),
+                    None,
                 )
-                .to_string_lossy()
-                .to_string(),
-                port: port_param.unwrap_or_else(|| db_type_clone.default_port()),
-                username: username_param,
-                password: password_param,
-                config: None,
-                status: crate::core::DatabaseStatus::Stopped,
-                auto_start: false,
-                pid: None,
-                created_at: crate::core::utils::get_timestamp(),
-                updated_at: crate::core::utils::get_timestamp(),
-            })
-        };
-
-        // 停止进度模拟
-        progress_stop.store(true, std::sync::atomic::Ordering::Relaxed);
-
-        // 处理安装结果
-        match install_result {
-            Ok(db_info) => {
-                // 添加数据库到状态
-                let add_result = {
-                    let mut databases = databases_arc.lock().unwrap();
-                    if databases.contains_key(&db_info.id) {
-                        Err(format!("Database id already exists: {}", db_info.id))
-                    } else {
-                        let new_type: crate::core::DatabaseType = db_info.db_type.clone();
-                        if let Some((existing_id, _)) = databases
-                            .iter()
-                            .find(|(_, existing_db)| existing_db.db_type == new_type)
-                        {
-                            Err(format!(
-                                "Database type already exists: {} (id={})",
-                                new_type.as_str(),
-                                existing_id
-                            ))
-                        } else {
-                            databases.insert(db_info.id.clone(), db_info.clone());
-                            // 保存状态
-                            drop(databases);
-                            save_app_state(&settings_arc, &databases_arc)
-                        }
-                    }
-                };
-
-                match add_result {
-                    Ok(_) => {
-                        use tauri::Emitter;
-                        let mut tasks = tasks_arc.lock().unwrap();
-                        if let Some(task) = tasks.get_mut(&task_id_clone) {
-                            task.status = crate::core::TaskStatus::Completed;
-                            task.progress = 100;
-                            task.message = "Installation completed successfully".to_string();
-                            task.updated_at = crate::core::utils::get_timestamp();
-                            let _ = app_handle_clone.emit("install-progress", task.clone());
-                        }
-                    }
-                    Err(e) => {
-                        use tauri::Emitter;
-                        let mut tasks = tasks_arc.lock().unwrap();
-                        if let Some(task) = tasks.get_mut(&task_id_clone) {
-                            task.status = crate::core::TaskStatus::Failed;
-                            task.error = Some(format!("Failed to add database to state: {}", e));
-                            task.message =
-                                "Installation completed but failed to register".to_string();
-                            task.updated_at = crate::core::utils::get_timestamp();
-                            let _ = app_handle_clone.emit("install-progress", task.clone());
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                use tauri::Emitter;
-                let mut tasks = tasks_arc.lock().unwrap();
-                if let Some(task) = tasks.get_mut(&task_id_clone) {
-                    task.status = crate::core::TaskStatus::Failed;
-                    task.error = Some(e);
-                    task.message = "Installation failed".to_string();
-                    task.updated_at = crate::core::utils::get_timestamp();
-                    let _ = app_handle_clone.emit("install-progress", task.clone());
-                }
             }
+            None => OperationResult::success("Copied", None),
         }
-    });
-
-    Ok(task_id)
+    })
+    .await
 }
 
-/// 保存应用状态辅助函数
-fn save_app_state(
-    settings: &std::sync::Arc<std::sync::Mutex<crate::core::GlobalSettings>>,
-    databases: &std::sync::Arc<
-        std::sync::Mutex<std::collections::HashMap<String, crate::core::DatabaseInfo>>,
-    >,
-) -> Result<(), String> {
-    fn get_state_path() -> std::path::PathBuf {
-        dirs::home_dir()
-            .map(|p| p.join(".local-db").join("state.json"))
-            .unwrap_or_else(|| std::path::PathBuf::from(".local-db/state.json"))
+/// 按框架的约定生成可粘贴到 `.env` 文件的连接变量
+#[tauri::command]
+pub fn generate_env_snippet(
+    state: State<AppState>,
+    id: String,
+    framework: EnvFramework,
+) -> OperationResult<String> {
+    match state.get_database(&id) {
+        Some(db_info) => match connection::env_snippet(&db_info, framework) {
+            Ok(snippet) => OperationResult::success("Snippet generated", Some(snippet)),
+            Err(e) => OperationResult::error(e.to_string()),
+        },
+        None => OperationResult::failure(AppError::DatabaseNotFound),
     }
+}
 
-    fn ensure_state_dir() -> Result<(), String> {
-        let state_path = get_state_path();
-        if let Some(parent) = state_path.parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create state directory: {}", e))?;
+/// 列出本机已安装、可以打开该数据库的客户端
+#[tauri::command]
+pub fn list_database_clients(
+    state: State<AppState>,
+    id: String,
+) -> OperationResult<Vec<DatabaseClient>> {
+    match state.get_database(&id) {
+        Some(db_info) => {
+            OperationResult::success("Clients detected", Some(client::detect(&db_info)))
         }
-        Ok(())
+        None => OperationResult::failure(AppError::DatabaseNotFound),
     }
+}
 
-    ensure_state_dir()?;
+/// 用已安装的客户端打开数据库连接
+#[tauri::command]
+pub async fn open_in_client(
+    state: State<'_, AppState>,
+    id: String,
+    client: DatabaseClient,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(db_info) => match client::open(&state.db_manager.storage_path(), &db_info, client) {
+            Ok(()) => OperationResult::success(
+                format!("Opened {} in {}", db_info.name, client.display_name()),
+                None,
+            ),
+            Err(e) => {
+                OperationResult::error(format!("Failed to open {}: {:#}", client.display_name(), e))
+            }
+        },
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
 
-    let state_path = get_state_path();
-    let databases_vec: Vec<crate::core::DatabaseInfo> = {
-        let db_map = databases.lock().unwrap();
-        db_map.values().cloned().collect()
+/// 列出 SQLite / DuckDB 实例下的数据库文件
+#[tauri::command]
+pub fn list_sqlite_files(state: State<AppState>, id: String) -> OperationResult<Vec<SqliteFile>> {
+    let result = match state.get_database(&id) {
+        Some(db_info) if db_info.db_type == DatabaseType::SQLite => sqlite::list_files(&db_info),
+        Some(db_info) if db_info.db_type == DatabaseType::DuckDB => duckdb::list_files(&db_info),
+        Some(_) => return OperationResult::error("Database is not a SQLite or DuckDB instance"),
+        None => return OperationResult::failure(AppError::DatabaseNotFound),
     };
-    let settings_clone = settings.lock().unwrap().clone();
+    match result {
+        Ok(files) => OperationResult::success("Database files listed", Some(files)),
+        Err(e) => OperationResult::error(format!("{:#}", e)),
+    }
+}
 
-    #[derive(serde::Serialize)]
-    struct AppStateData {
-        databases: Vec<crate::core::DatabaseInfo>,
-        settings: crate::core::GlobalSettings,
+/// 在 SQLite / DuckDB 实例下创建新的数据库文件
+#[tauri::command]
+pub async fn create_sqlite_file(
+    state: State<'_, AppState>,
+    id: String,
+    name: String,
+) -> Result<OperationResult<SqliteFile>, CommandError> {
+    let db_info = match state.get_database(&id) {
+        Some(db_info) if matches!(db_info.db_type, DatabaseType::SQLite | DatabaseType::DuckDB) => {
+            db_info
+        }
+        Some(_) => {
+            return Ok(OperationResult::error(
+                "Database is not a SQLite or DuckDB instance",
+            ))
+        }
+        None => return Ok(OperationResult::failure(AppError::DatabaseNotFound)),
+    };
+    // DuckDB 通过命令行创建文件
+    let result = run_blocking(move || match db_info.db_type {
+        DatabaseType::DuckDB => duckdb::create_file(&db_info, &name),
+        _ => sqlite::create_file(&db_info, &name),
+    })
+    .await?;
+    match result {
+        Ok(file) => Ok(OperationResult::success(
+            format!("{} created", file.name),
+            Some(file),
+        )),
+        Err(e) => Ok(OperationResult::error(format!("{:#}", e))),
     }
+}
+
+/// 读取数据库配置文件
+#[tauri::command]
+pub async fn get_database_config(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<String, CommandError> {
+    let db_info = state.get_database(&id).ok_or(AppError::DatabaseNotFound)?;
+    Ok(run_blocking(move || config_file::read_config(&db_info)).await??)
+}
 
-    let state_data = AppStateData {
-        databases: databases_vec,
-        settings: settings_clone,
+/// 校验并保存数据库配置文件，配置无效时拒绝保存，修改在重启数据库后生效
+#[tauri::command]
+pub async fn update_database_config(
+    state: State<'_, AppState>,
+    id: String,
+    contents: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let Some(db_info) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
     };
-    let json = serde_json::to_string_pretty(&state_data)
-        .map_err(|e| format!("Failed to serialize state: {}", e))?;
 
-    std::fs::write(&state_path, json).map_err(|e| format!("Failed to write state file: {}", e))
+    let name = db_info.name.clone();
+    let storage_path = state.db_manager.storage_path();
+    match run_blocking(move || config_file::write_config(&storage_path, &db_info, &contents))
+        .await?
+    {
+        Ok(()) => Ok(OperationResult::success(
+            format!("Configuration saved, restart {} to apply it", name),
+            None,
+        )),
+        Err(e) => Ok(OperationResult::error(format!("{:#}", e))),
+    }
 }
 
-/// 更新数据库自启动设置
+/// 应用资源配置预设，`restart` 为 true 时重启运行中的数据库使配置生效
 #[tauri::command]
-pub fn update_database_autostart(
-    state: State<AppState>,
+pub async fn apply_preset(
+    state: State<'_, AppState>,
     id: String,
-    auto_start: bool,
-) -> OperationResult<()> {
-    match state.get_database(&id) {
+    preset: ConfigPreset,
+    restart: Option<bool>,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
         Some(mut db_info) => {
-            db_info.auto_start = auto_start;
-            db_info.updated_at = crate::core::utils::get_timestamp();
-            state.update_database(db_info);
-            OperationResult::success("Autostart setting updated", None)
+            let result = apply_preset_to(&state, &mut db_info, preset, restart.unwrap_or(false));
+            if result.success {
+                state.update_database(db_info);
+            }
+            result
         }
-        None => OperationResult::error("Database not found"),
-    }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
 }
 
-/// 读取数据库日志文件
+fn apply_preset_to(
+    state: &AppState,
+    db_info: &mut DatabaseInfo,
+    preset: ConfigPreset,
+    restart: bool,
+) -> OperationResult<()> {
+    let overrides = match preset::preset_overrides(&db_info.db_type, preset) {
+        Ok(overrides) => overrides,
+        Err(e) => return OperationResult::error(e.to_string()),
+    };
+    let config = DatabaseConfig {
+        port: None,
+        data_path: None,
+        username: None,
+        password: None,
+        config: Some(overrides),
+    };
+    state
+        .db_manager
+        .apply_config(db_info, &config, restart)
+        .unwrap_or_else(|e| OperationResult::error(format!("Failed to apply preset: {:#}", e)))
+}
+
+/// 列出配置文件的历史版本，按时间倒序排列
 #[tauri::command]
-pub fn read_database_logs(
+pub fn list_config_versions(
     state: State<AppState>,
     id: String,
-    lines: Option<usize>,
-) -> Result<Vec<String>, String> {
-    match state.get_database(&id) {
-        Some(db_info) => {
-            let log_path = std::path::Path::new(&db_info.log_path);
+) -> Result<Vec<config_file::ConfigVersion>, CommandError> {
+    let db_info = state.get_database(&id).ok_or(AppError::DatabaseNotFound)?;
+    Ok(config_file::list_versions(
+        &state.db_manager.storage_path(),
+        &db_info,
+    )?)
+}
+
+/// 将配置文件恢复为指定的历史版本，修改在重启数据库后生效
+#[tauri::command]
+pub fn rollback_config(state: State<AppState>, id: String, version: String) -> OperationResult<()> {
+    let Some(db_info) = state.get_database(&id) else {
+        return OperationResult::failure(AppError::DatabaseNotFound);
+    };
+
+    match config_file::rollback(&state.db_manager.storage_path(), &db_info, &version) {
+        Ok(()) => OperationResult::success(
+            format!(
+                "Configuration restored to {}, restart {} to apply it",
+                version, db_info.name
+            ),
+            None,
+        ),
+        Err(e) => OperationResult::error(format!("Failed to restore configuration: {:#}", e)),
+    }
+}
 
-            // 如果日志路径不存在，返回空数组
-            if !log_path.exists() {
-                return Ok(vec![]);
+/// 应用结构化的配置修改，`restart` 为 true 时重启运行中的数据库使配置文件的修改生效
+#[tauri::command]
+pub async fn apply_database_config(
+    state: State<'_, AppState>,
+    id: String,
+    config: DatabaseConfig,
+    restart: Option<bool>,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => {
+            match state
+                .db_manager
+                .apply_config(&mut db_info, &config, restart.unwrap_or(false))
+            {
+                Ok(result) => {
+                    if result.success {
+                        state.update_database(db_info);
+                    }
+                    result
+                }
+                Err(e) => OperationResult::error(format!("Failed to apply config: {:#}", e)),
             }
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
 
-            // 读取日志文件
-            let content = std::fs::read_to_string(log_path)
-                .map_err(|e| format!("Failed to read log file: {}", e))?;
+/// 修改数据库启动时的环境变量和额外参数，`restart` 为 true 时重启运行中的数据库使其生效
+#[tauri::command]
+pub async fn update_database_launch_options(
+    state: State<'_, AppState>,
+    id: String,
+    env: HashMap<String, String>,
+    extra_args: Vec<String>,
+    restart: Option<bool>,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => {
+            match state.db_manager.update_launch_options(
+                &mut db_info,
+                env,
+                extra_args,
+                restart.unwrap_or(false),
+            ) {
+                Ok(result) => {
+                    if result.success {
+                        state.update_database(db_info);
+                    }
+                    result
+                }
+                Err(e) => {
+                    OperationResult::error(format!("Failed to update launch options: {:#}", e))
+                }
+            }
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
 
-            let mut log_lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+/// 开启或关闭数据库的局域网共享，开启时返回局域网中的连接地址
+#[tauri::command]
+pub async fn expose_database(
+    state: State<'_, AppState>,
+    id: String,
+    enable: bool,
+) -> Result<OperationResult<LanEndpoint>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => match state.db_manager.expose_database(&mut db_info, enable) {
+            Ok(result) => {
+                if result.success {
+                    state.update_database(db_info);
+                }
+                result
+            }
+            Err(e) => OperationResult::error(format!("Failed to change network sharing: {:#}", e)),
+        },
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
 
-            // 如果指定了行数，返回最后 N 行
-            if let Some(n) = lines {
-                if log_lines.len() > n {
-                    log_lines = log_lines.split_off(log_lines.len() - n);
+/// 将数据库的数据目录迁移到 `new_path`，迁移期间数据库会停止
+#[tauri::command]
+pub async fn move_database_data(
+    state: State<'_, AppState>,
+    id: String,
+    new_path: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => {
+            match state
+                .db_manager
+                .move_data(&mut db_info, Path::new(&new_path))
+            {
+                Ok(result) => {
+                    if result.success {
+                        state.update_database(db_info);
+                    }
+                    result
                 }
+                Err(e) => OperationResult::error_from(format!("Failed to move data: {:#}", e), &e),
             }
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 清空数据库的数据并重新初始化，完成后数据库处于运行状态
+#[tauri::command]
+pub async fn reset_database(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(mut db_info) => {
+            let result = match state.db_manager.reset_data(&mut db_info) {
+                Ok(result) => result,
+                Err(e) => OperationResult::error(format!("Failed to reset data: {:#}", e)),
+            };
+            // 数据目录已重建，失败时也要保存最新的运行状态
+            state.update_database(db_info);
+            result
+        }
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    })
+    .await
+}
+
+/// 统计数据库的数据目录、日志和备份占用的磁盘空间
+#[tauri::command]
+pub async fn get_database_disk_usage(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<DatabaseDiskUsage, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || match state.get_database(&id) {
+        Some(db_info) => Ok(state.disk_usage(&db_info)),
+        None => Err(AppError::DatabaseNotFound.into()),
+    })
+    .await?
+}
+
+/// 读取数据库日志文件
+#[tauri::command]
+pub async fn read_database_logs(
+    state: State<'_, AppState>,
+    id: String,
+    lines: Option<usize>,
+) -> Result<Vec<String>, CommandError> {
+    let db_info = state.get_database(&id).ok_or(AppError::DatabaseNotFound)?;
+    let log_path = std::path::PathBuf::from(&db_info.log_path);
+
+    // 如果日志路径不存在，返回空数组
+    if !log_path.exists() {
+        return Ok(vec![]);
+    }
+
+    // 读取日志文件
+    let content = run_blocking(move || std::fs::read_to_string(log_path))
+        .await?
+        .map_err(|e| format!("Failed to read log file: {}", e))?;
 
-            Ok(log_lines)
+    let mut log_lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+
+    // 如果指定了行数，返回最后 N 行
+    if let Some(n) = lines {
+        if log_lines.len() > n {
+            log_lines = log_lines.split_off(log_lines.len() - n);
         }
-        None => Err("Database not found".to_string()),
     }
+
+    Ok(log_lines)
 }
 
 /// 清除数据库日志文件
@@ -530,14 +2118,58 @@ pub fn clear_database_logs(state: State<AppState>, id: String) -> OperationResul
                 OperationResult::success("Log file does not exist", None)
             }
         }
-        None => OperationResult::error("Database not found"),
+        None => OperationResult::failure(AppError::DatabaseNotFound),
+    }
+}
+
+/// 读取日志最后 N 行，`follow` 为 true 时持续跟踪并通过 `log-line` 事件推送新行
+#[tauri::command]
+pub fn tail_database_log(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    id: String,
+    lines: Option<usize>,
+    follow: Option<bool>,
+) -> Result<Vec<String>, CommandError> {
+    let db_info = state.get_database(&id).ok_or(AppError::DatabaseNotFound)?;
+    let log_path = std::path::PathBuf::from(&db_info.log_path);
+
+    let log_lines = log_tail::read_last_lines(&log_path, lines.unwrap_or(100))
+        .map_err(|e| format!("Failed to read log file: {}", e))?;
+
+    if follow.unwrap_or(false) {
+        let stop = state.start_log_watcher(&id);
+        std::thread::spawn(move || {
+            use tauri::Emitter;
+            log_tail::follow(&log_path, stop, |line| {
+                let _ = app_handle.emit(
+                    "log-line",
+                    LogLineEvent {
+                        database_id: id.clone(),
+                        line,
+                    },
+                );
+            });
+        });
+    }
+
+    Ok(log_lines)
+}
+
+/// 停止跟踪数据库日志
+#[tauri::command]
+pub fn stop_tail_database_log(state: State<AppState>, id: String) -> OperationResult<()> {
+    if state.stop_log_watcher(&id) {
+        OperationResult::success("Log tailing stopped", None)
+    } else {
+        OperationResult::error("Log is not being tailed")
     }
 }
 
 /// 保存日志文件到指定路径
 #[tauri::command]
-pub fn save_logs_to_file(content: String, path: String) -> Result<(), String> {
-    std::fs::write(&path, content).map_err(|e| format!("Failed to save file: {}", e))
+pub fn save_logs_to_file(content: String, path: String) -> Result<(), CommandError> {
+    std::fs::write(&path, content).map_err(|e| format!("Failed to save file: {}", e).into())
 }
 
 /// 获取任务状态
@@ -546,17 +2178,64 @@ pub fn get_task_status(state: State<AppState>, task_id: String) -> Option<crate:
     state.get_task(&task_id)
 }
 
+/// 获取任务列表，按创建时间倒序排列
+#[tauri::command]
+pub fn list_tasks(
+    state: State<AppState>,
+    filter: Option<crate::core::TaskFilter>,
+) -> Vec<crate::core::AsyncTask> {
+    state.list_tasks(&filter.unwrap_or_default())
+}
+
+/// 清除已完成的任务，返回清除的数量
+#[tauri::command]
+pub fn clear_completed_tasks(state: State<AppState>) -> usize {
+    state.clear_completed_tasks()
+}
+
+/// 使用相同参数重新执行失败的安装任务，返回新任务的 ID
+///
+/// 之前已下载的发行包保存在下载缓存中，重试时不会重复下载。
+#[tauri::command]
+pub async fn retry_task(
+    state: State<'_, AppState>,
+    task_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<String, CommandError> {
+    let task = state
+        .get_task(&task_id)
+        .ok_or_else(|| format!("Task not found: {}", task_id))?;
+    if task.task_type != "install" {
+        return Err(format!("Task {} cannot be retried", task_id).into());
+    }
+    if task.status != crate::core::TaskStatus::Failed {
+        return Err(format!("Only failed tasks can be retried: {}", task_id).into());
+    }
+    let request = state
+        .get_install_request(&task_id)
+        .ok_or_else(|| format!("Install parameters not found for task {}", task_id))?;
+
+    let new_task_id = spawn_install(state.clone(), request.params, request.archive, app_handle)?;
+    state.remove_install_request(&task_id);
+    Ok(new_task_id)
+}
+
 /// 同步所有数据库的运行状态
 /// 此命令用于在前端页面加载完成后调用，检查并更新所有数据库的实际运行状态
 /// 流程：
-/// 1. 对于使用 PID 文件的数据库（Qdrant, SurrealDB），先检查 PID 文件
+/// 1. 对于使用 PID 文件的数据库（Qdrant, SurrealDB, OpenSearch, InfluxDB, Cassandra, Neo4j），先检查 PID 文件
 /// 2. 对于使用 Homebrew 的数据库（MySQL, PostgreSQL, MongoDB, Redis），检查 brew services list
 /// 3. 更新数据库状态信息，纠正可能不正确的状态
 #[tauri::command]
-pub fn sync_databases_status(
-    state: State<AppState>,
+pub async fn sync_databases_status(
+    state: State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Vec<DatabaseInfo> {
+) -> Result<Vec<DatabaseInfo>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || sync_status(&state, &app_handle)).await
+}
+
+fn sync_status(state: &AppState, app_handle: &tauri::AppHandle) -> Vec<DatabaseInfo> {
     #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
     {
         #[cfg(target_os = "macos")]
@@ -586,6 +2265,15 @@ pub fn sync_databases_status(
                     let pid_path = Path::new(&db_info.data_path).join(pid_file);
                     check_pid_file_status(&pid_path)
                 }
+                // OpenSearch、InfluxDB、Cassandra、Neo4j 通过 PID 文件检查状态
+                DatabaseType::OpenSearch
+                | DatabaseType::InfluxDB
+                | DatabaseType::Cassandra
+                | DatabaseType::Neo4j => {
+                    let pid_file = format!("{}.pid", db_info.db_type.as_str());
+                    let pid_path = Path::new(&db_info.data_path).join(pid_file);
+                    check_pid_file_status(&pid_path)
+                }
                 // Windows 上的其他数据库也通过 PID 文件检查
                 #[cfg(target_os = "windows")]
                 DatabaseType::Redis => {
@@ -603,6 +2291,11 @@ pub fn sync_databases_status(
                     check_pid_file_status(&pid_path)
                 }
                 #[cfg(target_os = "windows")]
+                DatabaseType::MariaDB => {
+                    let pid_path = Path::new(&db_info.data_path).join("mariadb.pid");
+                    check_pid_file_status(&pid_path)
+                }
+                #[cfg(target_os = "windows")]
                 DatabaseType::PostgreSQL => {
                     let pid_path = Path::new(&db_info.data_path).join("postgresql.pid");
                     check_pid_file_status(&pid_path)
@@ -625,6 +2318,11 @@ pub fn sync_databases_status(
                     check_pid_file_status(&pid_path)
                 }
                 #[cfg(target_os = "linux")]
+                DatabaseType::MariaDB => {
+                    let pid_path = Path::new(&db_info.data_path).join("mariadb.pid");
+                    check_pid_file_status(&pid_path)
+                }
+                #[cfg(target_os = "linux")]
                 DatabaseType::PostgreSQL => {
                     let pid_path = Path::new(&db_info.data_path).join("postgresql.pid");
                     check_pid_file_status(&pid_path)
@@ -635,42 +2333,45 @@ pub fn sync_databases_status(
                     check_pid_file_status(&pid_path)
                 }
 
-                // 其他数据库通过 brew services 检查状态
-                #[cfg(target_os = "macos")]
-                DatabaseType::Redis => get_database_status_from_brew(&brew_services, "redis")
-                    .unwrap_or(DatabaseStatus::Stopped),
-                #[cfg(target_os = "macos")]
-                DatabaseType::MySQL => {
-                    // 尝试多个常见的 MySQL 版本标签
-                    get_database_status_from_brew(&brew_services, "mysql@8.4")
-                        .or_else(|| get_database_status_from_brew(&brew_services, "mysql@8.0"))
-                        .or_else(|| get_database_status_from_brew(&brew_services, "mysql"))
-                        .unwrap_or(DatabaseStatus::Stopped)
-                }
-                #[cfg(target_os = "macos")]
-                DatabaseType::PostgreSQL => {
-                    // 尝试多个常见的 PostgreSQL 版本标签
-                    get_database_status_from_brew(&brew_services, "postgresql@18")
-                        .or_else(|| get_database_status_from_brew(&brew_services, "postgresql@17"))
-                        .or_else(|| get_database_status_from_brew(&brew_services, "postgresql@16"))
-                        .or_else(|| get_database_status_from_brew(&brew_services, "postgresql"))
-                        .unwrap_or(DatabaseStatus::Stopped)
-                }
+                // 其他数据库通过实例版本对应的 brew services 服务检查状态
                 #[cfg(target_os = "macos")]
-                DatabaseType::MongoDB => {
-                    // 尝试多个常见的 MongoDB 版本标签
-                    get_database_status_from_brew(&brew_services, "mongodb-community@7.0")
-                        .or_else(|| {
-                            get_database_status_from_brew(&brew_services, "mongodb-community@6.0")
-                        })
-                        .or_else(|| {
-                            get_database_status_from_brew(&brew_services, "mongodb-community")
-                        })
+                DatabaseType::Redis
+                | DatabaseType::MySQL
+                | DatabaseType::MariaDB
+                | DatabaseType::PostgreSQL
+                | DatabaseType::MongoDB => get_instance_status_from_brew(&brew_services, db_info),
+                DatabaseType::SQLite | DatabaseType::DuckDB | DatabaseType::Custom => {
+                    backend::backend_of(db_info)
+                        .status(db_info)
                         .unwrap_or(DatabaseStatus::Stopped)
                 }
-                DatabaseType::Neo4j | DatabaseType::SeekDB => DatabaseStatus::Stopped,
+                #[cfg(not(target_os = "linux"))]
+                DatabaseType::SeekDB => DatabaseStatus::Stopped,
+            };
+            // 容器中运行的数据库由容器运行时报告状态，系统软件包、LaunchAgent 和外部安装由服务管理器报告
+            let actual_status = match db_info.backend {
+                BackendKind::Docker
+                | BackendKind::Package
+                | BackendKind::LaunchAgent
+                | BackendKind::External => backend::backend_of(db_info)
+                    .status(db_info)
+                    .unwrap_or_else(|| db_info.status.clone()),
+                BackendKind::Native => actual_status,
             };
 
+            // 正在启动或停止的数据库由 db_manager 更新状态
+            if state.db_manager.transient_status(&db_info.id).is_some() {
+                continue;
+            }
+            // 进程已退出的数据库保留崩溃状态；由应用启动的交给守护任务处理崩溃和重启
+            if actual_status == DatabaseStatus::Stopped
+                && (db_info.status == DatabaseStatus::Crashed
+                    || (backend::backend_of(db_info).is_supervised()
+                        && state.db_manager.started_databases().contains(&db_info.id)))
+            {
+                continue;
+            }
+
             // 如果状态有变化，更新数据库信息
             if db_info.status != actual_status {
                 let mut updated_db = db_info.clone();
@@ -737,6 +2438,7 @@ pub fn sync_databases_status(
                             id: utils::generate_id(),
                             name: db_type.display_name().to_string(),
                             db_type: db_type.clone(),
+                            backend: BackendKind::Native,
                             version,
                             install_path: "Managed by Homebrew".to_string(),
                             data_path: "Managed by Homebrew".to_string(),
@@ -744,9 +2446,17 @@ pub fn sync_databases_status(
                             port,
                             username: default_user,
                             password: default_pass,
+                            credential_ref: None,
                             config: None,
                             status: DatabaseStatus::Running,
                             auto_start: false,
+                            depends_on: Vec::new(),
+                            env: HashMap::new(),
+                            extra_args: Vec::new(),
+                            schedules: Vec::new(),
+                            on_demand: false,
+                            replica_of: None,
+                            bind_address: crate::core::network::LOCAL_BIND_ADDRESS.to_string(),
                             pid: None,
                             created_at: utils::get_timestamp(),
                             updated_at: utils::get_timestamp(),
@@ -762,6 +2472,17 @@ pub fn sync_databases_status(
             }
         }
 
+        // 自动发现通过 Windows 服务或 systemd 管理的数据库并注册
+        #[cfg(any(target_os = "windows", target_os = "linux"))]
+        {
+            let ignored = state.get_settings().ignored_external;
+            let discovered = external::discover(&state.get_all_databases(), &ignored);
+            if !discovered.is_empty() {
+                state.update_databases(discovered);
+                _has_updates = true;
+            }
+        }
+
         // 检查并更新现有数据库的凭据
         #[cfg(target_os = "macos")]
         {
@@ -789,12 +2510,20 @@ pub fn sync_databases_status(
         // 如果有更新，或者仅仅为了通知前端最新状态，都发射事件
         // 这里选择总是发射，确保前端能收到最新的完整列表
         use tauri::Emitter;
-        let updated_databases = state.get_all_databases();
+        let updated_databases: Vec<DatabaseInfo> = state
+            .get_all_databases()
+            .into_iter()
+            .map(|db_info| state.db_manager.with_transient_status(db_info))
+            .collect();
         let _ = app_handle.emit("databases-updated", &updated_databases);
     }
 
     // 返回更新后的数据库列表
-    state.get_all_databases()
+    state
+        .get_all_databases()
+        .into_iter()
+        .map(|db_info| state.db_manager.with_transient_status(db_info))
+        .collect()
 }
 
 /// 从 Homebrew 服务状态映射中获取数据库状态
@@ -812,9 +2541,24 @@ fn get_database_status_from_brew(
     })
 }
 
+/// 按实例版本对应的 formula（如 postgresql@16）获取状态，找不到时再查不带版本号的服务
+#[cfg(target_os = "macos")]
+fn get_instance_status_from_brew(
+    brew_services: &std::collections::HashMap<String, bool>,
+    db_info: &DatabaseInfo,
+) -> DatabaseStatus {
+    let version = Some(db_info.version.as_str());
+    let formula =
+        crate::core::versions::homebrew_formula(&db_info.db_type, version).unwrap_or_default();
+    let base_name = formula.split('@').next().unwrap_or_default();
+    get_database_status_from_brew(brew_services, &formula)
+        .or_else(|| get_database_status_from_brew(brew_services, base_name))
+        .unwrap_or(DatabaseStatus::Stopped)
+}
+
 /// 检查 PID 文件中的进程是否存在
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
-fn check_pid_file_status(pid_path: &std::path::Path) -> DatabaseStatus {
+pub(crate) fn check_pid_file_status(pid_path: &std::path::Path) -> DatabaseStatus {
     use std::fs;
 
     if !pid_path.exists() {
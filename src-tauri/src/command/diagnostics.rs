@@ -0,0 +1,117 @@
+use crate::app::AppState;
+use crate::command::run_blocking;
+use crate::command::system_info::system_info;
+use crate::core::{diagnostics, log_tail, CommandError, OperationResult};
+use std::path::Path;
+use tauri::State;
+use tauri_plugin_log::log;
+
+/// 诊断包中每个数据库日志保留的行数
+const DIAGNOSTICS_LOG_LINES: usize = 1000;
+
+/// 导出诊断包（脱敏后的状态、最近日志、服务状态、系统信息），返回 zip 文件路径
+#[tauri::command]
+pub async fn export_diagnostics(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<OperationResult<String>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || write_diagnostics(&state, path)).await
+}
+
+fn write_diagnostics(state: &AppState, path: String) -> OperationResult<String> {
+    let mut files: Vec<(String, String)> = Vec::new();
+
+    // 脱敏后的应用状态
+    let mut state_json = serde_json::json!({
+        "databases": state.get_all_databases(),
+        "settings": state.get_settings(),
+        "backups": state.list_backups(None),
+    });
+    diagnostics::redact_secrets(&mut state_json);
+    files.push((
+        "state.json".to_string(),
+        serde_json::to_string_pretty(&state_json).unwrap_or_default(),
+    ));
+
+    // 最近的数据库日志
+    for db_info in state.get_all_databases() {
+        let log_path = Path::new(&db_info.log_path);
+        match log_tail::read_last_lines(log_path, DIAGNOSTICS_LOG_LINES) {
+            Ok(lines) if !lines.is_empty() => files.push((
+                format!("logs/{}-{}.log", db_info.db_type.as_str(), db_info.id),
+                lines.join("\n"),
+            )),
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to read log for {}: {}", db_info.name, e),
+        }
+    }
+
+    files.push(("services.txt".to_string(), collect_service_status(state)));
+
+    let system_info = serde_json::json!({
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "system": system_info(state),
+    });
+    files.push((
+        "system_info.json".to_string(),
+        serde_json::to_string_pretty(&system_info).unwrap_or_default(),
+    ));
+
+    match diagnostics::write_bundle(Path::new(&path), &files) {
+        Ok(_) => OperationResult::success("Diagnostics exported successfully", Some(path)),
+        Err(e) => OperationResult::error(format!("Failed to export diagnostics: {}", e)),
+    }
+}
+
+/// 汇总记录的状态、PID 文件以及 Homebrew 服务状态
+fn collect_service_status(state: &AppState) -> String {
+    let mut report = String::new();
+
+    for db_info in state.get_all_databases() {
+        report.push_str(&format!(
+            "{} ({}): status={:?}, pid={:?}, port={}\n",
+            db_info.name,
+            db_info.db_type.as_str(),
+            db_info.status,
+            db_info.pid,
+            db_info.port
+        ));
+
+        #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+        if let Ok(entries) = std::fs::read_dir(&db_info.data_path) {
+            for entry in entries.flatten() {
+                let pid_path = entry.path();
+                if pid_path.extension().is_some_and(|ext| ext == "pid") {
+                    let content = std::fs::read_to_string(&pid_path).unwrap_or_default();
+                    report.push_str(&format!(
+                        "  {}: {} ({:?})\n",
+                        pid_path.display(),
+                        content.trim(),
+                        crate::command::database::check_pid_file_status(&pid_path)
+                    ));
+                }
+            }
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use crate::core::macos::get_all_homebrew_services_status;
+
+        report.push_str("\nbrew services:\n");
+        let mut services: Vec<_> = get_all_homebrew_services_status().into_iter().collect();
+        services.sort();
+        for (service, running) in services {
+            report.push_str(&format!(
+                "  {}: {}\n",
+                service,
+                if running { "started" } else { "stopped" }
+            ));
+        }
+    }
+
+    report
+}
@@ -1,7 +1,28 @@
+use crate::core::CommandError;
+
+pub mod backup;
+pub mod benchmark;
 pub mod database;
+pub mod diagnostics;
+pub mod replication;
 pub mod settings;
 pub mod system_info;
 
+pub use backup::*;
+pub use benchmark::*;
 pub use database::*;
+pub use diagnostics::*;
+pub use replication::*;
 pub use settings::*;
 pub use system_info::*;
+
+/// 在后台线程池中执行会阻塞的操作（启停进程、网络请求、读写大文件等），避免占用 IPC
+pub(crate) async fn run_blocking<T, F>(f: F) -> Result<T, CommandError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| CommandError::Message(e.to_string()))
+}
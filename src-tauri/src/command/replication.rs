@@ -0,0 +1,180 @@
+use crate::app::AppState;
+use crate::command::{install_with_plan, prepare_install, run_blocking};
+use crate::core::replication::{self, ReplicationStatus};
+use crate::core::{
+    utils, AppError, AsyncTask, BackendKind, CommandError, InstallDatabaseParams, OperationResult,
+    ReplicaOptions, TaskStatus,
+};
+use tauri::{AppHandle, Emitter, State};
+
+/// 安装一个与源数据库同版本的新实例并配置为其副本，返回任务ID，进度通过 replica-progress 事件发送
+///
+/// 支持 Redis（REPLICAOF）、PostgreSQL（流复制和复制槽）和 MySQL / MariaDB（二进制日志复制），
+/// 副本使用源数据库的凭据。复制配置失败时副本仍会注册，可以删除后重试。
+#[tauri::command]
+pub async fn setup_replica(
+    state: State<'_, AppState>,
+    source_id: String,
+    options: Option<ReplicaOptions>,
+    app_handle: AppHandle,
+) -> Result<String, CommandError> {
+    let source = state
+        .get_database(&source_id)
+        .ok_or(AppError::DatabaseNotFound)?;
+    if !replication::supports(&source.db_type) {
+        return Err(format!(
+            "Replicas are not supported for {}",
+            source.db_type.display_name()
+        )
+        .into());
+    }
+    if !matches!(
+        source.backend,
+        BackendKind::Native | BackendKind::LaunchAgent
+    ) {
+        return Err("Replicas can only be created for native installs".into());
+    }
+    if source.replica_of.is_some() {
+        return Err(format!("{} is already a replica", source.name).into());
+    }
+
+    let options = options.unwrap_or_default();
+    let mut params = InstallDatabaseParams {
+        db_type: source.db_type.as_str().to_string(),
+        version: Some(source.version.clone()),
+        name: Some(
+            options
+                .name
+                .unwrap_or_else(|| format!("{}-replica", source.name)),
+        ),
+        port: options.port,
+        username: source.username.clone(),
+        password: source.password.clone(),
+        backend: BackendKind::Native,
+        preset: None,
+        env: source.env.clone(),
+        extra_args: source.extra_args.clone(),
+    };
+    let plan = prepare_install(&state, &mut params, None)?;
+
+    let task_id = format!(
+        "replica-{}-{}",
+        source.db_type.as_str(),
+        utils::generate_id()
+    );
+    state.add_task(AsyncTask {
+        id: task_id.clone(),
+        task_type: "replica".to_string(),
+        db_type: source.db_type.as_str().to_string(),
+        status: TaskStatus::Pending,
+        progress: 0,
+        message: format!("Replica of {} pending...", source.name),
+        error: None,
+        error_code: None,
+        queue_position: None,
+        created_at: utils::get_timestamp(),
+        updated_at: utils::get_timestamp(),
+    });
+    // 与安装任务共用队列
+    state.enqueue_install(&task_id);
+
+    let app_state = state.inner().clone();
+    let task_id_for_thread = task_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let update_task = |update: &dyn Fn(&mut AsyncTask)| {
+            let mut tasks = app_state.tasks.lock().unwrap();
+            if let Some(task) = tasks.get_mut(&task_id_for_thread) {
+                update(task);
+                task.updated_at = utils::get_timestamp();
+                let _ = app_handle.emit("replica-progress", task.clone());
+            }
+        };
+        let fail = |e: anyhow::Error, message: &str| {
+            update_task(&|task| {
+                task.status = TaskStatus::Failed;
+                task.error = Some(format!("{:#}", e));
+                task.error_code = AppError::of(&e);
+                task.message = message.to_string();
+            })
+        };
+
+        let _install_slot = app_state.wait_install_slot(&task_id_for_thread);
+        if let Err(e) = plan.preflight() {
+            return fail(e, "Pre-install check failed");
+        }
+        update_task(&|task| {
+            task.status = TaskStatus::Running;
+            task.progress = 10;
+            task.message = "Installing the replica...".to_string();
+        });
+        let mut replica = match install_with_plan(&app_state, plan) {
+            Ok(replica) => replica,
+            Err(e) => return fail(e, "Installation failed"),
+        };
+
+        update_task(&|task| {
+            task.progress = 60;
+            task.message = "Configuring replication...".to_string();
+        });
+        let result = match app_state.get_database(&source_id) {
+            Some(mut source) => {
+                let result = app_state
+                    .db_manager
+                    .setup_replica(&mut source, &mut replica);
+                // 源实例可能因开启二进制日志而重启
+                app_state.update_database(source);
+                result
+            }
+            None => Err(AppError::DatabaseNotFound.into()),
+        };
+        if let Err(e) = app_state.add_database(replica) {
+            return fail(anyhow::anyhow!(e), "Failed to register the replica");
+        }
+
+        match result {
+            Ok(result) if result.success => update_task(&|task| {
+                task.status = TaskStatus::Completed;
+                task.progress = 100;
+                task.message = result.message.clone();
+            }),
+            Ok(result) => fail(
+                anyhow::anyhow!(result.message),
+                "Replica installed, but replication could not be configured",
+            ),
+            Err(e) => fail(
+                e,
+                "Replica installed, but replication could not be configured",
+            ),
+        }
+    });
+
+    Ok(task_id)
+}
+
+/// 获取副本的复制状态和延迟
+#[tauri::command]
+pub async fn get_replication_status(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<OperationResult<ReplicationStatus>, CommandError> {
+    let Some(replica) = state.get_database(&id) else {
+        return Ok(OperationResult::failure(AppError::DatabaseNotFound));
+    };
+    let Some(source_id) = replica.replica_of.clone() else {
+        return Ok(OperationResult::error(format!(
+            "{} is not a replica",
+            replica.name
+        )));
+    };
+    let Some(source) = state.get_database(&source_id) else {
+        return Ok(OperationResult::error(
+            "The source database no longer exists",
+        ));
+    };
+
+    run_blocking(move || match replication::status(&source, &replica) {
+        Ok(status) => OperationResult::success("Replication status loaded", Some(status)),
+        Err(e) => OperationResult::error(format!("Failed to read replication status: {:#}", e)),
+    })
+    .await
+}
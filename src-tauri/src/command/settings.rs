@@ -1,6 +1,16 @@
 use crate::app::AppState;
-use crate::core::{GlobalSettings, OperationResult};
-use tauri::State;
+use crate::command::run_blocking;
+use crate::core::docker;
+use crate::core::download::{self, DownloadCacheInfo};
+use crate::core::{
+    utils, AppError, AppStateExport, AppStateImportResult, CommandError, GlobalSettings,
+    OperationResult,
+};
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, State};
+use tauri_plugin_autostart::ManagerExt;
+use tauri_plugin_log::log;
 
 /// 获取全局设置
 #[tauri::command]
@@ -8,9 +18,126 @@ pub fn get_settings(state: State<AppState>) -> GlobalSettings {
     state.get_settings()
 }
 
-/// 更新全局设置
+/// 更新全局设置，存储位置需要通过 `migrate_storage_path` 修改
 #[tauri::command]
-pub fn update_settings(state: State<AppState>, settings: GlobalSettings) -> OperationResult<()> {
+pub fn update_settings(
+    state: State<AppState>,
+    settings: GlobalSettings,
+    app_handle: AppHandle,
+) -> OperationResult<()> {
+    if settings.default_storage_path != state.get_settings().default_storage_path {
+        return OperationResult::error("Use the storage migration to change the storage path");
+    }
+    let fields = crate::core::settings::validate(&settings);
+    if !fields.is_empty() {
+        return OperationResult::failure(AppError::InvalidSettings { fields });
+    }
+    if let Err(e) = apply_launch_at_login(&app_handle, settings.auto_start) {
+        return OperationResult::error(e);
+    }
     state.update_settings(settings);
     OperationResult::success("Settings updated successfully", None)
 }
+
+/// 按 `auto_start` 设置注册或移除登录时启动应用，已是目标状态时不做修改
+pub fn apply_launch_at_login(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let autolaunch = app.autolaunch();
+    if autolaunch.is_enabled().unwrap_or(!enabled) == enabled {
+        return Ok(());
+    }
+    let applied = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    applied.map_err(|e| format!("Failed to update launch at login: {}", e))
+}
+
+/// 将存储位置迁移到 `new_path`，移动已安装数据库的程序、数据、日志、配置和备份
+#[tauri::command]
+pub async fn migrate_storage_path(
+    state: State<'_, AppState>,
+    new_path: String,
+) -> Result<OperationResult<()>, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || state.migrate_storage_path(Path::new(&new_path))).await
+}
+
+/// 将数据库列表、设置和备份记录导出为 JSON 文件，`include_secrets` 默认为 false，不导出密码和密钥
+#[tauri::command]
+pub fn export_app_state(
+    state: State<AppState>,
+    path: String,
+    include_secrets: Option<bool>,
+) -> OperationResult<()> {
+    let export = state.export_state(include_secrets.unwrap_or(false));
+    let json = match serde_json::to_string_pretty(&export) {
+        Ok(json) => json,
+        Err(e) => return OperationResult::error(format!("Failed to serialize state: {}", e)),
+    };
+    match fs::write(&path, json) {
+        Ok(()) => OperationResult::success(
+            format!("Exported {} databases to {}", export.databases.len(), path),
+            None,
+        ),
+        Err(e) => OperationResult::error(format!("Failed to write {}: {}", path, e)),
+    }
+}
+
+/// 生成以容器复现已注册数据库的 docker-compose.yml 内容，`project` 为可选的 Compose 项目名
+#[tauri::command]
+pub fn export_compose(state: State<AppState>, project: Option<String>) -> OperationResult<String> {
+    match docker::compose_file(&state.get_all_databases(), project.as_deref()) {
+        Ok(content) => OperationResult::success("Compose file generated", Some(content)),
+        Err(e) => OperationResult::error(format!("Failed to generate compose file: {:#}", e)),
+    }
+}
+
+/// 从 `export_app_state` 导出的文件导入数据库列表、设置和备份记录
+#[tauri::command]
+pub fn import_app_state(
+    state: State<AppState>,
+    path: String,
+    app_handle: AppHandle,
+) -> OperationResult<AppStateImportResult> {
+    let export = match fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))
+        .and_then(|content| {
+            serde_json::from_str::<AppStateExport>(&content)
+                .map_err(|e| format!("Invalid export file: {}", e))
+        }) {
+        Ok(export) => export,
+        Err(e) => return OperationResult::error(e),
+    };
+    match state.import_state(export) {
+        Ok(result) => {
+            // 导入的设置可能开启或关闭了登录时启动
+            if let Err(e) = apply_launch_at_login(&app_handle, state.get_settings().auto_start) {
+                log::warn!("{}", e);
+            }
+            OperationResult::success(
+                format!("Imported {} databases", result.imported_databases),
+                Some(result),
+            )
+        }
+        Err(e) => OperationResult::error(e),
+    }
+}
+
+/// 获取下载缓存的位置、文件数和大小
+#[tauri::command]
+pub fn get_download_cache_info() -> Result<DownloadCacheInfo, CommandError> {
+    Ok(download::cache_info()?)
+}
+
+/// 清空下载缓存，之后安装会重新下载发行包
+#[tauri::command]
+pub async fn clear_download_cache() -> Result<OperationResult<u64>, CommandError> {
+    Ok(match run_blocking(download::clear_cache).await? {
+        Ok(freed) => OperationResult::success(
+            format!("Freed {}", utils::format_file_size(freed)),
+            Some(freed),
+        ),
+        Err(e) => OperationResult::error(format!("Failed to clear download cache: {}", e)),
+    })
+}
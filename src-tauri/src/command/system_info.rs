@@ -1,5 +1,10 @@
+use crate::app::AppState;
+use crate::command::run_blocking;
+use crate::core::metrics::MetricSample;
+use crate::core::{CommandError, DatabaseDiskUsage};
 use serde::{Deserialize, Serialize};
 use sysinfo::{Disks, System};
+use tauri::State;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
@@ -10,6 +15,8 @@ pub struct SystemInfo {
     pub swap_used: u64,
     pub load_average: f64,
     pub disks: Vec<DiskInfo>,
+    /// 各数据库占用的磁盘空间，按占用从大到小排列
+    pub database_disk_usage: Vec<DatabaseDiskUsage>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,7 +31,13 @@ pub struct DiskInfo {
 
 /// 获取系统信息
 #[tauri::command]
-pub fn get_system_info() -> Result<SystemInfo, String> {
+pub async fn get_system_info(state: State<'_, AppState>) -> Result<SystemInfo, CommandError> {
+    let state = state.inner().clone();
+    run_blocking(move || system_info(&state)).await
+}
+
+/// 采集 CPU、内存、负载、磁盘和各数据库的磁盘占用，需要遍历进程、磁盘和数据目录，耗时较长
+pub(crate) fn system_info(state: &AppState) -> SystemInfo {
     let mut sys = System::new_all();
     sys.refresh_all();
 
@@ -71,7 +84,14 @@ pub fn get_system_info() -> Result<SystemInfo, String> {
         })
         .collect();
 
-    Ok(SystemInfo {
+    let mut database_disk_usage: Vec<DatabaseDiskUsage> = state
+        .get_all_databases()
+        .iter()
+        .map(|db_info| state.disk_usage(db_info))
+        .collect();
+    database_disk_usage.sort_by_key(|usage| std::cmp::Reverse(usage.total_size));
+
+    SystemInfo {
         cpu_usage,
         memory_total,
         memory_used,
@@ -79,17 +99,29 @@ pub fn get_system_info() -> Result<SystemInfo, String> {
         swap_used,
         load_average,
         disks: disk_infos,
-    })
+        database_disk_usage,
+    }
+}
+
+/// 获取后台采集的指标历史，`id` 为空时返回系统整体指标，`range` 为最近的秒数，默认 1 小时
+#[tauri::command]
+pub fn get_metrics_history(
+    state: State<AppState>,
+    id: Option<String>,
+    range: Option<u64>,
+) -> Vec<MetricSample> {
+    let range = std::time::Duration::from_secs(range.unwrap_or(60 * 60));
+    state.metrics_history(id.as_deref(), range)
 }
 
 /// 获取 CPU 使用率
 #[tauri::command]
-pub fn get_cpu_usage() -> Result<f32, String> {
-    let mut sys = System::new_all();
+pub async fn get_cpu_usage() -> Result<f32, CommandError> {
+    let mut sys = System::new();
     sys.refresh_cpu_all();
 
     // 等待一小段时间以获取准确的 CPU 使用率
-    std::thread::sleep(std::time::Duration::from_millis(200));
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
     sys.refresh_cpu_all();
 
     Ok(sys.global_cpu_usage())
@@ -97,8 +129,8 @@ pub fn get_cpu_usage() -> Result<f32, String> {
 
 /// 获取内存信息
 #[tauri::command]
-pub fn get_memory_info() -> Result<(u64, u64, f32), String> {
-    let mut sys = System::new_all();
+pub async fn get_memory_info() -> Result<(u64, u64, f32), CommandError> {
+    let mut sys = System::new();
     sys.refresh_memory();
 
     let total = sys.total_memory();
@@ -114,8 +146,8 @@ pub fn get_memory_info() -> Result<(u64, u64, f32), String> {
 
 /// 获取磁盘信息
 #[tauri::command]
-pub fn get_disk_info() -> Result<Vec<DiskInfo>, String> {
-    let disks = Disks::new_with_refreshed_list();
+pub async fn get_disk_info() -> Result<Vec<DiskInfo>, CommandError> {
+    let disks = run_blocking(Disks::new_with_refreshed_list).await?;
     let disk_infos: Vec<DiskInfo> = disks
         .iter()
         .map(|disk| {
@@ -0,0 +1,135 @@
+use crate::core::{BackendKind, DatabaseInfo, DatabaseType};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::io;
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// 等待连接时检查是否需要释放端口的间隔
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 数据库是否支持按需启动：需要监听端口，且由应用直接启动或通过容器、LaunchAgent 启动
+pub fn supports(db_info: &DatabaseInfo) -> bool {
+    db_info.port != 0
+        && !matches!(db_info.db_type, DatabaseType::SQLite | DatabaseType::DuckDB)
+        && matches!(
+            db_info.backend,
+            BackendKind::Native | BackendKind::Docker | BackendKind::LaunchAgent
+        )
+}
+
+#[derive(Debug)]
+struct Armed {
+    /// 监听的地址和端口
+    address: (String, u16),
+    release: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+/// 按需启动的端口监听
+///
+/// 数据库停止时代为监听它的端口，收到第一个连接后释放端口，由回调启动数据库，
+/// 就绪后通过 [`proxy`] 转发这个连接。启动期间的其它连接会被拒绝。
+#[derive(Debug, Clone, Default)]
+pub struct Activator {
+    armed: Arc<Mutex<HashMap<String, Armed>>>,
+}
+
+impl Activator {
+    /// 开始监听数据库的端口，收到连接时在监听线程中调用 `on_connect`；已在监听时不做修改，
+    /// 修改了端口时需要先 [`Activator::release`]
+    pub fn arm<F>(&self, db_info: &DatabaseInfo, on_connect: F) -> Result<()>
+    where
+        F: FnOnce(TcpStream) + Send + 'static,
+    {
+        let mut armed = self.armed.lock().unwrap();
+        if armed.contains_key(&db_info.id) {
+            return Ok(());
+        }
+        let listener = TcpListener::bind((db_info.bind_address.as_str(), db_info.port))
+            .with_context(|| format!("Failed to listen on port {}", db_info.port))?;
+        listener.set_nonblocking(true)?;
+
+        let release = Arc::new(AtomicBool::new(false));
+        let released = release.clone();
+        let all_armed = self.armed.clone();
+        let db_id = db_info.id.clone();
+        let handle = std::thread::spawn(move || {
+            let client = loop {
+                if released.load(Ordering::SeqCst) {
+                    return;
+                }
+                match listener.accept() {
+                    Ok((client, _)) => break client,
+                    Err(_) => std::thread::sleep(ACCEPT_POLL_INTERVAL),
+                }
+            };
+            drop(listener);
+            // 同时被释放时由释放方处理，丢弃这个连接
+            if all_armed.lock().unwrap().remove(&db_id).is_none() {
+                return;
+            }
+            let _ = client.set_nonblocking(false);
+            on_connect(client);
+        });
+        armed.insert(
+            db_info.id.clone(),
+            Armed {
+                address: (db_info.bind_address.clone(), db_info.port),
+                release,
+                handle,
+            },
+        );
+        Ok(())
+    }
+
+    /// 停止监听并释放端口，返回时端口已可用于启动数据库
+    pub fn release(&self, db_id: &str) {
+        let Some(armed) = self.armed.lock().unwrap().remove(db_id) else {
+            return;
+        };
+        armed.release.store(true, Ordering::SeqCst);
+        let _ = armed.handle.join();
+    }
+
+    /// 是否正在代为监听数据库当前的地址和端口
+    pub fn is_armed(&self, db_info: &DatabaseInfo) -> bool {
+        self.armed
+            .lock()
+            .unwrap()
+            .get(&db_info.id)
+            .is_some_and(|armed| armed.address == (db_info.bind_address.clone(), db_info.port))
+    }
+
+    /// 正在代为监听端口的数据库 ID
+    pub fn armed_databases(&self) -> Vec<String> {
+        self.armed.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+/// 将按需启动时收到的连接转发到已就绪的数据库，连接关闭后结束
+pub fn proxy(client: TcpStream, db_info: &DatabaseInfo) -> Result<()> {
+    let host = match db_info.bind_address.as_str() {
+        "0.0.0.0" => "127.0.0.1",
+        address => address,
+    };
+    let server = TcpStream::connect((host, db_info.port)).with_context(|| {
+        format!(
+            "Failed to connect to {} on port {}",
+            db_info.name, db_info.port
+        )
+    })?;
+    let client_reader = client.try_clone()?;
+    let server_writer = server.try_clone()?;
+    std::thread::spawn(move || pipe(client_reader, server_writer));
+    std::thread::spawn(move || pipe(server, client));
+    Ok(())
+}
+
+fn pipe(mut from: TcpStream, mut to: TcpStream) {
+    let _ = io::copy(&mut from, &mut to);
+    let _ = to.shutdown(Shutdown::Write);
+}
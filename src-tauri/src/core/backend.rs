@@ -0,0 +1,344 @@
+use crate::core::custom::CustomBackend;
+use crate::core::distro;
+use crate::core::distro::DistroPackageBackend;
+use crate::core::docker::DockerBackend;
+use crate::core::duckdb::DuckdbBackend;
+use crate::core::external::ExternalServiceBackend;
+use crate::core::launchd::LaunchAgentBackend;
+use crate::core::sqlite::SqliteBackend;
+use crate::core::windows_package::{self, WindowsPackageBackend};
+use crate::core::{watchdog, BackendKind, DatabaseInfo, DatabaseStatus, DatabaseType};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+const UNSUPPORTED_PLATFORM: &str = "Database management is not supported on this platform";
+
+/// 安装数据库时的通用选项，各平台后端共用
+pub struct InstallOptions<'a> {
+    pub version: Option<&'a str>,
+    pub port: Option<u16>,
+    /// 实例名，用于区分同类型的多个实例
+    pub instance_name: Option<&'a str>,
+    pub username: Option<&'a str>,
+    pub password: Option<&'a str>,
+    pub auto_start: bool,
+    /// 离线安装时使用的本地发行包，设置后不再下载
+    pub archive: Option<&'a Path>,
+}
+
+impl<'a> Default for InstallOptions<'a> {
+    fn default() -> Self {
+        Self {
+            version: None,
+            port: None,
+            instance_name: None,
+            username: None,
+            password: None,
+            auto_start: true,
+            archive: None,
+        }
+    }
+}
+
+/// 数据库后端：负责某类数据库在当前平台上的安装、启停和状态检查
+///
+/// 新增数据库类型或管理方式时只需实现该 trait，并在 [`backend_for`] 中注册。
+pub trait DatabaseBackend: Send + Sync {
+    /// 安装数据库并返回新实例的信息
+    fn install(
+        &self,
+        db_type: &DatabaseType,
+        storage_path: &Path,
+        options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo>;
+
+    /// 启动数据库
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()>;
+
+    /// 停止数据库
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()>;
+
+    /// 查询数据库当前是否在运行，无法确定时返回 None
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus>;
+
+    /// 是否需要由守护任务检测崩溃并重启
+    fn is_supervised(&self) -> bool;
+
+    /// 卸载数据库实例，删除其数据、日志和配置
+    ///
+    /// `remove_binaries` 为 true 时一并删除程序文件，调用方需确保没有其他实例共用。
+    fn uninstall(&self, db_info: &DatabaseInfo, remove_binaries: bool) -> Result<()>;
+}
+
+/// 获取数据库实例对应的后端
+pub fn backend_of(db_info: &DatabaseInfo) -> &'static dyn DatabaseBackend {
+    backend_for(&db_info.db_type, db_info.backend)
+}
+
+/// 当前平台能否通过系统包管理器安装该类型的数据库
+pub fn supports_package_install(db_type: &DatabaseType) -> bool {
+    if cfg!(target_os = "windows") {
+        windows_package::supports(db_type)
+    } else {
+        distro::supports(db_type)
+    }
+}
+
+/// 获取数据库类型在当前平台上以指定方式运行时对应的后端
+pub fn backend_for(db_type: &DatabaseType, kind: BackendKind) -> &'static dyn DatabaseBackend {
+    if kind == BackendKind::Docker {
+        &DockerBackend
+    } else if kind == BackendKind::Package {
+        if cfg!(target_os = "windows") {
+            &WindowsPackageBackend
+        } else {
+            &DistroPackageBackend
+        }
+    } else if kind == BackendKind::LaunchAgent {
+        &LaunchAgentBackend
+    } else if kind == BackendKind::External {
+        &ExternalServiceBackend
+    } else if *db_type == DatabaseType::SQLite {
+        &SqliteBackend
+    } else if *db_type == DatabaseType::DuckDB {
+        &DuckdbBackend
+    } else if *db_type == DatabaseType::Custom {
+        &CustomBackend
+    } else if cfg!(not(any(
+        target_os = "macos",
+        target_os = "windows",
+        target_os = "linux"
+    ))) {
+        &UnsupportedBackend
+    } else if watchdog::is_supervised(db_type) {
+        &DirectProcessBackend
+    } else {
+        &HomebrewServiceBackend
+    }
+}
+
+/// 由应用直接启动进程并通过 PID 文件管理的后端
+pub struct DirectProcessBackend;
+
+/// 通过 Homebrew 安装并交给 brew services（launchd）管理的后端，仅用于 macOS
+pub struct HomebrewServiceBackend;
+
+/// 不支持的平台，所有操作都返回错误
+pub struct UnsupportedBackend;
+
+impl DatabaseBackend for DirectProcessBackend {
+    fn install(
+        &self,
+        db_type: &DatabaseType,
+        storage_path: &Path,
+        options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        platform::install(db_type, storage_path, options)
+    }
+
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()> {
+        platform::start(db_info)
+    }
+
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()> {
+        platform::stop(db_info)
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        if watchdog::is_process_alive(db_info) {
+            Some(DatabaseStatus::Running)
+        } else {
+            Some(DatabaseStatus::Stopped)
+        }
+    }
+
+    fn is_supervised(&self) -> bool {
+        true
+    }
+
+    fn uninstall(&self, db_info: &DatabaseInfo, remove_binaries: bool) -> Result<()> {
+        if watchdog::is_process_alive(db_info) {
+            self.stop(db_info)?;
+        }
+        remove_instance_files(db_info)?;
+        if remove_binaries {
+            platform::uninstall_binaries(db_info)?;
+        }
+        Ok(())
+    }
+}
+
+impl DatabaseBackend for HomebrewServiceBackend {
+    fn install(
+        &self,
+        db_type: &DatabaseType,
+        storage_path: &Path,
+        options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        super::macos::install_database_via_homebrew(db_type, storage_path, options)
+    }
+
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()> {
+        super::macos::start_service_for_database(db_info)
+    }
+
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()> {
+        super::macos::stop_service_for_database(db_info)
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        super::macos::get_database_service_status(db_info).map(|running| {
+            if running {
+                DatabaseStatus::Running
+            } else {
+                DatabaseStatus::Stopped
+            }
+        })
+    }
+
+    // launchd 会自行拉起崩溃的服务
+    fn is_supervised(&self) -> bool {
+        false
+    }
+
+    fn uninstall(&self, db_info: &DatabaseInfo, remove_binaries: bool) -> Result<()> {
+        if self.status(db_info) == Some(DatabaseStatus::Running) {
+            self.stop(db_info)?;
+        }
+        remove_instance_files(db_info)?;
+        if remove_binaries {
+            super::macos::uninstall_binaries(db_info)?;
+        }
+        Ok(())
+    }
+}
+
+impl DatabaseBackend for UnsupportedBackend {
+    fn install(
+        &self,
+        _db_type: &DatabaseType,
+        _storage_path: &Path,
+        _options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        bail!(UNSUPPORTED_PLATFORM)
+    }
+
+    fn start(&self, _db_info: &DatabaseInfo) -> Result<()> {
+        bail!(UNSUPPORTED_PLATFORM)
+    }
+
+    fn stop(&self, _db_info: &DatabaseInfo) -> Result<()> {
+        bail!(UNSUPPORTED_PLATFORM)
+    }
+
+    fn status(&self, _db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        None
+    }
+
+    fn is_supervised(&self) -> bool {
+        false
+    }
+
+    fn uninstall(&self, _db_info: &DatabaseInfo, _remove_binaries: bool) -> Result<()> {
+        bail!(UNSUPPORTED_PLATFORM)
+    }
+}
+
+/// 删除实例自己的数据目录、日志和配置文件，二进制文件可能被其他实例共用，不在此删除
+pub(crate) fn remove_instance_files(db_info: &DatabaseInfo) -> Result<()> {
+    let data_dir = Path::new(&db_info.data_path);
+    if data_dir.is_dir() {
+        fs::remove_dir_all(data_dir)
+            .with_context(|| format!("Failed to remove {}", data_dir.display()))?;
+    }
+    remove_path(Path::new(&db_info.log_path))?;
+    if let Some(config) = db_info.config.as_deref() {
+        remove_path(Path::new(config))?;
+    }
+    Ok(())
+}
+
+/// 删除下载到 `bin/<db>` 下的程序目录，其他位置的目录不删除
+pub fn remove_binary_dir(db_info: &DatabaseInfo) -> Result<()> {
+    let bin_dir = Path::new(&db_info.install_path);
+    let in_bin = bin_dir
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "bin");
+    if !in_bin {
+        bail!("Refusing to remove {}", bin_dir.display());
+    }
+    if bin_dir.is_dir() {
+        fs::remove_dir_all(bin_dir)
+            .with_context(|| format!("Failed to remove {}", bin_dir.display()))?;
+    }
+    Ok(())
+}
+
+/// 删除文件或目录；删除文件后所在目录为空时一并删除
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    } else if path.is_file() {
+        fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        if let Some(parent) = path.parent() {
+            // 目录非空时会失败，忽略即可
+            let _ = fs::remove_dir(parent);
+        }
+    }
+    Ok(())
+}
+
+/// 当前平台直接管理进程的实现
+#[cfg(target_os = "macos")]
+mod platform {
+    pub use crate::core::macos::{
+        install_database_via_homebrew as install, start_service_for_database as start,
+        stop_service_for_database as stop, uninstall_binaries,
+    };
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    pub use super::remove_binary_dir as uninstall_binaries;
+    pub use crate::core::windows::{
+        install_database as install, start_service as start, stop_service as stop,
+    };
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    pub use super::remove_binary_dir as uninstall_binaries;
+    pub use crate::core::linux::{
+        install_database as install, start_service as start, stop_service as stop,
+    };
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+mod platform {
+    use super::{InstallOptions, UNSUPPORTED_PLATFORM};
+    use crate::core::{DatabaseInfo, DatabaseType};
+    use anyhow::{bail, Result};
+    use std::path::Path;
+
+    pub fn install(
+        _db_type: &DatabaseType,
+        _storage_path: &Path,
+        _options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        bail!(UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn start(_db_info: &DatabaseInfo) -> Result<()> {
+        bail!(UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn stop(_db_info: &DatabaseInfo) -> Result<()> {
+        bail!(UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn uninstall_binaries(_db_info: &DatabaseInfo) -> Result<()> {
+        bail!(UNSUPPORTED_PLATFORM)
+    }
+}
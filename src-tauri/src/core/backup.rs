@@ -0,0 +1,801 @@
+use crate::core::{
+    crypto, utils, BackupMode, BackupRecord, DatabaseInfo, DatabaseStatus, DatabaseType,
+};
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+/// 恢复时是否需要先停止数据库
+///
+/// 逻辑备份（SQL / archive / Qdrant 快照）通过客户端导入，需要数据库保持运行；
+/// 文件级备份（RDB 快照、数据目录归档、物理备份）需要在数据库停止时替换文件。
+/// SQLite 没有服务进程，直接替换文件即可。
+pub fn requires_offline_restore(db_type: &DatabaseType, mode: BackupMode) -> bool {
+    mode == BackupMode::Physical
+        || !matches!(
+            db_type,
+            DatabaseType::MySQL
+                | DatabaseType::MariaDB
+                | DatabaseType::PostgreSQL
+                | DatabaseType::MongoDB
+                | DatabaseType::Qdrant
+                | DatabaseType::SQLite
+        )
+}
+
+/// 创建数据库备份，备份文件写入 `backup_dir`
+///
+/// 提供 `passphrase` 时备份文件会被加密，记录中只保存加密标记和 salt。
+pub fn create_backup(
+    db_info: &DatabaseInfo,
+    backup_dir: &Path,
+    mode: BackupMode,
+    passphrase: Option<&str>,
+) -> Result<BackupRecord> {
+    if mode == BackupMode::Export {
+        bail!("Exports are created with export_data");
+    }
+    if mode == BackupMode::Physical && db_info.db_type != DatabaseType::PostgreSQL {
+        bail!("Physical backups are only supported for PostgreSQL");
+    }
+    utils::ensure_dir(backup_dir)?;
+
+    let file_name = format!(
+        "{}-{}.{}",
+        db_info.db_type.as_str(),
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        archive_extension(&db_info.db_type, mode)
+    );
+    let target = backup_dir.join(file_name);
+
+    let result = match (&db_info.db_type, mode) {
+        (DatabaseType::PostgreSQL, BackupMode::Physical) => {
+            backup_postgresql_physical(db_info, &target)
+        }
+        (DatabaseType::MySQL | DatabaseType::MariaDB, _) => backup_mysql(db_info, &target),
+        (DatabaseType::PostgreSQL, _) => backup_postgresql(db_info, &target),
+        (DatabaseType::MongoDB, _) => backup_mongodb(db_info, &target),
+        (DatabaseType::Redis, _) => backup_redis(db_info, &target),
+        (DatabaseType::Qdrant, _) => backup_qdrant(db_info, &target),
+        (
+            DatabaseType::SurrealDB
+            | DatabaseType::Neo4j
+            | DatabaseType::SeekDB
+            | DatabaseType::OpenSearch
+            | DatabaseType::InfluxDB
+            | DatabaseType::Cassandra
+            | DatabaseType::SQLite
+            | DatabaseType::DuckDB
+            | DatabaseType::Custom,
+            _,
+        ) => archive_directory(Path::new(&db_info.data_path), &target),
+    };
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&target);
+        return Err(e);
+    }
+
+    let mut salt = None;
+    let target = match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) => {
+            let encrypted_target = target.with_file_name(format!(
+                "{}.enc",
+                target.file_name().unwrap_or_default().to_string_lossy()
+            ));
+            let result = crypto::encrypt_file(&target, &encrypted_target, passphrase);
+            let _ = fs::remove_file(&target);
+            match result {
+                Ok(value) => salt = Some(value),
+                Err(e) => {
+                    let _ = fs::remove_file(&encrypted_target);
+                    return Err(e);
+                }
+            }
+            encrypted_target
+        }
+        None => target,
+    };
+
+    let file_size = fs::metadata(&target)
+        .with_context(|| format!("Failed to read backup file {}", target.display()))?
+        .len();
+
+    Ok(BackupRecord {
+        id: utils::generate_id(),
+        database_id: db_info.id.clone(),
+        file_path: target.to_string_lossy().to_string(),
+        file_size,
+        created_at: utils::get_timestamp(),
+        mode,
+        encrypted: salt.is_some(),
+        salt,
+        remote_url: None,
+        export_format: None,
+        export_database: None,
+    })
+}
+
+/// 从备份文件恢复数据库
+///
+/// 调用方负责根据 [`requires_offline_restore`] 停止或启动数据库。
+/// 加密的备份需要提供创建时使用的口令。
+pub fn restore_backup(
+    db_info: &DatabaseInfo,
+    record: &BackupRecord,
+    passphrase: Option<&str>,
+) -> Result<()> {
+    if record.mode == BackupMode::Export {
+        bail!("Exports cannot be restored, import them instead");
+    }
+    let source = Path::new(&record.file_path);
+    if !source.exists() {
+        bail!("Backup file not found: {}", source.display());
+    }
+
+    if !record.encrypted {
+        return restore_from_file(db_info, record.mode, source);
+    }
+
+    let passphrase = match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) => passphrase,
+        None => bail!("Passphrase is required to restore an encrypted backup"),
+    };
+    let salt = record
+        .salt
+        .as_deref()
+        .context("Encrypted backup is missing salt")?;
+
+    // 解密到临时文件后再走普通恢复流程
+    let decrypted = source.with_extension("restoring");
+    let result = crypto::decrypt_file(source, &decrypted, passphrase, salt)
+        .and_then(|_| restore_from_file(db_info, record.mode, &decrypted));
+    let _ = fs::remove_file(&decrypted);
+    result
+}
+
+fn restore_from_file(db_info: &DatabaseInfo, mode: BackupMode, source: &Path) -> Result<()> {
+    match (&db_info.db_type, mode) {
+        (DatabaseType::PostgreSQL, BackupMode::Physical) => {
+            restore_postgresql_physical(db_info, source)
+        }
+        (DatabaseType::MySQL | DatabaseType::MariaDB, _) => restore_mysql(db_info, source),
+        (DatabaseType::PostgreSQL, _) => restore_postgresql(db_info, source),
+        (DatabaseType::MongoDB, _) => restore_mongodb(db_info, source),
+        (DatabaseType::Redis, _) => restore_redis(db_info, source),
+        (DatabaseType::Qdrant, _) => restore_qdrant(db_info, source),
+        (
+            DatabaseType::SurrealDB
+            | DatabaseType::Neo4j
+            | DatabaseType::SeekDB
+            | DatabaseType::OpenSearch
+            | DatabaseType::InfluxDB
+            | DatabaseType::Cassandra
+            | DatabaseType::SQLite
+            | DatabaseType::DuckDB
+            | DatabaseType::Custom,
+            _,
+        ) => restore_directory(source, Path::new(&db_info.data_path)),
+    }
+}
+
+/// 判断备份是否超出保留期限，`retention_days` 为 0 时表示永久保留
+///
+/// 导出文件位于用户选择的位置，不参与自动清理。
+pub fn is_expired(record: &BackupRecord, retention_days: u32) -> bool {
+    if retention_days == 0 || record.mode == BackupMode::Export {
+        return false;
+    }
+
+    match chrono::DateTime::parse_from_rfc3339(&record.created_at) {
+        Ok(created_at) => {
+            let age = chrono::Local::now().signed_duration_since(created_at);
+            age > chrono::Duration::days(i64::from(retention_days))
+        }
+        Err(_) => false,
+    }
+}
+
+/// 删除备份文件，返回释放的磁盘空间（字节）
+///
+/// CSV / JSON 导出是一个目录，连同其中的文件一起删除。
+pub fn remove_backup_file(record: &BackupRecord) -> Result<u64> {
+    let path = Path::new(&record.file_path);
+    if !path.exists() {
+        return Ok(0);
+    }
+    if path.is_dir() {
+        let size = utils::dir_size(path);
+        fs::remove_dir_all(path)
+            .with_context(|| format!("Failed to delete export {}", path.display()))?;
+        return Ok(size);
+    }
+
+    let size = fs::metadata(path)?.len();
+    fs::remove_file(path)
+        .with_context(|| format!("Failed to delete backup file {}", path.display()))?;
+    Ok(size)
+}
+
+/// 清理 WAL 归档目录中恢复最早保留的物理备份时用不到的文件，返回释放的磁盘空间（字节）
+///
+/// 与 pg_archivecleanup 的做法一致：pg_basebackup 结束时会归档 `<起始段>.<偏移>.backup` 文件，
+/// 起始段之前的 WAL 段都可以删除。`oldest_backup` 为 None（没有保留的物理备份）时删除全部 WAL 段；
+/// 找不到该备份对应的 .backup 文件时不做处理。时间线历史文件（.history）始终保留。
+pub fn prune_wal_archive(wal_dir: &Path, oldest_backup: Option<SystemTime>) -> Result<u64> {
+    let Ok(entries) = fs::read_dir(wal_dir) else {
+        return Ok(0);
+    };
+    let files: Vec<(String, SystemTime, u64)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            (metadata.is_file() && is_wal_file(&name)).then(|| {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                (name, modified, metadata.len())
+            })
+        })
+        .collect();
+
+    // 备份记录的时间在 pg_basebackup 结束之后，留出余量匹配 .backup 文件的归档时间
+    let boundary = match oldest_backup {
+        Some(created_at) => {
+            let latest = created_at + Duration::from_secs(60);
+            match files
+                .iter()
+                .filter(|(name, modified, _)| name.ends_with(".backup") && *modified <= latest)
+                .map(|(name, _, _)| name)
+                .max()
+            {
+                Some(name) => Some(name[8..24].to_string()),
+                None => return Ok(0),
+            }
+        }
+        None => None,
+    };
+
+    let mut reclaimed = 0;
+    for (name, _, size) in &files {
+        if name.ends_with(".history") {
+            continue;
+        }
+        // 比较段号时忽略前 8 位的时间线
+        if boundary
+            .as_deref()
+            .is_some_and(|boundary| &name[8..24] >= boundary)
+        {
+            continue;
+        }
+        let path = wal_dir.join(name);
+        fs::remove_file(&path)
+            .with_context(|| format!("Failed to delete WAL file {}", path.display()))?;
+        reclaimed += size;
+    }
+    Ok(reclaimed)
+}
+
+/// WAL 段、.partial、.backup 和 .history 文件，文件名以 24 位十六进制段名开头
+fn is_wal_file(name: &str) -> bool {
+    name.len() >= 24 && name[..24].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn archive_extension(db_type: &DatabaseType, mode: BackupMode) -> &'static str {
+    if mode == BackupMode::Physical {
+        return "base.tar.gz";
+    }
+
+    match db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::PostgreSQL => "sql.gz",
+        DatabaseType::MongoDB => "archive.gz",
+        DatabaseType::Redis => "rdb.gz",
+        DatabaseType::Qdrant
+        | DatabaseType::SurrealDB
+        | DatabaseType::Neo4j
+        | DatabaseType::SeekDB
+        | DatabaseType::OpenSearch
+        | DatabaseType::InfluxDB
+        | DatabaseType::Cassandra
+        | DatabaseType::SQLite
+        | DatabaseType::DuckDB
+        | DatabaseType::Custom => "tar.gz",
+    }
+}
+
+fn ensure_running(db_info: &DatabaseInfo) -> Result<()> {
+    if db_info.status != DatabaseStatus::Running {
+        bail!("{} must be running to perform this operation", db_info.name);
+    }
+    Ok(())
+}
+
+// --- MySQL ---
+
+pub(crate) fn mysql_command(db_info: &DatabaseInfo, binary: &str) -> Command {
+    // MariaDB 11 起 mysql* 只是兼容软链接，调用时会打印弃用警告
+    let binary = match (&db_info.db_type, binary) {
+        (DatabaseType::MariaDB, "mysql") => "mariadb",
+        (DatabaseType::MariaDB, "mysqldump") => "mariadb-dump",
+        _ => binary,
+    };
+    let mut command = Command::new(utils::resolve_db_binary(&db_info.install_path, binary));
+    command
+        .arg("--host=127.0.0.1")
+        .arg(format!("--port={}", db_info.port))
+        .arg(format!(
+            "--user={}",
+            db_info.username.as_deref().unwrap_or("root")
+        ));
+    // 通过环境变量传递密码，避免出现在进程列表中
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        command.env("MYSQL_PWD", password);
+    }
+    command
+}
+
+fn backup_mysql(db_info: &DatabaseInfo, target: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+    let mut command = mysql_command(db_info, "mysqldump");
+    command
+        .arg("--all-databases")
+        .arg("--single-transaction")
+        .arg("--routines")
+        .arg("--events");
+    dump_to_gzip(command, target)
+}
+
+fn restore_mysql(db_info: &DatabaseInfo, source: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+    restore_from_gzip(mysql_command(db_info, "mysql"), source)
+}
+
+// --- PostgreSQL ---
+
+pub(crate) fn postgresql_command(db_info: &DatabaseInfo, binary: &str) -> Command {
+    let mut command = Command::new(utils::resolve_db_binary(&db_info.install_path, binary));
+    command
+        .arg("--host=127.0.0.1")
+        .arg(format!("--port={}", db_info.port))
+        .arg(format!(
+            "--username={}",
+            db_info.username.as_deref().unwrap_or("postgres")
+        ))
+        .arg("--no-password");
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        command.env("PGPASSWORD", password);
+    }
+    command
+}
+
+fn backup_postgresql(db_info: &DatabaseInfo, target: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+    dump_to_gzip(postgresql_command(db_info, "pg_dumpall"), target)
+}
+
+fn restore_postgresql(db_info: &DatabaseInfo, source: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+    let mut command = postgresql_command(db_info, "psql");
+    command.arg("--dbname=postgres").arg("--quiet");
+    restore_from_gzip(command, source)
+}
+
+/// 使用 pg_basebackup 生成物理备份，WAL 一并打包以保证备份自洽
+fn backup_postgresql_physical(db_info: &DatabaseInfo, target: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+    let mut command = postgresql_command(db_info, "pg_basebackup");
+    command
+        .arg("--pgdata=-")
+        .arg("--format=tar")
+        .arg("--wal-method=fetch")
+        .arg("--checkpoint=fast");
+    dump_to_gzip(command, target)
+}
+
+fn restore_postgresql_physical(db_info: &DatabaseInfo, source: &Path) -> Result<()> {
+    let data_dir = Path::new(&db_info.data_path);
+    restore_directory(source, data_dir)?;
+
+    // PostgreSQL 拒绝在权限过宽的数据目录上启动
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(data_dir, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+// --- MongoDB ---
+
+/// 保存 MongoDB 登录密码的临时配置文件（工具的 `--config` 参数），释放时删除
+pub(crate) struct MongoToolConfig(PathBuf);
+
+impl Drop for MongoToolConfig {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// mongodump、mongorestore、mongoexport 的命令
+///
+/// 有用户名时以 admin 库认证，密码写入只有当前用户可读的临时配置文件，不出现在进程参数中。
+/// 返回的配置文件需要保留到命令结束。
+pub(crate) fn mongodb_command(
+    db_info: &DatabaseInfo,
+    binary: &str,
+) -> Result<(Command, Option<MongoToolConfig>)> {
+    let mut command = Command::new(utils::resolve_db_binary(&db_info.install_path, binary));
+    command
+        .arg("--host=127.0.0.1")
+        .arg(format!("--port={}", db_info.port));
+    let Some(username) = db_info.username.as_deref().filter(|u| !u.is_empty()) else {
+        return Ok((command, None));
+    };
+
+    let path = std::env::temp_dir().join(format!("local-db-mongo-{}.yaml", utils::generate_id()));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let config = MongoToolConfig(path);
+    // JSON 字符串同时是合法的 YAML 双引号字符串
+    options
+        .open(&config.0)
+        .and_then(|mut file| {
+            writeln!(
+                file,
+                "password: {}",
+                serde_json::to_string(db_info.password.as_deref().unwrap_or_default())?
+            )
+        })
+        .context("Failed to write the MongoDB tool config")?;
+    command
+        .arg(format!("--username={}", username))
+        .arg("--authenticationDatabase=admin")
+        .arg(format!("--config={}", config.0.display()));
+    Ok((command, Some(config)))
+}
+
+fn backup_mongodb(db_info: &DatabaseInfo, target: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+    let (mut command, _config) = mongodb_command(db_info, "mongodump")?;
+    command.arg("--archive");
+    dump_to_gzip(command, target)
+}
+
+fn restore_mongodb(db_info: &DatabaseInfo, source: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+    let (mut command, _config) = mongodb_command(db_info, "mongorestore")?;
+    command.arg("--archive").arg("--drop");
+    restore_from_gzip(command, source)
+}
+
+// --- Redis ---
+
+fn backup_redis(db_info: &DatabaseInfo, target: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+
+    // 先执行 SAVE，确保 dump.rdb 为最新快照
+    let mut command = Command::new(utils::resolve_db_binary(&db_info.install_path, "redis-cli"));
+    command.arg("-p").arg(db_info.port.to_string()).arg("SAVE");
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        command.env("REDISCLI_AUTH", password);
+    }
+    let output = command.output().context("Failed to run redis-cli SAVE")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || !stdout.trim().eq_ignore_ascii_case("OK") {
+        bail!(
+            "redis-cli SAVE failed: {}{}",
+            stdout.trim(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let rdb_path = Path::new(&db_info.data_path).join("dump.rdb");
+    let mut source = fs::File::open(&rdb_path)
+        .with_context(|| format!("Failed to open {}", rdb_path.display()))?;
+    let mut encoder = GzEncoder::new(fs::File::create(target)?, Compression::default());
+    std::io::copy(&mut source, &mut encoder).context("Failed to compress dump.rdb")?;
+    encoder.finish()?;
+    Ok(())
+}
+
+fn restore_redis(db_info: &DatabaseInfo, source: &Path) -> Result<()> {
+    let data_dir = Path::new(&db_info.data_path);
+    utils::ensure_dir(data_dir)?;
+
+    let mut decoder = GzDecoder::new(fs::File::open(source)?);
+    let mut target = fs::File::create(data_dir.join("dump.rdb"))?;
+    std::io::copy(&mut decoder, &mut target).context("Failed to restore dump.rdb")?;
+    Ok(())
+}
+
+// --- Qdrant ---
+
+/// 快照文件在备份归档中的扩展名，文件名即集合名称
+const QDRANT_SNAPSHOT_EXTENSION: &str = "snapshot";
+
+pub(crate) fn qdrant_client(db_info: &DatabaseInfo) -> Result<reqwest::blocking::Client> {
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(api_key) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        headers.insert("api-key", api_key.parse()?);
+    }
+    // 快照可能很大，不设置请求超时
+    Ok(reqwest::blocking::Client::builder()
+        .default_headers(headers)
+        .timeout(None)
+        .build()?)
+}
+
+pub(crate) fn qdrant_url(db_info: &DatabaseInfo, segments: &[&str]) -> Result<url::Url> {
+    let mut url = url::Url::parse(&format!("http://127.0.0.1:{}", db_info.port))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("Invalid Qdrant endpoint"))?
+        .extend(segments);
+    Ok(url)
+}
+
+pub(crate) fn qdrant_json(response: reqwest::blocking::Response) -> Result<serde_json::Value> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().unwrap_or_default();
+        bail!("Qdrant returned {}: {}", status, body.trim());
+    }
+    Ok(response.json()?)
+}
+
+/// 通过快照 API 为每个集合创建快照，下载后打包为一个 tar.gz
+fn backup_qdrant(db_info: &DatabaseInfo, target: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+    let client = qdrant_client(db_info)?;
+
+    let collections = qdrant_json(
+        client
+            .get(qdrant_url(db_info, &["collections"])?)
+            .send()
+            .context("Failed to list Qdrant collections")?,
+    )?;
+    let names: Vec<String> = collections["result"]["collections"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item["name"].as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let encoder = GzEncoder::new(fs::File::create(target)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    let download_path = target.with_extension("download");
+
+    for name in &names {
+        let mut create_url = qdrant_url(db_info, &["collections", name, "snapshots"])?;
+        create_url.query_pairs_mut().append_pair("wait", "true");
+        let snapshot = qdrant_json(
+            client
+                .post(create_url)
+                .send()
+                .with_context(|| format!("Failed to create snapshot for {}", name))?,
+        )?;
+        let snapshot_name = snapshot["result"]["name"]
+            .as_str()
+            .context("Qdrant did not return a snapshot name")?
+            .to_string();
+        let snapshot_url =
+            qdrant_url(db_info, &["collections", name, "snapshots", &snapshot_name])?;
+
+        let result = (|| -> Result<()> {
+            let mut response = client.get(snapshot_url.clone()).send()?;
+            if !response.status().is_success() {
+                bail!("Qdrant returned {} downloading snapshot", response.status());
+            }
+            let mut file = fs::File::create(&download_path)?;
+            response.copy_to(&mut file)?;
+            builder.append_path_with_name(
+                &download_path,
+                format!("{}.{}", name, QDRANT_SNAPSHOT_EXTENSION),
+            )?;
+            Ok(())
+        })();
+
+        // 快照已打包进备份文件，删除服务端与本地临时副本
+        let _ = client.delete(snapshot_url).send();
+        let _ = fs::remove_file(&download_path);
+        result.with_context(|| format!("Failed to download snapshot for {}", name))?;
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// 逐个上传快照恢复集合，已存在的集合会被快照内容覆盖
+fn restore_qdrant(db_info: &DatabaseInfo, source: &Path) -> Result<()> {
+    ensure_running(db_info)?;
+    let client = qdrant_client(db_info)?;
+    let upload_path = source.with_extension("upload");
+
+    let mut archive = tar::Archive::new(GzDecoder::new(fs::File::open(source)?));
+    let mut restored = 0;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.into_owned();
+        if entry_path
+            .extension()
+            .is_none_or(|ext| ext != QDRANT_SNAPSHOT_EXTENSION)
+        {
+            continue;
+        }
+        let name = match entry_path.file_stem() {
+            Some(stem) => stem.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        let result = (|| -> Result<()> {
+            entry.unpack(&upload_path)?;
+            let mut url = qdrant_url(db_info, &["collections", &name, "snapshots", "upload"])?;
+            url.query_pairs_mut()
+                .append_pair("wait", "true")
+                .append_pair("priority", "snapshot");
+            let form = reqwest::blocking::multipart::Form::new().file("snapshot", &upload_path)?;
+            qdrant_json(client.post(url).multipart(form).send()?)?;
+            Ok(())
+        })();
+        let _ = fs::remove_file(&upload_path);
+        result.with_context(|| format!("Failed to restore collection {}", name))?;
+        restored += 1;
+    }
+
+    if restored == 0 {
+        bail!("Backup does not contain any Qdrant snapshots");
+    }
+    Ok(())
+}
+
+// --- 数据目录归档 ---
+
+fn archive_directory(data_dir: &Path, target: &Path) -> Result<()> {
+    if !data_dir.is_dir() {
+        bail!("Data directory not found: {}", data_dir.display());
+    }
+
+    let encoder = GzEncoder::new(fs::File::create(target)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    for entry in fs::read_dir(data_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+
+        // PID 文件与运行中的进程绑定，不应被恢复
+        if path.extension().is_some_and(|ext| ext == "pid") {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() {
+            builder.append_dir_all(&name, &path)?;
+        } else {
+            builder.append_path_with_name(&path, &name)?;
+        }
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+fn restore_directory(source: &Path, data_dir: &Path) -> Result<()> {
+    let staging = data_dir.with_extension("restoring");
+    let previous = data_dir.with_extension("pre-restore");
+
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    utils::ensure_dir(&staging)?;
+
+    let mut archive = tar::Archive::new(GzDecoder::new(fs::File::open(source)?));
+    if let Err(e) = archive.unpack(&staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e).context("Failed to extract backup archive");
+    }
+
+    // 先将旧数据移走，替换成功后再删除，失败时回滚
+    if previous.exists() {
+        fs::remove_dir_all(&previous)?;
+    }
+    if data_dir.exists() {
+        fs::rename(data_dir, &previous).context("Failed to move current data directory")?;
+    }
+    if let Err(e) = fs::rename(&staging, data_dir) {
+        if previous.exists() {
+            let _ = fs::rename(&previous, data_dir);
+        }
+        return Err(e).context("Failed to replace data directory");
+    }
+    if previous.exists() {
+        let _ = fs::remove_dir_all(&previous);
+    }
+
+    Ok(())
+}
+
+// --- 进程管道辅助函数 ---
+
+/// 运行导出命令，将 stdout 以 gzip 压缩写入目标文件
+fn dump_to_gzip(mut command: Command, target: &Path) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+
+    let stderr = collect_stderr(&mut child);
+    let mut stdout = child.stdout.take().context("Failed to capture stdout")?;
+    let mut encoder = GzEncoder::new(fs::File::create(target)?, Compression::default());
+    let copy_result = std::io::copy(&mut stdout, &mut encoder);
+
+    let status = child.wait()?;
+    let stderr = stderr.join().unwrap_or_default();
+    copy_result.context("Failed to write backup file")?;
+    encoder.finish()?;
+
+    if !status.success() {
+        bail!(
+            "{:?} exited with {}: {}",
+            command.get_program(),
+            status,
+            stderr.trim()
+        );
+    }
+    Ok(())
+}
+
+/// 解压 gzip 备份文件并通过 stdin 传给导入命令
+fn restore_from_gzip(mut command: Command, source: &Path) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+
+    let stderr = collect_stderr(&mut child);
+    let mut decoder = GzDecoder::new(fs::File::open(source)?);
+    let copy_result = {
+        let mut stdin = child.stdin.take().context("Failed to capture stdin")?;
+        std::io::copy(&mut decoder, &mut stdin).and_then(|_| stdin.flush())
+    };
+
+    let status = child.wait()?;
+    let stderr = stderr.join().unwrap_or_default();
+    copy_result.context("Failed to stream backup file")?;
+
+    if !status.success() {
+        bail!(
+            "{:?} exited with {}: {}",
+            command.get_program(),
+            status,
+            stderr.trim()
+        );
+    }
+    Ok(())
+}
+
+fn collect_stderr(child: &mut std::process::Child) -> std::thread::JoinHandle<String> {
+    let stderr = child.stderr.take();
+    std::thread::spawn(move || {
+        let mut buffer = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut buffer);
+        }
+        buffer
+    })
+}
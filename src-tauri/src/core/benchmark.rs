@@ -0,0 +1,529 @@
+use crate::core::{client, query, utils, DatabaseInfo, DatabaseStatus, DatabaseType};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri_plugin_log::log;
+
+/// 压测使用的临时数据库，压测结束后删除
+const BENCH_DATABASE: &str = "local_db_bench";
+
+/// 保留的压测结果数，超出时丢弃最旧的
+const HISTORY_LIMIT: usize = 500;
+
+/// 检查压测进程是否结束的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 压测进程超过预计时长这么久仍未结束时终止
+const RUN_GRACE: Duration = Duration::from_secs(120);
+
+/// 串行化压测历史的读写，避免同时完成的压测互相覆盖
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// 压测强度
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BenchmarkProfile {
+    /// 10 秒、4 个连接，快速验证
+    Quick,
+    /// 60 秒、16 个连接
+    #[default]
+    Standard,
+    /// 5 分钟、64 个连接，数据量更大
+    Heavy,
+}
+
+impl BenchmarkProfile {
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(match self {
+            BenchmarkProfile::Quick => 10,
+            BenchmarkProfile::Standard => 60,
+            BenchmarkProfile::Heavy => 300,
+        })
+    }
+
+    pub fn clients(&self) -> u32 {
+        match self {
+            BenchmarkProfile::Quick => 4,
+            BenchmarkProfile::Standard => 16,
+            BenchmarkProfile::Heavy => 64,
+        }
+    }
+
+    /// 测试数据量：sysbench 每张表的行数，pgbench 的 scale 为其 1/100000
+    fn rows(&self) -> u32 {
+        match self {
+            BenchmarkProfile::Quick => 100_000,
+            BenchmarkProfile::Standard => 1_000_000,
+            BenchmarkProfile::Heavy => 5_000_000,
+        }
+    }
+
+    /// redis-benchmark 按请求数而不是时长运行，按每秒约 5 万次估算
+    fn requests(&self) -> u64 {
+        self.duration().as_secs() * 50_000
+    }
+}
+
+/// 一次压测的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub id: String,
+    pub database_id: String,
+    pub database_name: String,
+    pub db_type: DatabaseType,
+    pub version: String,
+    /// 使用的压测工具，如 `sysbench`、`pgbench`
+    pub tool: String,
+    pub profile: BenchmarkProfile,
+    /// 备注，如压测时使用的配置预设，便于对比
+    pub label: Option<String>,
+    pub clients: u32,
+    pub duration_secs: u64,
+    /// 每秒事务数（sysbench、pgbench）或每秒请求数（redis-benchmark、memtier）
+    pub throughput: f64,
+    pub avg_latency_ms: Option<f64>,
+    pub p95_latency_ms: Option<f64>,
+    pub created_at: String,
+}
+
+/// 压测工具输出中解析出的指标
+#[derive(Debug, Default, PartialEq)]
+struct Metrics {
+    throughput: f64,
+    avg_latency_ms: Option<f64>,
+    p95_latency_ms: Option<f64>,
+}
+
+/// 对运行中的实例压测：MySQL 协议的数据库使用 sysbench，PostgreSQL 使用 pgbench，
+/// Redis 优先使用 memtier_benchmark，未安装时使用实例自带的 redis-benchmark
+///
+/// SQL 数据库的测试数据写入临时数据库 `local_db_bench`，结束后删除。
+/// `on_progress` 接收进度（0-100）和当前阶段的说明。
+pub fn run(
+    db_info: &DatabaseInfo,
+    profile: BenchmarkProfile,
+    label: Option<String>,
+    on_progress: impl Fn(u8, &str),
+) -> Result<BenchmarkResult> {
+    if db_info.status != DatabaseStatus::Running {
+        bail!("{} must be running to run a benchmark", db_info.name);
+    }
+
+    let (tool, metrics) = match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::SeekDB => {
+            with_bench_database(db_info, &on_progress, || {
+                run_sysbench(db_info, profile, &on_progress)
+            })
+            .map(|metrics| ("sysbench", metrics))?
+        }
+        DatabaseType::PostgreSQL => with_bench_database(db_info, &on_progress, || {
+            run_pgbench(db_info, profile, &on_progress)
+        })
+        .map(|metrics| ("pgbench", metrics))?,
+        DatabaseType::Redis => match client::find_in_path("memtier_benchmark") {
+            Some(program) => (
+                "memtier_benchmark",
+                run_memtier(db_info, &program, profile, &on_progress)?,
+            ),
+            None => (
+                "redis-benchmark",
+                run_redis_benchmark(db_info, profile, &on_progress)?,
+            ),
+        },
+        _ => bail!(
+            "Benchmarks are not supported for {}",
+            db_info.db_type.display_name()
+        ),
+    };
+
+    Ok(BenchmarkResult {
+        id: utils::generate_id(),
+        database_id: db_info.id.clone(),
+        database_name: db_info.name.clone(),
+        db_type: db_info.db_type.clone(),
+        version: db_info.version.clone(),
+        tool: tool.to_string(),
+        profile,
+        label,
+        clients: profile.clients(),
+        duration_secs: profile.duration().as_secs(),
+        throughput: metrics.throughput,
+        avg_latency_ms: metrics.avg_latency_ms,
+        p95_latency_ms: metrics.p95_latency_ms,
+        created_at: utils::get_timestamp(),
+    })
+}
+
+/// 创建临时数据库后执行 `bench`，无论成功与否都删除临时数据库
+fn with_bench_database(
+    db_info: &DatabaseInfo,
+    on_progress: &dyn Fn(u8, &str),
+    bench: impl FnOnce() -> Result<Metrics>,
+) -> Result<Metrics> {
+    on_progress(0, "Creating the benchmark database...");
+    let create = match db_info.db_type {
+        DatabaseType::PostgreSQL => format!("CREATE DATABASE {}", BENCH_DATABASE),
+        _ => format!("CREATE DATABASE IF NOT EXISTS {}", BENCH_DATABASE),
+    };
+    // 上次压测异常退出时可能残留
+    drop_bench_database(db_info)?;
+    query::execute(db_info, None, &create, 1, query::DEFAULT_QUERY_TIMEOUT)
+        .context("Failed to create the benchmark database")?;
+
+    let result = bench();
+    on_progress(99, "Removing the benchmark database...");
+    if let Err(e) = drop_bench_database(db_info) {
+        log::warn!("Failed to drop {}: {:#}", BENCH_DATABASE, e);
+    }
+    result
+}
+
+fn drop_bench_database(db_info: &DatabaseInfo) -> Result<()> {
+    let sql = format!("DROP DATABASE IF EXISTS {}", BENCH_DATABASE);
+    query::execute(db_info, None, &sql, 1, query::DEFAULT_QUERY_TIMEOUT).map(|_| ())
+}
+
+fn run_sysbench(
+    db_info: &DatabaseInfo,
+    profile: BenchmarkProfile,
+    on_progress: &dyn Fn(u8, &str),
+) -> Result<Metrics> {
+    let sysbench = |mode: &str| {
+        let mut command = Command::new("sysbench");
+        command
+            .arg("oltp_read_write")
+            .arg("--db-driver=mysql")
+            .arg("--mysql-host=127.0.0.1")
+            .arg(format!("--mysql-port={}", db_info.port))
+            .arg(format!(
+                "--mysql-user={}",
+                db_info.username.as_deref().unwrap_or("root")
+            ))
+            .arg(format!("--mysql-db={}", BENCH_DATABASE))
+            .arg("--tables=4")
+            .arg(format!("--table-size={}", profile.rows() / 4))
+            .arg(format!("--threads={}", profile.clients()))
+            .arg(format!("--time={}", profile.duration().as_secs()))
+            .arg(mode);
+        // sysbench 只能通过参数传递密码
+        if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+            command.arg(format!("--mysql-password={}", password));
+        }
+        command
+    };
+
+    on_progress(5, "Preparing test data...");
+    run_tool(sysbench("prepare"), None, &|_| {})?;
+    let output = run_tool(sysbench("run"), Some(profile.duration()), &|ratio| {
+        on_progress(progress(ratio), "Running sysbench...")
+    })?;
+    parse_sysbench(&output)
+}
+
+fn run_pgbench(
+    db_info: &DatabaseInfo,
+    profile: BenchmarkProfile,
+    on_progress: &dyn Fn(u8, &str),
+) -> Result<Metrics> {
+    let pgbench = || {
+        let mut command = Command::new(utils::resolve_db_binary(&db_info.install_path, "pgbench"));
+        command
+            .arg("--host=127.0.0.1")
+            .arg(format!("--port={}", db_info.port))
+            .arg(format!(
+                "--username={}",
+                db_info.username.as_deref().unwrap_or("postgres")
+            ));
+        if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+            command.env("PGPASSWORD", password);
+        }
+        command
+    };
+
+    on_progress(5, "Preparing test data...");
+    let mut init = pgbench();
+    init.arg("--initialize")
+        .arg("--quiet")
+        .arg(format!("--scale={}", (profile.rows() / 100_000).max(1)))
+        .arg(BENCH_DATABASE);
+    run_tool(init, None, &|_| {})?;
+
+    let clients = profile.clients();
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get() as u32)
+        .min(clients);
+    let mut bench = pgbench();
+    bench
+        .arg(format!("--client={}", clients))
+        .arg(format!("--jobs={}", threads))
+        .arg(format!("--time={}", profile.duration().as_secs()))
+        .arg(BENCH_DATABASE);
+    let output = run_tool(bench, Some(profile.duration()), &|ratio| {
+        on_progress(progress(ratio), "Running pgbench...")
+    })?;
+    parse_pgbench(&output)
+}
+
+fn run_memtier(
+    db_info: &DatabaseInfo,
+    program: &Path,
+    profile: BenchmarkProfile,
+    on_progress: &dyn Fn(u8, &str),
+) -> Result<Metrics> {
+    // memtier 的连接数为每个线程的连接数
+    const THREADS: u32 = 4;
+    let mut command = Command::new(program);
+    command
+        .arg("--server=127.0.0.1")
+        .arg(format!("--port={}", db_info.port))
+        .arg(format!("--threads={}", THREADS))
+        .arg(format!(
+            "--clients={}",
+            (profile.clients() / THREADS).max(1)
+        ))
+        .arg(format!("--test-time={}", profile.duration().as_secs()))
+        .arg("--ratio=1:10")
+        .arg("--hide-histogram");
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        command.arg(format!("--authenticate={}", password));
+    }
+    let output = run_tool(command, Some(profile.duration()), &|ratio| {
+        on_progress(progress(ratio), "Running memtier_benchmark...")
+    })?;
+    parse_memtier(&output)
+}
+
+fn run_redis_benchmark(
+    db_info: &DatabaseInfo,
+    profile: BenchmarkProfile,
+    on_progress: &dyn Fn(u8, &str),
+) -> Result<Metrics> {
+    let mut command = Command::new(utils::resolve_db_binary(
+        &db_info.install_path,
+        "redis-benchmark",
+    ));
+    command
+        .args(["-h", "127.0.0.1", "-p", &db_info.port.to_string()])
+        .args(["-c", &profile.clients().to_string()])
+        .args(["-n", &profile.requests().to_string()])
+        .args(["-t", "set,get", "--csv"]);
+    // 通过环境变量传递密码，避免出现在进程列表中
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        command.env("REDISCLI_AUTH", password);
+    }
+    // 按请求数运行，进度按预计时长估算
+    let output = run_tool(command, Some(profile.duration()), &|ratio| {
+        on_progress(progress(ratio), "Running redis-benchmark...")
+    })?;
+    parse_redis_benchmark(&output)
+}
+
+/// 测试阶段占总进度的 10% 到 95%
+fn progress(ratio: f64) -> u8 {
+    (10.0 + ratio.clamp(0.0, 1.0) * 85.0) as u8
+}
+
+/// 运行压测工具并返回标准输出；`expected` 为预计时长，运行期间按已用时间报告进度（0-1）
+fn run_tool(
+    mut command: Command,
+    expected: Option<Duration>,
+    on_progress: &dyn Fn(f64),
+) -> Result<String> {
+    let program = Path::new(command.get_program())
+        .file_stem()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            bail!(
+                "{} is not installed, install it to run this benchmark",
+                program
+            )
+        }
+        Err(e) => return Err(e).with_context(|| format!("Failed to run {}", program)),
+    };
+
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if let Some(expected) = expected {
+            if started.elapsed() > expected + RUN_GRACE {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!("{} did not finish in time", program);
+            }
+            on_progress(started.elapsed().as_secs_f64() / expected.as_secs_f64());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        let detail = if stderr.trim().is_empty() {
+            &stdout
+        } else {
+            &stderr
+        };
+        bail!("{} failed: {}", program, detail.trim());
+    }
+    Ok(stdout)
+}
+
+fn read_in_background(
+    stream: Option<impl Read + Send + 'static>,
+) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut stream) = stream {
+            let _ = stream.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+/// 取出 `label` 之后的第一个数值，如 `transactions: 1234 (41.1 per sec.)` 中的 1234
+fn number_after(line: &str, label: &str) -> Option<f64> {
+    let rest = &line[line.find(label)? + label.len()..];
+    rest.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .find(|part| !part.is_empty())?
+        .parse()
+        .ok()
+}
+
+/// sysbench 的统计，如 `transactions: 12345 (411.42 per sec.)` 与延迟中的 `avg`、`95th percentile`
+fn parse_sysbench(output: &str) -> Result<Metrics> {
+    let mut metrics = Metrics::default();
+    let mut throughput = None;
+    for line in output.lines().map(str::trim) {
+        if line.starts_with("transactions:") {
+            throughput = number_after(line, "(");
+        } else if line.starts_with("avg:") {
+            metrics.avg_latency_ms = number_after(line, "avg:");
+        } else if line.starts_with("95th percentile:") {
+            metrics.p95_latency_ms = number_after(line, "percentile:");
+        }
+    }
+    metrics.throughput = throughput.context("Unexpected sysbench output")?;
+    Ok(metrics)
+}
+
+/// pgbench 的 `tps = 1234.5 (without initial connection time)` 和 `latency average = 3.2 ms`
+fn parse_pgbench(output: &str) -> Result<Metrics> {
+    let mut metrics = Metrics::default();
+    let mut throughput = None;
+    for line in output.lines().map(str::trim) {
+        if line.starts_with("tps =") && throughput.is_none() {
+            throughput = number_after(line, "=");
+        } else if line.starts_with("latency average =") {
+            metrics.avg_latency_ms = number_after(line, "=");
+        }
+    }
+    metrics.throughput = throughput.context("Unexpected pgbench output")?;
+    Ok(metrics)
+}
+
+/// memtier 汇总表中的 `Totals` 行，列依次为每秒请求数、命中、未命中、平均延迟……
+fn parse_memtier(output: &str) -> Result<Metrics> {
+    let line = output
+        .lines()
+        .rev()
+        .find(|line| line.trim_start().starts_with("Totals"))
+        .context("Unexpected memtier_benchmark output")?;
+    let columns: Vec<f64> = line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|column| column.parse().ok())
+        .collect();
+    Ok(Metrics {
+        throughput: *columns
+            .first()
+            .context("Unexpected memtier_benchmark output")?,
+        avg_latency_ms: columns.get(3).copied(),
+        p95_latency_ms: None,
+    })
+}
+
+/// redis-benchmark 的 CSV 输出，每个命令一行，取各命令的平均值
+///
+/// Redis 7 起包含表头和延迟列，之前的版本每行只有命令和每秒请求数。
+fn parse_redis_benchmark(output: &str) -> Result<Metrics> {
+    let rows: Vec<Vec<String>> = output
+        .lines()
+        .map(|line| {
+            line.split(',')
+                .map(|v| v.trim_matches('"').to_string())
+                .collect()
+        })
+        .collect();
+    let header = rows
+        .first()
+        .filter(|row| row.first().is_some_and(|v| v == "test"));
+    let column = |name: &str, fallback: Option<usize>| {
+        header
+            .and_then(|header| header.iter().position(|v| v == name))
+            .or(fallback)
+    };
+    let mean = |index: Option<usize>| -> Option<f64> {
+        let values: Vec<f64> = rows
+            .iter()
+            .skip(usize::from(header.is_some()))
+            .filter_map(|row| row.get(index?)?.parse().ok())
+            .collect();
+        (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+    };
+    Ok(Metrics {
+        throughput: mean(column("rps", Some(1))).context("Unexpected redis-benchmark output")?,
+        avg_latency_ms: mean(column("avg_latency_ms", None)),
+        p95_latency_ms: mean(column("p95_latency_ms", None)),
+    })
+}
+
+fn history_path(storage_path: &Path) -> PathBuf {
+    storage_path.join("benchmarks.json")
+}
+
+/// 压测历史，按时间顺序排列；`database_id` 不为空时只返回该数据库的结果
+pub fn history(storage_path: &Path, database_id: Option<&str>) -> Vec<BenchmarkResult> {
+    let _lock = HISTORY_LOCK.lock().unwrap();
+    let mut results = load_history(storage_path);
+    if let Some(id) = database_id {
+        results.retain(|result| result.database_id == id);
+    }
+    results
+}
+
+/// 保存一次压测结果
+pub fn record(storage_path: &Path, result: BenchmarkResult) -> Result<()> {
+    let _lock = HISTORY_LOCK.lock().unwrap();
+    let mut results = load_history(storage_path);
+    results.push(result);
+    if results.len() > HISTORY_LIMIT {
+        results.drain(..results.len() - HISTORY_LIMIT);
+    }
+    let path = history_path(storage_path);
+    fs::write(&path, serde_json::to_string_pretty(&results)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// 文件不存在或损坏时返回空历史
+fn load_history(storage_path: &Path) -> Vec<BenchmarkResult> {
+    fs::read_to_string(history_path(storage_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,234 @@
+use crate::core::backend::InstallOptions;
+use crate::core::{
+    network, utils, watchdog, BackendKind, DatabaseInfo, DatabaseStatus, DatabaseType,
+};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// 未指定版本时下载的 Cassandra 版本
+pub const DEFAULT_VERSION: &str = "5.0.2";
+
+/// 节点间通信端口，使用 Cassandra 默认值
+const STORAGE_PORT: u16 = 7000;
+
+/// nodetool 连接的 JMX 端口，使用 Cassandra 默认值
+const JMX_PORT: u16 = 7199;
+
+/// JVM 堆大小，不设置时 cassandra-env.sh 会按内存大小分配，本地开发偏大
+pub const MAX_HEAP_SIZE: &str = "1G";
+pub const HEAP_NEWSIZE: &str = "256M";
+
+/// Cassandra 5.0 支持的 Java 主版本
+const SUPPORTED_JAVA_VERSIONS: [u32; 2] = [11, 17];
+
+/// 下载 Cassandra 发行包并生成实例配置
+///
+/// 发行包不带 JRE，安装前先检查本机 Java。节点间通信和 JMX 端口固定为默认值，
+/// 同一时间只能运行一个实例。
+pub fn install(storage_path: &Path, options: &InstallOptions<'_>) -> Result<DatabaseInfo> {
+    detect_java()?;
+
+    let version = options.version.unwrap_or(DEFAULT_VERSION);
+    let bin_dir = utils::get_db_version_bin_path(storage_path, "cassandra", version);
+    if !bin_dir.join("bin").join("cassandra").exists() {
+        let url = format!(
+            "https://archive.apache.org/dist/cassandra/{version}/apache-cassandra-{version}-bin.tar.gz"
+        );
+        utils::download_and_extract_tar_gz("Cassandra", &url, &bin_dir, options.archive)?;
+    }
+
+    let instance_dir = utils::get_instance_dir_name("cassandra", options.instance_name);
+    let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+    let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+    let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
+    utils::ensure_dir(&data_dir)?;
+    utils::ensure_dir(&logs_dir)?;
+
+    let port = options.port.unwrap_or(9042);
+    let config_path = write_config(&bin_dir.join("conf"), &config_dir, &data_dir, port)?;
+
+    let mut db_info = DatabaseInfo {
+        id: utils::generate_id(),
+        name: options.instance_name.unwrap_or("Cassandra").to_string(),
+        db_type: DatabaseType::Cassandra,
+        backend: BackendKind::Native,
+        version: version.to_string(),
+        install_path: bin_dir.to_string_lossy().to_string(),
+        data_path: data_dir.to_string_lossy().to_string(),
+        log_path: logs_dir.join("system.log").to_string_lossy().to_string(),
+        port,
+        username: None,
+        password: None,
+        credential_ref: None,
+        config: Some(config_path.to_string_lossy().to_string()),
+        status: DatabaseStatus::Stopped,
+        auto_start: options.auto_start,
+        depends_on: Vec::new(),
+        env: HashMap::new(),
+        extra_args: Vec::new(),
+        schedules: Vec::new(),
+        on_demand: false,
+        replica_of: None,
+        bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+        pid: None,
+        created_at: utils::get_timestamp(),
+        updated_at: utils::get_timestamp(),
+    };
+
+    if options.auto_start {
+        start(&db_info)?;
+        db_info.status = DatabaseStatus::Running;
+    }
+
+    Ok(db_info)
+}
+
+/// 启动 Cassandra，启动脚本转入后台后把 JVM 的 PID 写入 `cassandra.pid`
+pub fn start(db_info: &DatabaseInfo) -> Result<()> {
+    detect_java()?;
+    if watchdog::is_process_alive(db_info) {
+        return Ok(());
+    }
+
+    let status = cassandra_command(db_info)?
+        .arg("-p")
+        .arg(watchdog::pid_file_path(db_info))
+        .args(&db_info.extra_args)
+        // 后台运行的 JVM 会继承输出管道，不能等待其关闭
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .context("Failed to start Cassandra")?;
+    if !status.success() {
+        bail!(
+            "Cassandra failed to start, see {} for details",
+            db_info.log_path
+        );
+    }
+    Ok(())
+}
+
+/// 通过 `nodetool stopdaemon` 停止 Cassandra，失败时向进程发送 SIGTERM
+pub fn stop(db_info: &DatabaseInfo) -> Result<()> {
+    let nodetool = Command::new(
+        Path::new(&db_info.install_path)
+            .join("bin")
+            .join("nodetool"),
+    )
+    .arg("-h")
+    .arg("127.0.0.1")
+    .arg("-p")
+    .arg(JMX_PORT.to_string())
+    .arg("stopdaemon")
+    .output();
+    let stopped = nodetool.is_ok_and(|output| output.status.success());
+
+    #[cfg(unix)]
+    if !stopped && watchdog::is_process_alive(db_info) {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+
+        if let Some(pid) = watchdog::read_pid(db_info) {
+            kill(Pid::from_raw(pid as i32), Signal::SIGTERM)
+                .context("Failed to send SIGTERM to Cassandra")?;
+        }
+    }
+    #[cfg(not(unix))]
+    if !stopped {
+        bail!("nodetool stopdaemon failed");
+    }
+
+    let _ = fs::remove_file(watchdog::pid_file_path(db_info));
+    Ok(())
+}
+
+/// 检查本机 Java 是否可以运行 Cassandra，返回 Java 主版本号
+pub fn detect_java() -> Result<u32> {
+    utils::detect_java("Cassandra", &SUPPORTED_JAVA_VERSIONS)
+}
+
+/// 在前台运行 Cassandra 的命令，由 launchd 等进程管理器负责启停
+pub fn foreground_command(db_info: &DatabaseInfo) -> Result<Command> {
+    let mut command = cassandra_command(db_info)?;
+    command.arg("-f");
+    Ok(command)
+}
+
+/// 发行包自带的 `cassandra` 脚本，配置和日志目录指向实例自己的目录
+fn cassandra_command(db_info: &DatabaseInfo) -> Result<Command> {
+    let config_dir = db_info
+        .config
+        .as_deref()
+        .and_then(|config| Path::new(config).parent())
+        .context("Config path missing")?;
+    let logs_dir = Path::new(&db_info.log_path)
+        .parent()
+        .context("Log path missing")?;
+
+    let mut command = Command::new(
+        Path::new(&db_info.install_path)
+            .join("bin")
+            .join("cassandra"),
+    );
+    command
+        .env("CASSANDRA_CONF", config_dir)
+        .env("CASSANDRA_LOG_DIR", logs_dir)
+        .env("MAX_HEAP_SIZE", MAX_HEAP_SIZE)
+        .env("HEAP_NEWSIZE", HEAP_NEWSIZE)
+        .envs(&db_info.env);
+    Ok(command)
+}
+
+/// 复制发行包的 conf 目录，并生成数据目录指向实例数据目录的 cassandra.yaml
+fn write_config(
+    dist_conf_dir: &Path,
+    config_dir: &Path,
+    data_dir: &Path,
+    port: u16,
+) -> Result<PathBuf> {
+    utils::copy_dir(dist_conf_dir, config_dir)
+        .with_context(|| format!("Failed to copy {}", dist_conf_dir.display()))?;
+
+    let data = |name: &str| data_dir.join(name).to_string_lossy().to_string();
+    let config_path = config_dir.join("cassandra.yaml");
+    let config_content = format!(
+        r#"# Cassandra configuration for local-db
+cluster_name: 'local-db'
+num_tokens: 16
+partitioner: org.apache.cassandra.dht.Murmur3Partitioner
+endpoint_snitch: SimpleSnitch
+
+data_file_directories:
+  - {data}
+commitlog_directory: {commitlog}
+saved_caches_directory: {saved_caches}
+hints_directory: {hints}
+cdc_raw_directory: {cdc_raw}
+commitlog_sync: periodic
+commitlog_sync_period: 10000ms
+
+seed_provider:
+  - class_name: org.apache.cassandra.locator.SimpleSeedProvider
+    parameters:
+      - seeds: "127.0.0.1:{storage_port}"
+
+listen_address: 127.0.0.1
+rpc_address: 127.0.0.1
+storage_port: {storage_port}
+native_transport_port: {port}
+"#,
+        data = data("data"),
+        commitlog = data("commitlog"),
+        saved_caches = data("saved_caches"),
+        hints = data("hints"),
+        cdc_raw = data("cdc_raw"),
+        storage_port = STORAGE_PORT,
+        port = port,
+    );
+    fs::write(&config_path, config_content).context("Failed to write cassandra.yaml")?;
+    Ok(config_path)
+}
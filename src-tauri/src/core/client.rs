@@ -0,0 +1,326 @@
+use crate::core::{
+    connection, launchd, sqlite, utils, ConnectionUriFormat, DatabaseClient, DatabaseInfo,
+    DatabaseType,
+};
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// 客户端是否支持该数据库类型
+pub fn supports(client: DatabaseClient, db_type: &DatabaseType) -> bool {
+    match client {
+        DatabaseClient::TablePlus => matches!(
+            db_type,
+            DatabaseType::MySQL
+                | DatabaseType::MariaDB
+                | DatabaseType::SeekDB
+                | DatabaseType::PostgreSQL
+                | DatabaseType::Redis
+                | DatabaseType::MongoDB
+                | DatabaseType::SQLite
+        ),
+        DatabaseClient::DBeaver | DatabaseClient::DataGrip => matches!(
+            db_type,
+            DatabaseType::MySQL
+                | DatabaseType::MariaDB
+                | DatabaseType::SeekDB
+                | DatabaseType::PostgreSQL
+                | DatabaseType::SQLite
+        ),
+        DatabaseClient::Psql => *db_type == DatabaseType::PostgreSQL,
+        DatabaseClient::RedisCli => *db_type == DatabaseType::Redis,
+    }
+}
+
+/// 本机已安装且支持该数据库类型的客户端
+pub fn detect(db_info: &DatabaseInfo) -> Vec<DatabaseClient> {
+    DatabaseClient::ALL
+        .into_iter()
+        .filter(|client| supports(*client, &db_info.db_type) && locate(*client, db_info).is_some())
+        .collect()
+}
+
+/// 用客户端打开数据库连接，连接参数通过 URL 或命令行参数传入，不需要手动复制凭据
+///
+/// psql 和 redis-cli 在新的终端窗口中运行。
+pub fn open(storage_path: &Path, db_info: &DatabaseInfo, client: DatabaseClient) -> Result<()> {
+    if !supports(client, &db_info.db_type) {
+        bail!(
+            "{} cannot open {} databases",
+            client.display_name(),
+            db_info.db_type.display_name()
+        );
+    }
+    let Some(program) = locate(client, db_info) else {
+        bail!("{} is not installed", client.display_name());
+    };
+
+    match client {
+        DatabaseClient::TablePlus => {
+            let uri = connection::connection_uri(db_info, ConnectionUriFormat::Native)?;
+            launch_app(&program, &[uri], true)
+        }
+        DatabaseClient::DBeaver => launch_app(
+            &program,
+            &["-con".to_string(), dbeaver_connection(db_info)?],
+            false,
+        ),
+        DatabaseClient::DataGrip => {
+            let project = write_datagrip_project(storage_path, db_info)?;
+            launch_app(&program, &[project.to_string_lossy().to_string()], true)
+        }
+        DatabaseClient::Psql => {
+            let uri = connection::connection_uri(db_info, ConnectionUriFormat::Native)?;
+            run_in_terminal(&program, &[uri])
+        }
+        DatabaseClient::RedisCli => {
+            let uri = connection::connection_uri(db_info, ConnectionUriFormat::Native)?;
+            run_in_terminal(&program, &["-u".to_string(), uri])
+        }
+    }
+}
+
+/// 客户端程序的位置，macOS 上为 `.app` 包，未安装时为 None
+///
+/// 命令行客户端优先使用实例自带的程序。
+fn locate(client: DatabaseClient, db_info: &DatabaseInfo) -> Option<PathBuf> {
+    let (app, commands): (&str, &[&str]) = match client {
+        DatabaseClient::TablePlus => ("TablePlus", &["tableplus"]),
+        DatabaseClient::DBeaver => ("DBeaver", &["dbeaver", "dbeaver-ce"]),
+        DatabaseClient::DataGrip => ("DataGrip", &["datagrip", "datagrip64"]),
+        DatabaseClient::Psql | DatabaseClient::RedisCli => {
+            let name = if client == DatabaseClient::Psql {
+                "psql"
+            } else {
+                "redis-cli"
+            };
+            let bundled = utils::resolve_db_binary(&db_info.install_path, name);
+            return if bundled.is_file() {
+                Some(bundled)
+            } else {
+                find_in_path(name)
+            };
+        }
+    };
+
+    if cfg!(target_os = "macos") {
+        return [
+            Some(PathBuf::from("/Applications")),
+            dirs::home_dir().map(|home| home.join("Applications")),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join(format!("{}.app", app)))
+        .find(|bundle| bundle.is_dir());
+    }
+
+    let mut candidates = Vec::new();
+    if cfg!(windows) {
+        if let Some(program_files) = std::env::var_os("ProgramFiles") {
+            let program_files = PathBuf::from(program_files);
+            candidates.push(program_files.join(app).join(format!("{}.exe", app)));
+            candidates.push(program_files.join(app).join(format!("{}.exe", commands[0])));
+        }
+        if let Some(local) = dirs::data_local_dir() {
+            candidates.push(local.join(app).join(format!("{}.exe", commands[0])));
+            candidates.push(
+                local
+                    .join("JetBrains")
+                    .join("Toolbox")
+                    .join("scripts")
+                    .join(format!("{}.cmd", commands[0])),
+            );
+        }
+    } else if let Some(home) = dirs::home_dir() {
+        // JetBrains Toolbox 生成的启动脚本
+        candidates.push(
+            home.join(".local/share/JetBrains/Toolbox/scripts")
+                .join(commands[0]),
+        );
+    }
+    candidates
+        .into_iter()
+        .find(|path| path.is_file())
+        .or_else(|| commands.iter().find_map(|name| find_in_path(name)))
+}
+
+/// 在 PATH 中查找可执行文件
+pub(crate) fn find_in_path(name: &str) -> Option<PathBuf> {
+    let file_name = if cfg!(windows) {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths)
+        .map(|dir| dir.join(&file_name))
+        .find(|path| path.is_file())
+}
+
+/// 启动图形客户端，不等待其退出
+///
+/// macOS 上通过 `open -a` 启动，`as_documents` 为 true 时参数作为要打开的 URL 或目录传入，否则作为启动参数传入。
+fn launch_app(program: &Path, args: &[String], as_documents: bool) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg("-a").arg(program);
+        if !as_documents {
+            command.arg("--args");
+        }
+        command
+    } else if program.extension().is_some_and(|ext| ext == "cmd") {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(program);
+        command
+    } else {
+        Command::new(program)
+    };
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to launch {}", program.display()))?;
+    Ok(())
+}
+
+/// 在新的终端窗口中运行命令行客户端
+fn run_in_terminal(program: &Path, args: &[String]) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = std::iter::once(program.to_string_lossy().to_string())
+            .chain(args.iter().cloned())
+            .map(|arg| format!("'{}'", arg.replace('\'', r"'\''")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let script = script.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut command = Command::new("osascript");
+        command
+            .arg("-e")
+            .arg(format!(
+                "tell application \"Terminal\" to do script \"{}\"",
+                script
+            ))
+            .arg("-e")
+            .arg("tell application \"Terminal\" to activate");
+        command
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]).arg(program).args(args);
+        command
+    } else {
+        // 依次尝试 Debian 系的默认终端和常见的终端程序
+        let Some((terminal, separator)) = [
+            ("x-terminal-emulator", "-e"),
+            ("gnome-terminal", "--"),
+            ("konsole", "-e"),
+            ("xfce4-terminal", "-x"),
+            ("xterm", "-e"),
+        ]
+        .into_iter()
+        .find(|(terminal, _)| find_in_path(terminal).is_some()) else {
+            bail!("No terminal emulator found");
+        };
+        let mut command = Command::new(terminal);
+        command.arg(separator).arg(program).args(args);
+        command
+    };
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to open a terminal")?;
+    Ok(())
+}
+
+/// DBeaver `-con` 参数的连接描述，各项以 `|` 分隔，不保存到 DBeaver 的工作区
+fn dbeaver_connection(db_info: &DatabaseInfo) -> Result<String> {
+    let mut params = vec![format!("name={}", db_info.name)];
+    if db_info.db_type == DatabaseType::SQLite {
+        params.push("driver=sqlite".to_string());
+        params.push(format!(
+            "database={}",
+            sqlite::default_file_path(db_info).to_string_lossy()
+        ));
+    } else {
+        let (driver, database) = match db_info.db_type {
+            DatabaseType::PostgreSQL => ("postgresql", "postgres"),
+            DatabaseType::MariaDB => ("mariadb", ""),
+            _ => ("mysql", ""),
+        };
+        params.push(format!("driver={}", driver));
+        params.push("host=127.0.0.1".to_string());
+        params.push(format!("port={}", db_info.port));
+        if !database.is_empty() {
+            params.push(format!("database={}", database));
+        }
+        if let Some(username) = db_info.username.as_deref() {
+            params.push(format!("user={}", username));
+        }
+        if let Some(password) = db_info.password.as_deref() {
+            params.push(format!("password={}", password));
+        }
+    }
+    params.push("connect=true".to_string());
+    params.push("save=false".to_string());
+
+    // DBeaver 不支持转义分隔符
+    if params.iter().any(|param| param.contains('|')) {
+        bail!("Connection parameters containing '|' cannot be passed to DBeaver");
+    }
+    Ok(params.join("|"))
+}
+
+/// 生成只包含该实例数据源的 DataGrip 项目目录 `<storage_path>/clients/datagrip/<id>`
+fn write_datagrip_project(storage_path: &Path, db_info: &DatabaseInfo) -> Result<PathBuf> {
+    let (driver, driver_class) = match db_info.db_type {
+        DatabaseType::PostgreSQL => ("postgresql", "org.postgresql.Driver"),
+        DatabaseType::MariaDB => ("mariadb", "org.mariadb.jdbc.Driver"),
+        DatabaseType::SQLite => ("sqlite.xerial", "org.sqlite.JDBC"),
+        _ => ("mysql.8", "com.mysql.cj.jdbc.Driver"),
+    };
+    let url = connection::connection_uri(db_info, ConnectionUriFormat::Jdbc)?;
+
+    // 同一实例每次使用相同的数据源 ID，DataGrip 中的会话和设置得以保留
+    let hash = hex::encode(Sha256::digest(db_info.id.as_bytes()));
+    let uuid = format!(
+        "{}-{}-{}-{}-{}",
+        &hash[0..8],
+        &hash[8..12],
+        &hash[12..16],
+        &hash[16..20],
+        &hash[20..32]
+    );
+
+    let project = storage_path
+        .join("clients")
+        .join("datagrip")
+        .join(&db_info.id);
+    let idea_dir = project.join(".idea");
+    utils::ensure_dir(&idea_dir)?;
+    let content = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<project version="4">
+  <component name="DataSourceManagerImpl" format="xml" multifile-model="true">
+    <data-source source="LOCAL" name="{name}" uuid="{uuid}">
+      <driver-ref>{driver}</driver-ref>
+      <synchronize>true</synchronize>
+      <jdbc-driver>{driver_class}</jdbc-driver>
+      <jdbc-url>{url}</jdbc-url>
+    </data-source>
+  </component>
+</project>
+"#,
+        name = launchd::xml_escape(&db_info.name),
+        uuid = uuid,
+        driver = driver,
+        driver_class = driver_class,
+        url = launchd::xml_escape(&url),
+    );
+    let path = idea_dir.join("dataSources.xml");
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(project)
+}
@@ -0,0 +1,114 @@
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// 将文本写入系统剪贴板
+///
+/// 通过系统自带的命令读写：macOS 为 pbcopy/pbpaste，Windows 为 PowerShell，Linux 为 wl-copy、xclip 或 xsel。
+pub fn write(text: &str) -> Result<()> {
+    for (program, args) in write_commands() {
+        let Ok(mut child) = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .context("Failed to write to the clipboard")?;
+        }
+        let status = child.wait().context("Failed to write to the clipboard")?;
+        if status.success() {
+            return Ok(());
+        }
+    }
+    bail!("No clipboard tool is available")
+}
+
+/// 读取系统剪贴板中的文本，无法读取时为 None
+pub fn read() -> Option<String> {
+    read_commands().into_iter().find_map(|(program, args)| {
+        let output = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        Some(String::from_utf8_lossy(&output.stdout).to_string())
+    })
+}
+
+/// 清空剪贴板
+pub fn clear() -> Result<()> {
+    if cfg!(windows) {
+        // clip 读到空输入时清空剪贴板
+        let status = Command::new("cmd")
+            .args(["/C", "echo off | clip"])
+            .status()
+            .context("Failed to clear the clipboard")?;
+        if !status.success() {
+            bail!("Failed to clear the clipboard");
+        }
+        return Ok(());
+    }
+    write("")
+}
+
+/// `delay` 后剪贴板内容仍为 `text` 时清空，用户期间复制了其他内容则保留
+pub fn clear_after(text: String, delay: Duration) {
+    std::thread::spawn(move || {
+        std::thread::sleep(delay);
+        // PowerShell 读取的内容末尾会多出换行，比较时忽略末尾的空白
+        if read().is_some_and(|current| current.trim_end() == text.trim_end()) {
+            let _ = clear();
+        }
+    });
+}
+
+fn write_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", &[])]
+    } else if cfg!(windows) {
+        vec![(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "[Console]::InputEncoding = [Text.Encoding]::UTF8; Set-Clipboard -Value ([Console]::In.ReadToEnd())",
+            ],
+        )]
+    } else {
+        vec![
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    }
+}
+
+fn read_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbpaste", &[])]
+    } else if cfg!(windows) {
+        vec![(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "[Console]::OutputEncoding = [Text.Encoding]::UTF8; Get-Clipboard -Raw",
+            ],
+        )]
+    } else {
+        vec![
+            ("wl-paste", &["--no-newline"]),
+            ("xclip", &["-selection", "clipboard", "-o"]),
+            ("xsel", &["--clipboard", "--output"]),
+        ]
+    }
+}
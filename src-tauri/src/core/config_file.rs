@@ -0,0 +1,420 @@
+use crate::core::{utils, DatabaseInfo, DatabaseType};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 每个实例保留的配置历史版本数
+const MAX_CONFIG_VERSIONS: usize = 50;
+
+/// 配置文件的一个历史版本
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigVersion {
+    /// 版本号，即保存时间 `YYYYMMDDHHMMSSffffff`
+    pub version: String,
+    pub created_at: String,
+    pub size: u64,
+}
+
+/// 数据库实例的配置文件路径
+fn config_path(db_info: &DatabaseInfo) -> Result<PathBuf> {
+    match db_info.config.as_deref() {
+        Some(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+        _ => bail!("{} has no configuration file", db_info.name),
+    }
+}
+
+/// 读取数据库实例的配置文件
+pub fn read_config(db_info: &DatabaseInfo) -> Result<String> {
+    let path = config_path(db_info)?;
+    fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// 校验配置后写入数据库实例的配置文件，校验失败时不修改原文件
+///
+/// 写入前将原文件保存为历史版本，修改在数据库下次启动时生效。
+pub fn write_config(storage_path: &Path, db_info: &DatabaseInfo, contents: &str) -> Result<()> {
+    let path = config_path(db_info)?;
+    let temp = temp_path(&path);
+    fs::write(&temp, contents).with_context(|| format!("Failed to write {}", temp.display()))?;
+
+    if let Err(e) = validate(db_info, &temp, contents) {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    if let Err(e) = save_version(storage_path, db_info) {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    fs::rename(&temp, &path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// 实例配置的历史目录 `<storage_path>/config/<instance>/history`，实例目录名与数据目录一致
+fn history_dir(storage_path: &Path, db_info: &DatabaseInfo) -> PathBuf {
+    let instance_dir = Path::new(&db_info.data_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| db_info.id.clone());
+    utils::get_db_config_path(storage_path, &instance_dir).join("history")
+}
+
+/// 将当前的配置文件保存为历史版本，超出保留数量时删除最旧的版本
+///
+/// 修改配置文件前调用，没有配置文件时跳过。
+pub fn save_version(storage_path: &Path, db_info: &DatabaseInfo) -> Result<()> {
+    let Some(path) = db_info.config.as_deref().map(Path::new) else {
+        return Ok(());
+    };
+    if !path.is_file() {
+        return Ok(());
+    }
+
+    let history = history_dir(storage_path, db_info);
+    utils::ensure_dir(&history)?;
+    let version = chrono::Local::now().format("%Y%m%d%H%M%S%6f").to_string();
+    let target = history.join(version_file_name(path, &version));
+    fs::copy(path, &target)
+        .with_context(|| format!("Failed to save config version {}", target.display()))?;
+
+    let versions = list_versions(storage_path, db_info)?;
+    for stale in versions.iter().skip(MAX_CONFIG_VERSIONS) {
+        let _ = fs::remove_file(history.join(version_file_name(path, &stale.version)));
+    }
+    Ok(())
+}
+
+/// 历史版本的文件名，保留原文件名便于识别
+fn version_file_name(config_path: &Path, version: &str) -> String {
+    let file_name = config_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    format!("{}-{}", version, file_name)
+}
+
+/// 列出配置的历史版本，按时间倒序排列
+pub fn list_versions(storage_path: &Path, db_info: &DatabaseInfo) -> Result<Vec<ConfigVersion>> {
+    let history = history_dir(storage_path, db_info);
+    if !history.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(&history)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some((version, _)) = file_name.split_once('-') else {
+            continue;
+        };
+        let Ok(created_at) = chrono::NaiveDateTime::parse_from_str(version, "%Y%m%d%H%M%S%6f")
+        else {
+            continue;
+        };
+        versions.push(ConfigVersion {
+            version: version.to_string(),
+            created_at: created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            size: entry.metadata()?.len(),
+        });
+    }
+    versions.sort_by(|a, b| b.version.cmp(&a.version));
+    Ok(versions)
+}
+
+/// 将配置文件恢复为指定的历史版本，恢复前当前内容同样保存为历史版本
+///
+/// 历史版本是之前可用的配置，不再校验；修改在数据库下次启动时生效。
+pub fn rollback(storage_path: &Path, db_info: &DatabaseInfo, version: &str) -> Result<()> {
+    let path = config_path(db_info)?;
+    let source = history_dir(storage_path, db_info).join(version_file_name(&path, version));
+    if version.contains(['/', '\\']) || !source.is_file() {
+        bail!("Config version not found: {}", version);
+    }
+    let contents =
+        fs::read(&source).with_context(|| format!("Failed to read {}", source.display()))?;
+
+    save_version(storage_path, db_info)?;
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// 将键值覆盖合并到配置文件中，校验通过后写入，返回修改前的内容用于回滚
+///
+/// 键的写法按配置格式区分：
+/// - my.cnf：`section.key`，不带分组时写入 `[mysqld]`
+/// - postgresql.conf、redis.conf：配置项名
+/// - YAML（Qdrant、MongoDB）：用 `.` 分隔的路径，如 `service.max_request_size_mb`
+pub fn apply_overrides(
+    storage_path: &Path,
+    db_info: &DatabaseInfo,
+    overrides: &HashMap<String, String>,
+) -> Result<String> {
+    let original = read_config(db_info)?;
+    let mut keys: Vec<&String> = overrides.keys().collect();
+    keys.sort();
+
+    let mut contents = original.clone();
+    for key in keys {
+        let value = overrides[key].trim();
+        let key = key.trim();
+        if key.is_empty() {
+            bail!("Configuration key cannot be empty");
+        }
+        contents = match db_info.db_type {
+            DatabaseType::MySQL | DatabaseType::MariaDB => {
+                let (section, key) = key.split_once('.').unwrap_or(("mysqld", key));
+                set_ini_option(&contents, section, key, value)
+            }
+            DatabaseType::PostgreSQL => set_flat_option(&contents, key, " = ", value),
+            DatabaseType::Redis => set_flat_option(&contents, key, " ", value),
+            DatabaseType::Qdrant | DatabaseType::MongoDB => {
+                let path: Vec<&str> = key.split('.').collect();
+                set_yaml_option(&contents, &path, value)
+            }
+            _ => bail!(
+                "Configuration overrides are not supported for {}",
+                db_info.db_type.display_name()
+            ),
+        };
+    }
+
+    write_config(storage_path, db_info, &contents)?;
+    Ok(original)
+}
+
+/// 在 INI 格式的分组中设置选项，选项名中的 `-` 与 `_` 视为相同
+fn set_ini_option(contents: &str, section: &str, key: &str, value: &str) -> String {
+    let normalize = |name: &str| name.trim().replace('-', "_");
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    let line = format!("{} = {}", key, value);
+
+    let header = format!("[{}]", section);
+    let Some(start) = lines.iter().position(|l| l.trim() == header) else {
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(header);
+        lines.push(line);
+        return join_lines(lines);
+    };
+
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+    let existing = (start + 1..end).find(|&index| {
+        let current = lines[index].trim();
+        !current.starts_with('#')
+            && !current.starts_with(';')
+            && normalize(current.split('=').next().unwrap_or_default()) == normalize(key)
+    });
+    match existing {
+        Some(index) => lines[index] = line,
+        None => {
+            // 插在分组最后一个非空行之后
+            let at = (start + 1..end)
+                .rev()
+                .find(|&index| !lines[index].trim().is_empty())
+                .map_or(start + 1, |index| index + 1);
+            lines.insert(at, line);
+        }
+    }
+    join_lines(lines)
+}
+
+/// 设置每行一项的配置（`key = value` 或 `key value`），不存在时追加到末尾
+fn set_flat_option(contents: &str, key: &str, separator: &str, value: &str) -> String {
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    let line = format!("{}{}{}", key, separator, value);
+    let existing = lines.iter().position(|l| {
+        let current = l.trim_start();
+        !current.starts_with('#')
+            && current
+                .split(|c: char| c.is_whitespace() || c == '=')
+                .next()
+                .is_some_and(|name| name == key)
+    });
+    match existing {
+        Some(index) => lines[index] = line,
+        None => lines.push(line),
+    }
+    join_lines(lines)
+}
+
+/// 按路径设置 YAML 中的标量值，缺少的上级节点按两个空格缩进补齐
+fn set_yaml_option(contents: &str, path: &[&str], value: &str) -> String {
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let is_content = |line: &str| {
+        let trimmed = line.trim();
+        !trimmed.is_empty() && !trimmed.starts_with('#')
+    };
+
+    // 当前节点的子节点所在的行范围，以及子节点的缩进
+    let (mut start, mut end) = (0, lines.len());
+    let mut child_indent = 0;
+    for (depth, name) in path.iter().enumerate() {
+        let found = (start..end).find(|&index| {
+            let line = &lines[index];
+            is_content(line)
+                && indent_of(line) == child_indent
+                && line.trim_start().split(':').next() == Some(*name)
+        });
+        let Some(index) = found else {
+            // 从缺少的节点开始补齐剩余路径，插在当前范围的最后一个内容行之后
+            let at = (start..end)
+                .rev()
+                .find(|&index| is_content(&lines[index]))
+                .map_or(start, |index| index + 1);
+            let mut missing = Vec::new();
+            for (offset, name) in path[depth..].iter().enumerate() {
+                let indent = " ".repeat(child_indent + offset * 2);
+                if depth + offset == path.len() - 1 {
+                    missing.push(format!("{}{}: {}", indent, name, value));
+                } else {
+                    missing.push(format!("{}{}:", indent, name));
+                }
+            }
+            lines.splice(at..at, missing);
+            return join_lines(lines);
+        };
+
+        if depth == path.len() - 1 {
+            lines[index] = format!("{}{}: {}", " ".repeat(child_indent), name, value);
+            return join_lines(lines);
+        }
+
+        start = index + 1;
+        end = (start..end)
+            .find(|&i| is_content(&lines[i]) && indent_of(&lines[i]) <= child_indent)
+            .unwrap_or(end);
+        child_indent = (start..end)
+            .find(|&i| is_content(&lines[i]))
+            .map_or(child_indent + 2, |i| indent_of(&lines[i]));
+    }
+    join_lines(lines)
+}
+
+fn join_lines(lines: Vec<String>) -> String {
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    contents
+}
+
+/// 与配置文件在同一目录下的临时文件，配置中的相对路径（如 include）按原位置解析
+fn temp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.tmp", file_name))
+}
+
+/// 使用数据库自带的程序检查配置语法，没有检查方式的数据库只做基本检查
+fn validate(db_info: &DatabaseInfo, path: &Path, contents: &str) -> Result<()> {
+    if contents.contains('\0') {
+        bail!("Configuration contains invalid characters");
+    }
+
+    match db_info.db_type {
+        DatabaseType::MySQL => {
+            let mut command =
+                Command::new(utils::resolve_db_binary(&db_info.install_path, "mysqld"));
+            // --defaults-file 必须是第一个参数
+            command
+                .arg(format!("--defaults-file={}", path.display()))
+                .arg("--validate-config");
+            run_check(command, "mysqld")
+        }
+        DatabaseType::MariaDB => {
+            // MariaDB 不支持 --validate-config，打印生效的选项时会检查未知选项和非法取值
+            let mut command =
+                Command::new(utils::resolve_db_binary(&db_info.install_path, "mariadbd"));
+            command
+                .arg(format!("--defaults-file={}", path.display()))
+                .args(["--help", "--verbose"]);
+            run_check(command, "mariadbd")
+        }
+        DatabaseType::PostgreSQL => {
+            let mut command =
+                Command::new(utils::resolve_db_binary(&db_info.install_path, "postgres"));
+            command
+                .arg(format!("--config-file={}", path.display()))
+                .arg("-D")
+                .arg(&db_info.data_path)
+                .args(["-C", "port"]);
+            run_check(command, "postgres")
+        }
+        DatabaseType::Redis => check_redis_config(contents),
+        _ => Ok(()),
+    }
+}
+
+/// 执行检查命令，失败时返回其输出的错误信息
+fn run_check(mut command: Command, program: &str) -> Result<()> {
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {} to validate the configuration", program))?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr
+        .lines()
+        .filter(|line| line.contains("ERROR") || line.contains("error"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    bail!(
+        "Invalid configuration: {}",
+        if message.is_empty() {
+            stderr.trim()
+        } else {
+            message.trim()
+        }
+    )
+}
+
+/// 按 redis.conf 的规则检查每一行：指令名后跟参数，引号必须成对
+///
+/// redis-server 没有只检查配置的参数，加载错误的配置时直接退出。
+fn check_redis_config(contents: &str) -> Result<()> {
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        for c in line.chars() {
+            match quote {
+                Some(_) if escaped => escaped = false,
+                Some('"') if c == '\\' => escaped = true,
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None => {}
+            }
+        }
+        if quote.is_some() {
+            bail!(
+                "Invalid configuration at line {}: unbalanced quotes",
+                index + 1
+            );
+        }
+
+        let directive = line.split_whitespace().next().unwrap_or_default();
+        if !directive
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            bail!(
+                "Invalid configuration at line {}: bad directive {}",
+                index + 1,
+                directive
+            );
+        }
+    }
+    Ok(())
+}
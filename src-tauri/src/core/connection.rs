@@ -0,0 +1,639 @@
+use crate::core::backup::{mysql_command, postgresql_command, qdrant_client};
+use crate::core::health::{
+    connect, read_cql_frame, read_line, write_cql_frame, CQL_QUERY, CQL_READY, CQL_RESULT,
+    CQL_STARTUP, DEFAULT_HEALTH_TIMEOUT,
+};
+use crate::core::{
+    duckdb, mongo, sqlite, utils, watchdog, ConnectionUriFormat, DatabaseInfo, DatabaseType,
+    EnvFramework,
+};
+use anyhow::{bail, Context, Result};
+use std::io::{Read, Write};
+use std::process::Command;
+
+/// 使用给定凭据建立一次认证连接，成功时返回服务端版本
+///
+/// `db_info` 中的用户名和密码即为要验证的凭据。
+pub fn test_connection(db_info: &DatabaseInfo) -> Result<Option<String>> {
+    match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::SeekDB => {
+            let mut command = mysql_command(db_info, "mysql");
+            command
+                .arg("--batch")
+                .arg("--skip-column-names")
+                .arg("--execute=SELECT VERSION()");
+            run_version_query(command)
+        }
+        DatabaseType::PostgreSQL => {
+            let mut command = postgresql_command(db_info, "psql");
+            command
+                .arg("--dbname=postgres")
+                .arg("--tuples-only")
+                .arg("--no-align")
+                .arg("--command=SHOW server_version");
+            run_version_query(command)
+        }
+        DatabaseType::MongoDB => {
+            run_version_query(mongo::mongosh_command(db_info, "db.version()")?)
+        }
+        DatabaseType::Redis => test_redis(db_info),
+        DatabaseType::Qdrant => {
+            let response = qdrant_client(db_info)?
+                .get(format!("http://127.0.0.1:{}/", db_info.port))
+                .send()
+                .context("Failed to connect to Qdrant")?;
+            if !response.status().is_success() {
+                bail!("Qdrant returned {}", response.status());
+            }
+            let body: serde_json::Value = response.json()?;
+            Ok(body["version"].as_str().map(String::from))
+        }
+        DatabaseType::SurrealDB => test_surrealdb(db_info),
+        DatabaseType::Neo4j => test_neo4j(db_info),
+        DatabaseType::OpenSearch => test_opensearch(db_info),
+        DatabaseType::InfluxDB => test_influxdb(db_info),
+        DatabaseType::Cassandra => test_cassandra(db_info),
+        DatabaseType::SQLite => sqlite::check_file(db_info).map(|_| None),
+        DatabaseType::DuckDB => test_duckdb(db_info),
+        DatabaseType::Custom => bail!("Custom servers do not support credential checks"),
+    }
+}
+
+/// 根据数据库信息生成可直接复制使用的连接字符串
+pub fn connection_uri(db_info: &DatabaseInfo, format: ConnectionUriFormat) -> Result<String> {
+    if db_info.db_type == DatabaseType::SQLite {
+        return Ok(sqlite_uri(db_info, format));
+    }
+    if db_info.db_type == DatabaseType::DuckDB {
+        return duckdb_uri(db_info, format);
+    }
+
+    let host = format!("127.0.0.1:{}", db_info.port);
+    let userinfo = userinfo(db_info);
+
+    let uri = match (&db_info.db_type, format) {
+        (DatabaseType::MySQL | DatabaseType::SeekDB, ConnectionUriFormat::Native)
+        | (DatabaseType::MySQL | DatabaseType::SeekDB, ConnectionUriFormat::Prisma) => {
+            format!("mysql://{}{}/", userinfo, host)
+        }
+        (DatabaseType::MySQL | DatabaseType::SeekDB, ConnectionUriFormat::Sqlalchemy) => {
+            format!("mysql+pymysql://{}{}/", userinfo, host)
+        }
+        (DatabaseType::MySQL | DatabaseType::SeekDB, ConnectionUriFormat::Jdbc) => {
+            format!("jdbc:mysql://{}/{}", host, jdbc_params(db_info))
+        }
+        (DatabaseType::MariaDB, ConnectionUriFormat::Native)
+        | (DatabaseType::MariaDB, ConnectionUriFormat::Prisma) => {
+            format!("mysql://{}{}/", userinfo, host)
+        }
+        (DatabaseType::MariaDB, ConnectionUriFormat::Sqlalchemy) => {
+            format!("mariadb+pymysql://{}{}/", userinfo, host)
+        }
+        (DatabaseType::MariaDB, ConnectionUriFormat::Jdbc) => {
+            format!("jdbc:mariadb://{}/{}", host, jdbc_params(db_info))
+        }
+        (DatabaseType::PostgreSQL, ConnectionUriFormat::Native) => {
+            format!("postgresql://{}{}/postgres", userinfo, host)
+        }
+        (DatabaseType::PostgreSQL, ConnectionUriFormat::Prisma) => {
+            format!("postgresql://{}{}/postgres?schema=public", userinfo, host)
+        }
+        (DatabaseType::PostgreSQL, ConnectionUriFormat::Sqlalchemy) => {
+            format!("postgresql+psycopg2://{}{}/postgres", userinfo, host)
+        }
+        (DatabaseType::PostgreSQL, ConnectionUriFormat::Jdbc) => {
+            format!(
+                "jdbc:postgresql://{}/postgres{}",
+                host,
+                jdbc_params(db_info)
+            )
+        }
+        (DatabaseType::MongoDB, ConnectionUriFormat::Native) if userinfo.is_empty() => {
+            format!("mongodb://{}/", host)
+        }
+        (DatabaseType::MongoDB, ConnectionUriFormat::Native) => {
+            format!("mongodb://{}{}/?authSource=admin", userinfo, host)
+        }
+        (DatabaseType::MongoDB, ConnectionUriFormat::Prisma) if userinfo.is_empty() => {
+            format!("mongodb://{}/test", host)
+        }
+        (DatabaseType::MongoDB, ConnectionUriFormat::Prisma) => {
+            format!("mongodb://{}{}/test?authSource=admin", userinfo, host)
+        }
+        (DatabaseType::Redis, ConnectionUriFormat::Native) => {
+            format!("redis://{}{}", userinfo, host)
+        }
+        (
+            DatabaseType::Qdrant
+            | DatabaseType::Neo4j
+            | DatabaseType::OpenSearch
+            | DatabaseType::InfluxDB,
+            ConnectionUriFormat::Native,
+        ) => {
+            format!("http://{}", host)
+        }
+        (DatabaseType::SurrealDB, ConnectionUriFormat::Native) => format!("ws://{}/rpc", host),
+        (DatabaseType::Cassandra, ConnectionUriFormat::Native) => {
+            format!("cassandra://{}", host)
+        }
+        (db_type, format) => bail!(
+            "{:?} connection strings are not available for {}",
+            format,
+            db_type.display_name()
+        ),
+    };
+
+    Ok(uri)
+}
+
+/// 可粘贴到 `.env` 文件的连接变量：连接字符串以及主机、端口、用户名和密码
+///
+/// 连接字符串的变量名沿用常见框架的约定，如 `DATABASE_URL`、`REDIS_URL`、`MONGODB_URI`。
+pub fn env_block(db_info: &DatabaseInfo) -> Result<String> {
+    let uri = connection_uri(db_info, ConnectionUriFormat::Native)?;
+    let prefix = db_info.db_type.display_name().to_uppercase();
+    let uri_var = match db_info.db_type {
+        DatabaseType::MySQL
+        | DatabaseType::MariaDB
+        | DatabaseType::SeekDB
+        | DatabaseType::PostgreSQL
+        | DatabaseType::SQLite
+        | DatabaseType::DuckDB => "DATABASE_URL".to_string(),
+        DatabaseType::MongoDB => "MONGODB_URI".to_string(),
+        _ => format!("{}_URL", prefix),
+    };
+
+    let mut vars = vec![(uri_var, uri)];
+    if !matches!(db_info.db_type, DatabaseType::SQLite | DatabaseType::DuckDB) {
+        vars.push((format!("{}_HOST", prefix), "127.0.0.1".to_string()));
+        vars.push((format!("{}_PORT", prefix), db_info.port.to_string()));
+    }
+    if let Some(username) = db_info.username.as_deref().filter(|u| !u.is_empty()) {
+        vars.push((format!("{}_USER", prefix), username.to_string()));
+    }
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        vars.push((format!("{}_PASSWORD", prefix), password.to_string()));
+    }
+    Ok(render_env(&db_info.name, &vars))
+}
+
+/// 按框架的约定生成 `.env` 连接变量
+///
+/// SQL 数据库生成 `DATABASE_URL`（Laravel 为 `DB_*`，Spring 为 `SPRING_DATASOURCE_*`），
+/// Redis 生成 `REDIS_URL`，MongoDB 生成 `MONGODB_URI`。
+pub fn env_snippet(db_info: &DatabaseInfo, framework: EnvFramework) -> Result<String> {
+    let is_sql = matches!(
+        db_info.db_type,
+        DatabaseType::MySQL
+            | DatabaseType::MariaDB
+            | DatabaseType::SeekDB
+            | DatabaseType::PostgreSQL
+            | DatabaseType::SQLite
+    );
+    let username = db_info.username.clone().unwrap_or_default();
+    let password = db_info.password.clone().unwrap_or_default();
+    let var = |key: &str, value: String| (key.to_string(), value);
+
+    let vars = match (framework, &db_info.db_type) {
+        (EnvFramework::Laravel, _) if is_sql => {
+            let (connection, database) = match db_info.db_type {
+                DatabaseType::PostgreSQL => ("pgsql", "postgres".to_string()),
+                DatabaseType::MariaDB => ("mariadb", String::new()),
+                DatabaseType::SQLite => (
+                    "sqlite",
+                    sqlite::default_file_path(db_info)
+                        .to_string_lossy()
+                        .to_string(),
+                ),
+                _ => ("mysql", String::new()),
+            };
+            let mut vars = vec![var("DB_CONNECTION", connection.to_string())];
+            if db_info.db_type != DatabaseType::SQLite {
+                vars.push(var("DB_HOST", "127.0.0.1".to_string()));
+                vars.push(var("DB_PORT", db_info.port.to_string()));
+            }
+            vars.push(var("DB_DATABASE", database));
+            if db_info.db_type != DatabaseType::SQLite {
+                vars.push(var("DB_USERNAME", username));
+                vars.push(var("DB_PASSWORD", password));
+            }
+            vars
+        }
+        (EnvFramework::Spring, _) if is_sql => vec![
+            var(
+                "SPRING_DATASOURCE_URL",
+                connection_uri(db_info, ConnectionUriFormat::Jdbc)?
+                    .split('?')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            var("SPRING_DATASOURCE_USERNAME", username),
+            var("SPRING_DATASOURCE_PASSWORD", password),
+        ],
+        (EnvFramework::Rails, _) if is_sql => {
+            let uri = match db_info.db_type {
+                DatabaseType::SQLite => format!(
+                    "sqlite3:{}",
+                    sqlite::default_file_path(db_info).to_string_lossy()
+                ),
+                DatabaseType::PostgreSQL => connection_uri(db_info, ConnectionUriFormat::Native)?,
+                // mysql2 适配器使用 mysql2:// 协议
+                _ => connection_uri(db_info, ConnectionUriFormat::Native)?.replacen(
+                    "mysql://",
+                    "mysql2://",
+                    1,
+                ),
+            };
+            vec![var("DATABASE_URL", uri)]
+        }
+        (EnvFramework::Django, _) if is_sql => {
+            // dj-database-url 的 SQLite 地址为 sqlite:/// 加绝对路径
+            let format = if db_info.db_type == DatabaseType::SQLite {
+                ConnectionUriFormat::Sqlalchemy
+            } else {
+                ConnectionUriFormat::Native
+            };
+            vec![var("DATABASE_URL", connection_uri(db_info, format)?)]
+        }
+        (EnvFramework::Prisma, _) if is_sql => vec![var(
+            "DATABASE_URL",
+            connection_uri(db_info, ConnectionUriFormat::Prisma)?,
+        )],
+        (EnvFramework::Laravel, DatabaseType::Redis) => vec![
+            var("REDIS_HOST", "127.0.0.1".to_string()),
+            var("REDIS_PORT", db_info.port.to_string()),
+            var("REDIS_USERNAME", username),
+            var("REDIS_PASSWORD", password),
+        ],
+        (EnvFramework::Spring, DatabaseType::Redis) => vec![
+            var("SPRING_DATA_REDIS_HOST", "127.0.0.1".to_string()),
+            var("SPRING_DATA_REDIS_PORT", db_info.port.to_string()),
+            var("SPRING_DATA_REDIS_USERNAME", username),
+            var("SPRING_DATA_REDIS_PASSWORD", password),
+        ],
+        (_, DatabaseType::Redis) => vec![var(
+            "REDIS_URL",
+            connection_uri(db_info, ConnectionUriFormat::Native)?,
+        )],
+        (EnvFramework::Spring, DatabaseType::MongoDB) => vec![var(
+            "SPRING_DATA_MONGODB_URI",
+            connection_uri(db_info, ConnectionUriFormat::Native)?,
+        )],
+        (_, DatabaseType::MongoDB) => vec![var(
+            "MONGODB_URI",
+            connection_uri(db_info, ConnectionUriFormat::Native)?,
+        )],
+        (framework, db_type) => bail!(
+            "{} snippets are not available for {}",
+            framework.display_name(),
+            db_type.display_name()
+        ),
+    };
+    Ok(render_env(
+        &format!("{} ({})", db_info.name, framework.display_name()),
+        &vars,
+    ))
+}
+
+/// 以注释标题开头的 `KEY=value` 行
+fn render_env(title: &str, vars: &[(String, String)]) -> String {
+    let mut lines = vec![format!("# {}", title)];
+    lines.extend(
+        vars.iter()
+            .map(|(key, value)| format!("{}={}", key, env_value(value))),
+    );
+    format!("{}\n", lines.join("\n"))
+}
+
+/// `.env` 中的值，含空白、引号、`#`、`$` 或反斜杠时加上双引号并转义
+fn env_value(value: &str) -> String {
+    if value
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '#' | '\\' | '$'))
+    {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// SQLite 连接字符串，指向默认数据库文件
+fn sqlite_uri(db_info: &DatabaseInfo, format: ConnectionUriFormat) -> String {
+    let path = sqlite::default_file_path(db_info)
+        .to_string_lossy()
+        .replace('\\', "/");
+    match format {
+        ConnectionUriFormat::Native => format!("sqlite://{}", path),
+        ConnectionUriFormat::Prisma => format!("file:{}", path),
+        ConnectionUriFormat::Sqlalchemy => format!("sqlite:///{}", path),
+        ConnectionUriFormat::Jdbc => format!("jdbc:sqlite:{}", path),
+    }
+}
+
+/// DuckDB 连接字符串，指向默认数据库文件，DuckDB 客户端直接打开文件路径
+fn duckdb_uri(db_info: &DatabaseInfo, format: ConnectionUriFormat) -> Result<String> {
+    let path = duckdb::default_file_path(db_info)
+        .to_string_lossy()
+        .replace('\\', "/");
+    match format {
+        ConnectionUriFormat::Native => Ok(path),
+        ConnectionUriFormat::Sqlalchemy => Ok(format!("duckdb:///{}", path)),
+        ConnectionUriFormat::Jdbc => Ok(format!("jdbc:duckdb:{}", path)),
+        ConnectionUriFormat::Prisma => bail!("Prisma does not support DuckDB"),
+    }
+}
+
+/// `user:password@` 形式的认证信息，未设置用户名和密码时为空
+fn userinfo(db_info: &DatabaseInfo) -> String {
+    let username = db_info.username.as_deref().unwrap_or_default();
+    let password = db_info.password.as_deref().unwrap_or_default();
+
+    match (username.is_empty(), password.is_empty()) {
+        (true, true) => String::new(),
+        (false, true) => format!("{}@", utils::uri_encode(username, true)),
+        _ => format!(
+            "{}:{}@",
+            utils::uri_encode(username, true),
+            utils::uri_encode(password, true)
+        ),
+    }
+}
+
+fn jdbc_params(db_info: &DatabaseInfo) -> String {
+    let mut params = Vec::new();
+    if let Some(username) = db_info.username.as_deref().filter(|u| !u.is_empty()) {
+        params.push(format!("user={}", utils::uri_encode(username, true)));
+    }
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        params.push(format!("password={}", utils::uri_encode(password, true)));
+    }
+
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", params.join("&"))
+    }
+}
+
+fn run_version_query(mut command: Command) -> Result<Option<String>> {
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(Some(version).filter(|v| !v.is_empty()))
+}
+
+fn test_redis(db_info: &DatabaseInfo) -> Result<Option<String>> {
+    let mut stream = connect(db_info.port, DEFAULT_HEALTH_TIMEOUT)?;
+
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        let mut args = vec!["AUTH"];
+        if let Some(username) = db_info.username.as_deref().filter(|u| !u.is_empty()) {
+            args.push(username);
+        }
+        args.push(password);
+        stream.write_all(&encode_resp(&args))?;
+        let reply = read_line(&mut stream)?;
+        if !reply.starts_with("+OK") {
+            bail!("AUTH failed: {}", reply.trim_start_matches('-'));
+        }
+    }
+
+    stream.write_all(&encode_resp(&["INFO", "server"]))?;
+    let header = read_line(&mut stream)?;
+    let len: usize = match header.strip_prefix('$') {
+        Some(len) => len.parse().context("Invalid INFO reply")?,
+        None => bail!("INFO failed: {}", header.trim_start_matches('-')),
+    };
+    let mut body = vec![0u8; len + 2];
+    stream.read_exact(&mut body)?;
+
+    Ok(String::from_utf8_lossy(&body)
+        .lines()
+        .find_map(|line| line.strip_prefix("redis_version:"))
+        .map(|v| v.trim().to_string()))
+}
+
+pub(super) fn encode_resp(args: &[&str]) -> Vec<u8> {
+    let mut command = format!("*{}\r\n", args.len());
+    for arg in args {
+        command.push_str(&format!("${}\r\n{}\r\n", arg.len(), arg));
+    }
+    command.into_bytes()
+}
+
+fn http_client() -> Result<reqwest::blocking::Client> {
+    Ok(reqwest::blocking::Client::builder()
+        .timeout(DEFAULT_HEALTH_TIMEOUT)
+        .build()?)
+}
+
+fn test_surrealdb(db_info: &DatabaseInfo) -> Result<Option<String>> {
+    let client = http_client()?;
+    let base = format!("http://127.0.0.1:{}", db_info.port);
+
+    let response = client
+        .post(format!("{}/signin", base))
+        .header("Accept", "application/json")
+        .json(&serde_json::json!({
+            "user": db_info.username.as_deref().unwrap_or("root"),
+            "pass": db_info.password.as_deref().unwrap_or_default(),
+        }))
+        .send()
+        .context("Failed to connect to SurrealDB")?;
+    if !response.status().is_success() {
+        bail!("Authentication failed: {}", response.status());
+    }
+
+    let version = client
+        .get(format!("{}/version", base))
+        .send()
+        .and_then(|r| r.text())
+        .ok()
+        .map(|v| v.trim().trim_start_matches("surrealdb-").to_string());
+    Ok(version)
+}
+
+fn test_neo4j(db_info: &DatabaseInfo) -> Result<Option<String>> {
+    let client = http_client()?;
+    let base = format!("http://127.0.0.1:{}", db_info.port);
+
+    let response = client
+        .post(format!("{}/db/neo4j/tx/commit", base))
+        .basic_auth(
+            db_info.username.as_deref().unwrap_or("neo4j"),
+            db_info.password.as_deref(),
+        )
+        .json(&serde_json::json!({ "statements": [] }))
+        .send()
+        .context("Failed to connect to Neo4j")?;
+    if !response.status().is_success() {
+        bail!("Authentication failed: {}", response.status());
+    }
+
+    let discovery: serde_json::Value = client.get(format!("{}/", base)).send()?.json()?;
+    Ok(discovery["neo4j_version"].as_str().map(String::from))
+}
+
+fn test_opensearch(db_info: &DatabaseInfo) -> Result<Option<String>> {
+    let response = http_client()?
+        .get(format!("http://127.0.0.1:{}/", db_info.port))
+        .send()
+        .context("Failed to connect to OpenSearch")?;
+    if !response.status().is_success() {
+        bail!("OpenSearch returned {}", response.status());
+    }
+
+    let body: serde_json::Value = response.json()?;
+    Ok(body["version"]["number"].as_str().map(String::from))
+}
+
+/// InfluxDB 的凭据是安装时生成的 API token，保存在 `password` 中
+fn test_influxdb(db_info: &DatabaseInfo) -> Result<Option<String>> {
+    let client = http_client()?;
+    let base = format!("http://127.0.0.1:{}", db_info.port);
+
+    let response = client
+        .get(format!("{}/api/v2/orgs", base))
+        .header(
+            "Authorization",
+            format!("Token {}", db_info.password.as_deref().unwrap_or_default()),
+        )
+        .send()
+        .context("Failed to connect to InfluxDB")?;
+    if !response.status().is_success() {
+        bail!("Authentication failed: {}", response.status());
+    }
+
+    let health: serde_json::Value = client.get(format!("{}/health", base)).send()?.json()?;
+    Ok(health["version"]
+        .as_str()
+        .map(|v| v.trim_start_matches('v').to_string()))
+}
+
+/// 通过 CQL 原生协议查询 `system.local` 中的版本
+fn test_cassandra(db_info: &DatabaseInfo) -> Result<Option<String>> {
+    let mut stream = connect(db_info.port, DEFAULT_HEALTH_TIMEOUT)?;
+
+    // STARTUP: [string map] { CQL_VERSION: 3.0.0 }
+    let mut startup = Vec::new();
+    startup.extend_from_slice(&1u16.to_be_bytes());
+    for value in ["CQL_VERSION", "3.0.0"] {
+        startup.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        startup.extend_from_slice(value.as_bytes());
+    }
+    write_cql_frame(&mut stream, CQL_STARTUP, &startup)?;
+    match read_cql_frame(&mut stream)? {
+        (CQL_READY, _) => {}
+        // AUTHENTICATE 表示启用了认证，本地实例默认使用 AllowAllAuthenticator
+        (opcode, _) => bail!("Unexpected response opcode {} to STARTUP", opcode),
+    }
+
+    // QUERY: [long string] query, [short] consistency ONE, [byte] flags
+    let query = "SELECT release_version FROM system.local";
+    let mut body = Vec::new();
+    body.extend_from_slice(&(query.len() as u32).to_be_bytes());
+    body.extend_from_slice(query.as_bytes());
+    body.extend_from_slice(&1u16.to_be_bytes());
+    body.push(0);
+    write_cql_frame(&mut stream, CQL_QUERY, &body)?;
+    match read_cql_frame(&mut stream)? {
+        (CQL_RESULT, body) => Ok(parse_single_text_cell(&body)),
+        (opcode, _) => bail!("Unexpected response opcode {} to QUERY", opcode),
+    }
+}
+
+/// 从 Rows 类型的 RESULT 帧体中取出第一行第一列的文本
+fn parse_single_text_cell(body: &[u8]) -> Option<String> {
+    let mut reader = CqlReader { body, pos: 0 };
+    if reader.int()? != 2 {
+        return None;
+    }
+    let flags = reader.int()?;
+    let columns = reader.int()?;
+    if flags & 0x0002 != 0 {
+        let len = reader.int()?;
+        reader.skip(len.max(0) as usize)?;
+    }
+    if flags & 0x0004 == 0 {
+        let global_table_spec = flags & 0x0001 != 0;
+        if global_table_spec {
+            reader.string()?;
+            reader.string()?;
+        }
+        for _ in 0..columns {
+            if !global_table_spec {
+                reader.string()?;
+                reader.string()?;
+            }
+            reader.string()?;
+            // [option] 类型，自定义类型（0x0000）后跟类名
+            if reader.short()? == 0 {
+                reader.string()?;
+            }
+        }
+    }
+    if reader.int()? < 1 {
+        return None;
+    }
+    let len = reader.int()?;
+    let cell = reader.take(usize::try_from(len).ok()?)?;
+    Some(String::from_utf8_lossy(cell).to_string())
+}
+
+struct CqlReader<'a> {
+    body: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CqlReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.body.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        self.take(len).map(|_| ())
+    }
+
+    fn int(&mut self) -> Option<i32> {
+        self.take(4)
+            .map(|bytes| i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn short(&mut self) -> Option<u16> {
+        self.take(2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn string(&mut self) -> Option<&'a [u8]> {
+        let len = self.short()? as usize;
+        self.take(len)
+    }
+}
+
+/// 检查默认数据库文件，HTTP 服务运行时再用给定凭据查询版本
+fn test_duckdb(db_info: &DatabaseInfo) -> Result<Option<String>> {
+    duckdb::check_file(db_info)?;
+    if !watchdog::is_process_alive(db_info) {
+        return Ok(None);
+    }
+
+    let body = duckdb::query(
+        db_info,
+        "SELECT version() AS version",
+        DEFAULT_HEALTH_TIMEOUT,
+    )?;
+    // 默认以 JSONEachRow 格式返回，每行一个 JSON 对象
+    Ok(body
+        .lines()
+        .next()
+        .and_then(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .and_then(|row| row["version"].as_str().map(String::from)))
+}
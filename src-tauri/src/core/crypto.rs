@@ -0,0 +1,185 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::stream::{DecryptorBE32, EncryptorBE32};
+use aes_gcm::aead::{KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key};
+use anyhow::{anyhow, bail, Context, Result};
+use std::fs;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// 加密文件头部标识
+const MAGIC: &[u8; 8] = b"LDBENC1\0";
+/// 每个加密块的明文大小
+const CHUNK_SIZE: usize = 1024 * 1024;
+/// PBKDF2 迭代次数
+const PBKDF2_ROUNDS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+/// STREAM 模式下 AES-GCM 的随机 nonce 前缀长度（12 - 5 字节计数器）
+const NONCE_PREFIX_LEN: usize = 7;
+
+/// 使用口令加密文件，返回十六进制编码的 salt
+///
+/// 文件按块进行 AES-256-GCM STREAM 加密，避免将整个备份读入内存。
+pub fn encrypt_file(source: &Path, target: &Path, passphrase: &str) -> Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+    OsRng.fill_bytes(&mut nonce_prefix);
+
+    let cipher = derive_cipher(passphrase, &salt);
+    let mut encryptor = EncryptorBE32::from_aead(cipher, nonce_prefix.as_ref().into());
+
+    let mut reader = BufReader::new(fs::File::open(source)?);
+    let mut writer = BufWriter::new(fs::File::create(target)?);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&nonce_prefix)?;
+
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut current = read_chunk(&mut reader, &mut buffer)?;
+    loop {
+        let mut next_buffer = vec![0u8; CHUNK_SIZE];
+        let next = read_chunk(&mut reader, &mut next_buffer)?;
+
+        if next == 0 {
+            let ciphertext = encryptor
+                .encrypt_last(&buffer[..current])
+                .map_err(|_| anyhow!("Failed to encrypt backup"))?;
+            write_chunk(&mut writer, true, &ciphertext)?;
+            break;
+        }
+
+        let ciphertext = encryptor
+            .encrypt_next(&buffer[..current])
+            .map_err(|_| anyhow!("Failed to encrypt backup"))?;
+        write_chunk(&mut writer, false, &ciphertext)?;
+
+        buffer = next_buffer;
+        current = next;
+    }
+
+    writer.flush()?;
+    Ok(hex::encode(salt))
+}
+
+/// 使用口令解密由 [`encrypt_file`] 生成的文件
+pub fn decrypt_file(source: &Path, target: &Path, passphrase: &str, salt: &str) -> Result<()> {
+    let salt = hex::decode(salt).context("Invalid backup salt")?;
+
+    let mut reader = BufReader::new(fs::File::open(source)?);
+    let mut magic = [0u8; 8];
+    reader
+        .read_exact(&mut magic)
+        .context("Backup file is truncated")?;
+    if &magic != MAGIC {
+        bail!("Backup file is not encrypted by local-db");
+    }
+    let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+    reader.read_exact(&mut nonce_prefix)?;
+
+    let cipher = derive_cipher(passphrase, &salt);
+    let mut decryptor = DecryptorBE32::from_aead(cipher, nonce_prefix.as_ref().into());
+    let mut writer = BufWriter::new(fs::File::create(target)?);
+
+    loop {
+        let mut header = [0u8; 5];
+        reader
+            .read_exact(&mut header)
+            .context("Backup file is truncated")?;
+        let is_last = header[0] == 1;
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        if len > CHUNK_SIZE + 16 {
+            bail!("Backup file is corrupted");
+        }
+
+        let mut ciphertext = vec![0u8; len];
+        reader
+            .read_exact(&mut ciphertext)
+            .context("Backup file is truncated")?;
+
+        if is_last {
+            let plaintext = decryptor
+                .decrypt_last(ciphertext.as_slice())
+                .map_err(|_| anyhow!("Invalid passphrase or corrupted backup"))?;
+            writer.write_all(&plaintext)?;
+            break;
+        }
+
+        let plaintext = decryptor
+            .decrypt_next(ciphertext.as_slice())
+            .map_err(|_| anyhow!("Invalid passphrase or corrupted backup"))?;
+        writer.write_all(&plaintext)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// 生成十六进制编码的 32 字节随机令牌
+pub fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+fn derive_cipher(passphrase: &str, salt: &[u8]) -> Aes256Gcm {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+}
+
+fn read_chunk(reader: &mut impl Read, buffer: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let n = reader.read(&mut buffer[filled..])?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(filled)
+}
+
+fn write_chunk(writer: &mut impl Write, is_last: bool, ciphertext: &[u8]) -> Result<()> {
+    writer.write_all(&[u8::from(is_last)])?;
+    writer.write_all(&(ciphertext.len() as u32).to_be_bytes())?;
+    writer.write_all(ciphertext)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_then_decrypt_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let (plain, encrypted, decrypted) = (
+            dir.path().join("plain"),
+            dir.path().join("encrypted"),
+            dir.path().join("decrypted"),
+        );
+        // 跨越多个加密块，且最后一块不满
+        let content: Vec<u8> = (0..CHUNK_SIZE * 2 + 100).map(|i| i as u8).collect();
+        fs::write(&plain, &content).unwrap();
+
+        let salt = encrypt_file(&plain, &encrypted, "correct horse").unwrap();
+        assert_ne!(fs::read(&encrypted).unwrap()[MAGIC.len()..], content[..]);
+        decrypt_file(&encrypted, &decrypted, "correct horse", &salt).unwrap();
+        assert_eq!(fs::read(&decrypted).unwrap(), content);
+    }
+
+    #[test]
+    fn decrypt_with_wrong_passphrase_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let (plain, encrypted, decrypted) = (
+            dir.path().join("plain"),
+            dir.path().join("encrypted"),
+            dir.path().join("decrypted"),
+        );
+        fs::write(&plain, b"secret data").unwrap();
+
+        let salt = encrypt_file(&plain, &encrypted, "correct horse").unwrap();
+        let err = decrypt_file(&encrypted, &decrypted, "battery staple", &salt).unwrap_err();
+        assert!(err.to_string().contains("Invalid passphrase"));
+    }
+}
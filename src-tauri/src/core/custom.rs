@@ -0,0 +1,195 @@
+use crate::core::backend::{self, DatabaseBackend, InstallOptions};
+use crate::core::{
+    network, utils, watchdog, BackendKind, CustomServerParams, DatabaseInfo, DatabaseStatus,
+    DatabaseType,
+};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// 启动参数中可用的占位符
+pub const PLACEHOLDERS: &[&str] = &["{port}", "{data_path}"];
+
+/// 自定义服务后端：用户注册的二进制按启动参数直接运行，通过 PID 文件管理进程
+///
+/// 实例的 `install_path` 为二进制路径，`extra_args` 为完整的启动参数（含占位符），
+/// 健康检查只确认端口可以连接。
+pub struct CustomBackend;
+
+impl DatabaseBackend for CustomBackend {
+    fn install(
+        &self,
+        _db_type: &DatabaseType,
+        _storage_path: &Path,
+        _options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        bail!("Custom servers are registered from an existing binary, not installed")
+    }
+
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()> {
+        if watchdog::is_process_alive(db_info) {
+            return Ok(());
+        }
+
+        let log_file = fs::File::options()
+            .create(true)
+            .append(true)
+            .open(&db_info.log_path)
+            .with_context(|| format!("Failed to open {}", db_info.log_path))?;
+        let child = Command::new(&db_info.install_path)
+            .args(expand_args(db_info))
+            .envs(&db_info.env)
+            .current_dir(&db_info.data_path)
+            .stdin(Stdio::null())
+            .stdout(log_file.try_clone()?)
+            .stderr(log_file)
+            .spawn()
+            .with_context(|| format!("Failed to start {}", db_info.install_path))?;
+
+        fs::write(watchdog::pid_file_path(db_info), child.id().to_string())
+            .context("Failed to write PID file")?;
+        Ok(())
+    }
+
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()> {
+        if let Some(pid) = watchdog::read_pid(db_info) {
+            if watchdog::is_process_alive(db_info) {
+                kill_process(pid)?;
+            }
+            let _ = fs::remove_file(watchdog::pid_file_path(db_info));
+        }
+        Ok(())
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        if watchdog::is_process_alive(db_info) {
+            Some(DatabaseStatus::Running)
+        } else {
+            Some(DatabaseStatus::Stopped)
+        }
+    }
+
+    fn is_supervised(&self) -> bool {
+        true
+    }
+
+    // 二进制由用户提供，卸载时只删除实例的数据和日志
+    fn uninstall(&self, db_info: &DatabaseInfo, _remove_binaries: bool) -> Result<()> {
+        self.stop(db_info)?;
+        backend::remove_instance_files(db_info)
+    }
+}
+
+/// 注册自定义服务，创建实例的数据和日志目录，不启动进程
+pub fn register(storage_path: &Path, params: &CustomServerParams) -> Result<DatabaseInfo> {
+    let name = params.name.trim();
+    if name.is_empty() {
+        bail!("A name is required for a custom server");
+    }
+    let binary_path = Path::new(&params.binary_path);
+    if !binary_path.is_absolute() || !binary_path.is_file() {
+        bail!("Binary not found: {}", params.binary_path);
+    }
+    if params.port == 0 {
+        bail!("A health-check port is required for a custom server");
+    }
+    check_placeholders(&params.args)?;
+
+    let instance_dir = utils::get_instance_dir_name(DatabaseType::Custom.as_str(), Some(name));
+    let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+    let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+    if data_dir.exists() {
+        bail!("Instance name already exists: {}", name);
+    }
+    utils::ensure_dir(&data_dir)?;
+    utils::ensure_dir(&logs_dir)?;
+
+    Ok(DatabaseInfo {
+        id: utils::generate_id(),
+        name: name.to_string(),
+        db_type: DatabaseType::Custom,
+        backend: BackendKind::Native,
+        version: params.version.clone().unwrap_or_default(),
+        install_path: params.binary_path.clone(),
+        data_path: data_dir.to_string_lossy().to_string(),
+        log_path: logs_dir.join("server.log").to_string_lossy().to_string(),
+        port: params.port,
+        username: None,
+        password: None,
+        credential_ref: None,
+        config: None,
+        status: DatabaseStatus::Stopped,
+        auto_start: false,
+        depends_on: Vec::new(),
+        env: params.env.clone(),
+        extra_args: params.args.clone(),
+        schedules: Vec::new(),
+        on_demand: false,
+        replica_of: None,
+        bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+        pid: None,
+        created_at: utils::get_timestamp(),
+        updated_at: utils::get_timestamp(),
+    })
+}
+
+/// 替换启动参数中的占位符
+pub fn expand_args(db_info: &DatabaseInfo) -> Vec<String> {
+    db_info
+        .extra_args
+        .iter()
+        .map(|arg| {
+            arg.replace("{port}", &db_info.port.to_string())
+                .replace("{data_path}", &db_info.data_path)
+        })
+        .collect()
+}
+
+/// 启动参数中只能使用已知的占位符，拼错的占位符会原样传给进程，提前报错
+///
+/// 花括号内不是标识符的内容（如 JSON）不视为占位符。
+fn check_placeholders(args: &[String]) -> Result<()> {
+    for arg in args {
+        let mut rest = arg.as_str();
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            let placeholder = &rest[start..=start + len];
+            let is_identifier = len > 1
+                && placeholder[1..len]
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if is_identifier && !PLACEHOLDERS.contains(&placeholder) {
+                bail!(
+                    "Unknown placeholder {} in argument {:?}, expected one of {}",
+                    placeholder,
+                    arg,
+                    PLACEHOLDERS.join(", ")
+                );
+            }
+            rest = &rest[start + len + 1..];
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn kill_process(pid: u32) -> Result<()> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    kill(Pid::from_raw(pid as i32), Signal::SIGTERM).context("Failed to send SIGTERM")
+}
+
+#[cfg(windows)]
+fn kill_process(pid: u32) -> Result<()> {
+    Command::new("taskkill")
+        .arg("/PID")
+        .arg(pid.to_string())
+        .arg("/F")
+        .output()
+        .context("Failed to stop process via taskkill")?;
+    Ok(())
+}
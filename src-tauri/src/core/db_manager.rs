@@ -1,72 +1,133 @@
-use super::types::{DatabaseInfo, DatabaseStatus, OperationResult};
-use super::utils;
-#[cfg(target_os = "linux")]
-use crate::core::linux::{start_service, stop_service};
-#[cfg(target_os = "macos")]
-use crate::core::macos::{
-    start_service_for_database as start_service, stop_service_for_database as stop_service,
+use super::activation::Activator;
+use super::backend;
+use super::backup;
+use super::config_file;
+use super::error::AppError;
+use super::health;
+use super::launchd;
+use super::mongo;
+use super::network;
+use super::password;
+use super::port;
+use super::relocate;
+use super::replication;
+use super::reset;
+use super::snapshot;
+use super::types::{
+    BackendKind, BackupMode, BackupRecord, DataSnapshot, DatabaseConfig, DatabaseInfo,
+    DatabaseStatus, DatabaseType, LanEndpoint, OperationResult,
 };
-#[cfg(target_os = "windows")]
-use crate::core::windows::{start_service, stop_service};
+use super::utils;
+use super::watchdog;
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
 /// 数据库管理器
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
 pub struct DatabaseManager {
-    storage_path: PathBuf,
-    // 模拟的进程ID存储
-    mock_pids: Arc<Mutex<HashMap<String, u32>>>,
-    next_pid: Arc<Mutex<u32>>,
+    // 存储根目录，迁移存储位置后更新
+    storage_path: Arc<RwLock<PathBuf>>,
+    // 启动后等待数据库就绪的超时时间
+    startup_timeout: Arc<Mutex<Duration>>,
+    // 本次运行中由应用启动的数据库 ID
+    started: Arc<Mutex<HashSet<String>>>,
+    // 正在启动或停止中的数据库，值为过渡状态
+    transitions: Arc<Mutex<HashMap<String, DatabaseStatus>>>,
+    // 按需启动时代为监听已停止数据库的端口
+    activator: Activator,
+}
+
+/// 过渡状态守卫，离开作用域时清除对应数据库的过渡状态
+struct TransitionGuard {
+    transitions: Arc<Mutex<HashMap<String, DatabaseStatus>>>,
+    db_id: String,
+}
+
+impl Drop for TransitionGuard {
+    fn drop(&mut self) {
+        self.transitions.lock().unwrap().remove(&self.db_id);
+    }
 }
 
 impl DatabaseManager {
     pub fn new(storage_path: impl AsRef<Path>) -> Self {
         let storage_path = storage_path.as_ref().to_path_buf();
         Self {
-            storage_path,
-            mock_pids: Arc::new(Mutex::new(HashMap::new())),
-            next_pid: Arc::new(Mutex::new(10000)),
+            storage_path: Arc::new(RwLock::new(storage_path)),
+            startup_timeout: Arc::new(Mutex::new(Duration::from_secs(30))),
+            started: Arc::new(Mutex::new(HashSet::new())),
+            transitions: Arc::new(Mutex::new(HashMap::new())),
+            activator: Activator::default(),
         }
     }
 
-    /// 生成模拟的PID
-    #[allow(dead_code)]
-    fn generate_mock_pid(&self) -> u32 {
-        let mut next_pid = self.next_pid.lock().unwrap();
-        let pid = *next_pid;
-        *next_pid += 1;
-        pid
+    /// 按需启动的端口监听
+    pub fn activator(&self) -> &Activator {
+        &self.activator
     }
 
-    /// 检查模拟的进程是否运行
-    fn is_mock_process_running(&self, db_id: &str) -> bool {
-        let pids = self.mock_pids.lock().unwrap();
-        pids.contains_key(db_id)
+    /// 设置启动后等待就绪的超时时间
+    pub fn set_startup_timeout(&self, timeout: Duration) {
+        *self.startup_timeout.lock().unwrap() = timeout;
     }
 
-    /// 启动模拟进程
-    fn start_mock_process(&self, db_id: &str) -> u32 {
-        let pid = self.generate_mock_pid();
-        let mut pids = self.mock_pids.lock().unwrap();
-        pids.insert(db_id.to_string(), pid);
-        pid
+    /// 获取本次运行中由应用启动且尚未停止的数据库 ID
+    pub fn started_databases(&self) -> Vec<String> {
+        self.started.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// 获取正在进行中的过渡状态（`Starting` / `Stopping`）
+    pub fn transient_status(&self, db_id: &str) -> Option<DatabaseStatus> {
+        self.transitions.lock().unwrap().get(db_id).cloned()
+    }
+
+    /// 用过渡状态覆盖数据库信息中的状态，供前端展示
+    pub fn with_transient_status(&self, mut db_info: DatabaseInfo) -> DatabaseInfo {
+        if let Some(status) = self.transient_status(&db_info.id) {
+            db_info.status = status;
+        }
+        db_info
+    }
+
+    /// 进入过渡状态，同一数据库已在启动或停止中时返回 None
+    fn begin_transition(&self, db_id: &str, status: DatabaseStatus) -> Option<TransitionGuard> {
+        let mut transitions = self.transitions.lock().unwrap();
+        if transitions.contains_key(db_id) {
+            return None;
+        }
+        transitions.insert(db_id.to_string(), status);
+        Some(TransitionGuard {
+            transitions: self.transitions.clone(),
+            db_id: db_id.to_string(),
+        })
     }
 
-    /// 停止模拟进程
-    fn stop_mock_process(&self, db_id: &str) {
-        let mut pids = self.mock_pids.lock().unwrap();
-        pids.remove(db_id);
+    /// 进程意外退出后不再视为由应用启动
+    pub fn mark_exited(&self, db_id: &str) {
+        self.started.lock().unwrap().remove(db_id);
+    }
+
+    /// 获取存储根目录
+    pub fn storage_path(&self) -> PathBuf {
+        self.storage_path.read().unwrap().clone()
+    }
+
+    /// 设置存储根目录，迁移存储位置后调用
+    pub fn set_storage_path(&self, storage_path: impl AsRef<Path>) {
+        *self.storage_path.write().unwrap() = storage_path.as_ref().to_path_buf();
     }
 
     /// 初始化目录结构
     pub fn init_directories(&self) -> Result<()> {
+        let storage_path = self.storage_path();
         let dirs = vec!["bin", "config", "data", "logs", "backups"];
         for dir in dirs {
-            let path = self.storage_path.join(dir);
+            let path = storage_path.join(dir);
             utils::ensure_dir(&path)?;
         }
         Ok(())
@@ -74,116 +135,959 @@ impl DatabaseManager {
 
     /// 检查数据库是否已安装
     pub fn is_installed(&self, db_info: &DatabaseInfo) -> bool {
-        // 模拟：如果数据库状态不是 NotInstalled，则认为已安装
         db_info.status != DatabaseStatus::NotInstalled
     }
 
     /// 获取数据库状态
+    ///
+    /// 由数据库对应的后端检查实际状态，无法确定时沿用保存的状态。
     pub fn get_status(&self, db_info: &DatabaseInfo) -> DatabaseStatus {
-        // 如果数据库未安装，直接返回
-        if db_info.status == DatabaseStatus::NotInstalled {
-            return DatabaseStatus::NotInstalled;
+        if let Some(status) = self.transient_status(&db_info.id) {
+            return status;
         }
 
-        // 检查模拟进程是否在运行
-        if self.is_mock_process_running(&db_info.id) {
-            return DatabaseStatus::Running;
+        if !self.is_installed(db_info) {
+            return DatabaseStatus::NotInstalled;
         }
 
-        DatabaseStatus::Stopped
+        match backend::backend_of(db_info).status(db_info) {
+            Some(DatabaseStatus::Stopped) if db_info.status == DatabaseStatus::Crashed => {
+                DatabaseStatus::Crashed
+            }
+            Some(status) => status,
+            None => db_info.status.clone(),
+        }
     }
 
     /// 启动数据库
     pub fn start_database(&self, db_info: &mut DatabaseInfo) -> Result<OperationResult<()>> {
-        #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
-        {
-            if db_info.status == DatabaseStatus::NotInstalled {
-                return Ok(OperationResult::error("Database is not installed"));
+        if !self.is_installed(db_info) {
+            return Ok(OperationResult::error("Database is not installed"));
+        }
+
+        if db_info.status == DatabaseStatus::Running {
+            return Ok(OperationResult::error("Database is already running"));
+        }
+
+        let Some(_transition) = self.begin_transition(&db_info.id, DatabaseStatus::Starting) else {
+            return Ok(OperationResult::error("Database is busy"));
+        };
+
+        // 按需启动的监听占用着端口，启动前先释放
+        self.activator.release(&db_info.id);
+        let backend = backend::backend_of(db_info);
+        if let Err(err) = backend.start(db_info) {
+            return Ok(OperationResult::error(format!("Failed to start: {}", err)));
+        }
+
+        // 等待数据库真正可以连接后再报告成功，超时则停止服务避免状态不一致
+        let timeout = *self.startup_timeout.lock().unwrap();
+        if let Err(err) = health::wait_until_ready(db_info, timeout) {
+            let _ = backend.stop(db_info);
+            return Ok(OperationResult::error(format!(
+                "Failed to start: {:#}",
+                err
+            )));
+        }
+
+        db_info.status = DatabaseStatus::Running;
+        // Homebrew 服务没有 PID 文件，此时为 None
+        db_info.pid = watchdog::read_pid(db_info);
+        db_info.updated_at = utils::get_timestamp();
+        self.started.lock().unwrap().insert(db_info.id.clone());
+
+        Ok(OperationResult::success(
+            format!("{} started successfully", db_info.name),
+            None,
+        ))
+    }
+
+    /// 停止数据库
+    pub fn stop_database(&self, db_info: &mut DatabaseInfo) -> Result<OperationResult<()>> {
+        if db_info.status != DatabaseStatus::Running {
+            return Ok(OperationResult::error("Database is not running"));
+        }
+
+        let Some(_transition) = self.begin_transition(&db_info.id, DatabaseStatus::Stopping) else {
+            return Ok(OperationResult::error("Database is busy"));
+        };
+
+        // 先移出守护范围，避免停止过程中被误判为崩溃
+        let was_started = self.started.lock().unwrap().remove(&db_info.id);
+        if let Err(err) = backend::backend_of(db_info).stop(db_info) {
+            if was_started {
+                self.started.lock().unwrap().insert(db_info.id.clone());
+            }
+            return Ok(OperationResult::error(format!("Failed to stop: {}", err)));
+        }
+
+        db_info.status = DatabaseStatus::Stopped;
+        db_info.pid = None;
+        db_info.updated_at = utils::get_timestamp();
+
+        Ok(OperationResult::success(
+            format!("{} stopped successfully", db_info.name),
+            None,
+        ))
+    }
+
+    /// 备份数据库，备份文件存放在 `backups/<实例目录>/` 下
+    pub fn backup_database(
+        &self,
+        db_info: &DatabaseInfo,
+        mode: BackupMode,
+        passphrase: Option<&str>,
+    ) -> Result<BackupRecord> {
+        backup::create_backup(db_info, &self.backup_dir(db_info), mode, passphrase)
+    }
+
+    /// 实例的备份目录 `backups/<实例目录>/`，PostgreSQL 的 WAL 归档在其中的 wal 目录下
+    pub fn backup_dir(&self, db_info: &DatabaseInfo) -> PathBuf {
+        // 与数据目录同名，区分同类型的多个实例
+        let instance_dir = Path::new(&db_info.data_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| db_info.db_type.as_str().to_string());
+        utils::get_db_backup_path(&self.storage_path(), &instance_dir)
+    }
+
+    /// 修改数据库端口：改写配置文件并重启服务，失败时回滚配置和端口
+    pub fn update_port(
+        &self,
+        db_info: &mut DatabaseInfo,
+        new_port: u16,
+    ) -> Result<OperationResult<()>> {
+        if db_info.port == new_port {
+            return Ok(OperationResult::error("Port is unchanged"));
+        }
+        // 容器的端口映射在创建时确定，改写配置不会改变发布的端口
+        if db_info.backend == BackendKind::Docker {
+            return Ok(OperationResult::error(
+                "The port of a container is fixed when it is created; reinstall it to use another port",
+            ));
+        }
+        if !port::is_port_available(new_port) {
+            return Ok(OperationResult::failure(AppError::PortInUse {
+                port: new_port,
+            }));
+        }
+
+        config_file::save_version(&self.storage_path(), db_info)?;
+        let was_running = db_info.status == DatabaseStatus::Running;
+        if was_running {
+            let result = self.stop_database(db_info)?;
+            if !result.success {
+                return Ok(result);
+            }
+        }
+
+        let old_port = db_info.port;
+        let original_config = match port::rewrite_config_port(db_info, new_port) {
+            Ok(original) => original,
+            Err(e) => {
+                // 改写配置失败时按原端口重新启动，再返回错误
+                if was_running {
+                    let _ = self.start_database(db_info);
+                }
+                return Err(e);
+            }
+        };
+        db_info.port = new_port;
+
+        if was_running {
+            let result = self.start_database(db_info)?;
+            if !result.success {
+                // 新端口启动失败，恢复原配置并按原端口重新启动
+                if let Some(original) = original_config.as_deref() {
+                    port::restore_config(db_info, original)?;
+                }
+                db_info.port = old_port;
+                let _ = self.start_database(db_info);
+                return Ok(result);
             }
+        }
+
+        db_info.updated_at = utils::get_timestamp();
+        Ok(OperationResult::success(
+            format!("{} port changed to {}", db_info.name, new_port),
+            None,
+        ))
+    }
 
+    /// 修改数据库密码并更新 `DatabaseInfo`
+    ///
+    /// 除 SurrealDB 和 DuckDB 外都需要数据库处于运行状态，这两者的密码在启动时传入，通过重启生效。
+    pub fn update_password(
+        &self,
+        db_info: &mut DatabaseInfo,
+        new_password: &str,
+    ) -> Result<OperationResult<()>> {
+        if new_password.is_empty() {
+            return Ok(OperationResult::error("Password cannot be empty"));
+        }
+
+        if matches!(
+            db_info.db_type,
+            DatabaseType::SurrealDB | DatabaseType::DuckDB
+        ) {
+            let old_password = db_info.password.replace(new_password.to_string());
             if db_info.status == DatabaseStatus::Running {
-                return Ok(OperationResult::error("Database is already running"));
+                let result = self.restart(db_info)?;
+                if !result.success {
+                    db_info.password = old_password;
+                    let _ = self.start_database(db_info);
+                    return Ok(result);
+                }
+            }
+        } else {
+            if db_info.status != DatabaseStatus::Running {
+                return Ok(OperationResult::error(
+                    "Database must be running to change its password",
+                ));
             }
+            password::change_password(db_info, new_password)?;
+            db_info.password = Some(new_password.to_string());
+        }
 
-            if let Err(err) = start_service(db_info) {
-                return Ok(OperationResult::error(format!("Failed to start: {}", err)));
+        db_info.updated_at = utils::get_timestamp();
+        Ok(OperationResult::success(
+            format!("{} password updated successfully", db_info.name),
+            None,
+        ))
+    }
+
+    /// 为 MongoDB 开启访问控制：创建 root 角色的管理员，启用 `security.authorization` 后重启，并保存凭据
+    ///
+    /// 没有配置文件的安装（Windows）通过启动参数 `--auth` 开启。重启失败时恢复原配置并重新启动。
+    pub fn enable_mongodb_auth(
+        &self,
+        db_info: &mut DatabaseInfo,
+        username: &str,
+        password: &str,
+    ) -> Result<OperationResult<()>> {
+        if db_info.db_type != DatabaseType::MongoDB {
+            return Ok(OperationResult::error(
+                "Authentication can only be enabled for MongoDB",
+            ));
+        }
+        if !matches!(
+            db_info.backend,
+            BackendKind::Native | BackendKind::LaunchAgent
+        ) {
+            return Ok(OperationResult::error(
+                "Authentication can only be enabled on native installs",
+            ));
+        }
+        if username.is_empty() || password.is_empty() {
+            return Ok(OperationResult::error(
+                "A username and password are required",
+            ));
+        }
+        if db_info.status != DatabaseStatus::Running {
+            return Ok(OperationResult::error(
+                "Database must be running to enable authentication",
+            ));
+        }
+        if mongodb_auth_enabled(db_info) {
+            return Ok(OperationResult::error(format!(
+                "Authentication is already enabled for {}",
+                db_info.name
+            )));
+        }
+
+        // 访问控制开启前不带凭据连接即可创建第一个用户
+        let anonymous = DatabaseInfo {
+            username: None,
+            password: None,
+            ..db_info.clone()
+        };
+        mongo::create_admin_user(&anonymous, username, password)?;
+
+        let original_config = match db_info.config {
+            Some(_) => {
+                let overrides =
+                    HashMap::from([("security.authorization".to_string(), "enabled".to_string())]);
+                Some(config_file::apply_overrides(
+                    &self.storage_path(),
+                    db_info,
+                    &overrides,
+                )?)
+            }
+            None => {
+                db_info.extra_args.push("--auth".to_string());
+                None
             }
+        };
+        let original_credentials = (
+            db_info.username.replace(username.to_string()),
+            db_info.password.replace(password.to_string()),
+        );
 
-            db_info.status = DatabaseStatus::Running;
-            db_info.pid = None;
-            db_info.updated_at = utils::get_timestamp();
+        let result = self.restart(db_info)?;
+        if !result.success {
+            match original_config.as_deref() {
+                Some(content) => port::restore_config(db_info, content)?,
+                None => {
+                    db_info.extra_args.pop();
+                }
+            }
+            (db_info.username, db_info.password) = original_credentials;
+            if self.get_status(db_info) != DatabaseStatus::Running {
+                let _ = self.start_database(db_info);
+            }
+            return Ok(result);
+        }
 
-            Ok(OperationResult::success(
-                format!("{} started successfully", db_info.name),
-                None,
-            ))
+        db_info.updated_at = utils::get_timestamp();
+        Ok(OperationResult::success(
+            format!("Authentication enabled for {}", db_info.name),
+            None,
+        ))
+    }
+
+    /// 应用结构化的配置修改：配置文件中的键值覆盖、端口和密码
+    ///
+    /// 配置文件的修改在重启后生效，`restart` 为 true 且数据库在运行时自动重启，
+    /// 重启失败时恢复原配置并重新启动。数据目录和用户名不能通过配置修改。
+    pub fn apply_config(
+        &self,
+        db_info: &mut DatabaseInfo,
+        config: &DatabaseConfig,
+        restart: bool,
+    ) -> Result<OperationResult<()>> {
+        if config
+            .data_path
+            .as_deref()
+            .is_some_and(|path| path != db_info.data_path)
+        {
+            return Ok(OperationResult::error("The data path cannot be changed"));
+        }
+        if config
+            .username
+            .as_deref()
+            .is_some_and(|username| Some(username) != db_info.username.as_deref())
+        {
+            return Ok(OperationResult::error("The username cannot be changed"));
+        }
+
+        let original = match config.config.as_ref().filter(|o| !o.is_empty()) {
+            Some(overrides) => Some(config_file::apply_overrides(
+                &self.storage_path(),
+                db_info,
+                overrides,
+            )?),
+            None => None,
+        };
+        let rollback = |manager: &Self, db_info: &mut DatabaseInfo| -> Result<()> {
+            if let Some(original) = original.as_deref() {
+                port::restore_config(db_info, original)?;
+                if manager.get_status(db_info) != DatabaseStatus::Running {
+                    let _ = manager.start_database(db_info);
+                }
+            }
+            Ok(())
+        };
+
+        // 修改端口时运行中的数据库会重启，配置覆盖随之生效
+        let mut restarted = false;
+        if let Some(new_port) = config.port.filter(|&port| port != db_info.port) {
+            let result = self.update_port(db_info, new_port)?;
+            if !result.success {
+                rollback(self, db_info)?;
+                return Ok(result);
+            }
+            restarted = db_info.status == DatabaseStatus::Running;
+        }
+
+        let running = db_info.status == DatabaseStatus::Running;
+        if original.is_some() && restart && running && !restarted {
+            let result = self.restart(db_info)?;
+            if !result.success {
+                rollback(self, db_info)?;
+                return Ok(result);
+            }
+            restarted = true;
         }
 
-        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        if let Some(new_password) = config
+            .password
+            .as_deref()
+            .filter(|password| Some(*password) != db_info.password.as_deref())
         {
-            if !self.is_installed(db_info) {
-                return Ok(OperationResult::error("Database is not installed"));
+            let result = self.update_password(db_info, new_password)?;
+            if !result.success {
+                return Ok(result);
             }
+        }
+
+        db_info.updated_at = utils::get_timestamp();
+        let message = if original.is_some() && running && !restarted {
+            format!("Configuration saved, restart {} to apply it", db_info.name)
+        } else {
+            format!("Configuration applied to {}", db_info.name)
+        };
+        Ok(OperationResult::success(message, None))
+    }
+
+    /// 将数据目录迁移到 `new_path`：停止数据库，复制并校验数据，改写配置中的路径后按原状态启动
+    ///
+    /// 新目录启动失败时恢复原配置并在原目录重新启动，迁移成功后删除原数据目录。
+    pub fn move_data(
+        &self,
+        db_info: &mut DatabaseInfo,
+        new_path: &Path,
+    ) -> Result<OperationResult<()>> {
+        if matches!(
+            db_info.backend,
+            BackendKind::Docker | BackendKind::Package | BackendKind::External
+        ) {
+            return Ok(OperationResult::error(
+                "The data directory of this database is not managed by the app",
+            ));
+        }
+        let old_dir = PathBuf::from(&db_info.data_path);
+        if let Err(err) = relocate::check_target(&old_dir, new_path) {
+            return Ok(OperationResult::error(format!("{:#}", err)));
+        }
 
-            if self.get_status(db_info) == DatabaseStatus::Running {
-                return Ok(OperationResult::error("Database is already running"));
+        let was_running = db_info.status == DatabaseStatus::Running;
+        if was_running {
+            let result = self.stop_database(db_info)?;
+            if !result.success {
+                return Ok(result);
             }
+        }
 
-            // 确保目录存在
-            utils::ensure_dir(Path::new(&db_info.data_path))?;
-            utils::ensure_dir(Path::new(&db_info.log_path))?;
+        let original = db_info.clone();
+        // 复制或改写配置失败时删除不完整的副本，并按原状态重新启动
+        let abort = |manager: &Self, db_info: &mut DatabaseInfo, err: anyhow::Error| {
+            *db_info = original.clone();
+            let _ = fs::remove_dir_all(new_path);
+            if was_running {
+                let _ = manager.start_database(db_info);
+            }
+            OperationResult::error(format!("Failed to move data: {:#}", err))
+        };
+        if let Err(err) = relocate::copy_verified(&old_dir, new_path) {
+            return Ok(abort(self, db_info, err));
+        }
 
-            // 模拟启动：生成一个模拟的PID
-            let pid = self.start_mock_process(&db_info.id);
-            db_info.pid = Some(pid);
-            db_info.status = DatabaseStatus::Running;
-            db_info.updated_at = utils::get_timestamp();
+        // 位于数据目录下的配置和日志随数据一起迁移，改写的是副本；其他位置的配置先保存历史版本
+        let new_data_path = new_path.to_string_lossy().to_string();
+        match db_info
+            .config
+            .as_deref()
+            .and_then(|config| relocate::relocated_path(config, &old_dir, new_path))
+        {
+            Some(config) => db_info.config = Some(config),
+            None => {
+                if let Err(err) = config_file::save_version(&self.storage_path(), db_info) {
+                    return Ok(abort(self, db_info, err));
+                }
+            }
+        }
+        if let Some(log_path) = relocate::relocated_path(&db_info.log_path, &old_dir, new_path) {
+            db_info.log_path = log_path;
+        }
+        let original_config =
+            match relocate::rewrite_config_paths(db_info, &original.data_path, &new_data_path) {
+                Ok(original_config) => original_config,
+                Err(err) => return Ok(abort(self, db_info, err)),
+            };
+        db_info.data_path = new_data_path;
+
+        if was_running {
+            let result = self.start_database(db_info)?;
+            if !result.success {
+                // 配置不在数据目录下时改写的是原文件，需要恢复
+                if db_info.config == original.config {
+                    if let Some(content) = original_config.as_deref() {
+                        port::restore_config(&original, content)?;
+                    }
+                }
+                *db_info = original;
+                let _ = fs::remove_dir_all(new_path);
+                let _ = self.start_database(db_info);
+                return Ok(result);
+            }
+        }
 
+        db_info.updated_at = utils::get_timestamp();
+        let message = match fs::remove_dir_all(&old_dir) {
+            Ok(()) => format!("{} data moved to {}", db_info.name, db_info.data_path),
+            Err(err) => format!(
+                "{} data moved to {}, but {} could not be removed: {}",
+                db_info.name,
+                db_info.data_path,
+                old_dir.display(),
+                err
+            ),
+        };
+        Ok(OperationResult::success(message, None))
+    }
+
+    /// 修改启动进程时使用的环境变量和额外参数，`restart` 为 true 且数据库在运行时重启使其生效
+    ///
+    /// 只用于由应用启动进程的本机安装，容器、系统软件包和外部服务不支持。
+    pub fn update_launch_options(
+        &self,
+        db_info: &mut DatabaseInfo,
+        env: HashMap<String, String>,
+        extra_args: Vec<String>,
+        restart: bool,
+    ) -> Result<OperationResult<()>> {
+        if !matches!(
+            db_info.backend,
+            BackendKind::Native | BackendKind::LaunchAgent
+        ) {
+            return Ok(OperationResult::error(
+                "Launch options are only supported for native installs",
+            ));
+        }
+        if let Some(key) = env
+            .keys()
+            .find(|key| key.is_empty() || key.contains('=') || key.contains('\0'))
+        {
+            return Ok(OperationResult::error(format!(
+                "Invalid environment variable name: {:?}",
+                key
+            )));
+        }
+
+        let original = (
+            std::mem::replace(&mut db_info.env, env),
+            std::mem::replace(&mut db_info.extra_args, extra_args),
+        );
+        if restart && db_info.status == DatabaseStatus::Running {
+            let result = self.restart(db_info)?;
+            if !result.success {
+                // 新参数启动失败，恢复原参数并重新启动
+                (db_info.env, db_info.extra_args) = original;
+                let _ = self.start_database(db_info);
+                return Ok(result);
+            }
+        }
+
+        db_info.updated_at = utils::get_timestamp();
+        Ok(OperationResult::success(
+            format!("{} launch options updated", db_info.name),
+            None,
+        ))
+    }
+
+    /// 设置数据库是否开放到网络：开放时监听所有网卡，否则只监听本机，数据库在运行时重启使其生效
+    ///
+    /// 只用于由应用启动进程的本机安装。有配置文件时同时写入配置，brew services 等按配置启动的进程也会生效。
+    /// 开放前的密码检查由 [`Self::expose_database`] 负责，不要直接调用。
+    fn set_network_exposure(
+        &self,
+        db_info: &mut DatabaseInfo,
+        expose: bool,
+    ) -> Result<OperationResult<()>> {
+        if !matches!(
+            db_info.backend,
+            BackendKind::Native | BackendKind::LaunchAgent
+        ) {
+            return Ok(OperationResult::error(
+                "The listen address can only be changed for native installs",
+            ));
+        }
+        if !network::supports_bind_address(&db_info.db_type) {
+            return Ok(OperationResult::error(format!(
+                "Changing the listen address is not supported for {}",
+                db_info.db_type.display_name()
+            )));
+        }
+
+        let address = if expose {
+            network::NETWORK_BIND_ADDRESS
+        } else {
+            network::LOCAL_BIND_ADDRESS
+        };
+        let original_address = std::mem::replace(&mut db_info.bind_address, address.to_string());
+        let original_config = match network::config_override(db_info) {
+            Some((key, value)) if db_info.config.is_some() => {
+                let overrides = HashMap::from([(key, value)]);
+                match config_file::apply_overrides(&self.storage_path(), db_info, &overrides) {
+                    Ok(original) => Some(original),
+                    Err(e) => {
+                        db_info.bind_address = original_address;
+                        return Err(e);
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        if db_info.status == DatabaseStatus::Running {
+            let result = self.restart(db_info)?;
+            if !result.success {
+                // 新地址启动失败，恢复原地址和配置后重新启动
+                db_info.bind_address = original_address;
+                if let Some(content) = original_config.as_deref() {
+                    port::restore_config(db_info, content)?;
+                }
+                let _ = self.start_database(db_info);
+                return Ok(result);
+            }
+        } else if db_info.backend == BackendKind::LaunchAgent {
+            // 登录时按 plist 启动，需要按新地址重新生成
+            launchd::refresh_plist(db_info)?;
+        }
+
+        db_info.updated_at = utils::get_timestamp();
+        let message = if expose {
+            format!(
+                "{} is now listening on all network interfaces",
+                db_info.name
+            )
+        } else {
+            format!("{} is now only listening on localhost", db_info.name)
+        };
+        Ok(OperationResult::success(message, None))
+    }
+
+    /// 开启或关闭局域网共享，开启后返回局域网中可用的连接地址和防火墙提示
+    ///
+    /// 开启前要求数据库已设置密码且会校验密码。PostgreSQL 同时在 pg_hba.conf 中允许局域网的密码登录。
+    pub fn expose_database(
+        &self,
+        db_info: &mut DatabaseInfo,
+        enable: bool,
+    ) -> Result<OperationResult<LanEndpoint>> {
+        if enable {
+            if let Some(reason) = lan_sharing_blocker(db_info) {
+                return Ok(OperationResult::error(reason));
+            }
+        }
+
+        let is_postgres = db_info.db_type == DatabaseType::PostgreSQL;
+        if is_postgres && enable {
+            network::set_postgres_network_access(db_info, true)?;
+        }
+        let result = self.set_network_exposure(db_info, enable)?;
+        if !result.success {
+            if is_postgres && enable {
+                let _ = network::set_postgres_network_access(db_info, false);
+            }
+            return Ok(OperationResult::error(result.message));
+        }
+        if is_postgres && !enable {
+            network::set_postgres_network_access(db_info, false)?;
+        }
+
+        if !enable {
             return Ok(OperationResult::success(
-                format!("{} started successfully", db_info.name),
+                format!("{} is no longer shared on the network", db_info.name),
                 None,
             ));
         }
+        let endpoint = LanEndpoint {
+            host: network::lan_address().map(|ip| ip.to_string()),
+            port: db_info.port,
+            firewall_note: network::firewall_note(db_info),
+        };
+        let message = match &endpoint.host {
+            Some(host) => format!("{} is reachable at {}:{}", db_info.name, host, db_info.port),
+            None => format!(
+                "{} is listening on port {} of all network interfaces",
+                db_info.name, db_info.port
+            ),
+        };
+        Ok(OperationResult::success(message, Some(endpoint)))
     }
 
-    /// 停止数据库
-    pub fn stop_database(&self, db_info: &mut DatabaseInfo) -> Result<OperationResult<()>> {
-        #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
-        {
-            if db_info.status != DatabaseStatus::Running {
-                return Ok(OperationResult::error("Database is not running"));
+    /// 清空数据目录并重新初始化，然后启动数据库，得到与新安装相同的空实例
+    ///
+    /// 只用于由应用管理数据目录的本机安装，保存的密码在启动后重新设置。
+    pub fn reset_data(&self, db_info: &mut DatabaseInfo) -> Result<OperationResult<()>> {
+        if !matches!(
+            db_info.backend,
+            BackendKind::Native | BackendKind::LaunchAgent
+        ) {
+            return Ok(OperationResult::error(
+                "The data directory of this database is not managed by the app",
+            ));
+        }
+
+        if db_info.status == DatabaseStatus::Running {
+            let result = self.stop_database(db_info)?;
+            if !result.success {
+                return Ok(result);
+            }
+        }
+
+        reset::reinitialize_data_dir(db_info)?;
+        let result = self.start_database(db_info)?;
+        if !result.success {
+            return Ok(result);
+        }
+        if let Err(err) = reset::restore_credentials(db_info) {
+            return Ok(OperationResult::error(format!(
+                "{} was reset, but its credentials could not be restored: {:#}",
+                db_info.name, err
+            )));
+        }
+
+        db_info.updated_at = utils::get_timestamp();
+        Ok(OperationResult::success(
+            format!("{} data reset", db_info.name),
+            None,
+        ))
+    }
+
+    /// 为数据目录创建快照，数据库在运行时先停止，完成后重新启动
+    pub fn snapshot_data(
+        &self,
+        db_info: &mut DatabaseInfo,
+        label: &str,
+    ) -> Result<OperationResult<DataSnapshot>> {
+        if !matches!(
+            db_info.backend,
+            BackendKind::Native | BackendKind::LaunchAgent
+        ) {
+            return Ok(OperationResult::error(
+                "The data directory of this database is not managed by the app",
+            ));
+        }
+
+        let was_running = db_info.status == DatabaseStatus::Running;
+        if was_running {
+            let result = self.stop_database(db_info)?;
+            if !result.success {
+                return Ok(OperationResult::error(result.message));
             }
+        }
 
-            if let Err(err) = stop_service(db_info) {
-                return Ok(OperationResult::error(format!("Failed to stop: {}", err)));
+        let snapshot_result = snapshot::create(&self.storage_path(), db_info, label);
+        if was_running {
+            let result = self.start_database(db_info)?;
+            if !result.success && snapshot_result.is_ok() {
+                return Ok(OperationResult::error(result.message));
             }
+        }
+        let snapshot = snapshot_result?;
 
-            db_info.status = DatabaseStatus::Stopped;
-            db_info.pid = None;
-            db_info.updated_at = utils::get_timestamp();
+        Ok(OperationResult::success(
+            format!("Snapshot \"{}\" created", snapshot.label),
+            Some(snapshot),
+        ))
+    }
 
-            Ok(OperationResult::success(
-                format!("{} stopped successfully", db_info.name),
-                None,
-            ))
+    /// 用快照替换数据目录，数据库在运行时先停止，完成后重新启动
+    pub fn restore_snapshot(
+        &self,
+        db_info: &mut DatabaseInfo,
+        snapshot_id: &str,
+    ) -> Result<OperationResult<()>> {
+        if !matches!(
+            db_info.backend,
+            BackendKind::Native | BackendKind::LaunchAgent
+        ) {
+            return Ok(OperationResult::error(
+                "The data directory of this database is not managed by the app",
+            ));
         }
 
-        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-        {
-            if self.get_status(db_info) != DatabaseStatus::Running {
-                return Ok(OperationResult::error("Database is not running"));
+        let was_running = db_info.status == DatabaseStatus::Running;
+        if was_running {
+            let result = self.stop_database(db_info)?;
+            if !result.success {
+                return Ok(result);
+            }
+        }
+
+        // 无论恢复是否成功，都尽量恢复数据库原来的运行状态
+        let restore_result = snapshot::restore(&self.storage_path(), db_info, snapshot_id);
+        if was_running {
+            let result = self.start_database(db_info)?;
+            if !result.success && restore_result.is_ok() {
+                return Ok(result);
+            }
+        }
+        restore_result?;
+
+        Ok(OperationResult::success(
+            format!("{} restored from snapshot", db_info.name),
+            None,
+        ))
+    }
+
+    /// 将新安装的 `replica` 配置为 `source` 的副本，两者都需要是运行中的本机安装
+    ///
+    /// 源实例需要修改配置（如 MariaDB 开启二进制日志）时会重启源实例。PostgreSQL 副本的
+    /// 数据目录会被替换为源实例的基础备份。成功后副本记录源数据库并依赖其先启动。
+    pub fn setup_replica(
+        &self,
+        source: &mut DatabaseInfo,
+        replica: &mut DatabaseInfo,
+    ) -> Result<OperationResult<()>> {
+        if source.id == replica.id || source.db_type != replica.db_type {
+            return Ok(OperationResult::error(
+                "The replica must be another instance of the same database type",
+            ));
+        }
+        if ![&*source, &*replica].iter().all(|db| {
+            matches!(db.backend, BackendKind::Native | BackendKind::LaunchAgent)
+                && db.status == DatabaseStatus::Running
+        }) {
+            return Ok(OperationResult::error(
+                "Both instances must be running native installs",
+            ));
+        }
+
+        let storage_path = self.storage_path();
+        if replication::prepare_source(&storage_path, source)? {
+            let result = self.restart(source)?;
+            if !result.success {
+                return Ok(result);
+            }
+        }
+
+        if replica.db_type == DatabaseType::PostgreSQL {
+            let result = self.stop_database(replica)?;
+            if !result.success {
+                return Ok(result);
+            }
+            replication::clone_postgresql(source, replica)?;
+            let result = self.start_database(replica)?;
+            if !result.success {
+                return Ok(result);
+            }
+        } else {
+            if replication::prepare_replica(&storage_path, replica)? {
+                let result = self.restart(replica)?;
+                if !result.success {
+                    return Ok(result);
+                }
             }
+            replication::start(source, replica)?;
+        }
+
+        replica.replica_of = Some(source.id.clone());
+        if !replica.depends_on.contains(&source.id) {
+            replica.depends_on.push(source.id.clone());
+        }
+        replica.updated_at = utils::get_timestamp();
+        Ok(OperationResult::success(
+            format!("{} is now a replica of {}", replica.name, source.name),
+            None,
+        ))
+    }
+
+    /// 停止后重新启动数据库
+    fn restart(&self, db_info: &mut DatabaseInfo) -> Result<OperationResult<()>> {
+        let result = self.stop_database(db_info)?;
+        if !result.success {
+            return Ok(result);
+        }
+        self.start_database(db_info)
+    }
 
-            // 模拟停止：移除模拟的进程
-            self.stop_mock_process(&db_info.id);
-            db_info.status = DatabaseStatus::Stopped;
-            db_info.pid = None;
-            db_info.updated_at = utils::get_timestamp();
+    /// 从备份恢复数据库
+    ///
+    /// 文件级备份需要先停止数据库，恢复完成后按原状态重新启动。
+    pub fn restore_database(
+        &self,
+        db_info: &mut DatabaseInfo,
+        record: &BackupRecord,
+        passphrase: Option<&str>,
+    ) -> Result<OperationResult<()>> {
+        if record.database_id != db_info.id {
+            return Ok(OperationResult::error(
+                "Backup does not belong to this database",
+            ));
+        }
+
+        if record.mode == BackupMode::Export {
+            return Ok(OperationResult::error(
+                "Exports cannot be restored, import them instead",
+            ));
+        }
+
+        if record.encrypted && passphrase.is_none_or(|p| p.is_empty()) {
+            return Ok(OperationResult::error(
+                "Passphrase is required to restore an encrypted backup",
+            ));
+        }
 
+        if !backup::requires_offline_restore(&db_info.db_type, record.mode) {
+            if db_info.status != DatabaseStatus::Running {
+                return Ok(OperationResult::error(
+                    "Database must be running to restore this backup",
+                ));
+            }
+            backup::restore_backup(db_info, record, passphrase)?;
             return Ok(OperationResult::success(
-                format!("{} stopped successfully", db_info.name),
+                format!("{} restored successfully", db_info.name),
                 None,
             ));
         }
+
+        let was_running = db_info.status == DatabaseStatus::Running;
+        if was_running {
+            let result = self.stop_database(db_info)?;
+            if !result.success {
+                return Ok(result);
+            }
+        }
+
+        // 无论恢复是否成功，都尽量恢复数据库原来的运行状态
+        let restore_result = backup::restore_backup(db_info, record, passphrase);
+        if was_running {
+            let result = self.start_database(db_info)?;
+            if !result.success && restore_result.is_ok() {
+                return Ok(result);
+            }
+        }
+        restore_result?;
+
+        Ok(OperationResult::success(
+            format!("{} restored successfully", db_info.name),
+            None,
+        ))
+    }
+}
+
+/// 不能开启局域网共享的原因：未设置密码、仍在使用安装时的默认密码，或数据库在当前配置下不校验密码
+fn lan_sharing_blocker(db_info: &DatabaseInfo) -> Option<String> {
+    match db_info.db_type {
+        DatabaseType::Qdrant | DatabaseType::OpenSearch => {
+            return Some(format!(
+                "{} is installed without authentication and cannot be shared on the network",
+                db_info.db_type.display_name()
+            ));
+        }
+        DatabaseType::MongoDB if !mongodb_auth_enabled(db_info) => {
+            return Some(format!(
+                "Enable authentication for {} before sharing it on the network",
+                db_info.name
+            ));
+        }
+        _ => {}
+    }
+    match db_info.password.as_deref() {
+        None | Some("") => Some(format!(
+            "Set a password for {} before sharing it on the network",
+            db_info.name
+        )),
+        Some(password::DEFAULT_PASSWORD) => Some(format!(
+            "{} uses the default password; change it before sharing it on the network",
+            db_info.name
+        )),
+        _ => None,
+    }
+}
+
+/// MongoDB 是否已开启访问控制：配置文件中启用了 `security.authorization` 或启动参数带有 `--auth`
+fn mongodb_auth_enabled(db_info: &DatabaseInfo) -> bool {
+    if db_info.extra_args.iter().any(|arg| arg == "--auth") {
+        return true;
     }
+    config_file::read_config(db_info).is_ok_and(|contents| {
+        contents.lines().any(|line| {
+            let line = line.trim();
+            line.starts_with("authorization:") && line.ends_with("enabled")
+        })
+    })
 }
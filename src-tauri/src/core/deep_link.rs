@@ -0,0 +1,152 @@
+use crate::core::DatabaseInfo;
+use anyhow::{bail, Context, Result};
+use url::Url;
+
+/// 应用注册的 URL scheme
+pub const SCHEME: &str = "localdb";
+
+/// `localdb://` 链接请求的操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLinkAction {
+    /// 启动数据库，目标为数据库 ID、名称或类型
+    Start(String),
+    /// 停止数据库，目标同 [`DeepLinkAction::Start`]
+    Stop(String),
+    /// 安装数据库，可选参数来自查询字符串
+    Install {
+        db_type: String,
+        version: Option<String>,
+        name: Option<String>,
+        port: Option<u16>,
+    },
+}
+
+impl DeepLinkAction {
+    /// 确认对话框中的确认按钮文字
+    pub fn verb(&self) -> &'static str {
+        match self {
+            DeepLinkAction::Start(_) => "Start",
+            DeepLinkAction::Stop(_) => "Stop",
+            DeepLinkAction::Install { .. } => "Install",
+        }
+    }
+
+    /// 执行前请用户确认的提示
+    pub fn prompt(&self) -> String {
+        match self {
+            DeepLinkAction::Start(target) => format!("A link wants to start {}.", target),
+            DeepLinkAction::Stop(target) => format!("A link wants to stop {}.", target),
+            DeepLinkAction::Install {
+                db_type,
+                version,
+                name,
+                port,
+            } => {
+                let mut prompt = format!("A link wants to install {}", db_type);
+                if let Some(version) = version {
+                    prompt.push_str(&format!(" {}", version));
+                }
+                if let Some(name) = name {
+                    prompt.push_str(&format!(" as {}", name));
+                }
+                if let Some(port) = port {
+                    prompt.push_str(&format!(" on port {}", port));
+                }
+                prompt.push('.');
+                prompt
+            }
+        }
+    }
+}
+
+/// 解析 `localdb://<操作>/<目标>` 形式的链接
+///
+/// 如 `localdb://start/postgresql`、`localdb://stop/my-redis`，
+/// 安装时可通过查询字符串指定版本、实例名和端口，如 `localdb://install/redis?version=7.2&port=6380`。
+pub fn parse(url: &Url) -> Result<DeepLinkAction> {
+    if url.scheme() != SCHEME {
+        bail!("Unsupported link scheme {}", url.scheme());
+    }
+    let action = url.host_str().unwrap_or_default();
+    let target = decode(url.path().trim_matches('/'))?;
+    if target.is_empty() || target.contains('/') {
+        bail!(
+            "Invalid link {}, expected {}://{}/<database>",
+            url,
+            SCHEME,
+            action
+        );
+    }
+
+    match action {
+        "start" => Ok(DeepLinkAction::Start(target)),
+        "stop" => Ok(DeepLinkAction::Stop(target)),
+        "install" => {
+            let mut version = None;
+            let mut name = None;
+            let mut port = None;
+            for (key, value) in url.query_pairs() {
+                match key.as_ref() {
+                    "version" => version = Some(value.to_string()),
+                    "name" => name = Some(value.to_string()),
+                    "port" => {
+                        port = Some(
+                            value
+                                .parse::<u16>()
+                                .with_context(|| format!("Invalid port {}", value))?,
+                        )
+                    }
+                    _ => bail!("Unknown link parameter {}", key),
+                }
+            }
+            Ok(DeepLinkAction::Install {
+                db_type: target.to_lowercase(),
+                version,
+                name,
+                port,
+            })
+        }
+        _ => bail!("Unknown link action {}", action),
+    }
+}
+
+/// 按 ID、名称或类型（如 `postgresql`）查找链接指向的数据库，同类型有多个实例时需要使用 ID 或名称
+pub fn resolve_target<'a>(databases: &'a [DatabaseInfo], target: &str) -> Result<&'a DatabaseInfo> {
+    if let Some(db_info) = databases
+        .iter()
+        .find(|db| db.id == target || db.name == target)
+    {
+        return Ok(db_info);
+    }
+
+    let of_type: Vec<&DatabaseInfo> = databases
+        .iter()
+        .filter(|db| db.db_type.as_str().eq_ignore_ascii_case(target))
+        .collect();
+    match of_type.as_slice() {
+        [db_info] => Ok(db_info),
+        [] => bail!("Database not found: {}", target),
+        _ => bail!(
+            "There are {} {} instances, link to one by name instead",
+            of_type.len(),
+            target
+        ),
+    }
+}
+
+/// 解码路径中的 `%XX` 转义，实例名可能包含空格等字符
+fn decode(value: &str) -> Result<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' && tail.len() >= 2 {
+            let decoded = hex::decode(&tail[..2]).context("Invalid escape in link")?;
+            bytes.extend(decoded);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).context("Invalid escape in link")
+}
@@ -0,0 +1,145 @@
+use crate::core::DatabaseInfo;
+use anyhow::{bail, Result};
+use std::collections::{HashMap, HashSet};
+use tauri_plugin_log::log;
+
+/// 计算启动顺序：返回 `targets` 及其全部依赖的 ID，依赖总是排在被依赖者之前
+///
+/// 存在循环依赖时返回错误；依赖了不存在的数据库（如已被删除）时跳过该依赖并记录警告。
+pub fn start_order(
+    databases: &HashMap<String, DatabaseInfo>,
+    targets: &[String],
+) -> Result<Vec<String>> {
+    let mut order = Vec::new();
+    let mut visited = HashSet::new();
+    let mut visiting = Vec::new();
+
+    for id in targets {
+        visit(databases, id, &mut visited, &mut visiting, &mut order)?;
+    }
+    Ok(order)
+}
+
+/// 检查修改后的依赖是否都存在、是否会形成循环依赖
+pub fn validate_dependencies(
+    databases: &HashMap<String, DatabaseInfo>,
+    id: &str,
+    depends_on: &[String],
+) -> Result<()> {
+    if let Some(unknown) = depends_on.iter().find(|dep| !databases.contains_key(*dep)) {
+        bail!("Database not found: {}", unknown);
+    }
+    let mut databases = databases.clone();
+    match databases.get_mut(id) {
+        Some(db_info) => db_info.depends_on = depends_on.to_vec(),
+        None => bail!("Database not found: {}", id),
+    }
+    start_order(&databases, &[id.to_string()]).map(|_| ())
+}
+
+fn visit(
+    databases: &HashMap<String, DatabaseInfo>,
+    id: &str,
+    visited: &mut HashSet<String>,
+    visiting: &mut Vec<String>,
+    order: &mut Vec<String>,
+) -> Result<()> {
+    if visited.contains(id) {
+        return Ok(());
+    }
+    if let Some(index) = visiting.iter().position(|v| v == id) {
+        let cycle: Vec<&str> = visiting[index..]
+            .iter()
+            .chain(std::iter::once(&visiting[index]))
+            .map(|id| name_of(databases, id))
+            .collect();
+        bail!("Circular dependency: {}", cycle.join(" -> "));
+    }
+
+    let Some(db_info) = databases.get(id) else {
+        match visiting.last() {
+            Some(parent) => {
+                log::warn!(
+                    "{} depends on unknown database {}, skipping it",
+                    name_of(databases, parent),
+                    id
+                );
+                return Ok(());
+            }
+            None => bail!("Database not found: {}", id),
+        }
+    };
+
+    visiting.push(id.to_string());
+    for dependency in &db_info.depends_on {
+        visit(databases, dependency, visited, visiting, order)?;
+    }
+    visiting.pop();
+
+    visited.insert(id.to_string());
+    order.push(id.to_string());
+    Ok(())
+}
+
+fn name_of<'a>(databases: &'a HashMap<String, DatabaseInfo>, id: &'a str) -> &'a str {
+    databases.get(id).map(|db| db.name.as_str()).unwrap_or(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn databases(edges: &[(&str, &[&str])]) -> HashMap<String, DatabaseInfo> {
+        edges
+            .iter()
+            .map(|(id, depends_on)| {
+                let db_info: DatabaseInfo = serde_json::from_value(serde_json::json!({
+                    "id": id,
+                    "name": id,
+                    "type": "redis",
+                    "version": "7",
+                    "install_path": "",
+                    "data_path": "",
+                    "log_path": "",
+                    "port": 6379,
+                    "status": "stopped",
+                    "auto_start": false,
+                    "created_at": "",
+                    "updated_at": "",
+                    "depends_on": depends_on,
+                }))
+                .unwrap();
+                (id.to_string(), db_info)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn start_order_puts_dependencies_first() {
+        let databases = databases(&[("app", &["cache", "db"]), ("cache", &["db"]), ("db", &[])]);
+        let order = start_order(&databases, &["app".to_string()]).unwrap();
+        assert_eq!(order, ["db", "cache", "app"]);
+    }
+
+    #[test]
+    fn start_order_skips_unknown_dependencies() {
+        let databases = databases(&[("app", &["removed"])]);
+        let order = start_order(&databases, &["app".to_string()]).unwrap();
+        assert_eq!(order, ["app"]);
+    }
+
+    #[test]
+    fn start_order_detects_cycles() {
+        let databases = databases(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"])]);
+        let err = start_order(&databases, &["a".to_string()]).unwrap_err();
+        assert_eq!(err.to_string(), "Circular dependency: a -> b -> c -> a");
+    }
+
+    #[test]
+    fn validate_dependencies_rejects_new_cycle() {
+        let databases = databases(&[("a", &["b"]), ("b", &[])]);
+        assert!(validate_dependencies(&databases, "b", &["a".to_string()]).is_err());
+        assert!(validate_dependencies(&databases, "b", &["missing".to_string()]).is_err());
+        assert!(validate_dependencies(&databases, "b", &[]).is_ok());
+    }
+}
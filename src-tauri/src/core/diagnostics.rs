@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// 导出诊断信息时需要隐藏的字段
+const SECRET_KEYS: &[&str] = &["password", "access_key", "secret_key", "api_token"];
+const REDACTED: &str = "******";
+
+/// 启动参数中带这些词的选项视为敏感选项，隐藏其后的值（如 `--requirepass`、`--pass`）
+const SECRET_ARG_WORDS: &[&str] = &["pass", "secret", "token", "key", "auth"];
+
+/// 递归隐藏 JSON 中的敏感字段
+///
+/// 用户在 `env` 中设置的环境变量全部隐藏值，`extra_args` 中敏感选项的值也会隐藏。
+pub fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                match (key.as_str(), item) {
+                    (key, item) if SECRET_KEYS.contains(&key) && !item.is_null() => {
+                        *item = Value::String(REDACTED.to_string());
+                    }
+                    ("env", Value::Object(env)) => env
+                        .values_mut()
+                        .for_each(|value| *value = Value::String(REDACTED.to_string())),
+                    ("extra_args", Value::Array(args)) => redact_args(args),
+                    (_, item) => redact_secrets(item),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// 隐藏敏感选项的值，支持 `--pass value` 和 `--pass=value` 两种写法
+fn redact_args(args: &mut [Value]) {
+    let is_secret = |option: &str| {
+        let option = option.to_ascii_lowercase();
+        option.starts_with('-') && SECRET_ARG_WORDS.iter().any(|word| option.contains(word))
+    };
+    let mut redact_next = false;
+    for arg in args.iter_mut() {
+        let Some(text) = arg.as_str() else {
+            redact_next = false;
+            continue;
+        };
+        if redact_next {
+            redact_next = false;
+            *arg = Value::String(REDACTED.to_string());
+        } else if let Some((option, _)) = text.split_once('=') {
+            if is_secret(option) {
+                *arg = Value::String(format!("{}={}", option, REDACTED));
+            }
+        } else {
+            redact_next = is_secret(text);
+        }
+    }
+}
+
+/// 将诊断文件写入 zip 包，`files` 为 (包内路径, 内容)
+pub fn write_bundle(target: &Path, files: &[(String, String)]) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file = fs::File::create(target)
+        .with_context(|| format!("Failed to create {}", target.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, content) in files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
@@ -0,0 +1,387 @@
+use crate::core::backend::{DatabaseBackend, InstallOptions};
+use crate::core::{network, utils, BackendKind, DatabaseInfo, DatabaseStatus, DatabaseType};
+use anyhow::{bail, Context, Result};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+lazy_static! {
+    /// 本机的发行版包管理器，依次检测 apt、dnf、pacman
+    static ref PACKAGE_MANAGER: Option<PackageManager> = if cfg!(target_os = "linux") {
+        [PackageManager::Apt, PackageManager::Dnf, PackageManager::Pacman]
+            .into_iter()
+            .find(|manager| {
+                Command::new(manager.binary())
+                    .arg("--version")
+                    .output()
+                    .is_ok_and(|output| output.status.success())
+            })
+    } else {
+        None
+    };
+}
+
+/// 通过发行版包管理器安装、交给 systemd 管理的后端，仅用于 Linux
+///
+/// 数据库使用系统包的默认端口和数据目录，每种类型只能安装一个实例。
+/// `install_path` 记录为 `<包管理器>:<包名>`。
+pub struct DistroPackageBackend;
+
+/// 当前系统能否通过包管理器安装该类型的数据库
+pub fn supports(db_type: &DatabaseType) -> bool {
+    PACKAGE_MANAGER.is_some_and(|manager| DistroPackage::resolve(db_type, manager).is_ok())
+}
+
+impl DatabaseBackend for DistroPackageBackend {
+    fn install(
+        &self,
+        db_type: &DatabaseType,
+        _storage_path: &Path,
+        options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let manager = package_manager()?;
+        let package = DistroPackage::resolve(db_type, manager)?;
+        let port = db_type.default_port();
+        if options.port.is_some_and(|requested| requested != port) {
+            bail!(
+                "{} installed from distro packages listens on its default port {}",
+                db_type.display_name(),
+                port
+            );
+        }
+
+        let mut script = String::from("set -e\n");
+        if !manager.is_installed(package.name) {
+            script.push_str(&manager.install_command(package.name));
+        }
+        script.push_str(&package.init_script(manager));
+        script.push_str(&format!("systemctl enable --now {}\n", package.service));
+        let (username, password) = match db_type {
+            DatabaseType::Redis => (None, None),
+            _ => (options.username, options.password),
+        };
+        if let (Some(username), Some(password)) = (username, password) {
+            script.push_str(&credentials_script(db_type, username, password));
+        }
+        run_privileged(&script).with_context(|| format!("Failed to install {}", package.name))?;
+
+        let version = manager
+            .installed_version(package.name)
+            .or_else(|| options.version.map(|v| v.to_string()))
+            .unwrap_or_else(|| "system".to_string());
+
+        Ok(DatabaseInfo {
+            id: utils::generate_id(),
+            name: options
+                .instance_name
+                .unwrap_or(db_type.display_name())
+                .to_string(),
+            db_type: db_type.clone(),
+            backend: BackendKind::Package,
+            version,
+            install_path: format!("{}:{}", manager.binary(), package.name),
+            data_path: package.data_dir.to_string(),
+            // 日志写入 journald，使用 journalctl -u <service> 查看
+            log_path: String::new(),
+            port,
+            username: username.map(|s| s.to_string()),
+            password: password.map(|s| s.to_string()),
+            credential_ref: None,
+            config: None,
+            status: DatabaseStatus::Running,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        })
+    }
+
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()> {
+        let package = DistroPackage::resolve(&db_info.db_type, package_manager()?)?;
+        run_privileged(&format!("systemctl start {}\n", package.service))
+    }
+
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()> {
+        let package = DistroPackage::resolve(&db_info.db_type, package_manager()?)?;
+        run_privileged(&format!("systemctl stop {}\n", package.service))
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        let package = DistroPackage::resolve(&db_info.db_type, package_manager().ok()?).ok()?;
+        unit_status(package.service)
+    }
+
+    // systemd 会按服务配置自行重启
+    fn is_supervised(&self) -> bool {
+        false
+    }
+
+    /// 停止并禁用服务，`remove_binaries` 为 true 时卸载软件包
+    ///
+    /// 系统目录下的数据由软件包管理，卸载时不删除。
+    fn uninstall(&self, db_info: &DatabaseInfo, remove_binaries: bool) -> Result<()> {
+        let manager = package_manager()?;
+        let package = DistroPackage::resolve(&db_info.db_type, manager)?;
+        let mut script = format!("systemctl disable --now {}\n", package.service);
+        if remove_binaries {
+            script.push_str(&manager.remove_command(package.name));
+        }
+        run_privileged(&script)
+    }
+}
+
+/// systemd 服务单元的运行状态
+pub(crate) fn unit_status(unit: &str) -> Option<DatabaseStatus> {
+    let output = Command::new("systemctl")
+        .args(["is-active", unit])
+        .output()
+        .ok()?;
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "active" | "reloading" => Some(DatabaseStatus::Running),
+        "activating" => Some(DatabaseStatus::Starting),
+        "deactivating" => Some(DatabaseStatus::Stopping),
+        _ => Some(DatabaseStatus::Stopped),
+    }
+}
+
+/// 通过本机的包管理器查询已安装软件包的版本
+pub(crate) fn installed_version(package: &str) -> Option<String> {
+    PACKAGE_MANAGER.and_then(|manager| manager.installed_version(package))
+}
+
+/// 通过包管理器安装 PostgreSQL 扩展的软件包，`major` 为服务器的主版本号
+pub(crate) fn install_postgresql_extension(extension: &str, major: u32) -> Result<()> {
+    let manager = package_manager()?;
+    let package = postgresql_extension_package(extension, major, manager)?;
+    if manager.is_installed(&package) {
+        return Ok(());
+    }
+    run_privileged(&format!("set -e\n{}", manager.install_command(&package)))
+        .with_context(|| format!("Failed to install {}", package))
+}
+
+/// 扩展对应的系统软件包，apt 的包名带 PostgreSQL 主版本号
+fn postgresql_extension_package(
+    extension: &str,
+    major: u32,
+    manager: PackageManager,
+) -> Result<String> {
+    use PackageManager::*;
+
+    Ok(match (extension, manager) {
+        ("vector", Apt) => format!("postgresql-{}-pgvector", major),
+        ("postgis", Apt) => format!("postgresql-{}-postgis-3", major),
+        ("vector", Dnf | Pacman) => "pgvector".to_string(),
+        ("postgis", Dnf | Pacman) => "postgis".to_string(),
+        _ => bail!(
+            "Extension {} is not available from {} packages",
+            extension,
+            manager.binary()
+        ),
+    })
+}
+
+fn package_manager() -> Result<PackageManager> {
+    PACKAGE_MANAGER.context("No supported package manager (apt, dnf, pacman) found")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PackageManager {
+    Apt,
+    Dnf,
+    Pacman,
+}
+
+impl PackageManager {
+    fn binary(&self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt-get",
+            PackageManager::Dnf => "dnf",
+            PackageManager::Pacman => "pacman",
+        }
+    }
+
+    fn install_command(&self, package: &str) -> String {
+        match self {
+            PackageManager::Apt => format!(
+                "apt-get update\nDEBIAN_FRONTEND=noninteractive apt-get install -y {}\n",
+                package
+            ),
+            PackageManager::Dnf => format!("dnf install -y {}\n", package),
+            PackageManager::Pacman => format!("pacman -S --noconfirm --needed {}\n", package),
+        }
+    }
+
+    fn remove_command(&self, package: &str) -> String {
+        match self {
+            PackageManager::Apt => format!(
+                "DEBIAN_FRONTEND=noninteractive apt-get remove -y {}\n",
+                package
+            ),
+            PackageManager::Dnf => format!("dnf remove -y {}\n", package),
+            PackageManager::Pacman => format!("pacman -R --noconfirm {}\n", package),
+        }
+    }
+
+    fn is_installed(&self, package: &str) -> bool {
+        self.installed_version(package).is_some()
+    }
+
+    /// 查询已安装软件包的版本，未安装时返回 None
+    fn installed_version(&self, package: &str) -> Option<String> {
+        let output = match self {
+            PackageManager::Apt => Command::new("dpkg-query")
+                .args(["-W", "-f=${Status} ${Version}", package])
+                .output(),
+            PackageManager::Dnf => Command::new("rpm")
+                .args(["-q", "--qf", "install ok installed %{VERSION}", package])
+                .output(),
+            PackageManager::Pacman => Command::new("pacman").args(["-Q", package]).output(),
+        }
+        .ok()
+        .filter(|output| output.status.success())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        match self {
+            // `install ok installed 5:7.0.15-1`
+            PackageManager::Apt | PackageManager::Dnf => stdout
+                .strip_prefix("install ok installed ")
+                .map(|version| version.trim().to_string()),
+            // `redis 7.2.5-1`
+            PackageManager::Pacman => stdout.split_whitespace().nth(1).map(String::from),
+        }
+    }
+}
+
+/// 数据库类型对应的系统软件包
+struct DistroPackage {
+    db_type: DatabaseType,
+    name: &'static str,
+    /// systemd 服务名
+    service: &'static str,
+    /// 软件包默认的数据目录
+    data_dir: &'static str,
+}
+
+impl DistroPackage {
+    fn resolve(db_type: &DatabaseType, manager: PackageManager) -> Result<Self> {
+        use PackageManager::*;
+
+        let (name, service, data_dir) = match (db_type, manager) {
+            (DatabaseType::Redis, Apt) => ("redis-server", "redis-server", "/var/lib/redis"),
+            (DatabaseType::Redis, Dnf | Pacman) => ("redis", "redis", "/var/lib/redis"),
+            (DatabaseType::PostgreSQL, Apt) => ("postgresql", "postgresql", "/var/lib/postgresql"),
+            (DatabaseType::PostgreSQL, Dnf) => {
+                ("postgresql-server", "postgresql", "/var/lib/pgsql/data")
+            }
+            (DatabaseType::PostgreSQL, Pacman) => {
+                ("postgresql", "postgresql", "/var/lib/postgres/data")
+            }
+            (DatabaseType::MariaDB, Apt | Dnf) => ("mariadb-server", "mariadb", "/var/lib/mysql"),
+            (DatabaseType::MariaDB, Pacman) => ("mariadb", "mariadb", "/var/lib/mysql"),
+            (DatabaseType::MySQL, Apt) => ("mysql-server", "mysql", "/var/lib/mysql"),
+            (DatabaseType::MySQL, Dnf) => ("mysql-server", "mysqld", "/var/lib/mysql"),
+            _ => bail!(
+                "{} is not available from {} packages",
+                db_type.display_name(),
+                manager.binary()
+            ),
+        };
+        Ok(Self {
+            db_type: db_type.clone(),
+            name,
+            service,
+            data_dir,
+        })
+    }
+
+    /// 首次启动前的初始化，apt 安装时会自动完成
+    fn init_script(&self, manager: PackageManager) -> String {
+        match (&self.db_type, manager) {
+            (DatabaseType::PostgreSQL, PackageManager::Dnf) => format!(
+                "[ -f {}/PG_VERSION ] || postgresql-setup --initdb\n",
+                self.data_dir
+            ),
+            (DatabaseType::PostgreSQL, PackageManager::Pacman) => format!(
+                "[ -f {dir}/PG_VERSION ] || runuser -u postgres -- initdb -D {dir}\n",
+                dir = self.data_dir
+            ),
+            (DatabaseType::MariaDB, PackageManager::Pacman) => format!(
+                "[ -d {dir}/mysql ] || mariadb-install-db --user=mysql --basedir=/usr --datadir={dir}\n",
+                dir = self.data_dir
+            ),
+            _ => String::new(),
+        }
+    }
+}
+
+/// 创建可以通过 TCP 密码登录的管理员账号，系统包默认只允许本机系统用户登录
+fn credentials_script(db_type: &DatabaseType, username: &str, password: &str) -> String {
+    let password = password.replace('\'', "''");
+    let sql = match db_type {
+        DatabaseType::PostgreSQL => format!(
+            "DO $$ BEGIN IF NOT EXISTS (SELECT FROM pg_roles WHERE rolname = '{name}') THEN CREATE ROLE \"{ident}\" WITH LOGIN SUPERUSER CREATEDB CREATEROLE; END IF; END $$; ALTER ROLE \"{ident}\" WITH PASSWORD '{password}';",
+            name = username.replace('\'', "''"),
+            ident = username.replace('"', "\"\""),
+        ),
+        _ => format!(
+            "CREATE USER IF NOT EXISTS '{name}'@'localhost' IDENTIFIED BY '{password}'; ALTER USER '{name}'@'localhost' IDENTIFIED BY '{password}'; GRANT ALL PRIVILEGES ON *.* TO '{name}'@'localhost' WITH GRANT OPTION;",
+            name = username.replace('\'', "''"),
+        ),
+    };
+    match db_type {
+        DatabaseType::PostgreSQL => format!(
+            "runuser -u postgres -- psql -v ON_ERROR_STOP=1 -c {}\n",
+            shell_quote(&sql)
+        ),
+        _ => format!("mysql -e {}\n", shell_quote(&sql)),
+    }
+}
+
+/// 以 root 身份执行 shell 脚本，非 root 时通过 pkexec 弹出系统认证窗口
+///
+/// 脚本从 stdin 传入，避免密码出现在进程列表中；整个安装只需要认证一次。
+pub(crate) fn run_privileged(script: &str) -> Result<()> {
+    let is_root = Command::new("id")
+        .arg("-u")
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "0");
+    let mut command = if is_root {
+        Command::new("sh")
+    } else {
+        let mut command = Command::new("pkexec");
+        command.arg("sh");
+        command
+    };
+    let mut child = command
+        .arg("-s")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run privileged command (is pkexec installed?)")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(script.as_bytes())?;
+    }
+
+    let output = child.wait_with_output()?;
+    match output.status.code() {
+        Some(0) => Ok(()),
+        // pkexec 在用户取消认证时返回 126
+        Some(126) if !is_root => bail!("Authorization was cancelled or denied"),
+        _ => bail!("{}", String::from_utf8_lossy(&output.stderr).trim()),
+    }
+}
+
+/// 用单引号包裹 shell 参数
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
@@ -0,0 +1,561 @@
+use crate::core::backend::{self, DatabaseBackend, InstallOptions};
+use crate::core::{
+    cassandra, influxdb, network, opensearch, utils, AppError, BackendKind, DatabaseInfo,
+    DatabaseStatus, DatabaseType,
+};
+use anyhow::{bail, Context, Result};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+lazy_static! {
+    /// 本机可用的容器命令行，优先使用 docker，其次 podman
+    static ref CONTAINER_CLI: Option<&'static str> = ["docker", "podman"]
+        .into_iter()
+        .find(|cli| {
+            Command::new(cli)
+                .arg("--version")
+                .output()
+                .is_ok_and(|output| output.status.success())
+        });
+}
+
+/// 以 Docker/Podman 容器运行数据库的后端
+///
+/// 每个实例对应一个名为 `local-db-<id>` 的容器，数据目录挂载到容器内，端口映射到宿主机。
+pub struct DockerBackend;
+
+/// 检查本机是否安装了 Docker 或 Podman
+pub fn is_available() -> bool {
+    CONTAINER_CLI.is_some()
+}
+
+impl DatabaseBackend for DockerBackend {
+    fn install(
+        &self,
+        db_type: &DatabaseType,
+        storage_path: &Path,
+        options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let image = ContainerImage::resolve(db_type)?;
+        let tag = options.version.unwrap_or(image.default_tag);
+        let image_ref = format!("{}:{}", image.repository, tag);
+        run(&["pull", &image_ref]).with_context(|| format!("Failed to pull {}", image_ref))?;
+
+        let instance_dir = utils::get_instance_dir_name(db_type.as_str(), options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let log_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&log_dir)?;
+
+        let port = options.port.unwrap_or_else(|| db_type.default_port());
+        // Neo4j 镜像只允许 neo4j 用户
+        let username = match db_type {
+            DatabaseType::Neo4j => Some("neo4j"),
+            _ => options.username,
+        };
+
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options
+                .instance_name
+                .unwrap_or(db_type.display_name())
+                .to_string(),
+            db_type: db_type.clone(),
+            backend: BackendKind::Docker,
+            version: tag.to_string(),
+            install_path: image_ref.clone(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_dir.to_string_lossy().to_string(),
+            port,
+            username: username.map(|s| s.to_string()),
+            password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
+            config: None,
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        let name = container_name(&db_info);
+        let label = format!("local-db.id={}", db_info.id);
+        let port_mappings = port_mappings(&db_info, &image);
+        let volume = format!("{}:{}", data_dir.to_string_lossy(), image.data_mount);
+        let env = container_env(&db_info);
+        let command = container_command(&db_info);
+
+        let mut args = vec!["create", "--name", &name, "--label", &label, "-v", &volume];
+        for mapping in &port_mappings {
+            args.extend(["-p", mapping.as_str()]);
+        }
+        for value in &env {
+            args.extend(["-e", value.as_str()]);
+        }
+        args.push(&image_ref);
+        args.extend(command.iter().map(String::as_str));
+        run(&args).context("Failed to create container")?;
+
+        if options.auto_start {
+            self.start(&db_info)?;
+            db_info.status = DatabaseStatus::Running;
+        }
+
+        Ok(db_info)
+    }
+
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()> {
+        run(&["start", &container_name(db_info)]).context("Failed to start container")?;
+        Ok(())
+    }
+
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()> {
+        run(&["stop", &container_name(db_info)]).context("Failed to stop container")?;
+        Ok(())
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        let running = run(&[
+            "inspect",
+            "--format",
+            "{{.State.Running}}",
+            &container_name(db_info),
+        ])
+        .ok()?;
+        if running.trim() == "true" {
+            Some(DatabaseStatus::Running)
+        } else {
+            Some(DatabaseStatus::Stopped)
+        }
+    }
+
+    fn is_supervised(&self) -> bool {
+        true
+    }
+
+    fn uninstall(&self, db_info: &DatabaseInfo, remove_binaries: bool) -> Result<()> {
+        run(&["rm", "--force", &container_name(db_info)]).context("Failed to remove container")?;
+        backend::remove_instance_files(db_info)?;
+        if remove_binaries {
+            run(&["rmi", &db_info.install_path]).context("Failed to remove image")?;
+        }
+        Ok(())
+    }
+}
+
+/// 实例对应的容器名
+fn container_name(db_info: &DatabaseInfo) -> String {
+    format!("local-db-{}", db_info.id)
+}
+
+/// 执行容器命令，失败时返回 stderr 中的错误信息
+fn run(args: &[&str]) -> Result<String> {
+    let Some(cli) = *CONTAINER_CLI else {
+        return Err(AppError::DockerUnavailable.into());
+    };
+    let output = Command::new(cli)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {}", cli))?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 初始化账号所需的环境变量
+fn container_env(db_info: &DatabaseInfo) -> Vec<String> {
+    let username = db_info.username.as_deref();
+    let password = db_info.password.as_deref().unwrap_or_default();
+    match db_info.db_type {
+        DatabaseType::MySQL => {
+            let mut env = vec![format!("MYSQL_ROOT_PASSWORD={}", password)];
+            if let Some(username) = username.filter(|name| *name != "root") {
+                env.push(format!("MYSQL_USER={}", username));
+                env.push(format!("MYSQL_PASSWORD={}", password));
+            }
+            env
+        }
+        DatabaseType::MariaDB => {
+            let mut env = vec![format!("MARIADB_ROOT_PASSWORD={}", password)];
+            if let Some(username) = username.filter(|name| *name != "root") {
+                env.push(format!("MARIADB_USER={}", username));
+                env.push(format!("MARIADB_PASSWORD={}", password));
+            }
+            env
+        }
+        DatabaseType::PostgreSQL => vec![
+            format!("POSTGRES_USER={}", username.unwrap_or("postgres")),
+            format!("POSTGRES_PASSWORD={}", password),
+        ],
+        DatabaseType::MongoDB => match username {
+            Some(username) => vec![
+                format!("MONGO_INITDB_ROOT_USERNAME={}", username),
+                format!("MONGO_INITDB_ROOT_PASSWORD={}", password),
+            ],
+            None => Vec::new(),
+        },
+        // Bolt 映射到服务端口 +1，告知浏览器和驱动实际的宿主机端口
+        DatabaseType::Neo4j => vec![
+            format!("NEO4J_AUTH=neo4j/{}", password),
+            format!(
+                "NEO4J_server_bolt_advertised__address=localhost:{}",
+                db_info.port.saturating_add(1)
+            ),
+        ],
+        // 容器内无法取回生成的 token，使用密码作为管理员 token，与本机安装一样保存在 `password` 中
+        DatabaseType::InfluxDB => vec![
+            "DOCKER_INFLUXDB_INIT_MODE=setup".to_string(),
+            format!(
+                "DOCKER_INFLUXDB_INIT_USERNAME={}",
+                username.unwrap_or("admin")
+            ),
+            format!("DOCKER_INFLUXDB_INIT_PASSWORD={}", password),
+            format!("DOCKER_INFLUXDB_INIT_ORG={}", influxdb::ORG),
+            format!("DOCKER_INFLUXDB_INIT_BUCKET={}", influxdb::BUCKET),
+            format!("DOCKER_INFLUXDB_INIT_ADMIN_TOKEN={}", password),
+        ],
+        DatabaseType::Cassandra => vec![
+            format!("MAX_HEAP_SIZE={}", cassandra::MAX_HEAP_SIZE),
+            format!("HEAP_NEWSIZE={}", cassandra::HEAP_NEWSIZE),
+        ],
+        DatabaseType::OpenSearch => vec![
+            "discovery.type=single-node".to_string(),
+            "DISABLE_SECURITY_PLUGIN=true".to_string(),
+            format!(
+                "OPENSEARCH_JAVA_OPTS=-Xms{heap}m -Xmx{heap}m",
+                heap = opensearch::DEFAULT_HEAP_MB
+            ),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+/// 覆盖镜像默认启动参数，为空时使用镜像自带的命令
+fn container_command(db_info: &DatabaseInfo) -> Vec<String> {
+    match db_info.db_type {
+        DatabaseType::Redis => {
+            let mut command = vec![
+                "redis-server".to_string(),
+                "--appendonly".to_string(),
+                "yes".to_string(),
+            ];
+            if let Some(password) = db_info.password.as_deref() {
+                command.push("--requirepass".to_string());
+                command.push(password.to_string());
+            }
+            command
+        }
+        DatabaseType::SurrealDB => {
+            let mut command = vec!["start".to_string()];
+            if let (Some(username), Some(password)) =
+                (db_info.username.as_deref(), db_info.password.as_deref())
+            {
+                command.extend([
+                    "--user".to_string(),
+                    username.to_string(),
+                    "--pass".to_string(),
+                    password.to_string(),
+                ]);
+            }
+            command.push("rocksdb:/data/database.db".to_string());
+            command
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// 生成 docker-compose.yml，以容器复现已注册的数据库，`project` 为 Compose 项目名
+///
+/// 每个实例一个服务，镜像、端口映射、初始化账号的环境变量和启动命令与容器安装时相同，
+/// 数据保存在命名卷中。无法以容器运行的实例不导出，在文件开头以注释列出。
+/// 文件会分享给他人，密码写作 `${<服务名>_PASSWORD}`，由 Compose 从 `.env` 读取，文件开头列出需要设置的变量。
+pub fn compose_file(databases: &[DatabaseInfo], project: Option<&str>) -> Result<String> {
+    let mut services = Vec::new();
+    let mut volumes = Vec::new();
+    let mut skipped = Vec::new();
+    let mut used_names: Vec<String> = Vec::new();
+    let mut password_vars = Vec::new();
+
+    for db_info in databases {
+        let Ok(image) = ContainerImage::resolve(&db_info.db_type) else {
+            skipped.push(format!(
+                "{} ({})",
+                db_info.name,
+                db_info.db_type.display_name()
+            ));
+            continue;
+        };
+
+        let base = service_name(&db_info.name);
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while used_names.contains(&name) {
+            name = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        used_names.push(name.clone());
+
+        let mut db_info = db_info.clone();
+        let password_var = db_info
+            .password
+            .as_deref()
+            .filter(|password| !password.is_empty())
+            .map(|_| password_var(&name));
+        if let Some(var) = &password_var {
+            db_info.password = Some(PASSWORD_PLACEHOLDER.to_string());
+            password_vars.push(var.clone());
+        }
+        let quote = |value: &str| match &password_var {
+            Some(var) => {
+                compose_quote(value).replace(PASSWORD_PLACEHOLDER, &format!("${{{}}}", var))
+            }
+            None => compose_quote(value),
+        };
+        let db_info = &db_info;
+
+        let image_ref = if db_info.backend == BackendKind::Docker {
+            db_info.install_path.clone()
+        } else {
+            format!("{}:{}", image.repository, compose_tag(db_info, &image))
+        };
+        let volume = format!("{}-data", name);
+
+        let mut service = vec![
+            format!("  {}:", name),
+            format!("    image: {}", compose_quote(&image_ref)),
+            "    restart: unless-stopped".to_string(),
+            "    ports:".to_string(),
+        ];
+        service.extend(
+            port_mappings(db_info, &image)
+                .iter()
+                .map(|mapping| format!("      - {}", compose_quote(mapping))),
+        );
+        service.extend([
+            "    volumes:".to_string(),
+            format!("      - {}:{}", volume, image.data_mount),
+        ]);
+        let env = container_env(db_info);
+        if !env.is_empty() {
+            service.push("    environment:".to_string());
+            service.extend(env.iter().map(|value| format!("      - {}", quote(value))));
+        }
+        let command = container_command(db_info);
+        if !command.is_empty() {
+            service.push("    command:".to_string());
+            service.extend(command.iter().map(|arg| format!("      - {}", quote(arg))));
+        }
+        services.push(service.join("\n"));
+        volumes.push(format!("  {}:", volume));
+    }
+
+    if services.is_empty() {
+        bail!("None of the registered databases can run in a container");
+    }
+
+    let mut lines = vec!["# Generated by local-db".to_string()];
+    if !skipped.is_empty() {
+        lines.push(format!(
+            "# Not included, no container image available: {}",
+            skipped.join(", ")
+        ));
+    }
+    if !password_vars.is_empty() {
+        lines.push(
+            "# Passwords are not stored in this file. Set them in a .env file next to it:"
+                .to_string(),
+        );
+        lines.extend(password_vars.iter().map(|var| format!("#   {}=", var)));
+    }
+    if let Some(project) = project.map(str::trim).filter(|p| !p.is_empty()) {
+        lines.push(format!("name: {}", compose_quote(&service_name(project))));
+    }
+    lines.push("services:".to_string());
+    lines.push(services.join("\n\n"));
+    lines.push(String::new());
+    lines.push("volumes:".to_string());
+    lines.extend(volumes);
+    Ok(format!("{}\n", lines.join("\n")))
+}
+
+/// 生成 Compose 文件时代替密码的标记，引号转义后替换为 `${变量}`，因此不含需要转义的字符
+const PASSWORD_PLACEHOLDER: &str = "\u{1}password\u{1}";
+
+/// 服务密码的环境变量名，如服务 `my-postgres` 对应 `MY_POSTGRES_PASSWORD`
+fn password_var(service: &str) -> String {
+    let var = format!("{}_PASSWORD", service.to_uppercase().replace('-', "_"));
+    if var.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("DB_{}", var)
+    } else {
+        var
+    }
+}
+
+/// Compose 服务名，只保留小写字母、数字、`-` 和 `_`
+fn service_name(name: &str) -> String {
+    let name: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        "database".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// 本机安装的镜像标签，版本号为纯数字时使用相同版本，否则使用默认标签
+fn compose_tag<'a>(db_info: &'a DatabaseInfo, image: &'a ContainerImage) -> &'a str {
+    let version = db_info.version.trim();
+    if !version.is_empty() && version.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        version
+    } else {
+        image.default_tag
+    }
+}
+
+/// YAML 双引号字符串，`$` 写作 `$$`，避免被 Compose 当作变量替换
+fn compose_quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "$$")
+    )
+}
+
+/// 容器的端口映射（`宿主机地址:宿主机端口:容器端口`），服务端口在前，其他端口依次使用后续的宿主机端口
+fn port_mappings(db_info: &DatabaseInfo, image: &ContainerImage) -> Vec<String> {
+    std::iter::once(image.container_port)
+        .chain(image.secondary_ports.iter().copied())
+        .enumerate()
+        .map(|(offset, container_port)| {
+            format!(
+                "{}:{}:{}",
+                db_info.bind_address,
+                db_info.port.saturating_add(offset as u16),
+                container_port
+            )
+        })
+        .collect()
+}
+
+/// 数据库类型对应的官方镜像
+struct ContainerImage {
+    repository: &'static str,
+    default_tag: &'static str,
+    /// 服务在容器内监听的端口
+    container_port: u16,
+    /// 需要一并映射的其他容器端口（如 Neo4j 的 Bolt），宿主机端口依次为服务端口 +1、+2……
+    secondary_ports: &'static [u16],
+    /// 数据目录在容器内的挂载点
+    data_mount: &'static str,
+}
+
+impl ContainerImage {
+    fn resolve(db_type: &DatabaseType) -> Result<Self> {
+        let image = match db_type {
+            DatabaseType::MySQL => Self {
+                repository: "mysql",
+                default_tag: "8.4",
+                container_port: 3306,
+                secondary_ports: &[],
+                data_mount: "/var/lib/mysql",
+            },
+            DatabaseType::MariaDB => Self {
+                repository: "mariadb",
+                default_tag: "11.4",
+                container_port: 3306,
+                secondary_ports: &[],
+                data_mount: "/var/lib/mysql",
+            },
+            DatabaseType::PostgreSQL => Self {
+                repository: "postgres",
+                default_tag: "17",
+                container_port: 5432,
+                secondary_ports: &[],
+                data_mount: "/var/lib/postgresql/data",
+            },
+            DatabaseType::MongoDB => Self {
+                repository: "mongo",
+                default_tag: "7.0",
+                container_port: 27017,
+                secondary_ports: &[],
+                data_mount: "/data/db",
+            },
+            DatabaseType::Redis => Self {
+                repository: "redis",
+                default_tag: "7",
+                container_port: 6379,
+                secondary_ports: &[],
+                data_mount: "/data",
+            },
+            DatabaseType::Qdrant => Self {
+                repository: "qdrant/qdrant",
+                default_tag: "latest",
+                container_port: 6333,
+                secondary_ports: &[],
+                data_mount: "/qdrant/storage",
+            },
+            DatabaseType::SurrealDB => Self {
+                repository: "surrealdb/surrealdb",
+                default_tag: "latest",
+                container_port: 8000,
+                secondary_ports: &[],
+                data_mount: "/data",
+            },
+            DatabaseType::Neo4j => Self {
+                repository: "neo4j",
+                default_tag: "5",
+                container_port: 7474,
+                secondary_ports: &[7687],
+                data_mount: "/data",
+            },
+            DatabaseType::OpenSearch => Self {
+                repository: "opensearchproject/opensearch",
+                default_tag: "2",
+                container_port: 9200,
+                secondary_ports: &[],
+                data_mount: "/usr/share/opensearch/data",
+            },
+            DatabaseType::InfluxDB => Self {
+                repository: "influxdb",
+                default_tag: "2",
+                container_port: 8086,
+                secondary_ports: &[],
+                data_mount: "/var/lib/influxdb2",
+            },
+            DatabaseType::Cassandra => Self {
+                repository: "cassandra",
+                default_tag: "5.0",
+                container_port: 9042,
+                secondary_ports: &[],
+                data_mount: "/var/lib/cassandra",
+            },
+            DatabaseType::SeekDB => bail!("SeekDB is not available as a container image"),
+            DatabaseType::SQLite => bail!("SQLite has no server to run in a container"),
+            DatabaseType::DuckDB => bail!("DuckDB is not available as a container image"),
+            DatabaseType::Custom => bail!("Custom servers cannot run in a container"),
+        };
+        Ok(image)
+    }
+}
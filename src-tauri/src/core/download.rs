@@ -0,0 +1,308 @@
+use crate::core::{utils, AppError, GlobalSettings};
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use reqwest::{Client, ClientBuilder, Response};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, RwLock};
+use tauri_plugin_log::log;
+
+/// 下载缓存索引文件名
+const CACHE_INDEX: &str = "index.json";
+
+/// 可配置镜像的下载源及其官方地址前缀
+///
+/// 镜像地址替换 URL 中的官方前缀，其余路径保持不变，镜像需要与官方站点的目录结构一致。
+/// `homebrew` 不是 URL 前缀，对应 Homebrew 的 bottle 镜像（`HOMEBREW_BOTTLE_DOMAIN`）。
+pub const SOURCES: &[(&str, &[&str])] = &[
+    (
+        "mysql",
+        &[
+            "https://cdn.mysql.com/Downloads/",
+            "https://dev.mysql.com/get/Downloads/",
+        ],
+    ),
+    ("mariadb", &["https://archive.mariadb.org/"]),
+    ("mongodb", &["https://fastdl.mongodb.org/"]),
+    ("postgresql", &["https://get.enterprisedb.com/postgresql/"]),
+    ("redis", &["https://download.redis.io/releases/"]),
+    ("apache", &["https://archive.apache.org/dist/"]),
+    ("neo4j", &["https://dist.neo4j.org/"]),
+    ("opensearch", &["https://artifacts.opensearch.org/"]),
+    ("influxdb", &["https://dl.influxdata.com/"]),
+    ("github", &["https://github.com/"]),
+    ("homebrew", &[]),
+];
+
+/// 下载设置，由全局设置同步过来
+#[derive(Default)]
+struct DownloadConfig {
+    proxy: Option<String>,
+    mirrors: HashMap<String, String>,
+    /// 下载缓存目录 `<storage_path>/downloads`
+    cache_dir: Option<PathBuf>,
+}
+
+/// 下载缓存索引中的一项，键为官方下载地址
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    sha256: String,
+    size: u64,
+    downloaded_at: String,
+}
+
+/// 下载缓存的占用情况
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadCacheInfo {
+    pub path: String,
+    pub files: usize,
+    pub size: u64,
+}
+
+lazy_static! {
+    static ref CONFIG: RwLock<DownloadConfig> = RwLock::new(DownloadConfig::default());
+    /// 同时安装多个数据库时保护缓存索引的读写
+    static ref CACHE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// 按全局设置更新下载使用的代理、镜像和缓存目录，加载或修改设置后调用
+pub fn configure(settings: &GlobalSettings) {
+    let mut config = CONFIG.write().unwrap();
+    config.cache_dir = Some(utils::get_downloads_path(Path::new(
+        &settings.default_storage_path,
+    )));
+    config.proxy = settings
+        .download_proxy
+        .as_deref()
+        .map(str::trim)
+        .filter(|proxy| !proxy.is_empty())
+        .map(String::from);
+    config.mirrors = settings
+        .download_mirrors
+        .iter()
+        .map(|(source, mirror)| {
+            (
+                source.clone(),
+                mirror.trim().trim_end_matches('/').to_string(),
+            )
+        })
+        .filter(|(_, mirror)| !mirror.is_empty())
+        .collect();
+}
+
+/// 某个下载源配置的镜像地址
+pub fn mirror(source: &str) -> Option<String> {
+    CONFIG.read().unwrap().mirrors.get(source).cloned()
+}
+
+/// 将官方下载地址替换为配置的镜像地址，未配置镜像时原样返回
+pub fn mirror_url(url: &str) -> String {
+    for (source, prefixes) in SOURCES {
+        if let Some(path) = prefixes.iter().find_map(|prefix| url.strip_prefix(prefix)) {
+            return match mirror(source) {
+                Some(mirror) => format!("{}/{}", mirror, path),
+                None => url.to_string(),
+            };
+        }
+    }
+    url.to_string()
+}
+
+/// 使用配置代理的 HTTP 客户端构建器，未配置时使用系统代理环境变量
+pub fn client_builder() -> Result<ClientBuilder> {
+    let mut builder = Client::builder();
+    if let Some(proxy) = CONFIG.read().unwrap().proxy.as_deref() {
+        builder = builder
+            .proxy(reqwest::Proxy::all(proxy).with_context(|| format!("Invalid proxy {}", proxy))?);
+    }
+    Ok(builder)
+}
+
+/// 下载文件，经过镜像替换和代理，HTTP 状态不是成功时报错
+pub async fn get(url: &str) -> Result<Response> {
+    let url = mirror_url(url);
+    // 发行包较大，异步客户端默认不限制整体下载时间
+    let response = client_builder()?
+        .build()?
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| AppError::DownloadFailed {
+            url: url.clone(),
+            reason: e.to_string(),
+        })?;
+    if !response.status().is_success() {
+        return Err(AppError::DownloadFailed {
+            url,
+            reason: format!("HTTP {}", response.status()),
+        }
+        .into());
+    }
+    Ok(response)
+}
+
+/// 读取发行包内容：离线安装时使用本地文件 `archive`，否则优先使用下载缓存，未命中时从 `url` 下载
+pub async fn fetch(url: &str, archive: Option<&Path>) -> Result<Vec<u8>> {
+    if let Some(path) = archive {
+        return fs::read(path).with_context(|| format!("Failed to read {}", path.display()));
+    }
+    if let Some(bytes) = read_cached(url) {
+        return Ok(bytes);
+    }
+
+    let bytes: Vec<u8> = get(url)
+        .await?
+        .bytes()
+        .await
+        .context("Failed to read response bytes")?
+        .into();
+    // 缓存失败不影响安装
+    if let Err(e) = store_cached(url, &bytes) {
+        log::warn!("Failed to cache download {}: {:#}", url, e);
+    }
+    Ok(bytes)
+}
+
+/// 在同步代码中调用 [`fetch`]，供运行在后台线程池中的安装流程使用，不能在异步任务中调用
+pub fn fetch_blocking(url: &str, archive: Option<&Path>) -> Result<Vec<u8>> {
+    tauri::async_runtime::block_on(fetch(url, archive))
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    CONFIG.read().unwrap().cache_dir.clone()
+}
+
+/// 指向最新版本的地址内容会变化，不缓存
+fn is_cacheable(url: &str) -> bool {
+    !url.contains("/latest/")
+}
+
+fn load_index(cache_dir: &Path) -> HashMap<String, CacheEntry> {
+    fs::read_to_string(cache_dir.join(CACHE_INDEX))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(cache_dir: &Path, index: &HashMap<String, CacheEntry>) -> Result<()> {
+    fs::write(
+        cache_dir.join(CACHE_INDEX),
+        serde_json::to_string_pretty(index)?,
+    )
+    .context("Failed to write download cache index")
+}
+
+/// 从缓存读取 `url` 的内容，文件损坏或校验不一致时删除该项
+fn read_cached(url: &str) -> Option<Vec<u8>> {
+    let cache_dir = cache_dir()?;
+    if !is_cacheable(url) {
+        return None;
+    }
+    let _guard = CACHE_LOCK.lock().unwrap();
+    let mut index = load_index(&cache_dir);
+    let entry = index.get(url)?;
+
+    let blob = cache_dir.join(&entry.sha256);
+    if let Ok(bytes) = fs::read(&blob) {
+        if bytes.len() as u64 == entry.size && hex::encode(Sha256::digest(&bytes)) == entry.sha256 {
+            return Some(bytes);
+        }
+    }
+    log::warn!("Discarding corrupted download cache for {}", url);
+    let _ = fs::remove_file(&blob);
+    index.remove(url);
+    let _ = save_index(&cache_dir, &index);
+    None
+}
+
+/// 按内容的 SHA-256 保存下载的文件，并在索引中记录下载地址
+fn store_cached(url: &str, bytes: &[u8]) -> Result<()> {
+    let Some(cache_dir) = cache_dir() else {
+        return Ok(());
+    };
+    if !is_cacheable(url) {
+        return Ok(());
+    }
+    let _guard = CACHE_LOCK.lock().unwrap();
+    utils::ensure_dir(&cache_dir)?;
+
+    let sha256 = hex::encode(Sha256::digest(bytes));
+    let blob = cache_dir.join(&sha256);
+    if !blob.exists() {
+        // 先写临时文件再改名，避免中断时留下不完整的缓存
+        let temp = cache_dir.join(format!("{}.part", sha256));
+        fs::write(&temp, bytes).context("Failed to write download cache")?;
+        fs::rename(&temp, &blob).context("Failed to write download cache")?;
+    }
+
+    let mut index = load_index(&cache_dir);
+    index.insert(
+        url.to_string(),
+        CacheEntry {
+            sha256,
+            size: bytes.len() as u64,
+            downloaded_at: utils::get_timestamp(),
+        },
+    );
+    save_index(&cache_dir, &index)
+}
+
+/// 下载缓存的文件数和总大小
+pub fn cache_info() -> Result<DownloadCacheInfo> {
+    let cache_dir = cache_dir().context("Download cache is not configured")?;
+    let mut files = 0;
+    let mut size = 0;
+    if cache_dir.is_dir() {
+        for entry in fs::read_dir(&cache_dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_file() && entry.file_name() != CACHE_INDEX {
+                files += 1;
+                size += metadata.len();
+            }
+        }
+    }
+    Ok(DownloadCacheInfo {
+        path: cache_dir.to_string_lossy().to_string(),
+        files,
+        size,
+    })
+}
+
+/// 清空下载缓存，返回释放的字节数
+pub fn clear_cache() -> Result<u64> {
+    let info = cache_info()?;
+    let _guard = CACHE_LOCK.lock().unwrap();
+    let cache_dir = Path::new(&info.path);
+    if cache_dir.is_dir() {
+        fs::remove_dir_all(cache_dir)
+            .with_context(|| format!("Failed to remove {}", cache_dir.display()))?;
+    }
+    Ok(info.size)
+}
+
+/// 为外部下载工具（brew、curl 等）设置代理和 Homebrew 镜像的环境变量
+pub fn apply_env(command: &mut Command) -> &mut Command {
+    let config = CONFIG.read().unwrap();
+    if let Some(proxy) = config.proxy.as_deref() {
+        for key in [
+            "http_proxy",
+            "https_proxy",
+            "HTTP_PROXY",
+            "HTTPS_PROXY",
+            "ALL_PROXY",
+        ] {
+            command.env(key, proxy);
+        }
+    }
+    if let Some(mirror) = config.mirrors.get("homebrew") {
+        command
+            .env("HOMEBREW_BOTTLE_DOMAIN", mirror)
+            .env("HOMEBREW_API_DOMAIN", format!("{}/api", mirror));
+    }
+    command
+}
@@ -0,0 +1,292 @@
+use crate::core::backend::{self, DatabaseBackend, InstallOptions};
+use crate::core::{
+    download, network, sqlite, utils, watchdog, BackendKind, DatabaseInfo, DatabaseStatus,
+    DatabaseType, SqliteFile,
+};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// 未指定版本时下载的 DuckDB CLI 版本
+pub const DEFAULT_VERSION: &str = "1.2.1";
+
+/// 安装时创建的默认数据库文件，连接字符串也指向该文件
+pub const DEFAULT_FILE: &str = "main.duckdb";
+
+/// DuckDB 文件头中位于第 8 字节起的魔数
+const DUCKDB_MAGIC: &[u8] = b"DUCK";
+
+/// DuckDB 后端：实例是数据目录下的一组 .duckdb 文件，不需要服务进程即可使用
+///
+/// 启动时用 DuckDB CLI 加载 httpserver 扩展，通过 HTTP 对外提供查询；停止后文件仍可直接打开。
+/// 状态表示 HTTP 服务是否在运行。
+pub struct DuckdbBackend;
+
+impl DatabaseBackend for DuckdbBackend {
+    fn install(
+        &self,
+        db_type: &DatabaseType,
+        storage_path: &Path,
+        options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let version = options.version.unwrap_or(DEFAULT_VERSION);
+        let bin_dir = utils::get_db_bin_path(storage_path, "duckdb");
+        let binary_path = bin_dir.join(binary_name());
+        if !binary_path.exists() {
+            download_cli(version, &bin_dir)?;
+        }
+
+        let instance_dir = utils::get_instance_dir_name(db_type.as_str(), options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+
+        let log_file = logs_dir.join("httpserver.log");
+        let port = options
+            .port
+            .unwrap_or_else(|| DatabaseType::DuckDB.default_port());
+        let db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options.instance_name.unwrap_or("DuckDB").to_string(),
+            db_type: DatabaseType::DuckDB,
+            backend: BackendKind::Native,
+            version: version.to_string(),
+            install_path: bin_dir.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_file.to_string_lossy().to_string(),
+            port,
+            username: options.username.map(|s| s.to_string()),
+            password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
+            config: None,
+            // HTTP 服务按需启动
+            status: DatabaseStatus::Stopped,
+            auto_start: false,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+        create_file(&db_info, DEFAULT_FILE)?;
+
+        Ok(db_info)
+    }
+
+    /// 启动 httpserver，`httpserve_start` 在前台模式下阻塞，进程即为 HTTP 服务
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()> {
+        if watchdog::is_process_alive(db_info) {
+            return Ok(());
+        }
+
+        let log_file = fs::File::create(&db_info.log_path)
+            .with_context(|| format!("Failed to create {}", db_info.log_path))?;
+        let mut child = Command::new(Path::new(&db_info.install_path).join(binary_name()))
+            .arg(default_file_path(db_info))
+            .env("DUCKDB_HTTPSERVER_FOREGROUND", "1")
+            .stdin(Stdio::piped())
+            .stdout(log_file.try_clone()?)
+            .stderr(log_file)
+            .spawn()
+            .context("Failed to start DuckDB")?;
+
+        // 通过 stdin 传入启动语句，避免认证信息出现在进程列表中
+        let sql = format!(
+            "INSTALL httpserver FROM community;\nLOAD httpserver;\nSELECT httpserve_start('{}', {}, '{}');\n",
+            db_info.bind_address,
+            db_info.port,
+            http_auth(db_info).replace('\'', "''")
+        );
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(sql.as_bytes())
+                .context("Failed to send startup statements to DuckDB")?;
+        }
+
+        fs::write(watchdog::pid_file_path(db_info), child.id().to_string())?;
+        Ok(())
+    }
+
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()> {
+        if let Some(pid) = watchdog::read_pid(db_info) {
+            if watchdog::is_process_alive(db_info) {
+                kill_process(pid)?;
+            }
+            let _ = fs::remove_file(watchdog::pid_file_path(db_info));
+        }
+        Ok(())
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        if watchdog::is_process_alive(db_info) {
+            Some(DatabaseStatus::Running)
+        } else {
+            Some(DatabaseStatus::Stopped)
+        }
+    }
+
+    fn is_supervised(&self) -> bool {
+        true
+    }
+
+    fn uninstall(&self, db_info: &DatabaseInfo, remove_binaries: bool) -> Result<()> {
+        self.stop(db_info)?;
+        backend::remove_instance_files(db_info)?;
+        if remove_binaries {
+            backend::remove_binary_dir(db_info)?;
+        }
+        Ok(())
+    }
+}
+
+/// 默认数据库文件的路径
+pub fn default_file_path(db_info: &DatabaseInfo) -> PathBuf {
+    Path::new(&db_info.data_path).join(DEFAULT_FILE)
+}
+
+/// 列出实例数据目录下的 .duckdb 文件
+pub fn list_files(db_info: &DatabaseInfo) -> Result<Vec<SqliteFile>> {
+    sqlite::list_files_with_extension(db_info, "duckdb")
+}
+
+/// 在实例数据目录下创建数据库文件，`name` 不带扩展名时补上 `.duckdb`
+///
+/// 通过 CLI 创建，保证写入有效的 DuckDB 文件头。
+pub fn create_file(db_info: &DatabaseInfo, name: &str) -> Result<SqliteFile> {
+    let path = sqlite::new_file_path(db_info, name, "duckdb")?;
+    let output = Command::new(Path::new(&db_info.install_path).join(binary_name()))
+        .arg(&path)
+        .arg("-c")
+        .arg("CHECKPOINT;")
+        .output()
+        .context("Failed to run DuckDB CLI")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to create {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    sqlite::file_info(&path)
+}
+
+/// 检查默认数据库文件是否为有效的 DuckDB 文件
+pub fn check_file(db_info: &DatabaseInfo) -> Result<()> {
+    let path = default_file_path(db_info);
+    let mut header = Vec::with_capacity(8 + DUCKDB_MAGIC.len());
+    fs::File::open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?
+        .take((8 + DUCKDB_MAGIC.len()) as u64)
+        .read_to_end(&mut header)?;
+    if header.get(8..) != Some(DUCKDB_MAGIC) {
+        bail!("{} is not a DuckDB database", path.display());
+    }
+    Ok(())
+}
+
+/// 通过 httpserver 执行一条查询并返回响应内容，需要 HTTP 服务已启动
+pub fn query(db_info: &DatabaseInfo, sql: &str, timeout: Duration) -> Result<String> {
+    let mut request = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()?
+        .get(format!("http://127.0.0.1:{}/", db_info.port))
+        .query(&[("q", sql)]);
+    let password = db_info.password.as_deref().unwrap_or_default();
+    match db_info.username.as_deref().filter(|name| !name.is_empty()) {
+        Some(username) => request = request.basic_auth(username, Some(password)),
+        None if !password.is_empty() => request = request.header("X-API-Key", password),
+        None => {}
+    }
+
+    let response = request
+        .send()
+        .with_context(|| format!("Failed to connect to port {}", db_info.port))?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("DuckDB returned {}", status);
+    }
+    Ok(response.text()?)
+}
+
+/// httpserver 的认证参数：有用户名时为 Basic 认证的 `user:pass`，只有密码时作为 API key，均未设置时不认证
+fn http_auth(db_info: &DatabaseInfo) -> String {
+    let password = db_info.password.as_deref().unwrap_or_default();
+    match db_info.username.as_deref().filter(|name| !name.is_empty()) {
+        Some(username) => format!("{}:{}", username, password),
+        None => password.to_string(),
+    }
+}
+
+fn binary_name() -> &'static str {
+    if cfg!(windows) {
+        "duckdb.exe"
+    } else {
+        "duckdb"
+    }
+}
+
+/// 下载官方 CLI 压缩包，其中只有一个 duckdb 可执行文件
+fn download_cli(version: &str, bin_dir: &Path) -> Result<()> {
+    let platform = if cfg!(target_os = "macos") {
+        "osx-universal"
+    } else if cfg!(windows) {
+        "windows-amd64"
+    } else if cfg!(target_arch = "aarch64") {
+        "linux-aarch64"
+    } else {
+        "linux-amd64"
+    };
+    let url = format!(
+        "https://github.com/duckdb/duckdb/releases/download/v{}/duckdb_cli-{}.zip",
+        version, platform
+    );
+
+    let bytes = download::fetch_blocking(&url, None)?;
+    let mut archive =
+        zip::ZipArchive::new(std::io::Cursor::new(bytes)).context("Failed to open zip archive")?;
+    let mut entry = archive
+        .by_name(binary_name())
+        .context("duckdb not found in archive")?;
+
+    utils::ensure_dir(bin_dir)?;
+    let binary_path = bin_dir.join(binary_name());
+    let mut file = fs::File::create(&binary_path)
+        .with_context(|| format!("Failed to create {}", binary_path.display()))?;
+    std::io::copy(&mut entry, &mut file)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&binary_path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn kill_process(pid: u32) -> Result<()> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    kill(Pid::from_raw(pid as i32), Signal::SIGTERM).context("Failed to send SIGTERM to DuckDB")
+}
+
+#[cfg(windows)]
+fn kill_process(pid: u32) -> Result<()> {
+    Command::new("taskkill")
+        .arg("/PID")
+        .arg(pid.to_string())
+        .arg("/F")
+        .output()
+        .context("Failed to stop process via taskkill")?;
+    Ok(())
+}
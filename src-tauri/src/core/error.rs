@@ -0,0 +1,123 @@
+use crate::core::FieldError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 前端可以识别和本地化的错误，序列化为 `{ "code": "port_in_use", "context": { "port": 3306 } }`
+///
+/// 核心模块中随 `anyhow::Error` 传递（可作为错误本身或 context），命令通过 [`AppError::of`] 从错误链中取回。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "code", content = "context", rename_all = "snake_case")]
+pub enum AppError {
+    /// 数据库实例不存在
+    DatabaseNotFound,
+    /// 端口已被其他进程或实例占用
+    PortInUse { port: u16 },
+    /// 下载发行包失败
+    DownloadFailed { url: String, reason: String },
+    /// macOS 上找不到 Homebrew，且自动安装失败
+    BrewMissing,
+    /// 目标目录已存在且不为空
+    DataDirNotEmpty { path: String },
+    /// 磁盘剩余空间不足（字节）
+    InsufficientDiskSpace {
+        path: String,
+        required: u64,
+        available: u64,
+    },
+    /// 没有可用的容器运行时
+    DockerUnavailable,
+    /// 设置中有不合法的字段
+    InvalidSettings { fields: Vec<FieldError> },
+}
+
+impl AppError {
+    /// 错误链中的第一个 `AppError`，错误不是由已知原因导致时返回 None
+    pub fn of(error: &anyhow::Error) -> Option<AppError> {
+        error.downcast_ref::<AppError>().cloned()
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::DatabaseNotFound => write!(f, "Database not found"),
+            AppError::PortInUse { port } => write!(f, "Port {} is already in use", port),
+            AppError::DownloadFailed { url, reason } => {
+                write!(f, "Failed to download {}: {}", url, reason)
+            }
+            AppError::BrewMissing => write!(
+                f,
+                "Homebrew is not installed and could not be installed automatically"
+            ),
+            AppError::DataDirNotEmpty { path } => {
+                write!(f, "{} already exists and is not empty", path)
+            }
+            AppError::InsufficientDiskSpace {
+                path,
+                required,
+                available,
+            } => write!(
+                f,
+                "Not enough disk space in {}: {} required, {} available",
+                path,
+                crate::core::utils::format_file_size(*required),
+                crate::core::utils::format_file_size(*available)
+            ),
+            AppError::DockerUnavailable => write!(f, "Docker or Podman is not installed"),
+            AppError::InvalidSettings { fields } => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|error| format!("{}: {}", error.field, error.message))
+                    .collect();
+                write!(f, "Invalid settings: {}", fields.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// 直接返回 `Result` 的命令的错误：已知原因序列化为 `{ code, context }`，其他错误为文本
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum CommandError {
+    App(AppError),
+    Message(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::App(error) => error.fmt(f),
+            CommandError::Message(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<AppError> for CommandError {
+    fn from(error: AppError) -> Self {
+        CommandError::App(error)
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Message(message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        CommandError::Message(message.to_string())
+    }
+}
+
+/// 错误链中有 `AppError` 时保留错误码，否则使用完整的错误信息
+impl From<anyhow::Error> for CommandError {
+    fn from(error: anyhow::Error) -> Self {
+        match AppError::of(&error) {
+            Some(app_error) => CommandError::App(app_error),
+            None => CommandError::Message(format!("{:#}", error)),
+        }
+    }
+}
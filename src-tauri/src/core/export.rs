@@ -0,0 +1,403 @@
+use crate::core::backup::{mongodb_command, mysql_command, postgresql_command};
+use crate::core::provision::{mysql_ident, postgresql_ident};
+use crate::core::query::{self, unescape_mysql};
+use crate::core::{
+    mongo, utils, BackupMode, BackupRecord, DatabaseInfo, DatabaseStatus, DatabaseType,
+    ExportFormat,
+};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// 导出数据库中的数据，返回登记到备份历史中的记录
+///
+/// SQL 格式用 mysqldump / pg_dump 把整个数据库导出为 `path` 指定的文件；
+/// CSV / JSON 格式在 `path` 目录下为每张表（集合）生成一个文件。
+/// JSON 文件是对象数组，MySQL 导出的值均为字符串。
+pub fn export_data(
+    db_info: &DatabaseInfo,
+    target_db: &str,
+    format: ExportFormat,
+    path: &Path,
+) -> Result<BackupRecord> {
+    if db_info.status != DatabaseStatus::Running {
+        bail!("{} must be running to export data", db_info.name);
+    }
+    if target_db.is_empty() {
+        bail!("A database to export is required");
+    }
+    if !matches!(
+        db_info.db_type,
+        DatabaseType::MySQL
+            | DatabaseType::MariaDB
+            | DatabaseType::SeekDB
+            | DatabaseType::PostgreSQL
+            | DatabaseType::MongoDB
+    ) {
+        bail!(
+            "Exporting data is not supported for {}",
+            db_info.db_type.display_name()
+        );
+    }
+
+    let file_size = match format {
+        ExportFormat::Sql => {
+            if path.is_dir() {
+                bail!(
+                    "SQL exports are written to a file, not a directory: {}",
+                    path.display()
+                );
+            }
+            if let Some(parent) = path.parent() {
+                utils::ensure_dir(parent)?;
+            }
+            let mut command = match db_info.db_type {
+                DatabaseType::PostgreSQL => {
+                    let mut command = postgresql_command(db_info, "pg_dump");
+                    command.arg(format!("--dbname={}", target_db));
+                    command
+                }
+                DatabaseType::MongoDB => {
+                    bail!("MongoDB cannot be exported as SQL, export JSON instead")
+                }
+                _ => {
+                    let mut command = mysql_command(db_info, "mysqldump");
+                    command
+                        .arg("--single-transaction")
+                        .arg("--routines")
+                        .arg(target_db);
+                    command
+                }
+            };
+            run_to_file(&mut command, path)?;
+            fs::metadata(path)?.len()
+        }
+        ExportFormat::Csv | ExportFormat::Json => {
+            if path.is_file() {
+                bail!(
+                    "CSV and JSON exports are written to a directory: {}",
+                    path.display()
+                );
+            }
+            let created = !path.exists();
+            utils::ensure_dir(path)?;
+            let result = match db_info.db_type {
+                DatabaseType::PostgreSQL => {
+                    export_postgresql_tables(db_info, target_db, format, path)
+                }
+                DatabaseType::MongoDB => export_mongo_collections(db_info, target_db, format, path),
+                _ => export_mysql_tables(db_info, target_db, format, path),
+            };
+            if let Err(e) = result {
+                // 只清理本次创建的目录，不删除用户已有目录中的文件
+                if created {
+                    let _ = fs::remove_dir_all(path);
+                }
+                return Err(e);
+            }
+            utils::dir_size(path)
+        }
+    };
+
+    Ok(BackupRecord {
+        id: utils::generate_id(),
+        database_id: db_info.id.clone(),
+        file_path: path.to_string_lossy().to_string(),
+        file_size,
+        created_at: utils::get_timestamp(),
+        mode: BackupMode::Export,
+        encrypted: false,
+        salt: None,
+        remote_url: None,
+        export_format: Some(format),
+        export_database: Some(target_db.to_string()),
+    })
+}
+
+/// `mysql --batch` 逐行读出表数据，再转换为 CSV 或 JSON
+fn export_mysql_tables(
+    db_info: &DatabaseInfo,
+    target_db: &str,
+    format: ExportFormat,
+    dir: &Path,
+) -> Result<()> {
+    let tables = query::execute(
+        db_info,
+        Some(target_db),
+        "SELECT TABLE_NAME FROM information_schema.TABLES \
+        WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE' ORDER BY TABLE_NAME",
+        query::MAX_ROW_LIMIT,
+        query::DEFAULT_QUERY_TIMEOUT,
+    )?;
+    for table in tables
+        .rows
+        .into_iter()
+        .filter_map(|row| row.into_iter().next().flatten())
+    {
+        let mut command = mysql_command(db_info, "mysql");
+        command
+            .arg("--batch")
+            .arg("--quick")
+            .arg("--default-character-set=utf8mb4")
+            .arg(format!("--database={}", target_db))
+            .arg(format!("--execute=SELECT * FROM {}", mysql_ident(&table)));
+        let target = dir.join(file_name(&table, format));
+        run_with_lines(&mut command, &target, |lines, out| {
+            let mut lines = lines.map(|line| {
+                line.map(|line| {
+                    line.split('\t')
+                        .map(|value| (value != "NULL").then(|| unescape_mysql(value)))
+                        .collect::<Vec<_>>()
+                })
+            });
+            let columns: Vec<String> = match lines.next() {
+                Some(header) => header?.into_iter().map(Option::unwrap_or_default).collect(),
+                None => Vec::new(),
+            };
+            write_rows(format, &columns, lines, out)
+        })
+        .with_context(|| format!("Failed to export table {}", table))?;
+    }
+    Ok(())
+}
+
+/// CSV 使用 COPY 输出，JSON 每行由 row_to_json 生成，保留 PostgreSQL 的值类型
+fn export_postgresql_tables(
+    db_info: &DatabaseInfo,
+    target_db: &str,
+    format: ExportFormat,
+    dir: &Path,
+) -> Result<()> {
+    let tables = query::execute(
+        db_info,
+        Some(target_db),
+        "SELECT schemaname, tablename FROM pg_tables \
+        WHERE schemaname NOT IN ('pg_catalog', 'information_schema') ORDER BY 1, 2",
+        query::MAX_ROW_LIMIT,
+        query::DEFAULT_QUERY_TIMEOUT,
+    )?;
+    for row in tables.rows {
+        let [Some(schema), Some(table)] = row.as_slice() else {
+            continue;
+        };
+        let relation = format!("{}.{}", postgresql_ident(schema), postgresql_ident(table));
+        let target = dir.join(file_name(&format!("{}.{}", schema, table), format));
+
+        let mut command = postgresql_command(db_info, "psql");
+        command
+            .arg(format!("--dbname={}", target_db))
+            .arg("--no-psqlrc")
+            .arg("--no-align")
+            .arg("--tuples-only")
+            .arg("--set=ON_ERROR_STOP=1");
+        let result = match format {
+            ExportFormat::Csv => {
+                command.arg(format!(
+                    "--command=COPY (SELECT * FROM {}) TO STDOUT WITH (FORMAT csv, HEADER)",
+                    relation
+                ));
+                run_to_file(&mut command, &target)
+            }
+            _ => {
+                command.arg(format!(
+                    "--command=SELECT row_to_json(t) FROM {} t",
+                    relation
+                ));
+                run_with_lines(&mut command, &target, |lines, out| {
+                    write_json_lines(lines, out)
+                })
+            }
+        };
+        result.with_context(|| format!("Failed to export table {}.{}", schema, table))?;
+    }
+    Ok(())
+}
+
+/// mongoexport 的 CSV 需要指定字段列表，集合没有固定结构，只支持 JSON
+fn export_mongo_collections(
+    db_info: &DatabaseInfo,
+    target_db: &str,
+    format: ExportFormat,
+    dir: &Path,
+) -> Result<()> {
+    if format != ExportFormat::Json {
+        bail!("MongoDB collections can only be exported as JSON");
+    }
+    let collections = mongo::list_collections(db_info, target_db)?;
+    for collection in collections
+        .into_iter()
+        .filter(|c| c.collection_type == "collection")
+    {
+        let target = dir.join(file_name(&collection.name, format));
+        let (mut command, _config) = mongodb_command(db_info, "mongoexport")?;
+        let output = command
+            .arg(format!("--db={}", target_db))
+            .arg(format!("--collection={}", collection.name))
+            .arg("--jsonArray")
+            .arg(format!("--out={}", target.display()))
+            .output()
+            .context("Failed to run mongoexport")?;
+        if !output.status.success() {
+            bail!(
+                "Failed to export collection {}: {}",
+                collection.name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 表名中可能含有路径分隔符等字符，替换为下划线后作为文件名
+fn file_name(name: &str, format: ExportFormat) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let extension = match format {
+        ExportFormat::Csv => "csv",
+        _ => "json",
+    };
+    format!("{}.{}", name, extension)
+}
+
+type Lines<'a> = &'a mut dyn Iterator<Item = std::io::Result<String>>;
+
+fn write_rows(
+    format: ExportFormat,
+    columns: &[String],
+    rows: impl Iterator<Item = std::io::Result<Vec<Option<String>>>>,
+    out: &mut dyn Write,
+) -> Result<()> {
+    if format == ExportFormat::Csv {
+        write_csv_record(out, columns.iter().map(|c| Some(c.as_str())))?;
+        for row in rows {
+            write_csv_record(out, row?.iter().map(|v| v.as_deref()))?;
+        }
+        return Ok(());
+    }
+
+    write!(out, "[")?;
+    for (index, row) in rows.enumerate() {
+        let object: serde_json::Map<String, serde_json::Value> = columns
+            .iter()
+            .cloned()
+            .zip(
+                row?.into_iter()
+                    .map(|v| v.map_or(serde_json::Value::Null, Into::into)),
+            )
+            .collect();
+        writeln!(out, "{}", if index == 0 { "" } else { "," })?;
+        serde_json::to_writer(&mut *out, &object)?;
+    }
+    writeln!(out, "\n]")?;
+    Ok(())
+}
+
+/// NULL 写为空字段，含分隔符、引号或换行的值加引号
+fn write_csv_record<'a>(
+    out: &mut dyn Write,
+    values: impl Iterator<Item = Option<&'a str>>,
+) -> Result<()> {
+    for (index, value) in values.enumerate() {
+        if index > 0 {
+            write!(out, ",")?;
+        }
+        let value = value.unwrap_or_default();
+        if value.contains([',', '"', '\n', '\r']) {
+            write!(out, "\"{}\"", value.replace('"', "\"\""))?;
+        } else {
+            write!(out, "{}", value)?;
+        }
+    }
+    writeln!(out)?;
+    Ok(())
+}
+
+/// 每行一个 JSON 值，合并为数组
+fn write_json_lines(lines: Lines<'_>, out: &mut dyn Write) -> Result<()> {
+    write!(out, "[")?;
+    for (index, line) in lines.enumerate() {
+        write!(out, "{}\n{}", if index == 0 { "" } else { "," }, line?)?;
+    }
+    writeln!(out, "\n]")?;
+    Ok(())
+}
+
+/// 运行客户端并把标准输出直接写入文件，失败时删除不完整的文件
+fn run_to_file(command: &mut Command, target: &Path) -> Result<()> {
+    let file = fs::File::create(target)
+        .with_context(|| format!("Failed to create {}", target.display()))?;
+    let output = command
+        .stdin(Stdio::null())
+        .stdout(file)
+        .stderr(Stdio::piped())
+        .output()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    if !output.status.success() {
+        let _ = fs::remove_file(target);
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// 运行客户端，把输出按行交给 `convert` 写入文件，失败时删除不完整的文件
+fn run_with_lines(
+    command: &mut Command,
+    target: &Path,
+    convert: impl FnOnce(Lines<'_>, &mut dyn Write) -> Result<()>,
+) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .context("Failed to read client output")?;
+    let stderr_reader = std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stderr.read_to_string(&mut output);
+        output
+    });
+
+    let stdout = child
+        .stdout
+        .take()
+        .context("Failed to read client output")?;
+    let result = fs::File::create(target)
+        .with_context(|| format!("Failed to create {}", target.display()))
+        .and_then(|file| {
+            let mut out = BufWriter::new(file);
+            convert(&mut BufReader::new(stdout).lines(), &mut out)?;
+            out.flush()?;
+            Ok(())
+        });
+
+    if result.is_err() {
+        let _ = child.kill();
+    }
+    let status = child.wait()?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    let result = result.and_then(|()| {
+        if status.success() {
+            Ok(())
+        } else {
+            bail!("{}", stderr.trim())
+        }
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(target);
+    }
+    result
+}
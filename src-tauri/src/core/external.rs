@@ -0,0 +1,586 @@
+use crate::core::backend::{DatabaseBackend, InstallOptions};
+use crate::core::health::{self, DEFAULT_HEALTH_TIMEOUT};
+use crate::core::{
+    connection, distro, network, utils, windows_package, BackendKind, DatabaseInfo, DatabaseStatus,
+    DatabaseType,
+};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Windows 服务在 `install_path` 中的前缀
+const WINDOWS_SERVICE_PREFIX: &str = "windows-service:";
+
+/// systemd 服务单元在 `install_path` 中的前缀
+const SYSTEMD_PREFIX: &str = "systemd:";
+
+/// 通过端口接管、不知道由谁管理的实例在 `install_path` 中的前缀
+const ADOPTED_PREFIX: &str = "adopted:";
+
+/// 接管的实例判断运行状态时单次探测的超时时间
+const ADOPTED_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// 常见的数据库 systemd 服务单元、对应的数据库类型和提供它的软件包
+const SYSTEMD_UNITS: &[(&str, DatabaseType, &[&str])] = &[
+    (
+        "mariadb",
+        DatabaseType::MariaDB,
+        &["mariadb-server", "mariadb"],
+    ),
+    (
+        "mysql",
+        DatabaseType::MySQL,
+        &["mysql-server", "mysql-community-server"],
+    ),
+    (
+        "mysqld",
+        DatabaseType::MySQL,
+        &["mysql-server", "mysql-community-server"],
+    ),
+    (
+        "postgresql",
+        DatabaseType::PostgreSQL,
+        &["postgresql", "postgresql-server"],
+    ),
+    ("redis-server", DatabaseType::Redis, &["redis-server"]),
+    ("redis", DatabaseType::Redis, &["redis"]),
+    (
+        "mongod",
+        DatabaseType::MongoDB,
+        &["mongodb-org-server", "mongodb-server"],
+    ),
+];
+
+/// 系统中已有的、由系统服务管理器管理的安装
+///
+/// 由自动发现注册，`install_path` 记录服务名（如 `windows-service:MySQL80`、`systemd:mariadb.service`）。
+/// 应用只负责启停和监控，卸载时只从列表中移除，不改动系统中的安装。
+/// 通过端口接管的实例记录为 `adopted:<端口>`，只能监控和连接，不能启停。
+pub struct ExternalServiceBackend;
+
+/// 外部安装对应的系统服务
+enum ExternalService<'a> {
+    Windows(&'a str),
+    Systemd(&'a str),
+    Adopted,
+}
+
+impl<'a> ExternalService<'a> {
+    fn of(db_info: &'a DatabaseInfo) -> Result<Self> {
+        let path = db_info.install_path.as_str();
+        if let Some(name) = path.strip_prefix(WINDOWS_SERVICE_PREFIX) {
+            if cfg!(target_os = "windows") {
+                return Ok(Self::Windows(name));
+            }
+        }
+        if let Some(unit) = path.strip_prefix(SYSTEMD_PREFIX) {
+            if cfg!(target_os = "linux") {
+                return Ok(Self::Systemd(unit));
+            }
+        }
+        if path.starts_with(ADOPTED_PREFIX) {
+            return Ok(Self::Adopted);
+        }
+        bail!("{} is not a known system service", db_info.install_path)
+    }
+}
+
+impl DatabaseBackend for ExternalServiceBackend {
+    fn install(
+        &self,
+        _db_type: &DatabaseType,
+        _storage_path: &Path,
+        _options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        bail!("Externally managed databases are discovered, not installed")
+    }
+
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()> {
+        match ExternalService::of(db_info)? {
+            ExternalService::Windows(name) => {
+                windows_package::run_elevated("sc.exe", &["start", name])
+            }
+            ExternalService::Systemd(unit) => {
+                distro::run_privileged(&format!("systemctl start {}\n", unit))
+            }
+            ExternalService::Adopted => bail!(
+                "{} is not managed by a known service manager, start it where it was installed",
+                db_info.name
+            ),
+        }
+    }
+
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()> {
+        match ExternalService::of(db_info)? {
+            ExternalService::Windows(name) => {
+                windows_package::run_elevated("sc.exe", &["stop", name])
+            }
+            ExternalService::Systemd(unit) => {
+                distro::run_privileged(&format!("systemctl stop {}\n", unit))
+            }
+            ExternalService::Adopted => bail!(
+                "{} is not managed by a known service manager, stop it where it was installed",
+                db_info.name
+            ),
+        }
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        match ExternalService::of(db_info).ok()? {
+            ExternalService::Windows(name) => windows_package::service_status(name),
+            ExternalService::Systemd(unit) => distro::unit_status(unit),
+            // 没有服务管理器可查询，能按协议连上即视为运行中
+            ExternalService::Adopted => Some(match health::probe(db_info, ADOPTED_PROBE_TIMEOUT) {
+                Ok(_) => DatabaseStatus::Running,
+                Err(_) => DatabaseStatus::Stopped,
+            }),
+        }
+    }
+
+    // 由系统服务管理器按服务配置重启
+    fn is_supervised(&self) -> bool {
+        false
+    }
+
+    fn uninstall(&self, _db_info: &DatabaseInfo, _remove_binaries: bool) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 发现本机已安装、尚未注册的数据库
+///
+/// `registered` 为已注册的数据库，`ignored` 为用户移除过的外部服务，两者中已有的服务和端口都会跳过。
+pub fn discover(registered: &[DatabaseInfo], ignored: &[String]) -> Vec<DatabaseInfo> {
+    let discovered = if cfg!(target_os = "windows") {
+        discover_windows_services()
+    } else if cfg!(target_os = "linux") {
+        discover_systemd_units()
+    } else {
+        Vec::new()
+    };
+    let mut found: Vec<DatabaseInfo> = Vec::new();
+    for db_info in discovered {
+        let known = registered
+            .iter()
+            .chain(found.iter())
+            .any(|db| db.install_path == db_info.install_path || db.port == db_info.port);
+        if !known && !ignored.contains(&db_info.install_path) {
+            found.push(db_info);
+        }
+    }
+    found
+}
+
+/// 接管本机端口上已在运行的数据库：按协议探测确认类型，提供凭据时验证能否登录
+///
+/// 返回的实例只能监控和连接，启停需在原来的安装中进行。
+pub fn adopt(
+    db_type: &DatabaseType,
+    port: u16,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<DatabaseInfo> {
+    if matches!(db_type, DatabaseType::SQLite | DatabaseType::DuckDB) {
+        bail!(
+            "{} is file based and cannot be adopted by port",
+            db_type.display_name()
+        );
+    }
+
+    let mut db_info = DatabaseInfo {
+        id: utils::generate_id(),
+        name: format!("{} (port {})", db_type.display_name(), port),
+        db_type: db_type.clone(),
+        backend: BackendKind::External,
+        version: "detected".to_string(),
+        install_path: format!("{}{}", ADOPTED_PREFIX, port),
+        data_path: "Managed outside the app".to_string(),
+        log_path: String::new(),
+        port,
+        username,
+        password,
+        credential_ref: None,
+        config: None,
+        status: DatabaseStatus::Running,
+        auto_start: false,
+        depends_on: Vec::new(),
+        env: HashMap::new(),
+        extra_args: Vec::new(),
+        schedules: Vec::new(),
+        on_demand: false,
+        replica_of: None,
+        bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+        pid: None,
+        created_at: utils::get_timestamp(),
+        updated_at: utils::get_timestamp(),
+    };
+
+    let mut version = health::probe(&db_info, DEFAULT_HEALTH_TIMEOUT).with_context(|| {
+        format!(
+            "No {} server is responding on port {}",
+            db_type.display_name(),
+            port
+        )
+    })?;
+    if db_info.username.is_some() || db_info.password.is_some() {
+        let authenticated = connection::test_connection(&db_info)
+            .context("The server is running but the credentials were rejected")?;
+        version = authenticated.or(version);
+    }
+    if let Some(version) = version {
+        db_info.version = version;
+    }
+    Ok(db_info)
+}
+
+/// 通过服务控制管理器查找数据库服务，按服务程序识别类型，从启动参数和常见安装位置找到配置文件读取端口
+fn discover_windows_services() -> Vec<DatabaseInfo> {
+    let script = "Get-CimInstance Win32_Service | Where-Object { $_.PathName } | \
+                  Select-Object Name, PathName, State | ConvertTo-Json -Compress";
+    let Ok(output) = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .output()
+    else {
+        return Vec::new();
+    };
+    let Ok(services) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+        return Vec::new();
+    };
+    // 只有一个服务时 ConvertTo-Json 输出的是对象而不是数组
+    let services = match services {
+        serde_json::Value::Array(services) => services,
+        service => vec![service],
+    };
+
+    services
+        .iter()
+        .filter_map(|service| {
+            let name = service["Name"].as_str()?;
+            // 应用自己注册的服务由软件包后端管理
+            if name.starts_with("local-db-") {
+                return None;
+            }
+            let running = service["State"].as_str() == Some("Running");
+            windows_service_info(name, service["PathName"].as_str()?, running)
+        })
+        .collect()
+}
+
+/// 根据服务的启动命令生成数据库信息，不是已知的数据库服务时返回 None
+fn windows_service_info(name: &str, command_line: &str, running: bool) -> Option<DatabaseInfo> {
+    let args = split_command_line(command_line);
+    let program = PathBuf::from(args.first()?);
+    let program_name = program.file_name()?.to_string_lossy().to_lowercase();
+    let install_dir = program.parent()?.to_path_buf();
+    let is_mariadb = command_line.to_lowercase().contains("mariadb");
+
+    let (db_type, config, data_dir) = match program_name.as_str() {
+        "mysqld.exe" | "mariadbd.exe" => {
+            let db_type = if is_mariadb || program_name == "mariadbd.exe" {
+                DatabaseType::MariaDB
+            } else {
+                DatabaseType::MySQL
+            };
+            let config = option_value(&args, "--defaults-file")
+                .map(PathBuf::from)
+                .or_else(|| existing(&[install_dir.join("..").join("my.ini")]));
+            (db_type, config, None)
+        }
+        // PostgreSQL 服务通过 pg_ctl runservice 启动，配置文件在数据目录下
+        "pg_ctl.exe" | "postgres.exe" => {
+            let data_dir = option_value(&args, "-D").map(PathBuf::from);
+            let config = data_dir.as_ref().map(|dir| dir.join("postgresql.conf"));
+            (DatabaseType::PostgreSQL, config, data_dir)
+        }
+        "redis-server.exe" => {
+            let config = args
+                .iter()
+                .skip(1)
+                .find(|arg| arg.ends_with(".conf"))
+                .map(|arg| install_dir.join(arg))
+                .or_else(|| existing(&[install_dir.join("redis.windows-service.conf")]));
+            (DatabaseType::Redis, config, None)
+        }
+        "mongod.exe" => {
+            let config = option_value(&args, "--config")
+                .or_else(|| option_value(&args, "-f"))
+                .map(PathBuf::from)
+                .or_else(|| existing(&[install_dir.join("mongod.cfg")]));
+            (DatabaseType::MongoDB, config, None)
+        }
+        _ => return None,
+    };
+
+    Some(external_info(
+        db_type,
+        name,
+        format!("{}{}", WINDOWS_SERVICE_PREFIX, name),
+        version_from_path(&program),
+        config,
+        data_dir,
+        running,
+    ))
+}
+
+/// 通过 systemctl 查找常见的数据库服务单元，从软件包查询版本，从软件包默认的配置文件读取端口
+fn discover_systemd_units() -> Vec<DatabaseInfo> {
+    let units: Vec<String> = SYSTEMD_UNITS
+        .iter()
+        .map(|(unit, _, _)| format!("{}.service", unit))
+        .collect();
+    let Ok(output) = Command::new("systemctl")
+        .args(["show", "--property=Id,LoadState,ActiveState"])
+        .args(&units)
+        .output()
+    else {
+        return Vec::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let mut found: Vec<DatabaseInfo> = Vec::new();
+    // 每个单元输出一段 `key=value`，段之间以空行分隔
+    for block in stdout.split("\n\n") {
+        let property = |key: &str| {
+            block
+                .lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+        };
+        if property("LoadState") != Some("loaded") {
+            continue;
+        }
+        // 别名（如 Debian 上指向 mariadb.service 的 mysql.service）返回的是实际单元名
+        let Some(unit) = property("Id") else {
+            continue;
+        };
+        let install_path = format!("{}{}", SYSTEMD_PREFIX, unit);
+        if found.iter().any(|db| db.install_path == install_path) {
+            continue;
+        }
+        let Some((_, db_type, packages)) = SYSTEMD_UNITS
+            .iter()
+            .find(|(name, _, _)| unit.strip_suffix(".service") == Some(*name))
+        else {
+            continue;
+        };
+        let running = matches!(property("ActiveState"), Some("active" | "reloading"));
+        found.push(external_info(
+            db_type.clone(),
+            unit,
+            install_path,
+            packages.iter().find_map(|package| package_version(package)),
+            existing(&systemd_config_candidates(db_type)),
+            None,
+            running,
+        ));
+    }
+    found
+}
+
+/// 各发行版软件包默认的配置文件位置
+fn systemd_config_candidates(db_type: &DatabaseType) -> Vec<PathBuf> {
+    let paths: &[&str] = match db_type {
+        DatabaseType::MySQL => &[
+            "/etc/mysql/mysql.conf.d/mysqld.cnf",
+            "/etc/my.cnf.d/mysql-server.cnf",
+            "/etc/my.cnf",
+            "/etc/mysql/my.cnf",
+        ],
+        DatabaseType::MariaDB => &[
+            "/etc/mysql/mariadb.conf.d/50-server.cnf",
+            "/etc/my.cnf.d/mariadb-server.cnf",
+            "/etc/my.cnf.d/server.cnf",
+            "/etc/my.cnf",
+        ],
+        DatabaseType::PostgreSQL => &[
+            "/var/lib/pgsql/data/postgresql.conf",
+            "/var/lib/postgres/data/postgresql.conf",
+        ],
+        DatabaseType::Redis => &["/etc/redis/redis.conf", "/etc/redis.conf"],
+        DatabaseType::MongoDB => &["/etc/mongod.conf"],
+        _ => &[],
+    };
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    // Debian 系按主版本号放在 /etc/postgresql/<版本>/main 下，取最新的版本
+    if *db_type == DatabaseType::PostgreSQL {
+        if let Ok(entries) = fs::read_dir("/etc/postgresql") {
+            let mut versions: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .collect();
+            versions.sort_by_key(|path| {
+                path.file_name()
+                    .map(|name| {
+                        name.to_string_lossy()
+                            .split('.')
+                            .filter_map(|part| part.parse::<u32>().ok())
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default()
+            });
+            candidates.extend(
+                versions
+                    .iter()
+                    .rev()
+                    .map(|dir| dir.join("main").join("postgresql.conf")),
+            );
+        }
+    }
+    candidates.extend(paths.iter().map(PathBuf::from));
+    candidates
+}
+
+/// 软件包的上游版本号，去掉 epoch 和发行版修订号，如 `1:10.11.6-0+deb12u1` 得到 `10.11.6`
+fn package_version(package: &str) -> Option<String> {
+    let version = distro::installed_version(package)?;
+    let version = version.split_once(':').map_or(version.as_str(), |(_, v)| v);
+    let version = version.split(['-', '+']).next()?;
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// 生成外部安装的数据库信息，端口和数据目录优先从配置文件读取
+fn external_info(
+    db_type: DatabaseType,
+    service: &str,
+    install_path: String,
+    version: Option<String>,
+    config: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    running: bool,
+) -> DatabaseInfo {
+    let config = config.filter(|path| path.is_file());
+    let content = config
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    let port = config_value(&content, "port")
+        .and_then(|port| port.parse().ok())
+        .unwrap_or_else(|| db_type.default_port());
+    let data_path = data_dir
+        .map(|dir| dir.to_string_lossy().to_string())
+        .or_else(|| {
+            ["datadir", "data_directory", "dir", "dbPath"]
+                .iter()
+                .find_map(|key| config_value(&content, key))
+        })
+        .unwrap_or_else(|| "Managed by the system service manager".to_string());
+
+    DatabaseInfo {
+        id: utils::generate_id(),
+        name: format!("{} ({})", db_type.display_name(), service),
+        db_type,
+        backend: BackendKind::External,
+        version: version.unwrap_or_else(|| "detected".to_string()),
+        install_path,
+        data_path,
+        log_path: String::new(),
+        port,
+        username: None,
+        password: None,
+        credential_ref: None,
+        config: config.map(|path| path.to_string_lossy().to_string()),
+        status: if running {
+            DatabaseStatus::Running
+        } else {
+            DatabaseStatus::Stopped
+        },
+        auto_start: false,
+        depends_on: Vec::new(),
+        env: HashMap::new(),
+        extra_args: Vec::new(),
+        schedules: Vec::new(),
+        on_demand: false,
+        replica_of: None,
+        bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+        pid: None,
+        created_at: utils::get_timestamp(),
+        updated_at: utils::get_timestamp(),
+    }
+}
+
+/// 按 Windows 命令行规则拆分参数，双引号内的空格不拆分，`--key="a b"` 得到 `--key=a b`
+fn split_command_line(command_line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut has_arg = false;
+    for c in command_line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                has_arg = true;
+            }
+            ' ' | '\t' if !quoted => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            _ => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// 命令行参数 `--key=value` 或 `--key value` 的值
+fn option_value(args: &[String], key: &str) -> Option<String> {
+    let prefix = format!("{}=", key);
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if let Some(value) = arg.strip_prefix(&prefix) {
+            Some(value.to_string())
+        } else if arg == key {
+            args.get(i + 1).cloned()
+        } else {
+            None
+        }
+    })
+}
+
+fn existing(candidates: &[PathBuf]) -> Option<PathBuf> {
+    candidates.iter().find(|path| path.is_file()).cloned()
+}
+
+/// 配置文件中某一项的值，兼容 `key=value`、`key = value`、`key value` 和 YAML 的 `key: value`
+///
+/// 不区分 ini 的分组和 YAML 的层级，取第一个匹配的项。
+fn config_value(content: &str, key: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        if line.starts_with(['#', ';']) {
+            return None;
+        }
+        let rest = line.strip_prefix(key)?;
+        if !rest.starts_with([' ', '\t', '=', ':']) {
+            return None;
+        }
+        let value = rest.trim_start_matches([' ', '\t', '=', ':']);
+        let value = value.split('#').next().unwrap_or_default().trim();
+        let value = value.trim_matches(['"', '\'']);
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// 安装路径中的版本号，如 `MySQL Server 8.0` 中的 8.0、`PostgreSQL\16` 中的 16
+fn version_from_path(path: &Path) -> Option<String> {
+    path.components().find_map(|component| {
+        let component = component.as_os_str().to_string_lossy();
+        if !component.is_empty() && component.chars().all(|c| c.is_ascii_digit()) {
+            return Some(component.to_string());
+        }
+        component
+            .split(|c: char| !c.is_ascii_digit() && c != '.')
+            .find(|part| {
+                part.contains('.')
+                    && part
+                        .split('.')
+                        .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+            })
+            .map(String::from)
+    })
+}
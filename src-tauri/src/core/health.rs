@@ -0,0 +1,279 @@
+use crate::core::{duckdb, sqlite, DatabaseInfo, DatabaseType, HealthCheckResult};
+use anyhow::{bail, Context, Result};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+/// 健康检查默认超时时间
+pub const DEFAULT_HEALTH_TIMEOUT: Duration = Duration::from_secs(3);
+/// 等待就绪时单次探测的超时时间
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+/// 等待就绪时的轮询间隔
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 对数据库执行协议级健康检查，返回延迟与错误详情
+pub fn check_health(db_info: &DatabaseInfo, timeout: Duration) -> HealthCheckResult {
+    let started = Instant::now();
+    let result = probe(db_info, timeout);
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(server_version) => HealthCheckResult {
+            healthy: true,
+            latency_ms,
+            server_version,
+            error: None,
+        },
+        Err(e) => HealthCheckResult {
+            healthy: false,
+            latency_ms,
+            server_version: None,
+            error: Some(format!("{:#}", e)),
+        },
+    }
+}
+
+/// 按数据库协议进行一次探测，成功时返回可识别的服务端版本
+pub fn probe(db_info: &DatabaseInfo, timeout: Duration) -> Result<Option<String>> {
+    match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::SeekDB => {
+            probe_mysql(db_info.port, timeout)
+        }
+        DatabaseType::PostgreSQL => probe_postgresql(db_info.port, timeout).map(|_| None),
+        DatabaseType::MongoDB => probe_mongodb(db_info.port, timeout).map(|_| None),
+        DatabaseType::Redis => {
+            probe_redis(db_info.port, db_info.password.as_deref(), timeout).map(|_| None)
+        }
+        DatabaseType::Qdrant => probe_http(db_info.port, "/readyz", timeout),
+        DatabaseType::SurrealDB => probe_http(db_info.port, "/health", timeout),
+        DatabaseType::Neo4j => probe_http(db_info.port, "/", timeout),
+        DatabaseType::OpenSearch => probe_http(db_info.port, "/_cluster/health", timeout),
+        DatabaseType::InfluxDB => probe_http(db_info.port, "/health", timeout),
+        DatabaseType::Cassandra => probe_cassandra(db_info.port, timeout).map(|_| None),
+        DatabaseType::SQLite => sqlite::check_file(db_info).map(|_| None),
+        DatabaseType::DuckDB => duckdb::query(db_info, "SELECT 1", timeout).map(|_| None),
+        // 自定义服务的协议未知，只检查端口能否连接
+        DatabaseType::Custom => connect(db_info.port, timeout).map(|_| None),
+    }
+}
+
+/// 轮询探测直到数据库可以连接，超过 `timeout` 仍未就绪时返回最后一次的错误
+pub fn wait_until_ready(db_info: &DatabaseInfo, timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match probe(
+            db_info,
+            remaining.clamp(Duration::from_millis(100), PROBE_TIMEOUT),
+        ) {
+            Ok(_) => return Ok(()),
+            Err(e) if Instant::now() >= deadline => {
+                return Err(e.context(format!(
+                    "{} did not become ready within {}s",
+                    db_info.name,
+                    timeout.as_secs()
+                )))
+            }
+            Err(_) => std::thread::sleep(READY_POLL_INTERVAL),
+        }
+    }
+}
+
+pub(super) fn connect(port: u16, timeout: Duration) -> Result<TcpStream> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let stream = TcpStream::connect_timeout(&addr, timeout)
+        .with_context(|| format!("Failed to connect to port {}", port))?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    Ok(stream)
+}
+
+/// 读取服务端握手包（protocol v10），从中解析版本号
+fn probe_mysql(port: u16, timeout: Duration) -> Result<Option<String>> {
+    let mut stream = connect(port, timeout)?;
+
+    let mut header = [0u8; 4];
+    stream
+        .read_exact(&mut header)
+        .context("No handshake received")?;
+    let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload)?;
+
+    match payload.first() {
+        Some(10) => {
+            let version = payload[1..]
+                .split(|&b| b == 0)
+                .next()
+                .map(|v| String::from_utf8_lossy(v).to_string());
+            Ok(version)
+        }
+        Some(0xff) => bail!(
+            "Server rejected connection: {}",
+            String::from_utf8_lossy(payload.get(3..).unwrap_or_default())
+        ),
+        _ => bail!("Unexpected handshake from server"),
+    }
+}
+
+/// 发送 SSLRequest，服务端应回复 'S' 或 'N'
+fn probe_postgresql(port: u16, timeout: Duration) -> Result<()> {
+    let mut stream = connect(port, timeout)?;
+
+    let mut request = Vec::with_capacity(8);
+    request.extend_from_slice(&8u32.to_be_bytes());
+    request.extend_from_slice(&80877103u32.to_be_bytes());
+    stream.write_all(&request)?;
+
+    let mut response = [0u8; 1];
+    stream
+        .read_exact(&mut response)
+        .context("No response to SSLRequest")?;
+    match response[0] {
+        b'S' | b'N' => Ok(()),
+        b'E' => bail!("Server returned an error during startup"),
+        _ => bail!("Unexpected response from server"),
+    }
+}
+
+/// 发送 OP_MSG `{ hello: 1, $db: "admin" }`
+fn probe_mongodb(port: u16, timeout: Duration) -> Result<()> {
+    const OP_MSG: i32 = 2013;
+
+    let mut document = Vec::new();
+    document.push(0x10);
+    document.extend_from_slice(b"hello\0");
+    document.extend_from_slice(&1i32.to_le_bytes());
+    document.push(0x02);
+    document.extend_from_slice(b"$db\0");
+    document.extend_from_slice(&6i32.to_le_bytes());
+    document.extend_from_slice(b"admin\0");
+    document.push(0x00);
+    let document_len = (document.len() + 4) as i32;
+
+    let body_len = 4 + 1 + document_len as usize;
+    let mut message = Vec::with_capacity(16 + body_len);
+    message.extend_from_slice(&((16 + body_len) as i32).to_le_bytes());
+    message.extend_from_slice(&1i32.to_le_bytes());
+    message.extend_from_slice(&0i32.to_le_bytes());
+    message.extend_from_slice(&OP_MSG.to_le_bytes());
+    message.extend_from_slice(&0u32.to_le_bytes());
+    message.push(0);
+    message.extend_from_slice(&document_len.to_le_bytes());
+    message.extend_from_slice(&document);
+
+    let mut stream = connect(port, timeout)?;
+    stream.write_all(&message)?;
+
+    let mut header = [0u8; 16];
+    stream
+        .read_exact(&mut header)
+        .context("No response to hello")?;
+    let op_code = i32::from_le_bytes([header[12], header[13], header[14], header[15]]);
+    if op_code != OP_MSG {
+        bail!("Unexpected response opcode {}", op_code);
+    }
+    Ok(())
+}
+
+/// 发送 PING（配置了密码时先 AUTH），期望返回 +PONG
+fn probe_redis(port: u16, password: Option<&str>, timeout: Duration) -> Result<()> {
+    let mut stream = connect(port, timeout)?;
+
+    if let Some(password) = password.filter(|p| !p.is_empty()) {
+        let command = format!(
+            "*2\r\n$4\r\nAUTH\r\n${}\r\n{}\r\n",
+            password.len(),
+            password
+        );
+        stream.write_all(command.as_bytes())?;
+        let reply = read_line(&mut stream)?;
+        if !reply.starts_with("+OK") {
+            bail!("AUTH failed: {}", reply.trim_start_matches('-'));
+        }
+    }
+
+    stream.write_all(b"*1\r\n$4\r\nPING\r\n")?;
+    let reply = read_line(&mut stream)?;
+    if reply.starts_with("+PONG") {
+        Ok(())
+    } else {
+        bail!("PING failed: {}", reply.trim_start_matches('-'))
+    }
+}
+
+/// CQL 原生协议操作码
+pub(super) const CQL_STARTUP: u8 = 0x01;
+pub(super) const CQL_READY: u8 = 0x02;
+const CQL_OPTIONS: u8 = 0x05;
+const CQL_SUPPORTED: u8 = 0x06;
+pub(super) const CQL_QUERY: u8 = 0x07;
+pub(super) const CQL_RESULT: u8 = 0x08;
+
+/// 发送 OPTIONS，期望返回 SUPPORTED
+fn probe_cassandra(port: u16, timeout: Duration) -> Result<()> {
+    let mut stream = connect(port, timeout)?;
+    write_cql_frame(&mut stream, CQL_OPTIONS, &[])?;
+    match read_cql_frame(&mut stream)? {
+        (CQL_SUPPORTED, _) => Ok(()),
+        (opcode, _) => bail!("Unexpected response opcode {}", opcode),
+    }
+}
+
+/// 写入一个 CQL v4 请求帧
+pub(super) fn write_cql_frame(stream: &mut TcpStream, opcode: u8, body: &[u8]) -> Result<()> {
+    let mut frame = Vec::with_capacity(9 + body.len());
+    frame.extend_from_slice(&[0x04, 0x00, 0x00, 0x00, opcode]);
+    frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    frame.extend_from_slice(body);
+    stream.write_all(&frame)?;
+    Ok(())
+}
+
+/// 读取一个 CQL 响应帧，返回操作码和帧体；ERROR 帧转为错误
+pub(super) fn read_cql_frame(stream: &mut TcpStream) -> Result<(u8, Vec<u8>)> {
+    let mut header = [0u8; 9];
+    stream
+        .read_exact(&mut header)
+        .context("No response from server")?;
+    let len = u32::from_be_bytes([header[5], header[6], header[7], header[8]]) as usize;
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+
+    if header[4] == 0x00 {
+        // ERROR: [int code][string message]
+        let message = body
+            .get(6..)
+            .map(|message| String::from_utf8_lossy(message).to_string())
+            .unwrap_or_default();
+        bail!("Server returned an error: {}", message);
+    }
+    Ok((header[4], body))
+}
+
+pub(super) fn read_line(stream: &mut TcpStream) -> Result<String> {
+    let mut line = Vec::new();
+    let mut byte = [0u8; 1];
+    while stream.read(&mut byte)? == 1 {
+        if byte[0] == b'\n' {
+            break;
+        }
+        line.push(byte[0]);
+    }
+    Ok(String::from_utf8_lossy(&line).trim_end().to_string())
+}
+
+fn probe_http(port: u16, path: &str, timeout: Duration) -> Result<Option<String>> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()?;
+    let response = client
+        .get(format!("http://127.0.0.1:{}{}", port, path))
+        .send()
+        .with_context(|| format!("Failed to connect to port {}", port))?;
+
+    if !response.status().is_success() {
+        bail!("{} returned {}", path, response.status());
+    }
+    Ok(None)
+}
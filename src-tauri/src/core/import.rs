@@ -0,0 +1,160 @@
+use crate::core::backup::{mysql_command, postgresql_command};
+use crate::core::{DatabaseInfo, DatabaseStatus, DatabaseType};
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::{ErrorKind, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// 每次写入客户端的块大小
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// 把 .sql 或 .sql.gz 文件通过 mysql / psql 导入到实例
+///
+/// `target_db` 为空时 MySQL 使用转储中的 USE 语句，PostgreSQL 导入到 postgres 库。
+/// 遇到第一条失败的语句即停止，错误信息中包含转储文件中的行号（.gz 文件为解压后的行号）。
+/// `on_progress` 按已读取的文件字节数报告 0-100 的进度。
+pub fn import_dump(
+    db_info: &DatabaseInfo,
+    path: &Path,
+    target_db: Option<&str>,
+    mut on_progress: impl FnMut(u8),
+) -> Result<()> {
+    if db_info.status != DatabaseStatus::Running {
+        bail!("{} must be running to import a dump", db_info.name);
+    }
+    let target_db = target_db.filter(|db| !db.is_empty());
+    let mut command = match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::SeekDB => {
+            let mut command = mysql_command(db_info, "mysql");
+            command.arg("--default-character-set=utf8mb4");
+            if let Some(database) = target_db {
+                command.arg(format!("--database={}", database));
+            }
+            command
+        }
+        DatabaseType::PostgreSQL => {
+            let mut command = postgresql_command(db_info, "psql");
+            // 通过 --file=- 读取标准输入时，psql 的错误带有输入的行号
+            command
+                .arg(format!("--dbname={}", target_db.unwrap_or("postgres")))
+                .arg("--no-psqlrc")
+                .arg("--quiet")
+                .arg("--set=ON_ERROR_STOP=1")
+                .arg("--file=-");
+            command
+        }
+        _ => bail!(
+            "Importing SQL dumps is not supported for {}",
+            db_info.db_type.display_name()
+        ),
+    };
+
+    let file =
+        fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let total = file.metadata()?.len().max(1);
+    let reader = ProgressReader {
+        inner: file,
+        read: 0,
+    };
+    let is_gzip = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"));
+    if is_gzip {
+        stream(
+            &mut command,
+            GzDecoder::new(reader),
+            |r| r.get_ref().read,
+            total,
+            &mut on_progress,
+        )
+    } else {
+        stream(&mut command, reader, |r| r.read, total, &mut on_progress)
+    }
+}
+
+/// 记录从文件读取的字节数，压缩文件按压缩后的大小计算进度
+struct ProgressReader {
+    inner: fs::File,
+    read: u64,
+}
+
+impl Read for ProgressReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n as u64;
+        Ok(n)
+    }
+}
+
+/// 把 `source` 写入客户端的标准输入，客户端提前退出时停止写入并返回其错误输出
+fn stream<R: Read>(
+    command: &mut Command,
+    mut source: R,
+    position: impl Fn(&R) -> u64,
+    total: u64,
+    on_progress: &mut impl FnMut(u8),
+) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run the database client")?;
+    let mut stdin = child.stdin.take().context("Failed to open client input")?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .context("Failed to read client output")?;
+    // 在单独的线程中读取错误输出，避免输出过多时客户端阻塞
+    let stderr_reader = std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stderr.read_to_string(&mut output);
+        output
+    });
+
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut last_progress = 0;
+    let write_result: Result<()> = loop {
+        let n = match source.read(&mut buffer) {
+            Ok(0) => break Ok(()),
+            Ok(n) => n,
+            Err(e) => break Err(anyhow::Error::new(e).context("Failed to read the dump file")),
+        };
+        match stdin.write_all(&buffer[..n]) {
+            Ok(()) => {}
+            // 客户端遇到错误后退出，以它的错误输出为准
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => break Ok(()),
+            Err(e) => break Err(anyhow::Error::new(e).context("Failed to send the dump")),
+        }
+        let progress = (position(&source).min(total) * 100 / total) as u8;
+        if progress != last_progress {
+            last_progress = progress;
+            on_progress(progress);
+        }
+    };
+    drop(stdin);
+
+    if write_result.is_err() {
+        let _ = child.kill();
+    }
+    let status = child
+        .wait()
+        .context("Failed to wait for the database client")?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    write_result?;
+    if !status.success() {
+        bail!("{}", error_message(&stderr));
+    }
+    Ok(())
+}
+
+/// 从客户端的错误输出中取出第一条错误及其后的上下文，跳过前面的 NOTICE / 警告
+fn error_message(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr.lines().collect();
+    match lines.iter().position(|line| line.contains("ERROR")) {
+        Some(index) => lines[index..].join("\n"),
+        None => stderr.trim().to_string(),
+    }
+}
@@ -0,0 +1,123 @@
+use crate::core::{health, DatabaseInfo, DatabaseStatus};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 未指定版本时下载的 InfluxDB 版本
+pub const DEFAULT_VERSION: &str = "2.7.11";
+
+/// 初始化时创建的组织
+pub const ORG: &str = "local-db";
+
+/// 初始化时创建的默认 bucket
+pub const BUCKET: &str = "default";
+
+/// 首次启动后等待 InfluxDB 可以初始化的超时时间
+const SETUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 发行包下载地址，`suffix` 形如 `_linux_amd64.tar.gz`、`-windows.zip`
+pub fn download_url(version: &str, suffix: &str) -> String {
+    format!(
+        "https://dl.influxdata.com/influxdb/releases/influxdb2-{}{}",
+        version, suffix
+    )
+}
+
+/// 把解压出的 `file_name` 移到 `bin_dir` 根目录，发行包在各平台的目录结构不同（如 `usr/bin/influxd`）
+pub fn flatten_binary(bin_dir: &Path, file_name: &str) -> Result<PathBuf> {
+    let target = bin_dir.join(file_name);
+    if target.exists() {
+        return Ok(target);
+    }
+    let found = find_file(bin_dir, file_name)?
+        .with_context(|| format!("{} not found in InfluxDB archive", file_name))?;
+    fs::rename(&found, &target).with_context(|| format!("Failed to move {}", found.display()))?;
+    Ok(target)
+}
+
+/// influxd 的启动参数，数据文件都放在实例数据目录下
+pub fn server_args(db_info: &DatabaseInfo) -> Vec<String> {
+    let data_dir = Path::new(&db_info.data_path);
+    vec![
+        "--bolt-path".to_string(),
+        data_dir.join("influxd.bolt").to_string_lossy().to_string(),
+        "--engine-path".to_string(),
+        data_dir.join("engine").to_string_lossy().to_string(),
+        "--http-bind-address".to_string(),
+        format!("{}:{}", db_info.bind_address, db_info.port),
+        "--reporting-disabled".to_string(),
+    ]
+}
+
+/// 初始化 InfluxDB，等同于执行 `influx setup`：创建管理员用户、组织和默认 bucket
+///
+/// 需要数据库已启动，会先等待其就绪。返回服务端生成的管理员 API token。
+pub fn setup(db_info: &DatabaseInfo, username: &str, password: &str) -> Result<String> {
+    health::wait_until_ready(db_info, SETUP_TIMEOUT)?;
+
+    let response = reqwest::blocking::Client::builder()
+        .timeout(health::DEFAULT_HEALTH_TIMEOUT)
+        .build()?
+        .post(format!("http://127.0.0.1:{}/api/v2/setup", db_info.port))
+        .json(&serde_json::json!({
+            "username": username,
+            "password": password,
+            "org": ORG,
+            "bucket": BUCKET,
+        }))
+        .send()
+        .context("Failed to connect to InfluxDB")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().unwrap_or_default();
+        bail!("InfluxDB setup failed ({}): {}", status, body.trim());
+    }
+
+    let body: serde_json::Value = response.json()?;
+    body["auth"]["token"]
+        .as_str()
+        .map(String::from)
+        .context("InfluxDB setup returned no token")
+}
+
+/// 安装流程的最后一步：实例已首次启动，完成初始化并把 token 保存为实例凭据
+///
+/// 初始化失败时停止进程；未开启自动启动时初始化完成后同样停止。
+pub fn finish_install(
+    db_info: &mut DatabaseInfo,
+    username: &str,
+    password: &str,
+    stop: fn(&DatabaseInfo) -> Result<()>,
+) -> Result<()> {
+    let token = match setup(db_info, username, password) {
+        Ok(token) => token,
+        Err(e) => {
+            let _ = stop(db_info);
+            return Err(e);
+        }
+    };
+    db_info.password = Some(token);
+
+    if db_info.auto_start {
+        db_info.status = DatabaseStatus::Running;
+    } else {
+        stop(db_info)?;
+        db_info.status = DatabaseStatus::Stopped;
+    }
+    Ok(())
+}
+
+fn find_file(dir: &Path, file_name: &str) -> Result<Option<PathBuf>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if let Some(found) = find_file(&path, file_name)? {
+                return Ok(Some(found));
+            }
+        } else if path.file_name().is_some_and(|name| name == file_name) {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
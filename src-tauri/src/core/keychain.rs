@@ -0,0 +1,65 @@
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 系统钥匙串中的服务名
+const SERVICE_NAME: &str = "local-db";
+
+lazy_static! {
+    /// 已写入钥匙串的密码缓存，避免每次保存状态都访问钥匙串
+    static ref STORED: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+}
+
+/// 本机 HTTP API 令牌在钥匙串中的账户名，与数据库 ID 不会冲突
+pub const API_TOKEN_ACCOUNT: &str = "api-token";
+
+/// 远程备份密钥在钥匙串中的账户名
+pub const REMOTE_BACKUP_SECRET_ACCOUNT: &str = "remote-backup-secret";
+
+/// 数据库凭据在 state.json 中的引用
+pub fn credential_ref(database_id: &str) -> String {
+    format!("keychain:{}/{}", SERVICE_NAME, database_id)
+}
+
+fn entry(database_id: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE_NAME, database_id).context("Failed to open system keychain")
+}
+
+/// 保存密码到系统钥匙串（macOS Keychain / Windows 凭据管理器 / Secret Service）
+pub fn store_password(database_id: &str, password: &str) -> Result<()> {
+    let mut stored = STORED.lock().unwrap();
+    if stored.get(database_id).is_some_and(|p| p == password) {
+        return Ok(());
+    }
+
+    entry(database_id)?
+        .set_password(password)
+        .context("Failed to store password in system keychain")?;
+    stored.insert(database_id.to_string(), password.to_string());
+    Ok(())
+}
+
+/// 从系统钥匙串读取密码，不存在时返回 None
+pub fn load_password(database_id: &str) -> Result<Option<String>> {
+    match entry(database_id)?.get_password() {
+        Ok(password) => {
+            STORED
+                .lock()
+                .unwrap()
+                .insert(database_id.to_string(), password.clone());
+            Ok(Some(password))
+        }
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).context("Failed to read password from system keychain"),
+    }
+}
+
+/// 删除钥匙串中的密码
+pub fn delete_password(database_id: &str) -> Result<()> {
+    STORED.lock().unwrap().remove(database_id);
+    match entry(database_id)?.delete_credential() {
+        Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e).context("Failed to delete password from system keychain"),
+    }
+}
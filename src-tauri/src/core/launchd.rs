@@ -0,0 +1,360 @@
+use crate::core::backend::{self, DatabaseBackend, InstallOptions};
+use crate::core::{
+    cassandra, influxdb, macos, neo4j, network, opensearch, BackendKind, DatabaseInfo,
+    DatabaseStatus, DatabaseType,
+};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const UNSUPPORTED_PLATFORM: &str = "LaunchAgents are only available on macOS";
+
+/// 由应用为每个实例生成 LaunchAgent、交给 launchd 管理的后端，仅用于 macOS
+///
+/// 程序文件的安装和配置与本机安装相同，之后以前台方式运行数据库进程。
+/// plist 写入 `~/Library/LaunchAgents`，`RunAtLoad` 跟随实例的自动启动设置，
+/// 进程异常退出时由 launchd 重新拉起，不依赖 brew services 的服务命名。
+pub struct LaunchAgentBackend;
+
+impl DatabaseBackend for LaunchAgentBackend {
+    fn install(
+        &self,
+        db_type: &DatabaseType,
+        storage_path: &Path,
+        options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        ensure_macos()?;
+        if matches!(
+            db_type,
+            DatabaseType::SQLite | DatabaseType::DuckDB | DatabaseType::Custom
+        ) {
+            bail!("{} does not run as a service", db_type.display_name());
+        }
+
+        // 安装时不启动，避免 brew services 或直接启动的进程占用端口
+        let native_options = InstallOptions {
+            auto_start: false,
+            ..*options
+        };
+        let mut db_info =
+            macos::install_database_via_homebrew(db_type, storage_path, &native_options)?;
+        db_info.backend = BackendKind::LaunchAgent;
+        db_info.auto_start = options.auto_start;
+
+        write_plist(&db_info)?;
+        if options.auto_start {
+            self.start(&db_info)?;
+            db_info.status = DatabaseStatus::Running;
+        }
+        Ok(db_info)
+    }
+
+    /// 重新生成 plist 后加载，自动启动设置修改后在下次启动时生效
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()> {
+        ensure_macos()?;
+        let target = service_target(db_info)?;
+        if !is_loaded(&target) {
+            let plist = write_plist(db_info)?;
+            launchctl(&["bootstrap", &gui_domain()?, &plist.to_string_lossy()])?;
+        }
+        // 已在运行时 kickstart 不会重启进程
+        launchctl(&["kickstart", &target])
+    }
+
+    /// 从 launchd 卸载，plist 保留，下次登录时仍按 `RunAtLoad` 决定是否启动
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()> {
+        ensure_macos()?;
+        let target = service_target(db_info)?;
+        if is_loaded(&target) {
+            launchctl(&["bootout", &target])?;
+        }
+        Ok(())
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        if !cfg!(target_os = "macos") {
+            return None;
+        }
+        let output = Command::new("launchctl")
+            .args(["print", &service_target(db_info).ok()?])
+            .output()
+            .ok()?;
+        // 未加载的服务 launchctl print 返回非零
+        if !output.status.success() {
+            return Some(DatabaseStatus::Stopped);
+        }
+        let running = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|line| line.trim() == "state = running");
+        Some(if running {
+            DatabaseStatus::Running
+        } else {
+            DatabaseStatus::Stopped
+        })
+    }
+
+    // launchd 按 KeepAlive 设置拉起崩溃的进程
+    fn is_supervised(&self) -> bool {
+        false
+    }
+
+    fn uninstall(&self, db_info: &DatabaseInfo, remove_binaries: bool) -> Result<()> {
+        self.stop(db_info)?;
+        let plist = plist_path(db_info)?;
+        if plist.exists() {
+            fs::remove_file(&plist)
+                .with_context(|| format!("Failed to remove {}", plist.display()))?;
+        }
+        backend::remove_instance_files(db_info)?;
+        if remove_binaries {
+            macos::uninstall_binaries(db_info)?;
+        }
+        Ok(())
+    }
+}
+
+fn ensure_macos() -> Result<()> {
+    if !cfg!(target_os = "macos") {
+        bail!(UNSUPPORTED_PLATFORM);
+    }
+    Ok(())
+}
+
+/// 实例的 launchd 标签，按实例 ID 区分同类型的多个实例
+fn label(db_info: &DatabaseInfo) -> String {
+    format!("com.local-db.{}.{}", db_info.db_type.as_str(), db_info.id)
+}
+
+fn plist_path(db_info: &DatabaseInfo) -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Home directory not found")?;
+    Ok(home
+        .join("Library")
+        .join("LaunchAgents")
+        .join(format!("{}.plist", label(db_info))))
+}
+
+/// 当前登录用户的 launchd 域 `gui/<uid>`
+fn gui_domain() -> Result<String> {
+    let output = Command::new("id")
+        .arg("-u")
+        .output()
+        .context("Failed to get user id")?;
+    Ok(format!(
+        "gui/{}",
+        String::from_utf8_lossy(&output.stdout).trim()
+    ))
+}
+
+fn service_target(db_info: &DatabaseInfo) -> Result<String> {
+    Ok(format!("{}/{}", gui_domain()?, label(db_info)))
+}
+
+fn is_loaded(target: &str) -> bool {
+    Command::new("launchctl")
+        .args(["print", target])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn launchctl(args: &[&str]) -> Result<()> {
+    let output = Command::new("launchctl")
+        .args(args)
+        .output()
+        .context("Failed to run launchctl")?;
+    if !output.status.success() {
+        bail!(
+            "launchctl {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// 以前台方式运行数据库进程的命令，launchd 直接管理该进程
+fn launch_command(db_info: &DatabaseInfo) -> Result<Command> {
+    let prefix = Path::new(&db_info.install_path);
+    let bin = |name: &str| prefix.join("bin").join(name);
+    let config = || db_info.config.as_deref().context("Config path missing");
+
+    let mut command = match db_info.db_type {
+        DatabaseType::Redis => {
+            let mut command = Command::new(bin("redis-server"));
+            command.arg(config()?);
+            command
+        }
+        DatabaseType::MySQL => {
+            let mut command = Command::new(bin("mysqld"));
+            command.arg(format!("--defaults-file={}", config()?));
+            command
+        }
+        DatabaseType::MariaDB => {
+            let mut command = Command::new(bin("mariadbd"));
+            command.arg(format!("--defaults-file={}", config()?));
+            command
+        }
+        DatabaseType::PostgreSQL => {
+            let mut command = Command::new(bin("postgres"));
+            command
+                .arg("-D")
+                .arg(&db_info.data_path)
+                .arg("-c")
+                .arg(format!("config_file={}", config()?));
+            command
+        }
+        DatabaseType::MongoDB => {
+            let mut command = Command::new(bin("mongod"));
+            command
+                .arg("--dbpath")
+                .arg(&db_info.data_path)
+                .arg("--port")
+                .arg(db_info.port.to_string())
+                .arg("--logappend")
+                .arg("--logpath")
+                .arg(&db_info.log_path);
+            command
+        }
+        DatabaseType::Qdrant => {
+            let mut command = Command::new(prefix.join("qdrant"));
+            command
+                .arg("--config-path")
+                .arg(config()?)
+                .env("QDRANT__SERVICE__HOST", &db_info.bind_address);
+            command
+        }
+        DatabaseType::SurrealDB => {
+            let mut command = Command::new(bin("surreal"));
+            command
+                .arg("start")
+                .arg("--bind")
+                .arg(format!("{}:{}", db_info.bind_address, db_info.port))
+                .args(["--log", "info"])
+                .arg("--user")
+                .arg(db_info.username.as_deref().unwrap_or("admin"))
+                .arg("--pass")
+                .arg(db_info.password.as_deref().unwrap_or("admin888"))
+                .arg(format!("rocksdb://{}", db_info.data_path));
+            command
+        }
+        DatabaseType::OpenSearch => {
+            let mut command = Command::new(bin("opensearch"));
+            command.env("OPENSEARCH_PATH_CONF", opensearch::config_dir(db_info)?);
+            command
+        }
+        DatabaseType::InfluxDB => {
+            let mut command = Command::new(bin("influxd"));
+            command.args(influxdb::server_args(db_info));
+            command
+        }
+        DatabaseType::Cassandra => cassandra::foreground_command(db_info)?,
+        DatabaseType::Neo4j => neo4j::foreground_command(db_info)?,
+        DatabaseType::SeekDB
+        | DatabaseType::SQLite
+        | DatabaseType::DuckDB
+        | DatabaseType::Custom => {
+            bail!(
+                "{} is not supported as a LaunchAgent",
+                db_info.db_type.display_name()
+            )
+        }
+    };
+    command
+        .args(network::bind_args(db_info))
+        .envs(&db_info.env)
+        .args(&db_info.extra_args)
+        .current_dir(&db_info.data_path);
+    Ok(command)
+}
+
+/// 实例的路径等启动参数变化后重新生成已有的 plist，避免登录时按旧参数启动
+pub fn refresh_plist(db_info: &DatabaseInfo) -> Result<()> {
+    ensure_macos()?;
+    if plist_path(db_info)?.exists() {
+        write_plist(db_info)?;
+    }
+    Ok(())
+}
+
+/// 生成实例的 LaunchAgent plist，包含密码等参数，只允许当前用户读取
+fn write_plist(db_info: &DatabaseInfo) -> Result<PathBuf> {
+    let command = launch_command(db_info)?;
+    let mut arguments = String::new();
+    for arg in std::iter::once(command.get_program()).chain(command.get_args()) {
+        arguments.push_str(&format!(
+            "\t\t<string>{}</string>\n",
+            xml_escape(&arg.to_string_lossy())
+        ));
+    }
+    let mut environment = String::new();
+    for (key, value) in command.get_envs() {
+        if let Some(value) = value {
+            environment.push_str(&format!(
+                "\t\t<key>{}</key>\n\t\t<string>{}</string>\n",
+                xml_escape(&key.to_string_lossy()),
+                xml_escape(&value.to_string_lossy())
+            ));
+        }
+    }
+    let working_dir = command
+        .get_current_dir()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let content = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>Label</key>
+	<string>{label}</string>
+	<key>ProgramArguments</key>
+	<array>
+{arguments}	</array>
+	<key>EnvironmentVariables</key>
+	<dict>
+{environment}	</dict>
+	<key>WorkingDirectory</key>
+	<string>{working_dir}</string>
+	<key>RunAtLoad</key>
+	<{run_at_load}/>
+	<key>KeepAlive</key>
+	<dict>
+		<key>SuccessfulExit</key>
+		<false/>
+	</dict>
+	<key>StandardOutPath</key>
+	<string>{log}</string>
+	<key>StandardErrorPath</key>
+	<string>{log}</string>
+</dict>
+</plist>
+"#,
+        label = label(db_info),
+        arguments = arguments,
+        environment = environment,
+        working_dir = xml_escape(&working_dir),
+        run_at_load = db_info.auto_start,
+        log = xml_escape(&db_info.log_path),
+    );
+
+    let path = plist_path(db_info)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(path)
+}
+
+pub(super) fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
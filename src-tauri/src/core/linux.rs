@@ -6,26 +6,7 @@ use anyhow::Result;
 use std::path::Path;
 
 /// Options used when installing a database on Linux.
-#[allow(dead_code)]
-pub struct LinuxInstallOptions<'a> {
-    pub version: Option<&'a str>,
-    pub port: Option<u16>,
-    pub username: Option<&'a str>,
-    pub password: Option<&'a str>,
-    pub auto_start: bool,
-}
-
-impl<'a> Default for LinuxInstallOptions<'a> {
-    fn default() -> Self {
-        Self {
-            version: None,
-            port: None,
-            username: None,
-            password: None,
-            auto_start: true,
-        }
-    }
-}
+pub type LinuxInstallOptions<'a> = crate::core::backend::InstallOptions<'a>;
 
 #[cfg(target_os = "linux")]
 pub use imp::*;
@@ -33,11 +14,15 @@ pub use imp::*;
 #[cfg(target_os = "linux")]
 mod imp {
     use super::*;
-    use crate::core::{utils, DatabaseStatus};
+    use crate::core::{
+        cassandra, download, influxdb, mariadb, network, opensearch, redis, utils, versions,
+        BackendKind, DatabaseStatus,
+    };
     use anyhow::{bail, Context};
-    use reqwest::blocking::get;
+    use std::collections::HashMap;
     use std::fs;
     use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
     use std::process::Command;
 
     /// 安装数据库到 Linux
@@ -49,11 +34,18 @@ mod imp {
         match db_type {
             DatabaseType::Redis => install_redis(storage_path, options),
             DatabaseType::MySQL => install_mysql(storage_path, options),
+            DatabaseType::MariaDB => install_mariadb(storage_path, options),
             DatabaseType::MongoDB => install_mongodb(storage_path, options),
             DatabaseType::Qdrant => install_qdrant(storage_path, options),
             DatabaseType::Neo4j => install_neo4j(storage_path, options),
             DatabaseType::SurrealDB => install_surrealdb(storage_path, options),
             DatabaseType::SeekDB => install_seekdb(storage_path, options),
+            DatabaseType::OpenSearch => install_opensearch(storage_path, options),
+            DatabaseType::InfluxDB => install_influxdb(storage_path, options),
+            DatabaseType::Cassandra => cassandra::install(storage_path, options),
+            DatabaseType::SQLite => bail!("SQLite has no server binary to install"),
+            DatabaseType::DuckDB => bail!("DuckDB is managed by its own backend"),
+            DatabaseType::Custom => bail!("Custom servers are managed by their own backend"),
             DatabaseType::PostgreSQL => {
                 bail!("PostgreSQL installation on Linux is not yet implemented via binary")
             }
@@ -65,11 +57,18 @@ mod imp {
         match db_info.db_type {
             DatabaseType::Redis => start_redis_process(db_info),
             DatabaseType::MySQL => start_mysql_process(db_info),
+            DatabaseType::MariaDB => start_mariadb_process(db_info),
             DatabaseType::MongoDB => start_mongodb_process(db_info),
             DatabaseType::Qdrant => start_qdrant_process(db_info),
             DatabaseType::Neo4j => start_neo4j_process(db_info),
             DatabaseType::SurrealDB => start_surrealdb_process(db_info),
             DatabaseType::SeekDB => start_seekdb_process(db_info),
+            DatabaseType::OpenSearch => start_opensearch_process(db_info),
+            DatabaseType::InfluxDB => start_influxdb_process(db_info),
+            DatabaseType::Cassandra => cassandra::start(db_info),
+            DatabaseType::SQLite => bail!("SQLite has no server process to start"),
+            DatabaseType::DuckDB => bail!("DuckDB is managed by its own backend"),
+            DatabaseType::Custom => bail!("Custom servers are managed by their own backend"),
             DatabaseType::PostgreSQL => {
                 bail!("PostgreSQL service management on Linux is not yet implemented")
             }
@@ -81,11 +80,19 @@ mod imp {
         let pid_file = match db_info.db_type {
             DatabaseType::Redis => "redis.pid",
             DatabaseType::MySQL => "mysql.pid",
+            DatabaseType::MariaDB => "mariadb.pid",
             DatabaseType::MongoDB => "mongodb.pid",
             DatabaseType::Qdrant => "qdrant.pid",
             DatabaseType::Neo4j => "neo4j.pid",
             DatabaseType::SurrealDB => "surrealdb.pid",
             DatabaseType::SeekDB => "seekdb.pid",
+            DatabaseType::OpenSearch => "opensearch.pid",
+            DatabaseType::InfluxDB => "influxdb.pid",
+            // Cassandra 通过 nodetool 停止
+            DatabaseType::Cassandra => return cassandra::stop(db_info),
+            DatabaseType::SQLite => bail!("SQLite has no server process to stop"),
+            DatabaseType::DuckDB => bail!("DuckDB is managed by its own backend"),
+            DatabaseType::Custom => bail!("Custom servers are managed by their own backend"),
             DatabaseType::PostgreSQL => {
                 bail!("PostgreSQL service management on Linux is not yet implemented")
             }
@@ -112,10 +119,9 @@ mod imp {
 
     // --- Helper functions for binary installation ---
 
-    fn download_file(url: &str, target_path: &Path) -> Result<()> {
-        let response = get(url).context("Failed to download file")?;
-        let mut content = response.bytes().context("Failed to read response bytes")?;
-        fs::write(target_path, &mut content).context("Failed to write file")?;
+    fn download_file(url: &str, target_path: &Path, archive: Option<&Path>) -> Result<()> {
+        let content = download::fetch_blocking(url, archive)?;
+        fs::write(target_path, content).context("Failed to write file")?;
         Ok(())
     }
 
@@ -160,18 +166,144 @@ mod imp {
     }
 
     // --- Redis Implementation ---
-    // Note: Redis on Linux is usually installed via package manager as per docs/linux.md
-    // but for consistency with the "binary-first" approach, we might want to try binary if possible.
-    // However, following the doc exactly:
+
+    /// Redis 官方只发布源码包，安装时用本机的 make 和 C 编译器编译，
+    /// 通过 `make install PREFIX=<bin_dir>` 安装到 `bin_dir/bin`
     fn install_redis(
         storage_path: &Path,
         options: &LinuxInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        bail!("Redis on Linux should be installed via package manager as per docs/linux.md. Automated binary install not yet supported.")
+        let version = versions::resolve(&DatabaseType::Redis, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "redis", &version);
+        utils::ensure_dir(&bin_dir)?;
+
+        let instance_dir = utils::get_instance_dir_name("redis", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+        utils::ensure_dir(&config_dir)?;
+
+        let binary_path = bin_dir.join("bin").join("redis-server");
+        let config_path = config_dir.join("redis.conf");
+        let log_file = logs_dir.join("redis.log");
+
+        if !binary_path.exists() {
+            build_redis_from_source(&version, &bin_dir, options.archive)?;
+        }
+
+        let port = options.port.unwrap_or(6379);
+        let mut config_content = format!(
+            "port {port}\nbind 127.0.0.1\ndaemonize no\ndir \"{data_path}\"\nlogfile \"{log_path}\"\n",
+            port = port,
+            data_path = data_dir.to_string_lossy(),
+            log_path = log_file.to_string_lossy()
+        );
+        if let Some(password) = options.password.filter(|p| !p.is_empty()) {
+            config_content.push_str(&format!("requirepass {}\n", redis::quote(password)));
+        }
+        fs::write(&config_path, config_content)?;
+
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options.instance_name.unwrap_or("Redis").to_string(),
+            db_type: DatabaseType::Redis,
+            backend: BackendKind::Native,
+            version,
+            install_path: bin_dir.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_file.to_string_lossy().to_string(),
+            port,
+            username: None,
+            password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
+            config: Some(config_path.to_string_lossy().to_string()),
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        if options.auto_start {
+            start_redis_process(&db_info)?;
+            db_info.status = DatabaseStatus::Running;
+        }
+
+        Ok(db_info)
+    }
+
+    /// 下载源码包并编译安装，编译完成后删除源码目录
+    fn build_redis_from_source(
+        version: &str,
+        bin_dir: &Path,
+        archive: Option<&Path>,
+    ) -> Result<()> {
+        for tool in ["make", "cc"] {
+            let found = Command::new(tool)
+                .arg("--version")
+                .output()
+                .is_ok_and(|output| output.status.success());
+            if !found {
+                bail!("{} was not found. Building Redis requires make and a C compiler (e.g. build-essential)", tool);
+            }
+        }
+
+        let url = format!("https://download.redis.io/releases/redis-{version}.tar.gz");
+        let temp_archive = PathBuf::from(format!("{}.tar.gz", bin_dir.display()));
+        let source_dir = bin_dir.with_file_name(format!("redis-{version}-src"));
+        download_file(&url, &temp_archive, archive)?;
+        extract_archive(&temp_archive, &source_dir)?;
+        let _ = fs::remove_file(&temp_archive);
+
+        let jobs = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let prefix = format!("PREFIX={}", bin_dir.display());
+        let result = run_make(&source_dir, &[format!("-j{}", jobs)])
+            .and_then(|_| run_make(&source_dir, &["install".to_string(), prefix]));
+        let _ = fs::remove_dir_all(&source_dir);
+        result
+    }
+
+    fn run_make(source_dir: &Path, args: &[String]) -> Result<()> {
+        let output = Command::new("make")
+            .arg("-C")
+            .arg(source_dir)
+            .args(args)
+            .output()
+            .context("Failed to run make")?;
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            bail!("Building Redis failed: {}", err.trim());
+        }
+        Ok(())
     }
 
     fn start_redis_process(db_info: &DatabaseInfo) -> Result<()> {
-        bail!("Redis service management on Linux not yet implemented")
+        let binary_path = Path::new(&db_info.install_path)
+            .join("bin")
+            .join("redis-server");
+        let config_path = db_info.config.as_ref().context("Config path missing")?;
+        let data_dir = Path::new(&db_info.data_path);
+        let pid_path = data_dir.join("redis.pid");
+
+        let child = Command::new(binary_path)
+            .arg(config_path)
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
+            .spawn()
+            .context("Failed to start Redis")?;
+
+        fs::write(&pid_path, child.id().to_string())?;
+        Ok(())
     }
 
     // --- Qdrant Implementation ---
@@ -179,18 +311,27 @@ mod imp {
         storage_path: &Path,
         options: &LinuxInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "qdrant");
+        // 未指定版本时下载最新版本，放在不带版本号的目录下
+        let bin_dir = match options.version {
+            Some(version) => utils::get_db_version_bin_path(
+                storage_path,
+                "qdrant",
+                version.trim_start_matches('v'),
+            ),
+            None => utils::get_db_bin_path(storage_path, "qdrant"),
+        };
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "qdrant");
-        let logs_dir = utils::get_db_log_path(storage_path, "qdrant");
+        let instance_dir = utils::get_instance_dir_name("qdrant", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
 
         let binary_path = bin_dir.join("qdrant");
         let log_file = logs_dir.join("qdrant.log");
         let config_path =
-            utils::get_db_config_path(storage_path, "qdrant").join("qdrant-config.yaml");
+            utils::get_db_config_path(storage_path, &instance_dir).join("qdrant-config.yaml");
         utils::ensure_dir(config_path.parent().unwrap())?;
 
         if !binary_path.exists() {
@@ -199,12 +340,16 @@ mod imp {
             } else {
                 "x86_64"
             };
+            let release = match options.version {
+                Some(version) => format!("download/v{}", version.trim_start_matches('v')),
+                None => "latest/download".to_string(),
+            };
             let url = format!(
-                "https://github.com/qdrant/qdrant/releases/latest/download/qdrant-{}-unknown-linux-gnu.tar.gz",
-                arch
+                "https://github.com/qdrant/qdrant/releases/{}/qdrant-{}-unknown-linux-gnu.tar.gz",
+                release, arch
             );
             let temp_archive = bin_dir.join("qdrant.tar.gz");
-            download_file(&url, &temp_archive)?;
+            download_file(&url, &temp_archive, options.archive)?;
             extract_archive(&temp_archive, &bin_dir)?;
             let _ = fs::remove_file(&temp_archive);
 
@@ -226,18 +371,27 @@ mod imp {
 
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "Qdrant".to_string(),
+            name: options.instance_name.unwrap_or("Qdrant").to_string(),
             db_type: DatabaseType::Qdrant,
-            version: "latest".to_string(),
+            backend: BackendKind::Native,
+            version: options.version.unwrap_or("latest").to_string(),
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: None,
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: Some(config_path.to_string_lossy().to_string()),
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -260,6 +414,9 @@ mod imp {
         let child = Command::new(binary_path)
             .arg("--config-path")
             .arg(config_path)
+            .env("QDRANT__SERVICE__HOST", &db_info.bind_address)
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start Qdrant")?;
 
@@ -272,11 +429,13 @@ mod imp {
         storage_path: &Path,
         options: &LinuxInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "surrealdb");
+        let version = versions::resolve(&DatabaseType::SurrealDB, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "surrealdb", &version);
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "surrealdb");
-        let logs_dir = utils::get_db_log_path(storage_path, "surrealdb");
+        let instance_dir = utils::get_instance_dir_name("surrealdb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
 
@@ -292,11 +451,10 @@ mod imp {
                 "amd64"
             };
             let url = format!(
-                "https://github.com/surrealdb/surrealdb/releases/latest/download/surreal-v2.1.4-linux-{}.tar.gz",
-                arch
+                "https://github.com/surrealdb/surrealdb/releases/download/v{version}/surreal-v{version}-linux-{arch}.tar.gz"
             );
             let temp_archive = bin_dir.join("surreal.tar.gz");
-            download_file(&url, &temp_archive)?;
+            download_file(&url, &temp_archive, options.archive)?;
             extract_archive(&temp_archive, &bin_dir)?;
             let _ = fs::remove_file(&temp_archive);
 
@@ -314,18 +472,27 @@ mod imp {
         let port = options.port.unwrap_or(8000);
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "SurrealDB".to_string(),
+            name: options.instance_name.unwrap_or("SurrealDB").to_string(),
             db_type: DatabaseType::SurrealDB,
-            version: "latest".to_string(),
+            backend: BackendKind::Native,
+            version,
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: Some(options.username.unwrap_or("root").to_string()),
             password: Some(options.password.unwrap_or("root").to_string()),
+            credential_ref: None,
             config: None,
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -354,8 +521,10 @@ mod imp {
             .arg("--pass")
             .arg(pass)
             .arg("--bind")
-            .arg(format!("0.0.0.0:{}", db_info.port))
+            .arg(format!("{}:{}", db_info.bind_address, db_info.port))
             .arg(format!("file://{}", data_dir.to_string_lossy()))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start SurrealDB")?;
 
@@ -363,6 +532,193 @@ mod imp {
         Ok(())
     }
 
+    // --- OpenSearch Implementation ---
+    fn install_opensearch(
+        storage_path: &Path,
+        options: &LinuxInstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let version = options.version.unwrap_or(opensearch::DEFAULT_VERSION);
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "opensearch", version);
+
+        let instance_dir = utils::get_instance_dir_name("opensearch", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+        let log_file = opensearch::log_file(&logs_dir);
+
+        if !bin_dir.join("bin").join("opensearch").exists() {
+            let arch = if cfg!(target_arch = "aarch64") {
+                "arm64"
+            } else {
+                "x64"
+            };
+            let url = opensearch::download_url(version, &format!("linux-{}", arch), "tar.gz");
+            // 压缩包放在 bin_dir 之外，解压后才能去掉发行包的根目录
+            let temp_archive = PathBuf::from(format!("{}.tar.gz", bin_dir.display()));
+            utils::ensure_dir(&bin_dir)?;
+            download_file(&url, &temp_archive, options.archive)?;
+            let extracted = extract_archive(&temp_archive, &bin_dir);
+            let _ = fs::remove_file(&temp_archive);
+            extracted?;
+        }
+
+        let port = options.port.unwrap_or(9200);
+        let config_path = opensearch::write_config(
+            &bin_dir.join("config"),
+            &config_dir,
+            &data_dir,
+            &logs_dir,
+            port,
+        )?;
+
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options.instance_name.unwrap_or("OpenSearch").to_string(),
+            db_type: DatabaseType::OpenSearch,
+            backend: BackendKind::Native,
+            version: version.to_string(),
+            install_path: bin_dir.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_file.to_string_lossy().to_string(),
+            port,
+            username: None,
+            password: None,
+            credential_ref: None,
+            config: Some(config_path.to_string_lossy().to_string()),
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        if options.auto_start {
+            start_opensearch_process(&db_info)?;
+            db_info.status = DatabaseStatus::Running;
+        }
+
+        Ok(db_info)
+    }
+
+    fn start_opensearch_process(db_info: &DatabaseInfo) -> Result<()> {
+        let binary_path = Path::new(&db_info.install_path)
+            .join("bin")
+            .join("opensearch");
+        let pid_path = Path::new(&db_info.data_path).join("opensearch.pid");
+
+        // 启动脚本最终 exec java，子进程 PID 即为 OpenSearch 进程；日志写入 path.logs
+        let child = Command::new(binary_path)
+            .env("OPENSEARCH_PATH_CONF", opensearch::config_dir(db_info)?)
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .context("Failed to start OpenSearch")?;
+
+        fs::write(&pid_path, child.id().to_string())?;
+        Ok(())
+    }
+
+    // --- InfluxDB Implementation ---
+    fn install_influxdb(
+        storage_path: &Path,
+        options: &LinuxInstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let version = options.version.unwrap_or(influxdb::DEFAULT_VERSION);
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "influxdb", version);
+
+        let instance_dir = utils::get_instance_dir_name("influxdb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+        let log_file = logs_dir.join("influxd.log");
+
+        if !bin_dir.join("influxd").exists() {
+            let arch = if cfg!(target_arch = "aarch64") {
+                "arm64"
+            } else {
+                "amd64"
+            };
+            let url = influxdb::download_url(version, &format!("_linux_{}.tar.gz", arch));
+            let temp_archive = PathBuf::from(format!("{}.tar.gz", bin_dir.display()));
+            utils::ensure_dir(&bin_dir)?;
+            download_file(&url, &temp_archive, options.archive)?;
+            let extracted = extract_archive(&temp_archive, &bin_dir);
+            let _ = fs::remove_file(&temp_archive);
+            extracted?;
+            influxdb::flatten_binary(&bin_dir, "influxd")?;
+        }
+
+        let username = options.username.unwrap_or("admin");
+        let password = options.password.unwrap_or("admin888");
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options.instance_name.unwrap_or("InfluxDB").to_string(),
+            db_type: DatabaseType::InfluxDB,
+            backend: BackendKind::Native,
+            version: version.to_string(),
+            install_path: bin_dir.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_file.to_string_lossy().to_string(),
+            port: options.port.unwrap_or(8086),
+            username: Some(username.to_string()),
+            password: None,
+            credential_ref: None,
+            config: None,
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        // 首次启动后完成初始化，生成的 token 作为实例凭据
+        start_influxdb_process(&db_info)?;
+        influxdb::finish_install(&mut db_info, username, password, stop_service)?;
+
+        Ok(db_info)
+    }
+
+    fn start_influxdb_process(db_info: &DatabaseInfo) -> Result<()> {
+        let binary_path = Path::new(&db_info.install_path).join("influxd");
+        let pid_path = Path::new(&db_info.data_path).join("influxdb.pid");
+        let log_file = fs::File::options()
+            .create(true)
+            .append(true)
+            .open(&db_info.log_path)
+            .context("Failed to open InfluxDB log file")?;
+
+        let child = Command::new(binary_path)
+            .args(influxdb::server_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
+            .stdout(log_file.try_clone()?)
+            .stderr(log_file)
+            .spawn()
+            .context("Failed to start InfluxDB")?;
+
+        fs::write(&pid_path, child.id().to_string())?;
+        Ok(())
+    }
+
     // --- SeekDB Implementation ---
     fn install_seekdb(
         storage_path: &Path,
@@ -371,14 +727,16 @@ mod imp {
         let bin_dir = utils::get_db_bin_path(storage_path, "seekdb");
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "seekdb");
-        let logs_dir = utils::get_db_log_path(storage_path, "seekdb");
+        let instance_dir = utils::get_instance_dir_name("seekdb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
 
         let binary_path = bin_dir.join("seekdb");
         let log_file = logs_dir.join("seekdb.log");
-        let config_path = utils::get_db_config_path(storage_path, "seekdb").join("seekdb.conf");
+        let config_path =
+            utils::get_db_config_path(storage_path, &instance_dir).join("seekdb.conf");
         utils::ensure_dir(config_path.parent().unwrap())?;
 
         if !binary_path.exists() {
@@ -392,7 +750,7 @@ mod imp {
                 arch
             );
             let temp_archive = bin_dir.join("seekdb.tar.gz");
-            download_file(&url, &temp_archive)?;
+            download_file(&url, &temp_archive, options.archive)?;
             extract_archive(&temp_archive, &bin_dir)?;
             let _ = fs::remove_file(&temp_archive);
 
@@ -412,8 +770,9 @@ mod imp {
 
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "SeekDB".to_string(),
+            name: options.instance_name.unwrap_or("SeekDB").to_string(),
             db_type: DatabaseType::SeekDB,
+            backend: BackendKind::Native,
             version: "v0.1.0".to_string(),
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
@@ -421,9 +780,17 @@ mod imp {
             port,
             username: options.username.map(|s| s.to_string()),
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: Some(config_path.to_string_lossy().to_string()),
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -446,6 +813,8 @@ mod imp {
         let child = Command::new(binary_path)
             .arg("-config")
             .arg(config_path)
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start SeekDB")?;
 
@@ -463,17 +832,19 @@ mod imp {
         storage_path: &Path,
         options: &LinuxInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "mysql");
+        let version = versions::resolve(&DatabaseType::MySQL, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "mysql", &version);
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "mysql");
-        let logs_dir = utils::get_db_log_path(storage_path, "mysql");
+        let instance_dir = utils::get_instance_dir_name("mysql", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
 
         let binary_path = bin_dir.join("bin").join("mysqld");
         let log_file = logs_dir.join("mysqld.log");
-        let config_path = utils::get_db_config_path(storage_path, "mysql").join("my.cnf");
+        let config_path = utils::get_db_config_path(storage_path, &instance_dir).join("my.cnf");
         utils::ensure_dir(config_path.parent().unwrap())?;
 
         if !binary_path.exists() {
@@ -483,11 +854,13 @@ mod imp {
                 "x86_64"
             };
             let url = format!(
-                "https://cdn.mysql.com/Downloads/MySQL-8.4/mysql-8.4.0-linux-glibc2.28-{}.tar.xz",
+                "https://cdn.mysql.com/Downloads/MySQL-{}/mysql-{}-linux-glibc2.28-{}.tar.xz",
+                versions::series(&version, 2),
+                version,
                 arch
             );
             let temp_archive = bin_dir.join("mysql.tar.xz");
-            download_file(&url, &temp_archive)?;
+            download_file(&url, &temp_archive, options.archive)?;
             extract_archive(&temp_archive, &bin_dir)?;
             let _ = fs::remove_file(&temp_archive);
         }
@@ -520,18 +893,27 @@ mod imp {
 
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "MySQL".to_string(),
+            name: options.instance_name.unwrap_or("MySQL").to_string(),
             db_type: DatabaseType::MySQL,
-            version: "8.4.0".to_string(),
+            backend: BackendKind::Native,
+            version,
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: options.username.map(|s| s.to_string()).or(Some("root".to_string())),
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: Some(config_path.to_string_lossy().to_string()),
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -551,6 +933,9 @@ mod imp {
 
         let child = Command::new(binary_path)
             .arg(format!("--defaults-file={}", config_path))
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start MySQL")?;
 
@@ -561,23 +946,140 @@ mod imp {
         Ok(())
     }
 
+    // --- MariaDB Implementation ---
+
+    fn install_mariadb(
+        storage_path: &Path,
+        options: &LinuxInstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let version = versions::resolve(&DatabaseType::MariaDB, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "mariadb", &version);
+        utils::ensure_dir(&bin_dir)?;
+
+        let instance_dir = utils::get_instance_dir_name("mariadb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+
+        let binary_path = bin_dir.join("bin").join("mariadbd");
+        let log_file = logs_dir.join("mariadbd.log");
+        let config_path = utils::get_db_config_path(storage_path, &instance_dir).join("my.cnf");
+        utils::ensure_dir(config_path.parent().unwrap())?;
+
+        if !binary_path.exists() {
+            // 官方通用二进制包只提供 x86_64
+            if !cfg!(target_arch = "x86_64") {
+                bail!("MariaDB binary tarballs are only published for x86_64");
+            }
+            let url = mariadb::download_url(
+                &version,
+                "bintar-linux-systemd-x86_64",
+                "linux-systemd-x86_64.tar.gz",
+            );
+            let temp_archive = PathBuf::from(format!("{}.tar.gz", bin_dir.display()));
+            download_file(&url, &temp_archive, options.archive)?;
+            extract_archive(&temp_archive, &bin_dir)?;
+            let _ = fs::remove_file(&temp_archive);
+        }
+
+        mariadb::initialize_data_dir(
+            &bin_dir.join("scripts").join("mariadb-install-db"),
+            &bin_dir,
+            &data_dir,
+            None,
+        )?;
+
+        // socket 放在实例数据目录下，避免与 MySQL 的 /tmp/mysql.sock 冲突
+        let port = options.port.unwrap_or(mariadb::DEFAULT_PORT);
+        let config_content = format!(
+            "[mysqld]\ndatadir={data_path}\nbasedir={base_path}\nsocket={socket_path}\nport={port}\nlog-error={log_path}\npid-file={pid_path}\n",
+            data_path = data_dir.to_string_lossy(),
+            base_path = bin_dir.to_string_lossy(),
+            socket_path = data_dir.join("mariadb.sock").to_string_lossy(),
+            port = port,
+            log_path = log_file.to_string_lossy(),
+            pid_path = data_dir.join("mariadb.pid").to_string_lossy()
+        );
+        fs::write(&config_path, config_content)?;
+
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options.instance_name.unwrap_or("MariaDB").to_string(),
+            db_type: DatabaseType::MariaDB,
+            backend: BackendKind::Native,
+            version,
+            install_path: bin_dir.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_file.to_string_lossy().to_string(),
+            port,
+            username: options.username.map(|s| s.to_string()).or(Some("root".to_string())),
+            password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
+            config: Some(config_path.to_string_lossy().to_string()),
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        if options.auto_start {
+            start_mariadb_process(&db_info)?;
+            db_info.status = DatabaseStatus::Running;
+        }
+
+        Ok(db_info)
+    }
+
+    fn start_mariadb_process(db_info: &DatabaseInfo) -> Result<()> {
+        let binary_path = Path::new(&db_info.install_path)
+            .join("bin")
+            .join("mariadbd");
+        let config_path = db_info.config.as_ref().context("Config path missing")?;
+
+        let child = Command::new(binary_path)
+            .arg(format!("--defaults-file={}", config_path))
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
+            .spawn()
+            .context("Failed to start MariaDB")?;
+
+        let data_dir = Path::new(&db_info.data_path);
+        let pid_path = data_dir.join("mariadb.pid");
+        fs::write(&pid_path, child.id().to_string())?;
+
+        Ok(())
+    }
+
     // --- MongoDB Implementation ---
 
     fn install_mongodb(
         storage_path: &Path,
         options: &LinuxInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "mongodb");
+        let version = versions::resolve(&DatabaseType::MongoDB, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "mongodb", &version);
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "mongodb");
-        let logs_dir = utils::get_db_log_path(storage_path, "mongodb");
+        let instance_dir = utils::get_instance_dir_name("mongodb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
 
         let binary_path = bin_dir.join("bin").join("mongod");
         let log_file = logs_dir.join("mongod.log");
-        let config_path = utils::get_db_config_path(storage_path, "mongodb").join("mongod.conf");
+        let config_path =
+            utils::get_db_config_path(storage_path, &instance_dir).join("mongod.conf");
         utils::ensure_dir(config_path.parent().unwrap())?;
 
         if !binary_path.exists() {
@@ -587,11 +1089,11 @@ mod imp {
                 "x86_64"
             };
             let url = format!(
-                "https://fastdl.mongodb.org/linux/mongodb-linux-{}-ubuntu2004-7.0.9.tgz",
-                arch
+                "https://fastdl.mongodb.org/linux/mongodb-linux-{}-ubuntu2004-{}.tgz",
+                arch, version
             );
             let temp_archive = bin_dir.join("mongodb.tgz");
-            download_file(&url, &temp_archive)?;
+            download_file(&url, &temp_archive, options.archive)?;
             extract_archive(&temp_archive, &bin_dir)?;
             let _ = fs::remove_file(&temp_archive);
         }
@@ -608,18 +1110,27 @@ mod imp {
 
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "MongoDB".to_string(),
+            name: options.instance_name.unwrap_or("MongoDB").to_string(),
             db_type: DatabaseType::MongoDB,
-            version: "7.0.9".to_string(),
+            backend: BackendKind::Native,
+            version,
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: options.username.map(|s| s.to_string()),
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: Some(config_path.to_string_lossy().to_string()),
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -640,6 +1151,9 @@ mod imp {
         let output = Command::new(binary_path)
             .arg("--config")
             .arg(config_path)
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .output()
             .context("Failed to start MongoDB")?;
 
@@ -0,0 +1,83 @@
+use anyhow::Result;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 日志文件轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// 从文件末尾向前读取的块大小
+const TAIL_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// 读取日志文件最后 `lines` 行，只从文件末尾向前读取所需的部分
+pub fn read_last_lines(path: &Path, lines: usize) -> Result<Vec<String>> {
+    if lines == 0 || !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut file = fs::File::open(path)?;
+    let file_len = file.metadata()?.len();
+    let mut start = file_len;
+    let mut buffer = Vec::new();
+
+    // 多读一行，保证第一行是完整的
+    while start > 0 && buffer.iter().filter(|&&b| b == b'\n').count() <= lines {
+        let read_len = TAIL_CHUNK_SIZE.min(start);
+        start -= read_len;
+        file.seek(SeekFrom::Start(start))?;
+        let mut chunk = vec![0u8; read_len as usize];
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&buffer);
+        buffer = chunk;
+    }
+
+    let content = String::from_utf8_lossy(&buffer);
+    let mut result: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+    if result.len() > lines {
+        result = result.split_off(result.len() - lines);
+    }
+    Ok(result)
+}
+
+/// 持续跟踪日志文件新增内容，每读到一行调用一次 `on_line`，直到 `stop` 被置位
+///
+/// 文件被截断（清空日志或轮转）时从头开始读取。
+pub fn follow(path: &Path, stop: Arc<AtomicBool>, mut on_line: impl FnMut(String)) {
+    let mut position = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let mut pending = String::new();
+
+    while !stop.load(Ordering::Relaxed) {
+        let len = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if len < position {
+            position = 0;
+            pending.clear();
+        }
+
+        if len > position {
+            if let Ok(mut file) = fs::File::open(path) {
+                if file.seek(SeekFrom::Start(position)).is_ok() {
+                    let mut reader = BufReader::new(file);
+                    let mut raw = Vec::new();
+                    while let Ok(n) = reader.read_until(b'\n', &mut raw) {
+                        if n == 0 {
+                            break;
+                        }
+                        position += n as u64;
+                        pending.push_str(&String::from_utf8_lossy(&raw));
+                        raw.clear();
+
+                        // 未以换行结尾的内容留到下一轮，避免输出半行
+                        if pending.ends_with('\n') {
+                            on_line(pending.trim_end_matches(['\r', '\n']).to_string());
+                            pending.clear();
+                        }
+                    }
+                }
+            }
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -1,32 +1,18 @@
 use crate::core::{utils, DatabaseInfo, DatabaseStatus, DatabaseType};
 use anyhow::Result;
-use reqwest::blocking::get;
 
 /// Options used when installing a database via Homebrew.
-pub struct HomebrewInstallOptions<'a> {
-    pub version: Option<&'a str>,
-    pub port: Option<u16>,
-    pub username: Option<&'a str>,
-    pub password: Option<&'a str>,
-    pub auto_start: bool,
-}
-
-impl<'a> Default for HomebrewInstallOptions<'a> {
-    fn default() -> Self {
-        Self {
-            version: None,
-            port: None,
-            username: None,
-            password: None,
-            auto_start: true,
-        }
-    }
-}
+pub type HomebrewInstallOptions<'a> = crate::core::backend::InstallOptions<'a>;
 
 #[cfg(target_os = "macos")]
 mod imp {
     use super::*;
+    use crate::core::{
+        cassandra, download, influxdb, mariadb, neo4j, network, opensearch, redis, versions,
+        watchdog, AppError, BackendKind,
+    };
     use anyhow::{anyhow, bail, Context};
+    use std::collections::HashMap;
     use std::fs;
     use std::path::{Path, PathBuf};
     use std::process::Command;
@@ -43,6 +29,7 @@ mod imp {
             let formulas = vec![
                 ("redis", DatabaseType::Redis),
                 ("mysql", DatabaseType::MySQL),
+                ("mariadb@11.4", DatabaseType::MariaDB),
                 ("postgresql", DatabaseType::PostgreSQL),
                 ("mongodb-community@7.0", DatabaseType::MongoDB),
                 ("surreal", DatabaseType::SurrealDB),
@@ -69,6 +56,30 @@ mod imp {
         }
     }
 
+    /// 查询 formula 当前可安装的稳定版本，Homebrew 未安装或 formula 不存在时返回 None
+    pub fn get_homebrew_formula_stable_version(formula: &str) -> Option<String> {
+        let brew = Homebrew::detect().ok()??;
+        let output = brew
+            .run(&["info", "--json=v2", "--formula", formula])
+            .ok()?;
+        let info: serde_json::Value = serde_json::from_str(&output).ok()?;
+        info["formulae"][0]["versions"]["stable"]
+            .as_str()
+            .map(String::from)
+    }
+
+    /// 安装 formula，已安装时跳过，用于 PostgreSQL 扩展等附加组件
+    pub fn install_homebrew_formula(formula: &str) -> Result<()> {
+        Homebrew::bootstrap()?.ensure_formula(None, formula)
+    }
+
+    /// 检查数据库实例对应版本的 Homebrew 服务是否在运行
+    pub fn get_database_service_status(db_info: &DatabaseInfo) -> Option<bool> {
+        let recipe =
+            HomebrewDatabaseRecipe::resolve(&db_info.db_type, Some(&db_info.version)).ok()?;
+        get_homebrew_service_status(&recipe.service_name)
+    }
+
     /// 获取所有 Homebrew 服务的运行状态（一次性调用）
     /// 返回 HashMap<服务名, 是否运行>
     pub fn get_all_homebrew_services_status() -> std::collections::HashMap<String, bool> {
@@ -99,6 +110,23 @@ mod imp {
         result
     }
 
+    /// 是否支持安装多个实例
+    ///
+    /// Homebrew 服务每个 formula 只有一份配置，只有直接启动进程的 Qdrant、SurrealDB、OpenSearch、
+    /// InfluxDB 和基于文件的 SQLite、DuckDB 支持多实例。其他数据库的不同版本使用不同的 formula
+    /// （如 postgresql@16 和 postgresql@18），可以各安装一个实例。
+    pub fn supports_multiple_instances(db_type: &DatabaseType) -> bool {
+        matches!(
+            db_type,
+            DatabaseType::Qdrant
+                | DatabaseType::SurrealDB
+                | DatabaseType::OpenSearch
+                | DatabaseType::InfluxDB
+                | DatabaseType::SQLite
+                | DatabaseType::DuckDB
+        )
+    }
+
     pub fn install_database_via_homebrew(
         db_type: &DatabaseType,
         storage_path: &Path,
@@ -114,33 +142,62 @@ mod imp {
             return install_surrealdb_via_homebrew(storage_path, options);
         }
 
+        // OpenSearch 通过 Homebrew 安装，每个实例使用自己的配置目录直接启动进程
+        if *db_type == DatabaseType::OpenSearch {
+            return install_opensearch_via_homebrew(storage_path, options);
+        }
+
+        // Cassandra 下载官方发行包，使用本机 Java 运行
+        if *db_type == DatabaseType::Cassandra {
+            return cassandra::install(storage_path, options);
+        }
+
+        // Neo4j 下载官方发行包，使用本机 Java 运行
+        if *db_type == DatabaseType::Neo4j {
+            return neo4j::install(storage_path, options);
+        }
+
+        // InfluxDB 通过 Homebrew 安装，直接启动进程并在首次启动后完成初始化
+        if *db_type == DatabaseType::InfluxDB {
+            return install_influxdb_via_homebrew(storage_path, options);
+        }
+
         let brew = Homebrew::bootstrap()?;
-        let recipe = HomebrewDatabaseRecipe::resolve(db_type)?;
+        // 按请求的版本选择带版本号的 formula，如 postgresql@16；无法识别的版本直接报错
+        versions::resolve(db_type, options.version)?;
+        let recipe = HomebrewDatabaseRecipe::resolve(db_type, options.version)?;
 
         // 检查是否已安装，如果已安装则跳过安装步骤
-        let already_installed = brew.is_formula_installed(recipe.formula)?;
+        let already_installed = brew.is_formula_installed(&recipe.formula)?;
         if !already_installed {
             // 未安装，执行安装
-            brew.ensure_formula(recipe.tap, recipe.formula)?;
+            brew.ensure_formula(recipe.tap, &recipe.formula)?;
         }
+        // 固定 formula 版本，避免 brew upgrade 升级后与记录的版本和数据目录不一致
+        brew.pin_formula(&recipe.formula)?;
 
         // 继续配置流程（无论是否已安装都需要确保配置正确）
+        // 不同版本的 formula 可以并存，数据和日志目录按实例名区分
+        let instance_dir = utils::get_instance_dir_name(db_type.as_str(), options.instance_name);
         let configured = configure_database(
             db_type,
             &brew,
+            &recipe.formula,
             storage_path,
+            &instance_dir,
             options.port.unwrap_or(recipe.port),
+            options.password.filter(|p| !p.is_empty()),
         )?;
-        let data_path = utils::get_db_data_path(storage_path, db_type.as_str());
-        let install_prefix = brew.prefix(Some(recipe.formula))?;
+        let data_path = utils::get_db_data_path(storage_path, &instance_dir);
+        let install_prefix = brew.prefix(Some(&recipe.formula))?;
 
         let version = brew
-            .formula_version(recipe.formula)?
+            .formula_version(&recipe.formula)?
             .or_else(|| options.version.map(|v| v.to_string()))
             .unwrap_or_else(|| "latest".to_string());
 
         let status = if options.auto_start {
-            brew.restart_service(recipe.service_name)?;
+            brew.restart_service(&recipe.service_name)?;
             DatabaseStatus::Running
         } else {
             DatabaseStatus::Stopped
@@ -148,8 +205,12 @@ mod imp {
 
         Ok(DatabaseInfo {
             id: utils::generate_id(),
-            name: db_type.display_name().to_string(),
+            name: options
+                .instance_name
+                .unwrap_or(db_type.display_name())
+                .to_string(),
             db_type: db_type.clone(),
+            backend: BackendKind::Native,
             version,
             install_path: install_prefix.to_string_lossy().to_string(),
             data_path: data_path.to_string_lossy().to_string(),
@@ -157,9 +218,17 @@ mod imp {
             port: options.port.unwrap_or(recipe.port),
             username: resolve_username(db_type, options.username),
             password: resolve_password(db_type, options.password),
+            credential_ref: None,
             config: Some(configured.config_path.to_string_lossy().to_string()),
             status,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -184,8 +253,9 @@ mod imp {
 
         // 配置 SurrealDB
         let port = options.port.unwrap_or(default_port);
-        let configured = configure_surrealdb(&brew, storage_path, port)?;
-        let data_path = utils::get_db_data_path(storage_path, "surrealdb");
+        let instance_dir = utils::get_instance_dir_name("surrealdb", options.instance_name);
+        let configured = configure_surrealdb(&brew, storage_path, &instance_dir, port)?;
+        let data_path = utils::get_db_data_path(storage_path, &instance_dir);
         let install_prefix = brew.prefix(Some(formula))?;
 
         let version = brew
@@ -196,8 +266,12 @@ mod imp {
         // 创建 DatabaseInfo
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: DatabaseType::SurrealDB.display_name().to_string(),
+            name: options
+                .instance_name
+                .unwrap_or(DatabaseType::SurrealDB.display_name())
+                .to_string(),
             db_type: DatabaseType::SurrealDB,
+            backend: BackendKind::Native,
             version,
             install_path: install_prefix.to_string_lossy().to_string(),
             data_path: data_path.to_string_lossy().to_string(),
@@ -205,9 +279,17 @@ mod imp {
             port,
             username: resolve_username(&DatabaseType::SurrealDB, options.username),
             password: resolve_password(&DatabaseType::SurrealDB, options.password),
+            credential_ref: None,
             config: Some(configured.config_path.to_string_lossy().to_string()),
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -225,6 +307,144 @@ mod imp {
         Ok(db_info)
     }
 
+    /// 通过 Homebrew 安装 OpenSearch，但使用直接进程启动
+    fn install_opensearch_via_homebrew(
+        storage_path: &Path,
+        options: &HomebrewInstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let brew = Homebrew::bootstrap()?;
+        let formula = "opensearch";
+
+        if !brew.is_formula_installed(formula)? {
+            brew.ensure_formula(None, formula)?;
+        }
+
+        let port = options.port.unwrap_or(9200);
+        let instance_dir = utils::get_instance_dir_name("opensearch", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+        let log_file = opensearch::log_file(&logs_dir);
+
+        // formula 把发行包自带的配置安装在 $(brew --prefix)/etc/opensearch
+        let dist_config_dir = brew.prefix(None)?.join("etc").join("opensearch");
+        let config_path =
+            opensearch::write_config(&dist_config_dir, &config_dir, &data_dir, &logs_dir, port)?;
+        let install_prefix = brew.prefix(Some(formula))?;
+
+        let version = brew
+            .formula_version(formula)?
+            .or_else(|| options.version.map(|v| v.to_string()))
+            .unwrap_or_else(|| "latest".to_string());
+
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options
+                .instance_name
+                .unwrap_or(DatabaseType::OpenSearch.display_name())
+                .to_string(),
+            db_type: DatabaseType::OpenSearch,
+            backend: BackendKind::Native,
+            version,
+            install_path: install_prefix.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_file.to_string_lossy().to_string(),
+            port,
+            username: None,
+            password: None,
+            credential_ref: None,
+            config: Some(config_path.to_string_lossy().to_string()),
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        if options.auto_start {
+            if let Err(e) = start_opensearch_process(&db_info) {
+                eprintln!("Warning: Failed to start OpenSearch: {}", e);
+            } else {
+                db_info.status = DatabaseStatus::Running;
+            }
+        }
+
+        Ok(db_info)
+    }
+
+    /// 通过 Homebrew 安装 InfluxDB，但使用直接进程启动
+    fn install_influxdb_via_homebrew(
+        storage_path: &Path,
+        options: &HomebrewInstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let brew = Homebrew::bootstrap()?;
+        let formula = "influxdb";
+
+        if !brew.is_formula_installed(formula)? {
+            brew.ensure_formula(None, formula)?;
+        }
+
+        let instance_dir = utils::get_instance_dir_name("influxdb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+        let install_prefix = brew.prefix(Some(formula))?;
+
+        let version = brew
+            .formula_version(formula)?
+            .or_else(|| options.version.map(|v| v.to_string()))
+            .unwrap_or_else(|| "latest".to_string());
+
+        let username = options.username.unwrap_or("admin");
+        let password = options.password.unwrap_or("admin888");
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options
+                .instance_name
+                .unwrap_or(DatabaseType::InfluxDB.display_name())
+                .to_string(),
+            db_type: DatabaseType::InfluxDB,
+            backend: BackendKind::Native,
+            version,
+            install_path: install_prefix.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: logs_dir.join("influxd.log").to_string_lossy().to_string(),
+            port: options.port.unwrap_or(8086),
+            username: Some(username.to_string()),
+            password: None,
+            credential_ref: None,
+            config: None,
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        // 首次启动后完成初始化，生成的 token 作为实例凭据
+        start_influxdb_process(&db_info)?;
+        influxdb::finish_install(&mut db_info, username, password, stop_direct_process)?;
+
+        Ok(db_info)
+    }
+
     /// 获取当前系统架构对应的 Qdrant 二进制文件名
     fn get_qdrant_binary_name() -> &'static str {
         #[cfg(target_arch = "aarch64")]
@@ -268,12 +488,21 @@ mod imp {
         storage_path: &Path,
         options: &HomebrewInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "qdrant");
+        // 未指定版本时下载最新版本，放在不带版本号的目录下
+        let bin_dir = match options.version {
+            Some(version) => utils::get_db_version_bin_path(
+                storage_path,
+                "qdrant",
+                version.trim_start_matches('v'),
+            ),
+            None => utils::get_db_bin_path(storage_path, "qdrant"),
+        };
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "qdrant");
-        let logs_dir = utils::get_db_log_path(storage_path, "qdrant");
-        let config_dir = utils::get_db_config_path(storage_path, "qdrant");
+        let instance_dir = utils::get_instance_dir_name("qdrant", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         utils::ensure_dir(&config_dir)?;
@@ -287,16 +516,7 @@ mod imp {
 
         if !binary_path.exists() {
             eprintln!("Downloading Qdrant from {}...", binary_url);
-            let response = get(&binary_url).context("Failed to fetch Qdrant archive")?;
-
-            if !response.status().is_success() {
-                bail!(
-                    "Failed to download Qdrant archive: HTTP {}",
-                    response.status()
-                );
-            }
-
-            let bytes = response.bytes().context("Failed to read Qdrant archive")?;
+            let bytes = download::fetch_blocking(&binary_url, options.archive)?;
 
             // 解压 tar.gz 文件
             eprintln!("Extracting Qdrant binary...");
@@ -345,7 +565,7 @@ mod imp {
         let config_content = format!(
             r#"# Qdrant configuration for local-db
 service:
-  host: 0.0.0.0
+  host: 127.0.0.1
   http_port: {port}
   grpc_port: {grpc_port}
 
@@ -370,7 +590,14 @@ telemetry_disabled: true
 
         // 如果需要自动启动，启动 Qdrant
         let status = if options.auto_start {
-            if let Err(e) = start_qdrant_process(&binary_path, &config_path, &data_dir) {
+            if let Err(e) = start_qdrant_process(
+                &binary_path,
+                &config_path,
+                &data_dir,
+                network::LOCAL_BIND_ADDRESS,
+                &HashMap::new(),
+                &[],
+            ) {
                 eprintln!("Warning: Failed to start Qdrant: {}", e);
                 DatabaseStatus::Stopped
             } else {
@@ -382,8 +609,9 @@ telemetry_disabled: true
 
         Ok(DatabaseInfo {
             id: utils::generate_id(),
-            name: "Qdrant".to_string(),
+            name: options.instance_name.unwrap_or("Qdrant").to_string(),
             db_type: DatabaseType::Qdrant,
+            backend: BackendKind::Native,
             version,
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
@@ -391,17 +619,32 @@ telemetry_disabled: true
             port,
             username: None,
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: Some(config_path.to_string_lossy().to_string()),
             status,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
         })
     }
 
-    /// 启动 Qdrant 进程
-    fn start_qdrant_process(binary_path: &Path, config_path: &Path, data_dir: &Path) -> Result<()> {
+    /// 启动 Qdrant 进程，`bind_address` 为监听地址，`env` 和 `extra_args` 为实例自定义的环境变量和启动参数
+    fn start_qdrant_process(
+        binary_path: &Path,
+        config_path: &Path,
+        data_dir: &Path,
+        bind_address: &str,
+        env: &HashMap<String, String>,
+        extra_args: &[String],
+    ) -> Result<()> {
         // 检查二进制文件是否存在
         if !binary_path.exists() {
             bail!("Qdrant binary not found at {}", binary_path.display());
@@ -432,6 +675,9 @@ telemetry_disabled: true
         let child = Command::new(binary_path)
             .arg("--config-path")
             .arg(config_path)
+            .env("QDRANT__SERVICE__HOST", bind_address)
+            .envs(env)
+            .args(extra_args)
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
@@ -535,11 +781,11 @@ telemetry_disabled: true
         let logs_dir = Path::new(&db_info.log_path).parent().unwrap_or(data_dir);
         let log_file = logs_dir.join("surrealdb.log");
 
-        // 启动 SurrealDB: surreal start --bind 0.0.0.0:port rocksdb://data_path
+        // 启动 SurrealDB: surreal start --bind address:port rocksdb://data_path
         let child = Command::new(&binary_path)
             .arg("start")
             .arg("--bind")
-            .arg(format!("0.0.0.0:{}", db_info.port))
+            .arg(format!("{}:{}", db_info.bind_address, db_info.port))
             .arg("--log")
             .arg("info")
             .arg("--user")
@@ -547,6 +793,8 @@ telemetry_disabled: true
             .arg("--pass")
             .arg(db_info.password.as_deref().unwrap_or("admin888"))
             .arg(format!("rocksdb://{}", data_dir.display()))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .stdin(std::process::Stdio::null())
             .stdout(
                 fs::File::create(&log_file)
@@ -624,6 +872,84 @@ telemetry_disabled: true
         Ok(())
     }
 
+    /// 启动 OpenSearch 进程，`OPENSEARCH_PATH_CONF` 指向实例自己的配置目录
+    fn start_opensearch_process(db_info: &DatabaseInfo) -> Result<()> {
+        let binary_path = Path::new(&db_info.install_path)
+            .join("bin")
+            .join("opensearch");
+        if !binary_path.exists() {
+            bail!("OpenSearch binary not found at {}", binary_path.display());
+        }
+        if watchdog::is_process_alive(db_info) {
+            return Ok(());
+        }
+
+        // 日志由 OpenSearch 写入 path.logs，控制台输出直接丢弃
+        let child = Command::new(&binary_path)
+            .env("OPENSEARCH_PATH_CONF", opensearch::config_dir(db_info)?)
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .context("Failed to spawn OpenSearch process")?;
+
+        fs::write(watchdog::pid_file_path(db_info), child.id().to_string())
+            .context("Failed to write PID file")?;
+        Ok(())
+    }
+
+    /// 启动 InfluxDB 进程，输出写入实例日志文件
+    fn start_influxdb_process(db_info: &DatabaseInfo) -> Result<()> {
+        let binary_path = Path::new(&db_info.install_path).join("bin").join("influxd");
+        if !binary_path.exists() {
+            bail!("InfluxDB binary not found at {}", binary_path.display());
+        }
+        if watchdog::is_process_alive(db_info) {
+            return Ok(());
+        }
+
+        let log_file = fs::File::options()
+            .create(true)
+            .append(true)
+            .open(&db_info.log_path)
+            .context("Failed to open InfluxDB log file")?;
+        let child = Command::new(&binary_path)
+            .args(influxdb::server_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
+            .stdin(std::process::Stdio::null())
+            .stdout(log_file.try_clone()?)
+            .stderr(log_file)
+            .spawn()
+            .context("Failed to spawn InfluxDB process")?;
+
+        fs::write(watchdog::pid_file_path(db_info), child.id().to_string())
+            .context("Failed to write PID file")?;
+        Ok(())
+    }
+
+    /// 停止通过 PID 文件管理的进程（OpenSearch、InfluxDB）
+    fn stop_direct_process(db_info: &DatabaseInfo) -> Result<()> {
+        use nix::sys::signal::{self, Signal};
+        use nix::unistd::Pid;
+
+        if let Some(pid) = watchdog::read_pid(db_info) {
+            if watchdog::is_process_alive(db_info) {
+                signal::kill(Pid::from_raw(pid as i32), Signal::SIGTERM).with_context(|| {
+                    format!(
+                        "Failed to send SIGTERM to {}",
+                        db_info.db_type.display_name()
+                    )
+                })?;
+            }
+            let _ = fs::remove_file(watchdog::pid_file_path(db_info));
+        }
+        Ok(())
+    }
+
     pub fn start_service_for_database(db_info: &DatabaseInfo) -> Result<()> {
         // Qdrant 使用直接二进制进程管理
         if db_info.db_type == DatabaseType::Qdrant {
@@ -641,7 +967,14 @@ telemetry_disabled: true
                         .join("qdrant")
                         .join("config.yaml")
                 });
-            return start_qdrant_process(&binary_path, &config_path, data_dir);
+            return start_qdrant_process(
+                &binary_path,
+                &config_path,
+                data_dir,
+                &db_info.bind_address,
+                &db_info.env,
+                &db_info.extra_args,
+            );
         }
 
         // SurrealDB 使用直接二进制进程管理
@@ -649,9 +982,25 @@ telemetry_disabled: true
             return start_surrealdb_process(db_info);
         }
 
+        if db_info.db_type == DatabaseType::OpenSearch {
+            return start_opensearch_process(db_info);
+        }
+
+        if db_info.db_type == DatabaseType::InfluxDB {
+            return start_influxdb_process(db_info);
+        }
+
+        if db_info.db_type == DatabaseType::Cassandra {
+            return cassandra::start(db_info);
+        }
+
+        if db_info.db_type == DatabaseType::Neo4j {
+            return neo4j::start(db_info);
+        }
+
         let brew = Homebrew::bootstrap()?;
-        let recipe = HomebrewDatabaseRecipe::resolve(&db_info.db_type)?;
-        brew.start_service_with_retry(recipe.service_name)
+        let recipe = HomebrewDatabaseRecipe::resolve(&db_info.db_type, Some(&db_info.version))?;
+        brew.start_service_with_retry(&recipe.service_name)
     }
 
     pub fn stop_service_for_database(db_info: &DatabaseInfo) -> Result<()> {
@@ -665,9 +1014,64 @@ telemetry_disabled: true
             return stop_surrealdb_process(db_info);
         }
 
+        if matches!(
+            db_info.db_type,
+            DatabaseType::OpenSearch | DatabaseType::InfluxDB
+        ) {
+            return stop_direct_process(db_info);
+        }
+
+        if db_info.db_type == DatabaseType::Cassandra {
+            return cassandra::stop(db_info);
+        }
+
+        if db_info.db_type == DatabaseType::Neo4j {
+            return neo4j::stop(db_info);
+        }
+
+        let brew = Homebrew::bootstrap()?;
+        let recipe = HomebrewDatabaseRecipe::resolve(&db_info.db_type, Some(&db_info.version))?;
+        brew.stop_service(&recipe.service_name)
+    }
+
+    /// 卸载数据库的程序文件
+    ///
+    /// Qdrant、Cassandra、Neo4j 删除下载的二进制目录；其余通过 Homebrew 卸载 formula，并清理 brew services 留下的 launchd 配置。
+    pub fn uninstall_binaries(db_info: &DatabaseInfo) -> Result<()> {
+        if matches!(
+            db_info.db_type,
+            DatabaseType::Qdrant | DatabaseType::Cassandra | DatabaseType::Neo4j
+        ) {
+            return crate::core::backend::remove_binary_dir(db_info);
+        }
+
         let brew = Homebrew::bootstrap()?;
-        let recipe = HomebrewDatabaseRecipe::resolve(&db_info.db_type)?;
-        brew.stop_service(recipe.service_name)
+        if db_info.db_type == DatabaseType::SurrealDB {
+            return brew.uninstall_formula("surreal");
+        }
+        if db_info.db_type == DatabaseType::OpenSearch {
+            return brew.uninstall_formula("opensearch");
+        }
+        if db_info.db_type == DatabaseType::InfluxDB {
+            return brew.uninstall_formula("influxdb");
+        }
+
+        let recipe = HomebrewDatabaseRecipe::resolve(&db_info.db_type, Some(&db_info.version))?;
+        brew.stop_service(&recipe.service_name)?;
+        brew.uninstall_formula(&recipe.formula)?;
+        let _ = brew.run(&["services", "cleanup"]);
+
+        if let Some(home) = dirs::home_dir() {
+            let plist = home
+                .join("Library")
+                .join("LaunchAgents")
+                .join(format!("homebrew.mxcl.{}.plist", recipe.service_name));
+            if plist.exists() {
+                fs::remove_file(&plist)
+                    .with_context(|| format!("Failed to remove {}", plist.display()))?;
+            }
+        }
+        Ok(())
     }
 
     struct ConfiguredPaths {
@@ -678,30 +1082,55 @@ telemetry_disabled: true
     fn configure_database(
         db_type: &DatabaseType,
         brew: &Homebrew,
+        formula: &str,
         storage_path: &Path,
+        instance_dir: &str,
         port: u16,
+        password: Option<&str>,
     ) -> Result<ConfiguredPaths> {
         match db_type {
-            DatabaseType::Redis => configure_redis(brew, storage_path),
-            DatabaseType::MySQL => configure_mysql(brew, storage_path, port),
-            DatabaseType::PostgreSQL => configure_postgresql(brew, storage_path, port),
-            DatabaseType::MongoDB => configure_mongodb(brew, storage_path, port),
+            DatabaseType::Redis => {
+                configure_redis(brew, formula, storage_path, instance_dir, password)
+            }
+            DatabaseType::MySQL => configure_mysql(brew, formula, storage_path, instance_dir, port),
+            DatabaseType::MariaDB => {
+                configure_mariadb(brew, formula, storage_path, instance_dir, port)
+            }
+            DatabaseType::PostgreSQL => {
+                configure_postgresql(brew, formula, storage_path, instance_dir, port)
+            }
+            DatabaseType::MongoDB => {
+                configure_mongodb(brew, formula, storage_path, instance_dir, port)
+            }
             DatabaseType::Qdrant => configure_qdrant(brew, storage_path, port),
-            DatabaseType::SurrealDB => configure_surrealdb(brew, storage_path, port),
-            DatabaseType::Neo4j | DatabaseType::SeekDB => {
+            DatabaseType::SurrealDB => configure_surrealdb(brew, storage_path, "surrealdb", port),
+            DatabaseType::Neo4j
+            | DatabaseType::SeekDB
+            | DatabaseType::OpenSearch
+            | DatabaseType::InfluxDB
+            | DatabaseType::Cassandra
+            | DatabaseType::SQLite
+            | DatabaseType::DuckDB
+            | DatabaseType::Custom => {
                 bail!("Configuration for {:?} not implemented", db_type)
             }
         }
     }
 
-    fn configure_redis(brew: &Homebrew, storage_path: &Path) -> Result<ConfiguredPaths> {
-        let prefix = brew.prefix(Some("redis"))?;
+    fn configure_redis(
+        brew: &Homebrew,
+        formula: &str,
+        storage_path: &Path,
+        instance_dir: &str,
+        password: Option<&str>,
+    ) -> Result<ConfiguredPaths> {
+        let prefix = brew.prefix(Some(formula))?;
         let etc_dir = prefix.join("etc");
         utils::ensure_dir(&etc_dir)?;
         let conf_path = etc_dir.join("redis.conf");
 
-        let data_dir = utils::get_db_data_path(storage_path, "redis");
-        let logs_dir = utils::get_db_log_path(storage_path, "redis");
+        let data_dir = utils::get_db_data_path(storage_path, instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         let log_file = logs_dir.join("redis.log");
@@ -726,6 +1155,8 @@ telemetry_disabled: true
             } else if trimmed.starts_with("logfile ") {
                 rewritten.push_str(&format!("logfile {}\n", log_file.display()));
                 has_logfile = true;
+            } else if trimmed.starts_with("requirepass ") && password.is_some() {
+                // 使用安装时指定的密码，稍后统一追加
             } else {
                 rewritten.push_str(line);
                 rewritten.push('\n');
@@ -739,6 +1170,9 @@ telemetry_disabled: true
         if contents.is_empty() || !has_logfile {
             rewritten.push_str(&format!("logfile {}\n", log_file.display()));
         }
+        if let Some(password) = password {
+            rewritten.push_str(&format!("requirepass {}\n", redis::quote(password)));
+        }
         // 如果是空配置文件，添加一些基本设置
         if contents.is_empty() {
             rewritten.push_str("appendonly no\n");
@@ -753,14 +1187,20 @@ telemetry_disabled: true
         })
     }
 
-    fn configure_mysql(brew: &Homebrew, storage_path: &Path, port: u16) -> Result<ConfiguredPaths> {
-        let prefix = brew.prefix(Some("mysql@8.4"))?;
+    fn configure_mysql(
+        brew: &Homebrew,
+        formula: &str,
+        storage_path: &Path,
+        instance_dir: &str,
+        port: u16,
+    ) -> Result<ConfiguredPaths> {
+        let prefix = brew.prefix(Some(formula))?;
         let etc_dir = prefix.join("etc");
         utils::ensure_dir(&etc_dir)?;
         let conf_path = etc_dir.join("my.cnf");
 
-        let data_dir = utils::get_db_data_path(storage_path, "mysql");
-        let logs_dir = utils::get_db_log_path(storage_path, "mysql");
+        let data_dir = utils::get_db_data_path(storage_path, instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
 
@@ -817,21 +1257,72 @@ telemetry_disabled: true
         Ok(())
     }
 
+    fn configure_mariadb(
+        brew: &Homebrew,
+        formula: &str,
+        storage_path: &Path,
+        instance_dir: &str,
+        port: u16,
+    ) -> Result<ConfiguredPaths> {
+        let prefix = brew.prefix(Some(formula))?;
+        let etc_dir = prefix.join("etc");
+        utils::ensure_dir(&etc_dir)?;
+        let conf_path = etc_dir.join("my.cnf");
+
+        let data_dir = utils::get_db_data_path(storage_path, instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+
+        let log_file = logs_dir.join("mariadbd.log");
+        let pid_file = data_dir.join("mariadbd.pid");
+        let socket_file = data_dir.join("mariadb.sock");
+        let user = std::env::var("USER").unwrap_or_else(|_| "local".to_string());
+
+        let config_content = format!(
+            "[mysqld]\n\tdatadir = {datadir}\n\tsocket = {socket}\n\tlog-error = {log_error}\n\tpid-file = {pid_file}\n\tport = {port}\n\tuser = {user}\n[client]\n\tsocket = {socket}\n\tport = {port}\n",
+            datadir = data_dir.display(),
+            socket = socket_file.display(),
+            log_error = log_file.display(),
+            pid_file = pid_file.display(),
+            port = port,
+            user = user
+        );
+        fs::write(&conf_path, config_content).with_context(|| "Failed to write my.cnf")?;
+
+        mariadb::initialize_data_dir(
+            &prefix.join("bin").join("mariadb-install-db"),
+            &prefix,
+            &data_dir,
+            Some(&user),
+        )?;
+
+        Ok(ConfiguredPaths {
+            config_path: conf_path,
+            log_path: log_file,
+        })
+    }
+
     fn configure_postgresql(
         brew: &Homebrew,
+        formula: &str,
         storage_path: &Path,
+        instance_dir: &str,
         port: u16,
     ) -> Result<ConfiguredPaths> {
-        let prefix = brew.prefix(Some("postgresql@18"))?;
+        let prefix = brew.prefix(Some(formula))?;
         let etc_dir = prefix.join("etc");
         utils::ensure_dir(&etc_dir)?;
         let conf_path = etc_dir.join("postgresql.conf");
 
-        let data_dir = utils::get_db_data_path(storage_path, "postgresql");
-        let logs_dir = utils::get_db_log_path(storage_path, "postgresql");
+        let data_dir = utils::get_db_data_path(storage_path, instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         let log_file = logs_dir.join("postgresql.log");
+        // WAL 归档目录，配合 pg_basebackup 物理备份使用，由备份清理任务按保留的物理备份清理
+        let wal_archive_dir = utils::get_db_backup_path(storage_path, instance_dir).join("wal");
+        utils::ensure_dir(&wal_archive_dir)?;
 
         let user = std::env::var("USER").unwrap_or_else(|_| "local".to_string());
 
@@ -857,6 +1348,12 @@ telemetry_disabled: true
             min_wal_size = 1GB\n\
             max_wal_size = 4GB\n\
             \n\
+            # WAL archiving (physical backups)\n\
+            wal_level = replica\n\
+            max_wal_senders = 3\n\
+            archive_mode = on\n\
+            archive_command = 'test ! -f \"{wal_dir}/%f\" && cp \"%p\" \"{wal_dir}/%f\"'\n\
+            \n\
             # Logging\n\
             logging_collector = on\n\
             log_directory = '{}'\n\
@@ -871,7 +1368,8 @@ telemetry_disabled: true
             timezone = 'UTC'\n\
             default_text_search_config = 'pg_catalog.english'\n",
             port,
-            logs_dir.display()
+            logs_dir.display(),
+            wal_dir = wal_archive_dir.display()
         );
         fs::write(&conf_path, config_content).with_context(|| "Failed to write postgresql.conf")?;
 
@@ -960,12 +1458,14 @@ telemetry_disabled: true
 
     fn configure_mongodb(
         brew: &Homebrew,
+        formula: &str,
         storage_path: &Path,
+        instance_dir: &str,
         _port: u16,
     ) -> Result<ConfiguredPaths> {
-        let prefix = brew.prefix(Some("mongodb-community@7.0"))?;
-        let data_dir = utils::get_db_data_path(storage_path, "mongodb");
-        let logs_dir = utils::get_db_log_path(storage_path, "mongodb");
+        let prefix = brew.prefix(Some(formula))?;
+        let data_dir = utils::get_db_data_path(storage_path, instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         let log_file = logs_dir.join("mongod.log");
@@ -999,15 +1499,16 @@ telemetry_disabled: true
     fn configure_surrealdb(
         brew: &Homebrew,
         storage_path: &Path,
+        instance_dir: &str,
         port: u16,
     ) -> Result<ConfiguredPaths> {
         let prefix = brew.prefix(Some("surreal"))?;
         let etc_dir = prefix.join("etc");
         utils::ensure_dir(&etc_dir)?;
-        let conf_path = etc_dir.join("surrealdb.env");
+        let conf_path = etc_dir.join(format!("{}.env", instance_dir));
 
-        let data_dir = utils::get_db_data_path(storage_path, "surrealdb");
-        let logs_dir = utils::get_db_log_path(storage_path, "surrealdb");
+        let data_dir = utils::get_db_data_path(storage_path, instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         let log_file = logs_dir.join("surrealdb.log");
@@ -1018,7 +1519,7 @@ telemetry_disabled: true
             # Data directory\n\
             SURREAL_PATH={}\n\
             # Server bind address\n\
-            SURREAL_BIND=0.0.0.0:{}\n\
+            SURREAL_BIND=127.0.0.1:{}\n\
             # Log level\n\
             SURREAL_LOG=info\n\
             # Log file\n\
@@ -1064,9 +1565,11 @@ telemetry_disabled: true
             if let Some(existing) = Self::detect()? {
                 return Ok(existing);
             }
-            install_homebrew_via_script()?;
-            Self::detect()?
-                .ok_or_else(|| anyhow!("Homebrew installation completed but binary not found"))
+            install_homebrew_via_script().context(AppError::BrewMissing)?;
+            Self::detect()?.ok_or_else(|| {
+                anyhow!("Homebrew installation completed but binary not found")
+                    .context(AppError::BrewMissing)
+            })
         }
 
         fn detect() -> Result<Option<Self>> {
@@ -1099,6 +1602,13 @@ telemetry_disabled: true
             }
         }
 
+        /// brew 命令，带上设置中的代理和 bottle 镜像
+        fn command(&self) -> Command {
+            let mut command = Command::new(&self.bin_path);
+            download::apply_env(&mut command);
+            command
+        }
+
         fn ensure_formula(&self, tap: Option<&str>, formula: &str) -> Result<()> {
             if let Some(tap) = tap {
                 if !self.is_tap_exists(tap)? {
@@ -1113,7 +1623,8 @@ telemetry_disabled: true
         }
 
         fn is_tap_exists(&self, tap: &str) -> Result<bool> {
-            let output = Command::new(&self.bin_path)
+            let output = self
+                .command()
                 .args(["tap"])
                 .output()
                 .with_context(|| "Failed to list taps")?;
@@ -1122,7 +1633,8 @@ telemetry_disabled: true
         }
 
         fn is_formula_installed(&self, formula: &str) -> Result<bool> {
-            let output = Command::new(&self.bin_path)
+            let output = self
+                .command()
                 .args(["list", "--versions", formula])
                 .output()
                 .with_context(|| format!("Failed to check {} installation", formula))?;
@@ -1131,7 +1643,8 @@ telemetry_disabled: true
         }
 
         fn run(&self, args: &[&str]) -> Result<String> {
-            let output = Command::new(&self.bin_path)
+            let output = self
+                .command()
                 .args(args)
                 .output()
                 .with_context(|| format!("Failed to run brew {}", args.join(" ")))?;
@@ -1200,7 +1713,8 @@ telemetry_disabled: true
         }
 
         fn stop_service(&self, service: &str) -> Result<()> {
-            let output = Command::new(&self.bin_path)
+            let output = self
+                .command()
                 .args(["services", "stop", service])
                 .output()
                 .with_context(|| format!("Failed to stop brew service {}", service))?;
@@ -1214,13 +1728,28 @@ telemetry_disabled: true
             bail!("brew services stop {} failed: {}", service, stderr);
         }
 
+        fn uninstall_formula(&self, formula: &str) -> Result<()> {
+            if !self.is_formula_installed(formula)? {
+                return Ok(());
+            }
+            // 未固定时 unpin 会失败，忽略即可
+            let _ = self.run(&["unpin", formula]);
+            self.run(&["uninstall", "--force", formula]).map(|_| ())
+        }
+
+        /// 固定已安装的 formula，`brew upgrade` 不会再升级它
+        fn pin_formula(&self, formula: &str) -> Result<()> {
+            self.run(&["pin", formula]).map(|_| ())
+        }
+
         fn restart_service(&self, service: &str) -> Result<()> {
             let _ = self.stop_service(service);
             self.start_service(service)
         }
 
         fn get_service_status(&self, service: &str) -> Result<bool> {
-            let output = Command::new(&self.bin_path)
+            let output = self
+                .command()
                 .args(["services", "list"])
                 .output()
                 .with_context(|| "Failed to list brew services")?;
@@ -1232,7 +1761,8 @@ telemetry_disabled: true
         }
 
         fn list_services(&self) -> Result<String> {
-            let output = Command::new(&self.bin_path)
+            let output = self
+                .command()
                 .args(["services", "list"])
                 .output()
                 .with_context(|| "Failed to list brew services")?;
@@ -1244,54 +1774,51 @@ telemetry_disabled: true
     }
 
     struct HomebrewDatabaseRecipe {
-        #[allow(dead_code)]
-        db_type: DatabaseType,
         tap: Option<&'static str>,
-        formula: &'static str,
-        service_name: &'static str,
+        formula: String,
+        service_name: String,
         port: u16,
     }
 
     impl HomebrewDatabaseRecipe {
-        fn resolve(db_type: &DatabaseType) -> Result<Self> {
-            match db_type {
-                DatabaseType::Redis => Ok(Self {
-                    db_type: DatabaseType::Redis,
-                    tap: None,
-                    formula: "redis",
-                    service_name: "redis",
-                    port: 6379,
-                }),
-                DatabaseType::MySQL => Ok(Self {
-                    db_type: DatabaseType::MySQL,
-                    tap: None,
-                    formula: "mysql@8.4",
-                    service_name: "mysql@8.4",
-                    port: 3306,
-                }),
-                DatabaseType::PostgreSQL => Ok(Self {
-                    db_type: DatabaseType::PostgreSQL,
-                    tap: None,
-                    formula: "postgresql@18",
-                    service_name: "postgresql@18",
-                    port: 5432,
-                }),
-                DatabaseType::MongoDB => Ok(Self {
-                    db_type: DatabaseType::MongoDB,
-                    tap: Some("mongodb/brew"),
-                    formula: "mongodb-community@7.0",
-                    service_name: "mongodb-community@7.0",
-                    port: 27017,
-                }),
+        /// 按版本解析 formula，版本未指定或无法识别时使用默认版本
+        fn resolve(db_type: &DatabaseType, version: Option<&str>) -> Result<Self> {
+            let (tap, port) = match db_type {
+                DatabaseType::Redis => (None, 6379),
+                DatabaseType::MySQL => (None, 3306),
+                DatabaseType::MariaDB => (None, mariadb::DEFAULT_PORT),
+                DatabaseType::PostgreSQL => (None, 5432),
+                DatabaseType::MongoDB => (Some("mongodb/brew"), 27017),
                 DatabaseType::Qdrant => {
                     bail!("Qdrant should be installed via binary, not Homebrew")
                 }
                 DatabaseType::SurrealDB => {
                     bail!("SurrealDB should be started via direct process, not brew services")
                 }
-                DatabaseType::Neo4j => bail!("Neo4j not yet implemented on macOS"),
+                DatabaseType::OpenSearch => {
+                    bail!("OpenSearch should be started via direct process, not brew services")
+                }
+                DatabaseType::InfluxDB => {
+                    bail!("InfluxDB should be started via direct process, not brew services")
+                }
+                DatabaseType::Cassandra => {
+                    bail!("Cassandra should be installed via binary, not Homebrew")
+                }
+                DatabaseType::Neo4j => {
+                    bail!("Neo4j should be installed via binary, not Homebrew")
+                }
                 DatabaseType::SeekDB => bail!("SeekDB not yet implemented on macOS"),
-            }
+                DatabaseType::SQLite => bail!("SQLite is not installed via Homebrew"),
+                DatabaseType::DuckDB => bail!("DuckDB is not installed via Homebrew"),
+                DatabaseType::Custom => bail!("Custom servers are not installed via Homebrew"),
+            };
+            let formula = versions::homebrew_formula(db_type, version)?;
+            Ok(Self {
+                tap,
+                service_name: formula.clone(),
+                formula,
+                port,
+            })
         }
     }
 
@@ -1312,7 +1839,7 @@ telemetry_disabled: true
     }
 
     fn run_shell_command(command: &str) -> Result<()> {
-        let status = Command::new("/bin/bash")
+        let status = download::apply_env(&mut Command::new("/bin/bash"))
             .arg("-c")
             .arg(command)
             .status()
@@ -1346,6 +1873,10 @@ mod imp {
         bail!("Homebrew workflow is only available on macOS");
     }
 
+    pub fn uninstall_binaries(_db_info: &DatabaseInfo) -> Result<()> {
+        bail!("Homebrew workflow is only available on macOS");
+    }
+
     pub fn get_installed_databases_from_homebrew() -> Vec<DatabaseType> {
         Vec::new()
     }
@@ -1354,6 +1885,14 @@ mod imp {
         None
     }
 
+    pub fn get_homebrew_formula_stable_version(_formula: &str) -> Option<String> {
+        None
+    }
+
+    pub fn get_database_service_status(_db_info: &DatabaseInfo) -> Option<bool> {
+        None
+    }
+
     pub fn get_all_homebrew_services_status() -> std::collections::HashMap<String, bool> {
         std::collections::HashMap::new()
     }
@@ -1362,13 +1901,16 @@ mod imp {
 // macOS 导出
 #[cfg(target_os = "macos")]
 pub use imp::{
-    get_all_homebrew_services_status, install_database_via_homebrew, start_service_for_database,
-    stop_service_for_database,
+    get_all_homebrew_services_status, get_database_service_status,
+    get_homebrew_formula_stable_version, install_database_via_homebrew, install_homebrew_formula,
+    start_service_for_database, stop_service_for_database, supports_multiple_instances,
+    uninstall_binaries,
 };
 
 // 非 macOS 导出
 #[cfg(not(target_os = "macos"))]
 pub use imp::{
-    get_all_homebrew_services_status, install_database_via_homebrew, start_service_for_database,
-    stop_service_for_database,
+    get_all_homebrew_services_status, get_database_service_status,
+    get_homebrew_formula_stable_version, install_database_via_homebrew, start_service_for_database,
+    stop_service_for_database, uninstall_binaries,
 };
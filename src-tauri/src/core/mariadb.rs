@@ -0,0 +1,56 @@
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// 未指定版本时安装的 MariaDB 版本（11.4 LTS）
+pub const DEFAULT_VERSION: &str = "11.4.4";
+
+/// 默认端口，避开 MySQL 的 3306，两者可以同时运行
+pub const DEFAULT_PORT: u16 = 3307;
+
+/// 发行包下载地址，`package` 形如 `bintar-linux-systemd-x86_64`、`winx64-packages`，
+/// `suffix` 形如 `linux-systemd-x86_64.tar.gz`、`winx64.zip`
+pub fn download_url(version: &str, package: &str, suffix: &str) -> String {
+    format!("https://archive.mariadb.org/mariadb-{version}/{package}/mariadb-{version}-{suffix}")
+}
+
+/// 使用 `mariadb-install-db` 初始化数据目录，已初始化过时跳过
+///
+/// root 改为密码认证且初始密码为空，与 MySQL 的 `--initialize-insecure` 一致；
+/// MariaDB 默认只允许同名系统用户通过 unix socket 登录 root，无法用 TCP 连接。
+pub fn initialize_data_dir(
+    install_db: &Path,
+    base_dir: &Path,
+    data_dir: &Path,
+    user: Option<&str>,
+) -> Result<()> {
+    // 系统库目录与 MySQL 同名
+    if data_dir.join("mysql").exists() {
+        return Ok(());
+    }
+    if !install_db.exists() {
+        bail!("mariadb-install-db not found at {}", install_db.display());
+    }
+
+    let mut command = Command::new(install_db);
+    command
+        .arg("--no-defaults")
+        .arg(format!("--basedir={}", base_dir.display()))
+        .arg(format!("--datadir={}", data_dir.display()))
+        .arg("--auth-root-authentication-method=normal")
+        .arg("--skip-test-db");
+    if let Some(user) = user {
+        command.arg(format!("--user={}", user));
+    }
+
+    let output = command
+        .output()
+        .context("Failed to initialize MariaDB data directory")?;
+    if !output.status.success() {
+        bail!(
+            "MariaDB initialization failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use crate::core::{utils, watchdog, DatabaseInfo, DatabaseStatus};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// 每个对象保留的采样点数，按默认 10 秒的间隔约为 24 小时
+const HISTORY_CAPACITY: usize = 8640;
+
+/// 系统整体指标在历史记录中使用的键，数据库指标以数据库 ID 为键
+pub const SYSTEM_KEY: &str = "system";
+
+/// 一次采样的指标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSample {
+    /// 采样时间（Unix 时间戳，秒）
+    pub timestamp: i64,
+    /// CPU 使用率，数据库进程占用多个核心时可能超过 100
+    pub cpu_usage: f32,
+    /// 已用内存（字节）
+    pub memory_used: u64,
+}
+
+/// 指标历史，每个对象一个定长的环形缓冲区
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MetricsHistory {
+    samples: HashMap<String, VecDeque<MetricSample>>,
+}
+
+impl MetricsHistory {
+    /// 从磁盘加载保存的历史，文件不存在或损坏时返回空历史
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// 保存到磁盘
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            utils::ensure_dir(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// 记录一个采样点，超出容量时丢弃最旧的
+    pub fn record(&mut self, key: &str, sample: MetricSample) {
+        let samples = self.samples.entry(key.to_string()).or_default();
+        if samples.len() >= HISTORY_CAPACITY {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// 某个对象在 `since` 之后的采样点，按时间顺序排列
+    pub fn since(&self, key: &str, since: i64) -> Vec<MetricSample> {
+        self.samples
+            .get(key)
+            .map(|samples| {
+                samples
+                    .iter()
+                    .filter(|sample| sample.timestamp >= since)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 删除已不存在的数据库的历史
+    pub fn retain(&mut self, database_ids: &[String]) {
+        self.samples
+            .retain(|key, _| key == SYSTEM_KEY || database_ids.contains(key));
+    }
+}
+
+/// 指标采集器，保留 sysinfo 的状态以便按两次采样之间的差值计算 CPU 使用率
+pub struct Sampler {
+    sys: System,
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self { sys: System::new() }
+    }
+}
+
+impl Sampler {
+    /// 采集系统和各运行中数据库进程的指标，返回键与采样点
+    ///
+    /// 没有 PID 的数据库（如 Homebrew 服务、Docker 容器）不采集进程指标。
+    pub fn sample(&mut self, databases: &[DatabaseInfo]) -> Vec<(String, MetricSample)> {
+        let timestamp = chrono::Utc::now().timestamp();
+        self.sys.refresh_cpu_usage();
+        self.sys.refresh_memory();
+
+        let pids: Vec<(String, Pid)> = databases
+            .iter()
+            .filter(|db| db.status == DatabaseStatus::Running)
+            .filter_map(|db| {
+                db.pid
+                    .or_else(|| watchdog::read_pid(db))
+                    .map(|pid| (db.id.clone(), Pid::from_u32(pid)))
+            })
+            .collect();
+        let to_refresh: Vec<Pid> = pids.iter().map(|(_, pid)| *pid).collect();
+        self.sys.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&to_refresh),
+            true,
+            ProcessRefreshKind::new().with_cpu().with_memory(),
+        );
+
+        let mut samples = vec![(
+            SYSTEM_KEY.to_string(),
+            MetricSample {
+                timestamp,
+                cpu_usage: self.sys.global_cpu_usage(),
+                memory_used: self.sys.used_memory(),
+            },
+        )];
+        for (id, pid) in pids {
+            if let Some(process) = self.sys.process(pid) {
+                samples.push((
+                    id,
+                    MetricSample {
+                        timestamp,
+                        cpu_usage: process.cpu_usage(),
+                        memory_used: process.memory(),
+                    },
+                ));
+            }
+        }
+        samples
+    }
+}
@@ -1,9 +1,70 @@
+pub mod activation;
+pub mod backend;
+pub mod backup;
+pub mod benchmark;
+pub mod cassandra;
+pub mod client;
+pub mod clipboard;
+pub mod config_file;
+pub mod connection;
+pub mod crypto;
+pub mod custom;
 pub mod db_manager;
+pub mod deep_link;
+pub mod dependency;
+pub mod diagnostics;
+pub mod distro;
+pub mod docker;
+pub mod download;
+pub mod duckdb;
+pub mod error;
+pub mod export;
+pub mod external;
+pub mod health;
+pub mod import;
+pub mod influxdb;
+pub mod keychain;
+pub mod launchd;
 pub mod linux;
+pub mod log_tail;
 pub mod macos;
+pub mod mariadb;
+pub mod metrics;
+pub mod mongo;
+pub mod neo4j;
+pub mod network;
+pub mod offline;
+pub mod opensearch;
+pub mod password;
+pub mod pg_extension;
+pub mod port;
+pub mod preflight;
+pub mod preset;
+pub mod provision;
+pub mod query;
+pub mod query_stats;
+pub mod redis;
+pub mod relocate;
+pub mod remote;
+pub mod replication;
+pub mod reset;
+pub mod sample_data;
+pub mod schedule;
+pub mod schema;
+pub mod settings;
+pub mod slow_query;
+pub mod snapshot;
+pub mod sqlite;
+pub mod state_schema;
+pub mod surreal;
+pub mod tray;
 pub mod types;
 pub mod utils;
+pub mod versions;
+pub mod watchdog;
 pub mod windows;
+pub mod windows_package;
 
 pub use db_manager::*;
+pub use error::{AppError, CommandError};
 pub use types::*;
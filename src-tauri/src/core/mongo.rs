@@ -0,0 +1,230 @@
+use crate::core::{utils, DatabaseInfo, DatabaseStatus, MongoCollectionInfo, MongoDatabaseInfo};
+use anyhow::{bail, Context, Result};
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::options::{ClientOptions, Credential, ServerAddress};
+use mongodb::results::CollectionType;
+use mongodb::sync::{Client, Database};
+use std::process::Command;
+use std::time::Duration;
+
+/// 向 mongosh 脚本传递登录密码的环境变量，密码不出现在进程列表中
+pub(crate) const AUTH_PASSWORD_ENV: &str = "LOCAL_DB_AUTH_PASSWORD";
+
+/// 连接实例的超时时间，超时后返回错误
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 列出实例中的数据库及其占用的磁盘空间
+pub fn list_databases(db_info: &DatabaseInfo) -> Result<Vec<MongoDatabaseInfo>> {
+    let databases = client(db_info)?
+        .list_databases()
+        .run()
+        .context("Failed to list databases")?;
+    Ok(databases
+        .into_iter()
+        .map(|database| MongoDatabaseInfo {
+            name: database.name,
+            size_on_disk: database.size_on_disk,
+            empty: database.empty,
+        })
+        .collect())
+}
+
+/// 列出数据库中的集合和视图，集合附带估算的文档数
+pub fn list_collections(
+    db_info: &DatabaseInfo,
+    database: &str,
+) -> Result<Vec<MongoCollectionInfo>> {
+    collections(&client(db_info)?.database(database))
+}
+
+/// 一次列出所有数据库及其中的集合，按数据库名返回
+///
+/// 所有数据库共用同一个连接。
+pub fn list_all_collections(
+    db_info: &DatabaseInfo,
+) -> Result<Vec<(String, Vec<MongoCollectionInfo>)>> {
+    let client = client(db_info)?;
+    let names = client
+        .list_database_names()
+        .run()
+        .context("Failed to list databases")?;
+    names
+        .into_iter()
+        .map(|name| {
+            let collections = collections(&client.database(&name))?;
+            Ok((name, collections))
+        })
+        .collect()
+}
+
+fn collections(database: &Database) -> Result<Vec<MongoCollectionInfo>> {
+    let specifications = database
+        .list_collections()
+        .run()
+        .with_context(|| format!("Failed to list collections in {}", database.name()))?
+        .collect::<mongodb::error::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to list collections in {}", database.name()))?;
+    specifications
+        .into_iter()
+        .map(|specification| {
+            let (collection_type, document_count) = match specification.collection_type {
+                CollectionType::View => ("view", None),
+                CollectionType::Timeseries => ("timeseries", None),
+                _ => {
+                    let count = database
+                        .collection::<Document>(&specification.name)
+                        .estimated_document_count()
+                        .run()
+                        .with_context(|| {
+                            format!("Failed to count documents in {}", specification.name)
+                        })?;
+                    ("collection", Some(count))
+                }
+            };
+            Ok(MongoCollectionInfo {
+                name: specification.name,
+                collection_type: collection_type.to_string(),
+                document_count,
+            })
+        })
+        .collect()
+}
+
+/// 在指定数据库上执行数据库命令，`command` 为 Extended JSON 格式的命令文档
+///
+/// 命令失败时 MongoDB 的错误作为 Err 返回。
+pub fn run_command(
+    db_info: &DatabaseInfo,
+    database: &str,
+    command: &str,
+) -> Result<serde_json::Value> {
+    // 直接解析为 Document，保留字段顺序，命令名必须是第一个字段
+    let document: Document = serde_json::from_str(command).map_err(|e| {
+        if e.is_data() {
+            anyhow::anyhow!("The command must be a JSON object, such as {{\"ping\": 1}}")
+        } else {
+            anyhow::Error::new(e).context("The command is not valid JSON")
+        }
+    })?;
+
+    let result = client(db_info)?
+        .database(database)
+        .run_command(document)
+        .run()
+        .context("Command failed")?;
+    Ok(Bson::Document(result).into_relaxed_extjson())
+}
+
+/// 向集合批量插入文档，返回插入的数量
+pub fn insert_documents(
+    db_info: &DatabaseInfo,
+    database: &str,
+    collection: &str,
+    documents: &[serde_json::Value],
+) -> Result<u64> {
+    let documents = documents
+        .iter()
+        .map(|document| match Bson::try_from(document.clone()) {
+            Ok(Bson::Document(document)) => Ok(document),
+            Ok(_) => bail!("Documents must be JSON objects"),
+            Err(e) => Err(e).context("Invalid Extended JSON document"),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let result = client(db_info)?
+        .database(database)
+        .collection::<Document>(collection)
+        .insert_many(documents)
+        .run()
+        .with_context(|| format!("Failed to insert documents into {}", collection))?;
+    Ok(result.inserted_ids.len() as u64)
+}
+
+/// 在 admin 库创建用户，`databases` 中的每个库授予 readWrite 角色
+pub fn create_user(
+    db_info: &DatabaseInfo,
+    username: &str,
+    password: &str,
+    databases: &[String],
+) -> Result<()> {
+    let roles: Vec<Document> = databases
+        .iter()
+        .map(|db| doc! { "role": "readWrite", "db": db })
+        .collect();
+    create_user_with_roles(db_info, username, password, roles)
+}
+
+/// 在 admin 库创建拥有 root 角色的管理员用户
+pub fn create_admin_user(db_info: &DatabaseInfo, username: &str, password: &str) -> Result<()> {
+    let roles = vec![doc! { "role": "root", "db": "admin" }];
+    create_user_with_roles(db_info, username, password, roles)
+}
+
+fn create_user_with_roles(
+    db_info: &DatabaseInfo,
+    username: &str,
+    password: &str,
+    roles: Vec<Document>,
+) -> Result<()> {
+    client(db_info)?
+        .database("admin")
+        .run_command(doc! { "createUser": username, "pwd": password, "roles": roles })
+        .run()
+        .with_context(|| format!("Failed to create user {}", username))?;
+    Ok(())
+}
+
+/// 用 MongoDB 驱动连接实例
+///
+/// 有用户名时在 admin 库认证。连接超过 [`CONNECT_TIMEOUT`] 时返回错误。
+fn client(db_info: &DatabaseInfo) -> Result<Client> {
+    if db_info.status != DatabaseStatus::Running {
+        bail!("{} must be running to run commands", db_info.name);
+    }
+
+    let mut options = ClientOptions::default();
+    options.hosts = vec![ServerAddress::Tcp {
+        host: "127.0.0.1".to_string(),
+        port: Some(db_info.port),
+    }];
+    options.direct_connection = Some(true);
+    options.connect_timeout = Some(CONNECT_TIMEOUT);
+    options.server_selection_timeout = Some(CONNECT_TIMEOUT);
+    options.app_name = Some("local-db".to_string());
+    if let Some(username) = db_info.username.as_deref().filter(|u| !u.is_empty()) {
+        options.credential = Some(
+            Credential::builder()
+                .username(username.to_string())
+                .password(db_info.password.clone().unwrap_or_default())
+                .source("admin".to_string())
+                .build(),
+        );
+    }
+    Client::with_options(options).context("Failed to connect to MongoDB")
+}
+
+/// 生成执行 `script` 的 mongosh 命令
+///
+/// 有用户名时在脚本开头登录 admin 库，密码通过环境变量传入，不出现在进程列表中。
+pub(crate) fn mongosh_command(db_info: &DatabaseInfo, script: &str) -> Result<Command> {
+    let mut command = Command::new(utils::resolve_db_binary(&db_info.install_path, "mongosh"));
+    command
+        .arg("--quiet")
+        .arg(format!("mongodb://127.0.0.1:{}/admin", db_info.port));
+    let script = match db_info.username.as_deref().filter(|u| !u.is_empty()) {
+        Some(username) => {
+            command.env(
+                AUTH_PASSWORD_ENV,
+                db_info.password.as_deref().unwrap_or_default(),
+            );
+            format!(
+                "db.getSiblingDB('admin').auth({}, process.env.{});\n{}",
+                serde_json::to_string(username)?,
+                AUTH_PASSWORD_ENV,
+                script
+            )
+        }
+        None => script.to_string(),
+    };
+    command.arg("--eval").arg(script);
+    Ok(command)
+}
@@ -0,0 +1,237 @@
+use crate::core::backend::InstallOptions;
+use crate::core::{
+    network, utils, watchdog, BackendKind, DatabaseInfo, DatabaseStatus, DatabaseType,
+};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// 未指定版本时下载的 Neo4j 社区版版本（5.26 LTS）
+pub const DEFAULT_VERSION: &str = "5.26.0";
+
+/// Bolt 协议端口，使用 Neo4j 默认值
+const BOLT_PORT: u16 = 7687;
+
+/// Neo4j 5 支持的 Java 主版本
+const SUPPORTED_JAVA_VERSIONS: [u32; 2] = [17, 21];
+
+/// Neo4j 要求的最短密码长度
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// 下载 Neo4j 社区版发行包、生成实例配置并设置初始密码
+///
+/// 发行包不带 JRE，安装前先检查本机 Java。Bolt 端口固定为默认值，同一时间只能运行一个实例。
+/// Neo4j 只允许为内置的 `neo4j` 用户设置初始密码，忽略传入的用户名。
+pub fn install(storage_path: &Path, options: &InstallOptions<'_>) -> Result<DatabaseInfo> {
+    detect_java()?;
+    let password = options.password.unwrap_or("admin888");
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        bail!(
+            "Neo4j requires a password of at least {} characters",
+            MIN_PASSWORD_LENGTH
+        );
+    }
+
+    let version = options.version.unwrap_or(DEFAULT_VERSION);
+    let bin_dir = utils::get_db_version_bin_path(storage_path, "neo4j", version);
+    if !bin_dir.join("bin").join("neo4j").exists() {
+        let url = format!("https://dist.neo4j.org/neo4j-community-{version}-unix.tar.gz");
+        utils::download_and_extract_tar_gz("Neo4j", &url, &bin_dir, options.archive)?;
+    }
+
+    let instance_dir = utils::get_instance_dir_name("neo4j", options.instance_name);
+    let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+    let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+    let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
+    utils::ensure_dir(&data_dir)?;
+    utils::ensure_dir(&logs_dir)?;
+
+    let port = options.port.unwrap_or(7474);
+    let config_path = write_config(
+        &bin_dir.join("conf"),
+        &config_dir,
+        &data_dir,
+        &logs_dir,
+        port,
+    )?;
+    set_initial_password(&bin_dir, &config_dir, password)?;
+
+    let mut db_info = DatabaseInfo {
+        id: utils::generate_id(),
+        name: options.instance_name.unwrap_or("Neo4j").to_string(),
+        db_type: DatabaseType::Neo4j,
+        backend: BackendKind::Native,
+        version: version.to_string(),
+        install_path: bin_dir.to_string_lossy().to_string(),
+        data_path: data_dir.to_string_lossy().to_string(),
+        log_path: logs_dir.join("neo4j.log").to_string_lossy().to_string(),
+        port,
+        username: Some("neo4j".to_string()),
+        password: Some(password.to_string()),
+        credential_ref: None,
+        config: Some(config_path.to_string_lossy().to_string()),
+        status: DatabaseStatus::Stopped,
+        auto_start: options.auto_start,
+        depends_on: Vec::new(),
+        env: HashMap::new(),
+        extra_args: Vec::new(),
+        schedules: Vec::new(),
+        on_demand: false,
+        replica_of: None,
+        bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+        pid: None,
+        created_at: utils::get_timestamp(),
+        updated_at: utils::get_timestamp(),
+    };
+
+    if options.auto_start {
+        start(&db_info)?;
+        db_info.status = DatabaseStatus::Running;
+    }
+
+    Ok(db_info)
+}
+
+/// 通过 `neo4j start` 在后台启动，PID 文件写入数据目录下的 `neo4j.pid`
+pub fn start(db_info: &DatabaseInfo) -> Result<()> {
+    detect_java()?;
+    if watchdog::is_process_alive(db_info) {
+        return Ok(());
+    }
+
+    let status = neo4j_command(db_info)?
+        .arg("start")
+        .args(&db_info.extra_args)
+        // 后台运行的 JVM 会继承输出管道，不能等待其关闭
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .context("Failed to start Neo4j")?;
+    if !status.success() {
+        bail!(
+            "Neo4j failed to start, see {} for details",
+            db_info.log_path
+        );
+    }
+    Ok(())
+}
+
+/// 通过 `neo4j stop` 停止 Neo4j，失败时向进程发送 SIGTERM
+pub fn stop(db_info: &DatabaseInfo) -> Result<()> {
+    let stopped = neo4j_command(db_info)?
+        .arg("stop")
+        .output()
+        .is_ok_and(|output| output.status.success());
+
+    #[cfg(unix)]
+    if !stopped && watchdog::is_process_alive(db_info) {
+        use nix::sys::signal::{kill, Signal};
+        use nix::unistd::Pid;
+
+        if let Some(pid) = watchdog::read_pid(db_info) {
+            kill(Pid::from_raw(pid as i32), Signal::SIGTERM)
+                .context("Failed to send SIGTERM to Neo4j")?;
+        }
+    }
+    #[cfg(not(unix))]
+    if !stopped {
+        bail!("neo4j stop failed");
+    }
+
+    let _ = fs::remove_file(watchdog::pid_file_path(db_info));
+    Ok(())
+}
+
+/// 检查本机 Java 是否可以运行 Neo4j，返回 Java 主版本号
+pub fn detect_java() -> Result<u32> {
+    utils::detect_java("Neo4j", &SUPPORTED_JAVA_VERSIONS)
+}
+
+/// 在前台运行 Neo4j 的命令（`neo4j console`），由 launchd 等进程管理器负责启停
+pub fn foreground_command(db_info: &DatabaseInfo) -> Result<Command> {
+    let mut command = neo4j_command(db_info)?;
+    command.arg("console");
+    Ok(command)
+}
+
+/// 发行包自带的 `neo4j` 脚本，`NEO4J_CONF` 指向实例自己的配置目录
+fn neo4j_command(db_info: &DatabaseInfo) -> Result<Command> {
+    let config_dir = db_info
+        .config
+        .as_deref()
+        .and_then(|config| Path::new(config).parent())
+        .context("Config path missing")?;
+    let mut command = Command::new(Path::new(&db_info.install_path).join("bin").join("neo4j"));
+    command.env("NEO4J_CONF", config_dir).envs(&db_info.env);
+    Ok(command)
+}
+
+/// 复制发行包的 conf 目录（包含日志配置），并生成目录和端口指向实例的 neo4j.conf
+///
+/// 运行目录设为数据目录，`neo4j start` 写入的 PID 文件即为 `<data>/neo4j.pid`。
+fn write_config(
+    dist_conf_dir: &Path,
+    config_dir: &Path,
+    data_dir: &Path,
+    logs_dir: &Path,
+    port: u16,
+) -> Result<PathBuf> {
+    utils::copy_dir(dist_conf_dir, config_dir)
+        .with_context(|| format!("Failed to copy {}", dist_conf_dir.display()))?;
+
+    let config_path = config_dir.join("neo4j.conf");
+    let config_content = format!(
+        r#"# Neo4j configuration for local-db
+server.directories.data={data}
+server.directories.logs={logs}
+server.directories.run={data}
+
+server.default_listen_address=127.0.0.1
+server.bolt.listen_address=:{bolt_port}
+server.http.listen_address=:{port}
+server.https.enabled=false
+
+server.memory.heap.initial_size=512m
+server.memory.heap.max_size=1g
+server.memory.pagecache.size=512m
+
+dbms.security.auth_enabled=true
+
+server.jvm.additional=-XX:+UseG1GC
+server.jvm.additional=-XX:-OmitStackTraceInFastThrow
+server.jvm.additional=-XX:+AlwaysPreTouch
+server.jvm.additional=-XX:+UnlockExperimentalVMOptions
+server.jvm.additional=-XX:+TrustFinalNonStaticFields
+server.jvm.additional=-XX:+DisableExplicitGC
+server.jvm.additional=-Djdk.nio.maxCachedBufferSize=1024
+server.jvm.additional=-Dio.netty.tryReflectionSetAccessible=true
+server.jvm.additional=-Djdk.tls.ephemeralDHKeySize=2048
+server.jvm.additional=-Djdk.tls.rejectClientInitiatedRenegotiation=true
+"#,
+        data = data_dir.display(),
+        logs = logs_dir.display(),
+        bolt_port = BOLT_PORT,
+        port = port,
+    );
+    fs::write(&config_path, config_content).context("Failed to write neo4j.conf")?;
+    Ok(config_path)
+}
+
+/// 使用 `neo4j-admin dbms set-initial-password` 设置 `neo4j` 用户的密码，只在首次启动前生效
+fn set_initial_password(bin_dir: &Path, config_dir: &Path, password: &str) -> Result<()> {
+    let output = Command::new(bin_dir.join("bin").join("neo4j-admin"))
+        .args(["dbms", "set-initial-password", password])
+        .env("NEO4J_CONF", config_dir)
+        .output()
+        .context("Failed to run neo4j-admin")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to set Neo4j initial password: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
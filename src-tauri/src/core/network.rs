@@ -0,0 +1,119 @@
+use crate::core::{DatabaseInfo, DatabaseType};
+use anyhow::{Context, Result};
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::path::Path;
+
+/// 默认的监听地址，只接受本机的连接
+pub const LOCAL_BIND_ADDRESS: &str = "127.0.0.1";
+
+/// 开放到网络时的监听地址，接受所有网卡上的连接
+pub const NETWORK_BIND_ADDRESS: &str = "0.0.0.0";
+
+/// pg_hba.conf 中允许局域网连接的规则，只放行私有地址段（RFC 1918），要求密码认证
+const PG_HBA_NETWORK_RULES: &[&str] = &[
+    "host all all 10.0.0.0/8 scram-sha-256",
+    "host all all 172.16.0.0/12 scram-sha-256",
+    "host all all 192.168.0.0/16 scram-sha-256",
+];
+
+/// 是否可以修改监听地址，其余类型的监听地址固定写在安装时生成的配置中
+pub fn supports_bind_address(db_type: &DatabaseType) -> bool {
+    matches!(
+        db_type,
+        DatabaseType::Redis
+            | DatabaseType::MySQL
+            | DatabaseType::MariaDB
+            | DatabaseType::PostgreSQL
+            | DatabaseType::MongoDB
+            | DatabaseType::Qdrant
+            | DatabaseType::SurrealDB
+            | DatabaseType::OpenSearch
+            | DatabaseType::InfluxDB
+            | DatabaseType::DuckDB
+    )
+}
+
+/// 启动进程时指定监听地址的参数，放在实例的额外参数之前
+///
+/// Qdrant 通过环境变量 `QDRANT__SERVICE__HOST` 指定，SurrealDB、InfluxDB 和 DuckDB 的监听地址与端口一起传入。
+pub fn bind_args(db_info: &DatabaseInfo) -> Vec<String> {
+    let address = &db_info.bind_address;
+    match db_info.db_type {
+        DatabaseType::Redis => vec!["--bind".to_string(), address.clone()],
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            vec![format!("--bind-address={}", address)]
+        }
+        DatabaseType::PostgreSQL => vec!["-h".to_string(), address.clone()],
+        DatabaseType::MongoDB => vec!["--bind_ip".to_string(), address.clone()],
+        DatabaseType::OpenSearch => vec![format!("-Enetwork.host={}", address)],
+        _ => Vec::new(),
+    }
+}
+
+/// 配置文件中的监听地址，键的写法见 [`crate::core::config_file::apply_overrides`]
+///
+/// 用于由 brew services 等按配置文件启动、不经过应用传参的进程。
+pub fn config_override(db_info: &DatabaseInfo) -> Option<(String, String)> {
+    let address = db_info.bind_address.clone();
+    let (key, value) = match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB => ("mysqld.bind-address", address),
+        DatabaseType::PostgreSQL => ("listen_addresses", format!("'{}'", address)),
+        DatabaseType::Redis => ("bind", address),
+        DatabaseType::MongoDB => ("net.bindIp", address),
+        DatabaseType::Qdrant => ("service.host", address),
+        _ => return None,
+    };
+    Some((key.to_string(), value))
+}
+
+/// 本机在局域网中的 IPv4 地址，即默认路由所在网卡的地址，未联网时为 None
+pub fn lan_address() -> Option<Ipv4Addr> {
+    // UDP 的 connect 只选择路由，不会发出数据包
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(8, 8, 8, 8), 80)).ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) if !ip.is_loopback() && !ip.is_unspecified() => Some(ip),
+        _ => None,
+    }
+}
+
+/// 在 PostgreSQL 的 pg_hba.conf 中添加或移除允许局域网（私有地址段）连接的规则，局域网连接需要密码认证
+///
+/// 本机连接的规则在前面，不受影响。数据目录中没有 pg_hba.conf 时不做处理。
+pub fn set_postgres_network_access(db_info: &DatabaseInfo, allow: bool) -> Result<()> {
+    let path = Path::new(&db_info.data_path).join("pg_hba.conf");
+    if !path.exists() {
+        return Ok(());
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut lines: Vec<&str> = content
+        .lines()
+        .filter(|line| !PG_HBA_NETWORK_RULES.contains(&line.trim()))
+        .collect();
+    if allow {
+        lines.extend(PG_HBA_NETWORK_RULES);
+    }
+    fs::write(&path, format!("{}\n", lines.join("\n")))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// 放行入站连接的防火墙提示，Windows 防火墙默认拦截，macOS 开启防火墙或 pf 时需要放行
+pub fn firewall_note(db_info: &DatabaseInfo) -> Option<String> {
+    if cfg!(windows) {
+        Some(format!(
+            "Windows Firewall may block incoming connections. Allow TCP port {} with: \
+             netsh advfirewall firewall add rule name=\"local-db {}\" dir=in action=allow protocol=TCP localport={}",
+            db_info.port, db_info.name, db_info.port
+        ))
+    } else if cfg!(target_os = "macos") {
+        Some(format!(
+            "If the macOS firewall or pf is enabled, allow incoming connections to {} on TCP port {}.",
+            db_info.db_type.display_name(),
+            db_info.port
+        ))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,156 @@
+use crate::core::DatabaseType;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// 离线安装的发行包中必须包含的文件，路径相对于发行包的根目录
+///
+/// 与各平台安装流程解压后检查的程序文件一致。Linux 上的 Redis 与在线安装一样使用源码包编译。
+fn required_entry(db_type: &DatabaseType) -> Result<&'static str> {
+    let entry = if cfg!(target_os = "windows") {
+        match db_type {
+            DatabaseType::Redis => "redis-server.exe",
+            DatabaseType::MySQL => "bin/mysqld.exe",
+            DatabaseType::MariaDB => "bin/mariadbd.exe",
+            DatabaseType::PostgreSQL => "bin/pg_ctl.exe",
+            DatabaseType::MongoDB => "bin/mongod.exe",
+            DatabaseType::Qdrant => "qdrant.exe",
+            DatabaseType::SurrealDB => "surreal.exe",
+            DatabaseType::OpenSearch => "bin/opensearch.bat",
+            DatabaseType::InfluxDB => "influxd.exe",
+            DatabaseType::Neo4j => "bin/neo4j.bat",
+            _ => bail!(
+                "{} cannot be installed from an archive on Windows",
+                db_type.display_name()
+            ),
+        }
+    } else if cfg!(target_os = "macos") {
+        // 其他数据库通过 Homebrew 安装
+        match db_type {
+            DatabaseType::Qdrant => "qdrant",
+            DatabaseType::Cassandra => "bin/cassandra",
+            DatabaseType::Neo4j => "bin/neo4j",
+            _ => bail!(
+                "{} is installed through Homebrew on macOS",
+                db_type.display_name()
+            ),
+        }
+    } else {
+        match db_type {
+            DatabaseType::Redis => "src/server.c",
+            DatabaseType::MySQL => "bin/mysqld",
+            DatabaseType::MariaDB => "bin/mariadbd",
+            DatabaseType::MongoDB => "bin/mongod",
+            DatabaseType::Qdrant => "qdrant",
+            DatabaseType::SurrealDB => "surreal",
+            DatabaseType::OpenSearch => "bin/opensearch",
+            DatabaseType::InfluxDB => "influxd",
+            DatabaseType::Cassandra => "bin/cassandra",
+            _ => bail!(
+                "{} cannot be installed from an archive on Linux",
+                db_type.display_name()
+            ),
+        }
+    };
+    Ok(entry)
+}
+
+/// 检查离线安装使用的发行包，返回要安装的版本
+///
+/// 发行包格式需与在线安装下载的一致（Windows 上为 zip，其他平台为 tar 包），并包含安装流程需要的程序文件。
+/// 未指定版本时从文件名中识别，如 `mysql-8.0.40-linux-glibc2.28-x86_64.tar.xz` 识别为 8.0.40。
+pub fn check_archive(
+    db_type: &DatabaseType,
+    archive: &Path,
+    version: Option<&str>,
+) -> Result<String> {
+    if !archive.is_file() {
+        bail!("Archive not found: {}", archive.display());
+    }
+    let required = required_entry(db_type)?;
+
+    let file_name = archive
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let is_zip = file_name.ends_with(".zip");
+    let is_tar = [".tar.gz", ".tgz", ".tar.xz", ".tar"]
+        .iter()
+        .any(|ext| file_name.ends_with(ext));
+    if cfg!(target_os = "windows") && !is_zip {
+        bail!("Expected a .zip archive");
+    }
+    if !cfg!(target_os = "windows") && !is_tar {
+        bail!("Expected a .tar.gz, .tgz or .tar.xz archive");
+    }
+    // 这些发行包在内存中用 gzip 解压
+    let gzip_only = cfg!(target_os = "macos") || *db_type == DatabaseType::Cassandra;
+    if gzip_only && !(file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz")) {
+        bail!("Expected a .tar.gz archive");
+    }
+
+    let entries = if is_zip {
+        zip_entries(archive)?
+    } else {
+        tar_entries(archive)?
+    };
+    let found = entries.iter().any(|entry| {
+        let entry = entry.replace('\\', "/");
+        let entry = entry.trim_end_matches('/');
+        entry == required || entry.ends_with(&format!("/{}", required))
+    });
+    if !found {
+        bail!(
+            "{} does not look like a {} release: {} is missing",
+            archive.display(),
+            db_type.display_name(),
+            required
+        );
+    }
+
+    match version.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(version) => Ok(version.trim_start_matches('v').to_string()),
+        None => version_from_file_name(&file_name).with_context(|| {
+            format!(
+                "Cannot determine the version from {}, please specify it",
+                file_name
+            )
+        }),
+    }
+}
+
+/// 文件名中第一个形如 `x.y` 或 `x.y.z` 的版本号
+fn version_from_file_name(file_name: &str) -> Option<String> {
+    file_name
+        .split(|c: char| !c.is_ascii_digit() && c != '.')
+        .map(|part| part.trim_matches('.'))
+        .find(|part| part.contains('.') && part.split('.').all(|n| !n.is_empty()))
+        .map(String::from)
+}
+
+fn zip_entries(archive: &Path) -> Result<Vec<String>> {
+    let file =
+        fs::File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let zip = zip::ZipArchive::new(file).context("Failed to open zip archive")?;
+    Ok(zip.file_names().map(String::from).collect())
+}
+
+/// 用系统 tar 列出文件，支持 .tar.xz 等压缩格式
+fn tar_entries(archive: &Path) -> Result<Vec<String>> {
+    let output = Command::new("tar")
+        .arg("-tf")
+        .arg(archive)
+        .output()
+        .context("Failed to list archive via tar command")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to read archive: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(String::from)
+        .collect())
+}
@@ -0,0 +1,89 @@
+use crate::core::{utils, DatabaseInfo};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 未指定版本时下载的 OpenSearch 版本
+pub const DEFAULT_VERSION: &str = "2.19.1";
+
+/// 默认 JVM 堆大小（MB），OpenSearch 自带的 1g 对本地开发来说偏大
+pub const DEFAULT_HEAP_MB: u32 = 512;
+
+/// 集群名，同时决定主日志文件名 `<cluster.name>.log`
+const CLUSTER_NAME: &str = "local-db";
+
+/// 发行包下载地址，`platform` 形如 `linux-x64`、`linux-arm64`、`windows-x64`
+pub fn download_url(version: &str, platform: &str, extension: &str) -> String {
+    format!(
+        "https://artifacts.opensearch.org/releases/bundle/opensearch/{version}/opensearch-{version}-{platform}.{extension}"
+    )
+}
+
+/// OpenSearch 写入 `path.logs` 的主日志文件
+pub fn log_file(logs_dir: &Path) -> PathBuf {
+    logs_dir.join(format!("{}.log", CLUSTER_NAME))
+}
+
+/// 生成实例自己的配置目录，返回其中 opensearch.yml 的路径
+///
+/// 先复制发行包 `config` 目录下的 jvm.options、log4j2.properties 和插件配置，
+/// 再写入指向托管 data/logs 目录的 opensearch.yml，并通过 `jvm.options.d/heap.options` 设置堆大小。
+/// 本地单节点使用，关闭安全插件，只监听 127.0.0.1。
+pub fn write_config(
+    dist_config_dir: &Path,
+    config_dir: &Path,
+    data_dir: &Path,
+    logs_dir: &Path,
+    port: u16,
+) -> Result<PathBuf> {
+    utils::copy_dir(dist_config_dir, config_dir)
+        .with_context(|| format!("Failed to copy {}", dist_config_dir.display()))?;
+
+    let config_path = config_dir.join("opensearch.yml");
+    let config_content = format!(
+        r#"# OpenSearch configuration for local-db
+cluster.name: {cluster_name}
+node.name: {node_name}
+path.data: {data_path}
+path.logs: {logs_path}
+network.host: 127.0.0.1
+http.port: {port}
+transport.port: {transport_port}
+discovery.type: single-node
+plugins.security.disabled: true
+"#,
+        cluster_name = CLUSTER_NAME,
+        node_name = data_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "opensearch".to_string()),
+        data_path = data_dir.to_string_lossy().replace('\\', "/"),
+        logs_path = logs_dir.to_string_lossy().replace('\\', "/"),
+        port = port,
+        transport_port = port.saturating_add(100),
+    );
+    fs::write(&config_path, config_content).context("Failed to write opensearch.yml")?;
+
+    let jvm_options_dir = config_dir.join("jvm.options.d");
+    fs::create_dir_all(&jvm_options_dir)?;
+    fs::write(
+        jvm_options_dir.join("heap.options"),
+        format!(
+            "# Heap size for local-db\n-Xms{heap}m\n-Xmx{heap}m\n",
+            heap = DEFAULT_HEAP_MB
+        ),
+    )
+    .context("Failed to write heap.options")?;
+
+    Ok(config_path)
+}
+
+/// 启动进程时通过 `OPENSEARCH_PATH_CONF` 传入的配置目录
+pub fn config_dir(db_info: &DatabaseInfo) -> Result<PathBuf> {
+    db_info
+        .config
+        .as_deref()
+        .and_then(|config| Path::new(config).parent())
+        .map(Path::to_path_buf)
+        .context("Config path missing")
+}
@@ -0,0 +1,176 @@
+use crate::core::backup::{mysql_command, postgresql_command};
+use crate::core::connection::encode_resp;
+use crate::core::health::{connect, read_line, DEFAULT_HEALTH_TIMEOUT};
+use crate::core::{mongo, redis, utils, DatabaseInfo, DatabaseType};
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// 安装时未指定密码使用的默认密码，公开已知，不能用于开放到网络的数据库
+pub const DEFAULT_PASSWORD: &str = "admin888";
+
+/// 修改运行中数据库的密码，`db_info` 中保存的是当前（旧）凭据
+///
+/// SurrealDB 和 DuckDB HTTP 服务的密码在启动时传入，由调用方更新 `DatabaseInfo` 后重启生效。
+pub fn change_password(db_info: &DatabaseInfo, new_password: &str) -> Result<()> {
+    match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::SeekDB => {
+            let sql = format!(
+                "ALTER USER CURRENT_USER() IDENTIFIED BY '{}';",
+                escape_mysql(new_password)
+            );
+            run_with_stdin(mysql_command(db_info, "mysql"), &sql)
+        }
+        DatabaseType::PostgreSQL => {
+            let mut command = postgresql_command(db_info, "psql");
+            command.arg("--dbname=postgres").arg("--quiet");
+            let sql = format!(
+                "ALTER ROLE CURRENT_USER WITH PASSWORD '{}';",
+                new_password.replace('\'', "''")
+            );
+            run_with_stdin(command, &sql)
+        }
+        DatabaseType::MongoDB => change_mongodb_password(db_info, new_password),
+        DatabaseType::Redis => change_redis_password(db_info, new_password),
+        DatabaseType::Neo4j => change_neo4j_password(db_info, new_password),
+        DatabaseType::SurrealDB | DatabaseType::DuckDB => Ok(()),
+        DatabaseType::Qdrant => bail!("Qdrant does not support password authentication"),
+        DatabaseType::OpenSearch => {
+            bail!("OpenSearch runs with the security plugin disabled and has no password")
+        }
+        DatabaseType::Cassandra => {
+            bail!("Cassandra runs with AllowAllAuthenticator and has no password")
+        }
+        DatabaseType::InfluxDB => {
+            bail!("InfluxDB authenticates with an API token; manage users in the InfluxDB UI")
+        }
+        DatabaseType::SQLite => bail!("SQLite does not support password authentication"),
+        DatabaseType::Custom => bail!("Custom servers do not support password changes"),
+    }
+}
+
+/// 通过 stdin 传递 SQL，避免新密码出现在进程参数中
+fn run_with_stdin(mut command: Command, input: &str) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+fn escape_mysql(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// 已存在用户时 updateUser，否则创建拥有 root 角色的用户
+fn change_mongodb_password(db_info: &DatabaseInfo, new_password: &str) -> Result<()> {
+    let username = db_info.username.as_deref().unwrap_or("admin");
+    let mut command = Command::new(utils::resolve_db_binary(&db_info.install_path, "mongosh"));
+    command
+        .arg("--quiet")
+        .arg(format!("mongodb://127.0.0.1:{}/admin", db_info.port));
+    // 当前密码通过环境变量传入并在脚本中登录，不出现在进程参数中
+    let mut script = String::new();
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        command.env(mongo::AUTH_PASSWORD_ENV, password);
+        script = format!(
+            "db.getSiblingDB('admin').auth({}, process.env.{});\n",
+            serde_json::to_string(username)?,
+            mongo::AUTH_PASSWORD_ENV
+        );
+    }
+
+    script.push_str(&format!(
+        "const admin = db.getSiblingDB('admin');\n\
+        const user = {user};\n\
+        const pwd = {pwd};\n\
+        if (admin.getUser(user)) {{ admin.updateUser(user, {{ pwd }}); }}\n\
+        else {{ admin.createUser({{ user, pwd, roles: ['root'] }}); }}\n",
+        user = serde_json::to_string(username)?,
+        pwd = serde_json::to_string(new_password)?
+    ));
+    run_with_stdin(command, &script)
+}
+
+/// CONFIG SET 立即生效，同时改写 redis.conf 以便重启后保持
+fn change_redis_password(db_info: &DatabaseInfo, new_password: &str) -> Result<()> {
+    let mut stream = connect(db_info.port, DEFAULT_HEALTH_TIMEOUT)?;
+
+    let mut payload = Vec::new();
+    if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+        payload.extend(encode_resp(&["AUTH", password]));
+    }
+    payload.extend(encode_resp(&["CONFIG", "SET", "requirepass", new_password]));
+    stream.write_all(&payload)?;
+
+    if db_info.password.as_deref().is_some_and(|p| !p.is_empty()) {
+        let reply = read_line(&mut stream)?;
+        if !reply.starts_with("+OK") {
+            bail!("AUTH failed: {}", reply.trim_start_matches('-'));
+        }
+    }
+    let reply = read_line(&mut stream)?;
+    if !reply.starts_with("+OK") {
+        bail!("CONFIG SET failed: {}", reply.trim_start_matches('-'));
+    }
+
+    if let Some(config_path) = db_info.config.as_deref().map(Path::new) {
+        if config_path.exists() {
+            redis::write_directive(
+                config_path,
+                "requirepass ",
+                &format!("requirepass {}", redis::quote(new_password)),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn change_neo4j_password(db_info: &DatabaseInfo, new_password: &str) -> Result<()> {
+    let current_password = db_info.password.as_deref().unwrap_or_default();
+    let response = reqwest::blocking::Client::builder()
+        .timeout(DEFAULT_HEALTH_TIMEOUT)
+        .build()?
+        .post(format!(
+            "http://127.0.0.1:{}/db/system/tx/commit",
+            db_info.port
+        ))
+        .basic_auth(
+            db_info.username.as_deref().unwrap_or("neo4j"),
+            Some(current_password),
+        )
+        .json(&serde_json::json!({
+            "statements": [{
+                "statement": "ALTER CURRENT USER SET PASSWORD FROM $old TO $new",
+                "parameters": { "old": current_password, "new": new_password },
+            }]
+        }))
+        .send()
+        .context("Failed to connect to Neo4j")?;
+
+    if !response.status().is_success() {
+        bail!("Neo4j returned {}", response.status());
+    }
+    let body: serde_json::Value = response.json()?;
+    if let Some(error) = body["errors"].as_array().and_then(|errors| errors.first()) {
+        bail!(
+            "{}",
+            error["message"]
+                .as_str()
+                .unwrap_or("Failed to change password")
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use crate::core::{query, BackendKind, DatabaseInfo, DatabaseType};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// 不随 PostgreSQL 发行的常用扩展：扩展名、Homebrew formula、说明
+///
+/// 扩展文件不在安装目录中时，安装前先按平台下载：macOS 使用 Homebrew formula，
+/// Linux 使用发行版软件包，Windows 解压官方发布的二进制包。
+const DOWNLOADABLE: &[(&str, &str, &str)] = &[
+    (
+        "vector",
+        "pgvector",
+        "vector data type and ivfflat and hnsw access methods",
+    ),
+    (
+        "postgis",
+        "postgis",
+        "PostGIS geometry and geography spatial types and functions",
+    ),
+];
+
+/// PostgreSQL 扩展
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PgExtension {
+    pub name: String,
+    pub default_version: Option<String>,
+    /// 当前数据库中已创建的版本，未创建时为 None
+    pub installed_version: Option<String>,
+    pub comment: Option<String>,
+    /// 扩展文件已在安装目录中，为 false 时安装会先下载
+    pub available: bool,
+}
+
+/// 列出可以安装的扩展：安装目录中已有的扩展（pg_available_extensions）以及可以下载的 pgvector 和 PostGIS
+pub fn list(db_info: &DatabaseInfo, database: Option<&str>) -> Result<Vec<PgExtension>> {
+    ensure_postgresql(db_info)?;
+    let rows = run(
+        db_info,
+        database,
+        "SELECT name, default_version, installed_version, comment \
+         FROM pg_available_extensions ORDER BY name",
+    )?;
+    let mut extensions: Vec<PgExtension> = rows
+        .into_iter()
+        .filter_map(|row| {
+            let mut columns = row.into_iter();
+            Some(PgExtension {
+                name: columns.next().flatten()?,
+                default_version: columns.next().flatten(),
+                installed_version: columns.next().flatten(),
+                comment: columns.next().flatten(),
+                available: true,
+            })
+        })
+        .collect();
+
+    for (name, _, comment) in DOWNLOADABLE {
+        if !extensions.iter().any(|extension| extension.name == *name) {
+            extensions.push(PgExtension {
+                name: name.to_string(),
+                default_version: None,
+                installed_version: None,
+                comment: Some(comment.to_string()),
+                available: false,
+            });
+        }
+    }
+    extensions.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(extensions)
+}
+
+/// 在数据库中创建扩展（`CREATE EXTENSION ... CASCADE`），返回创建后的版本
+///
+/// 扩展文件不在安装目录中时，pgvector 和 PostGIS 先按平台和后端下载，其他扩展直接报错。
+pub fn install(db_info: &DatabaseInfo, database: Option<&str>, name: &str) -> Result<String> {
+    ensure_postgresql(db_info)?;
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        bail!("Invalid extension name: {}", name);
+    }
+
+    if available_version(db_info, database, name)?.is_none() {
+        if !DOWNLOADABLE
+            .iter()
+            .any(|(extension, _, _)| *extension == name)
+        {
+            bail!(
+                "Extension {} is not available in this PostgreSQL installation",
+                name
+            );
+        }
+        download(db_info, name, server_major_version(db_info)?)
+            .with_context(|| format!("Failed to download extension {}", name))?;
+        if available_version(db_info, database, name)?.is_none() {
+            bail!(
+                "Extension {} was downloaded but is not visible to PostgreSQL {}",
+                name,
+                db_info.version
+            );
+        }
+    }
+
+    run(
+        db_info,
+        database,
+        &format!("CREATE EXTENSION IF NOT EXISTS \"{}\" CASCADE", name),
+    )?;
+    available_version(db_info, database, name)?
+        .flatten()
+        .context("The extension was not created")
+}
+
+fn ensure_postgresql(db_info: &DatabaseInfo) -> Result<()> {
+    if db_info.db_type != DatabaseType::PostgreSQL {
+        bail!(
+            "Extensions are not supported for {}",
+            db_info.db_type.display_name()
+        );
+    }
+    Ok(())
+}
+
+fn run(
+    db_info: &DatabaseInfo,
+    database: Option<&str>,
+    sql: &str,
+) -> Result<Vec<Vec<Option<String>>>> {
+    query::execute(
+        db_info,
+        database,
+        sql,
+        query::MAX_ROW_LIMIT,
+        query::DEFAULT_QUERY_TIMEOUT,
+    )
+    .with_context(|| format!("Failed to run `{}`", sql))
+    .map(|result| result.rows)
+}
+
+/// 扩展在安装目录中时返回已创建的版本（未创建为 `Some(None)`），不在安装目录中时返回 None
+fn available_version(
+    db_info: &DatabaseInfo,
+    database: Option<&str>,
+    name: &str,
+) -> Result<Option<Option<String>>> {
+    let rows = run(
+        db_info,
+        database,
+        &format!(
+            "SELECT installed_version FROM pg_available_extensions WHERE name = '{}'",
+            name
+        ),
+    )?;
+    Ok(rows
+        .into_iter()
+        .next()
+        .map(|row| row.into_iter().next().flatten()))
+}
+
+/// 服务器的主版本号，如 16；发行版软件包和 Windows 二进制包按主版本区分
+fn server_major_version(db_info: &DatabaseInfo) -> Result<u32> {
+    run(
+        db_info,
+        None,
+        "SELECT current_setting('server_version_num')",
+    )?
+    .first()
+    .and_then(|row| row.first().cloned().flatten())
+    .and_then(|version| version.parse::<u32>().ok())
+    .map(|version| version / 10000)
+    .context("Failed to read the PostgreSQL version")
+}
+
+/// 按后端把扩展文件下载到 PostgreSQL 的安装目录
+fn download(db_info: &DatabaseInfo, name: &str, major: u32) -> Result<()> {
+    match db_info.backend {
+        BackendKind::Native | BackendKind::LaunchAgent => download_native(db_info, name, major),
+        BackendKind::Package if cfg!(target_os = "linux") => {
+            crate::core::distro::install_postgresql_extension(name, major)
+        }
+        BackendKind::Package => bail!(
+            "Install {} with Stack Builder, which comes with the PostgreSQL installer",
+            name
+        ),
+        BackendKind::Docker => bail!(
+            "Extensions cannot be added to the container; use an image that bundles {}, such as {}",
+            name,
+            match name {
+                "vector" => "pgvector/pgvector",
+                _ => "postgis/postgis",
+            }
+        ),
+        _ => bail!(
+            "{} is not managed by this app; install the extension with the tools that installed PostgreSQL",
+            db_info.name
+        ),
+    }
+}
+
+/// Homebrew 安装的 PostgreSQL 从共享目录加载扩展，安装对应的 formula 即可
+#[cfg(target_os = "macos")]
+fn download_native(_db_info: &DatabaseInfo, name: &str, _major: u32) -> Result<()> {
+    let formula = DOWNLOADABLE
+        .iter()
+        .find(|(extension, _, _)| *extension == name)
+        .map(|(_, formula, _)| *formula)
+        .unwrap_or(name);
+    crate::core::macos::install_homebrew_formula(formula)
+}
+
+#[cfg(target_os = "windows")]
+fn download_native(db_info: &DatabaseInfo, name: &str, major: u32) -> Result<()> {
+    crate::core::windows::install_postgresql_extension(db_info, name, major)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn download_native(_db_info: &DatabaseInfo, name: &str, _major: u32) -> Result<()> {
+    bail!("Extension {} cannot be downloaded on this platform", name)
+}
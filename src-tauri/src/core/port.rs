@@ -0,0 +1,112 @@
+use crate::core::{DatabaseInfo, DatabaseType};
+use anyhow::{Context, Result};
+use std::fs;
+use std::net::TcpListener;
+use std::path::Path;
+
+/// 检查本地端口是否可以监听
+pub fn is_port_available(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// 将配置文件中的端口改写为 `new_port`，返回改写前的内容用于回滚
+///
+/// 支持 `key = value`（my.cnf、postgresql.conf、TOML）、`key: value`（YAML）、
+/// `key value`（redis.conf）以及 SurrealDB 的 `SURREAL_BIND=host:port`。
+/// 未配置文件的数据库通过启动参数传递端口，无需改写。
+pub fn rewrite_config_port(db_info: &DatabaseInfo, new_port: u16) -> Result<Option<String>> {
+    let config_path = match db_info.config.as_deref() {
+        Some(path) if Path::new(path).exists() => Path::new(path),
+        _ => return Ok(None),
+    };
+
+    let original = fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+
+    let mut rewritten = String::with_capacity(original.len() + 16);
+    let mut replaced = false;
+    for line in original.lines() {
+        match rewrite_port_line(line, new_port) {
+            Some(line) => {
+                rewritten.push_str(&line);
+                replaced = true;
+            }
+            None => rewritten.push_str(line),
+        }
+        rewritten.push('\n');
+    }
+
+    // 配置中没有端口项时按数据库类型补充
+    if !replaced {
+        match db_info.db_type {
+            DatabaseType::Redis => rewritten.push_str(&format!("port {}\n", new_port)),
+            DatabaseType::MongoDB => {
+                rewritten = match rewritten.find("net:\n") {
+                    Some(index) => {
+                        let at = index + "net:\n".len();
+                        format!(
+                            "{}  port: {}\n{}",
+                            &rewritten[..at],
+                            new_port,
+                            &rewritten[at..]
+                        )
+                    }
+                    None => format!("{}net:\n  port: {}\n", rewritten, new_port),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    fs::write(config_path, rewritten)
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    Ok(Some(original))
+}
+
+/// 回滚 [`rewrite_config_port`] 的修改
+pub fn restore_config(db_info: &DatabaseInfo, original: &str) -> Result<()> {
+    if let Some(path) = db_info.config.as_deref() {
+        fs::write(path, original).with_context(|| format!("Failed to restore {}", path))?;
+    }
+    Ok(())
+}
+
+fn rewrite_port_line(line: &str, new_port: u16) -> Option<String> {
+    let content = line.trim_start();
+    let indent = &line[..line.len() - content.len()];
+    let key_end = content.find([' ', '\t', '=', ':'])?;
+    let key = &content[..key_end];
+    let value_start = key_end
+        + content[key_end..]
+            .find(|c: char| !matches!(c, ' ' | '\t' | '=' | ':'))
+            .unwrap_or(content.len() - key_end);
+    let separator = &content[key_end..value_start];
+    // 保留值之后的行内注释
+    let value = &content[value_start..];
+    let value_end = value.find([' ', '\t']).unwrap_or(value.len());
+    let (value, rest) = value.split_at(value_end);
+
+    let new_value = match key {
+        "port" | "http_port" | "http.port" | "native_transport_port"
+            if value.parse::<u16>().is_ok() =>
+        {
+            new_port.to_string()
+        }
+        // Qdrant 约定 gRPC 端口为 HTTP 端口 + 1
+        "grpc_port" if value.parse::<u16>().is_ok() => new_port.saturating_add(1).to_string(),
+        // OpenSearch 约定节点通信端口为 HTTP 端口 + 100
+        "transport.port" if value.parse::<u16>().is_ok() => {
+            new_port.saturating_add(100).to_string()
+        }
+        "SURREAL_BIND" => {
+            let host = value.rsplit_once(':').map(|(host, _)| host)?;
+            format!("{}:{}", host, new_port)
+        }
+        _ => return None,
+    };
+
+    Some(format!(
+        "{}{}{}{}{}",
+        indent, key, separator, new_value, rest
+    ))
+}
@@ -0,0 +1,123 @@
+use crate::core::{port, AppError, BackendKind, DatabaseType};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::Disks;
+
+const MB: u64 = 1024 * 1024;
+
+/// 离线发行包解压后大小相对压缩包的估算倍数
+const ARCHIVE_EXPANSION: u64 = 4;
+
+/// 除程序文件外为初始化数据和日志预留的空间
+const DATA_RESERVE: u64 = 256 * MB;
+
+/// 安装前检查的参数
+pub struct InstallCheck<'a> {
+    pub db_type: &'a DatabaseType,
+    pub backend: BackendKind,
+    pub storage_path: &'a Path,
+    /// 安装使用的端口，未指定时使用默认端口
+    pub port: Option<u16>,
+    /// 离线安装使用的本地发行包
+    pub archive: Option<&'a Path>,
+}
+
+/// 下载前检查存储目录所在磁盘的剩余空间和端口是否可用，避免安装到一半才失败
+pub fn check_install(check: &InstallCheck<'_>) -> Result<()> {
+    // Docker 镜像和系统软件包不安装到存储目录
+    if matches!(
+        check.backend,
+        BackendKind::Native | BackendKind::LaunchAgent
+    ) {
+        check_disk_space(check)?;
+    }
+    check_ports(check)
+}
+
+/// 安装需要的磁盘空间：离线安装按发行包大小估算，在线安装按数据库类型的经验值
+fn required_space(check: &InstallCheck<'_>) -> Result<u64> {
+    let program_size = match check.archive {
+        Some(archive) => {
+            let size = fs::metadata(archive)
+                .with_context(|| format!("Failed to read {}", archive.display()))?
+                .len();
+            size * ARCHIVE_EXPANSION
+        }
+        None => estimated_download_size(check.db_type) * ARCHIVE_EXPANSION,
+    };
+    Ok(program_size + DATA_RESERVE)
+}
+
+/// 各数据库发行包的大致大小
+fn estimated_download_size(db_type: &DatabaseType) -> u64 {
+    let size_mb = match db_type {
+        DatabaseType::MySQL => 500,
+        DatabaseType::MariaDB => 350,
+        DatabaseType::OpenSearch => 900,
+        DatabaseType::Cassandra | DatabaseType::Neo4j => 150,
+        DatabaseType::MongoDB => 120,
+        DatabaseType::PostgreSQL | DatabaseType::InfluxDB | DatabaseType::SeekDB => 100,
+        DatabaseType::Qdrant | DatabaseType::SurrealDB => 60,
+        DatabaseType::Redis | DatabaseType::DuckDB => 20,
+        DatabaseType::SQLite => 5,
+        // 自定义服务使用已有的二进制，不需要下载
+        DatabaseType::Custom => 0,
+    };
+    size_mb * MB
+}
+
+fn check_disk_space(check: &InstallCheck<'_>) -> Result<()> {
+    let required = required_space(check)?;
+    let Some(available) = available_space(check.storage_path) else {
+        // 找不到对应的磁盘时不阻止安装
+        return Ok(());
+    };
+    if available < required {
+        return Err(AppError::InsufficientDiskSpace {
+            path: check.storage_path.to_string_lossy().to_string(),
+            required,
+            available,
+        })
+        .with_context(|| format!("Cannot install {}", check.db_type.display_name()));
+    }
+    Ok(())
+}
+
+/// `path` 所在磁盘的剩余空间，取挂载点最长匹配的磁盘
+pub fn available_space(path: &Path) -> Option<u64> {
+    let path = existing_ancestor(path)?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// 存储目录可能尚未创建，向上查找第一个存在的目录
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .and_then(|ancestor| ancestor.canonicalize().ok())
+}
+
+fn check_ports(check: &InstallCheck<'_>) -> Result<()> {
+    // SQLite 没有服务进程
+    if *check.db_type == DatabaseType::SQLite {
+        return Ok(());
+    }
+    let port = check.port.unwrap_or_else(|| check.db_type.default_port());
+    let mut ports = vec![port];
+    // Qdrant 同时占用 HTTP 端口和其后的 gRPC 端口
+    if *check.db_type == DatabaseType::Qdrant {
+        ports.push(port.saturating_add(1));
+    }
+    if let Some(port) = ports
+        .into_iter()
+        .find(|&port| !port::is_port_available(port))
+    {
+        return Err(AppError::PortInUse { port }.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::core::{ConfigPreset, DatabaseType};
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use sysinfo::System;
+
+const MB: u64 = 1024 * 1024;
+
+/// 预设占用的内存比例（分母）和最大连接数
+fn preset_share(preset: ConfigPreset) -> (u64, u32) {
+    match preset {
+        ConfigPreset::Small => (16, 50),
+        ConfigPreset::Medium => (8, 150),
+        ConfigPreset::Large => (4, 500),
+    }
+}
+
+/// 按本机内存计算预设对应的配置覆盖，键的写法与 [`crate::core::config_file::apply_overrides`] 一致
+pub fn preset_overrides(
+    db_type: &DatabaseType,
+    preset: ConfigPreset,
+) -> Result<HashMap<String, String>> {
+    let mut sys = System::new();
+    sys.refresh_memory();
+    let (share, max_connections) = preset_share(preset);
+    // 至少分配 128MB，避免内存很小的机器上配置过小无法启动
+    let memory_mb = (sys.total_memory() / share / MB).max(128);
+
+    let overrides: Vec<(&str, String)> = match db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB => vec![
+            ("mysqld.innodb_buffer_pool_size", format!("{}M", memory_mb)),
+            ("mysqld.max_connections", max_connections.to_string()),
+        ],
+        DatabaseType::PostgreSQL => vec![
+            ("shared_buffers", format!("{}MB", memory_mb)),
+            // 操作系统缓存可以使用的内存，通常为 shared_buffers 的 3 倍
+            ("effective_cache_size", format!("{}MB", memory_mb * 3)),
+            (
+                "work_mem",
+                format!("{}MB", (memory_mb / max_connections as u64).max(4)),
+            ),
+            ("max_connections", max_connections.to_string()),
+        ],
+        DatabaseType::Redis => vec![
+            ("maxmemory", format!("{}mb", memory_mb)),
+            ("maxclients", (max_connections * 20).to_string()),
+        ],
+        DatabaseType::MongoDB => vec![(
+            "storage.wiredTiger.engineConfig.cacheSizeGB",
+            format!("{:.2}", (memory_mb as f64 / 1024.0).max(0.25)),
+        )],
+        _ => bail!(
+            "Configuration presets are not supported for {}",
+            db_type.display_name()
+        ),
+    };
+
+    Ok(overrides
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect())
+}
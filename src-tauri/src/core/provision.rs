@@ -0,0 +1,226 @@
+use crate::core::backup::{mysql_command, postgresql_command};
+use crate::core::{health, mongo};
+use crate::core::{DatabaseInfo, DatabaseStatus, DatabaseType};
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// 数据库名和用户名的最大长度，取 PostgreSQL 标识符的上限
+const MAX_NAME_LEN: usize = 63;
+
+/// 安装后等待实例可以连接的最长时间
+const READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 在实例上创建一个数据库，MySQL 使用 utf8mb4 字符集
+pub fn create_database(db_info: &DatabaseInfo, name: &str) -> Result<()> {
+    ensure_running(db_info)?;
+    check_name("Database name", name)?;
+
+    match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::SeekDB => run_mysql(
+            db_info,
+            &format!(
+                "CREATE DATABASE {} CHARACTER SET utf8mb4;",
+                mysql_ident(name)
+            ),
+        ),
+        DatabaseType::PostgreSQL => run_psql(
+            db_info,
+            "postgres",
+            &format!("CREATE DATABASE {};", postgresql_ident(name)),
+        ),
+        DatabaseType::MongoDB => {
+            bail!("MongoDB creates a database when data is first written to it")
+        }
+        _ => bail!(
+            "Creating databases is not supported for {}",
+            db_info.db_type.display_name()
+        ),
+    }
+}
+
+/// 创建可登录的用户，并授予 `grants` 中每个数据库的全部权限
+///
+/// MongoDB 的用户建在 admin 库，对每个数据库授予 readWrite 角色。
+pub fn create_user(
+    db_info: &DatabaseInfo,
+    username: &str,
+    password: &str,
+    grants: &[String],
+) -> Result<()> {
+    ensure_running(db_info)?;
+    check_name("Username", username)?;
+    if password.is_empty() {
+        bail!("A password is required");
+    }
+    for database in grants {
+        check_name("Database name", database)?;
+    }
+
+    match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::SeekDB => {
+            let user = format!("{}@'%'", mysql_string(username));
+            let mut sql = format!(
+                "CREATE USER {} IDENTIFIED BY {};\n",
+                user,
+                mysql_string(password)
+            );
+            for database in grants {
+                sql.push_str(&format!(
+                    "GRANT ALL PRIVILEGES ON {}.* TO {};\n",
+                    mysql_ident(database),
+                    user
+                ));
+            }
+            run_mysql(db_info, &sql)
+        }
+        DatabaseType::PostgreSQL => {
+            let role = postgresql_ident(username);
+            run_psql(
+                db_info,
+                "postgres",
+                &format!(
+                    "CREATE ROLE {} LOGIN PASSWORD {};",
+                    role,
+                    postgresql_string(password)
+                ),
+            )?;
+            // PostgreSQL 15 起 public 模式默认不允许其他用户建表，需要在各库中单独授权
+            for database in grants {
+                run_psql(
+                    db_info,
+                    database,
+                    &format!(
+                        "GRANT ALL PRIVILEGES ON DATABASE {} TO {};\n\
+                        GRANT ALL ON SCHEMA public TO {};",
+                        postgresql_ident(database),
+                        role,
+                        role
+                    ),
+                )?;
+            }
+            Ok(())
+        }
+        DatabaseType::MongoDB => mongo::create_user(db_info, username, password, grants),
+        _ => bail!(
+            "Creating users is not supported for {}",
+            db_info.db_type.display_name()
+        ),
+    }
+}
+
+/// 为刚初始化的 MySQL / MariaDB 实例设置保存的凭据
+///
+/// `--initialize-insecure` 和 mariadb-install-db 初始化后只有空密码的 root，这里以 root 登录并设置保存的密码；
+/// 保存的用户名不是 root 时再创建该用户并授予全部权限，root 使用同一密码。没有保存密码时不做修改。
+pub fn initialize_mysql_credentials(db_info: &DatabaseInfo) -> Result<()> {
+    ensure_running(db_info)?;
+    let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) else {
+        return Ok(());
+    };
+    health::wait_until_ready(db_info, READY_TIMEOUT)?;
+    let username = db_info
+        .username
+        .as_deref()
+        .filter(|u| !u.is_empty())
+        .unwrap_or("root");
+
+    let password = mysql_string(password);
+    let mut sql = format!("ALTER USER CURRENT_USER() IDENTIFIED BY {};\n", password);
+    if username != "root" {
+        check_name("Username", username)?;
+        let user = format!("{}@'%'", mysql_string(username));
+        sql.push_str(&format!(
+            "CREATE USER IF NOT EXISTS {user} IDENTIFIED BY {password};\n\
+            GRANT ALL PRIVILEGES ON *.* TO {user} WITH GRANT OPTION;\n",
+            user = user,
+            password = password
+        ));
+    }
+
+    let root = DatabaseInfo {
+        username: Some("root".to_string()),
+        password: None,
+        ..db_info.clone()
+    };
+    run_mysql(&root, &sql).context("Failed to set the initial credentials")
+}
+
+fn ensure_running(db_info: &DatabaseInfo) -> Result<()> {
+    if db_info.status != DatabaseStatus::Running {
+        bail!("{} must be running to make changes", db_info.name);
+    }
+    Ok(())
+}
+
+/// 名称只允许字母、数字、下划线和连字符，避免各数据库对特殊字符的不同处理
+fn check_name(label: &str, name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        bail!("{} must be 1-{} characters", label, MAX_NAME_LEN);
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        bail!(
+            "{} may only contain letters, digits, underscores and hyphens: {}",
+            label,
+            name
+        );
+    }
+    Ok(())
+}
+
+pub(super) fn mysql_ident(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+pub(super) fn mysql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+pub(super) fn postgresql_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub(super) fn postgresql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// 通过标准输入执行 SQL，密码不会出现在客户端的命令行参数中
+pub(super) fn run_mysql(db_info: &DatabaseInfo, sql: &str) -> Result<()> {
+    run_with_input(mysql_command(db_info, "mysql"), sql)
+}
+
+/// psql 从标准输入读取时每条语句单独提交，CREATE DATABASE 不能放在事务中执行
+pub(super) fn run_psql(db_info: &DatabaseInfo, database: &str, sql: &str) -> Result<()> {
+    let mut command = postgresql_command(db_info, "psql");
+    command
+        .arg(format!("--dbname={}", database))
+        .arg("--no-psqlrc")
+        .arg("--quiet")
+        .arg("--set=ON_ERROR_STOP=1");
+    run_with_input(command, sql)
+}
+
+fn run_with_input(mut command: Command, input: &str) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run the database client")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .context("Failed to send statements to the database client")?;
+    }
+    let output = child
+        .wait_with_output()
+        .context("Failed to wait for the database client")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
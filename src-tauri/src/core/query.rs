@@ -0,0 +1,319 @@
+use crate::core::backup::{mysql_command, postgresql_command};
+use crate::core::{DatabaseInfo, DatabaseStatus, DatabaseType, QueryResult};
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// 未指定时最多返回的行数
+pub const DEFAULT_ROW_LIMIT: usize = 1000;
+
+/// 允许设置的最大行数，避免一次把大表读入界面
+pub const MAX_ROW_LIMIT: usize = 10_000;
+
+/// 未指定时查询的超时时间
+pub const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// MySQL 查询后追加的 `ROW_COUNT()` 结果列名，用于取得影响的行数
+const MYSQL_ROW_COUNT_COLUMN: &str = "local_db_row_count";
+
+/// psql 输出使用的字段、记录分隔符和 NULL 占位符，均为数据中几乎不会出现的控制字符
+const PSQL_FIELD_SEPARATOR: char = '\x1f';
+const PSQL_RECORD_SEPARATOR: char = '\x1e';
+const PSQL_NULL: &str = "\x1d";
+
+/// 通过数据库自带的命令行客户端执行一条 SQL，超过 `row_limit` 的行被丢弃
+///
+/// 只支持 MySQL 协议的数据库和 PostgreSQL。SQL 通过 stdin 传给客户端，每次执行一条语句，多条语句的结果集会依次拼接。
+pub fn execute(
+    db_info: &DatabaseInfo,
+    database: Option<&str>,
+    sql: &str,
+    row_limit: usize,
+    timeout: Duration,
+) -> Result<QueryResult> {
+    if db_info.status != DatabaseStatus::Running {
+        bail!("{} must be running to run queries", db_info.name);
+    }
+    let sql = sql.trim().trim_end_matches(';').trim_end();
+    if sql.is_empty() {
+        bail!("The query is empty");
+    }
+    let row_limit = row_limit.clamp(1, MAX_ROW_LIMIT);
+
+    let started = Instant::now();
+    let mut result = match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::SeekDB => {
+            execute_mysql(db_info, database, sql, row_limit, timeout)?
+        }
+        DatabaseType::PostgreSQL => execute_postgresql(db_info, database, sql, row_limit, timeout)?,
+        _ => bail!(
+            "Queries are not supported for {}",
+            db_info.db_type.display_name()
+        ),
+    };
+    result.elapsed_ms = started.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+/// `mysql --batch` 以制表符分隔字段，每行一条记录，第一行为列名
+fn execute_mysql(
+    db_info: &DatabaseInfo,
+    database: Option<&str>,
+    sql: &str,
+    row_limit: usize,
+    timeout: Duration,
+) -> Result<QueryResult> {
+    let mut command = mysql_command(db_info, "mysql");
+    command
+        .arg("--batch")
+        .arg("--default-character-set=utf8mb4");
+    if let Some(database) = database.filter(|db| !db.is_empty()) {
+        command.arg(format!("--database={}", database));
+    }
+    let input = format!(
+        "{};\nSELECT ROW_COUNT() AS {};\n",
+        sql, MYSQL_ROW_COUNT_COLUMN
+    );
+
+    // 列名一行，加上末尾 ROW_COUNT() 的两行和判断是否超出的一行
+    let (mut lines, _) = run(command, Some(&input), b'\n', row_limit + 4, timeout)?;
+    let mut affected_rows = None;
+    if lines.len() >= 2 && lines[lines.len() - 2] == MYSQL_ROW_COUNT_COLUMN {
+        let count = lines.pop().unwrap_or_default();
+        lines.pop();
+        // 查询语句的 ROW_COUNT() 为 -1
+        affected_rows = count.trim().parse::<u64>().ok();
+    }
+
+    let mut lines = lines.into_iter();
+    let columns: Vec<String> = match lines.next() {
+        Some(header) => header.split('\t').map(unescape_mysql).collect(),
+        None => Vec::new(),
+    };
+    let mut rows: Vec<Vec<Option<String>>> = lines
+        .map(|line| {
+            line.split('\t')
+                .map(|value| (value != "NULL").then(|| unescape_mysql(value)))
+                .collect()
+        })
+        .collect();
+    let truncated = rows.len() > row_limit;
+    rows.truncate(row_limit);
+
+    Ok(QueryResult {
+        columns,
+        rows,
+        affected_rows,
+        truncated,
+        elapsed_ms: 0,
+    })
+}
+
+/// 还原 `mysql --batch` 对字段中特殊字符的转义
+pub(super) fn unescape_mysql(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('0') => result.push('\0'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// psql 以非对齐格式输出，第一条记录为列名；没有结果集的语句只输出命令标签（如 `INSERT 0 2`）
+fn execute_postgresql(
+    db_info: &DatabaseInfo,
+    database: Option<&str>,
+    sql: &str,
+    row_limit: usize,
+    timeout: Duration,
+) -> Result<QueryResult> {
+    let mut command = postgresql_command(db_info, "psql");
+    command
+        .arg(format!(
+            "--dbname={}",
+            database.filter(|db| !db.is_empty()).unwrap_or("postgres")
+        ))
+        .arg("--no-psqlrc")
+        .arg("--no-align")
+        .arg(format!("--field-separator={}", PSQL_FIELD_SEPARATOR))
+        .arg(format!("--record-separator={}", PSQL_RECORD_SEPARATOR))
+        .arg("--pset=footer=off")
+        .arg(format!("--pset=null={}", PSQL_NULL))
+        .arg("--set=ON_ERROR_STOP=1");
+
+    // SQL 从 stdin 传入，不出现在进程列表中
+    let (mut records, complete) = run(
+        command,
+        Some(sql),
+        PSQL_RECORD_SEPARATOR as u8,
+        row_limit + 2,
+        timeout,
+    )?;
+    // 最后一条记录以换行结束
+    if complete {
+        if let Some(last) = records.last_mut() {
+            if last.ends_with('\n') {
+                last.pop();
+            }
+        }
+    }
+
+    if let [record] = records.as_slice() {
+        if let Some(affected_rows) = command_tag(record) {
+            return Ok(QueryResult {
+                columns: Vec::new(),
+                rows: Vec::new(),
+                affected_rows,
+                truncated: false,
+                elapsed_ms: 0,
+            });
+        }
+    }
+
+    let mut records = records.into_iter();
+    let columns: Vec<String> = match records.next() {
+        Some(header) => header
+            .split(PSQL_FIELD_SEPARATOR)
+            .map(String::from)
+            .collect(),
+        None => Vec::new(),
+    };
+    let mut rows: Vec<Vec<Option<String>>> = records
+        .map(|record| {
+            record
+                .split(PSQL_FIELD_SEPARATOR)
+                .map(|value| (value != PSQL_NULL).then(|| value.to_string()))
+                .collect()
+        })
+        .collect();
+    let truncated = rows.len() > row_limit;
+    rows.truncate(row_limit);
+
+    Ok(QueryResult {
+        columns,
+        rows,
+        affected_rows: None,
+        truncated,
+        elapsed_ms: 0,
+    })
+}
+
+/// 解析命令标签，返回其中的影响行数；不是命令标签时返回 None
+///
+/// `UPDATE 3`、`INSERT 0 2` 的最后一个数字为影响的行数，`CREATE TABLE` 等没有行数。
+fn command_tag(record: &str) -> Option<Option<u64>> {
+    let mut words = record.split(' ');
+    let verb = words.next()?;
+    if verb.is_empty() || !verb.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let mut count = None;
+    for word in words {
+        if word.chars().all(|c| c.is_ascii_uppercase()) && count.is_none() {
+            continue;
+        }
+        count = Some(word.parse::<u64>().ok()?);
+    }
+    Some(count)
+}
+
+/// 运行客户端并按 `separator` 读取输出记录，最多读取 `max_records` 条
+///
+/// 读满后结束进程，返回的布尔值表示是否读到了输出末尾。超时后结束进程并返回错误。
+pub(crate) fn run(
+    mut command: Command,
+    input: Option<&str>,
+    separator: u8,
+    max_records: usize,
+    timeout: Duration,
+) -> Result<(Vec<String>, bool)> {
+    let mut child = command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run the database client")?;
+    // 输入、stdout 和 stderr 分别在线程中读写，避免任一管道写满后互相等待
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        let input = input.to_string();
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+    let stderr_reader = child.stderr.take().map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut stderr = String::new();
+            let _ = pipe.read_to_string(&mut stderr);
+            stderr
+        })
+    });
+
+    let stdout = child
+        .stdout
+        .take()
+        .context("Failed to read client output")?;
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        let mut records = Vec::new();
+        let mut complete = false;
+        while records.len() < max_records {
+            let mut record = Vec::new();
+            match reader.read_until(separator, &mut record) {
+                Ok(0) | Err(_) => {
+                    complete = true;
+                    break;
+                }
+                Ok(_) => {
+                    if record.last() == Some(&separator) {
+                        record.pop();
+                    }
+                    records.push(String::from_utf8_lossy(&record).to_string());
+                }
+            }
+        }
+        let _ = sender.send((records, complete));
+    });
+
+    let (records, complete) = match receiver.recv_timeout(timeout) {
+        Ok(output) => output,
+        Err(_) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("Query timed out after {}s", timeout.as_secs());
+        }
+    };
+    if !complete {
+        // 超出行数限制，不再读取剩余的输出
+        let _ = child.kill();
+        let _ = child.wait();
+        return Ok((records, false));
+    }
+
+    let status = child
+        .wait()
+        .context("Failed to wait for the database client")?;
+    if !status.success() {
+        let stderr = stderr_reader
+            .and_then(|reader| reader.join().ok())
+            .unwrap_or_default();
+        bail!("{}", stderr.trim());
+    }
+    Ok((records, true))
+}
@@ -0,0 +1,172 @@
+use crate::core::{config_file, query, slow_query, DatabaseInfo, DatabaseType};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 返回的查询数，按总耗时从高到低
+const TOP_QUERIES: usize = 50;
+
+/// 一类查询（参数替换为占位符后相同的语句）的累计统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryStat {
+    /// 规范化后的语句，参数为 `$1` 或 `?`
+    pub query: String,
+    pub database: Option<String>,
+    pub calls: u64,
+    pub total_time_ms: f64,
+    pub mean_time_ms: f64,
+    /// 返回或影响的行数合计
+    pub rows: u64,
+}
+
+/// 查询统计的读取结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryStats {
+    /// 统计已启用，为 false 时 `entries` 为空
+    pub available: bool,
+    /// 已修改配置，需要重启数据库后才开始统计
+    pub restart_required: bool,
+    /// 总耗时最高的查询在前
+    pub entries: Vec<QueryStat>,
+}
+
+/// 读取总耗时最高的查询：PostgreSQL 使用 pg_stat_statements，MySQL 使用 performance_schema 的摘要表
+///
+/// 统计未启用时修改配置并返回 `restart_required`：PostgreSQL 通过 `ALTER SYSTEM` 预加载
+/// pg_stat_statements，MariaDB 在配置文件中开启 performance_schema（MySQL 默认开启）。
+pub fn collect(storage_path: &Path, db_info: &DatabaseInfo) -> Result<QueryStats> {
+    match db_info.db_type {
+        DatabaseType::PostgreSQL => collect_postgresql(db_info),
+        DatabaseType::MySQL | DatabaseType::MariaDB => collect_mysql(storage_path, db_info),
+        _ => bail!(
+            "Query statistics are not supported for {}",
+            db_info.db_type.display_name()
+        ),
+    }
+}
+
+fn run(db_info: &DatabaseInfo, sql: &str, row_limit: usize) -> Result<Vec<Vec<Option<String>>>> {
+    query::execute(db_info, None, sql, row_limit, query::DEFAULT_QUERY_TIMEOUT)
+        .with_context(|| format!("Failed to run `{}`", sql))
+        .map(|result| result.rows)
+}
+
+/// 重启后才会生效的结果
+fn restart_required() -> QueryStats {
+    QueryStats {
+        available: false,
+        restart_required: true,
+        entries: Vec::new(),
+    }
+}
+
+fn collect_postgresql(db_info: &DatabaseInfo) -> Result<QueryStats> {
+    let rows = run(
+        db_info,
+        "SELECT current_setting('shared_preload_libraries'), \
+         current_setting('server_version_num')",
+        1,
+    )?;
+    let value = |index: usize| {
+        rows.first()
+            .and_then(|row| row.get(index).cloned().flatten())
+            .unwrap_or_default()
+    };
+
+    let mut libraries: Vec<String> = value(0)
+        .split(',')
+        .map(|lib| lib.trim().trim_matches('"').to_string())
+        .filter(|lib| !lib.is_empty())
+        .collect();
+    if !libraries.iter().any(|lib| lib == "pg_stat_statements") {
+        libraries.push("pg_stat_statements".to_string());
+        let list = libraries
+            .iter()
+            .map(|lib| format!("'{}'", lib.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        run(
+            db_info,
+            &format!("ALTER SYSTEM SET shared_preload_libraries = {}", list),
+            1,
+        )?;
+        return Ok(restart_required());
+    }
+
+    run(
+        db_info,
+        "CREATE EXTENSION IF NOT EXISTS pg_stat_statements",
+        1,
+    )
+    .context("pg_stat_statements is not available in this PostgreSQL installation")?;
+    // PostgreSQL 13 起耗时列改名为 *_exec_time
+    let (total, mean) = match value(1).parse::<u32>() {
+        Ok(version) if version < 130000 => ("total_time", "mean_time"),
+        _ => ("total_exec_time", "mean_exec_time"),
+    };
+    let rows = run(
+        db_info,
+        &format!(
+            "SELECT s.query, d.datname, s.calls, s.{total}, s.{mean}, s.rows \
+             FROM pg_stat_statements s LEFT JOIN pg_database d ON d.oid = s.dbid \
+             ORDER BY s.{total} DESC LIMIT {limit}",
+            total = total,
+            mean = mean,
+            limit = TOP_QUERIES
+        ),
+        TOP_QUERIES,
+    )?;
+    Ok(QueryStats {
+        available: true,
+        restart_required: false,
+        entries: rows.iter().filter_map(|row| parse_row(row)).collect(),
+    })
+}
+
+fn collect_mysql(storage_path: &Path, db_info: &DatabaseInfo) -> Result<QueryStats> {
+    let enabled = run(db_info, "SELECT @@GLOBAL.performance_schema", 1)?
+        .first()
+        .and_then(|row| row.first().cloned().flatten())
+        .is_some_and(|value| value == "1" || value.eq_ignore_ascii_case("ON"));
+    if !enabled {
+        let overrides =
+            HashMap::from([("mysqld.performance_schema".to_string(), "ON".to_string())]);
+        config_file::apply_overrides(storage_path, db_info, &overrides).context(
+            "performance_schema is disabled and could not be enabled in the config file",
+        )?;
+        return Ok(restart_required());
+    }
+
+    // 计时单位为皮秒
+    let rows = run(
+        db_info,
+        &format!(
+            "SELECT DIGEST_TEXT, SCHEMA_NAME, COUNT_STAR, SUM_TIMER_WAIT / 1000000000, \
+             AVG_TIMER_WAIT / 1000000000, SUM_ROWS_SENT + SUM_ROWS_AFFECTED \
+             FROM performance_schema.events_statements_summary_by_digest \
+             WHERE DIGEST_TEXT IS NOT NULL ORDER BY SUM_TIMER_WAIT DESC LIMIT {}",
+            TOP_QUERIES
+        ),
+        TOP_QUERIES,
+    )?;
+    Ok(QueryStats {
+        available: true,
+        restart_required: false,
+        entries: rows.iter().filter_map(|row| parse_row(row)).collect(),
+    })
+}
+
+/// 两种数据库的查询列顺序一致：语句、数据库、次数、总耗时、平均耗时、行数
+fn parse_row(row: &[Option<String>]) -> Option<QueryStat> {
+    let text = |index: usize| row.get(index).cloned().flatten();
+    let number = |index: usize| text(index).and_then(|value| value.parse::<f64>().ok());
+    Some(QueryStat {
+        query: slow_query::normalize(&text(0)?),
+        database: text(1).filter(|db| !db.is_empty()),
+        calls: number(2)? as u64,
+        total_time_ms: number(3)?,
+        mean_time_ms: number(4).unwrap_or_default(),
+        rows: number(5).unwrap_or_default() as u64,
+    })
+}
@@ -0,0 +1,363 @@
+use crate::core::connection::encode_resp;
+use crate::core::health::connect;
+use crate::core::{DatabaseInfo, DatabaseStatus, RedisKeyInfo, RedisKeyPage, RedisReply};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::time::Duration;
+
+/// 命令的默认超时时间
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 浏览键时每次 SCAN 的默认 COUNT
+pub const DEFAULT_SCAN_COUNT: usize = 100;
+
+/// 进入订阅或监视模式后连接不再返回普通回复，不能在一次性连接上执行
+const UNSUPPORTED_COMMANDS: &[&str] = &[
+    "SUBSCRIBE",
+    "PSUBSCRIBE",
+    "SSUBSCRIBE",
+    "MONITOR",
+    "SYNC",
+    "PSYNC",
+];
+
+/// 到 Redis 实例的一次性连接，使用 RESP2 协议
+struct Connection {
+    reader: BufReader<TcpStream>,
+}
+
+impl Connection {
+    /// 连接实例，配置了密码时先认证，`database` 不为空时切换到对应的库
+    fn open(db_info: &DatabaseInfo, database: Option<u32>, timeout: Duration) -> Result<Self> {
+        if db_info.status != DatabaseStatus::Running {
+            bail!("{} must be running to run commands", db_info.name);
+        }
+        let stream = connect(db_info.port, timeout)?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+        let mut connection = Self {
+            reader: BufReader::new(stream),
+        };
+
+        if let Some(password) = db_info.password.as_deref().filter(|p| !p.is_empty()) {
+            let mut args = vec!["AUTH"];
+            if let Some(username) = db_info.username.as_deref().filter(|u| !u.is_empty()) {
+                args.push(username);
+            }
+            args.push(password);
+            if let RedisReply::Error(message) = connection.command(&args)? {
+                bail!("AUTH failed: {}", message);
+            }
+        }
+        if let Some(database) = database.filter(|&db| db != 0) {
+            let database = database.to_string();
+            if let RedisReply::Error(message) = connection.command(&["SELECT", &database])? {
+                bail!("SELECT failed: {}", message);
+            }
+        }
+        Ok(connection)
+    }
+
+    /// 发送多条命令后依次读取回复
+    fn pipeline(&mut self, commands: &[Vec<&str>]) -> Result<Vec<RedisReply>> {
+        let payload: Vec<u8> = commands.iter().flat_map(|args| encode_resp(args)).collect();
+        self.reader.get_mut().write_all(&payload)?;
+        commands.iter().map(|_| self.read_reply()).collect()
+    }
+
+    fn command(&mut self, args: &[&str]) -> Result<RedisReply> {
+        self.reader.get_mut().write_all(&encode_resp(args))?;
+        self.read_reply()
+    }
+
+    fn read_reply(&mut self) -> Result<RedisReply> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            bail!("Connection closed by server");
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        let (kind, rest) = line.split_at(line.len().min(1));
+        match kind {
+            "+" => Ok(RedisReply::Status(rest.to_string())),
+            "-" => Ok(RedisReply::Error(rest.to_string())),
+            ":" => Ok(RedisReply::Integer(
+                rest.parse().context("Invalid integer reply")?,
+            )),
+            "$" => {
+                let len: i64 = rest.parse().context("Invalid bulk reply")?;
+                if len < 0 {
+                    return Ok(RedisReply::Bulk(None));
+                }
+                let mut body = vec![0u8; len as usize + 2];
+                self.reader.read_exact(&mut body)?;
+                body.truncate(len as usize);
+                Ok(RedisReply::Bulk(Some(
+                    String::from_utf8_lossy(&body).to_string(),
+                )))
+            }
+            "*" => {
+                let len: i64 = rest.parse().context("Invalid array reply")?;
+                if len < 0 {
+                    return Ok(RedisReply::Array(None));
+                }
+                let items = (0..len)
+                    .map(|_| self.read_reply())
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(RedisReply::Array(Some(items)))
+            }
+            _ => bail!("Unexpected reply: {}", line),
+        }
+    }
+}
+
+/// 为实例设置密码，立即通过 CONFIG SET / ACL SETUSER 生效，并写入 redis.conf 以便重启后保持
+///
+/// 提供 `username` 时额外创建拥有全部权限的 ACL 用户（需要 Redis 6.2 及以上），默认用户使用同一密码。
+/// `db_info` 中保存的是当前（旧）凭据。
+pub fn secure(db_info: &DatabaseInfo, username: Option<&str>, password: &str) -> Result<()> {
+    if password.is_empty() {
+        bail!("Password cannot be empty");
+    }
+    if username.is_some_and(|u| u.is_empty() || u == "default" || u.contains(char::is_whitespace)) {
+        bail!("Invalid ACL username");
+    }
+
+    let mut connection = Connection::open(db_info, None, DEFAULT_COMMAND_TIMEOUT)?;
+    let acl_password = format!(">{}", password);
+    if let Some(username) = username {
+        let reply = connection.command(&[
+            "ACL",
+            "SETUSER",
+            username,
+            "reset",
+            "on",
+            &acl_password,
+            "~*",
+            "&*",
+            "+@all",
+        ])?;
+        if let RedisReply::Error(message) = reply {
+            bail!("ACL SETUSER failed: {}", message);
+        }
+    }
+    if let RedisReply::Error(message) =
+        connection.command(&["CONFIG", "SET", "requirepass", password])?
+    {
+        bail!("CONFIG SET failed: {}", message);
+    }
+
+    let Some(config_path) = db_info.config.as_deref().map(Path::new) else {
+        return Ok(());
+    };
+    if !config_path.exists() {
+        return Ok(());
+    }
+    write_directive(
+        config_path,
+        "requirepass ",
+        &format!("requirepass {}", quote(password)),
+    )?;
+    if let Some(username) = username {
+        write_directive(
+            config_path,
+            &format!("user {} ", username),
+            &format!("user {} on {} ~* &* +@all", username, quote(&acl_password)),
+        )?;
+    }
+    Ok(())
+}
+
+/// 将实例设为 `source` 的副本，立即通过 REPLICAOF 生效，并写入 redis.conf 以便重启后保持
+///
+/// 副本只读且会清空已有的数据，从源实例完整同步。
+pub fn replicate(db_info: &DatabaseInfo, source: &DatabaseInfo) -> Result<()> {
+    let mut connection = Connection::open(db_info, None, DEFAULT_COMMAND_TIMEOUT)?;
+    let source_port = source.port.to_string();
+    let password = source.password.as_deref().filter(|p| !p.is_empty());
+    let username = source.username.as_deref().filter(|u| !u.is_empty());
+
+    let mut commands = Vec::new();
+    if let Some(password) = password {
+        commands.push(vec!["CONFIG", "SET", "masterauth", password]);
+    }
+    if let Some(username) = username.filter(|_| password.is_some()) {
+        commands.push(vec!["CONFIG", "SET", "masteruser", username]);
+    }
+    commands.push(vec!["REPLICAOF", "127.0.0.1", &source_port]);
+    for reply in connection.pipeline(&commands)? {
+        if let RedisReply::Error(message) = reply {
+            bail!("Failed to configure replication: {}", message);
+        }
+    }
+
+    let Some(config_path) = db_info.config.as_deref().map(Path::new) else {
+        return Ok(());
+    };
+    if !config_path.exists() {
+        return Ok(());
+    }
+    write_directive(
+        config_path,
+        "replicaof ",
+        &format!("replicaof 127.0.0.1 {}", source.port),
+    )?;
+    if let Some(password) = password {
+        write_directive(
+            config_path,
+            "masterauth ",
+            &format!("masterauth {}", quote(password)),
+        )?;
+        if let Some(username) = username {
+            write_directive(
+                config_path,
+                "masteruser ",
+                &format!("masteruser {}", quote(username)),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// `INFO replication` 中的字段
+pub fn replication_info(db_info: &DatabaseInfo) -> Result<HashMap<String, String>> {
+    let mut connection = Connection::open(db_info, None, DEFAULT_COMMAND_TIMEOUT)?;
+    match connection.command(&["INFO", "replication"])? {
+        RedisReply::Bulk(Some(info)) => Ok(info
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.to_string(), value.trim().to_string()))
+            .collect()),
+        RedisReply::Error(message) => bail!("INFO failed: {}", message),
+        reply => bail!("Unexpected INFO reply: {:?}", reply),
+    }
+}
+
+/// 用 `directive` 替换 redis.conf 中以 `prefix` 开头的配置行，不存在时追加到末尾
+pub(super) fn write_directive(config_path: &Path, prefix: &str, directive: &str) -> Result<()> {
+    let contents = fs::read_to_string(config_path)?;
+
+    let mut rewritten = String::with_capacity(contents.len() + directive.len());
+    let mut replaced = false;
+    for line in contents.lines() {
+        if line.trim_start().starts_with(prefix) {
+            rewritten.push_str(directive);
+            replaced = true;
+        } else {
+            rewritten.push_str(line);
+        }
+        rewritten.push('\n');
+    }
+    if !replaced {
+        rewritten.push_str(directive);
+        rewritten.push('\n');
+    }
+
+    fs::write(config_path, rewritten).context("Failed to write redis.conf")
+}
+
+/// 转为 redis.conf 中带双引号的参数
+pub(super) fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// 执行一条原始命令，`args` 的第一项为命令名
+///
+/// 命令本身的错误作为 [`RedisReply::Error`] 返回，连接或协议错误返回 Err。
+pub fn execute(
+    db_info: &DatabaseInfo,
+    database: Option<u32>,
+    args: &[String],
+    timeout: Duration,
+) -> Result<RedisReply> {
+    let Some(name) = args.first() else {
+        bail!("The command is empty");
+    };
+    if UNSUPPORTED_COMMANDS.contains(&name.to_ascii_uppercase().as_str()) {
+        bail!("{} is not supported in the command console", name);
+    }
+
+    let mut connection = Connection::open(db_info, database, timeout)?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    connection.command(&args)
+}
+
+/// 用 SCAN 浏览键，并查询每个键的类型和剩余过期时间
+///
+/// `cursor` 为 0 时从头开始，返回的 cursor 为 0 表示已遍历完。SCAN 的 COUNT 只是提示，
+/// 每页返回的键数可能多于或少于 `count`。
+pub fn scan_keys(
+    db_info: &DatabaseInfo,
+    database: Option<u32>,
+    cursor: u64,
+    pattern: Option<&str>,
+    count: usize,
+    timeout: Duration,
+) -> Result<RedisKeyPage> {
+    let mut connection = Connection::open(db_info, database, timeout)?;
+
+    let cursor = cursor.to_string();
+    let count = count.max(1).to_string();
+    let mut args = vec!["SCAN", &cursor, "COUNT", &count];
+    if let Some(pattern) = pattern.filter(|p| !p.is_empty()) {
+        args.extend(["MATCH", pattern]);
+    }
+    let (next_cursor, keys) = match connection.command(&args)? {
+        RedisReply::Array(Some(reply)) => match reply.as_slice() {
+            [RedisReply::Bulk(Some(cursor)), RedisReply::Array(Some(keys))] => (
+                cursor.parse::<u64>().context("Invalid SCAN cursor")?,
+                keys.iter()
+                    .filter_map(|key| match key {
+                        RedisReply::Bulk(Some(key)) => Some(key.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            _ => bail!("Unexpected SCAN reply"),
+        },
+        RedisReply::Error(message) => bail!("SCAN failed: {}", message),
+        _ => bail!("Unexpected SCAN reply"),
+    };
+
+    let commands: Vec<Vec<&str>> = keys
+        .iter()
+        .flat_map(|key| [vec!["TYPE", key.as_str()], vec!["TTL", key.as_str()]])
+        .collect();
+    let replies = connection.pipeline(&commands)?;
+    let keys = keys
+        .into_iter()
+        .zip(replies.chunks(2))
+        .map(|(key, replies)| {
+            let key_type = match &replies[0] {
+                RedisReply::Status(key_type) => key_type.clone(),
+                _ => "unknown".to_string(),
+            };
+            // TTL 为 -1 时没有过期时间，为 -2 时键已在两次命令之间过期
+            let ttl = match replies[1] {
+                RedisReply::Integer(ttl) if ttl >= 0 => Some(ttl),
+                _ => None,
+            };
+            RedisKeyInfo { key, key_type, ttl }
+        })
+        .collect();
+
+    Ok(RedisKeyPage {
+        cursor: next_cursor,
+        keys,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_escapes_quotes_and_backslashes() {
+        assert_eq!(quote("secret"), "\"secret\"");
+        assert_eq!(quote("pa ss#word"), "\"pa ss#word\"");
+        assert_eq!(quote(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
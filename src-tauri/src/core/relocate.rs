@@ -0,0 +1,237 @@
+use crate::core::{utils, AppError, DatabaseInfo};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 存储根目录下由应用管理、迁移存储位置时需要移动的目录
+pub const MANAGED_DIRS: &[&str] = &[
+    "bin",
+    "config",
+    "data",
+    "logs",
+    "backups",
+    "snapshots",
+    "downloads",
+    "metrics",
+];
+
+/// 检查数据目录能否迁移到 `target`：目标不能位于原目录内，且必须不存在或为空目录
+pub fn check_target(source: &Path, target: &Path) -> Result<()> {
+    if !target.is_absolute() {
+        bail!("The new data path must be an absolute path");
+    }
+    if !source.is_dir() {
+        bail!("Data directory not found: {}", source.display());
+    }
+    if target.starts_with(source) || source.starts_with(target) {
+        bail!(
+            "{} and {} must not contain each other",
+            source.display(),
+            target.display()
+        );
+    }
+    if target.exists() {
+        let empty = target.is_dir()
+            && fs::read_dir(target)
+                .with_context(|| format!("Failed to read {}", target.display()))?
+                .next()
+                .is_none();
+        if !empty {
+            return Err(AppError::DataDirNotEmpty {
+                path: target.to_string_lossy().to_string(),
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// 复制数据目录并逐个比较文件大小，确认复制完整
+pub fn copy_verified(source: &Path, target: &Path) -> Result<()> {
+    utils::copy_dir(source, target).with_context(|| {
+        format!(
+            "Failed to copy {} to {}",
+            source.display(),
+            target.display()
+        )
+    })?;
+
+    let expected = list_files(source)?;
+    let copied = list_files(target)?;
+    if expected != copied {
+        bail!(
+            "Copied data does not match {}: {} files expected, {} copied",
+            source.display(),
+            expected.len(),
+            copied.len()
+        );
+    }
+    Ok(())
+}
+
+/// 移动目录：同一文件系统内直接改名，否则复制校验后删除原目录
+pub fn move_dir(source: &Path, target: &Path) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        utils::ensure_dir(parent)?;
+    }
+    if fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+    if let Err(err) = copy_verified(source, target) {
+        let _ = fs::remove_dir_all(target);
+        return Err(err);
+    }
+    fs::remove_dir_all(source).with_context(|| format!("Failed to remove {}", source.display()))
+}
+
+/// 目录下所有文件的相对路径和大小，按路径排序
+fn list_files(dir: &Path) -> Result<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current)
+            .with_context(|| format!("Failed to read {}", current.display()))?
+        {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                pending.push(path);
+            } else {
+                let relative = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+                files.push((relative, entry.metadata()?.len()));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// 将配置文件中的原数据目录替换为新目录，返回修改前的内容；没有配置文件或未引用数据目录时返回 None
+pub fn rewrite_config_paths(
+    db_info: &DatabaseInfo,
+    old_path: &str,
+    new_path: &str,
+) -> Result<Option<String>> {
+    let config_path = match db_info.config.as_deref() {
+        Some(path) if Path::new(path).is_file() => Path::new(path),
+        _ => return Ok(None),
+    };
+
+    let original = fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    if !original.contains(old_path) {
+        return Ok(None);
+    }
+    fs::write(config_path, original.replace(old_path, new_path))
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    Ok(Some(original))
+}
+
+/// 位于原数据目录下的路径（如日志、配置）迁移后对应的新路径
+pub fn relocated_path(path: &str, old_dir: &Path, new_dir: &Path) -> Option<String> {
+    Path::new(path)
+        .strip_prefix(old_dir)
+        .ok()
+        .map(|relative| new_dir.join(relative).to_string_lossy().to_string())
+}
+
+/// 存储根目录的迁移，记录已移动的目录和改写前的配置，失败时据此回滚
+pub struct StorageMigration {
+    old_root: PathBuf,
+    new_root: PathBuf,
+    moved: Vec<(PathBuf, PathBuf)>,
+    configs: Vec<(PathBuf, String)>,
+}
+
+impl StorageMigration {
+    /// 检查能否迁移到 `new_root`：两者不能互相包含，新位置下不能已有同名的管理目录
+    pub fn check(old_root: &Path, new_root: &Path) -> Result<()> {
+        if !new_root.is_absolute() {
+            bail!("The new storage path must be an absolute path");
+        }
+        if new_root == old_root {
+            bail!("The storage path is unchanged");
+        }
+        if new_root.starts_with(old_root) || old_root.starts_with(new_root) {
+            bail!(
+                "{} and {} must not contain each other",
+                old_root.display(),
+                new_root.display()
+            );
+        }
+        utils::check_writable(new_root)?;
+        if let Some(dir) = MANAGED_DIRS.iter().find(|dir| new_root.join(dir).exists()) {
+            return Err(AppError::DataDirNotEmpty {
+                path: new_root.join(dir).to_string_lossy().to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// 将管理目录移动到新位置，中途失败时移回已移动的目录
+    pub fn move_dirs(old_root: &Path, new_root: &Path) -> Result<Self> {
+        Self::check(old_root, new_root)?;
+        let mut migration = Self {
+            old_root: old_root.to_path_buf(),
+            new_root: new_root.to_path_buf(),
+            moved: Vec::new(),
+            configs: Vec::new(),
+        };
+        for dir in MANAGED_DIRS {
+            let source = old_root.join(dir);
+            if !source.is_dir() {
+                continue;
+            }
+            let target = new_root.join(dir);
+            if let Err(err) = move_dir(&source, &target) {
+                let _ = migration.rollback();
+                return Err(err);
+            }
+            migration.moved.push((source, target));
+        }
+        Ok(migration)
+    }
+
+    /// 原存储目录下的路径在新位置对应的路径，不在原存储目录下时返回 None
+    pub fn relocate(&self, path: &str) -> Option<String> {
+        relocated_path(path, &self.old_root, &self.new_root)
+    }
+
+    /// 改写数据库实例中的路径，并将其配置文件中引用的原存储目录替换为新目录
+    pub fn relocate_database(&mut self, db_info: &mut DatabaseInfo) -> Result<()> {
+        for path in [
+            &mut db_info.data_path,
+            &mut db_info.log_path,
+            &mut db_info.install_path,
+        ] {
+            if let Some(relocated) = self.relocate(path) {
+                *path = relocated;
+            }
+        }
+        if let Some(config) = db_info.config.as_deref().and_then(|c| self.relocate(c)) {
+            db_info.config = Some(config);
+        }
+
+        let old_root = self.old_root.to_string_lossy().to_string();
+        let new_root = self.new_root.to_string_lossy().to_string();
+        if let Some(original) = rewrite_config_paths(db_info, &old_root, &new_root)? {
+            if let Some(config) = db_info.config.as_deref() {
+                self.configs.push((PathBuf::from(config), original));
+            }
+        }
+        Ok(())
+    }
+
+    /// 恢复改写过的配置文件，并将目录移回原位置
+    pub fn rollback(self) -> Result<()> {
+        for (path, original) in &self.configs {
+            fs::write(path, original)
+                .with_context(|| format!("Failed to restore {}", path.display()))?;
+        }
+        for (source, target) in self.moved.iter().rev() {
+            move_dir(target, source)?;
+        }
+        Ok(())
+    }
+}
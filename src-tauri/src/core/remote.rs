@@ -0,0 +1,206 @@
+use crate::core::{utils, BackupRecord, RemoteBackupTarget};
+use anyhow::{bail, Context, Result};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 上传备份文件到 S3 兼容存储，返回远程对象地址
+///
+/// 使用 path-style 地址（`{endpoint}/{bucket}/{key}`），同时兼容 AWS S3 与 MinIO。
+pub fn upload_backup(target: &RemoteBackupTarget, record: &BackupRecord) -> Result<String> {
+    validate_target(target)?;
+
+    let source = Path::new(&record.file_path);
+    if !source.exists() {
+        bail!("Backup file not found: {}", source.display());
+    }
+    if source.is_dir() {
+        bail!("Exports written to a directory cannot be uploaded");
+    }
+
+    let endpoint = url::Url::parse(target.endpoint.trim_end_matches('/'))
+        .context("Invalid remote backup endpoint")?;
+    let host = match (endpoint.host_str(), endpoint.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => bail!("Invalid remote backup endpoint"),
+    };
+
+    let key = object_key(target, source);
+    let canonical_uri = format!(
+        "{}/{}/{}",
+        endpoint.path().trim_end_matches('/'),
+        utils::uri_encode(&target.bucket, true),
+        utils::uri_encode(&key, false)
+    );
+
+    let payload_hash = hash_file(source)?;
+    let now = chrono::Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let region = if target.region.is_empty() {
+        "us-east-1"
+    } else {
+        target.region.as_str()
+    };
+
+    // AWS Signature Version 4
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+    let canonical_request = format!(
+        "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        canonical_uri, host, payload_hash, amz_date, signed_headers, payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let signature = sign(&target.secret_key, &scope, &amz_date, &canonical_request);
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        target.access_key, scope, signed_headers, signature
+    );
+
+    let remote_url = format!("{}://{}{}", endpoint.scheme(), host, canonical_uri);
+
+    let file = fs::File::open(source)?;
+    let file_size = file.metadata()?.len();
+    let client = reqwest::blocking::Client::builder().timeout(None).build()?;
+    let response = client
+        .put(&remote_url)
+        .header("Authorization", authorization)
+        .header("x-amz-content-sha256", &payload_hash)
+        .header("x-amz-date", &amz_date)
+        .body(reqwest::blocking::Body::sized(file, file_size))
+        .send()
+        .context("Failed to connect to remote backup storage")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        bail!("Remote storage returned {}: {}", status, body.trim());
+    }
+
+    Ok(remote_url)
+}
+
+/// 计算 AWS Signature Version 4 签名，`scope` 为 `{date}/{region}/{service}/aws4_request`
+fn sign(secret_key: &str, scope: &str, amz_date: &str, canonical_request: &str) -> String {
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let signing_key = scope
+        .split('/')
+        .fold(format!("AWS4{}", secret_key).into_bytes(), |key, part| {
+            hmac_sha256(&key, part.as_bytes())
+        });
+    hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()))
+}
+
+fn validate_target(target: &RemoteBackupTarget) -> Result<()> {
+    if target.endpoint.trim().is_empty() {
+        bail!("Remote backup endpoint is not configured");
+    }
+    if target.bucket.trim().is_empty() {
+        bail!("Remote backup bucket is not configured");
+    }
+    if target.access_key.is_empty() || target.secret_key.is_empty() {
+        bail!("Remote backup credentials are not configured");
+    }
+    Ok(())
+}
+
+/// 远程对象键：`{prefix}/{db_type}/{file_name}`，与本地备份目录结构保持一致
+fn object_key(target: &RemoteBackupTarget, source: &Path) -> String {
+    let file_name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let db_type = source
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    [
+        target.prefix.trim_matches('/'),
+        db_type.as_str(),
+        file_name.as_str(),
+    ]
+    .iter()
+    .filter(|part| !part.is_empty())
+    .cloned()
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET_KEY: &str = "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY";
+    const EMPTY_PAYLOAD_HASH: &str =
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    /// AWS S3 文档中 GET Object 的签名示例
+    #[test]
+    fn sign_matches_aws_get_object_example() {
+        let canonical_request = format!(
+            "GET\n/test.txt\n\nhost:examplebucket.s3.amazonaws.com\nrange:bytes=0-9\n\
+             x-amz-content-sha256:{hash}\nx-amz-date:20130524T000000Z\n\n\
+             host;range;x-amz-content-sha256;x-amz-date\n{hash}",
+            hash = EMPTY_PAYLOAD_HASH
+        );
+        assert_eq!(
+            sign(
+                SECRET_KEY,
+                "20130524/us-east-1/s3/aws4_request",
+                "20130524T000000Z",
+                &canonical_request
+            ),
+            "f0e8bdb87c964420e857bd35b5d6ed310bd44f0170aba48dd91039c6036bdb41"
+        );
+    }
+
+    /// AWS SigV4 测试集中的 get-vanilla
+    #[test]
+    fn sign_matches_aws_get_vanilla_example() {
+        let canonical_request = format!(
+            "GET\n/\n\nhost:example.amazonaws.com\nx-amz-date:20150830T123600Z\n\n\
+             host;x-amz-date\n{}",
+            EMPTY_PAYLOAD_HASH
+        );
+        assert_eq!(
+            sign(
+                "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+                "20150830/us-east-1/service/aws4_request",
+                "20150830T123600Z",
+                &canonical_request
+            ),
+            "5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+}
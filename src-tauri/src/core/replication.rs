@@ -0,0 +1,474 @@
+use crate::core::backup::{mysql_command, postgresql_command};
+use crate::core::provision::{mysql_string, postgresql_string, run_mysql};
+use crate::core::{
+    config_file, crypto, query, redis, DatabaseInfo, DatabaseStatus, DatabaseType, QueryResult,
+};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::Stdio;
+
+/// 副本的复制状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicationStatus {
+    pub source_id: String,
+    /// 副本与源实例的复制链路正常
+    pub connected: bool,
+    /// 落后源实例的秒数
+    pub lag_secs: Option<f64>,
+    /// 尚未应用的复制数据量（字节），MySQL 不提供
+    pub lag_bytes: Option<u64>,
+    /// 复制链路断开的原因
+    pub error: Option<String>,
+}
+
+/// 支持创建副本的数据库类型
+pub fn supports(db_type: &DatabaseType) -> bool {
+    matches!(
+        db_type,
+        DatabaseType::Redis
+            | DatabaseType::PostgreSQL
+            | DatabaseType::MySQL
+            | DatabaseType::MariaDB
+    )
+}
+
+/// 检查源实例能否被复制，需要修改配置时写入配置文件并返回 true，重启后生效
+///
+/// MySQL 需要开启二进制日志和非零的 server-id（MariaDB 默认未开启），
+/// PostgreSQL 的 `wal_level` 不能为 minimal。
+pub fn prepare_source(storage_path: &Path, source: &DatabaseInfo) -> Result<bool> {
+    match source.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            let result = run(source, "SELECT @@GLOBAL.log_bin, @@GLOBAL.server_id")?;
+            let log_bin = value(&result, 0).is_some_and(|v| v == "1" || v == "ON");
+            let server_id = value(&result, 1).unwrap_or_default();
+
+            let mut overrides = HashMap::new();
+            if !log_bin {
+                overrides.insert("mysqld.log-bin".to_string(), "mysql-bin".to_string());
+            }
+            if server_id == "0" || server_id.is_empty() {
+                overrides.insert("mysqld.server-id".to_string(), "1".to_string());
+            }
+            if overrides.is_empty() {
+                return Ok(false);
+            }
+            config_file::apply_overrides(storage_path, source, &overrides)
+                .context("Failed to enable binary logging on the source")?;
+            Ok(true)
+        }
+        DatabaseType::PostgreSQL => {
+            let result = run(source, "SHOW wal_level")?;
+            if value(&result, 0).as_deref() == Some("minimal") {
+                bail!(
+                    "{} uses wal_level = minimal, set it to replica and restart it first",
+                    source.name
+                );
+            }
+            Ok(false)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// 为副本写入复制需要的配置，需要重启时返回 true
+///
+/// MySQL 副本使用端口号作为 server-id，与源实例区分，并设为只读。
+pub fn prepare_replica(storage_path: &Path, replica: &DatabaseInfo) -> Result<bool> {
+    match replica.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            let overrides = HashMap::from([
+                ("mysqld.server-id".to_string(), replica.port.to_string()),
+                ("mysqld.read-only".to_string(), "ON".to_string()),
+            ]);
+            config_file::apply_overrides(storage_path, replica, &overrides)
+                .context("Failed to configure the replica")?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// 在运行中的副本上开始复制：Redis 执行 REPLICAOF；MySQL 创建复制账号，
+/// 导入源实例的全量数据（带二进制日志位置）后开始复制
+///
+/// PostgreSQL 副本需要在停止时通过 [`clone_postgresql`] 创建。
+pub fn start(source: &DatabaseInfo, replica: &DatabaseInfo) -> Result<()> {
+    match replica.db_type {
+        DatabaseType::Redis => redis::replicate(replica, source),
+        DatabaseType::MySQL | DatabaseType::MariaDB => start_mysql(source, replica),
+        _ => bail!(
+            "Replicas are not supported for {}",
+            replica.db_type.display_name()
+        ),
+    }
+}
+
+/// 用 pg_basebackup 从源实例复制数据目录，写入 standby.signal 和连接信息，
+/// 并在源实例上创建复制槽，副本需要先停止
+pub fn clone_postgresql(source: &DatabaseInfo, replica: &DatabaseInfo) -> Result<()> {
+    let slot = slot_name(replica);
+    // 之前失败的尝试可能留下了复制槽
+    drop_slot(source, &slot)?;
+
+    let data_dir = Path::new(&replica.data_path);
+    if data_dir.exists() {
+        fs::remove_dir_all(data_dir)
+            .with_context(|| format!("Failed to clear {}", data_dir.display()))?;
+    }
+    let mut command = postgresql_command(source, "pg_basebackup");
+    command
+        .arg(format!("--pgdata={}", data_dir.display()))
+        .arg("--wal-method=stream")
+        .arg("--write-recovery-conf")
+        .arg("--create-slot")
+        .arg(format!("--slot={}", slot))
+        .arg("--checkpoint=fast");
+    let output = command
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to run pg_basebackup")?;
+    if !output.status.success() {
+        bail!(
+            "pg_basebackup failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    // 复制来的是源实例的配置，用 postgresql.auto.conf 覆盖端口和日志目录
+    let _ = fs::remove_file(data_dir.join("postgresql.pid"));
+    let mut overrides = format!("port = {}\n", replica.port);
+    if let Some(log_dir) = Path::new(&replica.log_path).parent().filter(|d| d.is_dir()) {
+        overrides.push_str(&format!(
+            "log_directory = {}\n",
+            postgresql_string(&log_dir.to_string_lossy())
+        ));
+    }
+    OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(data_dir.join("postgresql.auto.conf"))
+        .and_then(|mut file| file.write_all(overrides.as_bytes()))
+        .context("Failed to write postgresql.auto.conf")
+}
+
+/// 删除副本前清理源实例上为其保留的资源：PostgreSQL 的复制槽（否则源实例会一直保留 WAL）、
+/// MySQL 的复制账号，源实例未运行时跳过
+pub fn detach(source: &DatabaseInfo, replica: &DatabaseInfo) -> Result<()> {
+    if source.status != DatabaseStatus::Running {
+        return Ok(());
+    }
+    match replica.db_type {
+        DatabaseType::PostgreSQL => drop_slot(source, &slot_name(replica)),
+        DatabaseType::MySQL | DatabaseType::MariaDB => run_mysql(
+            source,
+            &format!(
+                "DROP USER IF EXISTS {}@'%';",
+                mysql_string(&replication_user(replica))
+            ),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// 查询副本的复制状态和延迟
+pub fn status(source: &DatabaseInfo, replica: &DatabaseInfo) -> Result<ReplicationStatus> {
+    let mut status = ReplicationStatus {
+        source_id: source.id.clone(),
+        connected: false,
+        lag_secs: None,
+        lag_bytes: None,
+        error: None,
+    };
+    if replica.status != DatabaseStatus::Running {
+        status.error = Some(format!("{} is not running", replica.name));
+        return Ok(status);
+    }
+    if source.status != DatabaseStatus::Running {
+        status.error = Some(format!("{} is not running", source.name));
+        return Ok(status);
+    }
+
+    match replica.db_type {
+        DatabaseType::Redis => redis_status(source, replica, &mut status)?,
+        DatabaseType::PostgreSQL => postgresql_status(source, replica, &mut status)?,
+        DatabaseType::MySQL | DatabaseType::MariaDB => mysql_status(replica, &mut status)?,
+        _ => bail!(
+            "Replicas are not supported for {}",
+            replica.db_type.display_name()
+        ),
+    }
+    Ok(status)
+}
+
+fn run(db_info: &DatabaseInfo, sql: &str) -> Result<QueryResult> {
+    query::execute(db_info, None, sql, 1, query::DEFAULT_QUERY_TIMEOUT)
+        .with_context(|| format!("Failed to run `{}`", sql))
+}
+
+/// 第一行中第 `index` 列的值
+fn value(result: &QueryResult, index: usize) -> Option<String> {
+    result.rows.first()?.get(index)?.clone()
+}
+
+/// 第一行中名为 `names` 之一的列的值，用于兼容列名在不同版本中的改名
+fn column(result: &QueryResult, names: &[&str]) -> Option<String> {
+    let index = result
+        .columns
+        .iter()
+        .position(|column| names.contains(&column.as_str()))?;
+    value(result, index)
+}
+
+/// 版本号不低于 `min`，如 `version_at_least("8.0.36", &[8, 0, 23])`
+fn version_at_least(version: &str, min: &[u32]) -> bool {
+    let parts: Vec<u32> = version
+        .split(['.', '-'])
+        .map_while(|part| part.parse().ok())
+        .collect();
+    parts.as_slice() >= min
+}
+
+/// MySQL 8.0.23 起使用 SOURCE / REPLICA 的新语法，MariaDB 和更早的 MySQL 使用 MASTER / SLAVE
+fn uses_replica_syntax(db_info: &DatabaseInfo) -> bool {
+    db_info.db_type == DatabaseType::MySQL && version_at_least(&db_info.version, &[8, 0, 23])
+}
+
+/// 每个副本使用单独的复制账号，重建一个副本时不影响其它副本
+///
+/// 账号名按副本 ID 生成，修改副本端口后仍能找到源数据库上的账号；MySQL 账号名最长 32 个字符。
+fn replication_user(replica: &DatabaseInfo) -> String {
+    let mut user = format!("local_db_repl_{}", sanitized_id(replica));
+    user.truncate(32);
+    user
+}
+
+/// 复制槽名只能包含小写字母、数字和下划线
+fn slot_name(replica: &DatabaseInfo) -> String {
+    format!("local_db_{}", sanitized_id(replica))
+}
+
+/// 副本 ID 中的字母转为小写，其他字符替换为下划线
+fn sanitized_id(replica: &DatabaseInfo) -> String {
+    replica
+        .id
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            c @ ('a'..='z' | '0'..='9') => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+fn drop_slot(source: &DatabaseInfo, slot: &str) -> Result<()> {
+    run(
+        source,
+        &format!(
+            "SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots \
+             WHERE slot_name = {}",
+            postgresql_string(slot)
+        ),
+    )
+    .map(|_| ())
+}
+
+fn start_mysql(source: &DatabaseInfo, replica: &DatabaseInfo) -> Result<()> {
+    let user = mysql_string(&replication_user(replica));
+    let password = crypto::random_token()[..32].to_string();
+    run_mysql(
+        source,
+        &format!(
+            "CREATE USER IF NOT EXISTS {user}@'%' IDENTIFIED BY {password};\n\
+             ALTER USER {user}@'%' IDENTIFIED BY {password};\n\
+             GRANT REPLICATION SLAVE ON *.* TO {user}@'%';\n",
+            user = user,
+            password = mysql_string(&password)
+        ),
+    )
+    .context("Failed to create the replication user")?;
+
+    // 修改源地址会清空复制位置，因此先设置连接信息，再由导入的数据设置位置
+    let new_syntax = uses_replica_syntax(replica);
+    let change = if new_syntax {
+        format!(
+            "CHANGE REPLICATION SOURCE TO SOURCE_HOST = '127.0.0.1', SOURCE_PORT = {}, \
+             SOURCE_USER = {}, SOURCE_PASSWORD = {}, GET_SOURCE_PUBLIC_KEY = 1;\n",
+            source.port,
+            user,
+            mysql_string(&password)
+        )
+    } else {
+        // MySQL 8 默认的 caching_sha2_password 需要获取公钥
+        let public_key =
+            if replica.db_type == DatabaseType::MySQL && version_at_least(&replica.version, &[8]) {
+                ", GET_MASTER_PUBLIC_KEY = 1"
+            } else {
+                ""
+            };
+        format!(
+            "CHANGE MASTER TO MASTER_HOST = '127.0.0.1', MASTER_PORT = {}, \
+             MASTER_USER = {}, MASTER_PASSWORD = {}{};\n",
+            source.port,
+            user,
+            mysql_string(&password),
+            public_key
+        )
+    };
+    run_mysql(replica, &change).context("Failed to configure the replica")?;
+
+    copy_mysql_data(source, replica)?;
+    let start = if new_syntax {
+        "START REPLICA;"
+    } else {
+        "START SLAVE;"
+    };
+    run_mysql(replica, start).context("Failed to start replication")
+}
+
+/// 将 mysqldump 的输出直接导入副本，导出中带有对应的二进制日志位置
+fn copy_mysql_data(source: &DatabaseInfo, replica: &DatabaseInfo) -> Result<()> {
+    // MySQL 8.0.26 起 --master-data 更名为 --source-data
+    let position = if source.db_type == DatabaseType::MySQL
+        && version_at_least(&source.version, &[8, 0, 26])
+    {
+        "--source-data=1"
+    } else {
+        "--master-data=1"
+    };
+    let mut dump = mysql_command(source, "mysqldump");
+    dump.arg("--all-databases")
+        .arg("--single-transaction")
+        .arg("--routines")
+        .arg("--events")
+        .arg(position)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut dump = dump.spawn().context("Failed to run mysqldump")?;
+    let dump_stderr = dump.stderr.take();
+    let dump_errors = std::thread::spawn(move || {
+        let mut buffer = String::new();
+        if let Some(mut stderr) = dump_stderr {
+            let _ = stderr.read_to_string(&mut buffer);
+        }
+        buffer
+    });
+
+    let mut load = mysql_command(replica, "mysql");
+    load.stdin(
+        dump.stdout
+            .take()
+            .context("Failed to capture mysqldump output")?,
+    );
+    let load = load.output().context("Failed to run mysql")?;
+    let dump_status = dump.wait()?;
+    let dump_errors = dump_errors.join().unwrap_or_default();
+
+    if !dump_status.success() {
+        bail!("mysqldump failed: {}", dump_errors.trim());
+    }
+    if !load.status.success() {
+        bail!(
+            "Failed to load data into the replica: {}",
+            String::from_utf8_lossy(&load.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn redis_status(
+    source: &DatabaseInfo,
+    replica: &DatabaseInfo,
+    status: &mut ReplicationStatus,
+) -> Result<()> {
+    let info = redis::replication_info(replica)?;
+    status.connected = info.get("master_link_status").is_some_and(|s| s == "up");
+    if !status.connected {
+        status.error = Some("The replica is not connected to the source".to_string());
+        return Ok(());
+    }
+
+    // 源实例的 slaveN 行：ip=127.0.0.1,port=6380,state=online,offset=1234,lag=0
+    let source_info = redis::replication_info(source)?;
+    let replica_port = replica.port.to_string();
+    let entry = source_info
+        .iter()
+        .filter(|(key, _)| key.starts_with("slave"))
+        .map(|(_, value)| {
+            value
+                .split(',')
+                .filter_map(|field| field.split_once('='))
+                .collect::<HashMap<_, _>>()
+        })
+        .find(|fields| fields.get("port") == Some(&replica_port.as_str()));
+    if let Some(fields) = entry {
+        status.lag_secs = fields.get("lag").and_then(|lag| lag.parse().ok());
+        let source_offset = source_info
+            .get("master_repl_offset")
+            .and_then(|offset| offset.parse::<u64>().ok());
+        let replica_offset = fields.get("offset").and_then(|offset| offset.parse().ok());
+        if let (Some(source_offset), Some(replica_offset)) = (source_offset, replica_offset) {
+            status.lag_bytes = Some(source_offset.saturating_sub(replica_offset));
+        }
+    }
+    Ok(())
+}
+
+fn postgresql_status(
+    source: &DatabaseInfo,
+    replica: &DatabaseInfo,
+    status: &mut ReplicationStatus,
+) -> Result<()> {
+    // 没有新写入时 replay_lag 为 NULL，视为没有延迟
+    let result = run(
+        source,
+        &format!(
+            "SELECT r.state, pg_wal_lsn_diff(pg_current_wal_lsn(), r.replay_lsn), \
+             EXTRACT(EPOCH FROM COALESCE(r.replay_lag, interval '0')) \
+             FROM pg_replication_slots s JOIN pg_stat_replication r ON r.pid = s.active_pid \
+             WHERE s.slot_name = {}",
+            postgresql_string(&slot_name(replica))
+        ),
+    )?;
+    let state = value(&result, 0);
+    status.connected = state.as_deref() == Some("streaming");
+    status.lag_bytes = value(&result, 1)
+        .and_then(|bytes| bytes.parse::<f64>().ok())
+        .map(|bytes| bytes.max(0.0) as u64);
+    status.lag_secs = value(&result, 2).and_then(|secs| secs.parse().ok());
+    if !status.connected {
+        status.error = Some(match state {
+            Some(state) => format!("The replica is {}", state),
+            None => "The replica is not connected to the source".to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn mysql_status(replica: &DatabaseInfo, status: &mut ReplicationStatus) -> Result<()> {
+    let sql = if uses_replica_syntax(replica) {
+        "SHOW REPLICA STATUS"
+    } else {
+        "SHOW SLAVE STATUS"
+    };
+    let result = run(replica, sql)?;
+    if result.rows.is_empty() {
+        status.error = Some("Replication is not configured".to_string());
+        return Ok(());
+    }
+
+    let running = |names: &[&str]| column(&result, names).is_some_and(|v| v == "Yes");
+    status.connected = running(&["Replica_IO_Running", "Slave_IO_Running"])
+        && running(&["Replica_SQL_Running", "Slave_SQL_Running"]);
+    status.lag_secs = column(&result, &["Seconds_Behind_Source", "Seconds_Behind_Master"])
+        .and_then(|secs| secs.parse().ok());
+    status.error = ["Last_IO_Error", "Last_SQL_Error"]
+        .iter()
+        .filter_map(|name| column(&result, &[name]))
+        .find(|error| !error.is_empty());
+    Ok(())
+}
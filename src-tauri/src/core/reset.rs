@@ -0,0 +1,115 @@
+use crate::core::{mariadb, provision, surreal, utils};
+use crate::core::{DatabaseInfo, DatabaseType};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// 删除数据目录中的全部内容并重新初始化，数据库需已停止
+///
+/// MySQL 使用 `--initialize-insecure`，MariaDB 使用 `mariadb-install-db`，PostgreSQL 使用 initdb，
+/// 其余数据库在首次启动时自行创建数据文件，只需留下空目录。
+pub fn reinitialize_data_dir(db_info: &DatabaseInfo) -> Result<()> {
+    if matches!(
+        db_info.db_type,
+        DatabaseType::SQLite | DatabaseType::DuckDB | DatabaseType::Custom
+    ) {
+        bail!(
+            "Resetting data is not supported for {}",
+            db_info.db_type.display_name()
+        );
+    }
+
+    let data_dir = Path::new(&db_info.data_path);
+    if data_dir.exists() {
+        fs::remove_dir_all(data_dir)
+            .with_context(|| format!("Failed to remove {}", data_dir.display()))?;
+    }
+    utils::ensure_dir(data_dir)?;
+
+    let install_path = Path::new(&db_info.install_path);
+    match db_info.db_type {
+        DatabaseType::MySQL => {
+            let output = Command::new(utils::resolve_db_binary(&db_info.install_path, "mysqld"))
+                .arg("--initialize-insecure")
+                .arg(format!("--datadir={}", data_dir.display()))
+                .arg(format!("--basedir={}", install_path.display()))
+                .output()
+                .context("Failed to initialize MySQL")?;
+            if !output.status.success() {
+                bail!(
+                    "MySQL initialization failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+        }
+        DatabaseType::MariaDB if cfg!(windows) => {
+            // Windows 版在数据目录中生成 my.ini，需要传入端口
+            let output = Command::new(utils::resolve_db_binary(
+                &db_info.install_path,
+                "mariadb-install-db",
+            ))
+            .arg(format!("--datadir={}", data_dir.display()))
+            .arg(format!("--port={}", db_info.port))
+            .output()
+            .context("Failed to initialize MariaDB")?;
+            if !output.status.success() {
+                bail!(
+                    "MariaDB initialization failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+        }
+        DatabaseType::MariaDB => {
+            // 二进制包的脚本在 scripts 目录下，Homebrew 安装在 bin 目录下并以当前用户运行
+            let script = install_path.join("scripts").join("mariadb-install-db");
+            let script = if script.exists() {
+                script
+            } else {
+                install_path.join("bin").join("mariadb-install-db")
+            };
+            let user = if cfg!(target_os = "macos") {
+                std::env::var("USER").ok()
+            } else {
+                None
+            };
+            mariadb::initialize_data_dir(&script, install_path, data_dir, user.as_deref())?;
+        }
+        DatabaseType::PostgreSQL => {
+            let output = Command::new(utils::resolve_db_binary(&db_info.install_path, "initdb"))
+                .arg("-D")
+                .arg(data_dir)
+                .arg(format!(
+                    "--username={}",
+                    db_info.username.as_deref().unwrap_or("postgres")
+                ))
+                .arg("--auth-local=trust")
+                .arg("--auth-host=trust")
+                .arg("--encoding=UTF8")
+                .arg("--no-locale")
+                .output()
+                .context("Failed to initialize PostgreSQL")?;
+            if !output.status.success() {
+                bail!(
+                    "PostgreSQL initialization failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 重新初始化后的实例启动后，恢复 `DatabaseInfo` 中保存的凭据
+///
+/// MySQL 和 MariaDB 初始化后只有空密码的 root，需要重新设置保存的用户和密码；SurrealDB 需要重新创建命名空间和用户。
+pub fn restore_credentials(db_info: &DatabaseInfo) -> Result<()> {
+    match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB => {
+            provision::initialize_mysql_credentials(db_info)
+        }
+        DatabaseType::SurrealDB => surreal::initialize(db_info),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,260 @@
+use crate::core::backup::{qdrant_client, qdrant_json, qdrant_url};
+use crate::core::{mongo, provision};
+use crate::core::{DatabaseInfo, DatabaseStatus, DatabaseType, SampleDataset};
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+
+/// 示例电商库的数据库名，MongoDB 的示例文档也写入同名的库
+pub const SAMPLE_DATABASE: &str = "sample_shop";
+
+/// 示例向量写入的 Qdrant 集合
+pub const SAMPLE_COLLECTION: &str = "sample_vectors";
+
+/// 电商库的表结构和数据，只使用 MySQL 和 PostgreSQL 都支持的语法
+const ECOMMERCE_SQL: &str = "\
+CREATE TABLE customers (
+    id INTEGER PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    country CHAR(2) NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE TABLE products (
+    id INTEGER PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    category VARCHAR(50) NOT NULL,
+    price DECIMAL(10, 2) NOT NULL,
+    stock INTEGER NOT NULL
+);
+
+CREATE TABLE orders (
+    id INTEGER PRIMARY KEY,
+    customer_id INTEGER NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    ordered_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (customer_id) REFERENCES customers (id)
+);
+
+CREATE TABLE order_items (
+    order_id INTEGER NOT NULL,
+    product_id INTEGER NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price DECIMAL(10, 2) NOT NULL,
+    PRIMARY KEY (order_id, product_id),
+    FOREIGN KEY (order_id) REFERENCES orders (id),
+    FOREIGN KEY (product_id) REFERENCES products (id)
+);
+
+INSERT INTO customers (id, name, email, country, created_at) VALUES
+    (1, 'Alice Martin', 'alice@example.com', 'US', '2024-01-03 09:12:00'),
+    (2, 'Bruno Costa', 'bruno@example.com', 'BR', '2024-01-08 14:30:00'),
+    (3, 'Chen Wei', 'chen@example.com', 'CN', '2024-01-15 08:45:00'),
+    (4, 'Dana Kowalski', 'dana@example.com', 'PL', '2024-02-02 19:05:00'),
+    (5, 'Emma Schmidt', 'emma@example.com', 'DE', '2024-02-11 11:20:00'),
+    (6, 'Farah Haddad', 'farah@example.com', 'AE', '2024-02-27 16:40:00'),
+    (7, 'Goro Tanaka', 'goro@example.com', 'JP', '2024-03-06 07:55:00'),
+    (8, 'Hana Novak', 'hana@example.com', 'CZ', '2024-03-19 13:10:00');
+
+INSERT INTO products (id, name, category, price, stock) VALUES
+    (1, 'Mechanical Keyboard', 'electronics', 89.90, 120),
+    (2, 'Wireless Mouse', 'electronics', 29.50, 340),
+    (3, '27-inch Monitor', 'electronics', 249.00, 45),
+    (4, 'USB-C Hub', 'electronics', 39.99, 210),
+    (5, 'Standing Desk', 'furniture', 399.00, 18),
+    (6, 'Ergonomic Chair', 'furniture', 289.00, 25),
+    (7, 'Desk Lamp', 'furniture', 45.00, 80),
+    (8, 'Notebook Set', 'stationery', 12.75, 500),
+    (9, 'Fountain Pen', 'stationery', 58.00, 60),
+    (10, 'Coffee Mug', 'kitchen', 9.90, 260);
+
+INSERT INTO orders (id, customer_id, status, ordered_at) VALUES
+    (1, 1, 'delivered', '2024-03-01 10:00:00'),
+    (2, 2, 'delivered', '2024-03-03 15:20:00'),
+    (3, 1, 'shipped', '2024-03-10 09:45:00'),
+    (4, 3, 'delivered', '2024-03-12 12:05:00'),
+    (5, 4, 'cancelled', '2024-03-15 18:30:00'),
+    (6, 5, 'delivered', '2024-03-20 08:15:00'),
+    (7, 6, 'shipped', '2024-03-24 20:40:00'),
+    (8, 7, 'pending', '2024-03-28 11:25:00'),
+    (9, 8, 'pending', '2024-03-29 14:50:00'),
+    (10, 3, 'shipped', '2024-03-30 17:35:00');
+
+INSERT INTO order_items (order_id, product_id, quantity, unit_price) VALUES
+    (1, 1, 1, 89.90),
+    (1, 2, 1, 29.50),
+    (2, 5, 1, 399.00),
+    (3, 8, 3, 12.75),
+    (3, 10, 2, 9.90),
+    (4, 3, 2, 249.00),
+    (4, 4, 1, 39.99),
+    (5, 6, 1, 289.00),
+    (6, 7, 2, 45.00),
+    (6, 9, 1, 58.00),
+    (7, 1, 1, 89.90),
+    (7, 3, 1, 249.00),
+    (8, 2, 2, 29.50),
+    (9, 10, 4, 9.90),
+    (10, 5, 1, 399.00),
+    (10, 6, 1, 289.00);
+";
+
+/// 示例向量的类别及每个类别下的名称，同类向量彼此相近
+const VECTOR_CATEGORIES: &[(&str, &[&str])] = &[
+    (
+        "fruit",
+        &[
+            "apple", "banana", "cherry", "grape", "lemon", "mango", "orange", "peach",
+        ],
+    ),
+    (
+        "vehicle",
+        &[
+            "bicycle",
+            "bus",
+            "car",
+            "ferry",
+            "motorcycle",
+            "scooter",
+            "train",
+            "truck",
+        ],
+    ),
+    (
+        "animal",
+        &[
+            "cat", "dog", "eagle", "fox", "horse", "lion", "otter", "wolf",
+        ],
+    ),
+];
+
+/// 示例向量的维度
+const VECTOR_SIZE: usize = 4;
+
+/// 返回数据库类型可用的示例数据集
+pub fn datasets_for(db_type: &DatabaseType) -> &'static [SampleDataset] {
+    match db_type {
+        DatabaseType::MySQL
+        | DatabaseType::MariaDB
+        | DatabaseType::SeekDB
+        | DatabaseType::PostgreSQL => &[SampleDataset::Ecommerce],
+        DatabaseType::Qdrant => &[SampleDataset::Vectors],
+        DatabaseType::MongoDB => &[SampleDataset::Documents],
+        _ => &[],
+    }
+}
+
+/// 把示例数据集写入实例，返回数据所在的库或集合名
+///
+/// 数据写入新建的库或集合，已存在时报错而不是覆盖。
+pub fn seed(db_info: &DatabaseInfo, dataset: SampleDataset) -> Result<&'static str> {
+    if db_info.status != DatabaseStatus::Running {
+        bail!("{} must be running to load sample data", db_info.name);
+    }
+    if !datasets_for(&db_info.db_type).contains(&dataset) {
+        bail!(
+            "The {} dataset is not available for {}",
+            dataset.as_str(),
+            db_info.db_type.display_name()
+        );
+    }
+
+    match dataset {
+        SampleDataset::Ecommerce => {
+            provision::create_database(db_info, SAMPLE_DATABASE)
+                .context("Failed to create the sample database")?;
+            if db_info.db_type == DatabaseType::PostgreSQL {
+                provision::run_psql(db_info, SAMPLE_DATABASE, ECOMMERCE_SQL)?;
+            } else {
+                provision::run_mysql(
+                    db_info,
+                    &format!("USE {};\n{}", SAMPLE_DATABASE, ECOMMERCE_SQL),
+                )?;
+            }
+            Ok(SAMPLE_DATABASE)
+        }
+        SampleDataset::Vectors => {
+            seed_vectors(db_info)?;
+            Ok(SAMPLE_COLLECTION)
+        }
+        SampleDataset::Documents => {
+            mongo::insert_documents(db_info, SAMPLE_DATABASE, "products", &sample_products())?;
+            mongo::insert_documents(db_info, SAMPLE_DATABASE, "reviews", &sample_reviews())?;
+            Ok(SAMPLE_DATABASE)
+        }
+    }
+}
+
+fn seed_vectors(db_info: &DatabaseInfo) -> Result<()> {
+    let client = qdrant_client(db_info)?;
+    qdrant_json(
+        client
+            .put(qdrant_url(db_info, &["collections", SAMPLE_COLLECTION])?)
+            .json(&json!({ "vectors": { "size": VECTOR_SIZE, "distance": "Cosine" } }))
+            .send()
+            .context("Failed to create the sample collection")?,
+    )?;
+
+    let mut points = Vec::new();
+    for (category_index, (category, names)) in VECTOR_CATEGORIES.iter().enumerate() {
+        for (index, name) in names.iter().enumerate() {
+            // 类别决定主方向，序号带来小幅偏移，使同类向量聚在一起
+            let vector: Vec<f64> = (0..VECTOR_SIZE)
+                .map(|dim| {
+                    let base = if dim == category_index { 1.0 } else { 0.1 };
+                    base + ((index + dim) % 4) as f64 * 0.05
+                })
+                .collect();
+            points.push(json!({
+                "id": points.len() + 1,
+                "vector": vector,
+                "payload": { "name": name, "category": category },
+            }));
+        }
+    }
+
+    let mut url = qdrant_url(db_info, &["collections", SAMPLE_COLLECTION, "points"])?;
+    url.query_pairs_mut().append_pair("wait", "true");
+    qdrant_json(
+        client
+            .put(url)
+            .json(&json!({ "points": points }))
+            .send()
+            .context("Failed to insert sample vectors")?,
+    )?;
+    Ok(())
+}
+
+fn sample_products() -> Vec<serde_json::Value> {
+    vec![
+        json!({ "_id": 1, "name": "Mechanical Keyboard", "category": "electronics", "price": 89.9,
+            "tags": ["keyboard", "rgb"], "specs": { "switches": "brown", "layout": "ANSI" } }),
+        json!({ "_id": 2, "name": "Wireless Mouse", "category": "electronics", "price": 29.5,
+            "tags": ["mouse", "bluetooth"], "specs": { "dpi": 1600, "buttons": 6 } }),
+        json!({ "_id": 3, "name": "27-inch Monitor", "category": "electronics", "price": 249.0,
+            "tags": ["display", "4k"], "specs": { "resolution": "3840x2160", "refresh_hz": 60 } }),
+        json!({ "_id": 4, "name": "Standing Desk", "category": "furniture", "price": 399.0,
+            "tags": ["desk", "adjustable"], "specs": { "min_height_cm": 72, "max_height_cm": 120 } }),
+        json!({ "_id": 5, "name": "Ergonomic Chair", "category": "furniture", "price": 289.0,
+            "tags": ["chair"], "specs": { "lumbar_support": true, "max_load_kg": 130 } }),
+        json!({ "_id": 6, "name": "Fountain Pen", "category": "stationery", "price": 58.0,
+            "tags": ["pen", "gift"], "specs": { "nib": "F", "ink": "cartridge" } }),
+    ]
+}
+
+fn sample_reviews() -> Vec<serde_json::Value> {
+    vec![
+        json!({ "_id": 1, "product_id": 1, "author": "alice", "rating": 5,
+            "text": "Great feel and solid build.", "created_at": "2024-03-05T10:00:00Z" }),
+        json!({ "_id": 2, "product_id": 1, "author": "goro", "rating": 4,
+            "text": "A bit loud, but typing is a joy.", "created_at": "2024-03-12T08:30:00Z" }),
+        json!({ "_id": 3, "product_id": 3, "author": "chen", "rating": 5,
+            "text": "Sharp picture, easy to mount.", "created_at": "2024-03-18T19:45:00Z" }),
+        json!({ "_id": 4, "product_id": 4, "author": "bruno", "rating": 3,
+            "text": "Motor is slow, otherwise sturdy.", "created_at": "2024-03-21T14:10:00Z" }),
+        json!({ "_id": 5, "product_id": 5, "author": "dana", "rating": 4,
+            "text": "Comfortable for long days.", "created_at": "2024-03-25T16:20:00Z" }),
+        json!({ "_id": 6, "product_id": 6, "author": "emma", "rating": 5,
+            "text": "Writes smoothly, lovely gift.", "created_at": "2024-03-29T09:05:00Z" }),
+    ]
+}
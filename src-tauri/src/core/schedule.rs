@@ -0,0 +1,114 @@
+use crate::core::DatabaseSchedule;
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
+
+/// 检查计划的时间和星期是否有效
+pub fn validate(schedules: &[DatabaseSchedule]) -> Result<()> {
+    for schedule in schedules {
+        parse_time(&schedule.time)?;
+        if let Some(day) = schedule.days.iter().find(|day| !(1..=7).contains(*day)) {
+            bail!("Invalid day {}, expected 1 (Monday) to 7 (Sunday)", day);
+        }
+    }
+    Ok(())
+}
+
+/// 计划是否在 `(from, to]` 时间段内到期
+pub fn is_due(schedule: &DatabaseSchedule, from: NaiveDateTime, to: NaiveDateTime) -> bool {
+    let Ok(time) = parse_time(&schedule.time) else {
+        return false;
+    };
+    from.date()
+        .iter_days()
+        .take_while(|date| *date <= to.date())
+        .filter(|date| {
+            schedule.days.is_empty()
+                || schedule
+                    .days
+                    .contains(&(date.weekday().number_from_monday() as u8))
+        })
+        .map(|date| date.and_time(time))
+        .any(|at| at > from && at <= to)
+}
+
+fn parse_time(time: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .with_context(|| format!("Invalid time {}, expected HH:MM", time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ScheduleAction;
+
+    fn schedule(time: &str, days: &[u8]) -> DatabaseSchedule {
+        DatabaseSchedule {
+            action: ScheduleAction::Start,
+            time: time.to_string(),
+            days: days.to_vec(),
+        }
+    }
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn is_due_within_interval() {
+        let daily = schedule("08:30", &[]);
+        assert!(is_due(
+            &daily,
+            at("2024-01-01 08:29:30"),
+            at("2024-01-01 08:30:00")
+        ));
+        // 区间不包含起点，上一次检查已经执行过
+        assert!(!is_due(
+            &daily,
+            at("2024-01-01 08:30:00"),
+            at("2024-01-01 08:31:00")
+        ));
+        assert!(!is_due(
+            &daily,
+            at("2024-01-01 08:00:00"),
+            at("2024-01-01 08:29:59")
+        ));
+    }
+
+    #[test]
+    fn is_due_across_midnight() {
+        let daily = schedule("00:05", &[]);
+        assert!(is_due(
+            &daily,
+            at("2024-01-01 23:59:00"),
+            at("2024-01-02 00:10:00")
+        ));
+    }
+
+    #[test]
+    fn is_due_only_on_selected_days() {
+        // 2024-01-01 是周一
+        let weekdays = schedule("09:00", &[1, 2, 3, 4, 5]);
+        assert!(is_due(
+            &weekdays,
+            at("2024-01-01 08:59:00"),
+            at("2024-01-01 09:00:00")
+        ));
+        assert!(!is_due(
+            &weekdays,
+            at("2024-01-06 08:59:00"),
+            at("2024-01-06 09:00:00")
+        ));
+    }
+
+    #[test]
+    fn is_due_ignores_invalid_time() {
+        let invalid = schedule("25:00", &[]);
+        assert!(!is_due(
+            &invalid,
+            at("2024-01-01 00:00:00"),
+            at("2024-01-02 00:00:00")
+        ));
+        assert!(validate(&[invalid]).is_err());
+        assert!(validate(&[schedule("09:00", &[0])]).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::core::{mongo, query};
+use crate::core::{DatabaseInfo, DatabaseObject, DatabaseObjectKind, DatabaseType, QueryResult};
+use anyhow::{bail, Result};
+
+/// 浏览时隐藏的 MySQL 系统库
+const MYSQL_SYSTEM_SCHEMAS: &[&str] = &["information_schema", "performance_schema", "mysql", "sys"];
+
+/// 列出实例中的数据库、模式和表（集合），附带统计信息中的估算行数
+///
+/// 支持 MySQL 协议的数据库、PostgreSQL 和 MongoDB，系统库和系统模式不包含在内。
+pub fn list_objects(db_info: &DatabaseInfo) -> Result<Vec<DatabaseObject>> {
+    match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB | DatabaseType::SeekDB => {
+            list_mysql_objects(db_info)
+        }
+        DatabaseType::PostgreSQL => list_postgresql_objects(db_info),
+        DatabaseType::MongoDB => list_mongo_objects(db_info),
+        _ => bail!(
+            "Browsing objects is not supported for {}",
+            db_info.db_type.display_name()
+        ),
+    }
+}
+
+/// 从 information_schema 一次查出所有库和表，没有表的库也保留
+fn list_mysql_objects(db_info: &DatabaseInfo) -> Result<Vec<DatabaseObject>> {
+    let sql = format!(
+        "SELECT s.SCHEMA_NAME, t.TABLE_NAME, t.TABLE_TYPE, t.TABLE_ROWS \
+        FROM information_schema.SCHEMATA s \
+        LEFT JOIN information_schema.TABLES t ON t.TABLE_SCHEMA = s.SCHEMA_NAME \
+        WHERE s.SCHEMA_NAME NOT IN ({}) \
+        ORDER BY s.SCHEMA_NAME, t.TABLE_NAME",
+        MYSQL_SYSTEM_SCHEMAS
+            .iter()
+            .map(|schema| format!("'{}'", schema))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let result = run_query(db_info, None, &sql)?;
+
+    let mut databases: Vec<DatabaseObject> = Vec::new();
+    for row in &result.rows {
+        let [Some(schema), table, table_type, rows] = row.as_slice() else {
+            continue;
+        };
+        let database = parent(&mut databases, schema, DatabaseObjectKind::Database);
+        if let Some(table) = table {
+            let kind = match table_type.as_deref() {
+                Some("VIEW") | Some("SYSTEM VIEW") => DatabaseObjectKind::View,
+                _ => DatabaseObjectKind::Table,
+            };
+            database.children.push(leaf(table, kind, rows.as_deref()));
+        }
+    }
+    Ok(databases)
+}
+
+/// 先列出可连接的数据库，再逐个查询其中的模式和表
+///
+/// PostgreSQL 的目录信息按数据库隔离，每个数据库需要单独连接。
+fn list_postgresql_objects(db_info: &DatabaseInfo) -> Result<Vec<DatabaseObject>> {
+    let result = run_query(
+        db_info,
+        None,
+        "SELECT datname FROM pg_database WHERE datallowconn AND NOT datistemplate ORDER BY datname",
+    )?;
+    let names: Vec<String> = result
+        .rows
+        .into_iter()
+        .filter_map(|row| row.into_iter().next().flatten())
+        .collect();
+
+    // reltuples 为 -1 表示表还没有统计过
+    let sql = "SELECT n.nspname, c.relname, c.relkind, c.reltuples::bigint \
+        FROM pg_namespace n \
+        LEFT JOIN pg_class c ON c.relnamespace = n.oid AND c.relkind IN ('r', 'p', 'v', 'm', 'f') \
+        WHERE n.nspname NOT IN ('pg_catalog', 'information_schema', 'pg_toast') \
+        AND n.nspname NOT LIKE 'pg\\_temp\\_%' AND n.nspname NOT LIKE 'pg\\_toast\\_temp\\_%' \
+        ORDER BY n.nspname, c.relname";
+    let mut databases = Vec::new();
+    for name in names {
+        let result = run_query(db_info, Some(&name), sql)?;
+        let mut schemas: Vec<DatabaseObject> = Vec::new();
+        for row in &result.rows {
+            let [Some(namespace), relation, kind, rows] = row.as_slice() else {
+                continue;
+            };
+            let schema = parent(&mut schemas, namespace, DatabaseObjectKind::Schema);
+            if let Some(relation) = relation {
+                let (kind, rows) = match kind.as_deref() {
+                    Some("v") => (DatabaseObjectKind::View, None),
+                    _ => (DatabaseObjectKind::Table, rows.as_deref()),
+                };
+                schema.children.push(leaf(relation, kind, rows));
+            }
+        }
+        databases.push(DatabaseObject {
+            name,
+            kind: DatabaseObjectKind::Database,
+            row_estimate: None,
+            children: schemas,
+        });
+    }
+    Ok(databases)
+}
+
+fn list_mongo_objects(db_info: &DatabaseInfo) -> Result<Vec<DatabaseObject>> {
+    let databases = mongo::list_all_collections(db_info)?
+        .into_iter()
+        .map(|(name, collections)| DatabaseObject {
+            name,
+            kind: DatabaseObjectKind::Database,
+            row_estimate: None,
+            children: collections
+                .into_iter()
+                .map(|collection| DatabaseObject {
+                    kind: if collection.collection_type == "view" {
+                        DatabaseObjectKind::View
+                    } else {
+                        DatabaseObjectKind::Collection
+                    },
+                    name: collection.name,
+                    row_estimate: collection.document_count,
+                    children: Vec::new(),
+                })
+                .collect(),
+        })
+        .collect();
+    Ok(databases)
+}
+
+/// 目录查询的结果不截断，超出行数上限时报错而不是返回不完整的树
+fn run_query(db_info: &DatabaseInfo, database: Option<&str>, sql: &str) -> Result<QueryResult> {
+    let result = query::execute(
+        db_info,
+        database,
+        sql,
+        query::MAX_ROW_LIMIT,
+        query::DEFAULT_QUERY_TIMEOUT,
+    )?;
+    if result.truncated {
+        bail!(
+            "{} has more than {} objects to list",
+            db_info.name,
+            query::MAX_ROW_LIMIT
+        );
+    }
+    Ok(result)
+}
+
+/// 结果按名称排序，同名的行连续出现，只需与最后一个节点比较
+fn parent<'a>(
+    nodes: &'a mut Vec<DatabaseObject>,
+    name: &str,
+    kind: DatabaseObjectKind,
+) -> &'a mut DatabaseObject {
+    if nodes.last().map(|node| node.name.as_str()) != Some(name) {
+        nodes.push(DatabaseObject {
+            name: name.to_string(),
+            kind,
+            row_estimate: None,
+            children: Vec::new(),
+        });
+    }
+    nodes.last_mut().expect("node was just pushed")
+}
+
+fn leaf(name: &str, kind: DatabaseObjectKind, rows: Option<&str>) -> DatabaseObject {
+    DatabaseObject {
+        name: name.to_string(),
+        kind,
+        row_estimate: rows
+            .and_then(|rows| rows.parse::<i64>().ok())
+            .and_then(|rows| u64::try_from(rows).ok()),
+        children: Vec::new(),
+    }
+}
@@ -0,0 +1,139 @@
+use crate::core::{download, utils, FieldError, GlobalSettings};
+use std::path::Path;
+
+const THEMES: &[&str] = &["light", "dark"];
+const LOG_LEVELS: &[&str] = &["error", "warn", "info", "debug", "trace"];
+const BACKUP_FREQUENCIES: &[&str] = &["hourly", "daily", "weekly", "monthly"];
+
+/// 保留天数的上限，约 10 年
+const MAX_RETENTION_DAYS: u32 = 3650;
+/// 启动超时和指标采集间隔的上限（秒）
+const MAX_INTERVAL_SECS: u64 = 3600;
+
+/// 检查设置中的每个字段，返回所有不合法的字段
+pub fn validate(settings: &GlobalSettings) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let mut check = |field: &str, result: Result<(), String>| {
+        if let Err(message) = result {
+            errors.push(FieldError {
+                field: field.to_string(),
+                message,
+            });
+        }
+    };
+
+    check(
+        "default_storage_path",
+        check_storage_path(Path::new(&settings.default_storage_path)),
+    );
+    check("theme", one_of(&settings.theme, THEMES));
+    check("language", check_language(&settings.language));
+    check("log_level", one_of(&settings.log_level, LOG_LEVELS));
+    check(
+        "backup_frequency",
+        one_of(&settings.backup_frequency, BACKUP_FREQUENCIES),
+    );
+    check(
+        "backup_retention_days",
+        in_range(settings.backup_retention_days, 1, MAX_RETENTION_DAYS),
+    );
+    check(
+        "log_retention_days",
+        in_range(settings.log_retention_days, 1, MAX_RETENTION_DAYS),
+    );
+    check(
+        "startup_timeout_secs",
+        in_range(settings.startup_timeout_secs, 1, MAX_INTERVAL_SECS),
+    );
+    // 为 0 时关闭指标采集
+    if settings.metrics_interval_secs != 0 {
+        check(
+            "metrics_interval_secs",
+            in_range(settings.metrics_interval_secs, 1, MAX_INTERVAL_SECS),
+        );
+    }
+    check(
+        "api_server_port",
+        in_range(settings.api_server_port, 1024, u16::MAX),
+    );
+    if let Some(proxy) = settings.download_proxy.as_deref() {
+        check(
+            "download_proxy",
+            check_url(proxy, &["http", "https", "socks5", "socks5h"]),
+        );
+    }
+    for (source, mirror) in &settings.download_mirrors {
+        let field = format!("download_mirrors.{}", source);
+        if !download::SOURCES.iter().any(|(name, _)| name == source) {
+            check(&field, Err(format!("Unknown download source {}", source)));
+        } else {
+            check(&field, check_url(mirror, &["http", "https"]));
+        }
+    }
+    if let Some(target) = &settings.remote_backup {
+        check(
+            "remote_backup.endpoint",
+            check_url(&target.endpoint, &["http", "https"]),
+        );
+        check("remote_backup.bucket", not_empty(&target.bucket));
+        check("remote_backup.access_key", not_empty(&target.access_key));
+    }
+    errors
+}
+
+/// 存储目录必须是绝对路径，已存在时必须可写，不存在时必须可以创建
+pub fn check_storage_path(path: &Path) -> Result<(), String> {
+    if !path.is_absolute() {
+        return Err("Must be an absolute path".to_string());
+    }
+    utils::check_writable(path).map_err(|e| format!("{:#}", e))
+}
+
+fn one_of(value: &str, allowed: &[&str]) -> Result<(), String> {
+    if allowed.contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("Must be one of {}", allowed.join(", ")))
+    }
+}
+
+fn in_range<T: PartialOrd + std::fmt::Display>(value: T, min: T, max: T) -> Result<(), String> {
+    if value < min || value > max {
+        Err(format!("Must be between {} and {}", min, max))
+    } else {
+        Ok(())
+    }
+}
+
+fn not_empty(value: &str) -> Result<(), String> {
+    if value.trim().is_empty() {
+        Err("Must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// 语言标签，如 `en`、`zh-CN`
+fn check_language(value: &str) -> Result<(), String> {
+    let valid = !value.is_empty()
+        && value.len() <= 35
+        && value
+            .split(['-', '_'])
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("{} is not a valid language tag", value))
+    }
+}
+
+fn check_url(value: &str, schemes: &[&str]) -> Result<(), String> {
+    let url = url::Url::parse(value).map_err(|e| format!("Invalid URL {}: {}", value, e))?;
+    if !schemes.contains(&url.scheme()) {
+        return Err(format!("URL scheme must be one of {}", schemes.join(", ")));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(format!("URL {} has no host", value));
+    }
+    Ok(())
+}
@@ -0,0 +1,383 @@
+use crate::core::{log_tail, query, DatabaseInfo, DatabaseType};
+use anyhow::{bail, Context, Result};
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// 启用慢查询日志时使用的阈值，已由用户配置时沿用原值
+const SLOW_QUERY_THRESHOLD_MS: u64 = 1000;
+
+/// 每个日志文件最多读取的末尾行数
+const LOG_TAIL_LINES: usize = 20_000;
+
+/// PostgreSQL 日志目录中最多读取的文件数，从最新的开始
+const MAX_LOG_FILES: usize = 5;
+
+/// 最多返回的慢查询条数
+const ENTRY_LIMIT: usize = 500;
+
+/// 慢查询日志中的一条记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlowQuery {
+    /// 执行时间（Unix 时间戳，秒），日志中没有时间时为 None
+    pub timestamp: Option<i64>,
+    pub database: Option<String>,
+    /// 合并空白后的语句
+    pub query: String,
+    pub duration_ms: f64,
+    /// 返回的行数，只有 MySQL 记录
+    pub rows_sent: Option<u64>,
+    /// 扫描的行数，只有 MySQL 记录
+    pub rows_examined: Option<u64>,
+}
+
+/// 慢查询日志的读取结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlowQueryLog {
+    /// 当前生效的阈值（毫秒）
+    pub threshold_ms: u64,
+    /// 读取的日志文件，服务端未写入日志文件时为 None
+    pub log_file: Option<String>,
+    /// 本次调用才启用慢查询日志，之前的慢查询没有记录
+    pub newly_enabled: bool,
+    /// PostgreSQL 需要重启后才会把日志写入文件
+    pub restart_required: bool,
+    /// 最新的在前
+    pub entries: Vec<SlowQuery>,
+}
+
+/// 确保慢查询日志已启用，并读取 `since`（Unix 时间戳，秒）之后的慢查询
+///
+/// MySQL 通过 `SET GLOBAL` 启用，重启实例后会在下次读取时重新启用；
+/// PostgreSQL 通过 `ALTER SYSTEM` 设置 `log_min_duration_statement`，配置会持久保存。
+pub fn read(db_info: &DatabaseInfo, since: Option<i64>) -> Result<SlowQueryLog> {
+    let mut log = match db_info.db_type {
+        DatabaseType::MySQL | DatabaseType::MariaDB => read_mysql(db_info)?,
+        DatabaseType::PostgreSQL => read_postgresql(db_info)?,
+        _ => bail!(
+            "Slow query logs are not supported for {}",
+            db_info.db_type.display_name()
+        ),
+    };
+
+    if let Some(since) = since {
+        log.entries
+            .retain(|entry| entry.timestamp.is_some_and(|t| t >= since));
+    }
+    log.entries
+        .sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
+    log.entries.truncate(ENTRY_LIMIT);
+    Ok(log)
+}
+
+/// 执行查询并返回第一行
+fn first_row(db_info: &DatabaseInfo, sql: &str) -> Result<Vec<Option<String>>> {
+    query::execute(db_info, None, sql, 1, query::DEFAULT_QUERY_TIMEOUT)?
+        .rows
+        .into_iter()
+        .next()
+        .context("The query returned no rows")
+}
+
+fn execute(db_info: &DatabaseInfo, sql: &str) -> Result<()> {
+    query::execute(db_info, None, sql, 1, query::DEFAULT_QUERY_TIMEOUT)
+        .with_context(|| format!("Failed to run `{}`", sql))
+        .map(|_| ())
+}
+
+fn read_mysql(db_info: &DatabaseInfo) -> Result<SlowQueryLog> {
+    let row = first_row(
+        db_info,
+        "SELECT @@GLOBAL.slow_query_log, @@GLOBAL.long_query_time, \
+         @@GLOBAL.slow_query_log_file, @@GLOBAL.log_output, @@GLOBAL.datadir",
+    )?;
+    let value = |index: usize| row.get(index).cloned().flatten().unwrap_or_default();
+
+    let enabled = value(0) == "1" || value(0).eq_ignore_ascii_case("ON");
+    let logs_to_file = value(3).to_ascii_uppercase().contains("FILE");
+    let mut threshold_ms = value(1)
+        .parse::<f64>()
+        .map_or(SLOW_QUERY_THRESHOLD_MS, |secs| (secs * 1000.0) as u64);
+    if !enabled {
+        execute(
+            db_info,
+            &format!(
+                "SET GLOBAL long_query_time = {}",
+                SLOW_QUERY_THRESHOLD_MS as f64 / 1000.0
+            ),
+        )?;
+        threshold_ms = SLOW_QUERY_THRESHOLD_MS;
+    }
+    if !logs_to_file {
+        execute(db_info, "SET GLOBAL log_output = 'FILE'")?;
+    }
+    if !enabled {
+        execute(db_info, "SET GLOBAL slow_query_log = ON")?;
+    }
+
+    // 相对路径位于数据目录下
+    let log_file = Path::new(&value(4)).join(value(2));
+    let lines = log_tail::read_last_lines(&log_file, LOG_TAIL_LINES)
+        .with_context(|| format!("Failed to read {}", log_file.display()))?;
+    Ok(SlowQueryLog {
+        threshold_ms,
+        log_file: Some(log_file.to_string_lossy().to_string()),
+        newly_enabled: !enabled,
+        restart_required: false,
+        entries: parse_mysql(&lines),
+    })
+}
+
+fn read_postgresql(db_info: &DatabaseInfo) -> Result<SlowQueryLog> {
+    let row = first_row(
+        db_info,
+        "SELECT (SELECT setting FROM pg_settings WHERE name = 'log_min_duration_statement'), \
+         pg_current_logfile(), current_setting('data_directory'), \
+         current_setting('logging_collector')",
+    )?;
+    let value = |index: usize| row.get(index).cloned().flatten().unwrap_or_default();
+
+    let configured = value(0).parse::<i64>().ok().filter(|ms| *ms >= 0);
+    if configured.is_none() {
+        execute(
+            db_info,
+            &format!(
+                "ALTER SYSTEM SET log_min_duration_statement = {}",
+                SLOW_QUERY_THRESHOLD_MS
+            ),
+        )?;
+    }
+
+    // 未开启日志收集时输出到 stderr，由启动方式决定写到哪里，开启后写到实例的日志目录
+    let mut restart_required = false;
+    let log_dir = Path::new(&db_info.log_path).parent().map(Path::to_path_buf);
+    if value(3) != "on" {
+        if let Some(log_dir) = log_dir.as_ref().filter(|dir| dir.is_dir()) {
+            execute(db_info, "ALTER SYSTEM SET logging_collector = on")?;
+            execute(
+                db_info,
+                &format!(
+                    "ALTER SYSTEM SET log_directory = '{}'",
+                    log_dir.to_string_lossy().replace('\'', "''")
+                ),
+            )?;
+            restart_required = true;
+        }
+    }
+    if configured.is_none() || restart_required {
+        execute(db_info, "SELECT pg_reload_conf()")?;
+    }
+
+    let current = Some(value(1))
+        .filter(|file| !file.is_empty())
+        .map(|file| Path::new(&value(2)).join(file));
+    let files = match current.as_deref().and_then(Path::parent) {
+        Some(dir) => recent_log_files(dir),
+        None => vec![PathBuf::from(&db_info.log_path)],
+    };
+    let mut entries = Vec::new();
+    for file in &files {
+        if let Ok(lines) = log_tail::read_last_lines(file, LOG_TAIL_LINES) {
+            entries.extend(parse_postgresql(&lines));
+        }
+    }
+
+    Ok(SlowQueryLog {
+        threshold_ms: configured.map_or(SLOW_QUERY_THRESHOLD_MS, |ms| ms as u64),
+        log_file: current.map(|file| file.to_string_lossy().to_string()),
+        newly_enabled: configured.is_none(),
+        restart_required,
+        entries,
+    })
+}
+
+/// 日志目录中最近修改的 `.log` 文件
+fn recent_log_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<(PathBuf, u64)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "log"))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            let secs = modified.duration_since(UNIX_EPOCH).ok()?.as_secs();
+            Some((entry.path(), secs))
+        })
+        .collect();
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    files
+        .into_iter()
+        .take(MAX_LOG_FILES)
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// 取出 `label` 之后以空白分隔的第一个值
+fn field<'a>(line: &'a str, label: &str) -> Option<&'a str> {
+    let rest = &line[line.find(label)? + label.len()..];
+    rest.split_whitespace().next()
+}
+
+/// 合并语句中的空白，去掉末尾的分号
+pub(crate) fn normalize(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(';')
+        .trim_end()
+        .to_string()
+}
+
+/// MySQL / MariaDB 慢查询日志，每条记录以 `#` 开头的注释行描述，之后是 `use`、
+/// `SET timestamp` 和语句本身：
+///
+/// ```text
+/// # Time: 2024-01-01T12:00:00.123456Z
+/// # User@Host: root[root] @ localhost []  Id:     8
+/// # Query_time: 2.000215  Lock_time: 0.000000 Rows_sent: 1  Rows_examined: 0
+/// use app;
+/// SET timestamp=1704110400;
+/// select sleep(2);
+/// ```
+fn parse_mysql(lines: &[String]) -> Vec<SlowQuery> {
+    let mut entries = Vec::new();
+    let mut current: Option<SlowQuery> = None;
+
+    let mut finish = |current: &mut Option<SlowQuery>| {
+        if let Some(mut entry) = current.take() {
+            entry.query = normalize(&entry.query);
+            // 管理命令（如 `# administrator command: Quit;`）没有语句
+            if !entry.query.is_empty() {
+                entries.push(entry);
+            }
+        }
+    };
+
+    for line in lines {
+        if let Some(comment) = line.strip_prefix('#') {
+            if current
+                .as_ref()
+                .is_some_and(|entry| !entry.query.is_empty())
+            {
+                finish(&mut current);
+            }
+            if let Some(secs) = field(comment, "Query_time:").and_then(|v| v.parse::<f64>().ok()) {
+                current = Some(SlowQuery {
+                    timestamp: None,
+                    database: None,
+                    query: String::new(),
+                    duration_ms: secs * 1000.0,
+                    rows_sent: field(comment, "Rows_sent:").and_then(|v| v.parse().ok()),
+                    rows_examined: field(comment, "Rows_examined:").and_then(|v| v.parse().ok()),
+                });
+            }
+            continue;
+        }
+        // 实例重启时写入的文件头
+        if line.contains(", Version: ") && line.contains("started with:") {
+            finish(&mut current);
+            continue;
+        }
+        let Some(entry) = current.as_mut() else {
+            continue;
+        };
+        let trimmed = line.trim();
+        if entry.query.is_empty() {
+            if let Some(db) = trimmed
+                .strip_prefix("use ")
+                .and_then(|rest| rest.strip_suffix(';'))
+            {
+                entry.database = Some(db.trim_matches('`').to_string());
+                continue;
+            }
+            if let Some(secs) = trimmed
+                .strip_prefix("SET timestamp=")
+                .and_then(|rest| rest.trim_end_matches(';').parse().ok())
+            {
+                entry.timestamp = Some(secs);
+                continue;
+            }
+        }
+        entry.query.push_str(trimmed);
+        entry.query.push('\n');
+    }
+    finish(&mut current);
+    entries
+}
+
+/// PostgreSQL 日志中 `log_min_duration_statement` 产生的记录，多行语句的后续行以制表符开头：
+///
+/// ```text
+/// 2024-01-01 12:00:00.123 UTC [1234] LOG:  duration: 1503.221 ms  statement: select pg_sleep(1.5);
+/// ```
+fn parse_postgresql(lines: &[String]) -> Vec<SlowQuery> {
+    let mut entries: Vec<SlowQuery> = Vec::new();
+    let mut continuing = false;
+
+    for line in lines {
+        if let Some(rest) = line.strip_prefix('\t') {
+            if continuing {
+                if let Some(entry) = entries.last_mut() {
+                    entry.query.push(' ');
+                    entry.query.push_str(rest);
+                }
+            }
+            continue;
+        }
+        if let Some(entry) = entries.last_mut().filter(|_| continuing) {
+            entry.query = normalize(&entry.query);
+        }
+        continuing = false;
+
+        let Some(duration_ms) = field(line, "duration: ").and_then(|v| v.parse().ok()) else {
+            continue;
+        };
+        // 扩展协议的语句记录为 `execute <name>: ...`，只记录耗时（log_duration）的行没有语句
+        let query = ["  statement: ", "  execute "].iter().find_map(|marker| {
+            let rest = &line[line.find(marker)? + marker.len()..];
+            Some(match *marker {
+                "  execute " => rest.split_once(": ").map_or(rest, |(_, query)| query),
+                _ => rest,
+            })
+        });
+        let Some(query) = query else {
+            continue;
+        };
+        entries.push(SlowQuery {
+            timestamp: parse_postgresql_timestamp(line),
+            database: field(line, "db=")
+                .map(|v| v.split(',').next().unwrap_or_default().to_string())
+                .filter(|db| !db.is_empty() && db != "[unknown]"),
+            query: query.to_string(),
+            duration_ms,
+            rows_sent: None,
+            rows_examined: None,
+        });
+        continuing = true;
+    }
+    if let Some(entry) = entries.last_mut().filter(|_| continuing) {
+        entry.query = normalize(&entry.query);
+    }
+    entries
+}
+
+/// 行首的 `%m` / `%t` 时间，如 `2024-01-01 12:00:00.123 UTC`，非 UTC 的时区按本地时间处理
+fn parse_postgresql_timestamp(line: &str) -> Option<i64> {
+    let time = NaiveDateTime::parse_from_str(line.get(..19)?, "%Y-%m-%d %H:%M:%S").ok()?;
+    let zone = line[19..]
+        .trim_start_matches(|c: char| c == '.' || c.is_ascii_digit())
+        .split_whitespace()
+        .next()
+        .unwrap_or_default();
+    if zone == "UTC" || zone == "GMT" {
+        Some(Utc.from_utc_datetime(&time).timestamp())
+    } else {
+        Local
+            .from_local_datetime(&time)
+            .earliest()
+            .map(|t| t.timestamp())
+    }
+}
@@ -0,0 +1,218 @@
+use crate::core::{relocate, utils, DataSnapshot, DatabaseInfo};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// 快照说明的最大长度
+const MAX_LABEL_LEN: usize = 100;
+
+/// 快照目录中的元数据文件，数据复制完成后才写入，没有此文件的目录视为不完整的快照
+const METADATA_FILE: &str = "snapshot.json";
+
+/// 快照目录中保存数据副本的子目录
+const DATA_DIR: &str = "data";
+
+/// 为已停止的数据库创建数据目录快照
+pub fn create(storage_path: &Path, db_info: &DatabaseInfo, label: &str) -> Result<DataSnapshot> {
+    let label = label.trim();
+    if label.is_empty() || label.chars().count() > MAX_LABEL_LEN {
+        bail!("Snapshot label must be 1-{} characters", MAX_LABEL_LEN);
+    }
+    let data_dir = Path::new(&db_info.data_path);
+    if !data_dir.is_dir() {
+        bail!("Data directory not found: {}", data_dir.display());
+    }
+
+    let id = utils::generate_id();
+    let snapshot_dir = utils::get_db_snapshot_path(storage_path, &db_info.id).join(&id);
+    utils::ensure_dir(&snapshot_dir)?;
+    let result = clone_dir(data_dir, &snapshot_dir.join(DATA_DIR)).and_then(|()| {
+        let snapshot = DataSnapshot {
+            id,
+            database_id: db_info.id.clone(),
+            label: label.to_string(),
+            size: utils::dir_size(&snapshot_dir.join(DATA_DIR)),
+            created_at: utils::get_timestamp(),
+        };
+        fs::write(
+            snapshot_dir.join(METADATA_FILE),
+            serde_json::to_string_pretty(&snapshot)?,
+        )
+        .context("Failed to write snapshot metadata")?;
+        Ok(snapshot)
+    });
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&snapshot_dir);
+    }
+    result
+}
+
+/// 列出数据库的快照，最新的在前
+pub fn list(storage_path: &Path, db_id: &str) -> Result<Vec<DataSnapshot>> {
+    let dir = utils::get_db_snapshot_path(storage_path, db_id);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let metadata = entry?.path().join(METADATA_FILE);
+        let Ok(content) = fs::read_to_string(&metadata) else {
+            continue;
+        };
+        // 元数据损坏的快照无法恢复，不显示在列表中
+        if let Ok(snapshot) = serde_json::from_str::<DataSnapshot>(&content) {
+            snapshots.push(snapshot);
+        }
+    }
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(snapshots)
+}
+
+/// 用快照替换已停止数据库的数据目录
+///
+/// 先把快照复制到数据目录旁的临时目录，复制完成后再替换，复制或替换失败时原数据保持不变。
+pub fn restore(storage_path: &Path, db_info: &DatabaseInfo, snapshot_id: &str) -> Result<()> {
+    let source = snapshot_data_dir(storage_path, &db_info.id, snapshot_id)?;
+    let data_dir = PathBuf::from(&db_info.data_path);
+    let staging = PathBuf::from(format!("{}.restoring", db_info.data_path));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)
+            .with_context(|| format!("Failed to remove {}", staging.display()))?;
+    }
+    if let Err(err) = clone_dir(&source, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err);
+    }
+
+    let result = replace_dir(&staging, &data_dir);
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+/// 用 `staging` 替换 `data_dir`：先把原目录改名移开，移入成功后再删除，失败时移回原目录
+fn replace_dir(staging: &Path, data_dir: &Path) -> Result<()> {
+    let previous = PathBuf::from(format!("{}.pre-restore", data_dir.display()));
+    if previous.exists() {
+        fs::remove_dir_all(&previous)
+            .with_context(|| format!("Failed to remove {}", previous.display()))?;
+    }
+    if data_dir.exists() {
+        fs::rename(data_dir, &previous)
+            .with_context(|| format!("Failed to move {} aside", data_dir.display()))?;
+    }
+    if let Err(err) = fs::rename(staging, data_dir) {
+        if previous.exists() {
+            let _ = fs::rename(&previous, data_dir);
+        }
+        return Err(err)
+            .with_context(|| format!("Failed to move the snapshot to {}", data_dir.display()));
+    }
+    if previous.exists() {
+        let _ = fs::remove_dir_all(&previous);
+    }
+    Ok(())
+}
+
+/// 删除一个快照
+pub fn delete(storage_path: &Path, db_id: &str, snapshot_id: &str) -> Result<()> {
+    let source = snapshot_data_dir(storage_path, db_id, snapshot_id)?;
+    let snapshot_dir = source.parent().unwrap_or(&source);
+    fs::remove_dir_all(snapshot_dir)
+        .with_context(|| format!("Failed to remove {}", snapshot_dir.display()))
+}
+
+/// 删除数据库的全部快照，卸载实例时调用
+pub fn delete_all(storage_path: &Path, db_id: &str) -> Result<()> {
+    let dir = utils::get_db_snapshot_path(storage_path, db_id);
+    if dir.is_dir() {
+        fs::remove_dir_all(&dir).with_context(|| format!("Failed to remove {}", dir.display()))?;
+    }
+    Ok(())
+}
+
+/// 快照的数据目录，ID 只能是快照列表中出现的目录名
+fn snapshot_data_dir(storage_path: &Path, db_id: &str, snapshot_id: &str) -> Result<PathBuf> {
+    if !list(storage_path, db_id)?
+        .iter()
+        .any(|snapshot| snapshot.id == snapshot_id)
+    {
+        bail!("Snapshot not found");
+    }
+    Ok(utils::get_db_snapshot_path(storage_path, db_id)
+        .join(snapshot_id)
+        .join(DATA_DIR))
+}
+
+/// 复制目录，优先使用写时复制的克隆（Linux 的 reflink、macOS APFS 的 clonefile）
+///
+/// 文件系统不支持克隆时逐个复制文件并校验。不使用硬链接，数据库会原地修改数据文件。
+fn clone_dir(source: &Path, target: &Path) -> Result<()> {
+    let mut command = if cfg!(target_os = "linux") {
+        let mut command = Command::new("cp");
+        command.arg("-a").arg("--reflink=auto");
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("cp");
+        command.arg("-c").arg("-R").arg("-p");
+        command
+    } else {
+        return relocate::copy_verified(source, target);
+    };
+    let cloned = command
+        .arg(source)
+        .arg(target)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if cloned {
+        return Ok(());
+    }
+
+    if target.exists() {
+        fs::remove_dir_all(target)
+            .with_context(|| format!("Failed to remove {}", target.display()))?;
+    }
+    relocate::copy_verified(source, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_dir_swaps_in_staging() {
+        let dir = tempfile::tempdir().unwrap();
+        let (staging, data_dir) = (dir.path().join("data.restoring"), dir.path().join("data"));
+        fs::create_dir(&staging).unwrap();
+        fs::write(staging.join("file"), "snapshot").unwrap();
+        fs::create_dir(&data_dir).unwrap();
+        fs::write(data_dir.join("file"), "current").unwrap();
+
+        replace_dir(&staging, &data_dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(data_dir.join("file")).unwrap(),
+            "snapshot"
+        );
+        assert!(!staging.exists());
+        assert!(!dir.path().join("data.pre-restore").exists());
+    }
+
+    #[test]
+    fn replace_dir_rolls_back_on_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let (staging, data_dir) = (dir.path().join("missing"), dir.path().join("data"));
+        fs::create_dir(&data_dir).unwrap();
+        fs::write(data_dir.join("file"), "current").unwrap();
+
+        assert!(replace_dir(&staging, &data_dir).is_err());
+        assert_eq!(
+            fs::read_to_string(data_dir.join("file")).unwrap(),
+            "current"
+        );
+        assert!(!dir.path().join("data.pre-restore").exists());
+    }
+}
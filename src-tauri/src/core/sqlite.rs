@@ -0,0 +1,176 @@
+use crate::core::backend::{self, DatabaseBackend, InstallOptions};
+use crate::core::{
+    network, utils, BackendKind, DatabaseInfo, DatabaseStatus, DatabaseType, SqliteFile,
+};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// 安装时创建的默认数据库文件，连接字符串也指向该文件
+pub const DEFAULT_FILE: &str = "main.db";
+
+/// SQLite 文件头，空文件同样是合法的空数据库
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// SQLite 后端：没有服务进程，实例就是数据目录，安装后始终视为可用
+pub struct SqliteBackend;
+
+impl DatabaseBackend for SqliteBackend {
+    fn install(
+        &self,
+        db_type: &DatabaseType,
+        storage_path: &Path,
+        options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let instance_dir = utils::get_instance_dir_name(db_type.as_str(), options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+
+        let db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options.instance_name.unwrap_or("SQLite").to_string(),
+            db_type: DatabaseType::SQLite,
+            backend: BackendKind::Native,
+            version: "3".to_string(),
+            install_path: String::new(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: String::new(),
+            port: 0,
+            username: None,
+            password: None,
+            credential_ref: None,
+            config: None,
+            status: DatabaseStatus::Running,
+            auto_start: false,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+        create_file(&db_info, DEFAULT_FILE)?;
+
+        Ok(db_info)
+    }
+
+    fn start(&self, _db_info: &DatabaseInfo) -> Result<()> {
+        bail!("SQLite has no server process to start")
+    }
+
+    fn stop(&self, _db_info: &DatabaseInfo) -> Result<()> {
+        bail!("SQLite has no server process to stop")
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        if Path::new(&db_info.data_path).is_dir() {
+            Some(DatabaseStatus::Running)
+        } else {
+            Some(DatabaseStatus::Stopped)
+        }
+    }
+
+    fn is_supervised(&self) -> bool {
+        false
+    }
+
+    fn uninstall(&self, db_info: &DatabaseInfo, _remove_binaries: bool) -> Result<()> {
+        backend::remove_instance_files(db_info)
+    }
+}
+
+/// 默认数据库文件的路径
+pub fn default_file_path(db_info: &DatabaseInfo) -> PathBuf {
+    Path::new(&db_info.data_path).join(DEFAULT_FILE)
+}
+
+/// 列出实例数据目录下的 .db 文件
+pub fn list_files(db_info: &DatabaseInfo) -> Result<Vec<SqliteFile>> {
+    list_files_with_extension(db_info, "db")
+}
+
+/// 在实例数据目录下创建空的数据库文件，`name` 不带扩展名时补上 `.db`
+pub fn create_file(db_info: &DatabaseInfo, name: &str) -> Result<SqliteFile> {
+    let path = new_file_path(db_info, name, "db")?;
+    fs::File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+    file_info(&path)
+}
+
+/// 列出实例数据目录下指定扩展名的文件，按文件名排序
+pub(crate) fn list_files_with_extension(
+    db_info: &DatabaseInfo,
+    extension: &str,
+) -> Result<Vec<SqliteFile>> {
+    let data_dir = Path::new(&db_info.data_path);
+    let entries =
+        fs::read_dir(data_dir).with_context(|| format!("Failed to read {}", data_dir.display()))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == extension) {
+            files.push(file_info(&path)?);
+        }
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+/// 校验文件名并返回实例数据目录下尚不存在的文件路径，`name` 不带扩展名时补上 `extension`
+pub(crate) fn new_file_path(
+    db_info: &DatabaseInfo,
+    name: &str,
+    extension: &str,
+) -> Result<PathBuf> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        bail!("Invalid database file name: {}", name);
+    }
+    let suffix = format!(".{}", extension);
+    let file_name = if name.ends_with(&suffix) {
+        name.to_string()
+    } else {
+        format!("{}{}", name, suffix)
+    };
+
+    let path = Path::new(&db_info.data_path).join(file_name);
+    if path.exists() {
+        bail!("Database file already exists: {}", path.display());
+    }
+    Ok(path)
+}
+
+/// 检查默认数据库文件是否为有效的 SQLite 文件
+pub fn check_file(db_info: &DatabaseInfo) -> Result<()> {
+    let path = default_file_path(db_info);
+    let mut header = Vec::with_capacity(SQLITE_HEADER.len());
+    fs::File::open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?
+        .take(SQLITE_HEADER.len() as u64)
+        .read_to_end(&mut header)?;
+    if !header.is_empty() && header != SQLITE_HEADER {
+        bail!("{} is not a SQLite database", path.display());
+    }
+    Ok(())
+}
+
+pub(crate) fn file_info(path: &Path) -> Result<SqliteFile> {
+    let metadata = fs::metadata(path)?;
+    Ok(SqliteFile {
+        name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: path.to_string_lossy().to_string(),
+        size: metadata.len(),
+        modified_at: metadata
+            .modified()
+            .ok()
+            .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339()),
+    })
+}
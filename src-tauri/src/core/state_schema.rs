@@ -0,0 +1,85 @@
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+/// 当前 state.json 的结构版本，修改 `AppStateData` 或其中的类型且无法通过 serde 默认值兼容时递增，
+/// 并在 [`MIGRATIONS`] 末尾加入对应的迁移
+pub const STATE_SCHEMA_VERSION: u32 = 1;
+
+/// 迁移函数，`MIGRATIONS[n]` 将版本 n 的状态升级到版本 n + 1
+type Migration = fn(&mut Value) -> Result<()>;
+
+const MIGRATIONS: &[Migration] = &[migrate_v0];
+
+/// 状态文件中记录的结构版本，早期版本没有此字段，视为版本 0
+pub fn schema_version(state: &Value) -> u32 {
+    state["schema_version"]
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .unwrap_or(0)
+}
+
+/// 依次执行迁移，将状态升级到当前版本，返回迁移前的版本
+pub fn migrate(state: &mut Value) -> Result<u32> {
+    if !state.is_object() {
+        bail!("State file is not a JSON object");
+    }
+    let from = schema_version(state);
+    if from > STATE_SCHEMA_VERSION {
+        bail!(
+            "State file has schema version {}, newer than the supported version {}; upgrade the app",
+            from,
+            STATE_SCHEMA_VERSION
+        );
+    }
+    for (version, migration) in MIGRATIONS.iter().enumerate().skip(from as usize) {
+        migration(state)?;
+        state["schema_version"] = Value::from(version as u32 + 1);
+    }
+    Ok(from)
+}
+
+/// 版本 0：没有版本号的早期状态文件
+///
+/// 后来加入的字段都有 serde 默认值，这里只补全缺少的列表，使旧文件与版本 1 的结构一致。
+fn migrate_v0(state: &mut Value) -> Result<()> {
+    for key in ["databases", "backups"] {
+        if state[key].is_null() {
+            state[key] = Value::Array(Vec::new());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn migrate_upgrades_unversioned_state() {
+        let mut state = json!({ "settings": {} });
+        assert_eq!(migrate(&mut state).unwrap(), 0);
+        assert_eq!(schema_version(&state), STATE_SCHEMA_VERSION);
+        assert_eq!(state["databases"], json!([]));
+        assert_eq!(state["backups"], json!([]));
+    }
+
+    #[test]
+    fn migrate_keeps_current_state() {
+        let mut state = json!({
+            "schema_version": STATE_SCHEMA_VERSION,
+            "databases": [{ "id": "a" }],
+            "backups": [],
+        });
+        let before = state.clone();
+        assert_eq!(migrate(&mut state).unwrap(), STATE_SCHEMA_VERSION);
+        assert_eq!(state, before);
+    }
+
+    #[test]
+    fn migrate_rejects_newer_or_invalid_state() {
+        let mut newer = json!({ "schema_version": STATE_SCHEMA_VERSION + 1 });
+        assert!(migrate(&mut newer).is_err());
+        assert!(migrate(&mut json!([])).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::core::health;
+use crate::core::{DatabaseInfo, DatabaseStatus, DatabaseType, SurrealStatementResult};
+use anyhow::{bail, Context, Result};
+use std::time::Duration;
+
+/// 安装后创建的默认命名空间
+pub const DEFAULT_NAMESPACE: &str = "local";
+
+/// 安装后创建的默认数据库
+pub const DEFAULT_DATABASE: &str = "local";
+
+/// 查询的默认超时时间
+pub const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 初始化前等待实例就绪的最长时间
+const READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 通过 HTTP `/sql` 接口执行 SurrealQL，以实例的 root 用户认证
+///
+/// 返回每条语句的结果；语句本身的错误记录在结果中，请求失败时返回 Err。
+pub fn execute(
+    db_info: &DatabaseInfo,
+    namespace: Option<&str>,
+    database: Option<&str>,
+    query: &str,
+    timeout: Duration,
+) -> Result<Vec<SurrealStatementResult>> {
+    if db_info.db_type != DatabaseType::SurrealDB {
+        bail!(
+            "SurrealQL queries are not supported for {}",
+            db_info.db_type.display_name()
+        );
+    }
+    if db_info.status != DatabaseStatus::Running {
+        bail!("{} must be running to run queries", db_info.name);
+    }
+    if query.trim().is_empty() {
+        bail!("The query is empty");
+    }
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()?;
+    let mut request = client
+        .post(format!("http://127.0.0.1:{}/sql", db_info.port))
+        .header("Accept", "application/json")
+        .basic_auth(
+            db_info.username.as_deref().unwrap_or("root"),
+            db_info.password.as_deref(),
+        )
+        .body(query.to_string());
+    // 没有选择命名空间或数据库时，语句需要自行 USE
+    if let Some(namespace) = namespace.filter(|ns| !ns.is_empty()) {
+        request = request.header("surreal-ns", namespace);
+        if let Some(database) = database.filter(|db| !db.is_empty()) {
+            request = request.header("surreal-db", database);
+        }
+    }
+
+    let response = request.send().context("Failed to connect to SurrealDB")?;
+    let status = response.status();
+    let body = response
+        .text()
+        .context("Failed to read SurrealDB response")?;
+    if !status.is_success() {
+        // 解析失败等请求级错误以 JSON 对象返回说明
+        let message = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|v| {
+                v.get("information")
+                    .and_then(|i| i.as_str())
+                    .map(String::from)
+            })
+            .unwrap_or(body);
+        bail!("{}: {}", status, message.trim());
+    }
+    serde_json::from_str(&body)
+        .with_context(|| format!("Unexpected SurrealDB response: {}", body.trim()))
+}
+
+/// 为新实例创建默认的命名空间、数据库和同名的数据库级用户
+///
+/// 数据库级用户使用实例的用户名和密码，应用可以只登录到默认数据库而不使用 root。
+/// 已存在的定义会被跳过，可以重复执行。
+pub fn initialize(db_info: &DatabaseInfo) -> Result<()> {
+    health::wait_until_ready(db_info, READY_TIMEOUT)?;
+
+    let mut query = format!(
+        "DEFINE NAMESPACE IF NOT EXISTS {ns};\n\
+        USE NS {ns};\n\
+        DEFINE DATABASE IF NOT EXISTS {db};\n",
+        ns = DEFAULT_NAMESPACE,
+        db = DEFAULT_DATABASE
+    );
+    if let (Some(username), Some(password)) = (
+        db_info.username.as_deref().filter(|u| !u.is_empty()),
+        db_info.password.as_deref().filter(|p| !p.is_empty()),
+    ) {
+        query.push_str(&format!(
+            "USE NS {ns} DB {db};\n\
+            DEFINE USER IF NOT EXISTS {user} ON DATABASE PASSWORD {pass} ROLES OWNER;\n",
+            ns = DEFAULT_NAMESPACE,
+            db = DEFAULT_DATABASE,
+            user = escape_ident(username),
+            pass = serde_json::to_string(password)?
+        ));
+    }
+
+    let results = execute(db_info, None, None, &query, DEFAULT_QUERY_TIMEOUT)?;
+    if let Some(failed) = results.iter().find(|r| r.status != "OK") {
+        let message = match &failed.result {
+            serde_json::Value::String(message) => message.clone(),
+            other => other.to_string(),
+        };
+        bail!("Failed to initialize {}: {}", db_info.name, message);
+    }
+    Ok(())
+}
+
+/// 用反引号包裹标识符，转义其中的反引号和反斜杠
+fn escape_ident(ident: &str) -> String {
+    format!("`{}`", ident.replace('\\', "\\\\").replace('`', "\\`"))
+}
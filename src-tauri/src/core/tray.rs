@@ -0,0 +1,85 @@
+use crate::core::{DatabaseInfo, DatabaseStatus};
+
+/// 托盘菜单项对应的操作
+///
+/// 菜单项 ID 为操作名，针对单个数据库的操作为 `<操作名>:<数据库 ID>`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayAction {
+    /// 显示主窗口
+    Show,
+    StartAll,
+    StopAll,
+    Quit,
+    Start(String),
+    Stop(String),
+    Restart(String),
+    /// 用系统默认程序打开数据库日志
+    OpenLogs(String),
+}
+
+impl TrayAction {
+    /// 菜单项 ID
+    pub fn id(&self) -> String {
+        match self {
+            TrayAction::Show => "show".to_string(),
+            TrayAction::StartAll => "start-all".to_string(),
+            TrayAction::StopAll => "stop-all".to_string(),
+            TrayAction::Quit => "quit".to_string(),
+            TrayAction::Start(id) => format!("start:{}", id),
+            TrayAction::Stop(id) => format!("stop:{}", id),
+            TrayAction::Restart(id) => format!("restart:{}", id),
+            TrayAction::OpenLogs(id) => format!("logs:{}", id),
+        }
+    }
+
+    /// 由菜单项 ID 解析操作，子菜单等没有操作的菜单项返回 `None`
+    pub fn parse(id: &str) -> Option<Self> {
+        match id {
+            "show" => return Some(TrayAction::Show),
+            "start-all" => return Some(TrayAction::StartAll),
+            "stop-all" => return Some(TrayAction::StopAll),
+            "quit" => return Some(TrayAction::Quit),
+            _ => {}
+        }
+        let (action, db_id) = id.split_once(':')?;
+        let db_id = db_id.to_string();
+        match action {
+            "start" => Some(TrayAction::Start(db_id)),
+            "stop" => Some(TrayAction::Stop(db_id)),
+            "restart" => Some(TrayAction::Restart(db_id)),
+            "logs" => Some(TrayAction::OpenLogs(db_id)),
+            _ => None,
+        }
+    }
+}
+
+/// 托盘菜单中的一个数据库
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrayDatabase {
+    pub id: String,
+    /// 子菜单标题，如 `my-redis (running)`
+    pub label: String,
+    pub can_start: bool,
+    pub can_stop: bool,
+    pub has_log: bool,
+}
+
+impl TrayDatabase {
+    pub fn new(db_info: &DatabaseInfo) -> Self {
+        let status = &db_info.status;
+        Self {
+            id: db_info.id.clone(),
+            label: format!("{} ({})", db_info.name, status.label()),
+            can_start: matches!(status, DatabaseStatus::Stopped | DatabaseStatus::Crashed),
+            can_stop: *status == DatabaseStatus::Running,
+            has_log: !db_info.log_path.is_empty(),
+        }
+    }
+}
+
+/// 按名称排序的托盘菜单数据库列表，内容不变时无需重建菜单
+pub fn databases(databases: &[DatabaseInfo]) -> Vec<TrayDatabase> {
+    let mut entries: Vec<TrayDatabase> = databases.iter().map(TrayDatabase::new).collect();
+    entries.sort_by_cached_key(|entry| entry.label.to_lowercase());
+    entries
+}
@@ -1,3 +1,4 @@
+use crate::core::{network, AppError};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -13,6 +14,16 @@ pub enum DatabaseType {
     SurrealDB,
     Neo4j,
     SeekDB,
+    OpenSearch,
+    InfluxDB,
+    Cassandra,
+    MariaDB,
+    /// 无需服务进程，实例为 data 目录下的一组 .duckdb 文件，可按需启动 HTTP 服务
+    DuckDB,
+    /// 无服务进程，实例为 data 目录下的一组 .db 文件
+    SQLite,
+    /// 用户注册的自定义服务，由应用按启动命令运行并通过 PID 文件管理
+    Custom,
 }
 
 impl DatabaseType {
@@ -26,6 +37,13 @@ impl DatabaseType {
             DatabaseType::SurrealDB => "surrealdb",
             DatabaseType::Neo4j => "neo4j",
             DatabaseType::SeekDB => "seekdb",
+            DatabaseType::OpenSearch => "opensearch",
+            DatabaseType::InfluxDB => "influxdb",
+            DatabaseType::Cassandra => "cassandra",
+            DatabaseType::MariaDB => "mariadb",
+            DatabaseType::DuckDB => "duckdb",
+            DatabaseType::SQLite => "sqlite",
+            DatabaseType::Custom => "custom",
         }
     }
 
@@ -39,6 +57,13 @@ impl DatabaseType {
             DatabaseType::SurrealDB => "SurrealDB",
             DatabaseType::Neo4j => "Neo4j",
             DatabaseType::SeekDB => "SeekDB",
+            DatabaseType::OpenSearch => "OpenSearch",
+            DatabaseType::InfluxDB => "InfluxDB",
+            DatabaseType::Cassandra => "Cassandra",
+            DatabaseType::MariaDB => "MariaDB",
+            DatabaseType::DuckDB => "DuckDB",
+            DatabaseType::SQLite => "SQLite",
+            DatabaseType::Custom => "Custom",
         }
     }
 
@@ -52,6 +77,12 @@ impl DatabaseType {
             DatabaseType::SurrealDB => 8000,
             DatabaseType::Neo4j => 7474,
             DatabaseType::SeekDB => 8080,
+            DatabaseType::OpenSearch => 9200,
+            DatabaseType::InfluxDB => 8086,
+            DatabaseType::Cassandra => 9042,
+            DatabaseType::MariaDB => 3307,
+            DatabaseType::DuckDB => 9999,
+            DatabaseType::SQLite | DatabaseType::Custom => 0,
         }
     }
 }
@@ -63,6 +94,44 @@ pub enum DatabaseStatus {
     Running,
     Stopped,
     NotInstalled,
+    /// 已发起启动，等待数据库可以连接
+    Starting,
+    /// 正在停止
+    Stopping,
+    /// 进程意外退出
+    Crashed,
+}
+
+impl DatabaseStatus {
+    /// 命令行和托盘菜单中显示的状态
+    pub fn label(&self) -> &'static str {
+        match self {
+            DatabaseStatus::Running => "running",
+            DatabaseStatus::Stopped => "stopped",
+            DatabaseStatus::NotInstalled => "not installed",
+            DatabaseStatus::Starting => "starting",
+            DatabaseStatus::Stopping => "stopping",
+            DatabaseStatus::Crashed => "crashed",
+        }
+    }
+}
+
+/// 数据库的运行方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    /// 本机安装的二进制或 Homebrew 服务
+    #[default]
+    Native,
+    /// Docker/Podman 容器
+    Docker,
+    /// 系统包管理器安装的服务（Linux 上由 systemd 管理，Windows 上为 Windows 服务）
+    Package,
+    /// macOS 上由应用生成的 LaunchAgent 管理的本机安装
+    #[serde(rename = "launch_agent")]
+    LaunchAgent,
+    /// 系统中已有的安装，由系统服务管理器管理，通过自动发现注册
+    External,
 }
 
 /// 数据库信息结构
@@ -72,24 +141,72 @@ pub struct DatabaseInfo {
     pub name: String,
     #[serde(rename = "type")]
     pub db_type: DatabaseType,
+    #[serde(default)]
+    pub backend: BackendKind,
     pub version: String,
+    /// 本机安装时为二进制目录，容器运行时为镜像名，包管理器安装时为 `<包管理器>:<包名>`，
+    /// 外部安装时为其系统服务，如 `windows-service:<服务名>`、`systemd:<单元名>`
     pub install_path: String,
     pub data_path: String,
     pub log_path: String,
     pub port: u16,
     pub username: Option<String>,
     pub password: Option<String>,
+    /// 密码保存在系统钥匙串时的引用，此时 state.json 中不保存明文密码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_ref: Option<String>,
     pub config: Option<String>,
     pub status: DatabaseStatus,
     pub auto_start: bool,
+    /// 启动前需要先就绪的数据库 ID
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// 启动数据库进程时额外设置的环境变量，如 `SURREAL_CAPS_ALLOW_ALL=true`
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// 追加到数据库进程启动命令末尾的参数，如 `--innodb-flush-method=fsync`
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    /// 定时启动、停止的计划，如工作日 9:00 启动、19:00 停止
+    #[serde(default)]
+    pub schedules: Vec<DatabaseSchedule>,
+    /// 停止时由应用代为监听端口，收到第一个连接时自动启动数据库并转发该连接
+    #[serde(default)]
+    pub on_demand: bool,
+    /// 为副本时复制的源数据库 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replica_of: Option<String>,
+    /// 数据库监听的地址，默认只接受本机连接，开放到网络时为 `0.0.0.0`
+    #[serde(default = "default_bind_address")]
+    pub bind_address: String,
     pub pid: Option<u32>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// 数据库的定时启停计划，按本地时间执行
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DatabaseSchedule {
+    pub action: ScheduleAction,
+    /// 执行时间，格式为 `HH:MM`
+    pub time: String,
+    /// 执行的星期，1 为周一，7 为周日，为空时每天执行
+    #[serde(default)]
+    pub days: Vec<u8>,
+}
+
+/// 定时计划执行的操作
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleAction {
+    Start,
+    Stop,
+}
+
 /// 数据库配置参数
+///
+/// `config` 中的键值会合并到实例的配置文件中，键的写法见 [`crate::core::config_file::apply_overrides`]。
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[allow(dead_code)]
 pub struct DatabaseConfig {
     pub port: Option<u16>,
     pub data_path: Option<String>,
@@ -98,21 +215,163 @@ pub struct DatabaseConfig {
     pub config: Option<HashMap<String, String>>,
 }
 
+/// 资源配置预设，按本机内存计算缓存大小和连接数
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigPreset {
+    /// 占用约 1/16 内存，适合与其他服务共用的开发机
+    Small,
+    /// 占用约 1/8 内存
+    Medium,
+    /// 占用约 1/4 内存，适合专门运行该数据库的机器
+    Large,
+}
+
+/// 备份方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupMode {
+    /// 逻辑备份（SQL / archive 导出）
+    #[default]
+    Logical,
+    /// 物理备份（数据文件级别，目前仅支持 PostgreSQL 的 pg_basebackup）
+    Physical,
+    /// 数据导出（SQL / CSV / JSON），只记录在备份历史中，不能用于恢复
+    Export,
+}
+
+/// 内置的示例数据集
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleDataset {
+    /// 小型电商库（客户、商品、订单），用于 MySQL / PostgreSQL
+    Ecommerce,
+    /// 带类别标签的示例向量，用于 Qdrant
+    Vectors,
+    /// 商品和评论文档，用于 MongoDB
+    Documents,
+}
+
+impl SampleDataset {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SampleDataset::Ecommerce => "ecommerce",
+            SampleDataset::Vectors => "vectors",
+            SampleDataset::Documents => "documents",
+        }
+    }
+}
+
+/// 数据导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// mysqldump / pg_dump 导出的 SQL 文件
+    Sql,
+    /// 每张表一个 CSV 文件，首行为列名
+    Csv,
+    /// 每张表（集合）一个 JSON 数组文件
+    Json,
+}
+
 /// 备份记录
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[allow(dead_code)]
 pub struct BackupRecord {
     pub id: String,
     pub database_id: String,
     pub file_path: String,
     pub file_size: u64,
     pub created_at: String,
+    #[serde(default)]
+    pub mode: BackupMode,
+    /// 是否使用口令加密
+    #[serde(default)]
+    pub encrypted: bool,
+    /// 口令派生密钥所用的 salt（十六进制）
+    #[serde(default)]
+    pub salt: Option<String>,
+    /// 上传到远程存储后的对象地址
+    #[serde(default)]
+    pub remote_url: Option<String>,
+    /// 导出记录的格式，备份为 None
+    #[serde(default)]
+    pub export_format: Option<ExportFormat>,
+    /// 导出的数据库名，备份为 None
+    #[serde(default)]
+    pub export_database: Option<String>,
+}
+
+/// 数据目录快照，保存在存储目录的 snapshots 下
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataSnapshot {
+    pub id: String,
+    pub database_id: String,
+    pub label: String,
+    /// 快照占用的磁盘空间（字节），写时复制的克隆实际占用可能更小
+    pub size: u64,
+    pub created_at: String,
+}
+
+/// 开启局域网共享后的连接地址
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanEndpoint {
+    /// 本机的局域网地址，未检测到时为空
+    pub host: Option<String>,
+    pub port: u16,
+    /// 放行入站连接的防火墙提示
+    pub firewall_note: Option<String>,
+}
+
+/// 备份清理结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupPruneResult {
+    pub removed_count: usize,
+    pub reclaimed_bytes: u64,
+}
+
+/// 数据库实例占用的磁盘空间（字节）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseDiskUsage {
+    pub database_id: String,
+    pub name: String,
+    pub data_size: u64,
+    pub log_size: u64,
+    pub backup_size: u64,
+    pub total_size: u64,
+    /// 格式化后的总大小，如 `1.50 GB`
+    pub total_size_text: String,
+}
+
+/// 导出的应用状态，用于迁移到新机器或共享团队的基础配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppStateExport {
+    /// 导出文件的格式版本
+    pub format_version: u32,
+    pub exported_at: String,
+    /// 是否包含数据库密码和远程备份密钥
+    pub include_secrets: bool,
+    pub databases: Vec<DatabaseInfo>,
+    pub settings: GlobalSettings,
+    #[serde(default)]
+    pub backups: Vec<BackupRecord>,
+}
+
+/// 导入应用状态的结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppStateImportResult {
+    pub imported_databases: usize,
+    /// 因 ID 或端口与已注册的数据库冲突而跳过的数据库名称
+    pub skipped_databases: Vec<String>,
+    pub imported_backups: usize,
+    /// 依赖或复制源不在当前数据库列表中而被去掉的引用，形如 `数据库名称: 被引用的 ID`
+    pub dropped_references: Vec<String>,
 }
 
 /// 全局设置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalSettings {
     pub default_storage_path: String,
+    /// 登录系统时在后台启动应用（窗口隐藏在托盘中），并启动设置了自动启动的数据库
     pub auto_start: bool,
     pub theme: String,
     pub language: String,
@@ -121,6 +380,105 @@ pub struct GlobalSettings {
     pub backup_retention_days: u32,
     pub log_level: String,
     pub log_retention_days: u32,
+    /// 启动数据库后等待其可连接的超时时间（秒）
+    #[serde(default = "default_startup_timeout_secs")]
+    pub startup_timeout_secs: u64,
+    /// 远程备份目标，未配置时只保留本地备份
+    #[serde(default)]
+    pub remote_backup: Option<RemoteBackupTarget>,
+    /// 退出应用时停止由应用启动的数据库
+    #[serde(default = "default_stop_on_exit")]
+    pub stop_on_exit: bool,
+    /// 直接启动的数据库进程崩溃后自动重启
+    #[serde(default = "default_restart_on_crash")]
+    pub restart_on_crash: bool,
+    /// 本机安装时优先使用系统包管理器（Linux 上为 apt/dnf/pacman，Windows 上为 winget/Scoop），
+    /// 不支持的类型仍下载二进制
+    #[serde(default, alias = "use_distro_packages")]
+    pub use_package_manager: bool,
+    /// macOS 上本机安装的数据库使用应用生成的 LaunchAgent 管理，不使用 brew services
+    #[serde(default)]
+    pub use_launch_agents: bool,
+    /// 下载发行包和运行 Homebrew 时使用的 HTTP(S) 代理，如 `http://127.0.0.1:7890`
+    #[serde(default)]
+    pub download_proxy: Option<String>,
+    /// 下载源镜像，键为下载源名称（见 `download::SOURCES`），值为替换官方地址的镜像地址
+    #[serde(default)]
+    pub download_mirrors: HashMap<String, String>,
+    /// 用户移除过的外部安装（`install_path`），自动发现时不再注册
+    #[serde(default)]
+    pub ignored_external: Vec<String>,
+    /// 后台采集系统和数据库指标的间隔（秒），为 0 时不采集
+    #[serde(default = "default_metrics_interval_secs")]
+    pub metrics_interval_secs: u64,
+    /// 将指标历史保存到存储目录，重启应用后仍可查看
+    #[serde(default)]
+    pub persist_metrics: bool,
+    /// 启动应用时开启本机 HTTP API，供脚本和 CI 管理数据库，需以 `api-server` 特性编译，修改后重启应用生效
+    #[serde(default)]
+    pub api_server_enabled: bool,
+    /// 本机 HTTP API 监听的端口，只接受 127.0.0.1 的连接
+    #[serde(default = "default_api_server_port")]
+    pub api_server_port: u16,
+    /// 访问本机 HTTP API 的令牌，为空时在开启 API 后首次启动时生成
+    #[serde(default)]
+    pub api_token: String,
+    /// API 令牌保存在系统钥匙串时的引用，此时 state.json 中不保存明文令牌
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_token_ref: Option<String>,
+    /// 发送系统通知的范围，窗口隐藏时也能看到数据库崩溃、自动启动失败等问题
+    #[serde(default)]
+    pub notification_level: NotificationLevel,
+}
+
+/// 系统通知的范围
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationLevel {
+    /// 不发送系统通知
+    Off,
+    /// 只通知失败：数据库崩溃、自动启动失败和备份失败
+    #[default]
+    Errors,
+    /// 失败和警告，警告包括存储目录所在磁盘空间不足
+    All,
+}
+
+impl NotificationLevel {
+    /// 该范围内是否发送 `notification`
+    pub fn allows(&self, notification: &AppNotification) -> bool {
+        match self {
+            NotificationLevel::Off => false,
+            NotificationLevel::Errors => notification.error,
+            NotificationLevel::All => true,
+        }
+    }
+}
+
+/// 后台任务发出的系统通知
+#[derive(Debug, Clone)]
+pub struct AppNotification {
+    pub title: String,
+    pub body: String,
+    /// 是否为失败，否则为警告
+    pub error: bool,
+}
+
+/// 远程备份目标（S3 / MinIO 等兼容存储）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteBackupTarget {
+    pub endpoint: String,
+    pub bucket: String,
+    #[serde(default)]
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+    /// 密钥保存在系统钥匙串时的引用，此时 state.json 中不保存明文密钥
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_key_ref: Option<String>,
+    /// 对象键前缀
+    #[serde(default)]
+    pub prefix: String,
 }
 
 impl Default for GlobalSettings {
@@ -135,10 +493,50 @@ impl Default for GlobalSettings {
             backup_retention_days: 7,
             log_level: "info".to_string(),
             log_retention_days: 7,
+            startup_timeout_secs: default_startup_timeout_secs(),
+            remote_backup: None,
+            stop_on_exit: default_stop_on_exit(),
+            restart_on_crash: default_restart_on_crash(),
+            use_package_manager: false,
+            use_launch_agents: false,
+            download_proxy: None,
+            download_mirrors: HashMap::new(),
+            ignored_external: Vec::new(),
+            metrics_interval_secs: default_metrics_interval_secs(),
+            persist_metrics: false,
+            api_server_enabled: false,
+            api_server_port: default_api_server_port(),
+            api_token: String::new(),
+            api_token_ref: None,
+            notification_level: NotificationLevel::default(),
         }
     }
 }
 
+fn default_bind_address() -> String {
+    network::LOCAL_BIND_ADDRESS.to_string()
+}
+
+fn default_startup_timeout_secs() -> u64 {
+    30
+}
+
+fn default_metrics_interval_secs() -> u64 {
+    10
+}
+
+fn default_api_server_port() -> u16 {
+    47300
+}
+
+fn default_stop_on_exit() -> bool {
+    true
+}
+
+fn default_restart_on_crash() -> bool {
+    true
+}
+
 impl GlobalSettings {
     fn get_default_storage_path() -> String {
         dirs::home_dir()
@@ -147,12 +545,23 @@ impl GlobalSettings {
     }
 }
 
+/// 设置中不合法的字段
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldError {
+    /// 字段名，嵌套字段用 `.` 连接，如 `remote_backup.endpoint`
+    pub field: String,
+    pub message: String,
+}
+
 /// 操作结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OperationResult<T> {
     pub success: bool,
     pub message: String,
     pub data: Option<T>,
+    /// 失败原因的错误码，未知原因时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<AppError>,
 }
 
 impl<T> OperationResult<T> {
@@ -161,6 +570,7 @@ impl<T> OperationResult<T> {
             success: true,
             message: message.into(),
             data,
+            error: None,
         }
     }
 
@@ -169,8 +579,296 @@ impl<T> OperationResult<T> {
             success: false,
             message: message.into(),
             data: None,
+            error: None,
         }
     }
+
+    /// 由已知原因导致的失败，消息为错误的描述
+    pub fn failure(error: AppError) -> Self {
+        Self {
+            success: false,
+            message: error.to_string(),
+            data: None,
+            error: Some(error),
+        }
+    }
+
+    /// 由 `anyhow::Error` 导致的失败，从错误链中取出错误码
+    pub fn error_from(message: impl Into<String>, error: &anyhow::Error) -> Self {
+        Self {
+            success: false,
+            message: message.into(),
+            data: None,
+            error: AppError::of(error),
+        }
+    }
+}
+
+/// 连接字符串格式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionUriFormat {
+    /// 数据库原生 URI（mysql://、postgresql://、mongodb://、redis:// 等）
+    #[default]
+    Native,
+    Prisma,
+    Sqlalchemy,
+    Jdbc,
+}
+
+/// 复制到剪贴板的连接信息
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionInfoKind {
+    /// 数据库原生 URI
+    Uri,
+    Password,
+    /// 可粘贴到 `.env` 文件的连接变量
+    Env,
+}
+
+/// 生成 `.env` 连接变量时遵循的框架约定
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvFramework {
+    Rails,
+    Django,
+    Laravel,
+    /// Next.js 等使用 Prisma 的项目
+    Prisma,
+    Spring,
+}
+
+impl EnvFramework {
+    pub fn display_name(&self) -> &str {
+        match self {
+            EnvFramework::Rails => "Rails",
+            EnvFramework::Django => "Django",
+            EnvFramework::Laravel => "Laravel",
+            EnvFramework::Prisma => "Prisma",
+            EnvFramework::Spring => "Spring",
+        }
+    }
+}
+
+/// 可以打开数据库连接的客户端
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseClient {
+    TablePlus,
+    DBeaver,
+    DataGrip,
+    Psql,
+    #[serde(rename = "redis_cli")]
+    RedisCli,
+}
+
+impl DatabaseClient {
+    pub const ALL: [DatabaseClient; 5] = [
+        DatabaseClient::TablePlus,
+        DatabaseClient::DBeaver,
+        DatabaseClient::DataGrip,
+        DatabaseClient::Psql,
+        DatabaseClient::RedisCli,
+    ];
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            DatabaseClient::TablePlus => "TablePlus",
+            DatabaseClient::DBeaver => "DBeaver",
+            DatabaseClient::DataGrip => "DataGrip",
+            DatabaseClient::Psql => "psql",
+            DatabaseClient::RedisCli => "redis-cli",
+        }
+    }
+}
+
+/// 健康检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheckResult {
+    pub healthy: bool,
+    /// 探测耗时（毫秒）
+    pub latency_ms: u64,
+    pub server_version: Option<String>,
+    pub error: Option<String>,
+}
+
+/// SQL 查询结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    /// 每行的值，NULL 为 None
+    pub rows: Vec<Vec<Option<String>>>,
+    /// 修改数据的语句影响的行数，查询语句为 None
+    pub affected_rows: Option<u64>,
+    /// 结果超出行数限制，只返回了前面的行
+    pub truncated: bool,
+    /// 执行耗时（毫秒）
+    pub elapsed_ms: u64,
+}
+
+/// Redis 命令的回复，按 RESP 类型区分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum RedisReply {
+    Status(String),
+    Error(String),
+    Integer(i64),
+    /// 不存在的值为 None
+    Bulk(Option<String>),
+    Array(Option<Vec<RedisReply>>),
+}
+
+/// Redis 键的概要信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisKeyInfo {
+    pub key: String,
+    /// 键的类型，如 string、hash、list
+    pub key_type: String,
+    /// 剩余过期时间（秒），没有过期时间时为 None
+    pub ttl: Option<i64>,
+}
+
+/// 一页 SCAN 结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisKeyPage {
+    /// 下一页的游标，为 0 时已遍历完
+    pub cursor: u64,
+    pub keys: Vec<RedisKeyInfo>,
+}
+
+/// MongoDB 实例中的数据库
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MongoDatabaseInfo {
+    pub name: String,
+    /// 占用的磁盘空间（字节）
+    pub size_on_disk: u64,
+    pub empty: bool,
+}
+
+/// MongoDB 数据库中的集合或视图
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MongoCollectionInfo {
+    pub name: String,
+    /// collection、view 或 timeseries
+    pub collection_type: String,
+    /// 估算的文档数，视图为 None
+    pub document_count: Option<u64>,
+}
+
+/// 数据库对象的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseObjectKind {
+    Database,
+    Schema,
+    Table,
+    View,
+    Collection,
+}
+
+/// 实例中的数据库对象，按 数据库 → 模式 → 表/集合 组成树
+///
+/// MySQL 和 MongoDB 没有模式这一层，表和集合直接位于数据库下。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseObject {
+    pub name: String,
+    pub kind: DatabaseObjectKind,
+    /// 估算的行数或文档数，来自统计信息，视图和未统计的表为 None
+    pub row_estimate: Option<u64>,
+    pub children: Vec<DatabaseObject>,
+}
+
+/// SurrealQL 中一条语句的执行结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurrealStatementResult {
+    /// OK 或 ERR
+    pub status: String,
+    /// 执行耗时，如 `1.2ms`
+    pub time: String,
+    /// 语句返回的数据，出错时为错误信息
+    pub result: serde_json::Value,
+}
+
+/// SQLite / DuckDB 数据库文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqliteFile {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub modified_at: Option<String>,
+}
+
+/// 日志跟踪事件（`log-line`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLineEvent {
+    pub database_id: String,
+    pub line: String,
+}
+
+/// 数据库进程崩溃事件（`database-crashed`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseCrashedEvent {
+    pub database_id: String,
+    pub name: String,
+    /// 是否会自动重启
+    pub restarting: bool,
+    /// 本次是第几次连续重启，不重启时为 0
+    pub attempt: u32,
+}
+
+/// 安装数据库参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstallDatabaseParams {
+    pub db_type: String,
+    pub version: Option<String>,
+    /// 实例名，同类型已有实例时必填
+    pub name: Option<String>,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// 运行方式，默认本机安装
+    #[serde(default)]
+    pub backend: BackendKind,
+    /// 安装后应用的资源配置预设
+    #[serde(default)]
+    pub preset: Option<ConfigPreset>,
+    /// 启动进程时额外设置的环境变量
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// 启动进程时追加的命令行参数
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+}
+
+/// 创建副本的参数，未指定的项按源实例推导
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReplicaOptions {
+    /// 副本的实例名，默认为 `<源实例名>-replica`
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 默认使用下一个可用端口
+    #[serde(default)]
+    pub port: Option<u16>,
+}
+
+/// 注册自定义服务的参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomServerParams {
+    pub name: String,
+    /// 服务二进制的绝对路径
+    pub binary_path: String,
+    /// 启动参数，可使用 `{port}` 和 `{data_path}` 占位符
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 服务监听的端口，用于替换 `{port}` 和健康检查
+    pub port: u16,
+    /// 显示用的版本号
+    #[serde(default)]
+    pub version: Option<String>,
+    /// 启动进程时额外设置的环境变量
+    #[serde(default)]
+    pub env: HashMap<String, String>,
 }
 
 /// 任务状态枚举
@@ -193,6 +891,36 @@ pub struct AsyncTask {
     pub progress: u8,
     pub message: String,
     pub error: Option<String>,
+    /// 失败原因的错误码，未知原因时为空
+    #[serde(default)]
+    pub error_code: Option<AppError>,
+    /// 在安装队列中的位置（从 1 开始），开始执行后为空
+    #[serde(default)]
+    pub queue_position: Option<usize>,
     pub created_at: String,
     pub updated_at: String,
 }
+
+/// 查询任务列表的过滤条件，未设置的条件不参与过滤
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TaskFilter {
+    pub status: Option<TaskStatus>,
+    pub task_type: Option<String>,
+    pub db_type: Option<String>,
+}
+
+impl TaskFilter {
+    pub fn matches(&self, task: &AsyncTask) -> bool {
+        self.status
+            .as_ref()
+            .is_none_or(|status| task.status == *status)
+            && self
+                .task_type
+                .as_deref()
+                .is_none_or(|task_type| task.task_type == task_type)
+            && self
+                .db_type
+                .as_deref()
+                .is_none_or(|db_type| task.db_type == db_type)
+    }
+}
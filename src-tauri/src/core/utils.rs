@@ -1,4 +1,5 @@
-use anyhow::Result;
+use crate::core::download;
+use anyhow::{bail, Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -10,6 +11,20 @@ pub fn ensure_dir(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// 检查目录是否可写：已存在时在其中创建临时文件，不存在时检查最近的已有上级目录
+pub fn check_writable(path: &Path) -> Result<()> {
+    let Some(existing) = path.ancestors().find(|ancestor| ancestor.exists()) else {
+        bail!("No existing parent directory for {}", path.display());
+    };
+    if !existing.is_dir() {
+        bail!("{} is not a directory", existing.display());
+    }
+    let probe = existing.join(format!(".local-db-write-test-{}", std::process::id()));
+    fs::write(&probe, b"").with_context(|| format!("{} is not writable", existing.display()))?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
 /// 获取默认存储路径
 #[allow(dead_code)]
 pub fn get_default_storage_path() -> PathBuf {
@@ -23,6 +38,13 @@ pub fn get_db_bin_path(storage_path: &Path, db_name: &str) -> PathBuf {
     storage_path.join("bin").join(db_name)
 }
 
+/// 获取指定版本数据库的bin目录，不同版本安装在各自的目录下，可以并存
+pub fn get_db_version_bin_path(storage_path: &Path, db_name: &str, version: &str) -> PathBuf {
+    storage_path
+        .join("bin")
+        .join(format!("{}-{}", db_name, version))
+}
+
 /// 获取数据库的config目录
 #[allow(dead_code)]
 pub fn get_db_config_path(storage_path: &Path, db_name: &str) -> PathBuf {
@@ -39,12 +61,170 @@ pub fn get_db_log_path(storage_path: &Path, db_name: &str) -> PathBuf {
     storage_path.join("logs").join(db_name)
 }
 
+/// 获取数据库实例的目录名，用于区分同类型数据库的多个实例
+///
+/// 未指定实例名时沿用数据库类型名，与单实例时的目录结构保持兼容。
+pub fn get_instance_dir_name(db_name: &str, instance_name: Option<&str>) -> String {
+    let Some(instance_name) = instance_name.filter(|n| !n.trim().is_empty()) else {
+        return db_name.to_string();
+    };
+
+    let slug = instance_name
+        .trim()
+        .to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        // 实例名不含 ASCII 字符时使用随机后缀
+        format!("{}-{}", db_name, generate_id())
+    } else {
+        format!("{}-{}", db_name, slug)
+    }
+}
+
+/// 递归复制目录，目标中已有的同名文件会被覆盖
+pub fn copy_dir(source: &Path, target: &Path) -> Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let dest = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// 下载 tar.gz 发行包并解压到 `target_dir`，去掉包内的版本号根目录
+///
+/// 离线安装时 `archive` 为本地发行包，不再下载。
+pub fn download_and_extract_tar_gz(
+    product: &str,
+    url: &str,
+    target_dir: &Path,
+    archive: Option<&Path>,
+) -> Result<()> {
+    let bytes = download::fetch_blocking(url, archive)
+        .with_context(|| format!("Failed to fetch {} archive", product))?;
+
+    let decoder = flate2::read::GzDecoder::new(std::io::Cursor::new(bytes));
+    let mut archive = tar::Archive::new(decoder);
+    for entry in archive.entries().context("Failed to read tar entries")? {
+        let mut entry = entry?;
+        let path = entry.path()?.components().skip(1).collect::<PathBuf>();
+        if path.as_os_str().is_empty() {
+            continue;
+        }
+        let target = target_dir.join(path);
+        if let Some(parent) = target.parent() {
+            ensure_dir(parent)?;
+        }
+        entry
+            .unpack(&target)
+            .with_context(|| format!("Failed to extract {}", target.display()))?;
+    }
+    Ok(())
+}
+
+/// 检查本机 Java 是否可以运行 `product`，优先使用 `JAVA_HOME`，其次 PATH 中的 java
+///
+/// 返回 Java 主版本号，不在 `supported` 中时报错。
+pub fn detect_java(product: &str, supported: &[u32]) -> Result<u32> {
+    let supported_list = supported
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(" or ");
+    let java = std::env::var_os("JAVA_HOME")
+        .map(|home| PathBuf::from(home).join("bin").join("java"))
+        .filter(|java| java.exists())
+        .unwrap_or_else(|| PathBuf::from("java"));
+
+    let output = std::process::Command::new(&java)
+        .arg("-version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success());
+    let Some(output) = output else {
+        bail!(
+            "Java was not found. {} requires Java {}; install a JDK or set JAVA_HOME",
+            product,
+            supported_list
+        );
+    };
+
+    // `java -version` 输出到 stderr，如 `openjdk version "17.0.9" 2023-10-17` 或 `java version "1.8.0_381"`
+    let banner = String::from_utf8_lossy(&output.stderr);
+    let version = banner
+        .split('"')
+        .nth(1)
+        .context("Failed to parse java -version output")?;
+    let mut parts = version.split(['.', '_', '-', '+']);
+    let major = match parts.next() {
+        Some("1") => parts.next(),
+        other => other,
+    }
+    .and_then(|major| major.parse::<u32>().ok())
+    .with_context(|| format!("Unrecognized Java version: {}", version))?;
+
+    if !supported.contains(&major) {
+        bail!(
+            "Java {} is not supported by {}; install Java {} and set JAVA_HOME",
+            major,
+            product,
+            supported_list
+        );
+    }
+    Ok(major)
+}
+
+/// 获取下载缓存目录
+pub fn get_downloads_path(storage_path: &Path) -> PathBuf {
+    storage_path.join("downloads")
+}
+
+/// 获取指标历史的保存路径
+pub fn get_metrics_path(storage_path: &Path) -> PathBuf {
+    storage_path.join("metrics").join("history.json")
+}
+
 /// 获取数据库的backup目录
-#[allow(dead_code)]
 pub fn get_db_backup_path(storage_path: &Path, db_name: &str) -> PathBuf {
     storage_path.join("backups").join(db_name)
 }
 
+/// 获取数据库实例的快照目录
+pub fn get_db_snapshot_path(storage_path: &Path, db_id: &str) -> PathBuf {
+    storage_path.join("snapshots").join(db_id)
+}
+
+/// 解析数据库自带的命令行工具路径
+///
+/// 依次查找 `install_path/bin/<name>` 与 `install_path/<name>`，都不存在时交给 PATH 解析。
+pub fn resolve_db_binary(install_path: &str, name: &str) -> PathBuf {
+    let file_name = if cfg!(windows) {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+
+    let install_path = Path::new(install_path);
+    for candidate in [
+        install_path.join("bin").join(&file_name),
+        install_path.join(&file_name),
+    ] {
+        if candidate.is_file() {
+            return candidate;
+        }
+    }
+
+    PathBuf::from(file_name)
+}
+
 /// 生成唯一ID
 pub fn generate_id() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -60,8 +240,25 @@ pub fn get_timestamp() -> String {
     chrono::Local::now().to_rfc3339()
 }
 
+/// 文件或目录占用的字节数，递归统计目录下的文件，不跟随符号链接，无法读取的部分忽略
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| dir_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
 /// 格式化文件大小
-#[allow(dead_code)]
 pub fn format_file_size(size: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size = size as f64;
@@ -74,3 +271,18 @@ pub fn format_file_size(size: u64) -> String {
 
     format!("{:.2} {}", size, UNITS[unit_index])
 }
+
+/// 对 URI 组成部分进行百分号编码（RFC 3986），`encode_slash` 为 false 时保留路径分隔符
+pub fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,316 @@
+use crate::core::{cassandra, download, macos, mariadb, neo4j, DatabaseType};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::time::Duration;
+
+/// 查询版本列表的请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 每个来源最多返回的版本数
+const MAX_VERSIONS: usize = 20;
+
+/// 可安装的版本
+#[derive(Debug, Clone, Serialize)]
+pub struct AvailableVersion {
+    pub version: String,
+    /// 发布日期（`YYYY-MM-DD`），来源不提供时为空
+    pub released_at: Option<String>,
+}
+
+/// 下载官方发行包的数据库已知的发行版本，每个发行系列列出最新的补丁版本，按从新到旧排列
+///
+/// 请求主版本或发行系列（如 `16`、`8.0`）时从这里补全为具体版本，完整的版本号不需要在列表中。
+const MYSQL_RELEASES: &[&str] = &["9.1.0", "8.4.0", "8.0.40"];
+const POSTGRESQL_RELEASES: &[&str] = &["18.2", "17.2", "16.6", "15.10", "14.15"];
+const MARIADB_RELEASES: &[&str] = &["11.4.4", "10.11.10", "10.6.20"];
+const MONGODB_RELEASES: &[&str] = &["8.0.4", "7.0.9", "6.0.19"];
+const REDIS_RELEASES: &[&str] = &["7.4.1", "7.2.6", "6.2.16"];
+
+/// 未指定版本时安装的版本
+fn default_version(db_type: &DatabaseType) -> Option<&'static str> {
+    match db_type {
+        DatabaseType::MySQL => Some("8.4.0"),
+        DatabaseType::MariaDB => Some(mariadb::DEFAULT_VERSION),
+        DatabaseType::PostgreSQL => Some("18.2"),
+        DatabaseType::MongoDB => Some("7.0.9"),
+        DatabaseType::Redis => Some("7.4.1"),
+        DatabaseType::SurrealDB => Some("2.1.4"),
+        _ => None,
+    }
+}
+
+fn known_releases(db_type: &DatabaseType) -> &'static [&'static str] {
+    match db_type {
+        DatabaseType::MySQL => MYSQL_RELEASES,
+        DatabaseType::MariaDB => MARIADB_RELEASES,
+        DatabaseType::PostgreSQL => POSTGRESQL_RELEASES,
+        DatabaseType::MongoDB => MONGODB_RELEASES,
+        DatabaseType::Redis => REDIS_RELEASES,
+        _ => &[],
+    }
+}
+
+/// 完整版本号的段数，PostgreSQL 10 起为 `主版本.补丁版本`
+fn full_version_parts(db_type: &DatabaseType) -> usize {
+    if *db_type == DatabaseType::PostgreSQL {
+        2
+    } else {
+        3
+    }
+}
+
+/// 把安装时请求的版本解析为要下载的具体版本
+///
+/// 未指定或为 `latest` 时使用默认版本；完整版本号原样使用；主版本或发行系列
+/// 取已知的该系列最新补丁版本。版本号前的 `v` 和 Homebrew 的修订号（`_1`）会被去掉。
+pub fn resolve(db_type: &DatabaseType, requested: Option<&str>) -> Result<String> {
+    let requested = requested
+        .and_then(|v| v.trim().trim_start_matches('v').split('_').next())
+        .filter(|v| !v.is_empty() && *v != "latest");
+    let Some(requested) = requested else {
+        return match default_version(db_type) {
+            Some(version) => Ok(version.to_string()),
+            None => bail!("No default version for {}", db_type.display_name()),
+        };
+    };
+
+    let parts: Vec<&str> = requested.split('.').collect();
+    if parts.iter().any(|part| part.parse::<u32>().is_err()) {
+        bail!("Invalid {} version: {}", db_type.display_name(), requested);
+    }
+    if parts.len() >= full_version_parts(db_type) {
+        return Ok(requested.to_string());
+    }
+
+    known_releases(db_type)
+        .iter()
+        .find(|release| {
+            let release_parts: Vec<&str> = release.split('.').collect();
+            release_parts.starts_with(&parts)
+        })
+        .map(|release| release.to_string())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown {} version {}, specify the full version number",
+                db_type.display_name(),
+                requested
+            )
+        })
+}
+
+/// 版本号的前 `parts` 段，如 `series("8.4.3", 2)` 为 `8.4`
+pub fn series(version: &str, parts: usize) -> String {
+    version.split('.').take(parts).collect::<Vec<_>>().join(".")
+}
+
+/// 与版本对应的 Homebrew formula，同时也是 brew services 的服务名
+///
+/// Homebrew 只提供每个发行系列的最新版本，补丁版本无法指定。无法解析的版本
+/// （如自动发现的服务记录的 `detected`）使用默认版本的 formula。
+pub fn homebrew_formula(db_type: &DatabaseType, version: Option<&str>) -> Result<String> {
+    let version = resolve(db_type, version).or_else(|_| resolve(db_type, None))?;
+    let formula = match db_type {
+        // redis@6.2 之外只有最新版本的 redis formula
+        DatabaseType::Redis if series(&version, 2) == "6.2" => "redis@6.2".to_string(),
+        DatabaseType::Redis => "redis".to_string(),
+        // MySQL 9 为创新版，只有不带版本的 mysql formula
+        DatabaseType::MySQL if series(&version, 1) == "9" => "mysql".to_string(),
+        DatabaseType::MySQL => format!("mysql@{}", series(&version, 2)),
+        DatabaseType::MariaDB => format!("mariadb@{}", series(&version, 2)),
+        DatabaseType::PostgreSQL => format!("postgresql@{}", series(&version, 1)),
+        DatabaseType::MongoDB => format!("mongodb-community@{}", series(&version, 2)),
+        _ => bail!("{} is not installed via Homebrew", db_type.display_name()),
+    };
+    Ok(formula)
+}
+
+/// 查询数据库在当前平台上可安装的版本，从新到旧排列
+///
+/// macOS 上通过 Homebrew 安装的数据库查询 `brew info --json=v2`，只能安装各 formula 的最新版本；
+/// 其他情况查询 GitHub Releases 或官方发布接口。没有版本接口的数据库返回已知的发行版本。
+pub async fn available_versions(db_type: &DatabaseType) -> Result<Vec<AvailableVersion>> {
+    if cfg!(target_os = "macos") {
+        if let Some(formulas) = homebrew_formulas(db_type) {
+            // brew 是同步的子进程调用，放到后台线程池执行
+            return tauri::async_runtime::spawn_blocking(move || homebrew_versions(&formulas))
+                .await
+                .context("Failed to query Homebrew versions");
+        }
+    }
+
+    let mut versions = match db_type {
+        DatabaseType::PostgreSQL => postgresql_versions().await?,
+        DatabaseType::MariaDB => mariadb_versions().await?,
+        // Windows 使用 tporadowski 的移植版
+        DatabaseType::Redis if cfg!(target_os = "windows") => {
+            github_releases("tporadowski/redis", "").await?
+        }
+        DatabaseType::Redis => github_releases("redis/redis", "").await?,
+        DatabaseType::Qdrant => github_releases("qdrant/qdrant", "").await?,
+        DatabaseType::SurrealDB => github_releases("surrealdb/surrealdb", "2.").await?,
+        DatabaseType::OpenSearch => github_releases("opensearch-project/OpenSearch", "2.").await?,
+        DatabaseType::InfluxDB => github_releases("influxdata/influxdb", "2.").await?,
+        DatabaseType::MySQL | DatabaseType::MongoDB => known_releases(db_type)
+            .iter()
+            .map(|version| AvailableVersion {
+                version: version.to_string(),
+                released_at: None,
+            })
+            .collect(),
+        DatabaseType::Cassandra => vec![AvailableVersion {
+            version: cassandra::DEFAULT_VERSION.to_string(),
+            released_at: None,
+        }],
+        DatabaseType::Neo4j => vec![AvailableVersion {
+            version: neo4j::DEFAULT_VERSION.to_string(),
+            released_at: None,
+        }],
+        DatabaseType::SeekDB
+        | DatabaseType::SQLite
+        | DatabaseType::DuckDB
+        | DatabaseType::Custom => Vec::new(),
+    };
+    versions.sort_by_key(|v| std::cmp::Reverse(version_key(&v.version)));
+    versions.dedup_by(|a, b| a.version == b.version);
+    versions.truncate(MAX_VERSIONS);
+    Ok(versions)
+}
+
+/// macOS 上通过 Homebrew 安装的数据库可选的 formula
+fn homebrew_formulas(db_type: &DatabaseType) -> Option<Vec<String>> {
+    let formulas = match db_type {
+        DatabaseType::Redis
+        | DatabaseType::MySQL
+        | DatabaseType::MariaDB
+        | DatabaseType::PostgreSQL
+        | DatabaseType::MongoDB => {
+            let mut formulas: Vec<String> = known_releases(db_type)
+                .iter()
+                .filter_map(|version| homebrew_formula(db_type, Some(version)).ok())
+                .collect();
+            formulas.dedup();
+            formulas
+        }
+        DatabaseType::SurrealDB => vec!["surreal".to_string()],
+        DatabaseType::OpenSearch => vec!["opensearch".to_string()],
+        DatabaseType::InfluxDB => vec!["influxdb".to_string()],
+        _ => return None,
+    };
+    Some(formulas)
+}
+
+fn homebrew_versions(formulas: &[String]) -> Vec<AvailableVersion> {
+    formulas
+        .iter()
+        .filter_map(|formula| macos::get_homebrew_formula_stable_version(formula))
+        .map(|version| AvailableVersion {
+            version,
+            released_at: None,
+        })
+        .collect()
+}
+
+async fn http_get_json(url: &str) -> Result<serde_json::Value> {
+    let response = download::client_builder()?
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent("local-db")
+        .build()?
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    if !response.status().is_success() {
+        bail!("{} returned {}", url, response.status());
+    }
+    response
+        .json()
+        .await
+        .with_context(|| format!("Invalid response from {}", url))
+}
+
+/// GitHub Releases 中的正式版本，`prefix` 非空时只保留以其开头的版本（如只要 2.x）
+async fn github_releases(repo: &str, prefix: &str) -> Result<Vec<AvailableVersion>> {
+    let url = format!("https://api.github.com/repos/{repo}/releases?per_page=50");
+    let releases = http_get_json(&url).await?;
+    Ok(releases
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|release| {
+            !release["draft"].as_bool().unwrap_or(false)
+                && !release["prerelease"].as_bool().unwrap_or(false)
+        })
+        .filter_map(|release| {
+            let version = release["tag_name"].as_str()?.trim_start_matches('v');
+            (version.starts_with(prefix) && version_key(version).is_some()).then(|| {
+                AvailableVersion {
+                    version: version.to_string(),
+                    released_at: release["published_at"]
+                        .as_str()
+                        .and_then(|date| date.get(..10))
+                        .map(String::from),
+                }
+            })
+        })
+        .collect())
+}
+
+/// postgresql.org 发布的仍在支持期内的各主版本及其最新补丁版本
+async fn postgresql_versions() -> Result<Vec<AvailableVersion>> {
+    let versions = http_get_json("https://www.postgresql.org/versions.json").await?;
+    Ok(versions
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|version| version["supported"].as_bool().unwrap_or(false))
+        .filter_map(|version| {
+            Some(AvailableVersion {
+                version: format!(
+                    "{}.{}",
+                    version["major"].as_str()?,
+                    version["latestMinor"].as_str()?
+                ),
+                released_at: None,
+            })
+        })
+        .collect())
+}
+
+/// MariaDB 官方 REST API 中稳定的长期支持版本，每个发行系列取最新的版本
+async fn mariadb_versions() -> Result<Vec<AvailableVersion>> {
+    const API: &str = "https://downloads.mariadb.org/rest-api/mariadb";
+    let majors = http_get_json(&format!("{API}/")).await?;
+    let series: Vec<&str> = majors["major_releases"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|major| {
+            major["release_status"] == "Stable"
+                && major["release_support_type"] == "Long Term Support"
+        })
+        .filter_map(|major| major["release_id"].as_str())
+        .collect();
+
+    let mut versions = Vec::new();
+    for series in series {
+        let releases = http_get_json(&format!("{API}/{series}/")).await?;
+        let latest = releases["releases"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(version, _)| version_key(version).is_some())
+            .max_by_key(|(version, _)| version_key(version));
+        if let Some((version, release)) = latest {
+            versions.push(AvailableVersion {
+                version: version.clone(),
+                released_at: release["date_of_release"].as_str().map(String::from),
+            });
+        }
+    }
+    Ok(versions)
+}
+
+/// 用于排序的版本号，含非数字段时为 None
+fn version_key(version: &str) -> Option<Vec<u32>> {
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
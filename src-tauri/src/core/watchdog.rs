@@ -0,0 +1,117 @@
+use crate::core::{DatabaseInfo, DatabaseType};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// 进程存活检查间隔
+pub const WATCHDOG_INTERVAL: Duration = Duration::from_secs(2);
+/// 连续崩溃后最多自动重启的次数
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+/// 首次重启前的等待时间，之后每次翻倍
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// 重启等待时间上限
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// 重启后稳定运行超过该时间即重置崩溃计数
+const STABLE_AFTER: Duration = Duration::from_secs(120);
+
+/// 是否由应用直接管理进程
+///
+/// macOS 上除 Qdrant、SurrealDB、OpenSearch、InfluxDB、Cassandra、Neo4j 和 DuckDB 外都由 Homebrew 服务（launchd）
+/// 负责拉起，不需要守护。
+pub fn is_supervised(db_type: &DatabaseType) -> bool {
+    if cfg!(target_os = "macos") {
+        matches!(
+            db_type,
+            DatabaseType::Qdrant
+                | DatabaseType::SurrealDB
+                | DatabaseType::OpenSearch
+                | DatabaseType::InfluxDB
+                | DatabaseType::Cassandra
+                | DatabaseType::Neo4j
+                | DatabaseType::DuckDB
+        )
+    } else {
+        true
+    }
+}
+
+/// 直接启动的数据库进程写入的 PID 文件，位于数据目录下的 `<db_type>.pid`
+pub fn pid_file_path(db_info: &DatabaseInfo) -> PathBuf {
+    Path::new(&db_info.data_path).join(format!("{}.pid", db_info.db_type.as_str()))
+}
+
+/// 读取 PID 文件中的进程 ID
+pub fn read_pid(db_info: &DatabaseInfo) -> Option<u32> {
+    std::fs::read_to_string(pid_file_path(db_info))
+        .ok()
+        .and_then(|pid| pid.trim().parse().ok())
+}
+
+/// 根据 PID 文件判断进程是否仍在运行，PID 文件缺失视为已退出
+pub fn is_process_alive(db_info: &DatabaseInfo) -> bool {
+    read_pid(db_info).is_some_and(is_pid_alive)
+}
+
+#[cfg(unix)]
+fn is_pid_alive(pid: u32) -> bool {
+    use nix::errno::Errno;
+    use nix::sys::signal::kill;
+    use nix::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
+    use nix::unistd::Pid;
+
+    let pid = Pid::from_raw(pid as i32);
+    // 由本应用启动的子进程退出后会成为僵尸进程，需要先回收，否则 kill 仍会成功
+    match waitpid(pid, Some(WaitPidFlag::WNOHANG)) {
+        Ok(WaitStatus::StillAlive) => true,
+        Ok(_) => false,
+        Err(Errno::ECHILD) => kill(pid, None).is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(windows)]
+fn is_pid_alive(pid: u32) -> bool {
+    use sysinfo::{Pid, ProcessesToUpdate, System};
+
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).is_some()
+}
+
+/// 单个数据库的崩溃重启退避状态
+#[derive(Debug, Default)]
+pub struct RestartBackoff {
+    attempts: u32,
+    last_restart: Option<Instant>,
+}
+
+impl RestartBackoff {
+    /// 记录一次崩溃，返回重启前需要等待的时间；超过最大重启次数时返回 None
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        if self
+            .last_restart
+            .is_some_and(|restarted| restarted.elapsed() >= STABLE_AFTER)
+        {
+            self.attempts = 0;
+        }
+        if self.attempts >= MAX_RESTART_ATTEMPTS {
+            return None;
+        }
+
+        let delay = INITIAL_BACKOFF
+            .saturating_mul(1 << self.attempts)
+            .min(MAX_BACKOFF);
+        self.attempts += 1;
+        Some(delay)
+    }
+
+    /// 记录重启完成的时间
+    pub fn restarted(&mut self) {
+        self.last_restart = Some(Instant::now());
+    }
+
+    /// 当前已连续重启的次数
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
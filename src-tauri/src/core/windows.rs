@@ -3,35 +3,23 @@ use anyhow::Result;
 use std::path::Path;
 
 /// Options used when installing a database on Windows.
-#[allow(dead_code)]
-pub struct WindowsInstallOptions<'a> {
-    pub version: Option<&'a str>,
-    pub port: Option<u16>,
-    pub username: Option<&'a str>,
-    pub password: Option<&'a str>,
-    pub auto_start: bool,
-}
-
-impl<'a> Default for WindowsInstallOptions<'a> {
-    fn default() -> Self {
-        Self {
-            version: None,
-            port: None,
-            username: None,
-            password: None,
-            auto_start: true,
-        }
-    }
-}
+pub type WindowsInstallOptions<'a> = crate::core::backend::InstallOptions<'a>;
 
 #[cfg(target_os = "windows")]
 mod imp {
     use super::*;
-    use crate::core::{utils, DatabaseStatus};
+    use crate::core::{
+        download, influxdb, mariadb, neo4j, network, opensearch, redis, utils, versions,
+        BackendKind, DatabaseStatus,
+    };
     use anyhow::{bail, Context};
-    use reqwest::blocking::get;
+    use std::collections::HashMap;
     use std::fs;
     use std::process::Command;
+    use tauri_plugin_log::log;
+
+    /// 未指定版本时安装的 Redis Windows 移植版版本
+    const REDIS_VERSION: &str = "5.0.14.1";
 
     /// 安装数据库到 Windows
     pub fn install_database(
@@ -45,9 +33,16 @@ mod imp {
             DatabaseType::Qdrant => install_qdrant(storage_path, options),
             DatabaseType::SurrealDB => install_surrealdb(storage_path, options),
             DatabaseType::MySQL => install_mysql(storage_path, options),
+            DatabaseType::MariaDB => install_mariadb(storage_path, options),
             DatabaseType::PostgreSQL => install_postgresql(storage_path, options),
             DatabaseType::Neo4j => install_neo4j(storage_path, options),
             DatabaseType::SeekDB => install_seekdb(storage_path, options),
+            DatabaseType::OpenSearch => install_opensearch(storage_path, options),
+            DatabaseType::InfluxDB => install_influxdb(storage_path, options),
+            DatabaseType::Cassandra => bail!("Cassandra is not supported on Windows"),
+            DatabaseType::SQLite => bail!("SQLite has no server binary to install"),
+            DatabaseType::DuckDB => bail!("DuckDB is managed by its own backend"),
+            DatabaseType::Custom => bail!("Custom servers are managed by their own backend"),
         }
     }
 
@@ -59,9 +54,16 @@ mod imp {
             DatabaseType::Redis => start_redis_process(db_info),
             DatabaseType::MongoDB => start_mongodb_process(db_info),
             DatabaseType::MySQL => start_mysql_process(db_info),
+            DatabaseType::MariaDB => start_mariadb_process(db_info),
             DatabaseType::PostgreSQL => start_postgresql_process(db_info),
             DatabaseType::Neo4j => start_neo4j_process(db_info),
             DatabaseType::SeekDB => start_seekdb_process(db_info),
+            DatabaseType::OpenSearch => start_opensearch_process(db_info),
+            DatabaseType::InfluxDB => start_influxdb_process(db_info),
+            DatabaseType::Cassandra => bail!("Cassandra is not supported on Windows"),
+            DatabaseType::SQLite => bail!("SQLite has no server process to start"),
+            DatabaseType::DuckDB => bail!("DuckDB is managed by its own backend"),
+            DatabaseType::Custom => bail!("Custom servers are managed by their own backend"),
         }
     }
 
@@ -73,9 +75,16 @@ mod imp {
             DatabaseType::Redis => "redis.pid",
             DatabaseType::MongoDB => "mongodb.pid",
             DatabaseType::MySQL => "mysql.pid",
+            DatabaseType::MariaDB => "mariadb.pid",
             DatabaseType::PostgreSQL => "postgresql.pid",
             DatabaseType::Neo4j => "neo4j.pid",
             DatabaseType::SeekDB => "seekdb.pid",
+            DatabaseType::OpenSearch => "opensearch.pid",
+            DatabaseType::InfluxDB => "influxdb.pid",
+            DatabaseType::Cassandra => bail!("Cassandra is not supported on Windows"),
+            DatabaseType::SQLite => bail!("SQLite has no server process to stop"),
+            DatabaseType::DuckDB => bail!("DuckDB is managed by its own backend"),
+            DatabaseType::Custom => bail!("Custom servers are managed by their own backend"),
         };
 
         let data_dir = Path::new(&db_info.data_path);
@@ -85,10 +94,11 @@ mod imp {
             let pid_str = fs::read_to_string(&pid_path)?;
             let pid = pid_str.trim();
 
-            // Windows: 使用 taskkill 强制停止
+            // Windows: 使用 taskkill 强制停止，/T 连同 .bat 启动的子进程一起结束
             Command::new("taskkill")
                 .arg("/PID")
                 .arg(pid)
+                .arg("/T")
                 .arg("/F")
                 .output()
                 .context("Failed to stop process via taskkill")?;
@@ -105,12 +115,17 @@ mod imp {
         storage_path: &Path,
         options: &WindowsInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "redis");
+        let version = options
+            .version
+            .map(|v| v.trim_start_matches('v'))
+            .unwrap_or(REDIS_VERSION);
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "redis", version);
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "redis");
-        let logs_dir = utils::get_db_log_path(storage_path, "redis");
-        let config_dir = utils::get_db_config_path(storage_path, "redis");
+        let instance_dir = utils::get_instance_dir_name("redis", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         utils::ensure_dir(&config_dir)?;
@@ -121,33 +136,47 @@ mod imp {
 
         if !binary_path.exists() {
             // Redis Windows 移植版下载地址 (tporadowski/redis)
-            let url = "https://github.com/tporadowski/redis/releases/download/v5.0.14.1/Redis-x64-5.0.14.1.zip";
-            download_and_extract_zip(url, &bin_dir)?;
+            let url = format!(
+                "https://github.com/tporadowski/redis/releases/download/v{version}/Redis-x64-{version}.zip"
+            );
+            download_and_extract_zip(&url, &bin_dir, options.archive)?;
         }
 
         let port = options.port.unwrap_or(6379);
-        let config_content = format!(
+        let mut config_content = format!(
             "port {port}\nbind 127.0.0.1\ndir \"{data_path}\"\nlogfile \"{log_path}\"\n",
             port = port,
             data_path = data_dir.to_string_lossy().replace("\\", "/"),
             log_path = log_file.to_string_lossy().replace("\\", "/")
         );
+        if let Some(password) = options.password.filter(|p| !p.is_empty()) {
+            config_content.push_str(&format!("requirepass {}\n", redis::quote(password)));
+        }
         fs::write(&config_path, config_content)?;
 
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "Redis".to_string(),
+            name: options.instance_name.unwrap_or("Redis").to_string(),
             db_type: DatabaseType::Redis,
-            version: "5.0.14.1".to_string(),
+            backend: BackendKind::Native,
+            version: version.to_string(),
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: None,
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: Some(config_path.to_string_lossy().to_string()),
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -169,6 +198,9 @@ mod imp {
 
         let child = Command::new(binary_path)
             .arg(config_path)
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start Redis")?;
 
@@ -182,12 +214,21 @@ mod imp {
         storage_path: &Path,
         options: &WindowsInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "qdrant");
+        // 未指定版本时下载最新版本，放在不带版本号的目录下
+        let bin_dir = match options.version {
+            Some(version) => utils::get_db_version_bin_path(
+                storage_path,
+                "qdrant",
+                version.trim_start_matches('v'),
+            ),
+            None => utils::get_db_bin_path(storage_path, "qdrant"),
+        };
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "qdrant");
-        let logs_dir = utils::get_db_log_path(storage_path, "qdrant");
-        let config_dir = utils::get_db_config_path(storage_path, "qdrant");
+        let instance_dir = utils::get_instance_dir_name("qdrant", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         utils::ensure_dir(&config_dir)?;
@@ -202,11 +243,15 @@ mod imp {
             } else {
                 "x86_64"
             };
+            let release = match options.version {
+                Some(version) => format!("download/v{}", version.trim_start_matches('v')),
+                None => "latest/download".to_string(),
+            };
             let url = format!(
-                "https://github.com/qdrant/qdrant/releases/latest/download/qdrant-{}-pc-windows-msvc.zip",
-                arch
+                "https://github.com/qdrant/qdrant/releases/{}/qdrant-{}-pc-windows-msvc.zip",
+                release, arch
             );
-            download_and_extract_zip(&url, &bin_dir)?;
+            download_and_extract_zip(&url, &bin_dir, options.archive)?;
         }
 
         let port = options.port.unwrap_or(6333);
@@ -219,18 +264,27 @@ mod imp {
 
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "Qdrant".to_string(),
+            name: options.instance_name.unwrap_or("Qdrant").to_string(),
             db_type: DatabaseType::Qdrant,
-            version: "latest".to_string(),
+            backend: BackendKind::Native,
+            version: options.version.unwrap_or("latest").to_string(),
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: None,
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: Some(config_path.to_string_lossy().to_string()),
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -253,6 +307,9 @@ mod imp {
         let child = Command::new(binary_path)
             .arg("--config-path")
             .arg(config_path)
+            .env("QDRANT__SERVICE__HOST", &db_info.bind_address)
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start Qdrant")?;
 
@@ -266,11 +323,13 @@ mod imp {
         storage_path: &Path,
         options: &WindowsInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "surrealdb");
+        let version = versions::resolve(&DatabaseType::SurrealDB, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "surrealdb", &version);
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "surrealdb");
-        let logs_dir = utils::get_db_log_path(storage_path, "surrealdb");
+        let instance_dir = utils::get_instance_dir_name("surrealdb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
 
@@ -284,27 +343,35 @@ mod imp {
                 "amd64"
             };
             let url = format!(
-                "https://github.com/surrealdb/surrealdb/releases/latest/download/surreal-v2.1.4-windows-{}.zip",
-                arch
+                "https://github.com/surrealdb/surrealdb/releases/download/v{version}/surreal-v{version}-windows-{arch}.zip"
             );
-            download_and_extract_zip(&url, &bin_dir)?;
+            download_and_extract_zip(&url, &bin_dir, options.archive)?;
         }
 
         let port = options.port.unwrap_or(8000);
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "SurrealDB".to_string(),
+            name: options.instance_name.unwrap_or("SurrealDB").to_string(),
             db_type: DatabaseType::SurrealDB,
-            version: "latest".to_string(),
+            backend: BackendKind::Native,
+            version,
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: Some(options.username.unwrap_or("root").to_string()),
             password: Some(options.password.unwrap_or("root").to_string()),
+            credential_ref: None,
             config: None,
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -326,12 +393,14 @@ mod imp {
         let child = Command::new(binary_path)
             .arg("start")
             .arg("--bind")
-            .arg(format!("127.0.0.1:{}", db_info.port))
+            .arg(format!("{}:{}", db_info.bind_address, db_info.port))
             .arg("--user")
             .arg(db_info.username.as_deref().unwrap_or("root"))
             .arg("--pass")
             .arg(db_info.password.as_deref().unwrap_or("root"))
             .arg(format!("rocksdb://{}", data_dir.to_string_lossy()))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start SurrealDB")?;
 
@@ -345,11 +414,13 @@ mod imp {
         storage_path: &Path,
         options: &WindowsInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "mongodb");
+        let version = versions::resolve(&DatabaseType::MongoDB, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "mongodb", &version);
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "mongodb");
-        let logs_dir = utils::get_db_log_path(storage_path, "mongodb");
+        let instance_dir = utils::get_instance_dir_name("mongodb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
 
@@ -357,26 +428,38 @@ mod imp {
         let log_file = logs_dir.join("mongodb.log");
 
         if !binary_path.exists() {
-            // MongoDB 7.0 社区版下载地址
-            let url = "https://fastdl.mongodb.org/windows/mongodb-windows-x86_64-7.0.9.zip";
-            download_and_extract_zip(url, &bin_dir)?;
+            // MongoDB 社区版下载地址
+            let url = format!(
+                "https://fastdl.mongodb.org/windows/mongodb-windows-x86_64-{}.zip",
+                version
+            );
+            download_and_extract_zip(x64_only_url("MongoDB", &url)?, &bin_dir, options.archive)?;
         }
 
         let port = options.port.unwrap_or(27017);
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "MongoDB".to_string(),
+            name: options.instance_name.unwrap_or("MongoDB").to_string(),
             db_type: DatabaseType::MongoDB,
-            version: "7.0.9".to_string(),
+            backend: BackendKind::Native,
+            version,
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: None,
             password: None,
+            credential_ref: None,
             config: None,
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -405,6 +488,9 @@ mod imp {
             .arg(db_info.port.to_string())
             .arg("--logpath")
             .arg(log_file)
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start MongoDB")?;
 
@@ -418,12 +504,14 @@ mod imp {
         storage_path: &Path,
         options: &WindowsInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "mysql");
+        let version = versions::resolve(&DatabaseType::MySQL, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "mysql", &version);
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "mysql");
-        let logs_dir = utils::get_db_log_path(storage_path, "mysql");
-        let config_dir = utils::get_db_config_path(storage_path, "mysql");
+        let instance_dir = utils::get_instance_dir_name("mysql", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         utils::ensure_dir(&config_dir)?;
@@ -432,9 +520,13 @@ mod imp {
         let log_file = logs_dir.join("mysql.log");
 
         if !binary_path.exists() {
-            // MySQL 8.4 社区版下载地址
-            let url = "https://dev.mysql.com/get/Downloads/MySQL-8.4/mysql-8.4.0-winx64.zip";
-            download_and_extract_zip(url, &bin_dir)?;
+            // MySQL 社区版下载地址，按发行系列分目录
+            let url = format!(
+                "https://dev.mysql.com/get/Downloads/MySQL-{}/mysql-{}-winx64.zip",
+                versions::series(&version, 2),
+                version
+            );
+            download_and_extract_zip(x64_only_url("MySQL", &url)?, &bin_dir, options.archive)?;
         }
 
         // 初始化 MySQL 数据目录 (无密码)
@@ -449,18 +541,27 @@ mod imp {
         let port = options.port.unwrap_or(3306);
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "MySQL".to_string(),
+            name: options.instance_name.unwrap_or("MySQL").to_string(),
             db_type: DatabaseType::MySQL,
-            version: "8.4.0".to_string(),
+            backend: BackendKind::Native,
+            version,
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: options.username.map(|s| s.to_string()).or(Some("root".to_string())),
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: None,
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -485,6 +586,9 @@ mod imp {
             .arg(format!("--port={}", db_info.port))
             .arg(format!("--datadir={}", data_dir.to_string_lossy()))
             .arg("--console")
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start MySQL")?;
 
@@ -492,17 +596,114 @@ mod imp {
         Ok(())
     }
 
+    // --- MariaDB Implementation ---
+
+    fn install_mariadb(
+        storage_path: &Path,
+        options: &WindowsInstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let version = versions::resolve(&DatabaseType::MariaDB, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "mariadb", &version);
+        utils::ensure_dir(&bin_dir)?;
+
+        let instance_dir = utils::get_instance_dir_name("mariadb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+
+        let binary_path = bin_dir.join("bin").join("mariadbd.exe");
+        let log_file = logs_dir.join("mariadb.log");
+
+        if !binary_path.exists() {
+            let url = mariadb::download_url(&version, "winx64-packages", "winx64.zip");
+            download_and_extract_zip(&url, &bin_dir, options.archive)?;
+        }
+
+        // 初始化 MariaDB 数据目录，root 密码为空，并在数据目录中生成 my.ini
+        let port = options.port.unwrap_or(mariadb::DEFAULT_PORT);
+        if fs::read_dir(&data_dir)?.count() == 0 {
+            let output = Command::new(bin_dir.join("bin").join("mariadb-install-db.exe"))
+                .arg(format!("--datadir={}", data_dir.to_string_lossy()))
+                .arg(format!("--port={}", port))
+                .output()
+                .context("Failed to initialize MariaDB")?;
+            if !output.status.success() {
+                let err = String::from_utf8_lossy(&output.stderr);
+                bail!("MariaDB initialization failed: {}", err);
+            }
+        }
+
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options.instance_name.unwrap_or("MariaDB").to_string(),
+            db_type: DatabaseType::MariaDB,
+            backend: BackendKind::Native,
+            version,
+            install_path: bin_dir.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_file.to_string_lossy().to_string(),
+            port,
+            username: options.username.map(|s| s.to_string()).or(Some("root".to_string())),
+            password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
+            config: None,
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        if options.auto_start {
+            start_mariadb_process(&db_info)?;
+            db_info.status = DatabaseStatus::Running;
+        }
+
+        Ok(db_info)
+    }
+
+    fn start_mariadb_process(db_info: &DatabaseInfo) -> Result<()> {
+        let binary_path = Path::new(&db_info.install_path)
+            .join("bin")
+            .join("mariadbd.exe");
+        let data_dir = Path::new(&db_info.data_path);
+        let pid_path = data_dir.join("mariadb.pid");
+
+        let child = Command::new(binary_path)
+            .arg(format!("--port={}", db_info.port))
+            .arg(format!("--datadir={}", data_dir.to_string_lossy()))
+            .arg("--console")
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
+            .spawn()
+            .context("Failed to start MariaDB")?;
+
+        fs::write(&pid_path, child.id().to_string())?;
+        Ok(())
+    }
+
     // --- PostgreSQL Implementation ---
 
     fn install_postgresql(
         storage_path: &Path,
         options: &WindowsInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "postgresql");
+        let version = versions::resolve(&DatabaseType::PostgreSQL, options.version)?;
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "postgresql", &version);
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "postgresql");
-        let logs_dir = utils::get_db_log_path(storage_path, "postgresql");
+        let instance_dir = utils::get_instance_dir_name("postgresql", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
 
@@ -511,9 +712,12 @@ mod imp {
         let log_file = logs_dir.join("postgresql.log");
 
         if !binary_path.exists() {
-            // PostgreSQL 18 社区版下载地址 (EDB 提供的 ZIP 版)
-            let url = "https://get.enterprisedb.com/postgresql/postgresql-18.2-1-windows-x64-binaries.zip";
-            download_and_extract_zip(url, &bin_dir)?;
+            // PostgreSQL 社区版下载地址 (EDB 提供的 ZIP 版，文件名带构建号 -1)
+            let url = format!(
+                "https://get.enterprisedb.com/postgresql/postgresql-{}-1-windows-x64-binaries.zip",
+                version
+            );
+            download_and_extract_zip(x64_only_url("PostgreSQL", &url)?, &bin_dir, options.archive)?;
         }
 
         // 初始化 PostgreSQL 数据目录
@@ -531,18 +735,27 @@ mod imp {
         let port = options.port.unwrap_or(5432);
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "PostgreSQL".to_string(),
+            name: options.instance_name.unwrap_or("PostgreSQL").to_string(),
             db_type: DatabaseType::PostgreSQL,
-            version: "18.2".to_string(),
+            backend: BackendKind::Native,
+            version,
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: options.username.map(|s| s.to_string()).or(Some("postgres".to_string())),
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: None,
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -568,7 +781,15 @@ mod imp {
             .arg("-D")
             .arg(data_dir)
             .arg("-o")
-            .arg(format!("-p {}", db_info.port))
+            // 额外参数传给 postgres 而不是 pg_ctl
+            .arg(
+                std::iter::once(format!("-p {}", db_info.port))
+                    .chain(network::bind_args(db_info))
+                    .chain(db_info.extra_args.iter().cloned())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+            .envs(&db_info.env)
             .spawn()
             .context("Failed to start PostgreSQL")?;
 
@@ -576,18 +797,67 @@ mod imp {
         Ok(())
     }
 
+    /// 把 PostgreSQL 扩展的 Windows 二进制包解压到安装目录，`major` 为服务器的主版本号
+    ///
+    /// PostGIS 使用 OSGeo 发布的 bundle 包（包含 bin、lib、share 目录）；pgvector 没有发布 Windows 二进制。
+    pub fn install_postgresql_extension(
+        db_info: &DatabaseInfo,
+        extension: &str,
+        major: u32,
+    ) -> Result<()> {
+        let url = match extension {
+            "postgis" => latest_postgis_bundle(major)?,
+            "vector" => bail!(
+                "pgvector does not publish Windows binaries; build it with nmake as described in its README"
+            ),
+            _ => bail!("Extension {} has no Windows binaries to download", extension),
+        };
+        download_and_extract_zip(
+            x64_only_url("PostGIS", &url)?,
+            Path::new(&db_info.install_path),
+            None,
+        )
+    }
+
+    /// OSGeo 目录中该主版本最新的 PostGIS bundle 包地址，文件名形如 postgis-bundle-pg17-3.5.2x64.zip
+    fn latest_postgis_bundle(major: u32) -> Result<String> {
+        let base = format!("https://download.osgeo.org/postgis/windows/pg{}/", major);
+        let listing = tauri::async_runtime::block_on(async {
+            download::get(&base)
+                .await?
+                .text()
+                .await
+                .context("Failed to read the PostGIS download listing")
+        })?;
+        let prefix = format!("postgis-bundle-pg{}-", major);
+        let version_of = |file: &str| -> Vec<u32> {
+            file[prefix.len()..file.len() - "x64.zip".len()]
+                .split('.')
+                .filter_map(|part| part.parse().ok())
+                .collect()
+        };
+        listing
+            .split('"')
+            .filter(|link| link.starts_with(&prefix) && link.ends_with("x64.zip"))
+            .max_by_key(|link| version_of(link))
+            .map(|file| format!("{}{}", base, file))
+            .with_context(|| format!("No PostGIS build found for PostgreSQL {}", major))
+    }
+
     // --- Neo4j Implementation ---
 
     fn install_neo4j(
         storage_path: &Path,
         options: &WindowsInstallOptions<'_>,
     ) -> Result<DatabaseInfo> {
-        let bin_dir = utils::get_db_bin_path(storage_path, "neo4j");
+        let version = options.version.unwrap_or(neo4j::DEFAULT_VERSION);
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "neo4j", version);
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "neo4j");
-        let logs_dir = utils::get_db_log_path(storage_path, "neo4j");
-        let config_dir = utils::get_db_config_path(storage_path, "neo4j");
+        let instance_dir = utils::get_instance_dir_name("neo4j", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         utils::ensure_dir(&config_dir)?;
@@ -596,25 +866,37 @@ mod imp {
         let log_file = logs_dir.join("neo4j.log");
 
         if !binary_path.exists() {
-            let url = "https://neo4j.com/artifact.php?name=neo4j-community-5.20.0-windows.zip";
-            download_and_extract_zip(url, &bin_dir)?;
+            let url = format!(
+                "https://neo4j.com/artifact.php?name=neo4j-community-{}-windows.zip",
+                version
+            );
+            download_and_extract_zip(&url, &bin_dir, options.archive)?;
         }
 
         let port = options.port.unwrap_or(7474);
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "Neo4j".to_string(),
+            name: options.instance_name.unwrap_or("Neo4j").to_string(),
             db_type: DatabaseType::Neo4j,
-            version: "5.20.0".to_string(),
+            backend: BackendKind::Native,
+            version: version.to_string(),
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
             log_path: log_file.to_string_lossy().to_string(),
             port,
             username: options.username.map(|s| s.to_string()).or(Some("neo4j".to_string())),
             password: options.password.map(|s| s.to_string()).or(Some("password".to_string())),
+            credential_ref: None,
             config: None,
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -639,6 +921,8 @@ mod imp {
             .arg("/C")
             .arg(binary_path)
             .arg("console")
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start Neo4j")?;
 
@@ -646,6 +930,172 @@ mod imp {
         Ok(())
     }
 
+    // --- OpenSearch Implementation ---
+
+    fn install_opensearch(
+        storage_path: &Path,
+        options: &WindowsInstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let version = options.version.unwrap_or(opensearch::DEFAULT_VERSION);
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "opensearch", version);
+        utils::ensure_dir(&bin_dir)?;
+
+        let instance_dir = utils::get_instance_dir_name("opensearch", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+        let log_file = opensearch::log_file(&logs_dir);
+
+        if !bin_dir.join("bin").join("opensearch.bat").exists() {
+            let url = opensearch::download_url(version, "windows-x64", "zip");
+            download_and_extract_zip(&url, &bin_dir, options.archive)?;
+        }
+
+        let port = options.port.unwrap_or(9200);
+        let config_path = opensearch::write_config(
+            &bin_dir.join("config"),
+            &config_dir,
+            &data_dir,
+            &logs_dir,
+            port,
+        )?;
+
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options.instance_name.unwrap_or("OpenSearch").to_string(),
+            db_type: DatabaseType::OpenSearch,
+            backend: BackendKind::Native,
+            version: version.to_string(),
+            install_path: bin_dir.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_file.to_string_lossy().to_string(),
+            port,
+            username: None,
+            password: None,
+            credential_ref: None,
+            config: Some(config_path.to_string_lossy().to_string()),
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        if options.auto_start {
+            start_opensearch_process(&db_info)?;
+            db_info.status = DatabaseStatus::Running;
+        }
+
+        Ok(db_info)
+    }
+
+    fn start_opensearch_process(db_info: &DatabaseInfo) -> Result<()> {
+        let binary_path = Path::new(&db_info.install_path)
+            .join("bin")
+            .join("opensearch.bat");
+        let data_dir = Path::new(&db_info.data_path);
+        let pid_path = data_dir.join("opensearch.pid");
+
+        let child = Command::new("cmd")
+            .arg("/C")
+            .arg(binary_path)
+            .env("OPENSEARCH_PATH_CONF", opensearch::config_dir(db_info)?)
+            .args(network::bind_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .context("Failed to start OpenSearch")?;
+
+        fs::write(&pid_path, child.id().to_string())?;
+        Ok(())
+    }
+
+    // --- InfluxDB Implementation ---
+
+    fn install_influxdb(
+        storage_path: &Path,
+        options: &WindowsInstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let version = options.version.unwrap_or(influxdb::DEFAULT_VERSION);
+        let bin_dir = utils::get_db_version_bin_path(storage_path, "influxdb", version);
+        utils::ensure_dir(&bin_dir)?;
+
+        let instance_dir = utils::get_instance_dir_name("influxdb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        utils::ensure_dir(&data_dir)?;
+        utils::ensure_dir(&logs_dir)?;
+        let log_file = logs_dir.join("influxd.log");
+
+        if !bin_dir.join("influxd.exe").exists() {
+            let url = influxdb::download_url(version, "-windows.zip");
+            download_and_extract_zip(&url, &bin_dir, options.archive)?;
+            influxdb::flatten_binary(&bin_dir, "influxd.exe")?;
+        }
+
+        let username = options.username.unwrap_or("admin");
+        let password = options.password.unwrap_or("admin888");
+        let mut db_info = DatabaseInfo {
+            id: utils::generate_id(),
+            name: options.instance_name.unwrap_or("InfluxDB").to_string(),
+            db_type: DatabaseType::InfluxDB,
+            backend: BackendKind::Native,
+            version: version.to_string(),
+            install_path: bin_dir.to_string_lossy().to_string(),
+            data_path: data_dir.to_string_lossy().to_string(),
+            log_path: log_file.to_string_lossy().to_string(),
+            port: options.port.unwrap_or(8086),
+            username: Some(username.to_string()),
+            password: None,
+            credential_ref: None,
+            config: None,
+            status: DatabaseStatus::Stopped,
+            auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+            pid: None,
+            created_at: utils::get_timestamp(),
+            updated_at: utils::get_timestamp(),
+        };
+
+        // 首次启动后完成初始化，生成的 token 作为实例凭据
+        start_influxdb_process(&db_info)?;
+        influxdb::finish_install(&mut db_info, username, password, stop_service)?;
+
+        Ok(db_info)
+    }
+
+    fn start_influxdb_process(db_info: &DatabaseInfo) -> Result<()> {
+        let binary_path = Path::new(&db_info.install_path).join("influxd.exe");
+        let data_dir = Path::new(&db_info.data_path);
+        let pid_path = data_dir.join("influxdb.pid");
+
+        let child = Command::new(binary_path)
+            .args(influxdb::server_args(db_info))
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
+            .spawn()
+            .context("Failed to start InfluxDB")?;
+
+        fs::write(&pid_path, child.id().to_string())?;
+        Ok(())
+    }
+
     // --- SeekDB Implementation ---
 
     fn install_seekdb(
@@ -655,9 +1105,10 @@ mod imp {
         let bin_dir = utils::get_db_bin_path(storage_path, "seekdb");
         utils::ensure_dir(&bin_dir)?;
 
-        let data_dir = utils::get_db_data_path(storage_path, "seekdb");
-        let logs_dir = utils::get_db_log_path(storage_path, "seekdb");
-        let config_dir = utils::get_db_config_path(storage_path, "seekdb");
+        let instance_dir = utils::get_instance_dir_name("seekdb", options.instance_name);
+        let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+        let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+        let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
         utils::ensure_dir(&data_dir)?;
         utils::ensure_dir(&logs_dir)?;
         utils::ensure_dir(&config_dir)?;
@@ -668,7 +1119,7 @@ mod imp {
 
         if !binary_path.exists() {
             let url = "https://github.com/seekdb/seekdb/releases/download/v0.1.0/seekdb_0.1.0_windows_amd64.zip";
-            download_and_extract_zip(url, &bin_dir)?;
+            download_and_extract_zip(url, &bin_dir, options.archive)?;
         }
 
         let port = options.port.unwrap_or(8080);
@@ -682,8 +1133,9 @@ mod imp {
 
         let mut db_info = DatabaseInfo {
             id: utils::generate_id(),
-            name: "SeekDB".to_string(),
+            name: options.instance_name.unwrap_or("SeekDB").to_string(),
             db_type: DatabaseType::SeekDB,
+            backend: BackendKind::Native,
             version: "0.1.0".to_string(),
             install_path: bin_dir.to_string_lossy().to_string(),
             data_path: data_dir.to_string_lossy().to_string(),
@@ -691,9 +1143,17 @@ mod imp {
             port,
             username: options.username.map(|s| s.to_string()),
             password: options.password.map(|s| s.to_string()),
+            credential_ref: None,
             config: Some(config_path.to_string_lossy().to_string()),
             status: DatabaseStatus::Stopped,
             auto_start: options.auto_start,
+            depends_on: Vec::new(),
+            env: HashMap::new(),
+            extra_args: Vec::new(),
+            schedules: Vec::new(),
+            on_demand: false,
+            replica_of: None,
+            bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
             pid: None,
             created_at: utils::get_timestamp(),
             updated_at: utils::get_timestamp(),
@@ -717,6 +1177,8 @@ mod imp {
             .arg("server")
             .arg("--config")
             .arg(config_path)
+            .envs(&db_info.env)
+            .args(&db_info.extra_args)
             .spawn()
             .context("Failed to start SeekDB")?;
 
@@ -726,9 +1188,52 @@ mod imp {
 
     // --- Helper Functions ---
 
-    fn download_and_extract_zip(url: &str, target_dir: &Path) -> Result<()> {
-        let response = get(url).context("Failed to download file")?;
-        let bytes = response.bytes().context("Failed to get bytes")?;
+    /// 返回只有 x64 构建的发行包地址，ARM64 上先确认系统能仿真运行 x64 程序
+    ///
+    /// MySQL、MongoDB 和 PostgreSQL（EDB）没有提供 Windows ARM64 的 ZIP 包。
+    fn x64_only_url<'a>(product: &str, url: &'a str) -> Result<&'a str> {
+        if cfg!(target_arch = "aarch64") {
+            if !supports_x64_emulation() {
+                bail!(
+                    "{} has no Windows ARM64 build, and running the x64 build requires Windows 11",
+                    product
+                );
+            }
+            log::warn!(
+                "{} has no Windows ARM64 build, installing the x64 build to run under emulation",
+                product
+            );
+        }
+        Ok(url)
+    }
+
+    /// ARM64 上的 x64 仿真从 Windows 11（内部版本 22000）开始提供
+    fn supports_x64_emulation() -> bool {
+        Command::new("reg")
+            .args([
+                "query",
+                r"HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion",
+                "/v",
+                "CurrentBuildNumber",
+            ])
+            .output()
+            .ok()
+            .and_then(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .split_whitespace()
+                    .last()
+                    .and_then(|build| build.parse::<u32>().ok())
+            })
+            .is_some_and(|build| build >= 22000)
+    }
+
+    /// 下载并解压 zip 发行包，离线安装时 `local_archive` 为本地发行包
+    fn download_and_extract_zip(
+        url: &str,
+        target_dir: &Path,
+        local_archive: Option<&Path>,
+    ) -> Result<()> {
+        let bytes = download::fetch_blocking(url, local_archive)?;
         let reader = std::io::Cursor::new(bytes);
         let mut archive = zip::ZipArchive::new(reader).context("Failed to open zip archive")?;
 
@@ -0,0 +1,548 @@
+use crate::core::backend::{self, DatabaseBackend, InstallOptions};
+use crate::core::{network, redis, utils, BackendKind, DatabaseInfo, DatabaseStatus, DatabaseType};
+use anyhow::{bail, Context, Result};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+lazy_static! {
+    /// 本机已安装的包管理器
+    static ref AVAILABLE_MANAGERS: Vec<PackageManager> = if cfg!(target_os = "windows") {
+        [PackageManager::Winget, PackageManager::Scoop]
+            .into_iter()
+            .filter(|manager| {
+                manager
+                    .command(&["--version"])
+                    .output()
+                    .is_ok_and(|output| output.status.success())
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+}
+
+/// 通过 winget 或 Scoop 安装、以 Windows 服务运行的后端
+///
+/// 安装程序自带服务的数据库（PostgreSQL、MariaDB、MongoDB）使用 winget；
+/// Redis 和 MySQL 通过 Scoop 安装后，用自带的服务注册参数创建 `local-db-<类型>` 服务，
+/// 数据和配置放在应用的存储目录下。每种类型只能安装一个实例。
+pub struct WindowsPackageBackend;
+
+/// 当前系统能否通过包管理器安装该类型的数据库
+pub fn supports(db_type: &DatabaseType) -> bool {
+    WindowsPackage::resolve(db_type)
+        .is_ok_and(|package| AVAILABLE_MANAGERS.contains(&package.manager))
+}
+
+impl DatabaseBackend for WindowsPackageBackend {
+    fn install(
+        &self,
+        db_type: &DatabaseType,
+        storage_path: &Path,
+        options: &InstallOptions<'_>,
+    ) -> Result<DatabaseInfo> {
+        let package = WindowsPackage::resolve(db_type)?;
+        if !AVAILABLE_MANAGERS.contains(&package.manager) {
+            bail!("{} is not installed", package.manager.name());
+        }
+        let port = options.port.unwrap_or_else(|| db_type.default_port());
+
+        let mut db_info = match package.manager {
+            PackageManager::Winget => install_with_winget(&package, db_type, port, options)?,
+            PackageManager::Scoop => {
+                install_with_scoop(&package, db_type, storage_path, port, options)?
+            }
+        };
+
+        run_elevated("sc.exe", &["start", package.service])
+            .with_context(|| format!("Failed to start service {}", package.service))?;
+        db_info.status = DatabaseStatus::Running;
+
+        // Scoop 安装的 MySQL 以空密码初始化，服务启动后再设置 root 密码
+        if package.manager == PackageManager::Scoop && *db_type == DatabaseType::MySQL {
+            if let Some(password) = options.password.filter(|p| !p.is_empty()) {
+                set_mysql_root_password(&db_info, password)?;
+                db_info.password = Some(password.to_string());
+            }
+        }
+
+        Ok(db_info)
+    }
+
+    fn start(&self, db_info: &DatabaseInfo) -> Result<()> {
+        let package = WindowsPackage::resolve(&db_info.db_type)?;
+        run_elevated("sc.exe", &["start", package.service])
+    }
+
+    fn stop(&self, db_info: &DatabaseInfo) -> Result<()> {
+        let package = WindowsPackage::resolve(&db_info.db_type)?;
+        run_elevated("sc.exe", &["stop", package.service])
+    }
+
+    fn status(&self, db_info: &DatabaseInfo) -> Option<DatabaseStatus> {
+        let package = WindowsPackage::resolve(&db_info.db_type).ok()?;
+        service_status(package.service)
+    }
+
+    // 服务由 Windows 服务管理器按恢复策略重启
+    fn is_supervised(&self) -> bool {
+        false
+    }
+
+    /// 停止服务，`remove_binaries` 为 true 时卸载软件包
+    ///
+    /// Scoop 安装时删除注册的服务和实例文件；winget 安装程序的数据目录不删除。
+    fn uninstall(&self, db_info: &DatabaseInfo, remove_binaries: bool) -> Result<()> {
+        let package = WindowsPackage::resolve(&db_info.db_type)?;
+        if self.status(db_info) == Some(DatabaseStatus::Running) {
+            self.stop(db_info)?;
+        }
+
+        match package.manager {
+            PackageManager::Winget => {
+                if remove_binaries {
+                    run_checked(PackageManager::Winget.command(&[
+                        "uninstall",
+                        "--id",
+                        package.id,
+                        "--exact",
+                        "--silent",
+                    ]))?;
+                }
+            }
+            PackageManager::Scoop => {
+                run_elevated("sc.exe", &["delete", package.service])?;
+                backend::remove_instance_files(db_info)?;
+                if remove_binaries {
+                    run_checked(PackageManager::Scoop.command(&["uninstall", package.id]))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 运行安装程序，服务由安装程序创建，数据目录放在 `%ProgramData%\local-db` 下
+fn install_with_winget(
+    package: &WindowsPackage,
+    db_type: &DatabaseType,
+    port: u16,
+    options: &InstallOptions<'_>,
+) -> Result<DatabaseInfo> {
+    let program_data = std::env::var("ProgramData").unwrap_or_else(|_| r"C:\ProgramData".into());
+    let data_dir = Path::new(&program_data)
+        .join("local-db")
+        .join(db_type.as_str());
+    let password = options.password.unwrap_or_default();
+
+    let (installer_args, username, password) = match db_type {
+        DatabaseType::PostgreSQL => (
+            format!(
+                "--mode unattended --unattendedmodeui none --disable-components stackbuilder --serverport {} --superpassword \"{}\" --datadir \"{}\"",
+                port,
+                password,
+                data_dir.display()
+            ),
+            Some("postgres"),
+            Some(password),
+        ),
+        DatabaseType::MariaDB => (
+            format!(
+                "/qn SERVICENAME={} PORT={} PASSWORD=\"{}\" DATADIR=\"{}\"",
+                package.service,
+                port,
+                password,
+                data_dir.display()
+            ),
+            Some("root"),
+            Some(password),
+        ),
+        // MSI 不支持设置端口，MongoDB 服务使用默认端口且不开启认证
+        _ => (
+            format!(
+                "/qn ADDLOCAL=ServerService SHOULD_INSTALL_COMPASS=0 MONGO_DATA_PATH=\"{}\" MONGO_LOG_PATH=\"{}\"",
+                data_dir.join("data").display(),
+                data_dir.join("log").display()
+            ),
+            None,
+            None,
+        ),
+    };
+    if *db_type == DatabaseType::MongoDB && port != db_type.default_port() {
+        bail!("MongoDB installed with winget listens on its default port 27017");
+    }
+
+    let installed = PackageManager::Winget
+        .command(&["list", "--id", package.id, "--exact"])
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if installed {
+        bail!(
+            "{} is already installed; uninstall it first or use a native install",
+            package.id
+        );
+    }
+
+    let mut args = vec![
+        "install",
+        "--id",
+        package.id,
+        "--exact",
+        "--silent",
+        "--accept-package-agreements",
+        "--accept-source-agreements",
+        "--override",
+        &installer_args,
+    ];
+    if let Some(version) = options.version {
+        args.extend(["--version", version]);
+    }
+    run_checked(PackageManager::Winget.command(&args))
+        .with_context(|| format!("Failed to install {}", package.id))?;
+
+    Ok(DatabaseInfo {
+        id: utils::generate_id(),
+        name: options
+            .instance_name
+            .unwrap_or(db_type.display_name())
+            .to_string(),
+        db_type: db_type.clone(),
+        backend: BackendKind::Package,
+        version: options.version.unwrap_or("latest").to_string(),
+        install_path: format!("winget:{}", package.id),
+        data_path: data_dir.to_string_lossy().to_string(),
+        // 日志由安装程序配置的服务写入数据目录
+        log_path: String::new(),
+        port,
+        username: username.map(|s| s.to_string()),
+        password: password.map(|s| s.to_string()),
+        credential_ref: None,
+        config: None,
+        status: DatabaseStatus::Stopped,
+        auto_start: options.auto_start,
+        depends_on: Vec::new(),
+        env: HashMap::new(),
+        extra_args: Vec::new(),
+        schedules: Vec::new(),
+        on_demand: false,
+        replica_of: None,
+        bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+        pid: None,
+        created_at: utils::get_timestamp(),
+        updated_at: utils::get_timestamp(),
+    })
+}
+
+/// 通过 Scoop 安装程序文件，生成实例配置并注册 Windows 服务
+fn install_with_scoop(
+    package: &WindowsPackage,
+    db_type: &DatabaseType,
+    storage_path: &Path,
+    port: u16,
+    options: &InstallOptions<'_>,
+) -> Result<DatabaseInfo> {
+    let prefix = match scoop_prefix(package.id) {
+        Some(prefix) => prefix,
+        None => {
+            run_checked(PackageManager::Scoop.command(&["install", package.id]))
+                .with_context(|| format!("Failed to install {}", package.id))?;
+            scoop_prefix(package.id).context("Scoop did not report the install location")?
+        }
+    };
+
+    let instance_dir = utils::get_instance_dir_name(db_type.as_str(), None);
+    let data_dir = utils::get_db_data_path(storage_path, &instance_dir);
+    let logs_dir = utils::get_db_log_path(storage_path, &instance_dir);
+    let config_dir = utils::get_db_config_path(storage_path, &instance_dir);
+    utils::ensure_dir(&data_dir)?;
+    utils::ensure_dir(&logs_dir)?;
+    utils::ensure_dir(&config_dir)?;
+
+    let (config_path, log_file, username) = match db_type {
+        DatabaseType::Redis => {
+            let config_path = config_dir.join("redis.conf");
+            let log_file = logs_dir.join("redis.log");
+            let mut config_content = format!(
+                "port {port}\nbind 127.0.0.1\ndir \"{data_path}\"\nlogfile \"{log_path}\"\n",
+                port = port,
+                data_path = data_dir.to_string_lossy().replace('\\', "/"),
+                log_path = log_file.to_string_lossy().replace('\\', "/")
+            );
+            if let Some(password) = options.password.filter(|p| !p.is_empty()) {
+                config_content.push_str(&format!("requirepass {}\n", redis::quote(password)));
+            }
+            fs::write(&config_path, config_content)?;
+
+            let config_arg = config_path.to_string_lossy();
+            run_elevated(
+                &prefix.join("redis-server.exe").to_string_lossy(),
+                &[
+                    "--service-install",
+                    &config_arg,
+                    "--service-name",
+                    package.service,
+                ],
+            )?;
+            (config_path, log_file, None)
+        }
+        _ => {
+            let mysqld = prefix.join("bin").join("mysqld.exe");
+            let config_path = config_dir.join("my.ini");
+            let log_file = logs_dir.join("mysql.log");
+            let config_content = format!(
+                "[mysqld]\nbasedir={base}\ndatadir={data}\nport={port}\nbind-address=127.0.0.1\nlog-error={log}\n",
+                base = prefix.to_string_lossy().replace('\\', "/"),
+                data = data_dir.to_string_lossy().replace('\\', "/"),
+                port = port,
+                log = log_file.to_string_lossy().replace('\\', "/")
+            );
+            fs::write(&config_path, config_content)?;
+
+            if fs::read_dir(&data_dir)?.next().is_none() {
+                run_checked({
+                    let mut command = Command::new(&mysqld);
+                    command
+                        .arg(format!("--defaults-file={}", config_path.display()))
+                        .arg("--initialize-insecure");
+                    command
+                })
+                .context("Failed to initialize MySQL data directory")?;
+            }
+
+            let defaults_arg = format!("--defaults-file={}", config_path.display());
+            run_elevated(
+                &mysqld.to_string_lossy(),
+                &["--install", package.service, &defaults_arg],
+            )?;
+            (config_path, log_file, Some("root"))
+        }
+    };
+
+    let version = scoop_version(package.id)
+        .or_else(|| options.version.map(|v| v.to_string()))
+        .unwrap_or_else(|| "latest".to_string());
+
+    Ok(DatabaseInfo {
+        id: utils::generate_id(),
+        name: options
+            .instance_name
+            .unwrap_or(db_type.display_name())
+            .to_string(),
+        db_type: db_type.clone(),
+        backend: BackendKind::Package,
+        version,
+        install_path: format!("scoop:{}", package.id),
+        data_path: data_dir.to_string_lossy().to_string(),
+        log_path: log_file.to_string_lossy().to_string(),
+        port,
+        username: username.map(|s| s.to_string()),
+        // Redis 的密码写入配置文件，MySQL 的密码在服务启动后设置
+        password: match db_type {
+            DatabaseType::Redis => options.password.map(|s| s.to_string()),
+            _ => None,
+        },
+        credential_ref: None,
+        config: Some(config_path.to_string_lossy().to_string()),
+        status: DatabaseStatus::Stopped,
+        auto_start: options.auto_start,
+        depends_on: Vec::new(),
+        env: HashMap::new(),
+        extra_args: Vec::new(),
+        schedules: Vec::new(),
+        on_demand: false,
+        replica_of: None,
+        bind_address: network::LOCAL_BIND_ADDRESS.to_string(),
+        pid: None,
+        created_at: utils::get_timestamp(),
+        updated_at: utils::get_timestamp(),
+    })
+}
+
+/// 使用 Scoop 安装的 mysql 客户端为 root 设置密码
+fn set_mysql_root_password(db_info: &DatabaseInfo, password: &str) -> Result<()> {
+    let prefix = scoop_prefix("mysql").context("MySQL is not installed with Scoop")?;
+    let sql = format!(
+        "ALTER USER 'root'@'localhost' IDENTIFIED BY '{}';",
+        password.replace('\'', "''")
+    );
+    // 服务刚启动时可能还不能连接，重试几次
+    let mut last_error = None;
+    for _ in 0..10 {
+        let mut command = Command::new(prefix.join("bin").join("mysql.exe"));
+        command
+            .args(["-h", "127.0.0.1", "-u", "root"])
+            .arg(format!("--port={}", db_info.port))
+            .args(["-e", &sql]);
+        match run_checked(command) {
+            Ok(()) => return Ok(()),
+            Err(err) => last_error = Some(err),
+        }
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+    Err(last_error
+        .unwrap()
+        .context("Failed to set MySQL root password"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PackageManager {
+    Winget,
+    Scoop,
+}
+
+impl PackageManager {
+    fn name(&self) -> &'static str {
+        match self {
+            PackageManager::Winget => "winget",
+            PackageManager::Scoop => "Scoop",
+        }
+    }
+
+    /// scoop 是 PowerShell 脚本的 shim，需要通过 cmd 调用
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = match self {
+            PackageManager::Winget => Command::new("winget"),
+            PackageManager::Scoop => {
+                let mut command = Command::new("cmd");
+                command.args(["/C", "scoop"]);
+                command
+            }
+        };
+        command.args(args);
+        command
+    }
+}
+
+/// 数据库类型对应的软件包及其 Windows 服务名
+struct WindowsPackage {
+    manager: PackageManager,
+    id: &'static str,
+    service: &'static str,
+}
+
+impl WindowsPackage {
+    fn resolve(db_type: &DatabaseType) -> Result<Self> {
+        let (manager, id, service) = match db_type {
+            DatabaseType::PostgreSQL => (
+                PackageManager::Winget,
+                "PostgreSQL.PostgreSQL.17",
+                "postgresql-x64-17",
+            ),
+            DatabaseType::MariaDB => (PackageManager::Winget, "MariaDB.Server", "MariaDB"),
+            DatabaseType::MongoDB => (PackageManager::Winget, "MongoDB.Server", "MongoDB"),
+            DatabaseType::Redis => (PackageManager::Scoop, "redis", "local-db-redis"),
+            DatabaseType::MySQL => (PackageManager::Scoop, "mysql", "local-db-mysql"),
+            _ => bail!(
+                "{} is not available from winget or Scoop",
+                db_type.display_name()
+            ),
+        };
+        Ok(Self {
+            manager,
+            id,
+            service,
+        })
+    }
+}
+
+/// Scoop 软件包当前版本的安装目录，未安装时返回 None
+fn scoop_prefix(app: &str) -> Option<PathBuf> {
+    let output = PackageManager::Scoop
+        .command(&["prefix", app])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let prefix = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    prefix.is_dir().then_some(prefix)
+}
+
+/// Scoop 安装目录 `current` 指向的版本号
+fn scoop_version(app: &str) -> Option<String> {
+    let manifest = fs::read_to_string(scoop_prefix(app)?.join("manifest.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).ok()?;
+    manifest["version"].as_str().map(String::from)
+}
+
+/// 查询 Windows 服务的状态，服务不存在时返回 None
+pub(crate) fn service_status(service: &str) -> Option<DatabaseStatus> {
+    // Get-Service 的状态名不随系统语言变化，sc query 的输出会被本地化
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            &format!(
+                "(Get-Service -Name '{}').Status",
+                service.replace('\'', "''")
+            ),
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "Running" => Some(DatabaseStatus::Running),
+        "StartPending" => Some(DatabaseStatus::Starting),
+        "StopPending" => Some(DatabaseStatus::Stopping),
+        _ => Some(DatabaseStatus::Stopped),
+    }
+}
+
+fn run_checked(mut command: Command) -> Result<()> {
+    let output = command.output().context("Failed to run command")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        bail!(
+            "{}",
+            if stderr.trim().is_empty() {
+                stdout.trim()
+            } else {
+                stderr.trim()
+            }
+        );
+    }
+    Ok(())
+}
+
+/// 以管理员身份运行程序，通过 UAC 弹窗确认，等待其退出并检查退出码
+pub(crate) fn run_elevated(program: &str, args: &[&str]) -> Result<()> {
+    // Start-Process 用空格拼接参数，含空格的参数需要自行加双引号
+    let argument_list = args
+        .iter()
+        .map(|arg| {
+            let arg = if arg.contains(' ') {
+                format!("\"{}\"", arg)
+            } else {
+                arg.to_string()
+            };
+            format!("'{}'", arg.replace('\'', "''"))
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let script = format!(
+        "$p = Start-Process -FilePath '{}' -ArgumentList {} -Verb RunAs -WindowStyle Hidden -Wait -PassThru; exit $p.ExitCode",
+        program.replace('\'', "''"),
+        argument_list
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()
+        .context("Failed to run PowerShell")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("canceled by the user") {
+            bail!("Administrator permission was denied");
+        }
+        bail!(
+            "{} {} exited with {}",
+            program,
+            args.first().copied().unwrap_or_default(),
+            output.status.code().unwrap_or(-1)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::app::AppState;
+use crate::command;
+use crate::core::deep_link::{self, DeepLinkAction};
+use crate::core::{InstallDatabaseParams, OperationResult};
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tauri_plugin_log::log;
+use url::Url;
+
+/// 处理 `localdb://` 链接，用户确认后在后台执行，失败时弹出错误提示
+///
+/// 网页中的链接也能唤起应用，因此每个操作都需要用户确认。
+pub fn handle(app: &AppHandle, urls: Vec<Url>) {
+    for url in urls {
+        let action = match deep_link::parse(&url) {
+            Ok(action) => action,
+            Err(e) => {
+                show_error(app, format!("{:#}", e));
+                continue;
+            }
+        };
+        log::info!("Deep link {} requested", url);
+
+        let handle = app.clone();
+        app.dialog()
+            .message(action.prompt())
+            .title("local-db")
+            .buttons(MessageDialogButtons::OkCancelCustom(
+                action.verb().to_string(),
+                "Cancel".to_string(),
+            ))
+            .show(move |confirmed| {
+                if !confirmed {
+                    return;
+                }
+                tauri::async_runtime::spawn(async move {
+                    if let Err(message) = perform(&handle, action).await {
+                        show_error(&handle, message);
+                    }
+                });
+            });
+    }
+}
+
+async fn perform(app: &AppHandle, action: DeepLinkAction) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    match action {
+        DeepLinkAction::Start(target) => {
+            let id = resolve(&state, &target)?;
+            into_result(
+                command::start_database(state, id)
+                    .await
+                    .map_err(|e| e.to_string())?,
+            )
+        }
+        DeepLinkAction::Stop(target) => {
+            let id = resolve(&state, &target)?;
+            into_result(
+                command::stop_database(state, id)
+                    .await
+                    .map_err(|e| e.to_string())?,
+            )
+        }
+        DeepLinkAction::Install {
+            db_type,
+            version,
+            name,
+            port,
+        } => {
+            let params = InstallDatabaseParams {
+                db_type,
+                version,
+                name,
+                port,
+                username: None,
+                password: None,
+                backend: Default::default(),
+                preset: None,
+                env: HashMap::new(),
+                extra_args: Vec::new(),
+            };
+            // 安装进度通过 install-progress 事件推送，与界面发起的安装相同
+            command::install_database(state, params, app.clone())
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+fn resolve(state: &AppState, target: &str) -> Result<String, String> {
+    let databases = state.get_all_databases();
+    deep_link::resolve_target(&databases, target)
+        .map(|db_info| db_info.id.clone())
+        .map_err(|e| e.to_string())
+}
+
+fn into_result(result: OperationResult<()>) -> Result<(), String> {
+    if result.success {
+        Ok(())
+    } else {
+        Err(result.message)
+    }
+}
+
+fn show_error(app: &AppHandle, message: String) {
+    app.dialog()
+        .message(message)
+        .title("local-db")
+        .kind(MessageDialogKind::Error)
+        .show(|_| {});
+}
@@ -1,11 +1,22 @@
-mod app;
-mod command;
-mod core;
+#[cfg(feature = "api-server")]
+mod api;
+// 应用状态和数据库管理也供命令行工具 `local-db-cli` 使用
+pub mod app;
+pub mod command;
+pub mod core;
+mod deep_link;
+mod notify;
+mod tray;
 
 use app::AppState;
-use tauri::Manager;
+use core::AppNotification;
+use tauri::{Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_log::log;
 
+/// 登录时由系统启动应用的参数，带此参数启动时不显示主窗口
+const LAUNCHED_AT_LOGIN_ARG: &str = "--launched-at-login";
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // 创建应用状态
@@ -14,8 +25,14 @@ pub fn run() {
     let startup_duration = startup_time.elapsed();
     println!("Startup time: {}ms", startup_duration.as_millis());
 
+    // 定期清理过期备份
+    app_state.start_backup_pruner();
+    // 定期采集系统和数据库指标
+    app_state.start_metrics_sampler();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_positioner::init())
         .plugin(tauri_plugin_os::init())
         .plugin(
@@ -23,7 +40,11 @@ pub fn run() {
                 .level(log::LevelFilter::Info)
                 .build(),
         )
-        .plugin(tauri_plugin_autostart::Builder::new().build())
+        .plugin(
+            tauri_plugin_autostart::Builder::new()
+                .arg(LAUNCHED_AT_LOGIN_ARG)
+                .build(),
+        )
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             app.get_webview_window("main")
                 .map(|window| {
@@ -33,11 +54,101 @@ pub fn run() {
                     log::error!("Failed to get main window");
                 });
         }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .manage(app_state.clone())
+        .setup({
+            let app_state = app_state.clone();
+            move |app| {
+                // 守护直接启动的数据库进程，崩溃时通知前端，并发送系统通知（连续崩溃时只通知第一次）
+                let app_handle = app.handle().clone();
+                app_state.start_watchdog(move |event| {
+                    if event.attempt <= 1 {
+                        let body = if event.restarting {
+                            "The process exited unexpectedly and is being restarted"
+                        } else {
+                            "The process exited unexpectedly"
+                        };
+                        notify::send(
+                            &app_handle,
+                            AppNotification {
+                                title: format!("{} crashed", event.name),
+                                body: body.to_string(),
+                                error: true,
+                            },
+                        );
+                    }
+                    let _ = app_handle.emit("database-crashed", event);
+                });
+
+                // 按依赖顺序启动设置了自动启动的数据库，失败时发送系统通知
+                let app_handle = app.handle().clone();
+                app_state
+                    .spawn_autostart(move |notification| notify::send(&app_handle, notification));
+                // 按定时计划启动、停止数据库
+                let app_handle = app.handle().clone();
+                app_state
+                    .start_scheduler(move |notification| notify::send(&app_handle, notification));
+                // 为开启按需启动的数据库代为监听端口
+                let app_handle = app.handle().clone();
+                app_state.start_activation_monitor(move |notification| {
+                    notify::send(&app_handle, notification)
+                });
+                // 存储目录所在磁盘空间不足时发出警告
+                let app_handle = app.handle().clone();
+                app_state.start_disk_monitor(move |notification| {
+                    notify::send(&app_handle, notification)
+                });
+
+                // 处理 localdb:// 链接；应用已在运行时，链接由单实例插件转发过来
+                #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+                if let Err(e) = app.deep_link().register_all() {
+                    log::warn!("Failed to register the localdb:// scheme: {}", e);
+                }
+                let link_handle = app.handle().clone();
+                app.deep_link()
+                    .on_open_url(move |event| deep_link::handle(&link_handle, event.urls()));
+                // 链接启动应用时，链接在启动参数中
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    deep_link::handle(app.handle(), urls);
+                }
+                #[cfg(feature = "api-server")]
+                api::start(app.handle().clone());
+
+                if let Err(e) = tray::init(app.handle()) {
+                    log::warn!("Failed to create the tray icon: {}", e);
+                }
+
+                // 登录时启动与设置保持一致，应用未运行时设置文件可能被修改过
+                if let Err(e) = command::apply_launch_at_login(
+                    app.handle(),
+                    app_state.get_settings().auto_start,
+                ) {
+                    log::warn!("{}", e);
+                }
+                // 登录时启动只在后台运行数据库，窗口隐藏在托盘中
+                if std::env::args().any(|arg| arg == LAUNCHED_AT_LOGIN_ARG)
+                    && app.tray_by_id(tray::TRAY_ID).is_some()
+                {
+                    if let Some(window) = app.get_webview_window("main") {
+                        let _ = window.hide();
+                    }
+                }
+                Ok(())
+            }
+        })
+        // 关闭主窗口时隐藏到托盘，数据库继续运行，通过托盘菜单退出应用
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if window.app_handle().tray_by_id(tray::TRAY_ID).is_some() {
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             // 数据库命令
             command::get_databases,
@@ -45,25 +156,119 @@ pub fn run() {
             command::get_database_by_type,
             command::start_database,
             command::stop_database,
+            command::start_all_databases,
+            command::stop_all_databases,
+            command::start_autostart_databases,
             command::restart_database,
+            command::update_database_port,
+            command::update_database_password,
+            command::enable_mongodb_auth,
+            command::secure_redis,
             command::get_database_status,
+            command::check_database_health,
+            command::execute_query,
+            command::get_slow_queries,
+            command::get_query_stats,
+            command::setup_replica,
+            command::get_replication_status,
+            command::list_available_extensions,
+            command::install_extension,
+            command::execute_redis_command,
+            command::scan_redis_keys,
+            command::list_mongo_databases,
+            command::list_mongo_collections,
+            command::run_mongo_command,
+            command::execute_surreal_query,
+            command::get_database_objects,
+            command::create_logical_database,
+            command::create_user,
+            command::import_dump,
+            command::run_benchmark,
+            command::get_benchmark_history,
+            command::seed_sample_data,
+            command::test_connection,
+            command::get_connection_uri,
+            command::list_sqlite_files,
+            command::create_sqlite_file,
             command::delete_database,
+            command::uninstall_database,
+            command::adopt_database,
+            command::register_custom_server,
             command::install_database,
+            command::install_database_from_archive,
+            command::is_docker_available,
+            command::get_available_versions,
             command::update_database_autostart,
+            command::update_database_schedules,
+            command::update_database_on_demand,
+            command::update_database_dependencies,
             command::get_task_status,
+            command::list_tasks,
+            command::clear_completed_tasks,
+            command::retry_task,
             command::sync_databases_status,
+            command::get_database_config,
+            command::update_database_config,
+            command::apply_database_config,
+            command::update_database_launch_options,
+            command::expose_database,
+            command::copy_connection_info,
+            command::generate_env_snippet,
+            command::list_database_clients,
+            command::open_in_client,
+            command::move_database_data,
+            command::reset_database,
+            command::get_database_disk_usage,
+            command::apply_preset,
+            command::list_config_versions,
+            command::rollback_config,
             command::read_database_logs,
             command::clear_database_logs,
+            command::tail_database_log,
+            command::stop_tail_database_log,
             command::save_logs_to_file,
+            // 备份命令
+            command::backup_database,
+            command::export_data,
+            command::restore_database,
+            command::list_backups,
+            command::delete_backup,
+            command::snapshot_database,
+            command::list_snapshots,
+            command::restore_snapshot,
+            command::delete_snapshot,
+            command::upload_backup,
+            command::prune_backups,
             // 设置命令
             command::get_settings,
             command::update_settings,
+            command::migrate_storage_path,
+            command::export_app_state,
+            command::export_compose,
+            command::import_app_state,
+            command::get_download_cache_info,
+            command::clear_download_cache,
             // 系统信息命令
             command::get_system_info,
             command::get_cpu_usage,
+            command::get_metrics_history,
             command::get_memory_info,
             command::get_disk_info,
+            // 诊断命令
+            command::export_diagnostics,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(move |_app_handle, event| match event {
+            // 退出时停止由应用启动的数据库，避免直接启动的进程残留
+            tauri::RunEvent::Exit => {
+                if app_state.get_settings().stop_on_exit {
+                    app_state.stop_started_databases();
+                }
+            }
+            // 窗口隐藏到托盘后，点击 Dock 图标重新显示
+            #[cfg(target_os = "macos")]
+            tauri::RunEvent::Reopen { .. } => tray::show_main_window(_app_handle),
+            _ => {}
+        });
 }
@@ -0,0 +1,22 @@
+use crate::app::AppState;
+use crate::core::AppNotification;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_log::log;
+use tauri_plugin_notification::NotificationExt;
+
+/// 按设置中的通知范围发送系统通知，窗口隐藏时也能看到
+pub fn send(app: &AppHandle, notification: AppNotification) {
+    let level = app.state::<AppState>().get_settings().notification_level;
+    if !level.allows(&notification) {
+        return;
+    }
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(notification.title)
+        .body(notification.body)
+        .show()
+    {
+        log::warn!("Failed to show notification: {}", e);
+    }
+}
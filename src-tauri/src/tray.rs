@@ -0,0 +1,215 @@
+use crate::app::AppState;
+use crate::command;
+use crate::core::tray::{self, TrayAction, TrayDatabase};
+use crate::core::{AppNotification, CommandError, DatabaseInfo, OperationResult};
+use crate::notify;
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::menu::{Menu, MenuBuilder, MenuItem, MenuItemBuilder, SubmenuBuilder};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager, Wry};
+use tauri_plugin_log::log;
+use tauri_plugin_opener::OpenerExt;
+
+/// 托盘图标 ID，图标创建失败时关闭窗口直接退出应用
+pub const TRAY_ID: &str = "main";
+
+/// 检查数据库状态以更新托盘菜单的间隔
+const REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+
+/// 创建托盘图标，菜单列出各数据库的状态和常用操作，关闭窗口后也能管理数据库
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let databases = current_databases(app);
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("local-db")
+        .menu(&build_menu(app, &databases)?)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| {
+            if let Some(action) = TrayAction::parse(event.id().as_ref()) {
+                perform(app, action);
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    // 状态也会在界面、命令行和守护任务中变化，定期检查，有变化时重建菜单
+    let handle = app.clone();
+    std::thread::spawn(move || {
+        let mut shown = databases;
+        loop {
+            std::thread::sleep(REFRESH_INTERVAL);
+            let databases = current_databases(&handle);
+            if databases != shown {
+                refresh(&handle, &databases);
+                shown = databases;
+            }
+        }
+    });
+    Ok(())
+}
+
+/// 显示并聚焦主窗口
+pub fn show_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        log::error!("Failed to get main window");
+        return;
+    };
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+}
+
+fn current_databases(app: &AppHandle) -> Vec<TrayDatabase> {
+    let state = app.state::<AppState>();
+    let databases: Vec<DatabaseInfo> = state
+        .get_all_databases()
+        .into_iter()
+        .map(|db_info| state.db_manager.with_transient_status(db_info))
+        .collect();
+    tray::databases(&databases)
+}
+
+fn build_menu(app: &AppHandle, databases: &[TrayDatabase]) -> tauri::Result<Menu<Wry>> {
+    let mut menu = MenuBuilder::new(app)
+        .text(TrayAction::Show.id(), "Show local-db")
+        .separator();
+    if databases.is_empty() {
+        let empty = MenuItemBuilder::new("No databases installed")
+            .enabled(false)
+            .build(app)?;
+        menu = menu.item(&empty);
+    }
+    for db in databases {
+        let id = &db.id;
+        let start = item(app, TrayAction::Start(id.clone()), "Start", db.can_start)?;
+        let stop = item(app, TrayAction::Stop(id.clone()), "Stop", db.can_stop)?;
+        let restart = item(app, TrayAction::Restart(id.clone()), "Restart", db.can_stop)?;
+        let logs = item(
+            app,
+            TrayAction::OpenLogs(id.clone()),
+            "Open Logs",
+            db.has_log,
+        )?;
+        let submenu = SubmenuBuilder::new(app, &db.label)
+            .items(&[&start, &stop, &restart])
+            .separator()
+            .item(&logs)
+            .build()?;
+        menu = menu.item(&submenu);
+    }
+    menu.separator()
+        .text(TrayAction::StartAll.id(), "Start All")
+        .text(TrayAction::StopAll.id(), "Stop All")
+        .separator()
+        .text(TrayAction::Quit.id(), "Quit local-db")
+        .build()
+}
+
+fn item(
+    app: &AppHandle,
+    action: TrayAction,
+    text: &str,
+    enabled: bool,
+) -> tauri::Result<MenuItem<Wry>> {
+    MenuItemBuilder::with_id(action.id(), text)
+        .enabled(enabled)
+        .build(app)
+}
+
+fn refresh(app: &AppHandle, databases: &[TrayDatabase]) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let updated = build_menu(app, databases).and_then(|menu| tray.set_menu(Some(menu)));
+    if let Err(e) = updated {
+        log::warn!("Failed to update the tray menu: {}", e);
+    }
+}
+
+fn perform(app: &AppHandle, action: TrayAction) {
+    match action {
+        TrayAction::Show => show_main_window(app),
+        TrayAction::Quit => app.exit(0),
+        TrayAction::OpenLogs(id) => open_logs(app, &id),
+        action => {
+            let handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                run(&handle, action).await;
+                refresh(&handle, &current_databases(&handle));
+            });
+        }
+    }
+}
+
+/// 执行启动、停止操作，失败时发送系统通知
+async fn run(app: &AppHandle, action: TrayAction) {
+    let state = app.state::<AppState>();
+    let (verb, results) = match action {
+        TrayAction::Start(id) => (
+            "start",
+            single(id.clone(), command::start_database(state, id).await),
+        ),
+        TrayAction::Stop(id) => (
+            "stop",
+            single(id.clone(), command::stop_database(state, id).await),
+        ),
+        TrayAction::Restart(id) => (
+            "restart",
+            single(id.clone(), command::restart_database(state, id).await),
+        ),
+        TrayAction::StartAll => ("start", command::start_all_databases(state).await),
+        TrayAction::StopAll => ("stop", command::stop_all_databases(state).await),
+        _ => return,
+    };
+
+    let state = app.state::<AppState>();
+    let failures = match results {
+        Ok(results) => results
+            .into_iter()
+            .filter(|(_, result)| !result.success)
+            .map(|(id, result)| {
+                let name = state
+                    .get_database(&id)
+                    .map(|db_info| db_info.name)
+                    .unwrap_or(id);
+                (format!("Failed to {} {}", verb, name), result.message)
+            })
+            .collect(),
+        Err(e) => vec![(format!("Failed to {} databases", verb), e.to_string())],
+    };
+    for (title, body) in failures {
+        notify::send(
+            app,
+            AppNotification {
+                title,
+                body,
+                error: true,
+            },
+        );
+    }
+}
+
+fn single(
+    id: String,
+    result: Result<OperationResult<()>, CommandError>,
+) -> Result<HashMap<String, OperationResult<()>>, CommandError> {
+    result.map(|result| HashMap::from([(id, result)]))
+}
+
+fn open_logs(app: &AppHandle, id: &str) {
+    let Some(db_info) = app.state::<AppState>().get_database(id) else {
+        return;
+    };
+    if let Err(e) = app.opener().open_path(&db_info.log_path, None::<&str>) {
+        notify::send(
+            app,
+            AppNotification {
+                title: format!("Failed to open logs of {}", db_info.name),
+                body: e.to_string(),
+                error: true,
+            },
+        );
+    }
+}
@@ -0,0 +1,103 @@
+import { invoke } from '@tauri-apps/api/core';
+import { OperationResult } from './database';
+
+// 备份方式：logical 为逻辑导出，physical 为物理备份（仅 PostgreSQL）
+export type BackupMode = 'logical' | 'physical' | 'export';
+
+// 数据导出格式
+export type ExportFormat = 'sql' | 'csv' | 'json';
+
+// 备份记录接口
+export interface BackupRecord {
+  id: string;
+  database_id: string;
+  file_path: string;
+  file_size: number;
+  created_at: string;
+  mode: BackupMode;
+  encrypted: boolean;
+  salt?: string;
+  remote_url?: string;
+  // 导出记录的格式和数据库名，备份为空
+  export_format?: ExportFormat | null;
+  export_database?: string | null;
+}
+
+// 备份数据库，backupMode 默认为 logical，提供 passphrase 时加密备份文件
+export async function backupDatabase(
+  id: string,
+  backupMode?: BackupMode,
+  passphrase?: string
+): Promise<OperationResult<BackupRecord>> {
+  return invoke('backup_database', { id, backupMode, passphrase });
+}
+
+// 导出数据到 path：sql 写入单个文件，csv / json 在目录下为每张表生成一个文件，记录出现在备份历史中
+export async function exportData(
+  id: string,
+  targetDb: string,
+  format: ExportFormat,
+  path: string
+): Promise<OperationResult<BackupRecord>> {
+  return invoke('export_data', { id, targetDb, format, path });
+}
+
+// 从备份恢复数据库，加密备份需要提供相同的 passphrase
+export async function restoreDatabase(backupId: string, passphrase?: string): Promise<OperationResult> {
+  return invoke('restore_database', { backupId, passphrase });
+}
+
+// 获取备份历史，不指定 databaseId 时返回全部
+export async function listBackups(databaseId?: string): Promise<BackupRecord[]> {
+  return invoke('list_backups', { databaseId });
+}
+
+// 删除备份
+export async function deleteBackup(backupId: string): Promise<OperationResult> {
+  return invoke('delete_backup', { backupId });
+}
+
+// 备份清理结果
+export interface BackupPruneResult {
+  removed_count: number;
+  reclaimed_bytes: number;
+}
+
+// 上传备份到远程存储
+export async function uploadBackup(backupId: string): Promise<OperationResult<BackupRecord>> {
+  return invoke('upload_backup', { backupId });
+}
+
+// 清理超出保留期限的备份
+export async function pruneBackups(): Promise<OperationResult<BackupPruneResult>> {
+  return invoke('prune_backups');
+}
+
+// 数据目录快照
+export interface DataSnapshot {
+  id: string;
+  database_id: string;
+  label: string;
+  size: number;
+  created_at: string;
+}
+
+// 为数据目录创建快照，运行中的数据库会先停止，完成后重新启动
+export async function snapshotDatabase(id: string, label: string): Promise<OperationResult<DataSnapshot>> {
+  return invoke('snapshot_database', { id, label });
+}
+
+// 获取数据库的快照列表，最新的在前
+export async function listSnapshots(id: string): Promise<OperationResult<DataSnapshot[]>> {
+  return invoke('list_snapshots', { id });
+}
+
+// 用快照替换数据目录
+export async function restoreSnapshot(id: string, snapshotId: string): Promise<OperationResult> {
+  return invoke('restore_snapshot', { id, snapshotId });
+}
+
+// 删除快照
+export async function deleteSnapshot(id: string, snapshotId: string): Promise<OperationResult> {
+  return invoke('delete_snapshot', { id, snapshotId });
+}
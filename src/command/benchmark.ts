@@ -0,0 +1,36 @@
+import { invoke } from '@tauri-apps/api/core';
+import { DatabaseType } from './database';
+
+// 压测强度：quick 10 秒 4 个连接，standard 60 秒 16 个连接，heavy 5 分钟 64 个连接
+export type BenchmarkProfile = 'quick' | 'standard' | 'heavy';
+
+// 压测结果
+export interface BenchmarkResult {
+  id: string;
+  database_id: string;
+  database_name: string;
+  db_type: DatabaseType;
+  version: string;
+  // 使用的压测工具：sysbench、pgbench、memtier_benchmark 或 redis-benchmark
+  tool: string;
+  profile: BenchmarkProfile;
+  // 备注，如压测时使用的配置预设
+  label?: string | null;
+  clients: number;
+  duration_secs: number;
+  // 每秒事务数（sysbench、pgbench）或每秒请求数（Redis）
+  throughput: number;
+  avg_latency_ms?: number | null;
+  p95_latency_ms?: number | null;
+  created_at: string;
+}
+
+// 对运行中的 MySQL / MariaDB / PostgreSQL / Redis 实例压测，返回任务ID，进度通过 benchmark-progress 事件推送
+export async function runBenchmark(id: string, profile?: BenchmarkProfile, label?: string): Promise<string> {
+  return invoke('run_benchmark', { id, profile, label });
+}
+
+// 获取压测历史，按时间顺序排列，传入 id 时只返回该数据库的结果
+export async function getBenchmarkHistory(id?: string): Promise<BenchmarkResult[]> {
+  return invoke('get_benchmark_history', { id });
+}
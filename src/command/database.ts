@@ -1,14 +1,17 @@
 import { invoke } from '@tauri-apps/api/core';
 
 // 数据库类型
-export type DatabaseType = 'mysql' | 'postgresql' | 'mongodb' | 'redis' | 'qdrant' | 'seekdb' | 'surrealdb';
+export type DatabaseType = 'mysql' | 'mariadb' | 'postgresql' | 'mongodb' | 'redis' | 'qdrant' | 'seekdb' | 'surrealdb' | 'opensearch' | 'influxdb' | 'cassandra' | 'sqlite' | 'duckdb' | 'custom';
 
 // 数据库状态
-export type DatabaseStatus = 'running' | 'stopped' | 'notinstalled';
+export type DatabaseStatus = 'running' | 'stopped' | 'notinstalled' | 'starting' | 'stopping' | 'crashed';
 
 // 任务状态
 export type TaskStatus = 'pending' | 'running' | 'completed' | 'failed';
 
+// 数据库运行方式
+export type BackendKind = 'native' | 'docker' | 'package' | 'launch_agent' | 'external';
+
 // 异步任务信息接口
 export interface AsyncTask {
   id: string;
@@ -18,6 +21,10 @@ export interface AsyncTask {
   progress: number;
   message: string;
   error?: string;
+  // 失败原因的错误码，未知原因时为空
+  error_code?: AppError | null;
+  // 在安装队列中的位置（从 1 开始），开始执行后为空
+  queue_position?: number | null;
   created_at: string;
   updated_at: string;
 }
@@ -27,6 +34,7 @@ export interface DatabaseInfo {
   id: string;
   name: string;
   type: string;
+  backend?: BackendKind;
   version?: string;
   install_path?: string;
   data_path?: string;
@@ -34,9 +42,24 @@ export interface DatabaseInfo {
   port?: number;
   username?: string;
   password?: string;
+  // 密码保存在系统钥匙串时的引用
+  credential_ref?: string;
   config?: string;
   status?: DatabaseStatus;
   auto_start?: boolean;
+  // 启动前需要先就绪的数据库 ID
+  depends_on?: string[];
+  // 启动进程时额外设置的环境变量和追加的命令行参数
+  env?: Record<string, string>;
+  extra_args?: string[];
+  // 定时启动、停止的计划
+  schedules?: DatabaseSchedule[];
+  // 停止时由应用代为监听端口，收到第一个连接时自动启动数据库
+  on_demand?: boolean;
+  // 作为副本时，复制来源数据库的 ID
+  replica_of?: string | null;
+  // 监听地址，默认 127.0.0.1，开放到网络时为 0.0.0.0
+  bind_address?: string;
   pid?: number;
   created_at?: string;
   updated_at?: string;
@@ -49,17 +72,62 @@ export interface OperationResult<T = void> {
   success: boolean;
   message: string;
   data?: T;
+  // 失败原因的错误码，未知原因时为空
+  error?: AppError;
 }
 
+// 可识别的错误，按 code 区分，context 为错误的参数，可用于本地化提示
+export type AppError =
+  | { code: 'database_not_found' }
+  | { code: 'port_in_use'; context: { port: number } }
+  | { code: 'download_failed'; context: { url: string; reason: string } }
+  | { code: 'brew_missing' }
+  | { code: 'data_dir_not_empty'; context: { path: string } }
+  | { code: 'insufficient_disk_space'; context: { path: string; required: number; available: number } }
+  | { code: 'docker_unavailable' }
+  | { code: 'invalid_settings'; context: { fields: FieldError[] } };
+
+// 设置中不合法的字段，嵌套字段用 . 连接，如 remote_backup.endpoint
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+export type ErrorCode = AppError['code'];
+
+// 命令调用失败时抛出的错误（invoke 被拒绝）：已知原因为 AppError，其他为文本
+export type CommandError = AppError | string;
+
 // 安装数据库参数
 export interface InstallDatabaseParams {
   db_type: string; // 改为 string 以支持所有数据库类型
   version?: string;
+  // 实例名，同类型已有实例时必填
+  name?: string;
   port?: number;
   username?: string;
   password?: string;
+  // 运行方式，默认 native
+  backend?: BackendKind;
+  // 安装后应用的资源配置预设
+  preset?: ConfigPreset;
+  // 启动进程时额外设置的环境变量和追加的命令行参数
+  env?: Record<string, string>;
+  extra_args?: string[];
 }
 
+// 数据库的定时启停计划，按本地时间执行
+export interface DatabaseSchedule {
+  action: 'start' | 'stop';
+  // 执行时间，格式为 HH:MM
+  time: string;
+  // 执行的星期，1 为周一，7 为周日，为空时每天执行
+  days: number[];
+}
+
+// 资源配置预设，按本机内存计算缓存大小和连接数
+export type ConfigPreset = 'small' | 'medium' | 'large';
+
 // 获取所有数据库列表
 export async function getDatabases(): Promise<DatabaseInfo[]> {
   return invoke('get_databases');
@@ -85,42 +153,616 @@ export async function stopDatabase(id: string): Promise<OperationResult> {
   return invoke('stop_database', { id });
 }
 
+// 并发启动所有已停止的数据库，返回以数据库 ID 为键的操作结果
+export async function startAllDatabases(): Promise<Record<string, OperationResult>> {
+  return invoke('start_all_databases');
+}
+
+// 并发停止所有运行中的数据库，返回以数据库 ID 为键的操作结果
+export async function stopAllDatabases(): Promise<Record<string, OperationResult>> {
+  return invoke('stop_all_databases');
+}
+
+// 按依赖顺序启动所有设置了自动启动的数据库，返回以数据库 ID 为键的操作结果
+export async function startAutostartDatabases(): Promise<Record<string, OperationResult>> {
+  return invoke('start_autostart_databases');
+}
+
 // 重启数据库
 export async function restartDatabase(id: string): Promise<OperationResult> {
   return invoke('restart_database', { id });
 }
 
+// 修改数据库端口（改写配置文件，运行中的数据库会被重启）
+export async function updateDatabasePort(id: string, newPort: number): Promise<OperationResult> {
+  return invoke('update_database_port', { id, newPort });
+}
+
+// 修改数据库密码
+export async function updateDatabasePassword(id: string, newPassword: string): Promise<OperationResult> {
+  return invoke('update_database_password', { id, newPassword });
+}
+
+// 为 Redis 设置密码，提供 username 时同时创建 ACL 用户（需要 Redis 6.2+），立即生效
+export async function secureRedis(id: string, password: string, username?: string): Promise<OperationResult> {
+  return invoke('secure_redis', { id, password, username });
+}
+
+// 为 MongoDB 开启访问控制，创建 root 角色的管理员并保存凭据，数据库会重启
+export async function enableMongodbAuth(id: string, username: string, password: string): Promise<OperationResult> {
+  return invoke('enable_mongodb_auth', { id, username, password });
+}
+
 // 获取数据库状态
 export async function getDatabaseStatus(id: string): Promise<DatabaseStatus | null> {
   return invoke('get_database_status', { id });
 }
 
+// 健康检查结果
+export interface HealthCheckResult {
+  healthy: boolean;
+  latency_ms: number;
+  server_version?: string;
+  error?: string;
+}
+
+// 对数据库执行协议级健康检查（MySQL/PG 握手、Redis PING、Mongo hello、HTTP 就绪接口）
+export async function checkDatabaseHealth(id: string): Promise<OperationResult<HealthCheckResult>> {
+  return invoke('check_database_health', { id });
+}
+
+// SQL 查询结果，NULL 值为 null
+export interface QueryResult {
+  columns: string[];
+  rows: (string | null)[][];
+  // 修改数据的语句影响的行数，查询语句为空
+  affected_rows?: number;
+  // 结果超出行数限制，只返回了前面的行
+  truncated: boolean;
+  elapsed_ms: number;
+}
+
+// 在 MySQL / PostgreSQL 实例上执行一条 SQL，默认最多返回 1000 行，30 秒超时
+export async function executeQuery(
+  id: string,
+  sql: string,
+  database?: string,
+  rowLimit?: number,
+  timeoutSecs?: number
+): Promise<OperationResult<QueryResult>> {
+  return invoke('execute_query', { id, sql, database, rowLimit, timeoutSecs });
+}
+
+// 慢查询日志中的一条记录，rows_sent / rows_examined 只有 MySQL 记录
+export interface SlowQuery {
+  // Unix 时间戳（秒）
+  timestamp?: number | null;
+  database?: string | null;
+  query: string;
+  duration_ms: number;
+  rows_sent?: number | null;
+  rows_examined?: number | null;
+}
+
+// 慢查询日志的读取结果，entries 最新的在前
+export interface SlowQueryLog {
+  threshold_ms: number;
+  log_file?: string | null;
+  // 本次调用才启用慢查询日志
+  newly_enabled: boolean;
+  // PostgreSQL 需要重启后才会写入日志文件
+  restart_required: boolean;
+  entries: SlowQuery[];
+}
+
+// 读取 MySQL / PostgreSQL 的慢查询（默认超过 1 秒），未启用时先启用，since 为 Unix 时间戳（秒）
+export async function getSlowQueries(id: string, since?: number): Promise<OperationResult<SlowQueryLog>> {
+  return invoke('get_slow_queries', { id, since });
+}
+
+// 一类查询（参数替换为占位符后相同的语句）的累计统计
+export interface QueryStat {
+  query: string;
+  database?: string | null;
+  calls: number;
+  total_time_ms: number;
+  mean_time_ms: number;
+  rows: number;
+}
+
+// 查询统计，entries 按总耗时从高到低排列
+export interface QueryStats {
+  // 统计已启用
+  available: boolean;
+  // 已修改配置，需要重启数据库后才开始统计
+  restart_required: boolean;
+  entries: QueryStat[];
+}
+
+// 获取总耗时最高的查询（PostgreSQL 使用 pg_stat_statements，MySQL / MariaDB 使用 performance_schema）
+export async function getQueryStats(id: string): Promise<OperationResult<QueryStats>> {
+  return invoke('get_query_stats', { id });
+}
+
+// 创建副本的选项，未指定时名称为 `<源名称>-replica`，端口自动分配
+export interface ReplicaOptions {
+  name?: string;
+  port?: number;
+}
+
+// 副本的复制状态
+export interface ReplicationStatus {
+  source_id: string;
+  // 复制链路正常
+  connected: boolean;
+  // 落后源实例的秒数
+  lag_secs?: number | null;
+  // 尚未应用的复制数据量（字节），MySQL 不提供
+  lag_bytes?: number | null;
+  // 复制链路断开的原因
+  error?: string | null;
+}
+
+// 安装一个与源数据库同版本的新实例并配置为其副本（Redis、PostgreSQL、MySQL / MariaDB），返回任务ID
+export async function setupReplica(sourceId: string, options?: ReplicaOptions): Promise<string> {
+  return invoke('setup_replica', { sourceId, options });
+}
+
+// 获取副本的复制状态和延迟
+export async function getReplicationStatus(id: string): Promise<OperationResult<ReplicationStatus>> {
+  return invoke('get_replication_status', { id });
+}
+
+// PostgreSQL 扩展
+export interface PgExtension {
+  name: string;
+  default_version?: string | null;
+  // 当前数据库中已创建的版本
+  installed_version?: string | null;
+  comment?: string | null;
+  // 扩展文件已在安装目录中，为 false 时安装会先下载（pgvector、PostGIS）
+  available: boolean;
+}
+
+// 列出 PostgreSQL 实例可以安装的扩展，未指定 database 时查看默认数据库
+export async function listAvailableExtensions(id: string, database?: string): Promise<OperationResult<PgExtension[]>> {
+  return invoke('list_available_extensions', { id, database });
+}
+
+// 在 PostgreSQL 数据库中创建扩展，返回扩展版本
+export async function installExtension(id: string, name: string, database?: string): Promise<OperationResult<string>> {
+  return invoke('install_extension', { id, name, database });
+}
+
+// Redis 命令的回复，按 RESP 类型区分
+export type RedisReply =
+  | { type: 'status'; value: string }
+  | { type: 'error'; value: string }
+  | { type: 'integer'; value: number }
+  | { type: 'bulk'; value: string | null }
+  | { type: 'array'; value: RedisReply[] | null };
+
+// Redis 键的概要信息，ttl 为剩余秒数，没有过期时间时为空
+export interface RedisKeyInfo {
+  key: string;
+  key_type: string;
+  ttl?: number;
+}
+
+// 一页 SCAN 结果，cursor 为 0 时已遍历完
+export interface RedisKeyPage {
+  cursor: number;
+  keys: RedisKeyInfo[];
+}
+
+// 在 Redis 实例上执行一条原始命令，如 ['HGETALL', 'user:1']
+export async function executeRedisCommand(
+  id: string,
+  args: string[],
+  database?: number
+): Promise<OperationResult<RedisReply>> {
+  return invoke('execute_redis_command', { id, args, database });
+}
+
+// 分页浏览 Redis 的键，cursor 传上一页返回的游标，首页不传
+export async function scanRedisKeys(
+  id: string,
+  cursor?: number,
+  pattern?: string,
+  count?: number,
+  database?: number
+): Promise<OperationResult<RedisKeyPage>> {
+  return invoke('scan_redis_keys', { id, cursor, pattern, count, database });
+}
+
+// MongoDB 实例中的数据库，size_on_disk 为字节数
+export interface MongoDatabaseInfo {
+  name: string;
+  size_on_disk: number;
+  empty: boolean;
+}
+
+// MongoDB 数据库中的集合或视图，视图没有文档数
+export interface MongoCollectionInfo {
+  name: string;
+  collection_type: 'collection' | 'view' | 'timeseries';
+  document_count?: number;
+}
+
+// 列出 MongoDB 实例中的数据库
+export async function listMongoDatabases(id: string): Promise<OperationResult<MongoDatabaseInfo[]>> {
+  return invoke('list_mongo_databases', { id });
+}
+
+// 列出 MongoDB 数据库中的集合及估算的文档数
+export async function listMongoCollections(
+  id: string,
+  database: string
+): Promise<OperationResult<MongoCollectionInfo[]>> {
+  return invoke('list_mongo_collections', { id, database });
+}
+
+// 在 MongoDB 数据库上执行数据库命令，command 为 JSON 字符串，如 '{"dbStats": 1}'
+export async function runMongoCommand(
+  id: string,
+  database: string,
+  command: string
+): Promise<OperationResult<Record<string, unknown>>> {
+  return invoke('run_mongo_command', { id, database, command });
+}
+
+// SurrealQL 中一条语句的执行结果，status 为 ERR 时 result 为错误信息
+export interface SurrealStatementResult {
+  status: 'OK' | 'ERR';
+  time: string;
+  result: unknown;
+}
+
+// 在 SurrealDB 上执行 SurrealQL，ns / db 不传时使用安装时创建的默认命名空间和数据库
+export async function executeSurrealQuery(
+  id: string,
+  query: string,
+  ns?: string,
+  db?: string
+): Promise<OperationResult<SurrealStatementResult[]>> {
+  return invoke('execute_surreal_query', { id, ns, db, query });
+}
+
+// 数据库对象树：数据库 → 模式 → 表/集合，MySQL 和 MongoDB 没有模式层
+export interface DatabaseObject {
+  name: string;
+  kind: 'database' | 'schema' | 'table' | 'view' | 'collection';
+  row_estimate: number | null;
+  children: DatabaseObject[];
+}
+
+// 列出 MySQL / PostgreSQL / MongoDB 实例中的数据库对象及估算行数
+export async function getDatabaseObjects(id: string): Promise<OperationResult<DatabaseObject[]>> {
+  return invoke('get_database_objects', { id });
+}
+
+// 在 MySQL / PostgreSQL 实例上创建数据库
+export async function createLogicalDatabase(id: string, name: string): Promise<OperationResult> {
+  return invoke('create_logical_database', { id, name });
+}
+
+// 创建用户并授予 grants 中各数据库的全部权限，MongoDB 授予 readWrite 角色
+export async function createUser(
+  id: string,
+  username: string,
+  password: string,
+  grants?: string[]
+): Promise<OperationResult> {
+  return invoke('create_user', { id, username, password, grants });
+}
+
+// 把 .sql / .sql.gz 转储导入到 MySQL / PostgreSQL 实例，返回任务ID，进度通过 import-progress 事件推送
+export async function importDump(id: string, path: string, targetDb?: string): Promise<string> {
+  return invoke('import_dump', { id, path, targetDb });
+}
+
+// 内置示例数据集：ecommerce 用于 MySQL / PostgreSQL，vectors 用于 Qdrant，documents 用于 MongoDB
+export type SampleDataset = 'ecommerce' | 'vectors' | 'documents';
+
+// 写入示例数据，数据放在新建的 sample_shop 库或 sample_vectors 集合中
+export async function seedSampleData(id: string, dataset: SampleDataset): Promise<OperationResult> {
+  return invoke('seed_sample_data', { id, dataset });
+}
+
+// 使用给定凭据测试连接（不传则使用已保存的凭据），成功时 data 为服务端版本
+export async function testConnection(
+  id: string,
+  username?: string,
+  password?: string
+): Promise<OperationResult<string>> {
+  return invoke('test_connection', { id, username, password });
+}
+
+// 连接字符串格式
+export type ConnectionUriFormat = 'native' | 'prisma' | 'sqlalchemy' | 'jdbc';
+
+// 生成可直接复制的连接字符串，format 默认为 native
+export async function getConnectionUri(id: string, format?: ConnectionUriFormat): Promise<OperationResult<string>> {
+  return invoke('get_connection_uri', { id, format });
+}
+
+// 复制到剪贴板的连接信息：连接字符串、密码或 .env 连接变量
+export type ConnectionInfoKind = 'uri' | 'password' | 'env';
+
+// 复制连接信息到剪贴板，clearAfterSecs 秒后内容未被替换时自动清空
+export async function copyConnectionInfo(
+  id: string,
+  what: ConnectionInfoKind,
+  clearAfterSecs?: number
+): Promise<OperationResult> {
+  return invoke('copy_connection_info', { id, what, clearAfterSecs });
+}
+
+// 生成 .env 连接变量时遵循的框架约定，prisma 用于 Next.js 等使用 Prisma 的项目
+export type EnvFramework = 'rails' | 'django' | 'laravel' | 'prisma' | 'spring';
+
+// 按框架的约定生成可粘贴到 .env 文件的连接变量
+export async function generateEnvSnippet(id: string, framework: EnvFramework): Promise<OperationResult<string>> {
+  return invoke('generate_env_snippet', { id, framework });
+}
+
+// 可以打开数据库连接的客户端
+export type DatabaseClient = 'tableplus' | 'dbeaver' | 'datagrip' | 'psql' | 'redis_cli';
+
+// 本机已安装、可以打开该数据库的客户端
+export async function listDatabaseClients(id: string): Promise<OperationResult<DatabaseClient[]>> {
+  return invoke('list_database_clients', { id });
+}
+
+// 用客户端打开数据库连接，psql 和 redis-cli 在新的终端窗口中运行
+export async function openInClient(id: string, client: DatabaseClient): Promise<OperationResult> {
+  return invoke('open_in_client', { id, client });
+}
+
+// SQLite / DuckDB 数据库文件
+export interface SqliteFile {
+  name: string;
+  path: string;
+  size: number;
+  modified_at?: string;
+}
+
+// 列出 SQLite / DuckDB 实例下的数据库文件
+export async function listSqliteFiles(id: string): Promise<OperationResult<SqliteFile[]>> {
+  return invoke('list_sqlite_files', { id });
+}
+
+// 在 SQLite / DuckDB 实例下创建数据库文件
+export async function createSqliteFile(id: string, name: string): Promise<OperationResult<SqliteFile>> {
+  return invoke('create_sqlite_file', { id, name });
+}
+
 // 删除数据库
 export async function deleteDatabase(id: string, withData: boolean = false): Promise<OperationResult> {
   return invoke('delete_database', { id, withData });
 }
 
+// 卸载数据库，同时删除数据、日志、配置和不再使用的程序文件
+export async function uninstallDatabase(id: string): Promise<OperationResult> {
+  return invoke('uninstall_database', { id });
+}
+
+// 接管本机端口上已在运行的数据库，注册为外部管理的实例，只能监控和连接
+export async function adoptDatabase(
+  port: number,
+  dbType: DatabaseType,
+  username?: string,
+  password?: string
+): Promise<OperationResult<DatabaseInfo>> {
+  return invoke('adopt_database', { port, dbType, username, password });
+}
+
+// 注册自定义服务的参数，args 中可使用 {port} 和 {data_path} 占位符
+export interface CustomServerParams {
+  name: string;
+  // 服务二进制的绝对路径
+  binary_path: string;
+  args?: string[];
+  // 服务监听的端口，用于替换 {port} 和健康检查
+  port: number;
+  version?: string;
+  env?: Record<string, string>;
+}
+
+// 注册自定义服务，由应用按启动参数运行并通过 PID 文件管理进程
+export async function registerCustomServer(
+  params: CustomServerParams
+): Promise<OperationResult<DatabaseInfo>> {
+  return invoke('register_custom_server', { params });
+}
+
 // 安装数据库（异步，返回任务ID）
 export async function installDatabase(params: InstallDatabaseParams): Promise<string> {
   return invoke('install_database', { params });
 }
 
+// 从本地发行包离线安装数据库（异步，返回任务ID），版本未指定时从文件名识别
+export async function installDatabaseFromArchive(
+  params: InstallDatabaseParams,
+  archivePath: string
+): Promise<string> {
+  return invoke('install_database_from_archive', { params, archivePath });
+}
+
+// 检查本机是否安装了 Docker 或 Podman
+export async function isDockerAvailable(): Promise<boolean> {
+  return invoke('is_docker_available');
+}
+
+// 可安装的版本
+export interface AvailableVersion {
+  version: string;
+  released_at: string | null;
+}
+
+// 查询数据库可安装的版本，从新到旧排列
+export async function getAvailableVersions(dbType: DatabaseType): Promise<AvailableVersion[]> {
+  return invoke('get_available_versions', { dbType });
+}
+
 // 获取任务状态（用于轮询）
 export async function getTaskStatus(taskId: string): Promise<AsyncTask | null> {
   return invoke('get_task_status', { taskId });
 }
 
+// 任务列表过滤条件
+export interface TaskFilter {
+  status?: TaskStatus;
+  task_type?: string;
+  db_type?: string;
+}
+
+// 获取任务列表，按创建时间倒序排列
+export async function listTasks(filter?: TaskFilter): Promise<AsyncTask[]> {
+  return invoke('list_tasks', { filter });
+}
+
+// 清除已完成的任务，返回清除数量
+export async function clearCompletedTasks(): Promise<number> {
+  return invoke('clear_completed_tasks');
+}
+
+// 使用相同参数重试失败的安装任务，返回新任务 ID
+export async function retryTask(taskId: string): Promise<string> {
+  return invoke('retry_task', { taskId });
+}
+
 // 更新数据库自启动设置
 export async function updateDatabaseAutostart(id: string, autoStart: boolean): Promise<OperationResult> {
   return invoke('update_database_autostart', { id, autoStart });
 }
 
+// 开启或关闭按需启动
+export async function updateDatabaseOnDemand(id: string, onDemand: boolean): Promise<OperationResult> {
+  return invoke('update_database_on_demand', { id, onDemand });
+}
+
+// 更新数据库的定时启停计划
+export async function updateDatabaseSchedules(id: string, schedules: DatabaseSchedule[]): Promise<OperationResult> {
+  return invoke('update_database_schedules', { id, schedules });
+}
+
+// 更新数据库依赖（启动前需要先就绪的数据库 ID）
+export async function updateDatabaseDependencies(id: string, dependsOn: string[]): Promise<OperationResult> {
+  return invoke('update_database_dependencies', { id, dependsOn });
+}
+
 // 同步所有数据库的运行状态
 // 此函数用于在页面加载完成后调用，检查并更新所有数据库的实际运行状态
 export async function syncDatabasesStatus(): Promise<DatabaseInfo[]> {
   return invoke('sync_databases_status');
 }
 
+// 读取数据库配置文件
+export async function getDatabaseConfig(id: string): Promise<string> {
+  return invoke('get_database_config', { id });
+}
+
+// 校验并保存数据库配置文件，配置无效时不保存，重启数据库后生效
+export async function updateDatabaseConfig(id: string, contents: string): Promise<OperationResult> {
+  return invoke('update_database_config', { id, contents });
+}
+
+// 应用资源配置预设，restart 为 true 时重启运行中的数据库使配置生效
+export async function applyPreset(
+  id: string,
+  preset: ConfigPreset,
+  restart?: boolean
+): Promise<OperationResult> {
+  return invoke('apply_preset', { id, preset, restart });
+}
+
+// 配置文件的历史版本
+export interface ConfigVersion {
+  version: string;
+  created_at: string;
+  size: number;
+}
+
+// 列出配置文件的历史版本，按时间倒序排列
+export async function listConfigVersions(id: string): Promise<ConfigVersion[]> {
+  return invoke('list_config_versions', { id });
+}
+
+// 将配置文件恢复为指定的历史版本，重启数据库后生效
+export async function rollbackConfig(id: string, version: string): Promise<OperationResult> {
+  return invoke('rollback_config', { id, version });
+}
+
+// 结构化配置修改，config 中的键值合并到配置文件
+// my.cnf 使用 section.key（默认 [mysqld]），YAML 使用 . 分隔的路径
+export interface DatabaseConfig {
+  port?: number;
+  data_path?: string;
+  username?: string;
+  password?: string;
+  config?: Record<string, string>;
+}
+
+// 应用配置修改，restart 为 true 时重启运行中的数据库使配置生效
+export async function applyDatabaseConfig(
+  id: string,
+  config: DatabaseConfig,
+  restart?: boolean
+): Promise<OperationResult> {
+  return invoke('apply_database_config', { id, config, restart });
+}
+
+// 修改启动时的环境变量和额外参数，restart 为 true 时重启运行中的数据库使其生效
+export async function updateDatabaseLaunchOptions(
+  id: string,
+  env: Record<string, string>,
+  extraArgs: string[],
+  restart?: boolean
+): Promise<OperationResult> {
+  return invoke('update_database_launch_options', { id, env, extraArgs, restart });
+}
+
+// 局域网共享的连接地址，host 为本机的局域网地址，未检测到时为空
+export interface LanEndpoint {
+  host?: string;
+  port: number;
+  // 放行入站连接的防火墙提示
+  firewall_note?: string;
+}
+
+// 开启或关闭局域网共享，开启前需要先设置密码
+export async function exposeDatabase(id: string, enable: boolean): Promise<OperationResult<LanEndpoint>> {
+  return invoke('expose_database', { id, enable });
+}
+
+// 将数据目录迁移到 newPath（需为空目录或不存在），迁移期间数据库会停止
+export async function moveDatabaseData(id: string, newPath: string): Promise<OperationResult> {
+  return invoke('move_database_data', { id, newPath });
+}
+
+// 清空数据并重新初始化，完成后数据库处于运行状态
+export async function resetDatabase(id: string): Promise<OperationResult> {
+  return invoke('reset_database', { id });
+}
+
+// 数据库占用的磁盘空间（字节）
+export interface DatabaseDiskUsage {
+  database_id: string;
+  name: string;
+  data_size: number;
+  log_size: number;
+  backup_size: number;
+  total_size: number;
+  // 格式化后的总大小，如 1.50 GB
+  total_size_text: string;
+}
+
+// 统计数据库的数据目录、日志和备份占用的磁盘空间
+export async function getDatabaseDiskUsage(id: string): Promise<DatabaseDiskUsage> {
+  return invoke('get_database_disk_usage', { id });
+}
+
 // 读取数据库日志文件
 // lines: 可选参数，指定返回最后多少行日志，不指定则返回全部
 export async function readDatabaseLogs(id: string, lines?: number): Promise<string[]> {
@@ -131,3 +773,29 @@ export async function readDatabaseLogs(id: string, lines?: number): Promise<stri
 export async function clearDatabaseLogs(id: string): Promise<OperationResult> {
   return invoke('clear_database_logs', { id });
 }
+
+// 日志跟踪事件（log-line）
+export interface LogLineEvent {
+  database_id: string;
+  line: string;
+}
+
+// 数据库进程崩溃事件（database-crashed）
+export interface DatabaseCrashedEvent {
+  database_id: string;
+  name: string;
+  // 是否会自动重启
+  restarting: boolean;
+  // 本次是第几次连续重启，不重启时为 0
+  attempt: number;
+}
+
+// 读取日志最后 lines 行（默认 100），follow 为 true 时通过 log-line 事件持续推送新行
+export async function tailDatabaseLog(id: string, lines?: number, follow?: boolean): Promise<string[]> {
+  return invoke('tail_database_log', { id, lines, follow });
+}
+
+// 停止跟踪数据库日志
+export async function stopTailDatabaseLog(id: string): Promise<OperationResult> {
+  return invoke('stop_tail_database_log', { id });
+}
@@ -1,3 +1,5 @@
+export * from './backup';
+export * from './benchmark';
 export * from './database';
 export * from './settings';
 export * from './system_info';
@@ -4,6 +4,7 @@ import { OperationResult } from './database';
 // 全局设置接口
 export interface GlobalSettings {
   default_storage_path: string;
+  // 登录系统时在后台启动应用（窗口隐藏在托盘中），并启动设置了自动启动的数据库
   auto_start: boolean;
   theme: string;
   language: string;
@@ -12,6 +13,46 @@ export interface GlobalSettings {
   backup_retention_days: number;
   log_level: string;
   log_retention_days: number;
+  startup_timeout_secs: number;
+  remote_backup?: RemoteBackupTarget | null;
+  stop_on_exit: boolean;
+  restart_on_crash: boolean;
+  // 本机安装时优先使用系统包管理器（Linux 上为 apt/dnf/pacman，Windows 上为 winget/Scoop）
+  use_package_manager: boolean;
+  // macOS 上使用应用生成的 LaunchAgent 管理数据库，不使用 brew services
+  use_launch_agents: boolean;
+  // 下载发行包和运行 Homebrew 时使用的 HTTP(S) 代理
+  download_proxy?: string | null;
+  // 下载源镜像：mysql、mariadb、mongodb、postgresql、redis、apache、neo4j、opensearch、influxdb、github、homebrew
+  download_mirrors?: Record<string, string>;
+  // 用户移除过的外部安装，自动发现时不再注册
+  ignored_external?: string[];
+  // 后台采集系统和数据库指标的间隔（秒），为 0 时不采集
+  metrics_interval_secs: number;
+  // 将指标历史保存到存储目录，重启应用后仍可查看
+  persist_metrics: boolean;
+  // 启动应用时开启本机 HTTP API（需以 api-server 特性编译），修改后重启应用生效
+  api_server_enabled: boolean;
+  // 本机 HTTP API 监听的端口，只接受 127.0.0.1 的连接
+  api_server_port: number;
+  // 访问本机 HTTP API 的令牌，请求需带上 Authorization: Bearer <api_token>
+  api_token: string;
+  // 令牌保存在系统钥匙串时的引用，此时 state.json 中不保存明文令牌
+  api_token_ref?: string;
+  // 系统通知的范围：off 不通知，errors 只通知失败（默认），all 同时通知磁盘空间不足等警告
+  notification_level: 'off' | 'errors' | 'all';
+}
+
+// 远程备份目标（S3 / MinIO）
+export interface RemoteBackupTarget {
+  endpoint: string;
+  bucket: string;
+  region: string;
+  access_key: string;
+  secret_key: string;
+  // 密钥保存在系统钥匙串时的引用，此时 state.json 中不保存明文密钥
+  secret_key_ref?: string;
+  prefix: string;
 }
 
 const THEME_KEY = 'local-db-theme';
@@ -50,3 +91,51 @@ export async function updateSettings(settings: GlobalSettings): Promise<Operatio
   setStoredTheme(settings.theme);
   return invoke('update_settings', { settings });
 }
+
+// 将存储位置迁移到 newPath，运行中的数据库会先停止，迁移完成后重新启动
+// 存储位置不能通过 updateSettings 修改
+export async function migrateStoragePath(newPath: string): Promise<OperationResult> {
+  return invoke('migrate_storage_path', { newPath });
+}
+
+// 导入应用状态的结果
+export interface AppStateImportResult {
+  imported_databases: number;
+  // 因 ID 或端口冲突而跳过的数据库名称
+  skipped_databases: string[];
+  imported_backups: number;
+  // 依赖或复制源不在当前数据库列表中而被去掉的引用，形如 "数据库名称: 被引用的 ID"
+  dropped_references: string[];
+}
+
+// 将数据库列表、设置和备份记录导出为 JSON 文件，includeSecrets 默认为 false，不导出密码和密钥
+export async function exportAppState(path: string, includeSecrets?: boolean): Promise<OperationResult> {
+  return invoke('export_app_state', { path, includeSecrets });
+}
+
+// 生成以容器复现已注册数据库的 docker-compose.yml 内容，project 为可选的 Compose 项目名
+export async function exportCompose(project?: string): Promise<OperationResult<string>> {
+  return invoke('export_compose', { project });
+}
+
+// 从导出的文件导入数据库列表、设置和备份记录，只导入记录，不复制数据文件
+export async function importAppState(path: string): Promise<OperationResult<AppStateImportResult>> {
+  return invoke('import_app_state', { path });
+}
+
+// 下载缓存的占用情况
+export interface DownloadCacheInfo {
+  path: string;
+  files: number;
+  size: number;
+}
+
+// 获取下载缓存的位置、文件数和大小
+export async function getDownloadCacheInfo(): Promise<DownloadCacheInfo> {
+  return invoke('get_download_cache_info');
+}
+
+// 清空下载缓存，返回释放的字节数
+export async function clearDownloadCache(): Promise<OperationResult<number>> {
+  return invoke('clear_download_cache');
+}
@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
+import { DatabaseDiskUsage, OperationResult } from './database';
 
 export interface DiskInfo {
   name: string;
@@ -17,6 +18,8 @@ export interface SystemInfo {
   swap_used: number;
   load_average: number;
   disks: DiskInfo[];
+  // 各数据库占用的磁盘空间，按占用从大到小排列
+  database_disk_usage: DatabaseDiskUsage[];
 }
 
 /**
@@ -26,6 +29,23 @@ export async function getSystemInfo(): Promise<SystemInfo> {
   return invoke('get_system_info');
 }
 
+// 一次指标采样
+export interface MetricSample {
+  // Unix 时间戳（秒）
+  timestamp: number;
+  cpu_usage: number;
+  memory_used: number;
+}
+
+/**
+ * 获取后台采集的指标历史，用于绘制图表
+ * @param id 数据库 ID，不传时返回系统整体指标
+ * @param range 最近的秒数，默认 3600
+ */
+export async function getMetricsHistory(id?: string, range?: number): Promise<MetricSample[]> {
+  return invoke('get_metrics_history', { id, range });
+}
+
 /**
  * 获取 CPU 使用率
  */
@@ -47,3 +67,10 @@ export async function getMemoryInfo(): Promise<[number, number, number]> {
 export async function getDiskInfo(): Promise<DiskInfo[]> {
   return invoke('get_disk_info');
 }
+
+/**
+ * 导出诊断包（脱敏后的状态、最近日志、服务状态、系统信息）到指定的 zip 路径
+ */
+export async function exportDiagnostics(path: string): Promise<OperationResult<string>> {
+  return invoke('export_diagnostics', { path });
+}
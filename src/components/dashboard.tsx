@@ -16,12 +16,18 @@ import { ViewType } from '../types';
 // 数据库图标映射
 const dbIcons: Record<string, string> = {
   mysql: '🐬',
+  mariadb: '🦭',
   postgresql: '🐘',
   mongodb: '🍃',
   redis: '◆',
   qdrant: '🔍',
   seekdb: '📊',
-  surrealdb: '🌐'
+  surrealdb: '🌐',
+  opensearch: '🔎',
+  influxdb: '📈',
+  cassandra: '👁️',
+  sqlite: '🪶',
+  duckdb: '🦆'
 };
 
 interface DashboardProps {
@@ -13,7 +13,13 @@ import {
   FiDatabase,
   FiLayers,
   FiBox,
-  FiServer
+  FiServer,
+  FiFile,
+  FiSearch,
+  FiActivity,
+  FiColumns,
+  FiAnchor,
+  FiBarChart2
 } from 'react-icons/fi';
 import {
   getDatabases,
@@ -34,6 +40,13 @@ const TaskStatus = {
 
 export const SUPPORTED_DATABASES = [
   { id: 'mysql', type: 'mysql', name: 'MySQL', icon: 'database', colorClass: 'text-blue-500 bg-blue-500/10' },
+  {
+    id: 'mariadb',
+    type: 'mariadb',
+    name: 'MariaDB',
+    icon: 'anchor',
+    colorClass: 'text-cyan-600 bg-cyan-600/10'
+  },
   {
     id: 'postgresql',
     type: 'postgresql',
@@ -56,6 +69,35 @@ export const SUPPORTED_DATABASES = [
     name: 'SurrealDB',
     icon: 'share-2',
     colorClass: 'text-orange-500 bg-orange-500/10'
+  },
+  {
+    id: 'opensearch',
+    type: 'opensearch',
+    name: 'OpenSearch',
+    icon: 'search',
+    colorClass: 'text-teal-500 bg-teal-500/10'
+  },
+  {
+    id: 'influxdb',
+    type: 'influxdb',
+    name: 'InfluxDB',
+    icon: 'activity',
+    colorClass: 'text-purple-500 bg-purple-500/10'
+  },
+  {
+    id: 'cassandra',
+    type: 'cassandra',
+    name: 'Cassandra',
+    icon: 'columns',
+    colorClass: 'text-indigo-500 bg-indigo-500/10'
+  },
+  { id: 'sqlite', type: 'sqlite', name: 'SQLite', icon: 'file', colorClass: 'text-sky-500 bg-sky-500/10' },
+  {
+    id: 'duckdb',
+    type: 'duckdb',
+    name: 'DuckDB',
+    icon: 'bar-chart-2',
+    colorClass: 'text-yellow-500 bg-yellow-500/10'
   }
 ];
 
@@ -63,6 +105,8 @@ const getIconComponent = (dbType: string) => {
   switch (dbType) {
     case 'mysql':
       return FiDatabase;
+    case 'mariadb':
+      return FiAnchor;
     case 'postgresql':
       return FiLayers;
     case 'mongodb':
@@ -73,6 +117,16 @@ const getIconComponent = (dbType: string) => {
       return FiBox;
     case 'surrealdb':
       return FiShare2;
+    case 'opensearch':
+      return FiSearch;
+    case 'influxdb':
+      return FiActivity;
+    case 'cassandra':
+      return FiColumns;
+    case 'sqlite':
+      return FiFile;
+    case 'duckdb':
+      return FiBarChart2;
     default:
       return FiGrid;
   }